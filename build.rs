@@ -0,0 +1,23 @@
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    if env::var_os("CARGO_FEATURE_HOT_RELOAD").is_none() {
+        return;
+    }
+
+    // Embedded into hot reloaded libraries, see `hot_reload::abi_fingerprint`.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!(
+        "cargo:rustc-env=DRUID_NURSERY_RUSTC_VERSION={}",
+        version.trim()
+    );
+}
@@ -5,7 +5,12 @@ use syn::{parse_quote, spanned::Spanned, Data, DeriveInput, Fields, GenericParam
 pub fn expand_prism(input: DeriveInput) -> syn::Result<TokenStream> {
     let variants = match input.data {
         Data::Enum(e) => e.variants,
-        _ => panic!("this derive macro only works on enums"),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input.ident,
+                "`Prism` can only be derived for enums",
+            ))
+        }
     };
 
     let enum_name = input.ident;
@@ -40,11 +45,24 @@ pub fn expand_prism(input: DeriveInput) -> syn::Result<TokenStream> {
             let variant_expr;
 
             match &v.fields {
-                Fields::Named(_) => {
-                    return Err(syn::Error::new_spanned(
-                        v,
-                        "variants with named fields are not supported for deriving `Prism`",
-                    ));
+                Fields::Named(f) => {
+                    // Named fields are handled like unnamed ones, in the order
+                    // they are declared: `Variant { a: A, b: B }` has the inner
+                    // type `(A, B)`.
+                    let types = f.named.iter().map(|f| &f.ty);
+                    inner_type = quote! { (#(#types),*) };
+
+                    let names = f.named.iter().map(|f| &f.ident);
+                    let fields: Vec<_> = (0..f.named.len())
+                        .map(|n| format_ident!("_v{}", n + 1))
+                        .collect();
+                    let cloned = fields
+                        .iter()
+                        .map(|f| quote! { ::std::clone::Clone::clone(#f) });
+
+                    variant_expr = quote! { { #(#names: #fields),* } };
+                    inner_expr = quote! { (#(#fields),*) };
+                    cloned_inner = quote! { (#(#cloned),*) };
                 }
                 Fields::Unnamed(f) => {
                     let fields = f.unnamed.iter();
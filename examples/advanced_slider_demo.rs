@@ -1,11 +1,17 @@
 use druid::widget::{Flex, Slider};
-use druid::{AppLauncher, Color, Widget, WidgetExt, WindowDesc};
-use druid_widget_nursery::AdvancedSlider;
+use druid::{AppLauncher, Color, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::{AdvancedSlider, RangeSlider};
 
-fn build_ui() -> impl Widget<f64> {
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    value: f64,
+    range: (f64, f64),
+}
+
+fn build_ui() -> impl Widget<AppState> {
     Flex::column()
         .with_spacer(30.0)
-        .with_child(Slider::new().with_range(0.0, 80.0))
+        .with_child(Slider::new().with_range(0.0, 80.0).lens(AppState::value))
         .with_spacer(10.0)
         .with_flex_child(
             AdvancedSlider::new()
@@ -14,18 +20,43 @@ fn build_ui() -> impl Widget<f64> {
                 .with_step_size(1.0)
                 .with_significant(2)
                 .with_text_offset(0.0)
-                .background(Color::rgb8(30, 30, 30)),
+                .background(Color::rgb8(30, 30, 30))
+                .lens(AppState::value),
             1.0,
         )
+        .with_spacer(10.0)
+        .with_child(
+            AdvancedSlider::new()
+                .with_range(0.0, 100.0)
+                .with_step_size(5.0)
+                .with_ticks(25.0)
+                .with_tick_labels(true)
+                .with_vertical(true)
+                .with_editable(false)
+                .lens(AppState::value),
+        )
+        .with_spacer(30.0)
+        .with_child(
+            RangeSlider::new()
+                .with_range(0.0, 100.0)
+                .with_min_gap(10.0)
+                .with_step_size(5.0)
+                .with_ticks(10.0)
+                .lens(AppState::range)
+                .padding((10.0, 0.0)),
+        )
         .background(Color::rgb8(30, 30, 30))
 }
 
 fn main() {
     let window = WindowDesc::new(build_ui())
         .title("Advanced Slider")
-        .window_size((300.0, 250.0));
+        .window_size((300.0, 400.0));
 
     AppLauncher::with_window(window)
-        .launch(0.0)
+        .launch(AppState {
+            value: 0.0,
+            range: (20.0, 60.0),
+        })
         .expect("launch failed");
 }
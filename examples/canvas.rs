@@ -1,7 +1,7 @@
 use druid::lens::Map;
 use druid::widget::{Button, Flex, Label, LabelText, Split, TextBox};
 use druid::{AppLauncher, Data, Env, Lens, Point, Size, Widget, WidgetExt, WindowDesc};
-use druid_widget_nursery::{Canvas, CanvasWrap};
+use druid_widget_nursery::{Canvas, CanvasViewport, CanvasWrap};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::str::FromStr;
@@ -28,7 +28,8 @@ fn canvas_panel() -> impl Widget<AppData> {
     let canvas: Canvas<AppData> = Canvas::new()
         .with_child(CanvasWrap::new(label, |data| data.label_pos))
         .with_child(CanvasWrap::new(text_box, |data| data.text_box_pos))
-        .with_child(CanvasWrap::new(button, |data| data.button_pos));
+        .with_child(CanvasWrap::new(button, |data| data.button_pos))
+        .with_viewport(AppData::viewport);
 
     canvas
 }
@@ -156,6 +157,7 @@ struct AppData {
     pub text_box_pos: Point,
     pub btn_click_count: u32,
     pub button_pos: Point,
+    pub viewport: CanvasViewport,
 }
 
 impl AppData {
@@ -167,6 +169,7 @@ impl AppData {
             text_box_pos: Point::new(130., 90.),
             btn_click_count: 0,
             button_pos: Point::new(100., 120.),
+            viewport: CanvasViewport::default(),
         }
     }
 }
@@ -0,0 +1,52 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Checkbox, Flex, Label};
+use druid::{AppLauncher, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::{ColorScheme, SystemColorScheme, Theme};
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    scheme: ColorScheme,
+    checked: bool,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("System color scheme");
+    AppLauncher::with_window(window)
+        .launch(AppState {
+            scheme: ColorScheme::Light,
+            checked: false,
+        })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<AppState> {
+    let body = Flex::column()
+        .with_child(Label::dynamic(|data: &AppState, _| {
+            format!("The system is in {:?} mode.", data.scheme)
+        }))
+        .with_default_spacer()
+        .with_child(Label::new(
+            "Switch it in the system settings to see the app follow.",
+        ))
+        .with_default_spacer()
+        .with_child(Checkbox::new("A checkbox").lens(AppState::checked))
+        .padding(10.0);
+
+    SystemColorScheme::new(body)
+        .with_lens(AppState::scheme)
+        .with_themes(Theme::light(), Theme::dark())
+        .with_transition(0.3)
+}
@@ -0,0 +1,54 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Flex, Label};
+use druid::{AppLauncher, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::{Calendar, DatePicker};
+
+type Date = (i32, u32, u32);
+
+#[derive(Clone, Data, Lens)]
+struct Trip {
+    departure: Date,
+    reminder: Date,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("Date picker");
+    AppLauncher::with_window(window)
+        .launch(Trip {
+            departure: (2022, 7, 1),
+            reminder: (2022, 6, 24),
+        })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<Trip> {
+    Flex::column()
+        .with_child(
+            Flex::row()
+                .with_child(Label::new("Departure"))
+                .with_spacer(8.0)
+                .with_child(
+                    DatePicker::new()
+                        .with_min((2022, 1, 1))
+                        .with_max((2023, 12, 31))
+                        .lens(Trip::departure),
+                ),
+        )
+        .with_spacer(16.0)
+        .with_child(Label::new("Reminder"))
+        .with_child(Calendar::new().lens(Trip::reminder))
+        .padding(16.0)
+}
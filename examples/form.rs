@@ -0,0 +1,95 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Button, Flex, Label, Slider, TextBox};
+use druid::{AppLauncher, Data, Lens, Widget, WidgetExt as _, WindowDesc};
+use druid_widget_nursery::form::{self, Form, Validator};
+use druid_widget_nursery::WidgetExt;
+
+#[derive(Clone, Data, Lens)]
+struct Signup {
+    name: String,
+    email: String,
+    age: f64,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("Form");
+    AppLauncher::with_window(window)
+        .launch(Signup {
+            name: String::new(),
+            email: String::new(),
+            age: 10.0,
+        })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<Signup> {
+    let name = TextBox::new()
+        .with_placeholder("Name")
+        .validate(Validator::required("Please enter your name"))
+        .lens(Signup::name);
+
+    let email = TextBox::new()
+        .with_placeholder("Email")
+        .validate(Validator::required("Please enter your email"))
+        .with_validator(Validator::new(|email: &String| {
+            if email.contains('@') {
+                Ok(())
+            } else {
+                Err("This doesn't look like an email address".to_string())
+            }
+        }))
+        .lens(Signup::email);
+
+    let age = Flex::row()
+        .with_child(Label::dynamic(|age: &f64, _| format!("Age: {:.0}", age)))
+        .with_child(
+            Slider::new()
+                .with_range(0.0, 100.0)
+                .validate(Validator::range(18.0, 100.0)),
+        )
+        .lens(Signup::age);
+
+    let submit = Button::new("Sign up").on_click(|ctx, _: &mut Signup, env| {
+        if env.get(form::IS_VALID) {
+            println!("Signed up!");
+            ctx.submit_command(form::MARK_PRISTINE);
+        } else {
+            ctx.submit_command(form::TOUCH_ALL);
+        }
+    });
+    let status = Label::dynamic(|_: &Signup, env| {
+        match (env.get(form::IS_DIRTY), env.get(form::IS_VALID)) {
+            (false, _) => "Nothing changed yet",
+            (true, false) => "Some fields need attention",
+            (true, true) => "Ready to sign up",
+        }
+        .to_string()
+    });
+
+    Form::new(
+        Flex::column()
+            .with_child(name)
+            .with_default_spacer()
+            .with_child(email)
+            .with_default_spacer()
+            .with_child(age)
+            .with_default_spacer()
+            .with_child(submit)
+            .with_default_spacer()
+            .with_child(status)
+            .padding(10.0),
+    )
+}
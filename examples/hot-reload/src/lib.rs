@@ -1,5 +1,6 @@
 use druid::widget::{Flex, TextBox};
 use druid::{Data, Lens, Widget, WidgetExt};
+use druid_widget_nursery::hot_reload::HotState;
 
 #[derive(Debug, Default, Data, Clone, Lens)]
 pub struct AppData {
@@ -7,11 +8,46 @@ pub struct AppData {
     checked: String,
 }
 
+druid_widget_nursery::hot_reload_abi!(view);
+
 #[no_mangle]
-pub fn view() -> Box<dyn Widget<AppData>> {
+pub fn view() -> Box<dyn Widget<HotState>> {
     Flex::column()
         .with_child(TextBox::new().lens(AppData::name))
         .with_spacer(90.)
         .with_child(TextBox::new().lens(AppData::checked))
+        .lens(HotState::lens::<AppData>())
         .boxed()
 }
+
+pub fn initial_state() -> HotState {
+    HotState::new(AppData::default())
+}
+
+// Bump this when changing `AppData`, and teach `deserialize_state` to read the old version.
+#[no_mangle]
+pub fn state_version() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub fn serialize_state(state: &HotState) -> Vec<u8> {
+    match state.get::<AppData>() {
+        Some(data) => format!("{}\n{}", data.name, data.checked).into_bytes(),
+        // the reload fails on the empty state, instead of taking the app down
+        None => {
+            eprintln!("serialize_state: the state is not `AppData`");
+            Vec::new()
+        }
+    }
+}
+
+#[no_mangle]
+pub fn deserialize_state(_version: u32, bytes: &[u8]) -> Option<HotState> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let (name, checked) = text.split_once('\n')?;
+    Some(HotState::new(AppData {
+        name: name.into(),
+        checked: checked.into(),
+    }))
+}
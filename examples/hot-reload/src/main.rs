@@ -1,11 +1,10 @@
 use druid_widget_nursery::hot_reload::{AppLauncher, WindowDesc};
-use hot_reload::AppData;
+use hot_reload::initial_state;
 
 fn main() {
-    let window = WindowDesc::new("target/debug/hot_reload.dll", "view");
-    unsafe {
-        AppLauncher::with_window(window)
-            .launch(AppData::default())
-            .unwrap();
-    }
+    let window = WindowDesc::from_crate("hot-reload", "view");
+    AppLauncher::with_window(window)
+        .rebuild_on_change("hot-reload", ["src"])
+        .launch(initial_state())
+        .unwrap();
 }
@@ -1,8 +1,9 @@
 use druid::{widget::Flex, AppLauncher, Color, LocalizedString, Widget, WidgetExt, WindowDesc};
 use druid_widget_nursery::material_icons::{
-    normal::action::{ABC, ADD_CARD, ADD_TASK, ADD_TO_DRIVE},
-    Icon,
+    normal::action::{self, ABC, ADD_CARD, ADD_TASK, ADD_TO_DRIVE},
+    register_svg_icon, Icon,
 };
+use druid_widget_nursery::register_material_icons;
 use qu::ick_use::*;
 
 // Helps to make the icons visible.
@@ -19,11 +20,18 @@ fn ui_builder() -> impl Widget<()> {
         .with_child(show_icon(Icon::new(ADD_CARD).fix_size(24., 100.)))
         // different color
         .with_child(show_icon(Icon::new(ADD_TO_DRIVE).with_color(Color::MAROON)))
+        // looked up by name, and bigger
+        .with_child(show_icon(Icon::named("alarm_add").unwrap().with_size(48.)))
+        // a custom icon from SVG path data
+        .with_child(show_icon(Icon::named("triangle").unwrap()))
         .center()
 }
 
 #[qu::ick]
 pub fn main() -> Result {
+    register_material_icons!(action::ALARM_ADD);
+    register_svg_icon("triangle", &["M12 2 L22 20 L2 20 Z"], (24., 24.))?;
+
     // Create the main window
     let main_window = WindowDesc::new(ui_builder())
         .title(LocalizedString::new("material-icons").with_placeholder("Material Icons demo"));
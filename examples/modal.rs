@@ -0,0 +1,81 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Button, Flex, Label, TextBox};
+use druid::{AppLauncher, Color, Data, Env, EventCtx, Lens, Widget, WidgetExt, WindowDesc};
+
+use druid_widget_nursery::modal::{ModalResult, MODAL_CLOSE, MODAL_RESULT, MODAL_SHOW};
+use druid_widget_nursery::{Modal, WidgetExt as _};
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    name: String,
+    status: String,
+}
+
+fn dialog() -> impl Widget<AppState> {
+    Flex::column()
+        .with_child(Label::new("Rename to:"))
+        .with_spacer(5.)
+        .with_child(TextBox::new().lens(AppState::name))
+        .with_spacer(10.)
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Button::new("Cancel")
+                        .on_click(|ctx: &mut EventCtx, _, _| ctx.submit_notification(MODAL_CLOSE)),
+                )
+                .with_spacer(5.)
+                .with_child(Button::new("Ok").on_click(|ctx: &mut EventCtx, _, _| {
+                    ctx.submit_notification(MODAL_RESULT.with(ModalResult::new(true)))
+                })),
+        )
+        .padding(10.)
+        .background(Color::grey8(0x30))
+        .rounded(5.)
+}
+
+fn ui_builder() -> impl Widget<AppState> {
+    let content = Flex::column()
+        .with_child(Label::new(|data: &AppState, _: &Env| data.status.clone()))
+        .with_spacer(10.)
+        .with_child(
+            Button::new("Rename")
+                .on_click(|ctx: &mut EventCtx, _, _| ctx.submit_notification(MODAL_SHOW)),
+        )
+        .center()
+        .on_command(MODAL_RESULT, |_, result, data: &mut AppState| {
+            if result.get::<bool>() == Some(&true) {
+                data.status = format!("Renamed to {}", data.name);
+            }
+        });
+
+    Modal::new(content, |_, _| dialog())
+}
+
+pub fn main() {
+    let main_window = WindowDesc::new(ui_builder())
+        .window_size((400., 300.))
+        .title("Modal Example");
+
+    let state = AppState {
+        name: String::from("Untitled"),
+        status: String::from("Nothing renamed yet"),
+    };
+
+    AppLauncher::with_window(main_window)
+        .log_to_console()
+        .launch(state)
+        .expect("launch failed");
+}
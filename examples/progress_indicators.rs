@@ -0,0 +1,68 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Checkbox, Flex, Slider};
+use druid::{AppLauncher, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::{ActivitySpinner, CircularProgress, ProgressBar};
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    progress: f64,
+    known: bool,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("Progress indicators");
+    AppLauncher::with_window(window)
+        .configure_env(druid_widget_nursery::configure_env)
+        .launch(AppState {
+            progress: 0.3,
+            known: true,
+        })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<AppState> {
+    let circular = CircularProgress::new().lens(druid::lens::Map::new(
+        |data: &AppState| data.known.then(|| data.progress),
+        |_, _| {},
+    ));
+
+    Flex::column()
+        .with_child(
+            Flex::row()
+                .with_child(ActivitySpinner::new())
+                .with_default_spacer()
+                .with_child(
+                    ActivitySpinner::new()
+                        .with_size(48.0)
+                        .with_stroke_width(5.0),
+                )
+                .with_default_spacer()
+                .with_child(circular)
+                .with_default_spacer()
+                .with_child(
+                    CircularProgress::new()
+                        .with_size(48.0)
+                        .lens(AppState::progress),
+                ),
+        )
+        .with_default_spacer()
+        .with_child(ProgressBar::new().lens(AppState::progress))
+        .with_default_spacer()
+        .with_child(Slider::new().lens(AppState::progress))
+        .with_default_spacer()
+        .with_child(Checkbox::new("Progress is known").lens(AppState::known))
+        .padding(10.0)
+}
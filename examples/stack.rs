@@ -81,6 +81,20 @@ fn build_stack_ui() -> impl Widget<AppState> {
             Label::new("Animated").with_text_size(50.),
             StackChildParams::dynamic(|state: &AppState, _| &state.position).duration(1.0),
         )
+        .with_positioned_child(
+            Label::new("Badge").background(Color::RED),
+            StackChildPosition::new()
+                .align(UnitPoint::TOP_RIGHT)
+                .offset((-10., 10.)),
+        )
+        .with_positioned_child(
+            Label::new("Half width").background(Color::BLUE),
+            StackChildPosition::new()
+                .relative(true)
+                .left(Some(0.25))
+                .width(Some(0.5))
+                .align(UnitPoint::CENTER),
+        )
         .border(Color::WHITE, 1.0)
         .debug_paint_layout()
 }
@@ -0,0 +1,54 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Button, Checkbox, Flex, Label, Slider, TextBox};
+use druid::{AppLauncher, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::ThemeEditor;
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    text: String,
+    checked: bool,
+    value: f64,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui())
+        .title("Theme editor")
+        .window_size((800.0, 500.0));
+    AppLauncher::with_window(window)
+        .launch(AppState {
+            text: "Edit the theme on the right".into(),
+            checked: true,
+            value: 0.5,
+        })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<AppState> {
+    let app = Flex::column()
+        .with_child(Label::new("A small app"))
+        .with_default_spacer()
+        .with_child(TextBox::new().lens(AppState::text))
+        .with_default_spacer()
+        .with_child(Checkbox::new("Checked").lens(AppState::checked))
+        .with_default_spacer()
+        .with_child(Slider::new().lens(AppState::value))
+        .with_default_spacer()
+        .with_child(Button::new("Button"))
+        .padding(10.0)
+        .expand();
+
+    ThemeEditor::new(app)
+}
@@ -0,0 +1,77 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Button, Checkbox, Flex, Label, Slider, TextBox};
+use druid::{theme, AppLauncher, Color, Data, Lens, Widget, WidgetExt, WindowDesc};
+use druid_widget_nursery::{Theme, ThemeScope, SET_THEME};
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    text: String,
+    checked: bool,
+    value: f64,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("Theme scope");
+    AppLauncher::with_window(window)
+        .launch(AppState {
+            text: "Some text".into(),
+            checked: true,
+            value: 0.5,
+        })
+        .unwrap();
+}
+
+fn sepia() -> Theme {
+    Theme::light()
+        .adding(
+            theme::WINDOW_BACKGROUND_COLOR,
+            Color::rgb8(0xf4, 0xec, 0xd8),
+        )
+        .adding(theme::TEXT_COLOR, Color::rgb8(0x5b, 0x46, 0x36))
+        .adding(theme::PRIMARY_LIGHT, Color::rgb8(0xc0, 0x8a, 0x4e))
+        .adding(theme::PRIMARY_DARK, Color::rgb8(0x9c, 0x6a, 0x34))
+}
+
+fn ui() -> impl Widget<AppState> {
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Dark")
+                .on_click(|ctx, _, _| ctx.submit_command(SET_THEME.with(Theme::dark()))),
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Light")
+                .on_click(|ctx, _, _| ctx.submit_command(SET_THEME.with(Theme::light()))),
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Sepia").on_click(|ctx, _, _| ctx.submit_command(SET_THEME.with(sepia()))),
+        );
+
+    let body = Flex::column()
+        .with_child(buttons)
+        .with_default_spacer()
+        .with_child(Label::new("All widgets below follow the theme."))
+        .with_default_spacer()
+        .with_child(TextBox::new().lens(AppState::text))
+        .with_default_spacer()
+        .with_child(Checkbox::new("Checked").lens(AppState::checked))
+        .with_default_spacer()
+        .with_child(Slider::new().lens(AppState::value))
+        .padding(10.0);
+
+    ThemeScope::new(Theme::dark(), body).with_transition(0.3)
+}
@@ -1,29 +1,33 @@
 use druid::widget::{Button, Flex, Label};
-use druid::{AppLauncher, Application, Widget, WidgetExt, WindowDesc};
+use druid::{AppLauncher, MenuItem, Widget, WidgetExt, WindowDesc};
 use druid_widget_nursery::TitleBar;
 
-// TitleBar only works on windows.
+// Dragging the window by the TitleBar only works on windows.
 
 fn build_titlebar() -> impl Widget<u32> {
-    // Make a row of buttons and title for the titlebar.
-    Flex::row()
-        // Increases the state by one each time clicked.
-        .with_child(Button::new("+1").on_click(|_event, t, _env| *t += 1))
-        // Flex child so it fills up all the space, which means it has to be limited.
-        // This is done by the column in build_ui()
-        .with_flex_child(
-            // New titlebar with a label
-            TitleBar::new(
-                // label is dynamic, using the value from data
-                Label::dynamic(|data: &u32, _env| format!("TitleBar: Value = {data}"))
-                    // Center the label in the titlebar.
-                    .center(),
-            ),
-            1.0,
-        )
-        // Button to close the window.
-        // There isn't a normal titlebar, so something like this has to exist.
-        .with_child(Button::new("Close").on_click(|_, _, _| Application::global().quit()))
+    // New titlebar with a label, which is the area you can drag the window by
+    TitleBar::new(
+        // label is dynamic, using the value from data
+        Label::dynamic(|data: &u32, _env| format!("TitleBar: Value = {data}"))
+            // Center the label in the titlebar.
+            .center(),
+    )
+    // Increases the state by one each time clicked, children stay clickable.
+    .with_leading_child(Button::new("+1").on_click(|_event, t, _env| *t += 1))
+    .with_child(Button::new("Reset").on_click(|_event, t, _env| *t = 0))
+    // Make the window narrow, these move into the "..." menu, "+10" first.
+    .with_collapsible_child(
+        Button::new("+10").on_click(|_event, t, _env| *t += 10),
+        0,
+        || MenuItem::new("+10").on_activate(|_ctx, t: &mut u32, _env| *t += 10),
+    )
+    .with_collapsible_child(
+        Button::new("Double").on_click(|_event, t, _env| *t *= 2),
+        1,
+        || MenuItem::new("Double").on_activate(|_ctx, t: &mut u32, _env| *t *= 2),
+    )
+    // There isn't a normal titlebar, so add minimize, maximize and close buttons.
+    .with_window_controls(true)
 }
 
 fn build_ui() -> impl Widget<u32> {
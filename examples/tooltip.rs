@@ -1,9 +1,11 @@
+use std::time::Duration;
+
 use druid::{
     widget::{Flex, Label, TextBox},
-    AppLauncher, Data, Env, Lens, Widget, WidgetExt, WindowDesc,
+    AppLauncher, Color, Data, Env, Lens, Widget, WidgetExt, WindowDesc,
 };
 
-use druid_widget_nursery::WidgetExt as _;
+use druid_widget_nursery::{TooltipController, TooltipPlacement, WidgetExt as _};
 
 fn main() {
     let window = WindowDesc::new(ui());
@@ -21,10 +23,35 @@ struct AppState {
 fn ui() -> impl Widget<AppState> {
     let label = Label::new("Hover me for a secret message!")
         .tooltip(|data: &AppState, _env: &Env| data.message.clone());
-    let text_box = TextBox::new().lens(AppState::message);
+    // also shown when the text box is focused with Tab
+    let text_box = TextBox::new()
+        .tooltip("Edit the secret message")
+        .lens(AppState::message);
+    let rich = Label::new("Hover me for a colorful one!").controller(
+        TooltipController::widget(|| {
+            Flex::column()
+                .with_child(Label::new("The secret message is:"))
+                .with_child(
+                    Label::dynamic(|data: &AppState, _| data.message.clone())
+                        .with_text_color(Color::rgb8(0xe0, 0x60, 0x60)),
+                )
+                .padding(5.0)
+        })
+        .with_show_delay(Duration::from_millis(100))
+        .with_hide_delay(Duration::from_millis(500))
+        .with_placement(TooltipPlacement::Below),
+    );
 
     Flex::column()
         .with_child(label)
         .with_default_spacer()
+        .with_child(rich)
+        .with_default_spacer()
         .with_child(text_box)
+        .with_default_spacer()
+        .with_child(
+            Label::new("Tooltip above, flipped at the window edge").controller(
+                TooltipController::new("I'm above").with_placement(TooltipPlacement::Above),
+            ),
+        )
 }
@@ -0,0 +1,61 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::{Button, Flex, Label};
+use druid::{AppLauncher, Data, Env, Lens, Widget, WidgetExt, WindowDesc};
+
+use druid_widget_nursery::{TooltipController, TooltipHost, TooltipPlacement, WidgetExt as _};
+
+#[derive(Clone, Data, Lens)]
+struct AppState {
+    clicks: u32,
+}
+
+fn main() {
+    let window = WindowDesc::new(ui()).title("Tooltip host");
+    AppLauncher::with_window(window)
+        .launch(AppState { clicks: 0 })
+        .unwrap();
+}
+
+fn ui() -> impl Widget<AppState> {
+    let toolbar = Flex::row()
+        .with_child(Button::new("New").controller(
+            TooltipController::new("Create a new document").with_placement(TooltipPlacement::Below),
+        ))
+        .with_default_spacer()
+        .with_child(
+            Button::new("Open").controller(
+                TooltipController::new("Open an existing document")
+                    .with_placement(TooltipPlacement::Below),
+            ),
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Click me")
+                .on_click(|_, data: &mut AppState, _| data.clicks += 1)
+                .tooltip(|data: &AppState, _: &Env| format!("Clicked {} times", data.clicks)),
+        );
+
+    // all tooltips below are drawn by the host, inside this window
+    TooltipHost::new(
+        Flex::column()
+            .with_child(toolbar)
+            .with_default_spacer()
+            .with_child(Label::new(
+                "Move along the toolbar, only one tooltip is ever shown.",
+            ))
+            .padding(10.0),
+    )
+}
@@ -0,0 +1,309 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Roles, names and states describing widgets to assistive technologies.
+//!
+//! Druid doesn't have an accessibility tree yet. The widgets of this crate describe themselves
+//! with [`Accessible`], in the terms of [AccessKit], so that they can be exposed once it is
+//! integrated. Other widgets can be given a name with [`with_a11y_label`].
+//!
+//! [AccessKit]: https://github.com/AccessKit/accesskit
+//! [`with_a11y_label`]: crate::WidgetExt::with_a11y_label
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::{Controller, LabelText};
+use druid::{Selector, TimerToken};
+
+/// Announces a message to assistive technologies, like "3 results found". It's handled by the
+/// [`AnnouncementHost`] of the window.
+pub const ANNOUNCE: Selector<(String, Politeness)> =
+    Selector::new("druid-widget-nursery.accessibility.announce");
+
+/// How long an announcement is kept before the next one, so that it can be read out.
+const ANNOUNCEMENT_DURATION: Duration = Duration::from_millis(1500);
+
+/// What a widget is, for assistive technologies. The variants are roles of AccessKit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// A widget without a more specific role, like a container.
+    Generic,
+    Button,
+    CheckBox,
+    /// A button showing a list to pick a value from.
+    ComboBox,
+    Label,
+    ListBox,
+    ListBoxOption,
+    Slider,
+    Table,
+    Row,
+    Cell,
+    Tree,
+    TreeItem,
+}
+
+/// The state of a widget for assistive technologies. The fields which don't apply to a widget are
+/// `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessState {
+    /// Whether the children of a tree item, or the list of a combo box, are shown.
+    pub expanded: Option<bool>,
+    pub selected: Option<bool>,
+    pub checked: Option<bool>,
+    pub disabled: bool,
+    /// The value of a slider, between `min_value` and `max_value`.
+    pub numeric_value: Option<f64>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    /// The value read out, when it isn't only a number.
+    pub value: Option<String>,
+    /// For a live region, how changes to its name are announced.
+    pub live: Option<Politeness>,
+}
+
+/// How urgently an announcement is read out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Data)]
+pub enum Politeness {
+    /// Read out once what is being read is finished, after the announcements before.
+    Polite,
+    /// Read out right away, interrupting what is being read and dropping the polite
+    /// announcements waiting.
+    Assertive,
+}
+
+/// A widget describing itself to assistive technologies.
+pub trait Accessible<T> {
+    /// What the widget is.
+    fn role(&self) -> Role;
+
+    /// The name read out for the widget, like the text of its label.
+    fn accessible_name(&self, _data: &T) -> Option<String> {
+        None
+    }
+
+    /// The state of the widget with `data`.
+    fn accessible_state(&self, _data: &T) -> AccessState {
+        AccessState::default()
+    }
+}
+
+/// A wrapper giving its child a name, and a role, for assistive technologies.
+///
+/// It's made with [`with_a11y_label`], for widgets which don't have a name, like icon buttons or
+/// images, or which have a better one than their text.
+///
+/// ```
+/// # use druid::widget::{Button, Flex};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::accessibility::Role;
+/// # use druid_widget_nursery::WidgetExt as _;
+/// fn toolbar() -> impl Widget<()> {
+///     Flex::row().with_child(
+///         Button::new("✕")
+///             .with_a11y_label("Close")
+///             .with_role(Role::Button),
+///     )
+/// }
+/// ```
+///
+/// [`with_a11y_label`]: crate::WidgetExt::with_a11y_label
+pub struct A11yLabel<T, W> {
+    inner: W,
+    label: LabelText<T>,
+    role: Role,
+}
+
+impl<T: Data, W: Widget<T>> A11yLabel<T, W> {
+    pub fn new(inner: W, label: impl Into<LabelText<T>>) -> Self {
+        A11yLabel {
+            inner,
+            label: label.into(),
+            role: Role::Generic,
+        }
+    }
+
+    /// Builder-style method for setting the role of the child.
+    ///
+    /// For the non-builder varient, see [`set_role`].
+    ///
+    /// [`set_role`]: #method.set_role
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.set_role(role);
+        self
+    }
+
+    /// Set the role of the child, [`Role::Generic`] by default.
+    pub fn set_role(&mut self, role: Role) {
+        self.role = role;
+    }
+}
+
+impl<T: Data, W: Widget<T>> Accessible<T> for A11yLabel<T, W> {
+    fn role(&self) -> Role {
+        self.role
+    }
+
+    fn accessible_name(&self, _data: &T) -> Option<String> {
+        Some(self.label.display_text().to_string())
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for A11yLabel<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.label.resolve(data, env);
+        }
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.label.resolve(data, env);
+        self.inner.update(ctx, old_data, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.inner.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        self.inner.id()
+    }
+}
+
+type AnnounceFn = Box<dyn Fn(&str, Politeness)>;
+
+/// A controller queueing the [`ANNOUNCE`] commands of a window, and making them one at a time.
+///
+/// It's a live region for assistive technologies, whose name is the current announcement. As
+/// druid doesn't expose it yet, each announcement is also given to the handler, which logs it by
+/// default.
+///
+/// ```
+/// # use druid::widget::{Button, Flex};
+/// # use druid::{Widget, WidgetExt};
+/// # use druid_widget_nursery::accessibility::{AnnouncementHost, Politeness, ANNOUNCE};
+/// fn root() -> impl Widget<()> {
+///     Flex::column()
+///         .with_child(Button::new("Delete").on_click(|ctx, _, _| {
+///             ctx.submit_command(ANNOUNCE.with(("Item deleted".into(), Politeness::Polite)));
+///         }))
+///         .controller(AnnouncementHost::new())
+/// }
+/// ```
+pub struct AnnouncementHost {
+    queue: VecDeque<(String, Politeness)>,
+    current: Option<(String, Politeness)>,
+    timer: TimerToken,
+    on_announce: AnnounceFn,
+}
+
+impl AnnouncementHost {
+    pub fn new() -> Self {
+        AnnouncementHost {
+            queue: VecDeque::new(),
+            current: None,
+            timer: TimerToken::INVALID,
+            on_announce: Box::new(|message, politeness| {
+                tracing::info!("announcement ({:?}): {}", politeness, message)
+            }),
+        }
+    }
+
+    /// Builder-style method for setting what is done with each announcement, when it's made.
+    pub fn with_handler(mut self, on_announce: impl Fn(&str, Politeness) + 'static) -> Self {
+        self.on_announce = Box::new(on_announce);
+        self
+    }
+
+    /// The announcement being made.
+    pub fn current(&self) -> Option<&(String, Politeness)> {
+        self.current.as_ref()
+    }
+
+    /// Make the next announcement, if there is one.
+    fn next(&mut self, ctx: &mut EventCtx) {
+        self.current = self.queue.pop_front();
+        match &self.current {
+            Some((message, politeness)) => {
+                (self.on_announce)(message, *politeness);
+                self.timer = ctx.request_timer(ANNOUNCEMENT_DURATION);
+            }
+            None => self.timer = TimerToken::INVALID,
+        }
+    }
+}
+
+impl Default for AnnouncementHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for AnnouncementHost {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(ANNOUNCE) => {
+                let (message, politeness) = cmd.get_unchecked(ANNOUNCE).clone();
+                if politeness == Politeness::Assertive {
+                    self.queue
+                        .retain(|(_, politeness)| *politeness == Politeness::Assertive);
+                    // interrupt the current announcement
+                    self.current = None;
+                }
+                self.queue.push_back((message, politeness));
+                if self.current.is_none() {
+                    self.next(ctx);
+                }
+                ctx.set_handled();
+            }
+            Event::Timer(token) if *token == self.timer => {
+                self.next(ctx);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
+impl<T> Accessible<T> for AnnouncementHost {
+    fn role(&self) -> Role {
+        Role::Generic
+    }
+
+    fn accessible_name(&self, _data: &T) -> Option<String> {
+        self.current.as_ref().map(|(message, _)| message.clone())
+    }
+
+    fn accessible_state(&self, _data: &T) -> AccessState {
+        AccessState {
+            live: Some(
+                self.current
+                    .as_ref()
+                    .map_or(Politeness::Polite, |(_, politeness)| *politeness),
+            ),
+            ..AccessState::default()
+        }
+    }
+}
@@ -12,11 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use ::std::f64::consts::FRAC_PI_2;
 use ::std::time::Instant;
 
-use druid::kurbo::{Point, Rect, RoundedRect};
+use druid::keyboard_types::Key;
+use druid::kurbo::{Circle, Line, Point, Rect};
+use druid::piet::{Text, TextLayout as _, TextLayoutBuilder};
 use druid::widget::prelude::*;
-use druid::{Color, RenderContext, TextLayout, Widget};
+use druid::{theme, Affine, Color, LinearGradient, RenderContext, TextLayout, UnitPoint, Widget};
+
+use crate::accessibility::{AccessState, Accessible, Role};
+use crate::focus_ring::{self, FocusVisible};
+
+/// The length of the bar of the [`AdvancedSlider`].
+const SLIDER_LENGTH: f64 = 120.0;
+/// The width of the bar of the [`AdvancedSlider`], across its length.
+const SLIDER_THICKNESS: f64 = 20.0;
+/// The margin around the bar of the [`AdvancedSlider`].
+const SLIDER_MARGIN: f64 = 2.0;
 
 /// An advanced version of the slider, allowing interactive update of a numeric
 /// value.
@@ -24,6 +37,10 @@ use druid::{Color, RenderContext, TextLayout, Widget};
 /// This slider implements `Widget<f64>`, and works on values clamped
 /// in the range `min_val..max_val`. Additionally double clicks allows to enter
 /// the precise value over the keyboard.
+///
+/// When focused, the arrow keys change the value by one step and Page Up and
+/// Page Down by ten steps. Without a step size a step is a hundredth of the
+/// range.
 pub struct AdvancedSlider {
     min_val: f64,
     max_val: f64,
@@ -36,14 +53,26 @@ pub struct AdvancedSlider {
     input_string: String,
     keyboard_input_origin: bool,
     text_offset: f64,
+    vertical: bool,
+    tick_spacing: Option<f64>,
+    tick_labels: bool,
+    /// The size of the largest tick label, computed in layout.
+    tick_label_size: Size,
+    editable: bool,
+    focus: FocusVisible,
 }
 
 impl AdvancedSlider {
     /// Takes a mouse event and returns the slider value at the specified
-    /// x position.
-    fn x_from_mouse(&self, mouse_event: &druid::MouseEvent) -> f64 {
-        // Determines percentage regarding the slider size 120.0
-        let mut perc_attempt: f64 = (mouse_event.pos.x - 2.0) / 120.0;
+    /// position along the bar.
+    fn value_from_mouse(&self, mouse_event: &druid::MouseEvent) -> f64 {
+        // Determines percentage regarding the slider size, vertical sliders
+        // grow upwards
+        let mut perc_attempt: f64 = if self.vertical {
+            (SLIDER_MARGIN + SLIDER_LENGTH - mouse_event.pos.y) / SLIDER_LENGTH
+        } else {
+            (mouse_event.pos.x - SLIDER_MARGIN) / SLIDER_LENGTH
+        };
         // Make sure percentage is bounded between 0 and 1
         if perc_attempt < 0.0 {
             perc_attempt = 0.0;
@@ -116,6 +145,12 @@ impl AdvancedSlider {
             input_string: String::from(""),
             keyboard_input_origin: false,
             text_offset: 0.0,
+            vertical: false,
+            tick_spacing: None,
+            tick_labels: false,
+            tick_label_size: Size::ZERO,
+            editable: true,
+            focus: FocusVisible::new(),
         }
     }
 
@@ -161,6 +196,67 @@ impl AdvancedSlider {
         self.text_offset = offset;
         self
     }
+
+    /// Builder style method to lay out the slider vertically, with the
+    /// minimum value at the bottom.
+    pub fn with_vertical(mut self, vertical: bool) -> AdvancedSlider {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Builder style method to draw tick marks next to the bar, starting at
+    /// the minimum value. Zero disables the tick marks.
+    pub fn with_ticks(mut self, spacing: f64) -> AdvancedSlider {
+        if spacing <= 0.0 {
+            self.tick_spacing = None;
+        } else {
+            self.tick_spacing = Some(spacing);
+        }
+        self
+    }
+
+    /// Builder style method to label the tick marks with their value, using
+    /// the significant digits option.
+    pub fn with_tick_labels(mut self, tick_labels: bool) -> AdvancedSlider {
+        self.tick_labels = tick_labels;
+        self
+    }
+
+    /// Builder style method to enable or disable entering the value over the
+    /// keyboard after a double click. Enabled by default.
+    pub fn with_editable(mut self, editable: bool) -> AdvancedSlider {
+        self.editable = editable;
+        self
+    }
+
+    /// The values of the tick marks.
+    fn ticks(&self) -> impl Iterator<Item = f64> {
+        let min_val = self.min_val;
+        let (spacing, count) = match self.tick_spacing {
+            Some(spacing) => (
+                spacing,
+                ((self.max_val - self.min_val) / spacing + 1e-9).floor() as usize + 1,
+            ),
+            None => (0.0, 0),
+        };
+        (0..count).map(move |i| min_val + i as f64 * spacing)
+    }
+
+    /// The position of `value` along the bar, from the left or the top.
+    fn offset_of(&self, value: f64) -> f64 {
+        let fraction = (value - self.min_val) / (self.max_val - self.min_val);
+        if self.vertical {
+            SLIDER_MARGIN + (1.0 - fraction) * SLIDER_LENGTH
+        } else {
+            SLIDER_MARGIN + fraction * SLIDER_LENGTH
+        }
+    }
+
+    /// The amount the arrow keys change the value by.
+    fn key_step(&self) -> f64 {
+        self.step_size
+            .unwrap_or((self.max_val - self.min_val) / 100.0)
+    }
 }
 
 impl Default for AdvancedSlider {
@@ -173,6 +269,7 @@ impl Widget<f64> for AdvancedSlider {
     /// Handles clicking and draging the slider bar, aswell as a double click
     /// for Keyboard input
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut f64, env: &Env) {
+        self.focus.event(ctx, event);
         match event {
             Event::WindowConnected => {
                 *data = self.start_val;
@@ -183,7 +280,7 @@ impl Widget<f64> for AdvancedSlider {
                 // Make sure the widget only reacts when not in input mode
                 if !self.input_mode {
                     // Check whether a double click happened
-                    if self.last_click.elapsed().as_millis() < 100_u128 {
+                    if self.editable && self.last_click.elapsed().as_millis() < 100_u128 {
                         // Enter input mode
                         self.input_mode = true;
                         self.input_string = String::from("");
@@ -194,7 +291,8 @@ impl Widget<f64> for AdvancedSlider {
                     } else {
                         // Handle simple click
                         ctx.set_active(true);
-                        let data_attempt = self.x_from_mouse(mouse_event);
+                        ctx.request_focus();
+                        let data_attempt = self.value_from_mouse(mouse_event);
                         let data_tuple = self.data_from_attempt(data_attempt);
                         *data = data_tuple.0;
                     }
@@ -213,13 +311,31 @@ impl Widget<f64> for AdvancedSlider {
                 if !self.input_mode {
                     // Make sure widget only reacts when active
                     if ctx.is_active() {
-                        let data_attempt = self.x_from_mouse(mouse_event);
+                        let data_attempt = self.value_from_mouse(mouse_event);
                         let data_tuple = self.data_from_attempt(data_attempt);
                         *data = data_tuple.0;
                     }
                 }
             }
 
+            // Adjust the value with the arrow and page keys
+            Event::KeyDown(key_event) if !self.input_mode => {
+                let step = self.key_step();
+                let delta = match &key_event.key {
+                    Key::ArrowUp | Key::ArrowRight => step,
+                    Key::ArrowDown | Key::ArrowLeft => -step,
+                    Key::PageUp => 10.0 * step,
+                    Key::PageDown => -10.0 * step,
+                    Key::Home => self.min_val - *data,
+                    Key::End => self.max_val - *data,
+                    _ => return,
+                };
+                self.keyboard_input_origin = false;
+                let data_tuple = self.data_from_attempt(*data + delta);
+                *data = data_tuple.0;
+                ctx.set_handled();
+            }
+
             Event::KeyDown(key_event) => match &key_event.key {
                 // Enter to confirm keyboard input
                 druid::keyboard_types::Key::Enter => {
@@ -276,9 +392,11 @@ impl Widget<f64> for AdvancedSlider {
     // Handle initialisation
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &f64, _env: &Env) {
         if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
             ctx.request_layout();
             ctx.request_paint();
         }
+        self.focus.lifecycle(ctx, event);
     }
 
     fn update(&mut self, ctx: &mut UpdateCtx, old_data: &f64, data: &f64, env: &Env) {
@@ -299,39 +417,505 @@ impl Widget<f64> for AdvancedSlider {
 
     fn layout(
         &mut self,
-        _layout_ctx: &mut LayoutCtx,
+        layout_ctx: &mut LayoutCtx,
         _bc: &BoxConstraints,
         _data: &f64,
-        _env: &Env,
+        env: &Env,
     ) -> Size {
-        Size::new(124.0, 24.0)
+        // Make room for the tick marks and their labels across the bar
+        let mut ticks_extent = 0.0;
+        if self.tick_spacing.is_some() {
+            ticks_extent += TICK_LENGTH + 2.0;
+            if self.tick_labels {
+                self.tick_label_size = Size::ZERO;
+                for value in self.ticks() {
+                    let label = layout_ctx
+                        .text()
+                        .new_text_layout(format!("{:.*}", self.signif_dig, value))
+                        .font(
+                            env.get(theme::UI_FONT).family,
+                            env.get(theme::TEXT_SIZE_SMALL),
+                        )
+                        .build();
+                    if let Ok(label) = label {
+                        let size = label.size();
+                        self.tick_label_size = Size::new(
+                            self.tick_label_size.width.max(size.width),
+                            self.tick_label_size.height.max(size.height),
+                        );
+                    }
+                }
+                ticks_extent += if self.vertical {
+                    self.tick_label_size.width
+                } else {
+                    self.tick_label_size.height
+                } + 2.0;
+            }
+        }
+
+        let length = SLIDER_LENGTH + 2.0 * SLIDER_MARGIN;
+        let thickness = SLIDER_THICKNESS + 2.0 * SLIDER_MARGIN + ticks_extent;
+        if self.vertical {
+            Size::new(thickness, length)
+        } else {
+            Size::new(length, thickness)
+        }
     }
 
-    fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, _env: &Env) {
-        let rounded_box = RoundedRect::new(2.0, 2.0, 122.0, 22.0, 2.0);
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
+        let far_end = SLIDER_MARGIN + SLIDER_LENGTH;
+        let far_side = SLIDER_MARGIN + SLIDER_THICKNESS;
+        let bar = if self.vertical {
+            Rect::new(SLIDER_MARGIN, SLIDER_MARGIN, far_side, far_end)
+        } else {
+            Rect::new(SLIDER_MARGIN, SLIDER_MARGIN, far_end, far_side)
+        };
+        let rounded_box = bar.to_rounded_rect(2.0);
         // Handle in which mode to draw the widget
         if self.input_mode {
-            ctx.fill(rounded_box, &Color::rgb8(50, 50, 50));
-            ctx.stroke(rounded_box, &Color::rgb8(80, 80, 80), 1.0);
+            ctx.fill(rounded_box, &env.get(theme::BACKGROUND_DARK));
+            ctx.stroke(rounded_box, &env.get(theme::BORDER_DARK), 1.0);
         } else {
-            let percentage = (data - self.min_val) / (self.max_val - self.min_val) * 100.0;
-            let blocker = Rect::new(percentage * 1.2 + 2.0, 2.0, 122.0, 22.0);
+            let offset = self.offset_of(*data);
+            let blocker = if self.vertical {
+                Rect::new(SLIDER_MARGIN, SLIDER_MARGIN, far_side, offset)
+            } else {
+                Rect::new(offset, SLIDER_MARGIN, far_end, far_side)
+            };
 
             // Constrain blocker to within the slider. A blocker is used to make
             // sure the slider is flat on one side and rounded on the other side.
             if (data < &self.min_val) | (data > &self.max_val) {
                 ctx.fill(rounded_box, &Color::rgb8(212, 32, 35));
             } else {
-                ctx.fill(rounded_box, &Color::rgb8(41, 128, 186));
-                ctx.fill(blocker, &Color::rgb8(80, 80, 80));
+                ctx.fill(rounded_box, &env.get(theme::PRIMARY_DARK));
+                ctx.fill(blocker, &env.get(theme::BACKGROUND_LIGHT));
+            }
+            ctx.stroke(rounded_box, &env.get(theme::BORDER_DARK), 1.0);
+        }
+        if self.focus.is_visible() {
+            focus_ring::paint(ctx, bar.inflate(2.0, 2.0), 4.0, env);
+        }
+        // Draw the tick marks and their labels next to the bar
+        let tick_color = env.get(theme::BORDER_LIGHT);
+        let label_color = env.get(theme::TEXT_COLOR);
+        for value in self.ticks() {
+            let offset = self.offset_of(value);
+            let tick_start = far_side + 2.0;
+            let tick_end = tick_start + TICK_LENGTH;
+            let tick = if self.vertical {
+                Line::new((tick_start, offset), (tick_end, offset))
+            } else {
+                Line::new((offset, tick_start), (offset, tick_end))
+            };
+            ctx.stroke(tick, &tick_color, 1.0);
+
+            if self.tick_labels {
+                let label = ctx
+                    .text()
+                    .new_text_layout(format!("{:.*}", self.signif_dig, value))
+                    .font(
+                        env.get(theme::UI_FONT).family,
+                        env.get(theme::TEXT_SIZE_SMALL),
+                    )
+                    .text_color(label_color)
+                    .build();
+                if let Ok(label) = label {
+                    let size = label.size();
+                    let origin = if self.vertical {
+                        Point::new(tick_end + 2.0, offset - size.height / 2.0)
+                    } else {
+                        Point::new(offset - size.width / 2.0, tick_end + 2.0)
+                    };
+                    ctx.draw_text(&label, origin);
+                }
+            }
+        }
+
+        // Center Text and draw it, along the bar for vertical sliders
+        let text_size = self.val_text.layout_metrics().size;
+        let center = bar.center();
+        ctx.with_save(|ctx| {
+            ctx.transform(Affine::translate(center.to_vec2()));
+            if self.vertical {
+                ctx.transform(Affine::rotate(-FRAC_PI_2));
             }
-            ctx.stroke(rounded_box, &Color::rgb8(30, 30, 30), 1.0);
+            self.val_text.draw(
+                ctx,
+                Point::new(
+                    -text_size.width / 2.0,
+                    -SLIDER_THICKNESS / 2.0 + self.text_offset,
+                ),
+            );
+        });
+    }
+}
+
+/// The length of the tick marks below the track.
+const TICK_LENGTH: f64 = 4.0;
+/// The distance between a thumb and the value shown above it while dragging.
+const TOOLTIP_GAP: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Thumb {
+    Low,
+    High,
+}
+
+/// A slider with two thumbs, for selecting a range of values.
+///
+/// This slider implements `Widget<(f64, f64)>`, the lower and upper end of the range. The
+/// thumbs are kept at least the minimum gap apart, and the value of a thumb is shown above it
+/// while it is dragged.
+pub struct RangeSlider {
+    min_val: f64,
+    max_val: f64,
+    min_gap: f64,
+    step_size: Option<f64>,
+    tick_spacing: Option<f64>,
+    signif_dig: usize,
+    dragging: Option<Thumb>,
+    /// The distance between the mouse and the center of the dragged thumb.
+    drag_offset: f64,
+}
+
+impl RangeSlider {
+    /// Builder style method for constructing a new range slider
+    pub fn new() -> RangeSlider {
+        RangeSlider {
+            min_val: 0.0,
+            max_val: 100.0,
+            min_gap: 0.0,
+            step_size: None,
+            tick_spacing: None,
+            signif_dig: 0,
+            dragging: None,
+            drag_offset: 0.0,
         }
-        // Center Text and draw it
-        let text_width = self.val_text.layout_metrics().size.width;
-        self.val_text.draw(
-            ctx,
-            Point::new(62.0 - (text_width / 2.0), 2.0 + self.text_offset),
+    }
+
+    /// Builder style method for setting the range.
+    pub fn with_range(mut self, min_val: f64, max_val: f64) -> RangeSlider {
+        if min_val < max_val {
+            self.min_val = min_val;
+            self.max_val = max_val;
+        } else {
+            self.min_val = max_val;
+            self.max_val = min_val;
+        }
+        self
+    }
+
+    /// Builder style method for setting the minimum distance between the
+    /// lower and the upper value.
+    pub fn with_min_gap(mut self, min_gap: f64) -> RangeSlider {
+        self.min_gap = min_gap.max(0.0);
+        self
+    }
+
+    /// Builder style method to set the stepping size. Zero coresponds to no
+    /// (infinite small) stepping.
+    pub fn with_step_size(mut self, step_size: f64) -> RangeSlider {
+        if step_size <= 0.0 {
+            self.step_size = None;
+        } else {
+            self.step_size = Some(step_size);
+        }
+        self
+    }
+
+    /// Builder style method to draw tick marks below the track, starting at
+    /// the minimum value. Zero disables the tick marks.
+    pub fn with_ticks(mut self, spacing: f64) -> RangeSlider {
+        if spacing <= 0.0 {
+            self.tick_spacing = None;
+        } else {
+            self.tick_spacing = Some(spacing);
+        }
+        self
+    }
+
+    /// Builder style method to set the significant digits for displaying.
+    pub fn with_significant(mut self, signif_dig: usize) -> RangeSlider {
+        self.signif_dig = signif_dig;
+        self
+    }
+
+    fn knob_radius(env: &Env) -> f64 {
+        env.get(theme::BASIC_WIDGET_HEIGHT) / 2.0
+    }
+
+    fn x_from_value(&self, value: f64, width: f64, env: &Env) -> f64 {
+        let radius = Self::knob_radius(env);
+        let fraction = (value - self.min_val) / (self.max_val - self.min_val);
+        radius + fraction.max(0.0).min(1.0) * (width - 2.0 * radius)
+    }
+
+    fn value_from_x(&self, x: f64, width: f64, env: &Env) -> f64 {
+        let radius = Self::knob_radius(env);
+        let fraction = (x - radius) / (width - 2.0 * radius).max(1.0);
+        let value = self.min_val + fraction.max(0.0).min(1.0) * (self.max_val - self.min_val);
+        match self.step_size {
+            Some(step_size) => {
+                ((value - self.min_val) / step_size).round() * step_size + self.min_val
+            }
+            None => value,
+        }
+    }
+
+    /// Move a thumb to `value`, keeping the minimum gap to the other one.
+    fn move_thumb(&self, thumb: Thumb, value: f64, data: &mut (f64, f64)) {
+        match thumb {
+            Thumb::Low => {
+                let limit = (data.1 - self.min_gap).max(self.min_val);
+                data.0 = value.max(self.min_val).min(limit);
+            }
+            Thumb::High => {
+                let limit = (data.0 + self.min_gap).min(self.max_val);
+                data.1 = value.min(self.max_val).max(limit);
+            }
+        }
+    }
+
+    /// The thumb a click at `x` should move.
+    fn thumb_at(&self, x: f64, width: f64, data: &(f64, f64), env: &Env) -> Thumb {
+        let low = self.x_from_value(data.0, width, env);
+        let high = self.x_from_value(data.1, width, env);
+        if (x - low).abs() < (x - high).abs() {
+            Thumb::Low
+        } else if (x - low).abs() > (x - high).abs() || x > high {
+            Thumb::High
+        } else {
+            // both thumbs at the same position
+            Thumb::Low
+        }
+    }
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<(f64, f64)> for RangeSlider {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut (f64, f64), env: &Env) {
+        let width = ctx.size().width;
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button.is_left() => {
+                let thumb = self.thumb_at(mouse_event.pos.x, width, data, env);
+                let value = match thumb {
+                    Thumb::Low => data.0,
+                    Thumb::High => data.1,
+                };
+                let thumb_x = self.x_from_value(value, width, env);
+                if (mouse_event.pos.x - thumb_x).abs() <= Self::knob_radius(env) {
+                    // grabbed the thumb, don't let it jump
+                    self.drag_offset = thumb_x - mouse_event.pos.x;
+                } else {
+                    self.drag_offset = 0.0;
+                    let value = self.value_from_x(mouse_event.pos.x, width, env);
+                    self.move_thumb(thumb, value, data);
+                }
+                self.dragging = Some(thumb);
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseMove(mouse_event) if ctx.is_active() => {
+                if let Some(thumb) = self.dragging {
+                    let value = self.value_from_x(mouse_event.pos.x + self.drag_offset, width, env);
+                    self.move_thumb(thumb, value, data);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                self.dragging = None;
+                ctx.set_active(false);
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &(f64, f64),
+        _env: &Env,
+    ) {
+        if let LifeCycle::HotChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &(f64, f64),
+        data: &(f64, f64),
+        _env: &Env,
+    ) {
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &(f64, f64),
+        env: &Env,
+    ) -> Size {
+        let mut height = env.get(theme::BASIC_WIDGET_HEIGHT);
+        if self.tick_spacing.is_some() {
+            height += TICK_LENGTH + 2.0;
+        }
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            env.get(theme::WIDE_WIDGET_WIDTH)
+        };
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &(f64, f64), env: &Env) {
+        let width = ctx.size().width;
+        let radius = Self::knob_radius(env);
+        let center_y = radius;
+        let track_thickness = 4.0;
+
+        // the track, and the selected part of it
+        let track = Rect::new(
+            radius,
+            center_y - track_thickness / 2.0,
+            width - radius,
+            center_y + track_thickness / 2.0,
+        )
+        .to_rounded_rect(2.0);
+        ctx.fill(track, &env.get(theme::BACKGROUND_DARK));
+        ctx.stroke(track, &env.get(theme::BORDER_DARK), 1.0);
+
+        let low_x = self.x_from_value(data.0, width, env);
+        let high_x = self.x_from_value(data.1, width, env);
+        let selected = Rect::new(
+            low_x,
+            center_y - track_thickness / 2.0,
+            high_x,
+            center_y + track_thickness / 2.0,
         );
+        ctx.fill(selected, &env.get(theme::PRIMARY_LIGHT));
+
+        if let Some(spacing) = self.tick_spacing {
+            let color = env.get(theme::BORDER_LIGHT);
+            let y = 2.0 * radius + 2.0;
+            let count = ((self.max_val - self.min_val) / spacing).floor() as usize;
+            for i in 0..=count {
+                let x = self.x_from_value(self.min_val + i as f64 * spacing, width, env);
+                ctx.stroke(Line::new((x, y), (x, y + TICK_LENGTH)), &color, 1.0);
+            }
+        }
+
+        // the thumbs
+        let is_hot = ctx.is_hot();
+        for (thumb, x) in [(Thumb::Low, low_x), (Thumb::High, high_x)] {
+            let knob = Circle::new((x, center_y), radius - 1.0);
+            let dragged = self.dragging == Some(thumb);
+            let border_color = if dragged || is_hot {
+                env.get(theme::FOREGROUND_LIGHT)
+            } else {
+                env.get(theme::FOREGROUND_DARK)
+            };
+            let gradient = if dragged {
+                LinearGradient::new(
+                    UnitPoint::TOP,
+                    UnitPoint::BOTTOM,
+                    (
+                        env.get(theme::FOREGROUND_DARK),
+                        env.get(theme::FOREGROUND_LIGHT),
+                    ),
+                )
+            } else {
+                LinearGradient::new(
+                    UnitPoint::TOP,
+                    UnitPoint::BOTTOM,
+                    (
+                        env.get(theme::FOREGROUND_LIGHT),
+                        env.get(theme::FOREGROUND_DARK),
+                    ),
+                )
+            };
+            ctx.fill(knob, &gradient);
+            ctx.stroke(knob, &border_color, 2.0);
+        }
+
+        // the value of the dragged thumb, above the widget
+        if let Some(thumb) = self.dragging {
+            let (value, x) = match thumb {
+                Thumb::Low => (data.0, low_x),
+                Thumb::High => (data.1, high_x),
+            };
+            let layout = ctx
+                .text()
+                .new_text_layout(format!("{:.*}", self.signif_dig, value))
+                .font(
+                    env.get(theme::UI_FONT).family,
+                    env.get(theme::TEXT_SIZE_NORMAL),
+                )
+                .text_color(env.get(theme::TEXT_COLOR))
+                .build();
+            if let Ok(layout) = layout {
+                let text_size = layout.size();
+                let bubble = Rect::from_center_size(
+                    (x, -TOOLTIP_GAP - text_size.height / 2.0 - 2.0),
+                    (text_size.width + 8.0, text_size.height + 4.0),
+                )
+                .to_rounded_rect(env.get(theme::BUTTON_BORDER_RADIUS));
+                let background = env.get(theme::BACKGROUND_LIGHT);
+                let border = env.get(theme::BORDER_DARK);
+                ctx.paint_with_z_index(1_000_000, move |ctx| {
+                    ctx.fill(bubble, &background);
+                    ctx.stroke(bubble, &border, 1.0);
+                    let origin = bubble.center() - text_size.to_vec2() / 2.0;
+                    ctx.draw_text(&layout, origin);
+                });
+            }
+        }
+    }
+}
+
+impl Accessible<f64> for AdvancedSlider {
+    fn role(&self) -> Role {
+        Role::Slider
+    }
+
+    fn accessible_state(&self, data: &f64) -> AccessState {
+        AccessState {
+            numeric_value: Some(*data),
+            min_value: Some(self.min_val),
+            max_value: Some(self.max_val),
+            value: Some(self.data_from_attempt(*data).1),
+            ..AccessState::default()
+        }
+    }
+}
+
+impl Accessible<(f64, f64)> for RangeSlider {
+    fn role(&self) -> Role {
+        Role::Slider
+    }
+
+    /// The range is read out, as there is no numeric value for two thumbs.
+    fn accessible_state(&self, data: &(f64, f64)) -> AccessState {
+        AccessState {
+            min_value: Some(self.min_val),
+            max_value: Some(self.max_val),
+            value: Some(format!(
+                "{:.*} to {:.*}",
+                self.signif_dig, data.0, self.signif_dig, data.1
+            )),
+            ..AccessState::default()
+        }
     }
 }
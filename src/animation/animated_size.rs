@@ -0,0 +1,143 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::prelude::*;
+use druid::{Data, Point, Selector, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, Spring};
+
+/// Sent to ourselves from `layout`, which can't start animations.
+const SIZE_CHANGED: Selector<Size> = Selector::new("druid-widget-nursery.animated-size.changed");
+
+/// A container animating its size when the size of its child changes, instead of jumping.
+///
+/// The child is laid out at its natural size right away, and clipped while the container
+/// grows or shrinks to it.
+pub struct AnimatedSize<T, W> {
+    inner: WidgetPod<T, W>,
+    size: Animated<Size>,
+    /// The size we asked to animate to, but didn't start yet.
+    pending: Option<Size>,
+    laid_out: bool,
+}
+
+impl<T: Data, W: Widget<T>> AnimatedSize<T, W> {
+    /// Create a container animating over 0.25 seconds.
+    pub fn new(inner: W) -> Self {
+        AnimatedSize {
+            inner: WidgetPod::new(inner),
+            size: Animated::new(Size::ZERO)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+            pending: None,
+            laid_out: false,
+        }
+    }
+
+    /// Builder-style method for specifying the duration.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the size changes in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.size.set_duration(duration);
+    }
+
+    /// Builder-style method for specifying the [`AnimationCurve`].
+    ///
+    /// For the non-builder varient, see [`set_curve`].
+    ///
+    /// [`set_curve`]: #method.set_curve
+    pub fn curve(mut self, curve: AnimationCurve) -> Self {
+        self.set_curve(curve);
+        self
+    }
+
+    /// Set the [`AnimationCurve`].
+    pub fn set_curve(&mut self, curve: AnimationCurve) {
+        self.size.set_curve(curve);
+    }
+
+    /// Builder-style method for driving the animation with a [`Spring`].
+    ///
+    /// For the non-builder varient, see [`set_spring`].
+    ///
+    /// [`set_spring`]: #method.set_spring
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.set_spring(Some(spring));
+        self
+    }
+
+    /// Drive the animation with a [`Spring`], or with the duration and the curve for `None`.
+    pub fn set_spring(&mut self, spring: Option<Spring>) {
+        self.size.set_spring(spring);
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for AnimatedSize<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(SIZE_CHANGED) => {
+                self.pending = None;
+                self.size.animate(ctx, *cmd.get_unchecked(SIZE_CHANGED));
+                ctx.set_handled();
+                return;
+            }
+            Event::AnimFrame(nanos) if self.size.animating() => {
+                self.size.update(ctx, *nanos);
+            }
+            _ => {}
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_size = self.inner.layout(ctx, &bc.loosen(), data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        if !self.laid_out {
+            self.laid_out = true;
+            self.size.jump_to_value(child_size);
+        } else if child_size != self.size.end() && self.pending != Some(child_size) {
+            self.pending = Some(child_size);
+            ctx.submit_command(SIZE_CHANGED.with(child_size).to(ctx.widget_id()));
+        }
+
+        bc.constrain(self.size.get())
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let inner = &mut self.inner;
+        ctx.with_save(|ctx| {
+            let clip = ctx.size().to_rect();
+            ctx.clip(clip);
+            inner.paint(ctx, data, env);
+        });
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::prelude::*;
+use druid::{theme, Data, Point, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, Spring};
+
+type ChildPicker<T, U> = dyn Fn(&T, &Env) -> U;
+type ChildBuilder<T, U> = dyn Fn(&U, &T, &Env) -> Box<dyn Widget<T>>;
+
+/// How [`AnimatedSwitcher`] replaces one child with the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SwitchTransition {
+    /// Fade the old child out to the window background, then fade the new child in.
+    #[default]
+    Fade,
+    /// The new child pushes the old one out to the left.
+    SlideHorizontal,
+    /// The new child pushes the old one out to the top.
+    SlideVertical,
+}
+
+impl SwitchTransition {
+    /// The offsets of the old and the new child, at `progress` from 0 to 1.
+    pub(crate) fn offsets(self, progress: f64, size: Size) -> (Point, Point) {
+        match self {
+            SwitchTransition::Fade => (Point::ORIGIN, Point::ORIGIN),
+            SwitchTransition::SlideHorizontal => (
+                Point::new(-progress * size.width, 0.0),
+                Point::new((1.0 - progress) * size.width, 0.0),
+            ),
+            SwitchTransition::SlideVertical => (
+                Point::new(0.0, -progress * size.height),
+                Point::new(0.0, (1.0 - progress) * size.height),
+            ),
+        }
+    }
+
+    /// Paint the old and the new child at `progress` from 0 to 1, clipped to the widget.
+    pub(crate) fn paint(
+        self,
+        ctx: &mut PaintCtx,
+        progress: f64,
+        env: &Env,
+        paint_old: Option<impl FnOnce(&mut PaintCtx)>,
+        paint_new: impl FnOnce(&mut PaintCtx),
+    ) {
+        ctx.with_save(|ctx| {
+            let rect = ctx.size().to_rect();
+            ctx.clip(rect);
+            match (self, paint_old) {
+                (SwitchTransition::Fade, Some(paint_old)) => {
+                    // druid can't paint with opacity, fade through the background instead
+                    let background = env.get(theme::WINDOW_BACKGROUND_COLOR);
+                    if progress < 0.5 {
+                        paint_old(ctx);
+                        ctx.fill(rect, &background.with_alpha(progress * 2.0));
+                    } else {
+                        paint_new(ctx);
+                        ctx.fill(rect, &background.with_alpha((1.0 - progress) * 2.0));
+                    }
+                }
+                (_, paint_old) => {
+                    if let Some(paint_old) = paint_old {
+                        paint_old(ctx);
+                    }
+                    paint_new(ctx);
+                }
+            }
+        });
+    }
+}
+
+/// A widget switching between children like druid's [`ViewSwitcher`], animating the change.
+///
+/// `child_picker` returns a value identifying the child to show. Whenever it changes,
+/// `child_builder` creates the new child, and the old one is animated out with the data it had
+/// last.
+///
+/// ```
+/// # use druid::widget::Label;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::animation::{AnimatedSwitcher, SwitchTransition};
+/// fn page() -> impl Widget<usize> {
+///     AnimatedSwitcher::new(
+///         |page: &usize, _| *page,
+///         |page, _, _| Box::new(Label::new(format!("Page {}", page + 1))),
+///     )
+///     .transition(SwitchTransition::SlideHorizontal)
+/// }
+/// ```
+///
+/// [`ViewSwitcher`]: druid::widget::ViewSwitcher
+pub struct AnimatedSwitcher<T, U> {
+    child_picker: Box<ChildPicker<T, U>>,
+    child_builder: Box<ChildBuilder<T, U>>,
+    active_key: Option<U>,
+    active: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    /// The child being replaced, with its last data.
+    old: Option<(WidgetPod<T, Box<dyn Widget<T>>>, T)>,
+    progress: Animated<f64>,
+    transition: SwitchTransition,
+}
+
+impl<T: Data, U: Data> AnimatedSwitcher<T, U> {
+    /// Create a switcher animating over 0.25 seconds.
+    pub fn new(
+        child_picker: impl Fn(&T, &Env) -> U + 'static,
+        child_builder: impl Fn(&U, &T, &Env) -> Box<dyn Widget<T>> + 'static,
+    ) -> Self {
+        AnimatedSwitcher {
+            child_picker: Box::new(child_picker),
+            child_builder: Box::new(child_builder),
+            active_key: None,
+            active: None,
+            old: None,
+            progress: Animated::jump(1.0)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+            transition: SwitchTransition::default(),
+        }
+    }
+
+    /// Builder-style method for specifying the [`SwitchTransition`].
+    ///
+    /// For the non-builder varient, see [`set_transition`].
+    ///
+    /// [`set_transition`]: #method.set_transition
+    pub fn transition(mut self, transition: SwitchTransition) -> Self {
+        self.set_transition(transition);
+        self
+    }
+
+    /// Set the [`SwitchTransition`].
+    pub fn set_transition(&mut self, transition: SwitchTransition) {
+        self.transition = transition;
+    }
+
+    /// Builder-style method for specifying the duration.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the switch in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.progress.set_duration(duration);
+    }
+
+    /// Builder-style method for specifying the [`AnimationCurve`].
+    ///
+    /// For the non-builder varient, see [`set_curve`].
+    ///
+    /// [`set_curve`]: #method.set_curve
+    pub fn curve(mut self, curve: AnimationCurve) -> Self {
+        self.set_curve(curve);
+        self
+    }
+
+    /// Set the [`AnimationCurve`].
+    pub fn set_curve(&mut self, curve: AnimationCurve) {
+        self.progress.set_curve(curve);
+    }
+
+    /// Builder-style method for driving the animation with a [`Spring`].
+    ///
+    /// For the non-builder varient, see [`set_spring`].
+    ///
+    /// [`set_spring`]: #method.set_spring
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.set_spring(Some(spring));
+        self
+    }
+
+    /// Drive the animation with a [`Spring`], or with the duration and the curve for `None`.
+    pub fn set_spring(&mut self, spring: Option<Spring>) {
+        self.progress.set_spring(spring);
+    }
+}
+
+impl<T: Data, U: Data> Widget<T> for AnimatedSwitcher<T, U> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            if self.progress.animating() {
+                self.progress.update(ctx, *nanos);
+                if !self.progress.animating() {
+                    self.old = None;
+                    ctx.children_changed();
+                }
+            }
+        }
+        if let Some(active) = &mut self.active {
+            active.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            let key = (self.child_picker)(data, env);
+            self.active = Some(WidgetPod::new((self.child_builder)(&key, data, env)));
+            self.active_key = Some(key);
+        }
+        if let Some(active) = &mut self.active {
+            active.lifecycle(ctx, event, data, env);
+        }
+        if let Some((old, old_data)) = &mut self.old {
+            old.lifecycle(ctx, event, old_data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let key = (self.child_picker)(data, env);
+        if !matches!(&self.active_key, Some(active_key) if active_key.same(&key)) {
+            let child = WidgetPod::new((self.child_builder)(&key, data, env));
+            self.old = self
+                .active
+                .replace(child)
+                .map(|old| (old, old_data.clone()));
+            self.active_key = Some(key);
+            self.progress.jump_to_value(0.0);
+            self.progress.animate(ctx, 1.0);
+            if !self.progress.animating() {
+                self.old = None;
+            }
+            ctx.children_changed();
+        } else if let Some(active) = &mut self.active {
+            active.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = match &mut self.active {
+            Some(active) => active.layout(ctx, bc, data, env),
+            None => bc.min(),
+        };
+        let (old_offset, active_offset) = self.transition.offsets(self.progress.get(), size);
+        if let Some(active) = &mut self.active {
+            active.set_origin(ctx, active_offset);
+        }
+        if let Some((old, old_data)) = &mut self.old {
+            old.layout(ctx, bc, old_data, env);
+            old.set_origin(ctx, old_offset);
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let active = &mut self.active;
+        let paint_old = self
+            .old
+            .as_mut()
+            .map(|(old, old_data)| move |ctx: &mut PaintCtx| old.paint(ctx, old_data, env));
+        self.transition
+            .paint(ctx, self.progress.get(), env, paint_old, |ctx| {
+                if let Some(active) = active {
+                    active.paint(ctx, data, env);
+                }
+            });
+    }
+}
@@ -14,17 +14,23 @@
 
 use std::ops::Deref;
 
-use crate::animation::{AnimationController, AnimationCurve, Interpolate};
+use crate::animation::spring::SpringState;
+use crate::animation::{reduced_motion, AnimationController, AnimationCurve, Interpolate, Spring};
 
 use crate::RequestCtx;
 
 /// Animated provides simple transition-animations for single values or tuples of values that implement
 /// [`Interpolate`].
+///
+/// The animation is driven either by a duration and an [`AnimationCurve`], or by a [`Spring`].
 pub struct Animated<T> {
     start: T,
     end: T,
     controller: AnimationController,
     curve: AnimationCurve,
+    spring: Option<Spring>,
+    /// The running spring, if any.
+    spring_state: Option<SpringState>,
 
     current: T,
 }
@@ -47,6 +53,8 @@ impl<T: Interpolate> Animated<T> {
             end: value.clone(),
             controller,
             curve: Default::default(),
+            spring: None,
+            spring_state: None,
             current: value,
         }
     }
@@ -61,6 +69,8 @@ impl<T: Interpolate> Animated<T> {
             end: value.clone(),
             controller,
             curve: Default::default(),
+            spring: None,
+            spring_state: None,
             current: value,
         }
     }
@@ -95,6 +105,23 @@ impl<T: Interpolate> Animated<T> {
         self.controller.set_duration(duration);
     }
 
+    /// Builder-style method for driving the animation with a [`Spring`], instead of the
+    /// duration and the curve.
+    ///
+    /// For the non-builder varient, see [`set_spring`].
+    ///
+    /// [`set_spring`]: #method.set_spring
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.set_spring(Some(spring));
+        self
+    }
+
+    /// Drive the animation with a [`Spring`], or with the duration and the curve for `None`.
+    pub fn set_spring(&mut self, spring: Option<Spring>) {
+        self.spring = spring;
+        self.spring_state = None;
+    }
+
     /// Builder-style method for specifying the layout flag.
     ///
     /// For the non-builder varient, see [`set_layout`].
@@ -126,13 +153,19 @@ impl<T: Interpolate> Animated<T> {
     }
 
     /// Returns the animation progress (between 0.0 and 1.0)
+    ///
+    /// Springs can overshoot, their progress can be outside of that range.
     pub fn progress(&self) -> f64 {
-        self.controller.fraction()
+        match (&self.spring_state, &self.spring) {
+            (Some(state), _) => state.position,
+            (None, Some(_)) => 1.0,
+            (None, None) => self.controller.fraction(),
+        }
     }
 
     /// Returns true if the animation is running.
     pub fn animating(&self) -> bool {
-        self.controller.animating()
+        self.spring_state.is_some() || self.controller.animating()
     }
 
     /// Set the new end value.
@@ -143,10 +176,18 @@ impl<T: Interpolate> Animated<T> {
         if value != self.end {
             self.start = self.current.clone();
             self.end = value;
+            if self.spring.is_some() && !reduced_motion() {
+                // keep the momentum of a running spring
+                let velocity = self.spring_state.map_or(0.0, |state| state.velocity);
+                self.spring_state = Some(SpringState::new(velocity));
+                ctx.request_anim_frame();
+                return;
+            }
+            self.spring_state = None;
             self.controller.reset();
             self.controller.start(ctx);
             if !self.controller.animating() {
-                // handle duration 0.0
+                // handle duration 0.0 and reduced motion
                 self.current = self.end.clone();
             }
         }
@@ -154,7 +195,8 @@ impl<T: Interpolate> Animated<T> {
 
     /// Set the new end value, curve and duration.
     ///
-    /// If the animation is currently running, it will start from the current value.
+    /// If the animation is currently running, it will start from the current value. This
+    /// stops using a [`Spring`].
     pub fn animate_with(
         &mut self,
         ctx: &mut impl RequestCtx,
@@ -162,6 +204,7 @@ impl<T: Interpolate> Animated<T> {
         duration: f64,
         curve: AnimationCurve,
     ) {
+        self.set_spring(None);
         self.set_curve(curve);
         self.set_duration(duration);
         self.animate(ctx, value);
@@ -170,6 +213,7 @@ impl<T: Interpolate> Animated<T> {
     /// Stop the animation and set the value.
     pub fn jump_to_value(&mut self, value: T) {
         self.controller.reset();
+        self.spring_state = None;
         self.start = value.clone();
         self.end = value.clone();
         self.current = value;
@@ -178,6 +222,7 @@ impl<T: Interpolate> Animated<T> {
     /// Stop the animation at the current value
     pub fn end_animation(&mut self) {
         self.controller.reset();
+        self.spring_state = None;
         self.start = self.current.clone();
         self.end = self.current.clone();
     }
@@ -192,6 +237,23 @@ impl<T: Interpolate> Animated<T> {
     ///
     /// Note: This must be called to drive the animation.
     pub fn update(&mut self, ctx: &mut impl RequestCtx, nanos: u64) {
+        if let (Some(state), Some(spring)) = (&mut self.spring_state, &self.spring) {
+            state.step(spring, (nanos as f64) * 0.000000001);
+            if state.at_rest() || reduced_motion() {
+                self.spring_state = None;
+                self.current = self.end.clone();
+            } else {
+                self.current = self.start.interpolate(&self.end, state.position);
+                ctx.request_anim_frame();
+            }
+            if self.controller.requests_layout() {
+                ctx.request_layout();
+            } else {
+                ctx.request_paint();
+            }
+            return;
+        }
+
         self.controller.update(ctx, nanos);
         if self.animating() {
             let fraction = self.controller.fraction();
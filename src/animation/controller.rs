@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{AnimationDirection, AnimationStatus};
+use super::{reduced_motion, AnimationDirection, AnimationStatus};
 
 use crate::RequestCtx;
 
@@ -98,6 +98,11 @@ impl AnimationController {
         self.layout = layout;
     }
 
+    /// Returns true if updates request a layout instead of a paint.
+    pub(crate) fn requests_layout(&self) -> bool {
+        self.layout
+    }
+
     /// Builder-style method for specifying the duration.
     ///
     /// For the non-builder varient, see [`set_duration`].
@@ -175,7 +180,7 @@ impl AnimationController {
             Enlisting | Running | Repeating => {
                 self.since_start += (nanos as f64) * 0.000000001;
 
-                if self.duration <= 0.0 {
+                if self.duration <= 0.0 || reduced_motion() {
                     let end_fraction = self.direction.end_fraction(true);
                     self.fraction = end_fraction;
                     self.status = Retiring;
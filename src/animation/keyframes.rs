@@ -0,0 +1,261 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Deref;
+
+use crate::animation::{AnimationController, AnimationCurve, AnimationDirection, Interpolate};
+
+use crate::RequestCtx;
+
+/// A value at a point of a [`Keyframes`] timeline.
+#[derive(Debug)]
+struct Keyframe<T> {
+    /// The position on the timeline, between 0.0 and 1.0.
+    at: f64,
+    value: T,
+    /// The curve of the segment ending at this keyframe.
+    curve: AnimationCurve,
+}
+
+/// A timeline of values, with an [`AnimationCurve`] for each segment between two of them.
+///
+/// ```
+/// # use druid_widget_nursery::animation::{AnimationCurve, Keyframes};
+/// let pulse = Keyframes::new(0.5)
+///     .keyframe(0.2, 1.0, AnimationCurve::LINEAR)
+///     .keyframe(1.0, 0.5, AnimationCurve::EASE_IN_OUT);
+/// assert_eq!(pulse.value_at(0.1), 0.75);
+/// ```
+#[derive(Debug)]
+pub struct Keyframes<T> {
+    /// Sorted by position.
+    frames: Vec<Keyframe<T>>,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// Create a timeline starting with `value`.
+    pub fn new(value: T) -> Self {
+        Keyframes {
+            frames: vec![Keyframe {
+                at: 0.0,
+                value,
+                curve: AnimationCurve::LINEAR,
+            }],
+        }
+    }
+
+    /// Builder-style method for adding a keyframe.
+    ///
+    /// For the non-builder varient, see [`add_keyframe`].
+    ///
+    /// [`add_keyframe`]: #method.add_keyframe
+    pub fn keyframe(mut self, at: f64, value: T, curve: AnimationCurve) -> Self {
+        self.add_keyframe(at, value, curve);
+        self
+    }
+
+    /// Add `value` at position `at` (between 0.0 and 1.0) of the timeline, reached from the
+    /// previous keyframe with `curve`.
+    ///
+    /// A keyframe at the same position as an existing one replaces it.
+    pub fn add_keyframe(&mut self, at: f64, value: T, curve: AnimationCurve) {
+        let at = at.max(0.0).min(1.0);
+        let frame = Keyframe { at, value, curve };
+        match self
+            .frames
+            .binary_search_by(|frame| frame.at.partial_cmp(&at).unwrap())
+        {
+            Ok(index) => self.frames[index] = frame,
+            Err(index) => self.frames.insert(index, frame),
+        }
+    }
+
+    /// Returns the value at position `fraction` (between 0.0 and 1.0) of the timeline.
+    ///
+    /// Before the first and after the last keyframe, the value of that keyframe is returned.
+    pub fn value_at(&self, fraction: f64) -> T {
+        let next = self.frames.iter().position(|frame| frame.at > fraction);
+        match next {
+            Some(0) => self.frames[0].value.clone(),
+            Some(index) => {
+                let from = &self.frames[index - 1];
+                let to = &self.frames[index];
+                let t = (fraction - from.at) / (to.at - from.at);
+                from.value.interpolate(&to.value, to.curve.translate(t))
+            }
+            None => self.frames.last().unwrap().value.clone(),
+        }
+    }
+}
+
+/// How a [`KeyframeAnimation`] repeats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyframeRepeat {
+    /// Run through the keyframes once, and stop at the last one.
+    #[default]
+    Once,
+    /// Start over from the first keyframe until stopped.
+    Loop,
+    /// Run forward and backward through the keyframes until stopped.
+    PingPong,
+}
+
+/// Runs through [`Keyframes`], for loading indicators and attention effects.
+///
+/// Like [`Animated`], this has to be driven by calling [`update`] on every animation frame.
+///
+/// ```
+/// # use druid_widget_nursery::animation::{AnimationCurve, KeyframeAnimation, KeyframeRepeat, Keyframes};
+/// let shake = KeyframeAnimation::new(
+///     Keyframes::new(0.0)
+///         .keyframe(0.25, -8.0, AnimationCurve::EASE_OUT)
+///         .keyframe(0.75, 8.0, AnimationCurve::EASE_IN_OUT)
+///         .keyframe(1.0, 0.0, AnimationCurve::EASE_IN),
+/// )
+/// .duration(0.4);
+///
+/// let spinner = KeyframeAnimation::new(Keyframes::new(0.0).keyframe(1.0, 360.0, AnimationCurve::LINEAR))
+///     .duration(1.2)
+///     .repeat(KeyframeRepeat::Loop);
+/// ```
+///
+/// [`Animated`]: crate::animation::Animated
+/// [`update`]: #method.update
+pub struct KeyframeAnimation<T> {
+    keyframes: Keyframes<T>,
+    controller: AnimationController,
+    current: T,
+}
+
+impl<T: Interpolate> KeyframeAnimation<T> {
+    /// Create a stopped animation with a duration of one second, at the first keyframe.
+    pub fn new(keyframes: Keyframes<T>) -> Self {
+        let current = keyframes.value_at(0.0);
+        KeyframeAnimation {
+            keyframes,
+            controller: AnimationController::new(),
+            current,
+        }
+    }
+
+    /// Builder-style method for specifying the duration.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of one run through the keyframes in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.controller.set_duration(duration);
+    }
+
+    /// Builder-style method for specifying the [`KeyframeRepeat`] mode.
+    ///
+    /// For the non-builder varient, see [`set_repeat`].
+    ///
+    /// [`set_repeat`]: #method.set_repeat
+    pub fn repeat(mut self, repeat: KeyframeRepeat) -> Self {
+        self.set_repeat(repeat);
+        self
+    }
+
+    /// Set the [`KeyframeRepeat`] mode.
+    pub fn set_repeat(&mut self, repeat: KeyframeRepeat) {
+        let (direction, limit) = match repeat {
+            KeyframeRepeat::Once => (AnimationDirection::Forward, Some(1)),
+            KeyframeRepeat::Loop => (AnimationDirection::Forward, None),
+            KeyframeRepeat::PingPong => (AnimationDirection::Alternate, None),
+        };
+        self.controller.set_direction(direction);
+        self.controller.set_repeat_limit(limit);
+    }
+
+    /// Builder-style method for specifying the layout flag.
+    ///
+    /// For the non-builder varient, see [`set_layout`].
+    ///
+    /// [`set_layout`]: #method.set_layout
+    pub fn layout(mut self, layout: bool) -> Self {
+        self.set_layout(layout);
+        self
+    }
+
+    /// Request widget layout after each update (instead of a paint request).
+    pub fn set_layout(&mut self, layout: bool) {
+        self.controller.set_layout(layout);
+    }
+
+    /// Returns the [`Keyframes`].
+    pub fn keyframes(&self) -> &Keyframes<T> {
+        &self.keyframes
+    }
+
+    /// Replace the [`Keyframes`], keeping the animation running.
+    pub fn set_keyframes(&mut self, keyframes: Keyframes<T>) {
+        self.keyframes = keyframes;
+        self.current = self.keyframes.value_at(self.controller.fraction());
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> T {
+        self.current.clone()
+    }
+
+    /// Returns the position on the timeline (between 0.0 and 1.0).
+    pub fn progress(&self) -> f64 {
+        self.controller.fraction()
+    }
+
+    /// Returns true if the animation is running.
+    pub fn animating(&self) -> bool {
+        self.controller.animating()
+    }
+
+    /// Start the animation from the first keyframe.
+    pub fn start(&mut self, ctx: &mut impl RequestCtx) {
+        self.controller.reset();
+        self.controller.start(ctx);
+        self.current = self.keyframes.value_at(self.controller.fraction());
+    }
+
+    /// Stop the animation and go back to the first keyframe.
+    pub fn stop(&mut self) {
+        self.controller.reset();
+        self.current = self.keyframes.value_at(0.0);
+    }
+
+    /// Update animation state.
+    ///
+    /// This method should always be called in
+    /// [`Event::AnimFrame`](druid::Event::AnimFrame). If the animation isn't finished, an
+    /// additional animation-frame is requested.
+    ///
+    /// Note: This must be called to drive the animation.
+    pub fn update(&mut self, ctx: &mut impl RequestCtx, nanos: u64) {
+        self.controller.update(ctx, nanos);
+        self.current = self.keyframes.value_at(self.controller.fraction());
+    }
+}
+
+impl<T> Deref for KeyframeAnimation<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.current
+    }
+}
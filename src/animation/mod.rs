@@ -1,27 +1,38 @@
 //! Druid animation library
 
+mod animated_size;
+mod animated_switcher;
 mod animated_value;
 mod animator;
 mod context;
 mod controller;
 mod curve;
 mod interpolate;
+mod keyframes;
+mod spring;
 mod state;
 mod storage;
 #[cfg(test)]
 mod test;
+mod transition;
 
+pub use animated_size::AnimatedSize;
+pub use animated_switcher::{AnimatedSwitcher, SwitchTransition};
 pub use animated_value::Animated;
 pub use animator::Animator;
 pub use context::AnimationCtx;
 pub use controller::AnimationController;
 pub use curve::{AnimationCurve, CubicBezierAnimationCurve};
 pub use interpolate::Interpolate;
+pub use keyframes::{KeyframeAnimation, KeyframeRepeat, Keyframes};
+pub use spring::Spring;
 pub use storage::AnimationId;
+pub use transition::Transition;
 
 use druid::Data;
 use std::collections::{HashMap, VecDeque};
 use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use crate::animation::state::AnimationHandle;
@@ -33,6 +44,24 @@ type DelayNanos = Nanos; // delay after ready
 type StartNanos = Nanos; // start time
 type Animations = AnimationStorage<AnimationState>;
 
+static REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Collapse all animations driven by an [`AnimationController`] or [`Animated`] to instant
+/// changes, including the ones of the animated widgets in this crate.
+///
+/// This is meant for users who prefer reduced motion, and for tests that need deterministic
+/// values. Running animations end with their next update.
+pub fn set_reduced_motion(reduced: bool) {
+    REDUCED_MOTION.store(reduced, Ordering::Relaxed);
+}
+
+/// Returns true if animations are collapsed to instant changes.
+///
+/// See [`set_reduced_motion`].
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// The status of a currently running animation
 pub enum AnimationStatus {
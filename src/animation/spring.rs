@@ -0,0 +1,111 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The longest step of the simulation, in seconds. Longer frames are split.
+const MAX_STEP: f64 = 0.001;
+/// The spring is at rest when it is closer than this to the target...
+const REST_DISTANCE: f64 = 0.001;
+/// ...and slower than this.
+const REST_VELOCITY: f64 = 0.01;
+
+/// A damped spring, driving an animation instead of a duration and an [`AnimationCurve`].
+///
+/// The animation takes as long as the spring needs to come to rest. Springs with little damping
+/// overshoot their target and oscillate around it, critically damped springs don't.
+///
+/// ```
+/// # use druid_widget_nursery::animation::{Animated, Spring};
+/// let animated = Animated::new(0.0).spring(Spring::WOBBLY);
+/// let custom = Animated::new(0.0).spring(Spring::new(300.0, 20.0).with_mass(2.0));
+/// ```
+///
+/// [`AnimationCurve`]: crate::animation::AnimationCurve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    pub stiffness: f64,
+    pub damping: f64,
+    pub mass: f64,
+}
+
+impl Spring {
+    /// A quick spring with barely any overshoot.
+    pub const DEFAULT: Spring = Spring::new(170.0, 26.0);
+    /// A slow spring with a slight overshoot.
+    pub const GENTLE: Spring = Spring::new(120.0, 14.0);
+    /// A spring that overshoots and bounces back a few times.
+    pub const WOBBLY: Spring = Spring::new(180.0, 12.0);
+    /// A fast spring with a small overshoot.
+    pub const STIFF: Spring = Spring::new(210.0, 20.0);
+    /// A heavily damped, slow spring.
+    pub const SLOW: Spring = Spring::new(280.0, 60.0);
+
+    /// Create a spring with a mass of one.
+    pub const fn new(stiffness: f64, damping: f64) -> Self {
+        Spring {
+            stiffness,
+            damping,
+            mass: 1.0,
+        }
+    }
+
+    /// Create a spring with the smallest damping that doesn't overshoot.
+    pub fn critically_damped(stiffness: f64) -> Self {
+        Spring::new(stiffness, 2.0 * stiffness.sqrt())
+    }
+
+    /// Builder-style method for specifying the mass.
+    pub const fn with_mass(mut self, mass: f64) -> Self {
+        self.mass = mass;
+        self
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Spring::DEFAULT
+    }
+}
+
+/// A spring moving from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpringState {
+    pub(crate) position: f64,
+    pub(crate) velocity: f64,
+}
+
+impl SpringState {
+    /// Start at 0 with `velocity`.
+    pub(crate) fn new(velocity: f64) -> Self {
+        SpringState {
+            position: 0.0,
+            velocity,
+        }
+    }
+
+    /// Advance the simulation by `seconds`.
+    pub(crate) fn step(&mut self, spring: &Spring, seconds: f64) {
+        let steps = (seconds / MAX_STEP).ceil().max(1.0);
+        let dt = seconds / steps;
+        for _ in 0..steps as usize {
+            let force = -spring.stiffness * (self.position - 1.0) - spring.damping * self.velocity;
+            self.velocity += force / spring.mass * dt;
+            self.position += self.velocity * dt;
+        }
+    }
+
+    /// Returns `true` if the spring came to rest at 1.
+    pub(crate) fn at_rest(&self) -> bool {
+        (1.0 - self.position).abs() < REST_DISTANCE && self.velocity.abs() < REST_VELOCITY
+    }
+}
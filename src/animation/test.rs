@@ -65,3 +65,44 @@ fn test_animator() {
 // Events
 // Loops
 // Removal
+
+#[test]
+fn test_spring_comes_to_rest() {
+    for spring in [
+        Spring::DEFAULT,
+        Spring::WOBBLY,
+        Spring::critically_damped(200.0),
+    ] {
+        let mut state = spring::SpringState::new(0.0);
+        let mut frames = 0;
+        while !state.at_rest() {
+            state.step(&spring, 1.0 / 60.0);
+            frames += 1;
+            assert!(frames < 600, "{:?} didn't come to rest", spring);
+        }
+    }
+}
+
+#[test]
+fn test_critically_damped_spring_does_not_overshoot() {
+    let spring = Spring::critically_damped(200.0);
+    let mut state = spring::SpringState::new(0.0);
+    while !state.at_rest() {
+        state.step(&spring, 1.0 / 60.0);
+        assert!(state.position <= 1.0 + 1e-3);
+    }
+}
+
+#[test]
+fn test_keyframes_value_at() {
+    let keyframes = Keyframes::new(0.0)
+        .keyframe(1.0, 10.0, AnimationCurve::LINEAR)
+        .keyframe(0.5, 20.0, AnimationCurve::LINEAR);
+
+    assert_eq!(keyframes.value_at(0.0), 0.0);
+    assert_eq!(keyframes.value_at(0.25), 10.0);
+    assert_eq!(keyframes.value_at(0.5), 20.0);
+    assert_eq!(keyframes.value_at(0.75), 15.0);
+    assert_eq!(keyframes.value_at(1.0), 10.0);
+    assert_eq!(keyframes.value_at(2.0), 10.0);
+}
@@ -0,0 +1,182 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::prelude::*;
+use druid::{Data, Point, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, Interpolate, Spring};
+
+/// A widget animating the data of its child from the old to the new value, whenever the data
+/// changes.
+///
+/// The child always sees the interpolated value. If the child changes the data itself, for
+/// example because the user dragged a slider, the change is applied immediately.
+///
+/// ```
+/// # use druid::widget::Painter;
+/// # use druid::{Color, RenderContext, Widget};
+/// # use druid_widget_nursery::animation::{AnimationCurve, Transition};
+/// fn swatch() -> impl Widget<Color> {
+///     let painter = Painter::new(|ctx, color: &Color, _| {
+///         let rect = ctx.size().to_rect();
+///         ctx.fill(rect, color);
+///     });
+///     Transition::new(painter)
+///         .duration(0.3)
+///         .curve(AnimationCurve::EASE_OUT)
+/// }
+/// ```
+pub struct Transition<T, W> {
+    inner: WidgetPod<T, W>,
+    /// `None` until the widget is added, as we don't know the data before.
+    value: Option<Animated<T>>,
+    duration: f64,
+    curve: Option<AnimationCurve>,
+    spring: Option<Spring>,
+}
+
+impl<T: Data + Interpolate, W: Widget<T>> Transition<T, W> {
+    /// Create a transition with a duration of 0.25 seconds and a linear curve.
+    pub fn new(inner: W) -> Self {
+        Transition {
+            inner: WidgetPod::new(inner),
+            value: None,
+            duration: 0.25,
+            curve: None,
+            spring: None,
+        }
+    }
+
+    /// Builder-style method for specifying the duration.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the transitions in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.duration = duration;
+        if let Some(value) = &mut self.value {
+            value.set_duration(duration);
+        }
+    }
+
+    /// Builder-style method for specifying the [`AnimationCurve`].
+    ///
+    /// For the non-builder varient, see [`set_curve`].
+    ///
+    /// [`set_curve`]: #method.set_curve
+    pub fn curve(mut self, curve: AnimationCurve) -> Self {
+        self.set_curve(curve);
+        self
+    }
+
+    /// Set the [`AnimationCurve`].
+    pub fn set_curve(&mut self, curve: AnimationCurve) {
+        match &mut self.value {
+            Some(value) => value.set_curve(curve),
+            None => self.curve = Some(curve),
+        }
+    }
+
+    /// Builder-style method for driving the animation with a [`Spring`].
+    ///
+    /// For the non-builder varient, see [`set_spring`].
+    ///
+    /// [`set_spring`]: #method.set_spring
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.set_spring(Some(spring));
+        self
+    }
+
+    /// Drive the animation with a [`Spring`], or with the duration and the curve for `None`.
+    pub fn set_spring(&mut self, spring: Option<Spring>) {
+        self.spring = spring;
+        if let Some(value) = &mut self.value {
+            value.set_spring(spring);
+        }
+    }
+
+    /// Returns true if a transition is running.
+    pub fn animating(&self) -> bool {
+        self.value.as_ref().map_or(false, Animated::animating)
+    }
+
+    /// The value the child sees.
+    fn shown(&self, data: &T) -> T {
+        match &self.value {
+            Some(value) => value.get(),
+            None => data.clone(),
+        }
+    }
+}
+
+impl<T: Data + Interpolate, W: Widget<T>> Widget<T> for Transition<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let (Event::AnimFrame(nanos), Some(value)) = (event, &mut self.value) {
+            if value.animating() {
+                value.update(ctx, *nanos);
+                ctx.request_update();
+            }
+        }
+
+        let mut shown = self.shown(data);
+        self.inner.event(ctx, event, &mut shown, env);
+        if let Some(value) = &mut self.value {
+            if !shown.same(&value.get()) {
+                // changes made by the child are not animated
+                value.jump_to_value(shown.clone());
+                *data = shown;
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            let mut value = Animated::new(data.clone())
+                .duration(self.duration)
+                .curve(self.curve.take().unwrap_or_default());
+            value.set_spring(self.spring);
+            self.value = Some(value);
+        }
+        let shown = self.shown(data);
+        self.inner.lifecycle(ctx, event, &shown, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if let Some(value) = &mut self.value {
+            if !old_data.same(data) {
+                value.animate(ctx, data.clone());
+            }
+        }
+        let shown = self.shown(data);
+        self.inner.update(ctx, &shown, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let shown = self.shown(data);
+        let size = self.inner.layout(ctx, bc, &shown, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let shown = self.shown(data);
+        self.inner.paint(ctx, &shown, env);
+    }
+}
@@ -0,0 +1,467 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use druid::piet::{FontStyle, FontWeight, Text, TextAttribute, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, FontDescriptor, FontFamily, KeyOrValue, Point};
+
+const PADDING: f64 = 4.0;
+
+/// The colors of the codes 30-37 and 90-97, like xterm.
+const PALETTE: [Color; 16] = [
+    Color::rgb8(0x00, 0x00, 0x00),
+    Color::rgb8(0xcd, 0x00, 0x00),
+    Color::rgb8(0x00, 0xcd, 0x00),
+    Color::rgb8(0xcd, 0xcd, 0x00),
+    Color::rgb8(0x00, 0x00, 0xee),
+    Color::rgb8(0xcd, 0x00, 0xcd),
+    Color::rgb8(0x00, 0xcd, 0xcd),
+    Color::rgb8(0xe5, 0xe5, 0xe5),
+    Color::rgb8(0x7f, 0x7f, 0x7f),
+    Color::rgb8(0xff, 0x00, 0x00),
+    Color::rgb8(0x00, 0xff, 0x00),
+    Color::rgb8(0xff, 0xff, 0x00),
+    Color::rgb8(0x5c, 0x5c, 0xff),
+    Color::rgb8(0xff, 0x00, 0xff),
+    Color::rgb8(0x00, 0xff, 0xff),
+    Color::rgb8(0xff, 0xff, 0xff),
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Style {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Apply the parameters of a "select graphic rendition" sequence.
+    fn apply(&mut self, params: &str) {
+        let mut params = params
+            .split(';')
+            .map(|param| param.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                24 => self.underline = false,
+                30..=37 => self.foreground = Some(PALETTE[param as usize - 30]),
+                90..=97 => self.foreground = Some(PALETTE[param as usize - 90 + 8]),
+                40..=47 => self.background = Some(PALETTE[param as usize - 40]),
+                100..=107 => self.background = Some(PALETTE[param as usize - 100 + 8]),
+                39 => self.foreground = None,
+                49 => self.background = None,
+                38 | 48 => {
+                    let color = match params.next() {
+                        Some(5) => params.next().map(color_256),
+                        Some(2) => {
+                            let (r, g, b) = (params.next(), params.next(), params.next());
+                            Some(Color::rgb8(r.unwrap_or(0), g.unwrap_or(0), b.unwrap_or(0)))
+                        }
+                        _ => None,
+                    };
+                    if param == 38 {
+                        self.foreground = color;
+                    } else {
+                        self.background = color;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The color of an index of the 256 colors palette.
+fn color_256(index: u8) -> Color {
+    match index {
+        0..=15 => PALETTE[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            Color::rgb8(level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            Color::rgb8(gray, gray, gray)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Line {
+    text: String,
+    spans: Vec<(Range<usize>, Style)>,
+}
+
+impl Line {
+    fn push(&mut self, text: &str, style: &Style) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        let end = self.text.len();
+        if let Some((range, last)) = self.spans.last_mut() {
+            if *last == *style && range.end == start {
+                range.end = end;
+                return;
+            }
+        }
+        self.spans.push((start..end, style.clone()));
+    }
+}
+
+#[derive(Default)]
+struct Terminal {
+    lines: Vec<Line>,
+    max_lines: Option<usize>,
+    style: Style,
+    /// The start of an escape sequence cut at the end of the last text pushed.
+    pending: String,
+    /// Whether the next text replaces the last line, after a carriage return.
+    carriage_return: bool,
+    /// The number of characters of the longest line.
+    max_chars: usize,
+}
+
+impl Terminal {
+    fn current_line(&mut self) -> &mut Line {
+        if self.lines.is_empty() {
+            self.lines.push(Line::default());
+        }
+        if self.carriage_return {
+            self.carriage_return = false;
+            *self.lines.last_mut().unwrap() = Line::default();
+        }
+        self.lines.last_mut().unwrap()
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let style = self.style.clone();
+        let line = self.current_line();
+        line.push(text, &style);
+        let chars = line.text.chars().count();
+        self.max_chars = self.max_chars.max(chars);
+    }
+
+    fn new_line(&mut self) {
+        // a carriage return before a line feed keeps the line
+        self.carriage_return = false;
+        self.current_line();
+        self.lines.push(Line::default());
+        if let Some(max_lines) = self.max_lines {
+            if self.lines.len() > max_lines {
+                let excess = self.lines.len() - max_lines;
+                self.lines.drain(..excess);
+            }
+        }
+    }
+
+    fn push_str(&mut self, input: &str) {
+        let input = std::mem::take(&mut self.pending) + input;
+        let mut rest = input.as_str();
+        while let Some(position) = rest.find(|c: char| c == '\x1b' || c == '\n' || c == '\r') {
+            self.push_text(&rest[..position]);
+            rest = &rest[position..];
+            if let Some(after) = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+            {
+                self.new_line();
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('\r') {
+                self.carriage_return = true;
+                rest = after;
+            } else {
+                match escape_length(rest) {
+                    Some(length) => {
+                        if let Some(params) = rest[..length]
+                            .strip_prefix("\x1b[")
+                            .and_then(|sequence| sequence.strip_suffix('m'))
+                        {
+                            self.style.apply(params);
+                        }
+                        rest = &rest[length..];
+                    }
+                    None => {
+                        // the end of the sequence is in the next text
+                        self.pending = rest.to_string();
+                        return;
+                    }
+                }
+            }
+        }
+        self.push_text(rest);
+    }
+}
+
+/// The length of the escape sequence at the start of `text`, or `None` if it is cut.
+fn escape_length(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    match bytes.get(1)? {
+        // a control sequence ends with a byte from '@' to '~'
+        b'[' => bytes[2..]
+            .iter()
+            .position(|byte| (0x40..=0x7e).contains(byte))
+            .map(|position| position + 3),
+        // an operating system command ends with BEL or ESC \
+        b']' => {
+            let end = bytes[2..].iter().enumerate().find(|(index, byte)| {
+                **byte == 0x07 || (**byte == b'\\' && bytes[index + 1] == 0x1b)
+            })?;
+            Some(end.0 + 3)
+        }
+        _ => Some(1 + text[1..].chars().next()?.len_utf8()),
+    }
+}
+
+/// Text with ANSI escape codes for an [`AnsiText`], like the output of a compiler.
+///
+/// The text is parsed as it is pushed, and can be pushed in pieces cutting lines and escape
+/// sequences anywhere. The lines are shared by the clones of the buffer, like a [`LogBuffer`].
+///
+/// ```
+/// # use druid_widget_nursery::AnsiBuffer;
+/// let mut output = AnsiBuffer::new();
+/// output.push_str("\x1b[1;32m   Compiling\x1b[0m nursery\n\x1b[1;3");
+/// output.push_str("1merror\x1b[0m: oops\n");
+/// assert_eq!(output.plain_text(), "   Compiling nursery\nerror: oops\n");
+/// ```
+///
+/// [`LogBuffer`]: crate::LogBuffer
+#[derive(Clone)]
+pub struct AnsiBuffer {
+    terminal: Rc<RefCell<Terminal>>,
+    version: u64,
+}
+
+impl AnsiBuffer {
+    pub fn new() -> Self {
+        AnsiBuffer {
+            terminal: Rc::new(RefCell::new(Terminal::default())),
+            version: 0,
+        }
+    }
+
+    /// Builder-style method for keeping only the last `max_lines` lines.
+    pub fn with_max_lines(self, max_lines: usize) -> Self {
+        self.terminal.borrow_mut().max_lines = Some(max_lines.max(1));
+        self
+    }
+
+    /// Append text, with escape codes.
+    pub fn push_str(&mut self, text: &str) {
+        self.terminal.borrow_mut().push_str(text);
+        self.version += 1;
+    }
+
+    /// Remove all the text, and reset the style.
+    pub fn clear(&mut self) {
+        let mut terminal = self.terminal.borrow_mut();
+        let max_lines = terminal.max_lines;
+        *terminal = Terminal {
+            max_lines,
+            ..Terminal::default()
+        };
+        self.version += 1;
+    }
+
+    /// The number of lines, including the last one if it is empty.
+    pub fn line_count(&self) -> usize {
+        self.terminal.borrow().lines.len()
+    }
+
+    /// The text without the escape codes.
+    pub fn plain_text(&self) -> String {
+        let terminal = self.terminal.borrow();
+        let lines: Vec<_> = terminal
+            .lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect();
+        lines.join("\n")
+    }
+}
+
+impl Default for AnsiBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Data for AnsiBuffer {
+    fn same(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.terminal, &other.terminal) && self.version == other.version
+    }
+}
+
+/// A widget showing an [`AnsiBuffer`] like a terminal, with the colors, bold, italic and
+/// underline of the escape codes.
+///
+/// Other escape codes are left out, and a carriage return starts the line over, like progress
+/// bars do. The widget takes the size of all its text, and only lays out and paints the visible
+/// lines, so put it in a `Scroll`.
+///
+/// ```
+/// # use druid::widget::Scroll;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::{AnsiBuffer, AnsiText};
+/// fn build_output() -> impl Widget<AnsiBuffer> {
+///     Scroll::new(AnsiText::new())
+/// }
+/// ```
+pub struct AnsiText {
+    font: KeyOrValue<FontDescriptor>,
+    line_height: f64,
+}
+
+impl AnsiText {
+    pub fn new() -> Self {
+        AnsiText {
+            font: FontDescriptor::new(FontFamily::MONOSPACE).into(),
+            line_height: 0.0,
+        }
+    }
+
+    /// Builder-style method for specifying the font, a monospace font by default.
+    ///
+    /// For the non-builder varient, see [`set_font`].
+    ///
+    /// [`set_font`]: #method.set_font
+    pub fn with_font(mut self, font: impl Into<KeyOrValue<FontDescriptor>>) -> Self {
+        self.set_font(font);
+        self
+    }
+
+    /// Set the font of the text.
+    pub fn set_font(&mut self, font: impl Into<KeyOrValue<FontDescriptor>>) {
+        self.font = font.into();
+    }
+}
+
+impl Default for AnsiText {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<AnsiBuffer> for AnsiText {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut AnsiBuffer, _env: &Env) {}
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &AnsiBuffer,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &AnsiBuffer,
+        data: &AnsiBuffer,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) || ctx.env_changed() {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &AnsiBuffer,
+        env: &Env,
+    ) -> Size {
+        let font = self.font.resolve(env);
+        let sample = ctx
+            .text()
+            .new_text_layout("M")
+            .font(font.family, font.size)
+            .build()
+            .unwrap();
+        self.line_height = sample.size().height;
+
+        let terminal = data.terminal.borrow();
+        let width = terminal.max_chars as f64 * sample.size().width;
+        let height = terminal.lines.len() as f64 * self.line_height;
+        bc.constrain(Size::new(width + 2.0 * PADDING, height + 2.0 * PADDING))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &AnsiBuffer, env: &Env) {
+        if self.line_height <= 0.0 {
+            return;
+        }
+        let font = self.font.resolve(env);
+        let text_color = env.get(theme::TEXT_COLOR);
+        let visible = ctx.region().bounding_box();
+        let terminal = data.terminal.borrow();
+
+        let first = ((visible.y0 - PADDING) / self.line_height).floor().max(0.0) as usize;
+        let last = ((visible.y1 - PADDING) / self.line_height).ceil().max(0.0) as usize;
+        for (row, line) in terminal.lines.iter().enumerate().take(last + 1).skip(first) {
+            if line.text.is_empty() {
+                continue;
+            }
+            let mut builder = ctx
+                .text()
+                .new_text_layout(line.text.clone())
+                .font(font.family.clone(), font.size)
+                .text_color(text_color);
+            for (range, style) in &line.spans {
+                if let Some(color) = &style.foreground {
+                    builder =
+                        builder.range_attribute(range.clone(), TextAttribute::TextColor(*color));
+                }
+                if style.bold {
+                    builder = builder
+                        .range_attribute(range.clone(), TextAttribute::Weight(FontWeight::BOLD));
+                }
+                if style.italic {
+                    builder = builder
+                        .range_attribute(range.clone(), TextAttribute::Style(FontStyle::Italic));
+                }
+                if style.underline {
+                    builder =
+                        builder.range_attribute(range.clone(), TextAttribute::Underline(true));
+                }
+            }
+            let layout = builder.build().unwrap();
+            let origin = Point::new(PADDING, PADDING + row as f64 * self.line_height);
+
+            for (range, style) in &line.spans {
+                if let Some(color) = &style.background {
+                    for rect in layout.rects_for_range(range.clone()) {
+                        ctx.fill(rect + origin.to_vec2(), color);
+                    }
+                }
+            }
+            ctx.draw_text(&layout, origin);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use druid::kurbo::{Circle, RoundedRect};
+use druid::piet::{InterpolationMode, PietImage};
+use druid::widget::prelude::*;
+use druid::{theme, Color, ImageBuf, KeyOrValue, Point, Rect, Selector, TextLayout, Vec2};
+
+use crate::{AnyCtx, CommandCtx};
+
+/// Sent by the thread loading an image, with the source it was loaded from.
+const IMAGE_LOADED: Selector<(String, Option<ImageBuf>)> =
+    Selector::new("druid-widget-nursery.avatar.image-loaded");
+
+/// The colors behind the initials, picked by the name.
+const PALETTE: [Color; 8] = [
+    Color::rgb8(0xc6, 0x28, 0x28),
+    Color::rgb8(0xad, 0x14, 0x57),
+    Color::rgb8(0x6a, 0x1b, 0x9a),
+    Color::rgb8(0x28, 0x35, 0x93),
+    Color::rgb8(0x02, 0x77, 0xbd),
+    Color::rgb8(0x00, 0x69, 0x5c),
+    Color::rgb8(0x2e, 0x7d, 0x32),
+    Color::rgb8(0xef, 0x6c, 0x00),
+];
+
+type NameFn<T> = dyn Fn(&T) -> String;
+type ImageFn<T> = dyn Fn(&T) -> Option<ImageBuf>;
+type SourceFn<T> = dyn Fn(&T) -> Option<String>;
+type LoadFn = dyn Fn(&str) -> Option<ImageBuf> + Send + Sync;
+type StatusFn<T> = dyn Fn(&T) -> Option<Color>;
+
+/// The outline of an [`Avatar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvatarShape {
+    Circle,
+    /// A square with rounded corners.
+    Rounded,
+}
+
+enum ImageSource<T> {
+    None,
+    Data(Box<ImageFn<T>>),
+    Loaded {
+        source: Box<SourceFn<T>>,
+        load: Arc<LoadFn>,
+    },
+}
+
+/// A picture of a person, showing the initials of their name on a colored background until there
+/// is an image.
+///
+/// The image can be part of the data, or loaded on a thread from a source like a path, with
+/// [`with_image_loader`]. A presence indicator, like a green dot for people online, can be shown
+/// on the bottom right corner.
+///
+/// ```
+/// # use druid::{Color, Data, ImageBuf, Lens, Widget};
+/// # use druid_widget_nursery::{Avatar, AvatarShape};
+/// # fn decode(path: &str) -> Option<ImageBuf> {
+/// #     None
+/// # }
+/// #[derive(Clone, Data, Lens)]
+/// struct User {
+///     name: String,
+///     picture: Option<String>,
+///     online: bool,
+/// }
+///
+/// fn avatar() -> impl Widget<User> {
+///     Avatar::new(|user: &User| user.name.clone())
+///         .with_image_loader(
+///             |user: &User| user.picture.clone(),
+///             decode,
+///         )
+///         .with_status(|user: &User| user.online.then(|| Color::rgb8(0x43, 0xa0, 0x47)))
+///         .with_shape(AvatarShape::Rounded)
+///         .with_size(48.0)
+/// }
+/// ```
+///
+/// [`with_image_loader`]: #method.with_image_loader
+pub struct Avatar<T> {
+    name: Box<NameFn<T>>,
+    image_source: ImageSource<T>,
+    status: Option<Box<StatusFn<T>>>,
+    shape: AvatarShape,
+    size: KeyOrValue<f64>,
+    initials: TextLayout<String>,
+    /// The name the initials and the color are made from.
+    current_name: Option<String>,
+    background: Color,
+    /// The source of the image being loaded or shown.
+    source: Option<String>,
+    image: Option<ImageBuf>,
+    /// The image, prepared for painting.
+    paint_image: Option<PietImage>,
+}
+
+impl<T: Data> Avatar<T> {
+    /// Create an avatar for the person named by `name`.
+    pub fn new(name: impl Fn(&T) -> String + 'static) -> Self {
+        Avatar {
+            name: Box::new(name),
+            image_source: ImageSource::None,
+            status: None,
+            shape: AvatarShape::Circle,
+            size: KeyOrValue::Concrete(32.0),
+            initials: TextLayout::new(),
+            current_name: None,
+            background: PALETTE[0],
+            source: None,
+            image: None,
+            paint_image: None,
+        }
+    }
+
+    /// Builder-style method for showing the image returned by `image` instead of the initials.
+    pub fn with_image(mut self, image: impl Fn(&T) -> Option<ImageBuf> + 'static) -> Self {
+        self.image_source = ImageSource::Data(Box::new(image));
+        self
+    }
+
+    /// Builder-style method for loading the image on a thread.
+    ///
+    /// `source` returns where to load the image from, like a path, and `load` loads it, without
+    /// blocking the UI. The initials are shown while the image is loading, and when `load` returns
+    /// `None`.
+    pub fn with_image_loader(
+        mut self,
+        source: impl Fn(&T) -> Option<String> + 'static,
+        load: impl Fn(&str) -> Option<ImageBuf> + Send + Sync + 'static,
+    ) -> Self {
+        self.image_source = ImageSource::Loaded {
+            source: Box::new(source),
+            load: Arc::new(load),
+        };
+        self
+    }
+
+    /// Builder-style method for showing a presence indicator with the color returned by `status`,
+    /// or none for `None`.
+    pub fn with_status(mut self, status: impl Fn(&T) -> Option<Color> + 'static) -> Self {
+        self.status = Some(Box::new(status));
+        self
+    }
+
+    /// Builder-style method for specifying the [`AvatarShape`].
+    ///
+    /// For the non-builder varient, see [`set_shape`].
+    ///
+    /// [`set_shape`]: #method.set_shape
+    pub fn with_shape(mut self, shape: AvatarShape) -> Self {
+        self.set_shape(shape);
+        self
+    }
+
+    /// Set the [`AvatarShape`], a circle by default.
+    pub fn set_shape(&mut self, shape: AvatarShape) {
+        self.shape = shape;
+    }
+
+    /// Builder-style method for specifying the width and the height.
+    ///
+    /// For the non-builder varient, see [`set_size`].
+    ///
+    /// [`set_size`]: #method.set_size
+    pub fn with_size(mut self, size: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Set the width and the height, 32 by default.
+    pub fn set_size(&mut self, size: impl Into<KeyOrValue<f64>>) {
+        self.size = size.into();
+    }
+
+    /// Update the initials and the color for the name, returning whether they changed.
+    fn update_name(&mut self, data: &T) -> bool {
+        let name = (self.name)(data);
+        if self.current_name.as_ref() == Some(&name) {
+            return false;
+        }
+        let initials: String = name
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .take(2)
+            .flat_map(char::to_uppercase)
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        self.background = PALETTE[(hasher.finish() % PALETTE.len() as u64) as usize];
+        self.initials.set_text(initials);
+        self.current_name = Some(name);
+        true
+    }
+
+    /// Update the image, starting to load it when its source changed. Returns whether the image
+    /// changed.
+    fn update_image(&mut self, ctx: &mut (impl AnyCtx + CommandCtx), data: &T) -> bool {
+        match &self.image_source {
+            ImageSource::None => false,
+            ImageSource::Data(image) => {
+                let image = image(data);
+                if self.image.same(&image) {
+                    return false;
+                }
+                self.set_image(image);
+                true
+            }
+            ImageSource::Loaded { source, load } => {
+                let source = source(data);
+                if source == self.source {
+                    return false;
+                }
+                self.source = source.clone();
+                if let Some(source) = source {
+                    let load = load.clone();
+                    let sink = ctx.get_external_handle();
+                    let target = ctx.widget_id();
+                    std::thread::spawn(move || {
+                        let image = load(&source);
+                        // the avatar may be gone, there is nothing to do then
+                        let _ = sink.submit_command(IMAGE_LOADED, (source, image), target);
+                    });
+                }
+                // show the initials until the new image is loaded
+                self.set_image(None);
+                true
+            }
+        }
+    }
+
+    fn set_image(&mut self, image: Option<ImageBuf>) {
+        self.image = image;
+        self.paint_image = None;
+    }
+
+    /// The outline of the avatar in `rect`.
+    fn outline(&self, rect: Rect) -> RoundedRect {
+        let radius = match self.shape {
+            AvatarShape::Circle => rect.width() / 2.0,
+            AvatarShape::Rounded => rect.width() * 0.2,
+        };
+        rect.to_rounded_rect(radius)
+    }
+}
+
+impl<T: Data> Widget<T> for Avatar<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some((source, image)) = cmd.get(IMAGE_LOADED) {
+                // an image from an older source is dropped
+                if self.source.as_ref() == Some(source) {
+                    self.set_image(image.clone());
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, _env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.update_name(data);
+            self.update_image(ctx, data);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if self.update_name(data) {
+            ctx.request_layout();
+        }
+        if self.update_image(ctx, data) {
+            ctx.request_paint();
+        }
+        if let Some(status) = &self.status {
+            if status(old_data) != status(data) {
+                ctx.request_paint();
+            }
+        }
+        if self.initials.needs_rebuild_after_update(ctx) {
+            ctx.request_layout();
+        }
+        if let KeyOrValue::Key(key) = &self.size {
+            if ctx.env_key_changed(key) {
+                ctx.request_layout();
+            }
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let size = self.size.resolve(env);
+        self.initials.set_text_size(size * 0.4);
+        self.initials.set_text_color(Color::WHITE);
+        self.initials.rebuild_if_needed(ctx.text(), env);
+        bc.constrain(Size::new(size, size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let side = size.width.min(size.height);
+        let rect = Rect::from_center_size(size.to_rect().center(), Size::new(side, side));
+        let outline = self.outline(rect);
+
+        if let Some(image) = &self.image {
+            let paint_image = self
+                .paint_image
+                .get_or_insert_with(|| image.to_image(ctx.render_ctx));
+            ctx.with_save(|ctx| {
+                ctx.clip(outline);
+                ctx.draw_image(paint_image, rect, InterpolationMode::Bilinear);
+            });
+        } else {
+            ctx.fill(outline, &self.background);
+            let text_size = self.initials.size();
+            self.initials.draw(
+                ctx,
+                Point::new(
+                    rect.center().x - text_size.width / 2.0,
+                    rect.center().y - text_size.height / 2.0,
+                ),
+            );
+        }
+
+        if let Some(color) = self.status.as_ref().and_then(|status| status(data)) {
+            // the dot is ringed with the background to stand out of the image
+            let radius = side * 0.15;
+            let center = match self.shape {
+                // on the circle, at the bottom right
+                AvatarShape::Circle => {
+                    let offset = side / 2.0 * std::f64::consts::FRAC_1_SQRT_2;
+                    rect.center() + Vec2::new(offset, offset)
+                }
+                AvatarShape::Rounded => Point::new(rect.x1 - radius, rect.y1 - radius),
+            };
+            ctx.fill(
+                Circle::new(center, radius + 2.0),
+                &env.get(theme::WINDOW_BACKGROUND_COLOR),
+            );
+            ctx.fill(Circle::new(center, radius), &color);
+        }
+    }
+}
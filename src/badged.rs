@@ -0,0 +1,249 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::kurbo::{Affine, Circle, RoundedRect};
+use druid::widget::prelude::*;
+use druid::{Color, KeyOrValue, Lens, LensExt, Point, Rect, TextLayout, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve};
+
+const TEXT_SIZE: f64 = 10.0;
+const DOT_RADIUS: f64 = 4.0;
+
+/// What a [`Badged`] shows in the corner of its child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Badge {
+    Hidden,
+    Dot,
+    Count(usize),
+}
+
+/// A wrapper showing a bubble with a count, or a dot, on the top right corner of its child, like
+/// for unread messages.
+///
+/// The bubble grows in when it appears and shrinks away when it disappears. A count of zero
+/// hides the bubble, and counts above the maximum are shown like "99+".
+///
+/// ```
+/// # use druid::widget::Label;
+/// # use druid::{Data, Lens, Widget};
+/// # use druid_widget_nursery::WidgetExt as _;
+/// #[derive(Clone, Data, Lens)]
+/// struct Inbox {
+///     unread: usize,
+///     has_drafts: bool,
+/// }
+///
+/// fn inbox() -> impl Widget<Inbox> {
+///     Label::new("Inbox").badge_count(Inbox::unread)
+/// }
+///
+/// fn drafts() -> impl Widget<Inbox> {
+///     Label::new("Drafts").badge_dot(Inbox::has_drafts)
+/// }
+/// ```
+pub struct Badged<T, W> {
+    child: WidgetPod<T, W>,
+    badge: Box<dyn Fn(&T) -> Badge>,
+    /// The badge being shown, or the last one while it shrinks away.
+    shown: Badge,
+    max_count: usize,
+    color: KeyOrValue<Color>,
+    text: TextLayout<String>,
+    scale: Animated<f64>,
+}
+
+impl<T: Data, W: Widget<T>> Badged<T, W> {
+    fn new(child: W, badge: impl Fn(&T) -> Badge + 'static) -> Self {
+        let mut text = TextLayout::new();
+        text.set_text_size(TEXT_SIZE);
+        text.set_text_color(Color::WHITE);
+        Badged {
+            child: WidgetPod::new(child),
+            badge: Box::new(badge),
+            shown: Badge::Hidden,
+            max_count: 99,
+            color: KeyOrValue::Concrete(Color::rgb8(0xd3, 0x2f, 0x2f)),
+            text,
+            scale: Animated::jump(0.0)
+                .duration(0.2)
+                .curve(AnimationCurve::EASE_OUT),
+        }
+    }
+
+    /// Show the count of `lens` on `child`, hiding the bubble while the count is zero.
+    pub fn count(child: W, lens: impl Lens<T, usize> + 'static) -> Self {
+        Self::new(child, move |data| match lens.get(data) {
+            0 => Badge::Hidden,
+            count => Badge::Count(count),
+        })
+    }
+
+    /// Show a dot on `child` while the `bool` of `lens` is true.
+    pub fn dot(child: W, lens: impl Lens<T, bool> + 'static) -> Self {
+        Self::new(child, move |data| {
+            if lens.get(data) {
+                Badge::Dot
+            } else {
+                Badge::Hidden
+            }
+        })
+    }
+
+    /// Builder-style method for specifying the largest count shown. Larger counts are shown as
+    /// this count followed by "+".
+    ///
+    /// For the non-builder varient, see [`set_max_count`].
+    ///
+    /// [`set_max_count`]: #method.set_max_count
+    pub fn with_max_count(mut self, max_count: usize) -> Self {
+        self.set_max_count(max_count);
+        self
+    }
+
+    /// Set the largest count shown, 99 by default.
+    pub fn set_max_count(&mut self, max_count: usize) {
+        self.max_count = max_count;
+    }
+
+    /// Builder-style method for specifying the color of the bubble.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the bubble.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Show `badge`, animating it in or out. A hidden badge keeps showing the last one while it
+    /// shrinks.
+    fn show(&mut self, ctx: &mut UpdateCtx, badge: Badge) {
+        if badge == Badge::Hidden {
+            self.scale.animate(ctx, 0.0);
+            return;
+        }
+        if badge != self.shown {
+            self.shown = badge;
+            self.set_text(badge);
+            ctx.request_layout();
+        }
+        self.scale.animate(ctx, 1.0);
+    }
+
+    fn set_text(&mut self, badge: Badge) {
+        if let Badge::Count(count) = badge {
+            self.text.set_text(if count > self.max_count {
+                format!("{}+", self.max_count)
+            } else {
+                count.to_string()
+            });
+        }
+    }
+
+    /// The rect of the bubble, centered on the top right corner of the child.
+    fn badge_rect(&self, size: Size) -> Rect {
+        let badge_size = match self.shown {
+            Badge::Hidden => Size::ZERO,
+            Badge::Dot => Size::new(DOT_RADIUS * 2.0, DOT_RADIUS * 2.0),
+            Badge::Count(_) => {
+                let text_size = self.text.size();
+                let height = text_size.height;
+                Size::new(text_size.width.max(height) + height / 2.0, height)
+            }
+        };
+        Rect::from_center_size(Point::new(size.width, 0.0), badge_size)
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for Badged<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            if self.scale.animating() {
+                self.scale.update(ctx, *nanos);
+                ctx.request_paint();
+            }
+        }
+        self.child.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            // the badge shown at first doesn't grow in
+            let badge = (self.badge)(data);
+            if badge != Badge::Hidden {
+                self.set_text(badge);
+                self.shown = badge;
+                self.scale.jump_to_value(1.0);
+            }
+        }
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let badge = (self.badge)(data);
+        self.show(ctx, badge);
+        if self.text.needs_rebuild_after_update(ctx) {
+            ctx.request_layout();
+        }
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.text.rebuild_if_needed(ctx.text(), env);
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        // the bubble sticks out of the child
+        let paint_rect = self.child.paint_rect().union(self.badge_rect(size));
+        ctx.set_paint_insets(paint_rect - size.to_rect());
+        ctx.set_baseline_offset(self.child.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+
+        let scale = self.scale.get();
+        if scale <= 0.0 {
+            return;
+        }
+        let rect = self.badge_rect(ctx.size());
+        let center = rect.center().to_vec2();
+        let color = self.color.resolve(env);
+        let text = &self.text;
+        let shown = self.shown;
+        ctx.with_save(|ctx| {
+            ctx.transform(
+                Affine::translate(center) * Affine::scale(scale) * Affine::translate(-center),
+            );
+            match shown {
+                Badge::Hidden => {}
+                Badge::Dot => ctx.fill(Circle::new(rect.center(), DOT_RADIUS), &color),
+                Badge::Count(_) => {
+                    ctx.fill(RoundedRect::from_rect(rect, rect.height() / 2.0), &color);
+                    let text_size = text.size();
+                    text.draw(
+                        ctx,
+                        Point::new(rect.center().x - text_size.width / 2.0, rect.y0),
+                    );
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,296 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use barcoders::sym::{code128::Code128, code39::Code39, ean13::EAN13};
+use druid::kurbo::BezPath;
+use druid::widget::prelude::*;
+use druid::{Color, KeyOrValue, Point, Rect};
+use qrcode::{EcLevel, QrCode};
+
+/// The size of the side of a QR code when the constraints are loose.
+const DEFAULT_QR_SIZE: f64 = 160.0;
+/// The size of a bar module and the height of the bars when the constraints are loose.
+const DEFAULT_BAR_WIDTH: f64 = 2.0;
+const DEFAULT_BAR_HEIGHT: f64 = 80.0;
+
+/// The kind of code shown by a [`Barcode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum Symbology {
+    /// A QR code, holding any text.
+    Qr,
+    /// A Code 128 barcode, holding ASCII text.
+    Code128,
+    /// A Code 39 barcode, holding digits, capital letters, spaces and `-.$/+%`.
+    Code39,
+    /// An EAN-13 barcode, holding 12 digits; the check digit is added.
+    Ean13,
+}
+
+/// How much of a QR code can be damaged and still be read, for a bigger code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum ErrorCorrection {
+    /// About 7% of the code.
+    Low,
+    /// About 15% of the code.
+    Medium,
+    /// About 25% of the code.
+    Quartile,
+    /// About 30% of the code.
+    High,
+}
+
+/// The modules of an encoded code, `true` being dark.
+enum Modules {
+    Matrix { width: usize, dark: Vec<bool> },
+    Bars(Vec<bool>),
+}
+
+/// A widget showing its text as a QR code or a 1D barcode.
+///
+/// The code is encoded again when the text changes. Codes are painted on a background with a
+/// quiet zone around them, since scanners need both; text the symbology can't hold is logged
+/// and only the background is painted.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::{Barcode, ErrorCorrection, Symbology};
+/// fn pairing_code() -> impl Widget<String> {
+///     Barcode::new(Symbology::Qr).with_error_correction(ErrorCorrection::High)
+/// }
+/// ```
+pub struct Barcode {
+    symbology: Symbology,
+    error_correction: ErrorCorrection,
+    quiet_zone: Option<usize>,
+    color: KeyOrValue<Color>,
+    background: KeyOrValue<Color>,
+    modules: Option<Option<Modules>>,
+}
+
+impl Barcode {
+    /// Create a widget showing codes of `symbology`.
+    pub fn new(symbology: Symbology) -> Self {
+        Barcode {
+            symbology,
+            error_correction: ErrorCorrection::Medium,
+            quiet_zone: None,
+            color: Color::BLACK.into(),
+            background: Color::WHITE.into(),
+            modules: None,
+        }
+    }
+
+    /// Builder-style method for setting the error correction of QR codes.
+    ///
+    /// The default is [`ErrorCorrection::Medium`].
+    ///
+    /// For the non-builder varient, see [`set_error_correction`].
+    ///
+    /// [`set_error_correction`]: #method.set_error_correction
+    pub fn with_error_correction(mut self, error_correction: ErrorCorrection) -> Self {
+        self.set_error_correction(error_correction);
+        self
+    }
+
+    /// Set the error correction of QR codes.
+    pub fn set_error_correction(&mut self, error_correction: ErrorCorrection) {
+        self.error_correction = error_correction;
+        self.modules = None;
+    }
+
+    /// Builder-style method for setting the width of the quiet zone, in modules.
+    ///
+    /// The default is 4 modules around QR codes and 10 on the sides of barcodes.
+    ///
+    /// For the non-builder varient, see [`set_quiet_zone`].
+    ///
+    /// [`set_quiet_zone`]: #method.set_quiet_zone
+    pub fn with_quiet_zone(mut self, modules: usize) -> Self {
+        self.set_quiet_zone(modules);
+        self
+    }
+
+    /// Set the width of the quiet zone, in modules.
+    pub fn set_quiet_zone(&mut self, modules: usize) {
+        self.quiet_zone = Some(modules);
+    }
+
+    /// Builder-style method for setting the color of the dark modules.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the dark modules.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for setting the color of the background and the quiet zone.
+    ///
+    /// For the non-builder varient, see [`set_background`].
+    ///
+    /// [`set_background`]: #method.set_background
+    pub fn with_background(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_background(color);
+        self
+    }
+
+    /// Set the color of the background and the quiet zone.
+    pub fn set_background(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.background = color.into();
+    }
+
+    fn quiet_zone(&self) -> usize {
+        self.quiet_zone.unwrap_or(match self.symbology {
+            Symbology::Qr => 4,
+            _ => 10,
+        })
+    }
+
+    fn encode(&self, text: &str) -> Result<Modules, String> {
+        let bars = |encoded: Vec<u8>| Modules::Bars(encoded.into_iter().map(|m| m == 1).collect());
+        match self.symbology {
+            Symbology::Qr => {
+                let level = match self.error_correction {
+                    ErrorCorrection::Low => EcLevel::L,
+                    ErrorCorrection::Medium => EcLevel::M,
+                    ErrorCorrection::Quartile => EcLevel::Q,
+                    ErrorCorrection::High => EcLevel::H,
+                };
+                let code =
+                    QrCode::with_error_correction_level(text, level).map_err(|e| e.to_string())?;
+                let dark = code
+                    .to_colors()
+                    .into_iter()
+                    .map(|color| color == qrcode::Color::Dark)
+                    .collect();
+                Ok(Modules::Matrix {
+                    width: code.width(),
+                    dark,
+                })
+            }
+            // code set B holds the printable ASCII characters
+            Symbology::Code128 => Code128::new(format!("\u{0181}{}", text))
+                .map(|code| bars(code.encode()))
+                .map_err(|e| e.to_string()),
+            Symbology::Code39 => Code39::new(text)
+                .map(|code| bars(code.encode()))
+                .map_err(|e| e.to_string()),
+            Symbology::Ean13 => EAN13::new(text)
+                .map(|code| bars(code.encode()))
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// The modules for `text`, encoded again when it changed.
+    fn modules(&mut self, text: &str) -> Option<&Modules> {
+        if self.modules.is_none() {
+            let modules = match self.encode(text) {
+                Ok(modules) => Some(modules),
+                Err(e) => {
+                    log::warn!("Can't encode {:?} as {:?}: {}", text, self.symbology, e);
+                    None
+                }
+            };
+            self.modules = Some(modules);
+        }
+        self.modules.as_ref().and_then(Option::as_ref)
+    }
+}
+
+impl<T: Data + AsRef<str>> Widget<T> for Barcode {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if old_data.as_ref() != data.as_ref() {
+            self.modules = None;
+            ctx.request_layout();
+        }
+        if ctx.env_key_changed(&self.color) || ctx.env_key_changed(&self.background) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, _env: &Env) -> Size {
+        let quiet_zone = self.quiet_zone();
+        match self.modules(data.as_ref()) {
+            Some(Modules::Bars(bars)) => {
+                let width = (bars.len() + 2 * quiet_zone) as f64 * DEFAULT_BAR_WIDTH;
+                bc.constrain((width, DEFAULT_BAR_HEIGHT))
+            }
+            _ => bc.constrain_aspect_ratio(1.0, DEFAULT_QR_SIZE),
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let rect = size.to_rect();
+        ctx.fill(rect, &self.background.resolve(env));
+
+        let quiet_zone = self.quiet_zone();
+        let color = self.color.resolve(env);
+        let mut path = BezPath::new();
+        // modules are pixel sized when there's room, so there are no seams between them
+        let module_size = |available: f64, count: usize| {
+            let size = available / count as f64;
+            if size >= 1.0 {
+                size.floor()
+            } else {
+                size
+            }
+        };
+        match self.modules(data.as_ref()) {
+            Some(Modules::Matrix { width, dark }) => {
+                let count = width + 2 * quiet_zone;
+                let module = module_size(size.width.min(size.height), count);
+                let side = module * *width as f64;
+                let origin = Point::new(
+                    ((size.width - side) / 2.0).round(),
+                    ((size.height - side) / 2.0).round(),
+                );
+                for (i, _) in dark.iter().enumerate().filter(|(_, dark)| **dark) {
+                    let x = origin.x + (i % width) as f64 * module;
+                    let y = origin.y + (i / width) as f64 * module;
+                    add_rect(&mut path, Rect::new(x, y, x + module, y + module));
+                }
+            }
+            Some(Modules::Bars(bars)) => {
+                let count = bars.len() + 2 * quiet_zone;
+                let module = module_size(size.width, count);
+                let x0 = ((size.width - module * bars.len() as f64) / 2.0).round();
+                for (i, _) in bars.iter().enumerate().filter(|(_, dark)| **dark) {
+                    let x = x0 + i as f64 * module;
+                    add_rect(&mut path, Rect::new(x, 0.0, x + module, size.height));
+                }
+            }
+            None => return,
+        }
+        ctx.fill(path, &color);
+    }
+}
+
+fn add_rect(path: &mut BezPath, rect: Rect) {
+    path.move_to((rect.x0, rect.y0));
+    path.line_to((rect.x1, rect.y0));
+    path.line_to((rect.x1, rect.y1));
+    path.line_to((rect.x0, rect.y1));
+    path.close_path();
+}
@@ -13,22 +13,72 @@
 // limitations under the License.
 
 //! A widget that allows for arbitrary layout of it's children.
-use druid::kurbo::Rect;
+use druid::kurbo::{Affine, Rect};
 use druid::{
-    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    Point, Size, UpdateCtx, Widget, WidgetPod,
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, Lens, LifeCycle, LifeCycleCtx,
+    MouseEvent, PaintCtx, Point, RenderContext, Size, UpdateCtx, Vec2, Widget, WidgetPod,
 };
 
+use crate::DynLens;
+
+/// How much the zoom changes for a wheel delta of one.
+const ZOOM_SPEED: f64 = 0.002;
+
+/// The part of a [`Canvas`] which is shown, when it can be panned and zoomed.
+///
+/// A point of the canvas is shown at `point * zoom + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Data)]
+pub struct CanvasViewport {
+    pub offset: Vec2,
+    pub zoom: f64,
+}
+
+impl Default for CanvasViewport {
+    fn default() -> Self {
+        CanvasViewport {
+            offset: Vec2::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl CanvasViewport {
+    /// The transform from the canvas to the view.
+    pub fn transform(&self) -> Affine {
+        Affine::translate(self.offset) * Affine::scale(self.zoom)
+    }
+
+    /// The point of the canvas shown at `point` of the view.
+    pub fn to_canvas(&self, point: Point) -> Point {
+        self.transform().inverse() * point
+    }
+
+    /// Where `point` of the canvas is shown in the view.
+    pub fn to_view(&self, point: Point) -> Point {
+        self.transform() * point
+    }
+}
+
 ///A container that allows for arbitrary layout.
 ///
 ///This widget allows you to lay widgets out at any point, and to allow that positioning to be dependent on the data.
 ///This is facilitated by the [`CanvasLayout`] trait, and will most typically be used by wrapping your desired widgets
 ///in a [`CanvasWrap`] wrapper.
 ///
+///With [`with_viewport`], the canvas can be panned by dragging its background, or with the middle
+///button, and zoomed with the mouse wheel. The viewport is kept in the data, so it can be saved or
+///changed by the application.
+///
 ///[`CanvasLayout`]: trait.CanvasLayout.html
 ///[`CanvasWrap`]: struct.CanvasWrap.html
+///[`with_viewport`]: #method.with_viewport
 pub struct Canvas<T: Data> {
     children: Vec<(Rect, Box<dyn CanvasLayout<T>>)>,
+    viewport: Option<Box<dyn DynLens<T, CanvasViewport>>>,
+    min_zoom: f64,
+    max_zoom: f64,
+    /// The last mouse position while panning.
+    pan_origin: Option<Point>,
 }
 
 impl<T: Data> Default for Canvas<T> {
@@ -39,7 +89,13 @@ impl<T: Data> Default for Canvas<T> {
 
 impl<T: Data> Canvas<T> {
     pub fn new() -> Self {
-        Self { children: vec![] }
+        Self {
+            children: vec![],
+            viewport: None,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            pan_origin: None,
+        }
     }
     pub fn with_child(mut self, child: impl CanvasLayout<T> + 'static) -> Self {
         self.children.push((Rect::ZERO, Box::new(child)));
@@ -50,14 +106,135 @@ impl<T: Data> Canvas<T> {
         self.children.push((Rect::ZERO, Box::new(child)));
         ctx.children_changed();
     }
+
+    /// Builder-style method for making the canvas pannable and zoomable, keeping the viewport in
+    /// the data with `lens`.
+    pub fn with_viewport(mut self, lens: impl Lens<T, CanvasViewport> + 'static) -> Self {
+        self.viewport = Some(Box::new(lens));
+        self
+    }
+
+    /// Builder-style method for specifying the smallest and the largest zoom.
+    ///
+    /// For the non-builder varient, see [`set_zoom_range`].
+    ///
+    /// [`set_zoom_range`]: #method.set_zoom_range
+    pub fn with_zoom_range(mut self, min: f64, max: f64) -> Self {
+        self.set_zoom_range(min, max);
+        self
+    }
+
+    /// Set the smallest and the largest zoom, from 0.1 to 10 by default.
+    pub fn set_zoom_range(&mut self, min: f64, max: f64) {
+        self.min_zoom = min;
+        self.max_zoom = max;
+    }
+
+    fn viewport(&self, data: &T) -> CanvasViewport {
+        match &self.viewport {
+            Some(lens) => lens.with(data, |viewport| *viewport),
+            None => CanvasViewport::default(),
+        }
+    }
+
+    fn set_viewport(&self, data: &mut T, new: CanvasViewport) {
+        if let Some(lens) = &self.viewport {
+            lens.with_mut(data, |viewport| *viewport = new);
+        }
+    }
+
+    /// Handle panning and zooming, after the children had the event.
+    fn navigate(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T) {
+        let viewport = self.viewport(data);
+        match event {
+            Event::MouseDown(mouse) if !ctx.is_handled() => {
+                // the background is dragged with the left button, anything with the middle one
+                let on_child = self
+                    .children
+                    .iter()
+                    .any(|(rect, _)| rect.contains(viewport.to_canvas(mouse.pos)));
+                if mouse.button.is_middle() || (mouse.button.is_left() && !on_child) {
+                    self.pan_origin = Some(mouse.pos);
+                    ctx.set_active(true);
+                    ctx.set_handled();
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some(origin) = self.pan_origin {
+                    self.pan_origin = Some(mouse.pos);
+                    self.set_viewport(
+                        data,
+                        CanvasViewport {
+                            offset: viewport.offset + (mouse.pos - origin),
+                            ..viewport
+                        },
+                    );
+                    ctx.set_handled();
+                }
+            }
+            Event::MouseUp(_) if self.pan_origin.is_some() => {
+                self.pan_origin = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+            }
+            Event::Wheel(mouse) if !ctx.is_handled() => {
+                let zoom = (viewport.zoom * (-mouse.wheel_delta.y * ZOOM_SPEED).exp())
+                    .max(self.min_zoom)
+                    .min(self.max_zoom);
+                // the point under the mouse stays there
+                let anchor = viewport.to_canvas(mouse.pos);
+                self.set_viewport(
+                    data,
+                    CanvasViewport {
+                        offset: mouse.pos.to_vec2() - anchor.to_vec2() * zoom,
+                        zoom,
+                    },
+                );
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `event` with the mouse position moved by `transform`, for mouse events.
+pub(crate) fn transform_mouse(event: &Event, transform: Affine) -> Option<Event> {
+    let transformed = |mouse: &MouseEvent| {
+        let mut mouse = mouse.clone();
+        mouse.pos = transform * mouse.pos;
+        mouse
+    };
+    match event {
+        Event::MouseDown(mouse) => Some(Event::MouseDown(transformed(mouse))),
+        Event::MouseUp(mouse) => Some(Event::MouseUp(transformed(mouse))),
+        Event::MouseMove(mouse) => Some(Event::MouseMove(transformed(mouse))),
+        Event::Wheel(mouse) => Some(Event::Wheel(transformed(mouse))),
+        _ => None,
+    }
 }
 
 impl<T: Data> Widget<T> for Canvas<T> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        //we're letting their own filtering handle event filtering
-        //we may want to revisit that decision
+        if self.viewport.is_none() {
+            //we're letting their own filtering handle event filtering
+            //we may want to revisit that decision
+            for (_, child) in &mut self.children {
+                child.event(ctx, event, data, env);
+            }
+            return;
+        }
+
+        // the children are hit tested in the coordinates of the canvas
+        let viewport = self.viewport(data);
+        let child_event = transform_mouse(event, viewport.transform().inverse());
         for (_, child) in &mut self.children {
-            child.event(ctx, event, data, env);
+            child.event(ctx, child_event.as_ref().unwrap_or(event), data, env);
+        }
+        self.navigate(ctx, event, data);
+
+        // the children invalidate their rects without the transform
+        if viewport != CanvasViewport::default() {
+            ctx.request_paint();
         }
     }
 
@@ -71,6 +248,9 @@ impl<T: Data> Widget<T> for Canvas<T> {
         for (_, child) in &mut self.children {
             child.update(ctx, old_data, data, env);
         }
+        if self.viewport.is_some() && !old_data.same(data) {
+            ctx.request_paint();
+        }
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
@@ -91,11 +271,31 @@ impl<T: Data> Widget<T> for Canvas<T> {
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        //TODO: filter painting based on our extents? (don't draw widgets entirely outside our bounds?)
-        //It's the main reason we keep and update the rect
-        for (_, child) in &mut self.children {
-            child.paint(ctx, data, env);
+        if self.viewport.is_none() {
+            //TODO: filter painting based on our extents? (don't draw widgets entirely outside our bounds?)
+            //It's the main reason we keep and update the rect
+            for (_, child) in &mut self.children {
+                child.paint(ctx, data, env);
+            }
+            return;
         }
+
+        let transform = self.viewport(data).transform();
+        let clip = ctx.size().to_rect();
+        // the children only paint in the invalid region, which they see in canvas coordinates
+        let region = transform
+            .inverse()
+            .transform_rect_bbox(ctx.region().bounding_box());
+        let children = &mut self.children;
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            ctx.transform(transform);
+            ctx.with_child_ctx(region, |ctx| {
+                for (_, child) in children {
+                    child.paint(ctx, data, env);
+                }
+            });
+        });
     }
 }
 
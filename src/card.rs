@@ -0,0 +1,292 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::kurbo::{Affine, Insets, Line};
+use druid::widget::prelude::*;
+use druid::{theme, Color, KeyOrValue, Point, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve};
+
+type Child<T> = WidgetPod<T, Box<dyn Widget<T>>>;
+
+/// How far the card moves up when it is lifted.
+const LIFT_OFFSET: f64 = 2.0;
+
+/// A surface with rounded corners and a drop shadow, holding a body with an optional header and
+/// footer.
+///
+/// The shadow grows with the elevation. With [`with_hover_elevation`], the card is lifted while
+/// the mouse is over it.
+///
+/// ```
+/// # use druid::widget::{Button, Label};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::Card;
+/// fn card() -> impl Widget<()> {
+///     Card::new(Label::new("A card with a title and an action."))
+///         .with_header(Label::new("Title").with_text_size(18.0))
+///         .with_footer(Button::new("Open"))
+///         .with_elevation(2.0)
+///         .with_hover_elevation(6.0)
+/// }
+/// ```
+///
+/// [`with_hover_elevation`]: #method.with_hover_elevation
+pub struct Card<T> {
+    header: Option<Child<T>>,
+    body: Child<T>,
+    footer: Option<Child<T>>,
+    padding: KeyOrValue<f64>,
+    corner_radius: KeyOrValue<f64>,
+    background: KeyOrValue<Color>,
+    elevation: f64,
+    hover_elevation: Option<f64>,
+    /// The current elevation, animated towards the hover elevation.
+    current_elevation: Animated<f64>,
+    /// Where the header and the body end, for the dividers.
+    dividers: (Option<f64>, Option<f64>),
+}
+
+impl<T: Data> Card<T> {
+    pub fn new(body: impl Widget<T> + 'static) -> Self {
+        Card {
+            header: None,
+            body: WidgetPod::new(Box::new(body)),
+            footer: None,
+            padding: KeyOrValue::Concrete(12.0),
+            corner_radius: KeyOrValue::Concrete(8.0),
+            background: theme::BACKGROUND_LIGHT.into(),
+            elevation: 1.0,
+            hover_elevation: None,
+            current_elevation: Animated::jump(1.0)
+                .duration(0.15)
+                .curve(AnimationCurve::EASE_OUT),
+            dividers: (None, None),
+        }
+    }
+
+    /// Builder-style method for adding a header, divided from the body by a line.
+    pub fn with_header(mut self, header: impl Widget<T> + 'static) -> Self {
+        self.header = Some(WidgetPod::new(Box::new(header)));
+        self
+    }
+
+    /// Builder-style method for adding a footer, divided from the body by a line.
+    pub fn with_footer(mut self, footer: impl Widget<T> + 'static) -> Self {
+        self.footer = Some(WidgetPod::new(Box::new(footer)));
+        self
+    }
+
+    /// Builder-style method for specifying the space around the header, the body and the footer.
+    ///
+    /// For the non-builder varient, see [`set_padding`].
+    ///
+    /// [`set_padding`]: #method.set_padding
+    pub fn with_padding(mut self, padding: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_padding(padding);
+        self
+    }
+
+    /// Set the space around the header, the body and the footer.
+    pub fn set_padding(&mut self, padding: impl Into<KeyOrValue<f64>>) {
+        self.padding = padding.into();
+    }
+
+    /// Builder-style method for specifying the radius of the corners.
+    ///
+    /// For the non-builder varient, see [`set_corner_radius`].
+    ///
+    /// [`set_corner_radius`]: #method.set_corner_radius
+    pub fn with_corner_radius(mut self, radius: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_corner_radius(radius);
+        self
+    }
+
+    /// Set the radius of the corners.
+    pub fn set_corner_radius(&mut self, radius: impl Into<KeyOrValue<f64>>) {
+        self.corner_radius = radius.into();
+    }
+
+    /// Builder-style method for specifying the background color.
+    ///
+    /// For the non-builder varient, see [`set_background`].
+    ///
+    /// [`set_background`]: #method.set_background
+    pub fn with_background(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_background(color);
+        self
+    }
+
+    /// Set the background color.
+    pub fn set_background(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.background = color.into();
+    }
+
+    /// Builder-style method for specifying the elevation, which sets the size of the shadow.
+    ///
+    /// For the non-builder varient, see [`set_elevation`].
+    ///
+    /// [`set_elevation`]: #method.set_elevation
+    pub fn with_elevation(mut self, elevation: f64) -> Self {
+        self.set_elevation(elevation);
+        self
+    }
+
+    /// Set the elevation, 1 by default, or 0 for no shadow.
+    pub fn set_elevation(&mut self, elevation: f64) {
+        self.elevation = elevation;
+        self.current_elevation.jump_to_value(elevation);
+    }
+
+    /// Builder-style method for lifting the card to `elevation` while the mouse is over it.
+    ///
+    /// For the non-builder varient, see [`set_hover_elevation`].
+    ///
+    /// [`set_hover_elevation`]: #method.set_hover_elevation
+    pub fn with_hover_elevation(mut self, elevation: f64) -> Self {
+        self.set_hover_elevation(Some(elevation));
+        self
+    }
+
+    /// Set the elevation while the mouse is over the card, or `None` to keep the elevation.
+    pub fn set_hover_elevation(&mut self, elevation: Option<f64>) {
+        self.hover_elevation = elevation;
+    }
+
+    /// The space the shadow takes around the card at `elevation`.
+    fn shadow_insets(elevation: f64) -> Insets {
+        let blur = elevation * 2.0;
+        Insets::new(blur, blur, blur, blur + elevation / 2.0 + LIFT_OFFSET)
+    }
+
+    fn children(&mut self) -> impl Iterator<Item = &mut Child<T>> {
+        self.header
+            .iter_mut()
+            .chain(Some(&mut self.body))
+            .chain(self.footer.iter_mut())
+    }
+}
+
+impl<T: Data> Widget<T> for Card<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            if self.current_elevation.animating() {
+                self.current_elevation.update(ctx, *nanos);
+                ctx.request_paint();
+            }
+        }
+        for child in self.children() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::HotChanged(hot) = event {
+            if let Some(hover_elevation) = self.hover_elevation {
+                let elevation = if *hot {
+                    hover_elevation
+                } else {
+                    self.elevation
+                };
+                self.current_elevation.animate(ctx, elevation);
+            }
+        }
+        for child in self.children() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in self.children() {
+            child.update(ctx, data, env);
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let padding = self.padding.resolve(env);
+        // the children are as wide as the card, and as high as they like
+        let child_bc = BoxConstraints::new(
+            Size::new((bc.min().width - 2.0 * padding).max(0.0), 0.0),
+            Size::new((bc.max().width - 2.0 * padding).max(0.0), f64::INFINITY),
+        );
+
+        let mut width: f64 = 0.0;
+        let mut y = 0.0;
+        let mut dividers = (None, None);
+        if let Some(header) = &mut self.header {
+            let size = header.layout(ctx, &child_bc, data, env);
+            header.set_origin(ctx, Point::new(padding, padding));
+            width = width.max(size.width);
+            y += size.height + 2.0 * padding;
+            dividers.0 = Some(y);
+        }
+        let size = self.body.layout(ctx, &child_bc, data, env);
+        self.body.set_origin(ctx, Point::new(padding, y + padding));
+        width = width.max(size.width);
+        y += size.height + 2.0 * padding;
+        if let Some(footer) = &mut self.footer {
+            dividers.1 = Some(y);
+            let size = footer.layout(ctx, &child_bc, data, env);
+            footer.set_origin(ctx, Point::new(padding, y + padding));
+            width = width.max(size.width);
+            y += size.height + 2.0 * padding;
+        }
+        self.dividers = dividers;
+
+        let elevation = self
+            .elevation
+            .max(self.hover_elevation.unwrap_or(self.elevation));
+        ctx.set_paint_insets(Self::shadow_insets(elevation));
+        bc.constrain(Size::new(width + 2.0 * padding, y))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let elevation = self.current_elevation.get();
+        let radius = self.corner_radius.resolve(env);
+        let size = ctx.size();
+        let rect = size.to_rect();
+        // lifting moves the card up a little, proportionally to the extra elevation
+        let lift = match self.hover_elevation {
+            Some(hover) if hover > self.elevation => {
+                (elevation - self.elevation) / (hover - self.elevation) * LIFT_OFFSET
+            }
+            _ => 0.0,
+        };
+
+        ctx.with_save(|ctx| {
+            ctx.transform(Affine::translate((0.0, -lift)));
+            if elevation > 0.0 {
+                let shadow = rect + (0.0, elevation / 2.0 + lift).into();
+                ctx.blurred_rect(shadow, elevation * 2.0, &Color::rgba8(0, 0, 0, 0x50));
+            }
+            let surface = rect.to_rounded_rect(radius);
+            ctx.fill(surface, &self.background.resolve(env));
+
+            let border = env.get(theme::BORDER_LIGHT);
+            for y in [self.dividers.0, self.dividers.1].iter().flatten() {
+                ctx.stroke(Line::new((0.0, *y), (size.width, *y)), &border, 1.0);
+            }
+
+            ctx.with_save(|ctx| {
+                ctx.clip(surface);
+                for child in self.children() {
+                    child.paint(ctx, data, env);
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,825 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line, bar and pie charts of [`ChartData`], with axes, legends, tooltips and animated
+//! transitions when the data changes.
+//!
+//! ```
+//! # use druid::im::vector;
+//! # use druid::Widget;
+//! # use druid_widget_nursery::chart::{ChartData, LineChart, Series};
+//! fn sales() -> impl Widget<ChartData> {
+//!     LineChart::new().with_format(|value| format!("${:.0}", value))
+//! }
+//!
+//! fn data() -> ChartData {
+//!     ChartData::new(
+//!         vector!["Jan".to_string(), "Feb".to_string(), "Mar".to_string()],
+//!         vector![
+//!             Series::new("Books", vector![120.0, 180.0, 150.0]),
+//!             Series::new("Games", vector![80.0, 60.0, 140.0]),
+//!         ],
+//!     )
+//! }
+//! ```
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use druid::im::Vector;
+use druid::kurbo::{BezPath, Circle, Line};
+use druid::piet::{PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, Data, Lens, Point, Rect, Vec2};
+
+use crate::animation::{Animated, AnimationCurve};
+
+/// The colors of the series without one, and of the slices of pie charts.
+const PALETTE: [Color; 8] = [
+    Color::rgb8(0x1f, 0x77, 0xb4),
+    Color::rgb8(0xff, 0x7f, 0x0e),
+    Color::rgb8(0x2c, 0xa0, 0x2c),
+    Color::rgb8(0xd6, 0x27, 0x28),
+    Color::rgb8(0x94, 0x67, 0xbd),
+    Color::rgb8(0x8c, 0x56, 0x4b),
+    Color::rgb8(0xe3, 0x77, 0xc2),
+    Color::rgb8(0x17, 0xbe, 0xcf),
+];
+
+/// The size taken when the constraints are loose.
+const DEFAULT_SIZE: Size = Size::new(400.0, 250.0);
+const PADDING: f64 = 8.0;
+const LEGEND_HEIGHT: f64 = 24.0;
+const LEGEND_WIDTH: f64 = 120.0;
+const SWATCH_SIZE: f64 = 10.0;
+/// The space for the labels of the value axis, and of the categories.
+const AXIS_WIDTH: f64 = 48.0;
+const AXIS_HEIGHT: f64 = 20.0;
+/// About how many ticks the value axis has.
+const TICK_COUNT: f64 = 5.0;
+
+/// A named series of values, one for each category of a [`ChartData`].
+#[derive(Debug, Clone, Data, Lens)]
+pub struct Series {
+    pub name: String,
+    pub values: Vector<f64>,
+    /// The color of the series, or `None` for a color of the palette.
+    pub color: Option<Color>,
+}
+
+impl Series {
+    pub fn new(name: impl Into<String>, values: Vector<f64>) -> Self {
+        Series {
+            name: name.into(),
+            values,
+            color: None,
+        }
+    }
+
+    /// Builder-style method for specifying the color of the series.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// The data of a chart: the labels of the categories, and series of values for them.
+///
+/// A [`PieChart`] shows the first series, with a slice for each category.
+#[derive(Debug, Clone, Default, Data, Lens)]
+pub struct ChartData {
+    pub labels: Vector<String>,
+    pub series: Vector<Series>,
+}
+
+impl ChartData {
+    pub fn new(labels: Vector<String>, series: Vector<Series>) -> Self {
+        ChartData { labels, series }
+    }
+
+    /// The number of categories, from the labels or the longest series.
+    fn categories(&self) -> usize {
+        self.series
+            .iter()
+            .map(|series| series.values.len())
+            .fold(self.labels.len(), usize::max)
+    }
+
+    fn label(&self, index: usize) -> String {
+        self.labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| (index + 1).to_string())
+    }
+
+    fn color(&self, series: usize) -> Color {
+        self.series
+            .get(series)
+            .and_then(|series| series.color)
+            .unwrap_or_else(|| PALETTE[series % PALETTE.len()])
+    }
+}
+
+/// What the charts have in common: the format of the values, the legend, the transitions and
+/// the mouse.
+struct Core {
+    format: Box<dyn Fn(f64) -> String>,
+    legend: bool,
+    /// The values shown when the data last changed, animated from.
+    from: Vec<Vec<f64>>,
+    progress: Animated<f64>,
+    mouse: Option<Point>,
+}
+
+impl Core {
+    fn new() -> Self {
+        Core {
+            format: Box::new(|value| {
+                if value.fract() == 0.0 {
+                    format!("{:.0}", value)
+                } else {
+                    format!("{:.2}", value)
+                }
+            }),
+            legend: true,
+            from: Vec::new(),
+            progress: Animated::jump(1.0)
+                .duration(0.4)
+                .curve(AnimationCurve::EASE_IN_OUT),
+            mouse: None,
+        }
+    }
+
+    /// The value shown for `index` of `series`, on its way from the previous data.
+    fn value(&self, data: &ChartData, series: usize, index: usize) -> f64 {
+        let to = data
+            .series
+            .get(series)
+            .and_then(|series| series.values.get(index))
+            .copied()
+            .unwrap_or(0.0);
+        let from = self
+            .from
+            .get(series)
+            .and_then(|values| values.get(index))
+            .copied()
+            .unwrap_or(0.0);
+        from + (to - from) * self.progress.get()
+    }
+
+    fn shown_values(&self, data: &ChartData) -> Vec<Vec<f64>> {
+        (0..data.series.len())
+            .map(|series| {
+                (0..data.categories())
+                    .map(|index| self.value(data, series, index))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event) {
+        match event {
+            Event::AnimFrame(nanos) if self.progress.animating() => {
+                self.progress.update(ctx, *nanos);
+            }
+            Event::MouseMove(mouse) => {
+                self.mouse = Some(mouse.pos);
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                // the values grow from zero
+                self.progress.jump_to_value(0.0);
+                self.progress.animate(ctx, 1.0);
+            }
+            LifeCycle::HotChanged(false) => {
+                self.mouse = None;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &ChartData, data: &ChartData) {
+        if !old_data.same(data) {
+            // from what is shown now, even in the middle of a transition
+            self.from = self.shown_values(old_data);
+            self.progress.jump_to_value(0.0);
+            self.progress.animate(ctx, 1.0);
+            ctx.request_paint();
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    /// The value axis and the rect the values are plotted in, for line and bar charts.
+    fn axes(&self, size: Size, data: &ChartData) -> Axes {
+        let top = if self.legend { LEGEND_HEIGHT } else { 0.0 } + PADDING;
+        let plot = Rect::new(
+            AXIS_WIDTH,
+            top,
+            (size.width - PADDING).max(AXIS_WIDTH),
+            (size.height - AXIS_HEIGHT).max(top),
+        );
+        let values: Vec<f64> = data
+            .series
+            .iter()
+            .flat_map(|series| series.values.iter().copied())
+            .collect();
+        let min = values.iter().copied().fold(0.0, f64::min);
+        let max = values.iter().copied().fold(0.0, f64::max);
+        let (min, max) = if max - min < f64::EPSILON {
+            (0.0, 1.0)
+        } else {
+            (min, max)
+        };
+        // round numbers for the ticks
+        let min_step = (max - min) / TICK_COUNT;
+        let magnitude = 10f64.powf(min_step.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|factor| factor * magnitude)
+            .find(|step| *step >= min_step)
+            .unwrap_or(10.0 * magnitude);
+        Axes {
+            plot,
+            min: (min / step).floor() * step,
+            max: (max / step).ceil() * step,
+            step,
+            categories: data.categories(),
+        }
+    }
+
+    /// The category under the mouse, for line and bar charts.
+    fn hovered_category(&self, axes: &Axes) -> Option<usize> {
+        let mouse = self.mouse?;
+        if !axes.plot.contains(mouse) || axes.categories == 0 {
+            return None;
+        }
+        let index = ((mouse.x - axes.plot.x0) / axes.slot()).floor() as usize;
+        Some(index.min(axes.categories - 1))
+    }
+
+    /// The legend of the series, in a row at the top.
+    fn paint_series_legend(&self, ctx: &mut PaintCtx, data: &ChartData, env: &Env) {
+        if !self.legend {
+            return;
+        }
+        let text_color = env.get(theme::TEXT_COLOR);
+        let mut x = AXIS_WIDTH;
+        for (index, series) in data.series.iter().enumerate() {
+            let layout = text(ctx, series.name.clone(), &text_color, env);
+            let y = (LEGEND_HEIGHT - SWATCH_SIZE) / 2.0;
+            let swatch = Rect::from_origin_size((x, y), (SWATCH_SIZE, SWATCH_SIZE));
+            ctx.fill(swatch.to_rounded_rect(2.0), &data.color(index));
+            x += SWATCH_SIZE + 4.0;
+            ctx.draw_text(&layout, (x, (LEGEND_HEIGHT - layout.size().height) / 2.0));
+            x += layout.size().width + 12.0;
+        }
+    }
+
+    /// The values of a category, in a box next to the mouse.
+    fn paint_category_tooltip(
+        &self,
+        ctx: &mut PaintCtx,
+        data: &ChartData,
+        index: usize,
+        env: &Env,
+    ) {
+        let mut lines = vec![(None, data.label(index))];
+        for (series_index, series) in data.series.iter().enumerate() {
+            if let Some(value) = series.values.get(index) {
+                let line = format!("{}: {}", series.name, (self.format)(*value));
+                lines.push((Some(data.color(series_index)), line));
+            }
+        }
+        if let Some(mouse) = self.mouse {
+            paint_tooltip(ctx, mouse, lines, env);
+        }
+    }
+}
+
+/// The value axis and the rect the values are plotted in.
+struct Axes {
+    plot: Rect,
+    min: f64,
+    max: f64,
+    step: f64,
+    categories: usize,
+}
+
+impl Axes {
+    fn y_at(&self, value: f64) -> f64 {
+        self.plot.y1 - (value - self.min) / (self.max - self.min) * self.plot.height()
+    }
+
+    /// The width taken by each category.
+    fn slot(&self) -> f64 {
+        self.plot.width() / self.categories.max(1) as f64
+    }
+
+    fn x_at(&self, index: usize) -> f64 {
+        self.plot.x0 + (index as f64 + 0.5) * self.slot()
+    }
+
+    /// The grid, with the labels of the ticks and of the categories.
+    fn paint(
+        &self,
+        ctx: &mut PaintCtx,
+        data: &ChartData,
+        format: &dyn Fn(f64) -> String,
+        env: &Env,
+    ) {
+        let text_color = env.get(theme::TEXT_COLOR);
+        let grid_color = env.get(theme::BORDER_LIGHT);
+
+        let ticks = ((self.max - self.min) / self.step).round() as usize;
+        for tick in 0..=ticks {
+            let value = self.min + tick as f64 * self.step;
+            let y = self.y_at(value).round() + 0.5;
+            let width = if value == 0.0 { 1.5 } else { 1.0 };
+            ctx.stroke(
+                Line::new((self.plot.x0, y), (self.plot.x1, y)),
+                &grid_color,
+                width,
+            );
+            let layout = text(ctx, format(value), &text_color, env);
+            let size = layout.size();
+            ctx.draw_text(
+                &layout,
+                (self.plot.x0 - 4.0 - size.width, y - size.height / 2.0),
+            );
+        }
+
+        // the labels of the categories, skipping some when they don't fit
+        let layouts: Vec<_> = (0..self.categories)
+            .map(|index| text(ctx, data.label(index), &text_color, env))
+            .collect();
+        let widest = layouts
+            .iter()
+            .map(|layout| layout.size().width)
+            .fold(0.0, f64::max);
+        let every = ((widest + 4.0) / self.slot()).ceil().max(1.0) as usize;
+        for (index, layout) in layouts.iter().enumerate().step_by(every) {
+            let size = layout.size();
+            let x = self.x_at(index) - size.width / 2.0;
+            ctx.draw_text(
+                layout,
+                (x, self.plot.y1 + (AXIS_HEIGHT - size.height) / 2.0),
+            );
+        }
+    }
+}
+
+fn text(ctx: &mut PaintCtx, text: String, color: &Color, env: &Env) -> PietTextLayout {
+    let font = env.get(theme::UI_FONT);
+    ctx.text()
+        .new_text_layout(text)
+        .font(font.family, env.get(theme::TEXT_SIZE_SMALL))
+        .text_color(color)
+        .build()
+        .unwrap()
+}
+
+/// A box with a line of text for each of `lines`, with an optional swatch, next to `anchor`
+/// and inside the widget.
+fn paint_tooltip(
+    ctx: &mut PaintCtx,
+    anchor: Point,
+    lines: Vec<(Option<Color>, String)>,
+    env: &Env,
+) {
+    let text_color = env.get(theme::TEXT_COLOR);
+    let layouts: Vec<_> = lines
+        .into_iter()
+        .map(|(color, line)| (color, text(ctx, line, &text_color, env)))
+        .collect();
+    let line_height = layouts
+        .iter()
+        .map(|(_, layout)| layout.size().height)
+        .fold(0.0, f64::max);
+    let width = layouts
+        .iter()
+        .map(|(color, layout)| {
+            let swatch = if color.is_some() {
+                SWATCH_SIZE + 4.0
+            } else {
+                0.0
+            };
+            swatch + layout.size().width
+        })
+        .fold(0.0, f64::max);
+    let size = Size::new(
+        width + 2.0 * PADDING,
+        line_height * layouts.len() as f64 + PADDING,
+    );
+
+    // on the right of the mouse, or on its left when there is no room
+    let bounds = ctx.size();
+    let x = if anchor.x + 12.0 + size.width <= bounds.width {
+        anchor.x + 12.0
+    } else {
+        (anchor.x - 12.0 - size.width).max(0.0)
+    };
+    let y = (anchor.y - size.height / 2.0)
+        .min(bounds.height - size.height)
+        .max(0.0);
+    let rect = Rect::from_origin_size((x, y), size);
+    ctx.blurred_rect(rect, 4.0, &Color::rgba8(0, 0, 0, 0x40));
+    ctx.fill(rect.to_rounded_rect(4.0), &env.get(theme::BACKGROUND_LIGHT));
+    ctx.stroke(
+        rect.inset(-0.5).to_rounded_rect(4.0),
+        &env.get(theme::BORDER_DARK),
+        1.0,
+    );
+
+    let mut y = y + PADDING / 2.0;
+    for (color, layout) in &layouts {
+        let mut x = x + PADDING;
+        if let Some(color) = color {
+            let swatch_y = y + (line_height - SWATCH_SIZE) / 2.0;
+            let swatch = Rect::from_origin_size((x, swatch_y), (SWATCH_SIZE, SWATCH_SIZE));
+            ctx.fill(swatch.to_rounded_rect(2.0), color);
+            x += SWATCH_SIZE + 4.0;
+        }
+        ctx.draw_text(layout, (x, y));
+        y += line_height;
+    }
+}
+
+/// Builder methods for the options the charts have in common.
+macro_rules! chart_options {
+    ($ty:ty) => {
+        impl $ty {
+            /// Builder-style method for turning the values into text, on the axis and in the
+            /// tooltips.
+            pub fn with_format(mut self, format: impl Fn(f64) -> String + 'static) -> Self {
+                self.core.format = Box::new(format);
+                self
+            }
+
+            /// Builder-style method for specifying whether the legend is shown.
+            ///
+            /// For the non-builder varient, see [`set_legend`].
+            ///
+            /// [`set_legend`]: #method.set_legend
+            pub fn with_legend(mut self, legend: bool) -> Self {
+                self.set_legend(legend);
+                self
+            }
+
+            /// Set whether the legend is shown, which it is by default.
+            pub fn set_legend(&mut self, legend: bool) {
+                self.core.legend = legend;
+            }
+
+            /// Builder-style method for specifying how long the transitions to new data take.
+            ///
+            /// For the non-builder varient, see [`set_duration`].
+            ///
+            /// [`set_duration`]: #method.set_duration
+            pub fn with_duration(mut self, duration: f64) -> Self {
+                self.set_duration(duration);
+                self
+            }
+
+            /// Set how long the transitions to new data take in seconds, 0.4 by default, or 0
+            /// for no transitions.
+            pub fn set_duration(&mut self, duration: f64) {
+                self.core.progress.set_duration(duration);
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// The methods of [`Widget`] the charts have in common.
+macro_rules! chart_widget {
+    () => {
+        fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut ChartData, _env: &Env) {
+            self.core.event(ctx, event);
+        }
+
+        fn lifecycle(
+            &mut self,
+            ctx: &mut LifeCycleCtx,
+            event: &LifeCycle,
+            _data: &ChartData,
+            _env: &Env,
+        ) {
+            self.core.lifecycle(ctx, event);
+        }
+
+        fn update(
+            &mut self,
+            ctx: &mut UpdateCtx,
+            old_data: &ChartData,
+            data: &ChartData,
+            _env: &Env,
+        ) {
+            self.core.update(ctx, old_data, data);
+        }
+
+        fn layout(
+            &mut self,
+            _ctx: &mut LayoutCtx,
+            bc: &BoxConstraints,
+            _data: &ChartData,
+            _env: &Env,
+        ) -> Size {
+            bc.constrain(DEFAULT_SIZE)
+        }
+    };
+}
+
+/// A chart with a line for each series, over the categories.
+///
+/// The values of the category under the mouse are shown in a tooltip. See the
+/// [module documentation](self) for an example.
+pub struct LineChart {
+    core: Core,
+    points: bool,
+}
+
+impl LineChart {
+    pub fn new() -> Self {
+        LineChart {
+            core: Core::new(),
+            points: true,
+        }
+    }
+
+    /// Builder-style method for specifying whether the values are marked with dots.
+    ///
+    /// For the non-builder varient, see [`set_points`].
+    ///
+    /// [`set_points`]: #method.set_points
+    pub fn with_points(mut self, points: bool) -> Self {
+        self.set_points(points);
+        self
+    }
+
+    /// Set whether the values are marked with dots, which they are by default.
+    pub fn set_points(&mut self, points: bool) {
+        self.points = points;
+    }
+}
+
+chart_options!(LineChart);
+
+impl Widget<ChartData> for LineChart {
+    chart_widget!();
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ChartData, env: &Env) {
+        let axes = self.core.axes(ctx.size(), data);
+        axes.paint(ctx, data, &self.core.format, env);
+        self.core.paint_series_legend(ctx, data, env);
+
+        let hovered = self.core.hovered_category(&axes);
+        if let Some(index) = hovered {
+            let x = axes.x_at(index).round() + 0.5;
+            let line = Line::new((x, axes.plot.y0), (x, axes.plot.y1));
+            ctx.stroke(line, &env.get(theme::BORDER_DARK), 1.0);
+        }
+
+        for series in 0..data.series.len() {
+            let color = data.color(series);
+            let mut path = BezPath::new();
+            let points: Vec<Point> = (0..data.series[series].values.len())
+                .map(|index| {
+                    Point::new(
+                        axes.x_at(index),
+                        axes.y_at(self.core.value(data, series, index)),
+                    )
+                })
+                .collect();
+            for (index, point) in points.iter().enumerate() {
+                if index == 0 {
+                    path.move_to(*point);
+                } else {
+                    path.line_to(*point);
+                }
+            }
+            ctx.stroke(path, &color, 2.0);
+            for (index, point) in points.iter().enumerate() {
+                let radius = if hovered == Some(index) { 4.5 } else { 3.0 };
+                if self.points || hovered == Some(index) {
+                    ctx.fill(Circle::new(*point, radius), &color);
+                }
+            }
+        }
+
+        if let Some(index) = hovered {
+            self.core.paint_category_tooltip(ctx, data, index, env);
+        }
+    }
+}
+
+/// A chart with a bar for each value, grouped by category.
+///
+/// Negative values go down from zero. The values of the category under the mouse are shown in a
+/// tooltip.
+pub struct BarChart {
+    core: Core,
+}
+
+impl BarChart {
+    pub fn new() -> Self {
+        BarChart { core: Core::new() }
+    }
+}
+
+chart_options!(BarChart);
+
+impl Widget<ChartData> for BarChart {
+    chart_widget!();
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ChartData, env: &Env) {
+        let axes = self.core.axes(ctx.size(), data);
+        let hovered = self.core.hovered_category(&axes);
+        if let Some(index) = hovered {
+            let x = axes.plot.x0 + index as f64 * axes.slot();
+            let rect = Rect::new(x, axes.plot.y0, x + axes.slot(), axes.plot.y1);
+            ctx.fill(rect, &env.get(theme::FOREGROUND_LIGHT).with_alpha(0.08));
+        }
+        axes.paint(ctx, data, &self.core.format, env);
+        self.core.paint_series_legend(ctx, data, env);
+
+        // the bars of a category take most of its slot
+        let series_count = data.series.len().max(1);
+        let group_width = axes.slot() * 0.8;
+        let bar_width = group_width / series_count as f64;
+        let zero = axes.y_at(0.0);
+        for series in 0..data.series.len() {
+            let color = data.color(series);
+            for index in 0..data.series[series].values.len() {
+                let y = axes.y_at(self.core.value(data, series, index));
+                let x = axes.x_at(index) - group_width / 2.0 + series as f64 * bar_width;
+                let rect = Rect::new(x, zero.min(y), x + bar_width - 1.0, zero.max(y));
+                ctx.fill(rect, &color);
+            }
+        }
+
+        if let Some(index) = hovered {
+            self.core.paint_category_tooltip(ctx, data, index, env);
+        }
+    }
+}
+
+/// A chart with a slice for each category, of the first series.
+///
+/// Negative values are left out. The hovered slice is moved out, and its value and its share are
+/// shown in a tooltip.
+///
+/// ```
+/// # use druid::im::vector;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::chart::{ChartData, PieChart, Series};
+/// fn languages() -> impl Widget<ChartData> {
+///     PieChart::new().with_inner_radius(0.5)
+/// }
+///
+/// fn data() -> ChartData {
+///     ChartData::new(
+///         vector!["Rust".to_string(), "Other".to_string()],
+///         vector![Series::new("Lines", vector![9000.0, 1000.0])],
+///     )
+/// }
+/// ```
+pub struct PieChart {
+    core: Core,
+    inner_radius: f64,
+}
+
+impl PieChart {
+    pub fn new() -> Self {
+        PieChart {
+            core: Core::new(),
+            inner_radius: 0.0,
+        }
+    }
+
+    /// Builder-style method for making a donut chart, with a hole of `fraction` of the radius.
+    ///
+    /// For the non-builder varient, see [`set_inner_radius`].
+    ///
+    /// [`set_inner_radius`]: #method.set_inner_radius
+    pub fn with_inner_radius(mut self, fraction: f64) -> Self {
+        self.set_inner_radius(fraction);
+        self
+    }
+
+    /// Set the size of the hole as a fraction of the radius, 0 by default for no hole.
+    pub fn set_inner_radius(&mut self, fraction: f64) {
+        self.inner_radius = fraction.max(0.0).min(1.0);
+    }
+}
+
+chart_options!(PieChart);
+
+impl Widget<ChartData> for PieChart {
+    chart_widget!();
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &ChartData, env: &Env) {
+        let size = ctx.size();
+        let text_color = env.get(theme::TEXT_COLOR);
+        let legend_width = if self.core.legend { LEGEND_WIDTH } else { 0.0 };
+        let center = Point::new((size.width - legend_width) / 2.0, size.height / 2.0);
+        let radius = ((size.width - legend_width).min(size.height) / 2.0 - PADDING).max(0.0);
+
+        let count = data.categories();
+        let values: Vec<f64> = (0..count)
+            .map(|index| self.core.value(data, 0, index).max(0.0))
+            .collect();
+        let total: f64 = values.iter().sum();
+
+        if self.core.legend {
+            let x = size.width - legend_width + PADDING;
+            let mut y = PADDING;
+            for index in 0..count {
+                let layout = text(ctx, data.label(index), &text_color, env);
+                let swatch_y = y + (layout.size().height - SWATCH_SIZE) / 2.0;
+                let swatch = Rect::from_origin_size((x, swatch_y), (SWATCH_SIZE, SWATCH_SIZE));
+                ctx.fill(swatch.to_rounded_rect(2.0), &PALETTE[index % PALETTE.len()]);
+                ctx.draw_text(&layout, (x + SWATCH_SIZE + 4.0, y));
+                y += layout.size().height + 4.0;
+            }
+        }
+
+        if total <= 0.0 {
+            ctx.stroke(
+                Circle::new(center, radius),
+                &env.get(theme::BORDER_LIGHT),
+                1.0,
+            );
+            return;
+        }
+
+        // the slice under the mouse, by its angle from the top, clockwise
+        let hovered = self.core.mouse.and_then(|mouse| {
+            let offset = mouse - center;
+            let distance = offset.hypot();
+            if distance > radius || distance < radius * self.inner_radius {
+                return None;
+            }
+            let angle = (offset.atan2() + FRAC_PI_2).rem_euclid(2.0 * PI);
+            let mut end = 0.0;
+            values.iter().position(|value| {
+                end += value / total * 2.0 * PI;
+                angle < end
+            })
+        });
+
+        let mut start = -FRAC_PI_2;
+        for (index, value) in values.iter().enumerate() {
+            let sweep = value / total * 2.0 * PI;
+            let mut slice_center = center;
+            if hovered == Some(index) {
+                slice_center += Vec2::from_angle(start + sweep / 2.0) * 6.0;
+            }
+            let slice =
+                Circle::new(slice_center, radius).segment(radius * self.inner_radius, start, sweep);
+            ctx.fill(slice, &PALETTE[index % PALETTE.len()]);
+            start += sweep;
+        }
+
+        if let (Some(index), Some(mouse)) = (hovered, self.core.mouse) {
+            let value = data
+                .series
+                .get(0)
+                .and_then(|series| series.values.get(index))
+                .copied()
+                .unwrap_or(0.0);
+            let share = values[index] / total * 100.0;
+            let line = format!(
+                "{}: {} ({:.0}%)",
+                data.label(index),
+                (self.core.format)(value),
+                share
+            );
+            let color = PALETTE[index % PALETTE.len()];
+            paint_tooltip(ctx, mouse, vec![(Some(color), line)], env);
+        }
+    }
+}
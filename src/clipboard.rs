@@ -0,0 +1,249 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Copying and pasting without reaching for the clipboard in every widget.
+//!
+//! A [`ClipboardController`] near the root of a window handles the [`COPY_TO_CLIPBOARD`],
+//! [`COPY_FORMATS_TO_CLIPBOARD`] and [`REQUEST_PASTE`] commands, which any widget below it can
+//! also submit as notifications. That way a list or a tree can copy its selection without
+//! knowing about the clipboard.
+//!
+//! Besides text, custom formats like images can be copied as bytes with a [`FormatId`], on the
+//! platforms supporting them.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, Controller, LabelText};
+use druid::{Application, Clipboard, ClipboardFormat, FormatId, Selector, WidgetId};
+
+/// Put the text on the clipboard.
+pub const COPY_TO_CLIPBOARD: Selector<String> =
+    Selector::new("druid-widget-nursery.clipboard.copy");
+
+/// Put the data on the clipboard in several formats, in the order of preference, like an image
+/// as PNG with a text fallback.
+pub const COPY_FORMATS_TO_CLIPBOARD: Selector<Vec<ClipboardFormat>> =
+    Selector::new("druid-widget-nursery.clipboard.copy-formats");
+
+/// Read the clipboard, answered with [`PASTE`] sent to the target of the request.
+pub const REQUEST_PASTE: Selector<PasteRequest> =
+    Selector::new("druid-widget-nursery.clipboard.request-paste");
+
+/// The content of the clipboard, as answer to a [`REQUEST_PASTE`].
+pub const PASTE: Selector<Pasted> = Selector::new("druid-widget-nursery.clipboard.paste");
+
+/// Which widget wants the content of the clipboard, and in which formats.
+#[derive(Debug, Clone)]
+pub struct PasteRequest {
+    pub target: WidgetId,
+    /// The accepted formats in the order of preference, or empty for text.
+    pub formats: Vec<FormatId>,
+}
+
+impl PasteRequest {
+    /// Request the text on the clipboard.
+    pub fn text(target: WidgetId) -> Self {
+        PasteRequest {
+            target,
+            formats: Vec::new(),
+        }
+    }
+
+    /// Request the first of `formats` that is on the clipboard.
+    pub fn formats(target: WidgetId, formats: impl Into<Vec<FormatId>>) -> Self {
+        PasteRequest {
+            target,
+            formats: formats.into(),
+        }
+    }
+}
+
+/// Content read from the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pasted {
+    Text(String),
+    /// The bytes of a format other than text.
+    Format(FormatId, Vec<u8>),
+}
+
+/// Read the first of `formats` that is on the clipboard, or the text if `formats` is empty.
+pub fn read_clipboard(clipboard: &Clipboard, formats: &[FormatId]) -> Option<Pasted> {
+    if formats.is_empty() {
+        return clipboard.get_string().map(Pasted::Text);
+    }
+    let format = clipboard.preferred_format(formats)?;
+    if format == ClipboardFormat::TEXT {
+        clipboard.get_string().map(Pasted::Text)
+    } else {
+        clipboard
+            .get_format(format)
+            .map(|data| Pasted::Format(format, data))
+    }
+}
+
+/// A controller handling the clipboard commands for the widgets below it.
+///
+/// See the [module documentation](self) for the commands.
+#[derive(Debug, Default)]
+pub struct ClipboardController;
+
+impl ClipboardController {
+    fn copy(&self, text: Option<&String>, formats: Option<&Vec<ClipboardFormat>>) -> bool {
+        if let Some(text) = text {
+            Application::global().clipboard().put_string(text);
+        } else if let Some(formats) = formats {
+            Application::global().clipboard().put_formats(formats);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn paste(&self, ctx: &mut EventCtx, request: Option<&PasteRequest>) -> bool {
+        let request = match request {
+            Some(request) => request,
+            None => return false,
+        };
+        let clipboard = Application::global().clipboard();
+        if let Some(pasted) = read_clipboard(&clipboard, &request.formats) {
+            ctx.submit_command(PASTE.with(pasted).to(request.target));
+        }
+        true
+    }
+}
+
+impl<T, W: Widget<T>> Controller<T, W> for ClipboardController {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let handled = match event {
+            Event::Command(cmd) => {
+                self.copy(
+                    cmd.get(COPY_TO_CLIPBOARD),
+                    cmd.get(COPY_FORMATS_TO_CLIPBOARD),
+                ) || self.paste(ctx, cmd.get(REQUEST_PASTE))
+            }
+            Event::Notification(notification) => {
+                self.copy(
+                    notification.get(COPY_TO_CLIPBOARD),
+                    notification.get(COPY_FORMATS_TO_CLIPBOARD),
+                ) || self.paste(ctx, notification.get(REQUEST_PASTE))
+            }
+            _ => false,
+        };
+        if handled {
+            ctx.set_handled();
+        } else {
+            child.event(ctx, event, data, env);
+        }
+    }
+}
+
+/// A button putting content made from its data on the clipboard when clicked.
+pub struct CopyButton<T> {
+    button: Button<T>,
+    #[allow(clippy::type_complexity)]
+    content: Box<dyn Fn(&T, &Env) -> Vec<ClipboardFormat>>,
+}
+
+impl<T: Data> CopyButton<T> {
+    /// A "Copy" button copying the text made by `text`.
+    pub fn new(text: impl Fn(&T, &Env) -> String + 'static) -> Self {
+        Self::formats(move |data, env| vec![text(data, env).into()])
+    }
+
+    /// A "Copy" button copying the formats made by `formats`, in the order of preference.
+    pub fn formats(formats: impl Fn(&T, &Env) -> Vec<ClipboardFormat> + 'static) -> Self {
+        CopyButton {
+            button: Button::new("Copy"),
+            content: Box::new(formats),
+        }
+    }
+
+    /// Builder-style method for specifying the label of the button.
+    pub fn with_label(mut self, label: impl Into<LabelText<T>>) -> Self {
+        self.button = Button::new(label);
+        self
+    }
+}
+
+impl<T: Data> Widget<T> for CopyButton<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        // the button is clicked when the mouse is released over it after being pressed on it
+        if let Event::MouseUp(_) = event {
+            if ctx.is_active() && ctx.is_hot() {
+                let formats = (self.content)(data, env);
+                Application::global().clipboard().put_formats(&formats);
+            }
+        }
+        self.button.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.button.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.button.update(ctx, old_data, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.button.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.button.paint(ctx, data, env);
+    }
+}
+
+/// A controller merging pasted content into the data of its widget.
+///
+/// It handles the paste event of the focused widget, and [`PASTE`] answers sent to the widget.
+pub struct PasteTarget<T> {
+    formats: Vec<FormatId>,
+    #[allow(clippy::type_complexity)]
+    on_paste: Box<dyn Fn(&mut EventCtx, Pasted, &mut T, &Env)>,
+}
+
+impl<T> PasteTarget<T> {
+    /// Accept text.
+    pub fn new(on_paste: impl Fn(&mut EventCtx, Pasted, &mut T, &Env) + 'static) -> Self {
+        PasteTarget {
+            formats: Vec::new(),
+            on_paste: Box::new(on_paste),
+        }
+    }
+
+    /// Builder-style method for specifying the accepted formats, in the order of preference.
+    pub fn with_formats(mut self, formats: impl Into<Vec<FormatId>>) -> Self {
+        self.formats = formats.into();
+        self
+    }
+}
+
+impl<T, W: Widget<T>> Controller<T, W> for PasteTarget<T> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Paste(clipboard) => {
+                if let Some(pasted) = read_clipboard(clipboard, &self.formats) {
+                    (self.on_paste)(ctx, pasted, data, env);
+                }
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(PASTE) => {
+                let pasted = cmd.get_unchecked(PASTE).clone();
+                (self.on_paste)(ctx, pasted, data, env);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
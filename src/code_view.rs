@@ -0,0 +1,466 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A read-only view of code or logs, with line numbers and syntax highlighting.
+
+use std::ops::Range;
+
+use druid::piet::{PietText, PietTextLayout, Text, TextAttribute, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{
+    theme, Affine, Application, Color, Cursor, FontDescriptor, FontFamily, HotKey, KeyOrValue,
+    Point, Rect, Selector, SysMods, Vec2,
+};
+
+/// Highlights a line of a [`CodeView`] and scrolls to it, with the number of the line from 1, or
+/// `None` to remove the highlight.
+pub const HIGHLIGHT_LINE: Selector<Option<usize>> =
+    Selector::new("druid-widget-nursery.code-view.highlight-line");
+
+const PADDING: f64 = 4.0;
+/// The space on each side of the line numbers.
+const GUTTER_PADDING: f64 = 8.0;
+
+/// Colors the text of a [`CodeView`].
+///
+/// This is implemented for closures, and by `SyntectHighlighter` with the `syntect` feature.
+pub trait Highlighter {
+    /// The colors of ranges of `text`, in bytes. The rest of the text has the text color.
+    fn highlight(&self, text: &str) -> Vec<(Range<usize>, Color)>;
+}
+
+impl<F: Fn(&str) -> Vec<(Range<usize>, Color)>> Highlighter for F {
+    fn highlight(&self, text: &str) -> Vec<(Range<usize>, Color)> {
+        self(text)
+    }
+}
+
+/// A [`Highlighter`] using the default syntaxes and themes of `syntect`.
+#[cfg(feature = "syntect")]
+pub struct SyntectHighlighter {
+    syntaxes: syntect::parsing::SyntaxSet,
+    theme: syntect::highlighting::Theme,
+    extension: String,
+}
+
+#[cfg(feature = "syntect")]
+impl SyntectHighlighter {
+    /// Highlight the language of the files with `extension`, like `"rs"`, with a theme of
+    /// `syntect` like `"base16-ocean.dark"`, which is used when `theme` is not found.
+    pub fn new(extension: &str, theme: &str) -> Self {
+        let mut themes = syntect::highlighting::ThemeSet::load_defaults().themes;
+        let theme = themes
+            .remove(theme)
+            .or_else(|| themes.remove("base16-ocean.dark"))
+            .expect("missing default theme");
+        SyntectHighlighter {
+            syntaxes: syntect::parsing::SyntaxSet::load_defaults_newlines(),
+            theme,
+            extension: extension.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "syntect")]
+impl Highlighter for SyntectHighlighter {
+    fn highlight(&self, text: &str) -> Vec<(Range<usize>, Color)> {
+        use syntect::easy::HighlightLines;
+        use syntect::util::LinesWithEndings;
+
+        let syntax = self
+            .syntaxes
+            .find_syntax_by_extension(&self.extension)
+            .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text());
+        let mut lines = HighlightLines::new(syntax, &self.theme);
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        for line in LinesWithEndings::from(text) {
+            match lines.highlight_line(line, &self.syntaxes) {
+                Ok(pieces) => {
+                    for (style, piece) in pieces {
+                        let color = style.foreground;
+                        let range = offset..offset + piece.len();
+                        spans.push((range, Color::rgba8(color.r, color.g, color.b, color.a)));
+                        offset += piece.len();
+                    }
+                }
+                Err(_) => offset += line.len(),
+            }
+        }
+        spans
+    }
+}
+
+/// A read-only view of text in a monospace font, with line numbers, like code, logs or diffs.
+///
+/// The text is colored by a [`Highlighter`], and scrolled horizontally with shift and the mouse
+/// wheel; it takes the height of all its lines, so put it in a `Scroll` to scroll vertically.
+/// The text can be selected with the mouse and copied with the usual shortcut. A line is
+/// highlighted with [`HIGHLIGHT_LINE`] or [`with_highlighted_line`].
+///
+/// ```
+/// # use druid::{Color, Widget, WidgetExt};
+/// # use druid::widget::Scroll;
+/// # use druid_widget_nursery::CodeView;
+/// fn log() -> impl Widget<String> {
+///     let errors = |text: &str| {
+///         let mut spans = Vec::new();
+///         let mut offset = 0;
+///         for line in text.split_inclusive('\n') {
+///             if line.starts_with("error") {
+///                 spans.push((offset..offset + line.len(), Color::rgb8(0xd3, 0x2f, 0x2f)));
+///             }
+///             offset += line.len();
+///         }
+///         spans
+///     };
+///     Scroll::new(CodeView::new().with_highlighter(errors)).vertical()
+/// }
+/// ```
+///
+/// [`with_highlighted_line`]: #method.with_highlighted_line
+pub struct CodeView {
+    highlighter: Option<Box<dyn Highlighter>>,
+    line_numbers: bool,
+    font: KeyOrValue<FontDescriptor>,
+    highlighted_line: Option<usize>,
+    /// The colors of the text, from the highlighter.
+    spans: Vec<(Range<usize>, Color)>,
+    layout: Option<PietTextLayout>,
+    gutter_width: f64,
+    scroll_x: f64,
+    /// Where the selection started, and where it ends, in bytes.
+    selection: Option<(usize, usize)>,
+}
+
+impl CodeView {
+    pub fn new() -> Self {
+        CodeView {
+            highlighter: None,
+            line_numbers: true,
+            font: FontDescriptor::new(FontFamily::MONOSPACE).into(),
+            highlighted_line: None,
+            spans: Vec::new(),
+            layout: None,
+            gutter_width: 0.0,
+            scroll_x: 0.0,
+            selection: None,
+        }
+    }
+
+    /// Builder-style method for coloring the text with `highlighter`.
+    pub fn with_highlighter(mut self, highlighter: impl Highlighter + 'static) -> Self {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    /// Builder-style method for specifying whether the lines are numbered.
+    ///
+    /// For the non-builder varient, see [`set_line_numbers`].
+    ///
+    /// [`set_line_numbers`]: #method.set_line_numbers
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.set_line_numbers(line_numbers);
+        self
+    }
+
+    /// Set whether the lines are numbered, which they are by default.
+    pub fn set_line_numbers(&mut self, line_numbers: bool) {
+        self.line_numbers = line_numbers;
+    }
+
+    /// Builder-style method for specifying the font, a monospace font by default.
+    ///
+    /// For the non-builder varient, see [`set_font`].
+    ///
+    /// [`set_font`]: #method.set_font
+    pub fn with_font(mut self, font: impl Into<KeyOrValue<FontDescriptor>>) -> Self {
+        self.set_font(font);
+        self
+    }
+
+    /// Set the font of the text and of the line numbers.
+    pub fn set_font(&mut self, font: impl Into<KeyOrValue<FontDescriptor>>) {
+        self.font = font.into();
+        self.layout = None;
+    }
+
+    /// Builder-style method for highlighting a line, numbered from 1.
+    ///
+    /// For the non-builder varient, see [`set_highlighted_line`].
+    ///
+    /// [`set_highlighted_line`]: #method.set_highlighted_line
+    pub fn with_highlighted_line(mut self, line: usize) -> Self {
+        self.set_highlighted_line(Some(line));
+        self
+    }
+
+    /// Set the line highlighted, numbered from 1, or `None` for no highlight.
+    pub fn set_highlighted_line(&mut self, line: Option<usize>) {
+        self.highlighted_line = line;
+    }
+
+    /// The x of the text, after the line numbers.
+    fn text_x(&self) -> f64 {
+        self.gutter_width + PADDING
+    }
+
+    /// The offset of the text under `pos`, in bytes.
+    fn offset_at(&self, pos: Point) -> Option<usize> {
+        let layout = self.layout.as_ref()?;
+        let pos = Point::new(pos.x - self.text_x() + self.scroll_x, pos.y - PADDING);
+        Some(layout.hit_test_point(pos).idx)
+    }
+
+    /// The rect of a line numbered from 1, in the widget.
+    fn line_rect(&self, line: usize, width: f64) -> Option<Rect> {
+        let metric = self.layout.as_ref()?.line_metric(line.checked_sub(1)?)?;
+        let y = PADDING + metric.y_offset;
+        Some(Rect::new(0.0, y, width, y + metric.height))
+    }
+
+    fn selected_range(&self) -> Option<Range<usize>> {
+        let (anchor, active) = self.selection?;
+        Some(anchor.min(active)..anchor.max(active)).filter(|range| !range.is_empty())
+    }
+
+    fn build_layout(&mut self, text: &mut PietText, data: &str, env: &Env) {
+        let font = self.font.resolve(env);
+        let text_color = env.get(theme::TEXT_COLOR);
+        let mut builder = text
+            .new_text_layout(data.to_string())
+            .font(font.family.clone(), font.size)
+            .default_attribute(TextAttribute::Weight(font.weight))
+            .text_color(text_color);
+        for (range, color) in &self.spans {
+            let range = range.start.min(data.len())..range.end.min(data.len());
+            builder = builder.range_attribute(range, TextAttribute::TextColor(*color));
+        }
+        let layout = builder.build().unwrap();
+
+        self.gutter_width = if self.line_numbers {
+            let widest = layout.line_count().to_string();
+            let numbers = text
+                .new_text_layout(widest)
+                .font(font.family, font.size)
+                .build()
+                .unwrap();
+            numbers.size().width + 2.0 * GUTTER_PADDING
+        } else {
+            0.0
+        };
+        self.layout = Some(layout);
+    }
+
+    fn scroll_by(&mut self, ctx: &mut EventCtx, delta: f64) {
+        let content = self
+            .layout
+            .as_ref()
+            .map_or(0.0, |layout| layout.size().width);
+        let view = ctx.size().width - self.text_x() - PADDING;
+        let scroll_x = (self.scroll_x + delta).min(content - view).max(0.0);
+        if scroll_x != self.scroll_x {
+            self.scroll_x = scroll_x;
+            ctx.request_paint();
+        }
+    }
+}
+
+impl Default for CodeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data + AsRef<str>> Widget<T> for CodeView {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.request_focus();
+                ctx.set_active(true);
+                if let Some(offset) = self.offset_at(mouse.pos) {
+                    self.selection = match self.selection {
+                        Some((anchor, _)) if mouse.mods.shift() => Some((anchor, offset)),
+                        _ => Some((offset, offset)),
+                    };
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if mouse.pos.x >= self.gutter_width {
+                    ctx.set_cursor(&Cursor::IBeam);
+                } else {
+                    ctx.clear_cursor();
+                }
+                if ctx.is_active() {
+                    if let (Some((anchor, _)), Some(offset)) =
+                        (self.selection, self.offset_at(mouse.pos))
+                    {
+                        self.selection = Some((anchor, offset));
+                        ctx.request_paint();
+                    }
+                }
+            }
+            Event::MouseUp(_) if ctx.is_active() => ctx.set_active(false),
+            Event::Wheel(wheel) => {
+                let delta = if wheel.mods.shift() {
+                    wheel.wheel_delta.y
+                } else {
+                    wheel.wheel_delta.x
+                };
+                if delta != 0.0 {
+                    self.scroll_by(ctx, delta);
+                    ctx.set_handled();
+                }
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                if HotKey::new(SysMods::Cmd, "c").matches(key) {
+                    if let Some(range) = self.selected_range() {
+                        let text = data.as_ref().get(range).unwrap_or_default();
+                        Application::global().clipboard().put_string(text);
+                    }
+                    ctx.set_handled();
+                } else if HotKey::new(SysMods::Cmd, "a").matches(key) {
+                    self.selection = Some((0, data.as_ref().len()));
+                    ctx.request_paint();
+                    ctx.set_handled();
+                }
+            }
+            Event::Command(cmd) if cmd.is(HIGHLIGHT_LINE) => {
+                self.highlighted_line = *cmd.get_unchecked(HIGHLIGHT_LINE);
+                let width = ctx.size().width;
+                if let Some(rect) = self
+                    .highlighted_line
+                    .and_then(|line| self.line_rect(line, width))
+                {
+                    ctx.scroll_area_to_view(rect);
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.register_for_focus();
+                if let Some(highlighter) = &self.highlighter {
+                    self.spans = highlighter.highlight(data.as_ref());
+                }
+            }
+            LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            if let Some(highlighter) = &self.highlighter {
+                self.spans = highlighter.highlight(data.as_ref());
+            }
+            self.selection = None;
+            self.layout = None;
+            ctx.request_layout();
+        } else if ctx.env_changed() {
+            self.layout = None;
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if self.layout.is_none() {
+            self.build_layout(ctx.text(), data.as_ref(), env);
+        }
+        let content = self
+            .layout
+            .as_ref()
+            .map_or(Size::ZERO, |layout| layout.size());
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            self.text_x() + content.width + PADDING
+        };
+        let size = bc.constrain(Size::new(width, content.height + 2.0 * PADDING));
+        let view = size.width - self.text_x() - PADDING;
+        self.scroll_x = self.scroll_x.min(content.width - view).max(0.0);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let layout = match &self.layout {
+            Some(layout) => layout,
+            None => return,
+        };
+        let size = ctx.size();
+        let visible = ctx.region().bounding_box();
+
+        if let Some(rect) = self
+            .highlighted_line
+            .and_then(|line| self.line_rect(line, size.width))
+        {
+            ctx.fill(rect, &env.get(theme::PRIMARY_DARK).with_alpha(0.3));
+        }
+
+        if self.line_numbers {
+            let gutter = Rect::new(0.0, 0.0, self.gutter_width, size.height);
+            ctx.fill(gutter, &env.get(theme::BACKGROUND_LIGHT));
+            let font = self.font.resolve(env);
+            for line in 0..layout.line_count() {
+                let metric = match layout.line_metric(line) {
+                    Some(metric) => metric,
+                    None => break,
+                };
+                let y = PADDING + metric.y_offset;
+                if y + metric.height < visible.y0 || y > visible.y1 {
+                    continue;
+                }
+                let color = if self.highlighted_line == Some(line + 1) {
+                    env.get(theme::TEXT_COLOR)
+                } else {
+                    env.get(theme::DISABLED_TEXT_COLOR)
+                };
+                let number = ctx
+                    .text()
+                    .new_text_layout((line + 1).to_string())
+                    .font(font.family.clone(), font.size)
+                    .text_color(color)
+                    .build()
+                    .unwrap();
+                let x = self.gutter_width - GUTTER_PADDING - number.size().width;
+                ctx.draw_text(&number, (x, y));
+            }
+        }
+
+        let clip = Rect::new(self.text_x(), 0.0, size.width, size.height);
+        let selection_color = if ctx.has_focus() {
+            env.get(theme::SELECTED_TEXT_BACKGROUND_COLOR)
+        } else {
+            env.get(theme::SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR)
+        };
+        let selection = self.selected_range();
+        let offset = Vec2::new(self.text_x() - self.scroll_x, PADDING);
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            ctx.transform(Affine::translate(offset));
+            if let Some(range) = selection {
+                for rect in layout.rects_for_range(range) {
+                    ctx.fill(rect, &selection_color);
+                }
+            }
+            ctx.draw_text(layout, Point::ORIGIN);
+        });
+    }
+}
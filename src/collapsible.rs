@@ -0,0 +1,356 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Panels expanding and collapsing under their header, alone or in an [`Accordion`].
+
+use druid::widget::prelude::*;
+use druid::widget::{Label, LabelText};
+use druid::{KeyOrValue, Point, Rect, Selector, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve};
+use crate::Wedge;
+
+/// Expand (`true`) or collapse (`false`) the [`Collapsible`] this is sent to.
+pub const SET_EXPANDED: Selector<bool> =
+    Selector::new("druid-widget-nursery.collapsible.set-expanded");
+
+/// Notification sent by a [`Collapsible`] when it was expanded (`true`) or collapsed (`false`)
+/// by its header or by [`SET_EXPANDED`].
+pub const EXPANDED_CHANGED: Selector<bool> =
+    Selector::new("druid-widget-nursery.collapsible.expanded-changed");
+
+/// The space between the wedge and the header.
+const HEADER_SPACING: f64 = 4.0;
+
+/// A panel showing its body under a header, expanded or collapsed by clicking on the header.
+///
+/// The body keeps its state while it is collapsed. Expanding and collapsing animate its height
+/// and the wedge in front of the header.
+///
+/// ```
+/// # use druid::widget::TextBox;
+/// # use druid::{Data, Lens, Widget, WidgetExt};
+/// # use druid_widget_nursery::{Accordion, Collapsible};
+/// #[derive(Clone, Data, Lens)]
+/// struct Settings {
+///     name: String,
+///     email: String,
+/// }
+///
+/// fn settings() -> impl Widget<Settings> {
+///     Accordion::new()
+///         .with_panel(
+///             Collapsible::new("Name", TextBox::new().lens(Settings::name)).expanded(true),
+///         )
+///         .with_panel(Collapsible::new("Email", TextBox::new().lens(Settings::email)))
+///         .with_single_open(true)
+/// }
+/// ```
+pub struct Collapsible<T> {
+    wedge: WidgetPod<bool, Wedge>,
+    header: WidgetPod<T, Box<dyn Widget<T>>>,
+    body: WidgetPod<T, Box<dyn Widget<T>>>,
+    expanded: bool,
+    /// 0 for collapsed, 1 for expanded.
+    openness: Animated<f64>,
+    header_height: f64,
+}
+
+impl<T: Data> Collapsible<T> {
+    /// Create a collapsed panel with a label as the header.
+    pub fn new(header: impl Into<LabelText<T>>, body: impl Widget<T> + 'static) -> Self {
+        Self::from_widgets(Label::new(header), body)
+    }
+
+    /// Create a collapsed panel with an arbitrary header widget.
+    pub fn from_widgets(header: impl Widget<T> + 'static, body: impl Widget<T> + 'static) -> Self {
+        Collapsible {
+            wedge: WidgetPod::new(Wedge::new()),
+            header: WidgetPod::new(Box::new(header)),
+            body: WidgetPod::new(Box::new(body)),
+            expanded: false,
+            openness: Animated::jump(0.0)
+                .duration(0.2)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+            header_height: 0.0,
+        }
+    }
+
+    /// Builder-style method for specifying whether the panel starts expanded.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self.openness
+            .jump_to_value(if expanded { 1.0 } else { 0.0 });
+        self
+    }
+
+    /// Builder-style method for specifying the duration of the animation.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the animation in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.openness.set_duration(duration);
+    }
+
+    /// Whether the panel is expanded, or expanding.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    fn set_expanded(&mut self, ctx: &mut EventCtx, expanded: bool) {
+        if self.expanded == expanded {
+            return;
+        }
+        self.expanded = expanded;
+        self.openness.animate(ctx, if expanded { 1.0 } else { 0.0 });
+        // the wedge gets the new state in update
+        ctx.request_update();
+        ctx.request_layout();
+        ctx.submit_notification(EXPANDED_CHANGED.with(expanded));
+    }
+
+    /// Whether the body is visible, at least partly.
+    fn body_visible(&self) -> bool {
+        self.expanded || self.openness.animating()
+    }
+}
+
+impl<T: Data> Widget<T> for Collapsible<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::AnimFrame(nanos) if self.openness.animating() => {
+                self.openness.update(ctx, *nanos);
+            }
+            Event::Command(cmd) if cmd.is(SET_EXPANDED) => {
+                self.set_expanded(ctx, *cmd.get_unchecked(SET_EXPANDED));
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseDown(mouse) if mouse.pos.y < self.header_height => {
+                ctx.set_active(true);
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() && mouse.pos.y < self.header_height {
+                    self.set_expanded(ctx, !self.expanded);
+                }
+            }
+            _ => {}
+        }
+
+        // the wedge only shows the state, its own clicks are dropped
+        let mut expanded = self.expanded;
+        self.wedge.event(ctx, event, &mut expanded, env);
+        self.header.event(ctx, event, data, env);
+        if self.body_visible() || event.should_propagate_to_hidden() {
+            self.body.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.wedge.lifecycle(ctx, event, &self.expanded, env);
+        self.header.lifecycle(ctx, event, data, env);
+        self.body.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.wedge.update(ctx, &self.expanded, env);
+        self.header.update(ctx, data, env);
+        self.body.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let max_width = bc.max().width;
+        let wedge_size = self.wedge.layout(ctx, &bc.loosen(), &self.expanded, env);
+        let header_bc = BoxConstraints::new(
+            Size::ZERO,
+            Size::new(
+                (max_width - wedge_size.width - HEADER_SPACING).max(0.0),
+                f64::INFINITY,
+            ),
+        );
+        let header_size = self.header.layout(ctx, &header_bc, data, env);
+        self.header_height = wedge_size.height.max(header_size.height);
+        self.wedge.set_origin(
+            ctx,
+            Point::new(0.0, (self.header_height - wedge_size.height) / 2.0),
+        );
+        self.header.set_origin(
+            ctx,
+            Point::new(
+                wedge_size.width + HEADER_SPACING,
+                (self.header_height - header_size.height) / 2.0,
+            ),
+        );
+
+        // the body is laid out while collapsed too, so the width doesn't change
+        let body_bc = BoxConstraints::new(
+            Size::new(bc.min().width, 0.0),
+            Size::new(max_width, f64::INFINITY),
+        );
+        let body_size = self.body.layout(ctx, &body_bc, data, env);
+        self.body
+            .set_origin(ctx, Point::new(0.0, self.header_height));
+
+        let width = body_size
+            .width
+            .max(wedge_size.width + HEADER_SPACING + header_size.width);
+        let height = self.header_height + body_size.height * self.openness.get();
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.wedge.paint(ctx, &self.expanded, env);
+        self.header.paint(ctx, data, env);
+        if self.body_visible() {
+            let size = ctx.size();
+            let clip = Rect::new(0.0, self.header_height, size.width, size.height);
+            ctx.with_save(|ctx| {
+                ctx.clip(clip);
+                self.body.paint(ctx, data, env);
+            });
+        }
+    }
+}
+
+/// A column of [`Collapsible`] panels, optionally keeping only one of them open.
+///
+/// See [`Collapsible`] for an example.
+pub struct Accordion<T> {
+    panels: Vec<WidgetPod<T, Collapsible<T>>>,
+    single_open: bool,
+    spacing: KeyOrValue<f64>,
+}
+
+impl<T: Data> Accordion<T> {
+    pub fn new() -> Self {
+        Accordion {
+            panels: Vec::new(),
+            single_open: false,
+            spacing: KeyOrValue::Concrete(0.0),
+        }
+    }
+
+    /// Builder-style method for adding a panel.
+    pub fn with_panel(mut self, panel: Collapsible<T>) -> Self {
+        self.add_panel(panel);
+        self
+    }
+
+    /// Add a panel.
+    pub fn add_panel(&mut self, panel: Collapsible<T>) {
+        self.panels.push(WidgetPod::new(panel));
+    }
+
+    /// Builder-style method for collapsing the other panels when one is expanded.
+    ///
+    /// For the non-builder varient, see [`set_single_open`].
+    ///
+    /// [`set_single_open`]: #method.set_single_open
+    pub fn with_single_open(mut self, single_open: bool) -> Self {
+        self.set_single_open(single_open);
+        self
+    }
+
+    /// Set whether expanding a panel collapses the others.
+    pub fn set_single_open(&mut self, single_open: bool) {
+        self.single_open = single_open;
+    }
+
+    /// Builder-style method for specifying the spacing between the panels.
+    ///
+    /// For the non-builder varient, see [`set_spacing`].
+    ///
+    /// [`set_spacing`]: #method.set_spacing
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Set the spacing between the panels.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+}
+
+impl<T: Data> Default for Accordion<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for Accordion<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Notification(notification) = event {
+            if notification.get(EXPANDED_CHANGED) == Some(&true) && self.single_open {
+                let source = notification.source();
+                for panel in &self.panels {
+                    if panel.id() != source && panel.widget().is_expanded() {
+                        ctx.submit_command(SET_EXPANDED.with(false).to(panel.id()));
+                    }
+                }
+            }
+            return;
+        }
+        for panel in &mut self.panels {
+            panel.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for panel in &mut self.panels {
+            panel.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for panel in &mut self.panels {
+            panel.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let spacing = self.spacing.resolve(env);
+        let panel_bc = BoxConstraints::new(
+            Size::new(bc.min().width, 0.0),
+            Size::new(bc.max().width, f64::INFINITY),
+        );
+        let mut width = bc.min().width;
+        let mut y = 0.0;
+        for (index, panel) in self.panels.iter_mut().enumerate() {
+            if index > 0 {
+                y += spacing;
+            }
+            let size = panel.layout(ctx, &panel_bc, data, env);
+            panel.set_origin(ctx, Point::new(0.0, y));
+            width = width.max(size.width);
+            y += size.height;
+        }
+        bc.constrain(Size::new(width, y))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for panel in &mut self.panels {
+            panel.paint(ctx, data, env);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Following the light or dark appearance of the operating system.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::{Data, ExtEventSink, Lens, Selector, WidgetId};
+
+use crate::{Theme, ThemeScope};
+
+/// Sent by the watcher thread when the color scheme of the system changed.
+const COLOR_SCHEME_CHANGED: Selector<ColorScheme> =
+    Selector::new("druid-widget-nursery.color-scheme.changed");
+
+/// How often the watcher thread asks the system for its color scheme.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The appearance preferred by the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Data)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// Ask the system for the current color scheme.
+    ///
+    /// This reads the `AppleInterfaceStyle` default on macOS, the `AppsUseLightTheme` registry
+    /// value on Windows and the GNOME `color-scheme` (or the name of the GTK theme) elsewhere.
+    /// Returns `None` if the color scheme could not be found out.
+    ///
+    /// This starts a process, so avoid calling it too often.
+    pub fn detect() -> Option<Self> {
+        detect()
+    }
+
+    /// Returns `true` for [`ColorScheme::Dark`].
+    pub fn is_dark(self) -> bool {
+        self == ColorScheme::Dark
+    }
+}
+
+/// Run `program` and return whether it succeeded, and its output.
+#[cfg(any(unix, windows))]
+fn run(program: &str, args: &[&str]) -> Option<(bool, String)> {
+    let mut command = std::process::Command::new(program);
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // don't flash a console window
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    Some((output.status.success(), stdout))
+}
+
+#[cfg(target_os = "macos")]
+fn detect() -> Option<ColorScheme> {
+    // the key is only set in dark mode
+    let (success, output) = run("defaults", &["read", "-g", "AppleInterfaceStyle"])?;
+    if success && output.trim() == "Dark" {
+        Some(ColorScheme::Dark)
+    } else {
+        Some(ColorScheme::Light)
+    }
+}
+
+#[cfg(windows)]
+fn detect() -> Option<ColorScheme> {
+    let (success, output) = run(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ],
+    )?;
+    if !success {
+        return None;
+    }
+    match output.split_whitespace().last()? {
+        "0x0" => Some(ColorScheme::Dark),
+        "0x1" => Some(ColorScheme::Light),
+        _ => None,
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn detect() -> Option<ColorScheme> {
+    let get = |key| match run("gsettings", &["get", "org.gnome.desktop.interface", key]) {
+        Some((true, output)) => Some(output.trim().trim_matches('\'').to_ascii_lowercase()),
+        _ => None,
+    };
+    match get("color-scheme").as_deref() {
+        Some("prefer-dark") => Some(ColorScheme::Dark),
+        Some("prefer-light") => Some(ColorScheme::Light),
+        // older desktops only have dark variants of themes
+        _ => match get("gtk-theme") {
+            Some(theme) if theme.contains("dark") => Some(ColorScheme::Dark),
+            Some(_) => Some(ColorScheme::Light),
+            None => None,
+        },
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn detect() -> Option<ColorScheme> {
+    None
+}
+
+/// A widget following the color scheme of the system.
+///
+/// The color scheme is detected when the window is connected, and then checked every few
+/// seconds on a background thread. It can be written to the data with [`with_lens`], and a
+/// light and a dark [`Theme`] can be applied to the child with [`with_themes`].
+///
+/// If the color scheme can't be detected on this platform, nothing happens.
+///
+/// [`with_lens`]: SystemColorScheme::with_lens
+/// [`with_themes`]: SystemColorScheme::with_themes
+pub struct SystemColorScheme<T> {
+    inner: ThemeScope<T>,
+    scheme: Option<ColorScheme>,
+    set_data: Option<Box<dyn Fn(&mut T, ColorScheme)>>,
+    /// The light and the dark theme.
+    themes: Option<(Theme, Theme)>,
+    /// Tells the watcher thread to stop.
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: Data> SystemColorScheme<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            inner: ThemeScope::new(Theme::default(), inner),
+            scheme: None,
+            set_data: None,
+            themes: None,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Builder-style method for writing the color scheme to the data.
+    pub fn with_lens(mut self, lens: impl Lens<T, ColorScheme> + 'static) -> Self {
+        self.set_data = Some(Box::new(move |data, scheme| {
+            lens.with_mut(data, |data| *data = scheme)
+        }));
+        self
+    }
+
+    /// Builder-style method for applying `light` or `dark` to the child, depending on the
+    /// color scheme.
+    ///
+    /// For the non-builder varient, see [`set_themes`].
+    ///
+    /// [`set_themes`]: SystemColorScheme::set_themes
+    pub fn with_themes(mut self, light: Theme, dark: Theme) -> Self {
+        self.set_themes(light, dark);
+        self
+    }
+
+    /// Apply `light` or `dark` to the child, depending on the color scheme.
+    ///
+    /// [`Theme::light`] and [`Theme::dark`] are a good start.
+    pub fn set_themes(&mut self, light: Theme, dark: Theme) {
+        self.themes = Some((light, dark));
+    }
+
+    /// Builder-style method for animating the switch between the themes over `duration`
+    /// seconds.
+    ///
+    /// For the non-builder varient, see [`set_transition`].
+    ///
+    /// [`set_transition`]: SystemColorScheme::set_transition
+    pub fn with_transition(mut self, duration: f64) -> Self {
+        self.set_transition(duration);
+        self
+    }
+
+    /// Set the duration of the switch between the themes in seconds.
+    pub fn set_transition(&mut self, duration: f64) {
+        self.inner.set_transition(duration);
+    }
+
+    /// The current color scheme, or `None` if it wasn't detected (yet).
+    pub fn scheme(&self) -> Option<ColorScheme> {
+        self.scheme
+    }
+
+    fn theme(&self, scheme: ColorScheme) -> Option<Theme> {
+        self.themes.as_ref().map(|(light, dark)| match scheme {
+            ColorScheme::Light => light.clone(),
+            ColorScheme::Dark => dark.clone(),
+        })
+    }
+
+    fn set_scheme(&mut self, data: &mut T, scheme: ColorScheme) {
+        self.scheme = Some(scheme);
+        if let Some(set_data) = &self.set_data {
+            set_data(data, scheme);
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for SystemColorScheme<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::WindowConnected => {
+                if let Some(scheme) = ColorScheme::detect() {
+                    self.set_scheme(data, scheme);
+                    // nothing was painted yet, don't animate
+                    if let Some(theme) = self.theme(scheme) {
+                        self.inner.replace_theme(theme);
+                    }
+                    start_watcher(
+                        ctx.get_external_handle(),
+                        ctx.widget_id(),
+                        scheme,
+                        self.stop.clone(),
+                    );
+                }
+            }
+            Event::Command(cmd) if cmd.is(COLOR_SCHEME_CHANGED) => {
+                let scheme = *cmd.get_unchecked(COLOR_SCHEME_CHANGED);
+                self.set_scheme(data, scheme);
+                if let Some(theme) = self.theme(scheme) {
+                    self.inner.set_theme(ctx, theme);
+                }
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, old_data, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.inner.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
+
+impl<T> Drop for SystemColorScheme<T> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn start_watcher(sink: ExtEventSink, target: WidgetId, scheme: ColorScheme, stop: Arc<AtomicBool>) {
+    std::thread::spawn(move || {
+        let mut scheme = scheme;
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(POLL_INTERVAL);
+            match ColorScheme::detect() {
+                Some(new_scheme) if new_scheme != scheme => {
+                    scheme = new_scheme;
+                    if sink
+                        .submit_command(COLOR_SCHEME_CHANGED, scheme, target)
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,224 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Logging of commands and notifications, for debugging.
+
+use std::time::Instant;
+
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::{AppDelegate, Command, DelegateCtx, Handled, Target, WindowHandle, WindowId};
+use tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
+use tracing::Level;
+
+type FilterFn = Box<dyn Fn(&str) -> bool>;
+
+/// Like [`tracing::event!`], with a level only known at runtime.
+macro_rules! log_at {
+    ($level:expr, $($arg:tt)+) => {
+        match $level {
+            Level::ERROR => tracing::event!(Level::ERROR, $($arg)+),
+            Level::WARN => tracing::event!(Level::WARN, $($arg)+),
+            Level::INFO => tracing::event!(Level::INFO, $($arg)+),
+            Level::DEBUG => tracing::event!(Level::DEBUG, $($arg)+),
+            _ => tracing::event!(Level::TRACE, $($arg)+),
+        }
+    };
+}
+
+/// Logs every command and notification passing through, with its selector,
+/// target and how long it took to handle.
+///
+/// Use it as a [`Controller`] to see what reaches a part of the widget tree,
+/// or wrap your [`AppDelegate`] with [`CommandLogger::delegate`] to see every
+/// command submitted in the app.
+pub struct CommandLogger {
+    filter: Option<FilterFn>,
+    level: Level,
+}
+
+impl Default for CommandLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandLogger {
+    /// Create a logger logging everything at [`Level::DEBUG`].
+    pub fn new() -> Self {
+        Self {
+            filter: None,
+            level: Level::Debug,
+        }
+    }
+
+    /// Builder-style method to only log selectors for which `filter` returns
+    /// `true`. It gets the selector key, e.g. `"druid-builtin.close-window"`.
+    pub fn filter(mut self, filter: impl Fn(&str) -> bool + 'static) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Builder-style method to only log selectors whose key contains `pattern`.
+    pub fn matching(self, pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        self.filter(move |key| key.contains(&pattern))
+    }
+
+    /// Builder-style method to set the log level.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Wrap an [`AppDelegate`], logging the commands it receives.
+    pub fn delegate<D>(self, inner: D) -> CommandLoggerDelegate<D> {
+        CommandLoggerDelegate {
+            inner,
+            logger: self,
+        }
+    }
+
+    /// Whether anything is logged at our level, checked before building the selector key.
+    fn enabled(&self) -> bool {
+        self.level <= STATIC_MAX_LEVEL && self.level <= LevelFilter::current()
+    }
+
+    fn accepts(&self, key: &str) -> bool {
+        self.filter.as_ref().map_or(true, |f| f(key))
+    }
+}
+
+impl<T, W: Widget<T>> Controller<T, W> for CommandLogger {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let start = Instant::now();
+        child.event(ctx, event, data, env);
+
+        match event {
+            Event::Command(cmd) if self.enabled() => {
+                let key = command_key(cmd);
+                if self.accepts(&key) {
+                    log_at!(
+                        self.level,
+                        "{:?} command {} to {:?}, handled: {}, took {:?}",
+                        ctx.widget_id(),
+                        key,
+                        cmd.target(),
+                        ctx.is_handled(),
+                        start.elapsed()
+                    );
+                }
+            }
+            Event::Notification(notification) if self.enabled() => {
+                let key = notification_key(notification);
+                if self.accepts(&key) {
+                    log_at!(
+                        self.level,
+                        "{:?} notification {} from {:?}, handled: {}, took {:?}",
+                        ctx.widget_id(),
+                        key,
+                        notification.source(),
+                        ctx.is_handled(),
+                        start.elapsed()
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// An [`AppDelegate`] logging the commands it receives, see [`CommandLogger::delegate`].
+pub struct CommandLoggerDelegate<D> {
+    inner: D,
+    logger: CommandLogger,
+}
+
+impl<T: Data, D: AppDelegate<T>> AppDelegate<T> for CommandLoggerDelegate<D> {
+    fn event(
+        &mut self,
+        ctx: &mut DelegateCtx,
+        window_id: WindowId,
+        event: Event,
+        data: &mut T,
+        env: &Env,
+    ) -> Option<Event> {
+        self.inner.event(ctx, window_id, event, data, env)
+    }
+
+    fn command(
+        &mut self,
+        ctx: &mut DelegateCtx,
+        target: Target,
+        cmd: &Command,
+        data: &mut T,
+        env: &Env,
+    ) -> Handled {
+        let start = Instant::now();
+        let handled = self.inner.command(ctx, target, cmd, data, env);
+        if !self.logger.enabled() {
+            return handled;
+        }
+        let key = command_key(cmd);
+        if self.logger.accepts(&key) {
+            log_at!(
+                self.logger.level,
+                "command {} to {:?}, handled by delegate: {}, took {:?}",
+                key,
+                target,
+                handled.is_handled(),
+                start.elapsed()
+            );
+        }
+        handled
+    }
+
+    fn window_added(
+        &mut self,
+        id: WindowId,
+        handle: WindowHandle,
+        data: &mut T,
+        env: &Env,
+        ctx: &mut DelegateCtx,
+    ) {
+        self.inner.window_added(id, handle, data, env, ctx)
+    }
+
+    fn window_removed(&mut self, id: WindowId, data: &mut T, env: &Env, ctx: &mut DelegateCtx) {
+        self.inner.window_removed(id, data, env, ctx)
+    }
+}
+
+// TODO: druid should expose the raw selector, until then we take it from the debug output.
+fn command_key(cmd: &Command) -> String {
+    // `Command { symbol: "key", payload: .., target: .. }`
+    let debug = format!("{:?}", cmd);
+    debug
+        .split("symbol: \"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap_or(&debug)
+        .to_string()
+}
+
+fn notification_key(notification: &druid::Notification) -> String {
+    // `Notification: Selector key from WidgetId(..)`
+    let debug = format!("{:?}", notification);
+    debug
+        .split("Selector ")
+        .nth(1)
+        .and_then(|rest| rest.rsplit(" from ").nth(1))
+        .unwrap_or(&debug)
+        .to_string()
+}
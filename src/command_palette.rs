@@ -0,0 +1,603 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A searchable overlay listing the commands of an app.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use druid::keyboard_types::Key;
+use druid::piet::{Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, Command, HotKey, Point, Rect, SysMods, WidgetPod};
+
+crate::selectors! {
+    /// Open the palette. Can be sent to the [`CommandPalette`] as a command, or submitted as a
+    /// notification by any widget it wraps.
+    SHOW_PALETTE,
+}
+
+const PALETTE_WIDTH: f64 = 480.0;
+/// The space between the top of the window and the palette.
+const PALETTE_TOP: f64 = 48.0;
+const PADDING: f64 = 6.0;
+const QUERY_HEIGHT: f64 = 30.0;
+const ROW_HEIGHT: f64 = 26.0;
+const MAX_ROWS: usize = 10;
+
+/// An entry of a [`CommandRegistry`].
+#[derive(Clone)]
+pub struct PaletteAction {
+    pub name: String,
+    /// The key chord shown next to the name, like "Ctrl+S".
+    pub shortcut: Option<String>,
+    pub command: Command,
+}
+
+#[derive(Default)]
+struct Registry {
+    actions: Vec<PaletteAction>,
+    /// The names of the actions used, most recent first.
+    recent: Vec<String>,
+}
+
+/// The actions listed by a [`CommandPalette`], shared between all its clones.
+///
+/// The registry can be kept in the app data, so actions can be added and removed while the app
+/// is running. It also remembers which actions were used most recently.
+#[derive(Clone, Default)]
+pub struct CommandRegistry(Rc<RefCell<Registry>>);
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an action submitting `command`, replacing any action with the same name.
+    pub fn register(&self, name: impl Into<String>, command: impl Into<Command>) {
+        self.add(PaletteAction {
+            name: name.into(),
+            shortcut: None,
+            command: command.into(),
+        });
+    }
+
+    /// Add an action submitting `command` and showing its shortcut, replacing any action with
+    /// the same name.
+    pub fn register_with_shortcut(
+        &self,
+        name: impl Into<String>,
+        shortcut: impl Into<String>,
+        command: impl Into<Command>,
+    ) {
+        self.add(PaletteAction {
+            name: name.into(),
+            shortcut: Some(shortcut.into()),
+            command: command.into(),
+        });
+    }
+
+    /// Remove the action with this name.
+    pub fn unregister(&self, name: &str) {
+        let mut registry = self.0.borrow_mut();
+        registry.actions.retain(|action| action.name != name);
+        registry.recent.retain(|recent| recent != name);
+    }
+
+    /// All actions, in the order they were registered.
+    pub fn actions(&self) -> Vec<PaletteAction> {
+        self.0.borrow().actions.clone()
+    }
+
+    fn add(&self, action: PaletteAction) {
+        let mut registry = self.0.borrow_mut();
+        match registry.actions.iter_mut().find(|a| a.name == action.name) {
+            Some(existing) => *existing = action,
+            None => registry.actions.push(action),
+        }
+    }
+
+    fn record_use(&self, name: &str) {
+        let mut registry = self.0.borrow_mut();
+        registry.recent.retain(|recent| recent != name);
+        registry.recent.insert(0, name.to_string());
+    }
+
+    /// The indices of the actions matching `query`, best match first.
+    fn search(&self, query: &str) -> Vec<usize> {
+        let registry = self.0.borrow();
+        let recency = |name: &str| {
+            registry
+                .recent
+                .iter()
+                .position(|recent| recent == name)
+                .unwrap_or(usize::MAX)
+        };
+        let mut matches: Vec<(i64, usize, usize)> = registry
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, action)| {
+                // without a query, the actions are listed in the order they were registered
+                let score = if query.trim().is_empty() {
+                    0
+                } else {
+                    fuzzy_score(query, &action.name)?
+                };
+                Some((-score, recency(&action.name), index))
+            })
+            .collect();
+        matches.sort_unstable();
+        matches.into_iter().map(|(_, _, index)| index).collect()
+    }
+}
+
+impl Data for CommandRegistry {
+    fn same(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// How well `query` matches `name`, or `None` if the characters of `query` don't appear in
+/// `name` in order. Consecutive characters and characters starting a word score higher.
+fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut last_match = None;
+    for q in query.chars().filter(|c| !c.is_whitespace()).map(lowercase) {
+        let found = (position..name.len()).find(|&i| lowercase(name[i]) == q)?;
+        score += 1;
+        if last_match.map_or(false, |last| last + 1 == found) {
+            score += 5;
+        }
+        let word_start = found == 0
+            || !name[found - 1].is_alphanumeric()
+            || (name[found - 1].is_lowercase() && name[found].is_uppercase());
+        if word_start {
+            score += 8;
+        }
+        last_match = Some(found);
+        position = found + 1;
+    }
+    // prefer shorter names among equally good matches
+    Some(score * 100 - name.len() as i64)
+}
+
+struct PaletteState {
+    query: String,
+    /// Indices into the actions of the registry.
+    matches: Vec<usize>,
+    selected: usize,
+    /// The first match shown.
+    scroll: usize,
+    hover: Option<usize>,
+}
+
+/// A widget showing a searchable list of commands on top of its child, usually the root widget
+/// of a window.
+///
+/// The palette opens with Ctrl+Shift+P (Cmd+Shift+P on macOS), or when it gets [`SHOW_PALETTE`].
+/// Typing filters the actions by fuzzy matching, the arrow keys move the selection, and Enter or
+/// a click submits the command of the action. Actions used recently are listed first.
+///
+/// ```
+/// # use druid::widget::Label;
+/// # use druid::{commands, Widget};
+/// # use druid_widget_nursery::CommandPalette;
+/// fn root() -> impl Widget<()> {
+///     CommandPalette::new(Label::new("Press Ctrl+Shift+P"))
+///         .with_action("Quit", commands::QUIT_APP)
+///         .with_action("Show about", commands::SHOW_ABOUT)
+/// }
+/// ```
+pub struct CommandPalette<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    registry: CommandRegistry,
+    hotkey: HotKey,
+    state: Option<PaletteState>,
+}
+
+impl<T: Data> CommandPalette<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        CommandPalette {
+            inner: WidgetPod::new(Box::new(inner)),
+            registry: CommandRegistry::new(),
+            hotkey: HotKey::new(SysMods::CmdShift, "P"),
+            state: None,
+        }
+    }
+
+    /// Builder-style method for adding an action.
+    pub fn with_action(self, name: impl Into<String>, command: impl Into<Command>) -> Self {
+        self.registry.register(name, command);
+        self
+    }
+
+    /// Builder-style method for using a shared registry instead of an own one.
+    ///
+    /// For the non-builder varient, see [`set_registry`].
+    ///
+    /// [`set_registry`]: #method.set_registry
+    pub fn with_registry(mut self, registry: CommandRegistry) -> Self {
+        self.set_registry(registry);
+        self
+    }
+
+    /// Set the registry the actions are taken from.
+    pub fn set_registry(&mut self, registry: CommandRegistry) {
+        self.registry = registry;
+    }
+
+    /// Builder-style method for specifying the key chord opening the palette.
+    ///
+    /// For the non-builder varient, see [`set_hotkey`].
+    ///
+    /// [`set_hotkey`]: #method.set_hotkey
+    pub fn with_hotkey(mut self, hotkey: HotKey) -> Self {
+        self.set_hotkey(hotkey);
+        self
+    }
+
+    /// Set the key chord opening the palette.
+    pub fn set_hotkey(&mut self, hotkey: HotKey) {
+        self.hotkey = hotkey;
+    }
+
+    /// Returns `true` if the palette is currently open.
+    pub fn is_open(&self) -> bool {
+        self.state.is_some()
+    }
+
+    fn show(&mut self, ctx: &mut EventCtx) {
+        if self.state.is_none() {
+            self.state = Some(PaletteState {
+                query: String::new(),
+                matches: self.registry.search(""),
+                selected: 0,
+                scroll: 0,
+                hover: None,
+            });
+            ctx.request_focus();
+            ctx.request_paint();
+        }
+    }
+
+    fn close(&mut self, ctx: &mut EventCtx) {
+        if self.state.take().is_some() {
+            ctx.resign_focus();
+            ctx.request_paint();
+        }
+    }
+
+    fn execute(&mut self, ctx: &mut EventCtx, index: usize) {
+        let action = self.registry.0.borrow().actions.get(index).cloned();
+        if let Some(action) = action {
+            self.registry.record_use(&action.name);
+            ctx.submit_command(action.command.clone());
+        }
+        self.close(ctx);
+    }
+
+    fn palette_rect(&self, size: Size) -> Rect {
+        let rows = self
+            .state
+            .as_ref()
+            .map_or(0, |state| state.matches.len().clamp(1, MAX_ROWS));
+        let width = PALETTE_WIDTH.min(size.width - 2.0 * PADDING);
+        let height = QUERY_HEIGHT + 3.0 * PADDING + rows as f64 * ROW_HEIGHT;
+        Rect::from_origin_size(((size.width - width) / 2.0, PALETTE_TOP), (width, height))
+    }
+
+    fn row_rect(&self, size: Size, row: usize) -> Rect {
+        let palette = self.palette_rect(size);
+        let y = palette.y0 + QUERY_HEIGHT + 2.0 * PADDING + row as f64 * ROW_HEIGHT;
+        Rect::new(
+            palette.x0 + PADDING,
+            y,
+            palette.x1 - PADDING,
+            y + ROW_HEIGHT,
+        )
+    }
+
+    /// The index into the matches of the row under `pos`.
+    fn match_at(&self, size: Size, pos: Point) -> Option<usize> {
+        let state = self.state.as_ref()?;
+        let rows = state.matches.len().min(MAX_ROWS);
+        (0..rows)
+            .find(|&row| self.row_rect(size, row).contains(pos))
+            .map(|row| state.scroll + row)
+    }
+
+    fn handle_key(&mut self, ctx: &mut EventCtx, key: &druid::KeyEvent) {
+        let state = match &mut self.state {
+            Some(state) => state,
+            None => return,
+        };
+        let mut query_changed = false;
+        match &key.key {
+            Key::Escape => {
+                self.close(ctx);
+                return;
+            }
+            Key::Enter => {
+                if let Some(&index) = state.matches.get(state.selected) {
+                    self.execute(ctx, index);
+                }
+                return;
+            }
+            Key::ArrowDown if state.selected + 1 < state.matches.len() => state.selected += 1,
+            Key::ArrowUp if state.selected > 0 => state.selected -= 1,
+            Key::Backspace => query_changed = state.query.pop().is_some(),
+            Key::Character(text) if !key.mods.ctrl() && !key.mods.alt() && !key.mods.meta() => {
+                state.query.push_str(text);
+                query_changed = true;
+            }
+            _ => return,
+        }
+
+        if query_changed {
+            state.matches = self.registry.search(&state.query);
+            state.selected = 0;
+        }
+        // keep the selection visible
+        if state.selected < state.scroll {
+            state.scroll = state.selected;
+        } else if state.selected >= state.scroll + MAX_ROWS {
+            state.scroll = state.selected + 1 - MAX_ROWS;
+        }
+        state.scroll = state
+            .scroll
+            .min(state.matches.len().saturating_sub(MAX_ROWS));
+        ctx.request_paint();
+    }
+}
+
+impl<T: Data> Widget<T> for CommandPalette<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(SHOW_PALETTE) => {
+                self.show(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(cmd) if cmd.is(SHOW_PALETTE) => {
+                self.show(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if self.state.is_none() && self.hotkey.matches(key) => {
+                self.show(ctx);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        if self.state.is_none() {
+            self.inner.event(ctx, event, data, env);
+            return;
+        }
+
+        match event {
+            Event::KeyDown(key) => {
+                self.handle_key(ctx, key);
+                ctx.set_handled();
+            }
+            Event::MouseMove(mouse) => {
+                let hover = self.match_at(ctx.size(), mouse.pos);
+                if let Some(state) = &mut self.state {
+                    if state.hover != hover {
+                        state.hover = hover;
+                        ctx.request_paint();
+                    }
+                }
+            }
+            Event::MouseDown(mouse) => {
+                if let Some(index) = self.match_at(ctx.size(), mouse.pos) {
+                    let action = self.state.as_ref().map(|state| state.matches[index]);
+                    if let Some(action) = action {
+                        self.execute(ctx, action);
+                    }
+                } else if !self.palette_rect(ctx.size()).contains(mouse.pos) {
+                    self.close(ctx);
+                }
+                ctx.set_handled();
+            }
+            Event::Wheel(mouse) => {
+                if let Some(state) = &mut self.state {
+                    let max_scroll = state.matches.len().saturating_sub(MAX_ROWS);
+                    state.scroll = if mouse.wheel_delta.y > 0.0 {
+                        (state.scroll + 1).min(max_scroll)
+                    } else {
+                        state.scroll.saturating_sub(1)
+                    };
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+        // the child only gets events which are not user input
+        if event.should_propagate_to_hidden() {
+            self.inner.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::FocusChanged(false) = event {
+            // another widget took the focus
+            if self.state.take().is_some() {
+                ctx.request_paint();
+            }
+        }
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+
+        let state = match &self.state {
+            Some(state) => state,
+            None => return,
+        };
+        let size = ctx.size();
+        let radius = env.get(theme::BUTTON_BORDER_RADIUS);
+        let font = env.get(theme::UI_FONT);
+        let text_color = env.get(theme::TEXT_COLOR);
+        let dim_color = env.get(theme::PLACEHOLDER_COLOR);
+
+        let palette = self.palette_rect(size);
+        ctx.blurred_rect(palette, 8.0, &Color::rgba8(0, 0, 0, 0x60));
+        ctx.fill(
+            palette.to_rounded_rect(radius),
+            &env.get(theme::WINDOW_BACKGROUND_COLOR),
+        );
+        ctx.stroke(
+            palette.inset(-0.5).to_rounded_rect(radius),
+            &env.get(theme::BORDER_DARK),
+            1.0,
+        );
+
+        // the query, with a caret at its end
+        let query_rect = Rect::new(
+            palette.x0 + PADDING,
+            palette.y0 + PADDING,
+            palette.x1 - PADDING,
+            palette.y0 + PADDING + QUERY_HEIGHT,
+        );
+        ctx.fill(
+            query_rect.to_rounded_rect(radius),
+            &env.get(theme::BACKGROUND_LIGHT),
+        );
+        ctx.stroke(
+            query_rect.inset(-0.5).to_rounded_rect(radius),
+            &env.get(theme::PRIMARY_LIGHT),
+            1.0,
+        );
+        let (query, color) = if state.query.is_empty() {
+            ("Type to search commands", dim_color)
+        } else {
+            (state.query.as_str(), text_color)
+        };
+        let query_layout = ctx
+            .text()
+            .new_text_layout(query.to_string())
+            .font(font.family.clone(), font.size)
+            .text_color(color)
+            .build()
+            .unwrap();
+        let text_height = query_layout.size().height;
+        let text_origin = Point::new(
+            query_rect.x0 + PADDING,
+            query_rect.center().y - text_height / 2.0,
+        );
+        ctx.draw_text(&query_layout, text_origin);
+        let caret_x = if state.query.is_empty() {
+            text_origin.x
+        } else {
+            text_origin.x + query_layout.size().width
+        };
+        let caret = Rect::new(
+            caret_x,
+            text_origin.y,
+            caret_x + 1.0,
+            text_origin.y + text_height,
+        );
+        ctx.fill(caret, &env.get(theme::CURSOR_COLOR));
+
+        if state.matches.is_empty() {
+            let layout = ctx
+                .text()
+                .new_text_layout("No matching commands")
+                .font(font.family.clone(), font.size)
+                .text_color(dim_color)
+                .build()
+                .unwrap();
+            let row = self.row_rect(size, 0);
+            let origin = Point::new(
+                row.x0 + PADDING,
+                row.center().y - layout.size().height / 2.0,
+            );
+            ctx.draw_text(&layout, origin);
+            return;
+        }
+
+        let registry = self.registry.0.borrow();
+        let visible = state
+            .matches
+            .iter()
+            .enumerate()
+            .skip(state.scroll)
+            .take(MAX_ROWS);
+        for (row, (position, &index)) in visible.enumerate() {
+            let action = match registry.actions.get(index) {
+                Some(action) => action,
+                None => continue,
+            };
+            let rect = self.row_rect(size, row);
+            if position == state.selected {
+                ctx.fill(
+                    rect.to_rounded_rect(radius),
+                    &env.get(theme::SELECTED_TEXT_BACKGROUND_COLOR),
+                );
+            } else if state.hover == Some(position) {
+                ctx.fill(
+                    rect.to_rounded_rect(radius),
+                    &env.get(theme::BACKGROUND_LIGHT),
+                );
+            }
+
+            let name = ctx
+                .text()
+                .new_text_layout(action.name.clone())
+                .font(font.family.clone(), font.size)
+                .text_color(text_color)
+                .max_width(rect.width() - 2.0 * PADDING)
+                .build()
+                .unwrap();
+            let y = rect.center().y - name.size().height / 2.0;
+            ctx.draw_text(&name, (rect.x0 + PADDING, y));
+
+            if let Some(shortcut) = &action.shortcut {
+                let shortcut = ctx
+                    .text()
+                    .new_text_layout(shortcut.clone())
+                    .font(font.family.clone(), env.get(theme::TEXT_SIZE_SMALL))
+                    .text_color(dim_color)
+                    .build()
+                    .unwrap();
+                let origin = Point::new(
+                    rect.x1 - PADDING - shortcut.size().width,
+                    rect.center().y - shortcut.size().height / 2.0,
+                );
+                ctx.draw_text(&shortcut, origin);
+            }
+        }
+    }
+}
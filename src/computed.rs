@@ -14,13 +14,25 @@
 
 //! Widget to dynamically compute data.
 //! It is like Label::dynamic but more general.
+//!
+//! The data is only computed again when the input data changed, or the inputs selected with
+//! [`ComputedWidget::with_inputs`], so expensive values like parsed markup are cached across
+//! updates. A [`INVALIDATE_COMPUTED`] command computes it again anyway.
 use druid::widget::prelude::*;
-use druid::{Point, Widget, WidgetPod};
+use druid::{Point, Selector, Widget, WidgetPod};
+
+/// Compute the data of [`ComputedWidget`]s again, for computers depending on something outside
+/// the data. Send it to a widget, or to a window for all of them.
+pub const INVALIDATE_COMPUTED: Selector = Selector::new("druid-widget-nursery.computed.invalidate");
+
+type InputsChanged<T> = dyn FnMut(&T) -> bool;
 
 pub struct ComputedWidget<T, U> {
     child: WidgetPod<U, Box<dyn Widget<U>>>,
     data: Option<U>,
     computer: Box<dyn FnMut(&T) -> U>,
+    inputs_changed: Option<Box<InputsChanged<T>>>,
+    invalid: bool,
 }
 
 impl<T, U> ComputedWidget<T, U> {
@@ -29,12 +41,58 @@ impl<T, U> ComputedWidget<T, U> {
             child: WidgetPod::new(Box::new(child)),
             data: None,
             computer: Box::new(computer),
+            inputs_changed: None,
+            invalid: false,
+        }
+    }
+
+    /// Builder-style method for only computing the data again when the inputs selected by
+    /// `inputs` change, instead of any part of the data. Several inputs can be selected as a
+    /// tuple.
+    pub fn with_inputs<I: Data>(self, inputs: impl Fn(&T) -> I + 'static) -> Self {
+        self.with_inputs_eq(inputs, I::same)
+    }
+
+    /// Builder-style method like [`with_inputs`], comparing the inputs with `eq`, like to ignore
+    /// the case of a text.
+    ///
+    /// [`with_inputs`]: #method.with_inputs
+    pub fn with_inputs_eq<I: 'static>(
+        mut self,
+        inputs: impl Fn(&T) -> I + 'static,
+        eq: impl Fn(&I, &I) -> bool + 'static,
+    ) -> Self {
+        let mut last: Option<I> = None;
+        self.inputs_changed = Some(Box::new(move |data: &T| {
+            let new = inputs(data);
+            let changed = !matches!(&last, Some(last) if eq(last, &new));
+            last = Some(new);
+            changed
+        }));
+        self
+    }
+
+    fn inputs_changed(&mut self, old_data: &T, data: &T) -> bool
+    where
+        T: Data,
+    {
+        match &mut self.inputs_changed {
+            Some(inputs_changed) => inputs_changed(data),
+            None => !old_data.same(data),
         }
     }
 }
 
-impl<T, U: Data> Widget<T> for ComputedWidget<T, U> {
+impl<T: Data, U: Data> Widget<T> for ComputedWidget<T, U> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if cmd.is(INVALIDATE_COMPUTED) {
+                // not handled, so a command sent to a window reaches every computed widget
+                self.invalid = true;
+                ctx.request_update();
+            }
+        }
+
         let old_data = self.data.as_ref().unwrap();
         let mut data = old_data.clone();
 
@@ -47,14 +105,20 @@ impl<T, U: Data> Widget<T> for ComputedWidget<T, U> {
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
         if let LifeCycle::WidgetAdded = event {
+            if let Some(inputs_changed) = &mut self.inputs_changed {
+                inputs_changed(data);
+            }
             self.data = Some((self.computer)(data));
         }
         self.child
             .lifecycle(ctx, event, self.data.as_ref().unwrap(), env);
     }
 
-    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
-        self.data = Some((self.computer)(data));
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if self.inputs_changed(old_data, data) || self.invalid {
+            self.invalid = false;
+            self.data = Some((self.computer)(data));
+        }
         self.child.update(ctx, self.data.as_ref().unwrap(), env);
     }
 
@@ -1,6 +1,9 @@
 use crate::multi_value::INDENT;
+use crate::progress_indicator;
 use druid::Env;
 
 pub fn configure_env<T>(env: &mut Env, _: &T) {
     env.set(INDENT, 30.0);
+    env.set(progress_indicator::SIZE, 24.0);
+    env.set(progress_indicator::STROKE_WIDTH, 3.0);
 }
@@ -0,0 +1,578 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A date picker with a calendar popup.
+
+use std::marker::PhantomData;
+
+use druid::commands::CLOSE_WINDOW;
+use druid::kurbo::BezPath;
+use druid::piet::{LineCap, LineJoin, StrokeStyle, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::widget::Button;
+use druid::{theme, Color, Point, Rect, Vec2, WidgetExt as _, WidgetPod};
+
+use crate::dropdown::DROPDOWN_SHOW;
+use crate::Dropdown;
+
+const CELL_WIDTH: f64 = 32.0;
+const ROW_HEIGHT: f64 = 26.0;
+const PADDING: f64 = 4.0;
+/// The month header, the weekday names, six weeks and the today shortcut.
+const ROWS: usize = 9;
+
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// A date as year, month (1-12) and day (1-31).
+pub(crate) type Ymd = (i32, u32, u32);
+
+/// Data that can be edited by a [`DatePicker`] or [`Calendar`].
+///
+/// This is implemented for `(year, month, day)` tuples, and for [`ChronoDate`] with the `chrono`
+/// feature.
+pub trait DateValue: Data {
+    /// The year, month (1-12) and day (1-31).
+    fn to_ymd(&self) -> (i32, u32, u32);
+    /// Create a value from a valid date.
+    fn from_ymd(year: i32, month: u32, day: u32) -> Self;
+}
+
+impl DateValue for (i32, u32, u32) {
+    fn to_ymd(&self) -> (i32, u32, u32) {
+        *self
+    }
+
+    fn from_ymd(year: i32, month: u32, day: u32) -> Self {
+        (year, month, day)
+    }
+}
+
+/// A `chrono::NaiveDate` which can be used as druid data, as `NaiveDate` doesn't implement
+/// [`Data`].
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChronoDate(pub chrono::NaiveDate);
+
+#[cfg(feature = "chrono")]
+impl Data for ChronoDate {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for ChronoDate {
+    fn from(date: chrono::NaiveDate) -> Self {
+        ChronoDate(date)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<ChronoDate> for chrono::NaiveDate {
+    fn from(date: ChronoDate) -> Self {
+        date.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DateValue for ChronoDate {
+    fn to_ymd(&self) -> (i32, u32, u32) {
+        use chrono::Datelike;
+        (self.0.year(), self.0.month(), self.0.day())
+    }
+
+    fn from_ymd(year: i32, month: u32, day: u32) -> Self {
+        ChronoDate(chrono::NaiveDate::from_ymd_opt(year, month, day).expect("invalid date"))
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of days since 1970-01-01.
+// see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub(crate) fn days_from_civil((year, month, day): Ymd) -> i64 {
+    let year = (if month <= 2 { year - 1 } else { year }) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The inverse of [`days_from_civil`].
+pub(crate) fn civil_from_days(days: i64) -> Ymd {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = (year_of_era + era * 400) as i32 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// The day of the week, 0 for monday.
+pub(crate) fn weekday(date: Ymd) -> i64 {
+    // 1970-01-01 was a thursday
+    (days_from_civil(date) + 3).rem_euclid(7)
+}
+
+fn add_months((year, month): (i32, u32), months: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + months;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn today() -> Ymd {
+    ChronoDate(chrono::Local::now().date_naive()).to_ymd()
+}
+
+#[cfg(not(feature = "chrono"))]
+pub(crate) fn today() -> Ymd {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    // without a time zone database, UTC is the best we can do
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+    civil_from_days(seconds.div_euclid(86400))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Limits {
+    min: Option<Ymd>,
+    max: Option<Ymd>,
+}
+
+impl Limits {
+    fn contains(&self, date: Ymd) -> bool {
+        self.min.map_or(true, |min| date >= min) && self.max.map_or(true, |max| date <= max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Hit {
+    Previous,
+    Next,
+    Day(Ymd),
+    Today,
+}
+
+/// A month view for picking a date, with buttons for the previous and next month and a
+/// shortcut for today.
+///
+/// This is shown in the popup of a [`DatePicker`], but can also be used on its own.
+pub struct Calendar<T> {
+    limits: Limits,
+    month: (i32, u32),
+    hot: Option<Hit>,
+    pressed: Option<Hit>,
+    close_on_select: bool,
+    _t: PhantomData<T>,
+}
+
+impl<T: DateValue> Calendar<T> {
+    pub fn new() -> Self {
+        let (year, month, _) = today();
+        Calendar {
+            limits: Limits::default(),
+            month: (year, month),
+            hot: None,
+            pressed: None,
+            close_on_select: false,
+            _t: PhantomData,
+        }
+    }
+
+    /// Builder-style method for specifying the earliest date that can be picked.
+    ///
+    /// For the non-builder varient, see [`set_min`].
+    ///
+    /// [`set_min`]: #method.set_min
+    pub fn with_min(mut self, min: T) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Set the earliest date that can be picked.
+    pub fn set_min(&mut self, min: T) {
+        self.limits.min = Some(min.to_ymd());
+    }
+
+    /// Builder-style method for specifying the latest date that can be picked.
+    ///
+    /// For the non-builder varient, see [`set_max`].
+    ///
+    /// [`set_max`]: #method.set_max
+    pub fn with_max(mut self, max: T) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    /// Set the latest date that can be picked.
+    pub fn set_max(&mut self, max: T) {
+        self.limits.max = Some(max.to_ymd());
+    }
+
+    /// The date of a cell of the grid, starting with the monday before the first of the month.
+    fn cell_date(&self, cell: usize) -> Ymd {
+        let first = (self.month.0, self.month.1, 1);
+        let start = days_from_civil(first) - weekday(first);
+        civil_from_days(start + cell as i64)
+    }
+
+    fn hit_test(&self, pos: Point) -> Option<Hit> {
+        let pos = pos - Vec2::new(PADDING, PADDING);
+        if pos.x < 0.0 || pos.y < 0.0 || pos.x >= 7.0 * CELL_WIDTH {
+            return None;
+        }
+        let row = (pos.y / ROW_HEIGHT) as usize;
+        let column = (pos.x / CELL_WIDTH) as usize;
+        let hit = match row {
+            0 if column == 0 => Hit::Previous,
+            0 if column == 6 => Hit::Next,
+            2..=7 => Hit::Day(self.cell_date((row - 2) * 7 + column)),
+            8 => Hit::Today,
+            _ => return None,
+        };
+        Some(hit).filter(|hit| self.is_enabled(*hit))
+    }
+
+    fn is_enabled(&self, hit: Hit) -> bool {
+        match hit {
+            Hit::Previous => {
+                let (year, month) = add_months(self.month, -1);
+                let last = (year, month, days_in_month(year, month));
+                self.limits.min.map_or(true, |min| last >= min)
+            }
+            Hit::Next => {
+                let (year, month) = add_months(self.month, 1);
+                self.limits.max.map_or(true, |max| (year, month, 1) <= max)
+            }
+            Hit::Day(date) => self.limits.contains(date),
+            Hit::Today => self.limits.contains(today()),
+        }
+    }
+
+    fn cell_rect(row: usize, column: usize) -> Rect {
+        Rect::from_origin_size(
+            (
+                PADDING + column as f64 * CELL_WIDTH,
+                PADDING + row as f64 * ROW_HEIGHT,
+            ),
+            (CELL_WIDTH, ROW_HEIGHT),
+        )
+    }
+
+    fn select(&mut self, ctx: &mut EventCtx, data: &mut T, date: Ymd) {
+        *data = T::from_ymd(date.0, date.1, date.2);
+        self.month = (date.0, date.1);
+        if self.close_on_select {
+            ctx.submit_command(CLOSE_WINDOW.to(ctx.window_id()));
+        }
+    }
+}
+
+impl<T: DateValue> Default for Calendar<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn draw_text(ctx: &mut PaintCtx, text: &str, rect: Rect, color: Color, env: &Env) {
+    let font = env.get(theme::UI_FONT);
+    let layout = ctx
+        .text()
+        .new_text_layout(text.to_string())
+        .font(font.family.clone(), font.size)
+        .text_color(color)
+        .build();
+    if let Ok(layout) = layout {
+        let origin = rect.center() - layout.size().to_vec2() / 2.0;
+        ctx.draw_text(&layout, origin);
+    }
+}
+
+impl<T: DateValue> Widget<T> for Calendar<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        match event {
+            Event::MouseMove(mouse) => {
+                let hot = self.hit_test(mouse.pos);
+                if hot != self.hot {
+                    self.hot = hot;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseDown(mouse) => {
+                self.pressed = self.hit_test(mouse.pos);
+                if self.pressed.is_some() {
+                    ctx.set_active(true);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                let pressed = self.pressed.take();
+                if pressed.is_some() && pressed == self.hit_test(mouse.pos) {
+                    match pressed {
+                        Some(Hit::Previous) => self.month = add_months(self.month, -1),
+                        Some(Hit::Next) => self.month = add_months(self.month, 1),
+                        Some(Hit::Day(date)) => self.select(ctx, data, date),
+                        Some(Hit::Today) => self.select(ctx, data, today()),
+                        None => {}
+                    }
+                    self.hot = self.hit_test(mouse.pos);
+                }
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                let (year, month, _) = data.to_ymd();
+                self.month = (year, month);
+            }
+            LifeCycle::HotChanged(false) => {
+                self.hot = None;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            let (year, month, _) = data.to_ymd();
+            self.month = (year, month);
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, _env: &Env) -> Size {
+        bc.constrain(Size::new(
+            7.0 * CELL_WIDTH + 2.0 * PADDING,
+            ROWS as f64 * ROW_HEIGHT + 2.0 * PADDING,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let text_color = env.get(theme::TEXT_COLOR);
+        let dim_color = env.get(theme::PLACEHOLDER_COLOR);
+        let disabled_color = env.get(theme::DISABLED_TEXT_COLOR);
+        let highlight = |hit: Hit| {
+            if self.pressed == Some(hit) {
+                Some(env.get(theme::PRIMARY_DARK))
+            } else if self.hot == Some(hit) {
+                Some(env.get(theme::BUTTON_LIGHT))
+            } else {
+                None
+            }
+        };
+        let radius = env.get(theme::BUTTON_BORDER_RADIUS);
+
+        // the month header
+        let style = StrokeStyle::new()
+            .line_cap(LineCap::Round)
+            .line_join(LineJoin::Round);
+        for (hit, column, dx) in [(Hit::Previous, 0, 3.0), (Hit::Next, 6, -3.0)] {
+            let rect = Self::cell_rect(0, column);
+            if let Some(color) = highlight(hit) {
+                ctx.fill(rect.inset(-2.0).to_rounded_rect(radius), &color);
+            }
+            let color = if self.is_enabled(hit) {
+                &text_color
+            } else {
+                &disabled_color
+            };
+            let center = rect.center();
+            let mut chevron = BezPath::new();
+            chevron.move_to((center.x + dx, center.y - 5.0));
+            chevron.line_to((center.x - dx, center.y));
+            chevron.line_to((center.x + dx, center.y + 5.0));
+            ctx.stroke_styled(chevron, color, 1.5, &style);
+        }
+        let title = format!(
+            "{} {}",
+            MONTH_NAMES[self.month.1 as usize - 1],
+            self.month.0
+        );
+        let title_rect = Self::cell_rect(0, 1).union(Self::cell_rect(0, 5));
+        draw_text(ctx, &title, title_rect, text_color, env);
+
+        for (column, name) in WEEKDAY_NAMES.iter().enumerate() {
+            draw_text(ctx, name, Self::cell_rect(1, column), dim_color, env);
+        }
+
+        // the days
+        let selected = data.to_ymd();
+        let today = today();
+        for cell in 0..42 {
+            let date = self.cell_date(cell);
+            let rect = Self::cell_rect(cell / 7 + 2, cell % 7)
+                .inset(-2.0)
+                .to_rounded_rect(radius);
+            let mut color = if !self.limits.contains(date) {
+                disabled_color
+            } else if (date.0, date.1) != self.month {
+                dim_color
+            } else {
+                text_color
+            };
+            if date == selected {
+                ctx.fill(rect, &env.get(theme::PRIMARY_LIGHT));
+                color = env.get(theme::FOREGROUND_DARK);
+            } else if let Some(background) = highlight(Hit::Day(date)) {
+                ctx.fill(rect, &background);
+            }
+            if date == today {
+                ctx.stroke(rect, &env.get(theme::PRIMARY_LIGHT), 1.0);
+            }
+            draw_text(ctx, &date.2.to_string(), rect.rect(), color, env);
+        }
+
+        // the today shortcut
+        let rect = Self::cell_rect(8, 2).union(Self::cell_rect(8, 4));
+        if let Some(color) = highlight(Hit::Today) {
+            ctx.fill(rect.inset(-2.0).to_rounded_rect(radius), &color);
+        }
+        let color = if self.is_enabled(Hit::Today) {
+            text_color
+        } else {
+            disabled_color
+        };
+        draw_text(ctx, "Today", rect, color, env);
+    }
+}
+
+/// A button showing a date, which opens a [`Calendar`] in a popup to pick another one.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::DatePicker;
+/// fn birthday() -> impl Widget<(i32, u32, u32)> {
+///     DatePicker::new()
+///         .with_min((1900, 1, 1))
+///         .with_max((2022, 12, 31))
+/// }
+/// ```
+pub struct DatePicker<T> {
+    limits: Limits,
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+}
+
+impl<T: DateValue> DatePicker<T> {
+    pub fn new() -> Self {
+        DatePicker::with_limits(Limits::default())
+    }
+
+    fn with_limits(limits: Limits) -> Self {
+        let header = Button::dynamic(|data: &T, _| {
+            let (year, month, day) = data.to_ymd();
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        })
+        .on_click(|ctx, _, _| ctx.submit_notification(DROPDOWN_SHOW));
+        let make_drop = move |_: &T, _: &Env| {
+            Calendar {
+                limits,
+                close_on_select: true,
+                ..Calendar::new()
+            }
+            .background(theme::BACKGROUND_LIGHT)
+            .border(theme::BORDER_DARK, 1.0)
+        };
+        DatePicker {
+            limits,
+            inner: WidgetPod::new(Box::new(Dropdown::new(header, make_drop))),
+        }
+    }
+
+    /// Builder-style method for specifying the earliest date that can be picked.
+    pub fn with_min(self, min: T) -> Self {
+        DatePicker::with_limits(Limits {
+            min: Some(min.to_ymd()),
+            ..self.limits
+        })
+    }
+
+    /// Builder-style method for specifying the latest date that can be picked.
+    pub fn with_max(self, max: T) -> Self {
+        DatePicker::with_limits(Limits {
+            max: Some(max.to_ymd()),
+            ..self.limits
+        })
+    }
+}
+
+impl<T: DateValue> Default for DatePicker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DateValue> Widget<T> for DatePicker<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::TimerToken;
+
+/// A controller calling a function once the data of its child stopped changing for a while.
+///
+/// Like [`OnChange`], only changes made by the child widget are noticed.
+///
+/// [`OnChange`]: crate::OnChange
+#[allow(clippy::type_complexity)]
+pub struct OnDebounce<T> {
+    delay: Duration,
+    timer: TimerToken,
+    f: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+}
+
+impl<T> OnDebounce<T> {
+    /// Call `f` after the data didn't change for `delay`.
+    pub fn new(delay: Duration, f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        OnDebounce {
+            delay,
+            timer: TimerToken::INVALID,
+            f: Box::new(f),
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for OnDebounce<T> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Timer(token) = event {
+            if *token == self.timer {
+                self.timer = TimerToken::INVALID;
+                (self.f)(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let old_data = data.clone();
+        child.event(ctx, event, data, env);
+        if !old_data.same(data) {
+            // a new timer replaces the pending one
+            self.timer = ctx.request_timer(self.delay);
+        }
+    }
+}
+
+/// A controller calling a function with the old and the new data, once the data of its child
+/// stopped changing for a while.
+///
+/// The old data is the data before the first of the changes, so several quick edits are seen as
+/// one change. If they were undone in the end, the function isn't called.
+///
+/// Like [`OnChange`], only changes made by the child widget are noticed.
+///
+/// [`OnChange`]: crate::OnChange
+#[allow(clippy::type_complexity)]
+pub struct OnChangeDebounced<T> {
+    delay: Duration,
+    timer: TimerToken,
+    /// The data before the pending changes.
+    before: Option<T>,
+    f: Box<dyn Fn(&mut EventCtx, &T, &mut T, &Env)>,
+}
+
+impl<T> OnChangeDebounced<T> {
+    /// Call `f` after the data didn't change for `delay`.
+    pub fn new(delay: Duration, f: impl Fn(&mut EventCtx, &T, &mut T, &Env) + 'static) -> Self {
+        OnChangeDebounced {
+            delay,
+            timer: TimerToken::INVALID,
+            before: None,
+            f: Box::new(f),
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for OnChangeDebounced<T> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Timer(token) = event {
+            if *token == self.timer {
+                self.timer = TimerToken::INVALID;
+                if let Some(before) = self.before.take() {
+                    if !before.same(data) {
+                        (self.f)(ctx, &before, data, env);
+                    }
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let old_data = data.clone();
+        child.event(ctx, event, data, env);
+        if !old_data.same(data) {
+            self.before.get_or_insert(old_data);
+            // a new timer replaces the pending one
+            self.timer = ctx.request_timer(self.delay);
+        }
+    }
+}
+
+/// A controller calling a function at most once per interval while the data of its child
+/// changes.
+///
+/// The first change calls the function right away. Changes during the interval after it are
+/// collected into one more call at the end of the interval, with the latest data.
+///
+/// Like [`OnChange`], only changes made by the child widget are noticed.
+///
+/// [`OnChange`]: crate::OnChange
+#[allow(clippy::type_complexity)]
+pub struct OnThrottle<T> {
+    interval: Duration,
+    timer: TimerToken,
+    /// The data changed since the function was called last.
+    pending: bool,
+    f: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+}
+
+impl<T> OnThrottle<T> {
+    /// Call `f` at most once every `interval`.
+    pub fn new(interval: Duration, f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        OnThrottle {
+            interval,
+            timer: TimerToken::INVALID,
+            pending: false,
+            f: Box::new(f),
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for OnThrottle<T> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Timer(token) = event {
+            if *token == self.timer {
+                if self.pending {
+                    self.pending = false;
+                    (self.f)(ctx, data, env);
+                    self.timer = ctx.request_timer(self.interval);
+                } else {
+                    self.timer = TimerToken::INVALID;
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let old_data = data.clone();
+        child.event(ctx, event, data, env);
+        if !old_data.same(data) {
+            if self.timer == TimerToken::INVALID {
+                (self.f)(ctx, data, env);
+                self.timer = ctx.request_timer(self.interval);
+            } else {
+                self.pending = true;
+            }
+        }
+    }
+}
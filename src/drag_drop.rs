@@ -0,0 +1,444 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dragging data from one widget to another inside a window.
+//!
+//! A [`DragSource`] turns its data into a payload when a drag starts, and a [`DropTarget`]
+//! accepting payloads of the same type gets it when the mouse is released over it. Widgets
+//! with different data types, like a list and a tree, can exchange payloads this way.
+//!
+//! A [`DragDropHost`] at the root of the window follows the drag and paints its preview on top
+//! of everything else.
+//!
+//! ```
+//! # use druid::widget::{Flex, Label};
+//! # use druid::{Widget, WidgetExt};
+//! # use druid_widget_nursery::drag_drop::{DragDropHost, DragSource, DropTarget};
+//! fn ui() -> impl Widget<(String, String)> {
+//!     let source = DragSource::new(Label::raw(), |text: &String, _| text.clone())
+//!         .with_preview(|| Label::raw())
+//!         .lens(druid::lens!((String, String), 0));
+//!     let target = DropTarget::new(Label::raw(), |_, text: String, data: &mut String, _| {
+//!         *data = text
+//!     })
+//!     .lens(druid::lens!((String, String), 1));
+//!     DragDropHost::new(Flex::row().with_child(source).with_child(target))
+//! }
+//! ```
+
+use std::any::Any;
+use std::rc::Rc;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::Vec2;
+use druid::widget::prelude::*;
+use druid::{theme, Point, Selector, SingleUse, WidgetPod};
+
+use crate::tooltip_host::Snapshot;
+
+/// How far the mouse has to move with the button pressed before a drag starts.
+const DRAG_THRESHOLD: f64 = 4.0;
+/// Where the preview is drawn, from the mouse.
+const PREVIEW_OFFSET: Vec2 = Vec2::new(12.0, 12.0);
+
+/// Sent by a [`DragSource`] to the host when the drag starts.
+const START_DRAG: Selector<SingleUse<Drag>> =
+    Selector::new("druid-widget-nursery.drag-drop.start-drag");
+/// Sent by the host to the whole window after [`START_DRAG`].
+const DRAG_STARTED: Selector<Rc<dyn Any>> =
+    Selector::new("druid-widget-nursery.drag-drop.drag-started");
+/// Sent by a [`DropTarget`] to the host when it took the payload.
+const DROPPED: Selector = Selector::new("druid-widget-nursery.drag-drop.dropped");
+/// Sent by the host to the whole window when the drag is over.
+const DRAG_ENDED: Selector<DragEnded> = Selector::new("druid-widget-nursery.drag-drop.drag-ended");
+
+struct Drag {
+    source: WidgetId,
+    payload: Rc<dyn Any>,
+    preview: Option<Box<dyn Widget<()>>>,
+}
+
+struct ActiveDrag {
+    source: WidgetId,
+    preview: Option<WidgetPod<(), Box<dyn Widget<()>>>>,
+    dropped: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DragEnded {
+    source: WidgetId,
+    dropped: bool,
+}
+
+/// A widget following the drags started inside it, and painting their previews.
+///
+/// Put it at the root of a window. See the [module documentation](self) for an example.
+pub struct DragDropHost<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    drag: Option<ActiveDrag>,
+    mouse: Point,
+}
+
+impl<T: Data> DragDropHost<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            drag: None,
+            mouse: Point::ORIGIN,
+        }
+    }
+
+    /// Returns `true` while something is dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    fn end_drag(&mut self, ctx: &mut EventCtx) {
+        if let Some(drag) = self.drag.take() {
+            let ended = DragEnded {
+                source: drag.source,
+                dropped: drag.dropped,
+            };
+            ctx.submit_command(DRAG_ENDED.with(ended).to(ctx.window_id()));
+            ctx.children_changed();
+            ctx.request_paint();
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for DragDropHost<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Notification(notification) if notification.is(START_DRAG) => {
+                if let Some(drag) = notification.get(START_DRAG).and_then(|drag| drag.take()) {
+                    ctx.submit_command(DRAG_STARTED.with(drag.payload).to(ctx.window_id()));
+                    self.drag = Some(ActiveDrag {
+                        source: drag.source,
+                        preview: drag.preview.map(WidgetPod::new),
+                        dropped: false,
+                    });
+                    ctx.children_changed();
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(notification) if notification.is(DROPPED) => {
+                if let Some(drag) = &mut self.drag {
+                    drag.dropped = true;
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse) => {
+                self.mouse = mouse.pos;
+                if self.drag.is_some() {
+                    // the preview follows the mouse
+                    ctx.request_layout();
+                }
+            }
+            Event::KeyDown(key) if key.key == Key::Escape && self.drag.is_some() => {
+                self.end_drag(ctx);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        self.inner.event(ctx, event, data, env);
+
+        if let Event::MouseUp(_) = event {
+            // after the targets had their chance to take the payload
+            self.end_drag(ctx);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+        if let Some(preview) = self.drag.as_mut().and_then(|drag| drag.preview.as_mut()) {
+            preview.lifecycle(ctx, event, &(), env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        if let Some(preview) = self.drag.as_mut().and_then(|drag| drag.preview.as_mut()) {
+            preview.layout(ctx, &bc.loosen(), &(), env);
+            preview.set_origin(ctx, self.mouse + PREVIEW_OFFSET);
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+        if let Some(preview) = self.drag.as_mut().and_then(|drag| drag.preview.as_mut()) {
+            let clip = ctx.size().to_rect();
+            ctx.with_save(|ctx| {
+                ctx.clip(clip);
+                preview.paint(ctx, &(), env);
+            });
+        }
+    }
+}
+
+/// A widget whose data can be dragged onto a [`DropTarget`] accepting payloads of type `P`.
+///
+/// Mouse events are not passed to the child during a drag.
+pub struct DragSource<T, P> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    payload: Box<dyn Fn(&T, &Env) -> P>,
+    preview: Option<Box<dyn Fn() -> Box<dyn Widget<T>>>>,
+    #[allow(clippy::type_complexity)]
+    on_drag_end: Option<Box<dyn Fn(&mut EventCtx, bool, &mut T, &Env)>>,
+    /// Where the mouse button was pressed, until it is released.
+    press: Option<Point>,
+    dragging: bool,
+}
+
+impl<T: Data, P: 'static> DragSource<T, P> {
+    /// Drag the payload made by `payload` from the data when the drag starts.
+    pub fn new(inner: impl Widget<T> + 'static, payload: impl Fn(&T, &Env) -> P + 'static) -> Self {
+        DragSource {
+            inner: WidgetPod::new(Box::new(inner)),
+            payload: Box::new(payload),
+            preview: None,
+            on_drag_end: None,
+            press: None,
+            dragging: false,
+        }
+    }
+
+    /// Builder-style method for showing a widget next to the mouse during the drag. It gets a
+    /// copy of the data taken when the drag starts.
+    pub fn with_preview<W: Widget<T> + 'static>(
+        mut self,
+        make_widget: impl Fn() -> W + 'static,
+    ) -> Self {
+        self.preview = Some(Box::new(move || Box::new(make_widget())));
+        self
+    }
+
+    /// Builder-style method for reacting to the end of a drag, with `true` if a target took the
+    /// payload. Useful for moving instead of copying, by removing the item here.
+    pub fn on_drag_end(mut self, f: impl Fn(&mut EventCtx, bool, &mut T, &Env) + 'static) -> Self {
+        self.on_drag_end = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T: Data, P: 'static> Widget<T> for DragSource<T, P> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(DRAG_ENDED) => {
+                let ended = cmd.get_unchecked(DRAG_ENDED);
+                if ended.source == ctx.widget_id() {
+                    self.dragging = false;
+                    if let Some(on_drag_end) = &self.on_drag_end {
+                        on_drag_end(ctx, ended.dropped, data, env);
+                    }
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                self.press = Some(mouse.window_pos);
+                ctx.set_active(true);
+            }
+            Event::MouseMove(mouse) if ctx.is_active() && !self.dragging => {
+                if let Some(press) = self.press {
+                    if (mouse.window_pos - press).hypot() > DRAG_THRESHOLD {
+                        self.dragging = true;
+                        let preview: Option<Box<dyn Widget<()>>> =
+                            self.preview.as_ref().map(|make_widget| {
+                                Box::new(Snapshot::new(data.clone(), make_widget())) as _
+                            });
+                        let drag = Drag {
+                            source: ctx.widget_id(),
+                            payload: Rc::new((self.payload)(data, env)),
+                            preview,
+                        };
+                        ctx.submit_notification(START_DRAG.with(SingleUse::new(drag)));
+                    }
+                }
+            }
+            Event::MouseUp(_) => {
+                self.press = None;
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                }
+            }
+            _ => {}
+        }
+
+        let is_mouse = matches!(
+            event,
+            Event::MouseDown(_) | Event::MouseMove(_) | Event::MouseUp(_) | Event::Wheel(_)
+        );
+        if !(self.dragging && is_mouse) {
+            self.inner.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
+
+/// A widget taking payloads of type `P` dropped onto it.
+///
+/// While an accepted payload is dragged over it, the widget is outlined.
+#[allow(clippy::type_complexity)]
+pub struct DropTarget<T, P> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    accept: Box<dyn Fn(&P, &T) -> bool>,
+    on_hover: Option<Box<dyn Fn(&mut EventCtx, &P, bool, &mut T, &Env)>>,
+    on_drop: Box<dyn Fn(&mut EventCtx, P, &mut T, &Env)>,
+    /// The payload being dragged, if it has the right type.
+    payload: Option<Rc<dyn Any>>,
+    hovered: bool,
+}
+
+impl<T: Data, P: Clone + 'static> DropTarget<T, P> {
+    /// Call `on_drop` with the payload dropped onto the widget.
+    pub fn new(
+        inner: impl Widget<T> + 'static,
+        on_drop: impl Fn(&mut EventCtx, P, &mut T, &Env) + 'static,
+    ) -> Self {
+        DropTarget {
+            inner: WidgetPod::new(Box::new(inner)),
+            accept: Box::new(|_, _| true),
+            on_hover: None,
+            on_drop: Box::new(on_drop),
+            payload: None,
+            hovered: false,
+        }
+    }
+
+    /// Builder-style method for only accepting some payloads, depending on the data.
+    ///
+    /// For the non-builder varient, see [`set_accept`].
+    ///
+    /// [`set_accept`]: #method.set_accept
+    pub fn with_accept(mut self, accept: impl Fn(&P, &T) -> bool + 'static) -> Self {
+        self.set_accept(accept);
+        self
+    }
+
+    /// Set which payloads are accepted, depending on the data.
+    pub fn set_accept(&mut self, accept: impl Fn(&P, &T) -> bool + 'static) {
+        self.accept = Box::new(accept);
+    }
+
+    /// Builder-style method for reacting to an accepted payload being dragged over the widget,
+    /// with `true` when it enters and `false` when it leaves.
+    pub fn on_hover(mut self, f: impl Fn(&mut EventCtx, &P, bool, &mut T, &Env) + 'static) -> Self {
+        self.on_hover = Some(Box::new(f));
+        self
+    }
+
+    fn accepted(&self, data: &T) -> Option<&P> {
+        let payload = self.payload.as_ref()?.downcast_ref::<P>()?;
+        if (self.accept)(payload, data) {
+            Some(payload)
+        } else {
+            None
+        }
+    }
+
+    fn set_hovered(&mut self, ctx: &mut EventCtx, hovered: bool, data: &mut T, env: &Env) {
+        if self.hovered == hovered {
+            return;
+        }
+        self.hovered = hovered;
+        ctx.request_paint();
+        if let (Some(on_hover), Some(payload)) = (&self.on_hover, &self.payload) {
+            if let Some(payload) = payload.downcast_ref::<P>() {
+                on_hover(ctx, payload, hovered, data, env);
+            }
+        }
+    }
+}
+
+impl<T: Data, P: Clone + 'static> Widget<T> for DropTarget<T, P> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(DRAG_STARTED) => {
+                let payload = cmd.get_unchecked(DRAG_STARTED);
+                if payload.is::<P>() {
+                    self.payload = Some(payload.clone());
+                }
+            }
+            Event::Command(cmd) if cmd.is(DRAG_ENDED) => {
+                self.set_hovered(ctx, false, data, env);
+                self.payload = None;
+            }
+            Event::MouseMove(_) if self.payload.is_some() => {
+                let hovered = ctx.is_hot() && self.accepted(data).is_some();
+                self.set_hovered(ctx, hovered, data, env);
+            }
+            Event::MouseUp(_) if self.hovered => {
+                if let Some(payload) = self.accepted(data).cloned() {
+                    (self.on_drop)(ctx, payload, data, env);
+                    ctx.submit_notification(DROPPED);
+                }
+                self.set_hovered(ctx, false, data, env);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+        if self.hovered {
+            let rect = ctx.size().to_rect().inset(-1.0).to_rounded_rect(2.0);
+            ctx.stroke(rect, &env.get(theme::PRIMARY_LIGHT), 2.0);
+        }
+    }
+}
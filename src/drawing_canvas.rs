@@ -0,0 +1,350 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use druid::im::Vector;
+use druid::kurbo::{Circle, Line};
+use druid::piet::{Device, ImageFormat, LineCap, StrokeStyle};
+use druid::widget::prelude::*;
+use druid::{theme, Color, HotKey, ImageBuf, KeyOrValue, Lens, Point, SysMods};
+
+/// The size taken when the constraints are loose.
+const DEFAULT_SIZE: Size = Size::new(400.0, 300.0);
+/// The width of a stroke drawn slowly, and quickly, as a fraction of the pen size.
+const SLOW_WIDTH: f64 = 1.2;
+const FAST_WIDTH: f64 = 0.3;
+/// How much of the width of a point comes from the speed, the rest coming from the point before.
+const WIDTH_SMOOTHING: f64 = 0.3;
+
+/// What dragging on a [`DrawingCanvas`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum DrawingTool {
+    /// Draw strokes.
+    Pen,
+    /// Remove the strokes touched.
+    Eraser,
+}
+
+/// A stroke of a [`Drawing`], through points with the width of the stroke there.
+#[derive(Debug, Clone, Data)]
+pub struct Stroke {
+    pub points: Arc<Vec<(Point, f64)>>,
+    pub color: Color,
+}
+
+impl Stroke {
+    /// Whether the circle at `center` with `radius` touches the stroke.
+    fn touches(&self, center: Point, radius: f64) -> bool {
+        let mut previous = match self.points.first() {
+            Some(&point) => point,
+            None => return false,
+        };
+        self.points.iter().any(|&(point, width)| {
+            let (start, start_width) = std::mem::replace(&mut previous, (point, width));
+            let distance = segment_distance(start, point, center);
+            distance <= radius + start_width.max(width) / 2.0
+        })
+    }
+}
+
+fn segment_distance(start: Point, end: Point, point: Point) -> f64 {
+    let segment = end - start;
+    let length = segment.hypot2();
+    if length == 0.0 {
+        return start.distance(point);
+    }
+    let t = ((point - start).dot(segment) / length).max(0.0).min(1.0);
+    (start + segment * t).distance(point)
+}
+
+fn draw_stroke(rc: &mut impl RenderContext, stroke: &Stroke) {
+    let style = StrokeStyle::new().line_cap(LineCap::Round);
+    let points = &stroke.points;
+    if let [(point, width)] = &points[..] {
+        rc.stroke_styled(Line::new(*point, *point), &stroke.color, *width, &style);
+    }
+    for pair in points.windows(2) {
+        let ((start, start_width), (end, end_width)) = (pair[0], pair[1]);
+        let width = (start_width + end_width) / 2.0;
+        rc.stroke_styled(Line::new(start, end), &stroke.color, width, &style);
+    }
+}
+
+/// The strokes of a [`DrawingCanvas`], with the tool, color and size used for the next ones.
+///
+/// Each change to the strokes made by the canvas can be undone.
+#[derive(Debug, Clone, Data, Lens)]
+pub struct Drawing {
+    pub strokes: Vector<Stroke>,
+    pub tool: DrawingTool,
+    pub color: Color,
+    /// The width of the pen, or of the eraser.
+    pub size: f64,
+    /// The strokes before each change.
+    history: Vector<Vector<Stroke>>,
+}
+
+impl Drawing {
+    /// An empty drawing with a pen of `color` and `size`.
+    pub fn new(color: Color, size: f64) -> Self {
+        Drawing {
+            strokes: Vector::new(),
+            tool: DrawingTool::Pen,
+            color,
+            size,
+            history: Vector::new(),
+        }
+    }
+
+    /// Keep the strokes before a change, to undo it.
+    fn checkpoint(&mut self) {
+        self.history.push_back(self.strokes.clone());
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Undo the last change to the strokes, returning whether there was one.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(strokes) => {
+                self.strokes = strokes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all the strokes, which can be undone.
+    pub fn clear(&mut self) {
+        if !self.strokes.is_empty() {
+            self.checkpoint();
+            self.strokes.clear();
+        }
+    }
+
+    /// Draw the strokes on an image of `size`, on `background` or on a transparent one.
+    pub fn to_image(
+        &self,
+        size: Size,
+        background: Option<Color>,
+    ) -> Result<ImageBuf, druid::piet::Error> {
+        let mut device = Device::new()?;
+        let mut target =
+            device.bitmap_target(size.width.ceil() as usize, size.height.ceil() as usize, 1.0)?;
+        {
+            let mut rc = target.render_context();
+            if let Some(background) = background {
+                rc.clear(None, background);
+            }
+            for stroke in &self.strokes {
+                draw_stroke(&mut rc, stroke);
+            }
+            rc.finish()?;
+        }
+        target.to_image_buf(ImageFormat::RgbaPremul)
+    }
+}
+
+/// A canvas drawing strokes with the mouse, or a pen, onto a [`Drawing`].
+///
+/// Strokes get thinner when they are drawn quickly, like with a pen pressed more lightly. The
+/// eraser removes the strokes it touches, and the shortcut for undo, with the focus, undoes the
+/// last stroke drawn or erased. Tools, colors and sizes are chosen by setting them in the data.
+///
+/// ```
+/// # use druid::widget::{Button, Flex};
+/// # use druid::{Color, Widget, WidgetExt};
+/// # use druid_widget_nursery::{Drawing, DrawingCanvas, DrawingTool};
+/// fn sketch() -> impl Widget<Drawing> {
+///     Flex::column()
+///         .with_child(
+///             Flex::row()
+///                 .with_child(Button::new("Pen").on_click(|_, drawing: &mut Drawing, _| {
+///                     drawing.tool = DrawingTool::Pen;
+///                 }))
+///                 .with_child(Button::new("Eraser").on_click(|_, drawing: &mut Drawing, _| {
+///                     drawing.tool = DrawingTool::Eraser;
+///                 }))
+///                 .with_child(Button::new("Undo").on_click(|_, drawing: &mut Drawing, _| {
+///                     drawing.undo();
+///                 })),
+///         )
+///         .with_flex_child(DrawingCanvas::new(), 1.0)
+/// }
+///
+/// let drawing = Drawing::new(Color::BLACK, 3.0);
+/// ```
+pub struct DrawingCanvas {
+    background: KeyOrValue<Color>,
+    /// The stroke being drawn.
+    stroke: Option<Stroke>,
+    /// Where the eraser is while it's dragged.
+    eraser: Option<Point>,
+}
+
+impl DrawingCanvas {
+    pub fn new() -> Self {
+        DrawingCanvas {
+            background: theme::BACKGROUND_LIGHT.into(),
+            stroke: None,
+            eraser: None,
+        }
+    }
+
+    /// Builder-style method for setting the color behind the strokes.
+    ///
+    /// For the non-builder varient, see [`set_background`].
+    ///
+    /// [`set_background`]: #method.set_background
+    pub fn with_background(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_background(color);
+        self
+    }
+
+    /// Set the color behind the strokes.
+    pub fn set_background(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.background = color.into();
+    }
+
+    /// Remove the strokes under the eraser at `pos`.
+    fn erase(&mut self, ctx: &mut EventCtx, pos: Point, data: &mut Drawing) {
+        let radius = data.size / 2.0;
+        data.strokes.retain(|stroke| !stroke.touches(pos, radius));
+        self.eraser = Some(pos);
+        ctx.request_paint();
+    }
+}
+
+impl Default for DrawingCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<Drawing> for DrawingCanvas {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Drawing, _env: &Env) {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.request_focus();
+                ctx.set_active(true);
+                match data.tool {
+                    DrawingTool::Pen => {
+                        self.stroke = Some(Stroke {
+                            points: Arc::new(vec![(mouse.pos, data.size)]),
+                            color: data.color,
+                        });
+                        ctx.request_paint();
+                    }
+                    DrawingTool::Eraser => {
+                        data.checkpoint();
+                        self.erase(ctx, mouse.pos, data);
+                    }
+                }
+            }
+            Event::MouseMove(mouse) if ctx.is_active() => match data.tool {
+                DrawingTool::Pen => {
+                    if let Some(stroke) = &mut self.stroke {
+                        let points = Arc::make_mut(&mut stroke.points);
+                        let (last, last_width) = points[points.len() - 1];
+                        let distance = (mouse.pos - last).hypot();
+                        if distance < 1.0 {
+                            return;
+                        }
+                        // the speed, in pen sizes per move, gives the width
+                        let speed = distance / data.size.max(1.0);
+                        let target = FAST_WIDTH + (SLOW_WIDTH - FAST_WIDTH) / (1.0 + speed / 4.0);
+                        let width = last_width * (1.0 - WIDTH_SMOOTHING)
+                            + target * data.size * WIDTH_SMOOTHING;
+                        points.push((mouse.pos, width));
+                        ctx.request_paint();
+                    }
+                }
+                DrawingTool::Eraser => self.erase(ctx, mouse.pos, data),
+            },
+            Event::MouseUp(mouse) if mouse.button.is_left() && ctx.is_active() => {
+                ctx.set_active(false);
+                if let Some(stroke) = self.stroke.take() {
+                    data.checkpoint();
+                    data.strokes.push_back(stroke);
+                }
+                if self.eraser.take().is_some() {
+                    ctx.request_paint();
+                }
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                if HotKey::new(SysMods::Cmd, "z").matches(key) {
+                    data.undo();
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Drawing,
+        _env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &Drawing, data: &Drawing, _env: &Env) {
+        if !old_data.strokes.same(&data.strokes) || ctx.env_key_changed(&self.background) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Drawing,
+        _env: &Env,
+    ) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            DEFAULT_SIZE.width
+        };
+        let height = if bc.is_height_bounded() {
+            bc.max().height
+        } else {
+            DEFAULT_SIZE.height
+        };
+        bc.constrain((width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Drawing, env: &Env) {
+        let rect = ctx.size().to_rect();
+        ctx.fill(rect, &self.background.resolve(env));
+        ctx.with_save(|ctx| {
+            ctx.clip(rect);
+            for stroke in data.strokes.iter().chain(&self.stroke) {
+                draw_stroke(ctx.render_ctx, stroke);
+            }
+            if let Some(eraser) = self.eraser {
+                let circle = Circle::new(eraser, data.size / 2.0);
+                ctx.stroke(circle, &env.get(theme::BORDER_LIGHT), 1.0);
+            }
+        });
+    }
+}
@@ -7,6 +7,8 @@ use druid::WindowSizePolicy;
 use druid::{Point, WindowConfig};
 use druid::{WindowId, WindowLevel};
 
+use crate::accessibility::{AccessState, Accessible, Role};
+
 type DropFn<T> = Box<dyn Fn(&T, &Env) -> Box<dyn Widget<T>>>;
 
 pub struct Dropdown<T> {
@@ -130,3 +132,16 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for Dropdown<T> {
         child.lifecycle(ctx, event, data, env)
     }
 }
+
+impl<T: Data> Accessible<T> for Dropdown<T> {
+    fn role(&self) -> Role {
+        Role::ComboBox
+    }
+
+    fn accessible_state(&self, _data: &T) -> AccessState {
+        AccessState {
+            expanded: Some(self.window.is_some()),
+            ..AccessState::default()
+        }
+    }
+}
@@ -15,14 +15,15 @@
 //! A simple list selection widget, for selecting a single value out of a list.
 
 use crate::dropdown::{DROPDOWN_CLOSED, DROPDOWN_HIDE, DROPDOWN_SHOW};
+use crate::focus_ring::{self, FocusVisible};
 use crate::{AutoFocus, Dropdown, ListSelect, Wedge, WidgetExt as _};
 use druid::commands::CLOSE_WINDOW;
 use druid::kurbo::{BezPath, TranslateScale};
 use druid::widget::{Controller, DefaultScopePolicy, Label, LabelText, LineBreaking, Scope};
 use druid::{
-    theme, Affine, BoxConstraints, Data, Env, Event, EventCtx, Insets, LayoutCtx, Lens, LifeCycle,
-    LifeCycleCtx, LinearGradient, PaintCtx, Point, RenderContext, Size, UnitPoint, UpdateCtx,
-    Widget, WidgetExt, WidgetPod,
+    theme, Affine, BoxConstraints, Data, Env, Event, EventCtx, Insets, KbKey, LayoutCtx, Lens,
+    LifeCycle, LifeCycleCtx, LinearGradient, PaintCtx, Point, RenderContext, Size, UnitPoint,
+    UpdateCtx, Widget, WidgetExt, WidgetPod,
 };
 use std::marker::PhantomData;
 
@@ -139,6 +140,7 @@ struct DropdownButton<T> {
     wedge: WidgetPod<bool, Wedge>,
     label: Label<T>,
     label_size: Size,
+    focus: FocusVisible,
 }
 
 impl<T: Data> DropdownButton<T> {
@@ -151,6 +153,7 @@ impl<T: Data> DropdownButton<T> {
             wedge: WidgetPod::new(Wedge::new()),
             label,
             label_size: Size::ZERO,
+            focus: FocusVisible::new(),
         }
     }
 }
@@ -160,14 +163,30 @@ impl<T: Data> Widget<DropdownState<T>> for DropdownButton<T> {
         &mut self,
         ctx: &mut EventCtx,
         event: &Event,
-        _data: &mut DropdownState<T>,
+        data: &mut DropdownState<T>,
         _env: &Env,
     ) {
+        self.focus.event(ctx, event);
         match event {
             Event::MouseDown(_) => {
                 ctx.set_active(true);
+                ctx.request_focus();
                 ctx.request_paint();
             }
+            Event::KeyDown(key) if ctx.is_focused() => {
+                // open and close like with a click
+                if matches!(&key.key, KbKey::Enter)
+                    || matches!(&key.key, KbKey::Character(c) if c == " ")
+                {
+                    data.expanded = !data.expanded;
+                    if data.expanded {
+                        ctx.submit_notification(DROPDOWN_SHOW);
+                    } else {
+                        ctx.submit_notification(DROPDOWN_HIDE);
+                    }
+                    ctx.set_handled();
+                }
+            }
             Event::MouseUp(_) => {
                 if ctx.is_active() {
                     ctx.set_active(false);
@@ -185,9 +204,12 @@ impl<T: Data> Widget<DropdownState<T>> for DropdownButton<T> {
         data: &DropdownState<T>,
         env: &Env,
     ) {
-        if let LifeCycle::HotChanged(_) = event {
-            ctx.request_paint();
+        match event {
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            LifeCycle::HotChanged(_) => ctx.request_paint(),
+            _ => {}
         }
+        self.focus.lifecycle(ctx, event);
         self.wedge.lifecycle(ctx, event, &data.expanded, env);
         self.label.lifecycle(ctx, event, &data.data, env)
     }
@@ -290,6 +312,10 @@ impl<T: Data> Widget<DropdownState<T>> for DropdownButton<T> {
         });
 
         self.wedge.paint(ctx, &data.expanded, env);
+
+        if self.focus.is_visible() {
+            focus_ring::paint(ctx, size.to_rect(), radius.top_left, env);
+        }
     }
 }
 
@@ -1,23 +1,52 @@
+use std::collections::HashMap;
+
+use crate::animation::{Animated, AnimationCurve, SwitchTransition};
 use crate::prism::{Prism, PrismWidget, PrismWrap};
 use druid::{
     BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
-    UpdateCtx, Widget,
+    UpdateCtx, Widget, WidgetPod,
 };
 
-type WidgetBuilder<T> = Box<dyn Fn(&T) -> Option<Box<dyn PrismWidget<T>>>>;
+type ChildPod<T> = WidgetPod<T, Box<dyn PrismWidget<T>>>;
+
+struct LazyVariant<T> {
+    is_active_for: Box<dyn Fn(&T) -> bool>,
+    build: Box<dyn Fn() -> Box<dyn PrismWidget<T>>>,
+}
 
 /// A widget like switcher, but the inner widgets are created on demand. This is useful for tree-like
 /// structures, which you can't represent with Switcher recursively.
+///
+/// By default the widget of a variant is dropped when another variant becomes active, and built
+/// again when the variant comes back. With [`keep_alive`] it is kept instead, along with its
+/// state. The change can also be animated with a [`SwitchTransition`].
+///
+/// [`keep_alive`]: #method.with_keep_alive
 pub struct LazySwitcher<T: Data> {
-    builder: Vec<WidgetBuilder<T>>,
-    current: Option<Box<dyn PrismWidget<T>>>,
+    variants: Vec<LazyVariant<T>>,
+    current: Option<(usize, ChildPod<T>)>,
+    /// The child being replaced while the transition runs.
+    old: Option<(usize, ChildPod<T>)>,
+    /// The children of inactive variants, when they are kept alive.
+    cache: HashMap<usize, ChildPod<T>>,
+    keep_alive: bool,
+    transition: Option<SwitchTransition>,
+    progress: Animated<f64>,
 }
 
 impl<T: Data> LazySwitcher<T> {
     pub fn new() -> Self {
         LazySwitcher {
-            builder: vec![],
+            variants: vec![],
             current: None,
+            old: None,
+            cache: HashMap::new(),
+            keep_alive: false,
+            transition: None,
+            progress: Animated::jump(1.0)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
         }
     }
 
@@ -28,32 +57,116 @@ impl<T: Data> LazySwitcher<T> {
         prism: P,
         builder: impl Fn() -> W + 'static,
     ) -> Self {
-        self.builder.push(Box::new(move |data| {
-            prism
-                .get(data)
-                .map(|_| Box::new(PrismWrap::new(builder(), prism.clone())) as _)
-        }));
+        let check = prism.clone();
+        self.variants.push(LazyVariant {
+            is_active_for: Box::new(move |data| check.get(data).is_some()),
+            build: Box::new(move || Box::new(PrismWrap::new(builder(), prism.clone()))),
+        });
+        self
+    }
+
+    /// Builder-style method for keeping the widgets of inactive variants.
+    ///
+    /// For the non-builder varient, see [`set_keep_alive`].
+    ///
+    /// [`set_keep_alive`]: #method.set_keep_alive
+    pub fn with_keep_alive(mut self, keep_alive: bool) -> Self {
+        self.set_keep_alive(keep_alive);
+        self
+    }
+
+    /// Set whether the widget of a variant is kept when another variant becomes active, instead
+    /// of being built again later.
+    pub fn set_keep_alive(&mut self, keep_alive: bool) {
+        self.keep_alive = keep_alive;
+        if !keep_alive {
+            self.cache.clear();
+        }
+    }
+
+    /// Builder-style method for animating the change of the variant.
+    ///
+    /// For the non-builder varient, see [`set_transition`].
+    ///
+    /// [`set_transition`]: #method.set_transition
+    pub fn with_transition(mut self, transition: SwitchTransition) -> Self {
+        self.set_transition(Some(transition));
         self
     }
 
+    /// Set the [`SwitchTransition`], or `None` to switch at once.
+    pub fn set_transition(&mut self, transition: Option<SwitchTransition>) {
+        self.transition = transition;
+    }
+
+    /// Builder-style method for specifying the duration of the transition.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the transition in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.progress.set_duration(duration);
+    }
+
+    /// Drop a child that isn't shown anymore, or keep it for later.
+    fn retire(&mut self, (index, child): (usize, ChildPod<T>)) {
+        if self.keep_alive {
+            self.cache.insert(index, child);
+        }
+    }
+
     /// updates the inner widget and returns true if the widget changed
     fn rebuild_if_needed(&mut self, data: &T) -> bool {
-        if let Some(current) = &self.current {
-            if current.is_active_for(data) {
+        if let Some((_, current)) = &self.current {
+            if current.widget().is_active_for(data) {
                 return false;
             }
         }
 
-        let had_child = self.current.is_none();
-        let new = self
-            .builder
+        let index = self
+            .variants
             .iter()
-            .filter_map(|builder| (builder)(data))
-            .next();
-        self.current = new;
+            .position(|variant| (variant.is_active_for)(data));
+        let new = index.map(|index| {
+            let child = match self.old.take() {
+                // switching back during the transition
+                Some((old_index, old)) if old_index == index => old,
+                old => {
+                    if let Some(old) = old {
+                        self.retire(old);
+                    }
+                    self.cache
+                        .remove(&index)
+                        .unwrap_or_else(|| WidgetPod::new((self.variants[index].build)()))
+                }
+            };
+            (index, child)
+        });
+        let had_child = self.current.is_some();
+        if let Some(old) = std::mem::replace(&mut self.current, new) {
+            if self.transition.is_some() {
+                if let Some(older) = self.old.replace(old) {
+                    self.retire(older);
+                }
+            } else {
+                self.retire(old);
+            }
+        }
 
         had_child || self.current.is_some()
     }
+
+    fn end_transition(&mut self) {
+        if let Some(old) = self.old.take() {
+            self.retire(old);
+        }
+    }
 }
 
 impl<T: Data> Default for LazySwitcher<T> {
@@ -64,9 +177,27 @@ impl<T: Data> Default for LazySwitcher<T> {
 
 impl<T: Data> Widget<T> for LazySwitcher<T> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        if let Some(inner) = &mut self.current {
+        if let Event::AnimFrame(nanos) = event {
+            if self.progress.animating() {
+                self.progress.update(ctx, *nanos);
+                if !self.progress.animating() {
+                    self.end_transition();
+                    ctx.children_changed();
+                }
+            }
+        }
+
+        if let Some((_, inner)) = &mut self.current {
             inner.event(ctx, event, data, env);
         }
+        if event.should_propagate_to_hidden() {
+            if let Some((_, old)) = &mut self.old {
+                old.event(ctx, event, data, env);
+            }
+            for child in self.cache.values_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
@@ -74,39 +205,75 @@ impl<T: Data> Widget<T> for LazySwitcher<T> {
             self.rebuild_if_needed(data);
         }
 
-        if let Some(inner) = &mut self.current {
+        if let Some((_, inner)) = &mut self.current {
             inner.lifecycle(ctx, event, data, env);
         }
+        if let Some((_, old)) = &mut self.old {
+            old.lifecycle(ctx, event, data, env);
+        }
+        if event.should_propagate_to_hidden() {
+            for child in self.cache.values_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
     }
 
-    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
         if self.rebuild_if_needed(data) {
+            if self.old.is_some() && self.current.is_some() {
+                self.progress.jump_to_value(0.0);
+                self.progress.animate(ctx, 1.0);
+            }
+            if !self.progress.animating() {
+                self.end_transition();
+            }
             ctx.children_changed();
             ctx.request_layout();
         }
 
-        if let Some(inner) = &mut self.current {
-            inner.update(ctx, old_data, data, env);
+        if let Some((_, inner)) = &mut self.current {
+            inner.update(ctx, data, env);
         }
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
-        if let Some(inner) = &mut self.current {
-            inner.layout(ctx, bc, data, env)
-        } else {
-            bc.min()
+        let size = match &mut self.current {
+            Some((_, inner)) => inner.layout(ctx, bc, data, env),
+            None => bc.min(),
+        };
+        let transition = self.transition.unwrap_or_default();
+        let (old_offset, current_offset) = transition.offsets(self.progress.get(), size);
+        if let Some((_, inner)) = &mut self.current {
+            inner.set_origin(ctx, current_offset);
         }
+        if let Some((_, old)) = &mut self.old {
+            // the old child keeps painting the data of its variant
+            old.layout(ctx, bc, data, env);
+            old.set_origin(ctx, old_offset);
+        }
+        size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        if let Some(inner) = &mut self.current {
-            inner.paint(ctx, data, env);
-        }
+        let current = &mut self.current;
+        let paint_old = self
+            .old
+            .as_mut()
+            .map(|(_, old)| move |ctx: &mut PaintCtx| old.paint(ctx, data, env));
+        let transition = self.transition.unwrap_or_default();
+        transition.paint(ctx, self.progress.get(), env, paint_old, |ctx| {
+            if let Some((_, inner)) = current {
+                inner.paint(ctx, data, env);
+            }
+        });
     }
 }
 
 /// A widget which displays the first widget of which the associated prism returned `Some()` for the
 /// current data.
+///
+/// The widgets of all variants are built up front. To only build the widget of the active variant,
+/// use [`LazySwitcher`].
 pub struct Switcher<T: Data> {
     widgets: Vec<Box<dyn PrismWidget<T>>>,
     current: Option<usize>,
@@ -0,0 +1,120 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Calling a function on a repeating timer.
+
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::TimerToken;
+
+use crate::focus_scope::is_request;
+
+crate::selectors! {
+    /// Stop calling the function of an [`Every`] until [`RESUME_EVERY`]. Can be sent as a command
+    /// or submitted as a notification by a widget inside it.
+    PAUSE_EVERY,
+    /// Call the function of an [`Every`] again after [`PAUSE_EVERY`], starting a new interval.
+    RESUME_EVERY,
+}
+
+/// A controller calling a function every interval while its widget is in the widget tree, like
+/// for clocks, polling or auto-saving.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use druid::widget::Label;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::WidgetExt as _;
+/// fn stopwatch() -> impl Widget<u64> {
+///     Label::dynamic(|seconds: &u64, _| format!("{}s", seconds))
+///         .every(Duration::from_secs(1), |_ctx, seconds, _env| *seconds += 1)
+/// }
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Every<T> {
+    interval: Duration,
+    timer: TimerToken,
+    paused: bool,
+    f: Box<dyn Fn(&mut EventCtx, &mut T, &Env)>,
+}
+
+impl<T> Every<T> {
+    pub fn new(interval: Duration, f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        Every {
+            interval,
+            timer: TimerToken::INVALID,
+            paused: false,
+            f: Box::new(f),
+        }
+    }
+
+    /// Builder-style method for starting paused, until [`RESUME_EVERY`] is received.
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    /// Whether the function isn't called right now.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl<T: Data, W: Widget<T>> Controller<T, W> for Every<T> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Timer(token) if *token == self.timer => {
+                (self.f)(ctx, data, env);
+                self.timer = ctx.request_timer(self.interval);
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, PAUSE_EVERY) => {
+                self.paused = true;
+                // a pending timer is ignored from now on
+                self.timer = TimerToken::INVALID;
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, RESUME_EVERY) => {
+                if self.paused {
+                    self.paused = false;
+                    self.timer = ctx.request_timer(self.interval);
+                }
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        child.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            if !self.paused {
+                self.timer = ctx.request_timer(self.interval);
+            }
+        }
+        child.lifecycle(ctx, event, data, env);
+    }
+}
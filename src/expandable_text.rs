@@ -0,0 +1,323 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::piet::{PietText, PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::widget::LabelText;
+use druid::{theme, Cursor, Lens, LensExt, Point, Rect};
+
+use crate::animation::{Animated, AnimationCurve};
+
+const ELLIPSIS: &str = "…";
+
+/// The text of the toggle and where it is.
+struct Toggle {
+    layout: PietTextLayout,
+    origin: Point,
+}
+
+impl Toggle {
+    fn rect(&self) -> Rect {
+        Rect::from_origin_size(self.origin, self.layout.size())
+    }
+}
+
+/// The layouts of the text, built for a width.
+struct Layouts {
+    full: PietTextLayout,
+    /// The first lines ending with an ellipsis and "more", when the text is longer.
+    collapsed: Option<(PietTextLayout, Toggle)>,
+    less: Toggle,
+    width: f64,
+}
+
+/// A label showing the first lines of its text, ending with an ellipsis and a "more" toggle.
+///
+/// The toggle expands the label to the whole text, with a "less" toggle at the end, animating
+/// its height. Whether it is expanded is a `bool` of the data, so it can also be expanded and
+/// collapsed by the application.
+///
+/// ```
+/// # use druid::{Data, Env, Lens, Widget};
+/// # use druid_widget_nursery::ExpandableText;
+/// #[derive(Clone, Data, Lens)]
+/// struct Review {
+///     text: String,
+///     expanded: bool,
+/// }
+///
+/// fn review() -> impl Widget<Review> {
+///     ExpandableText::new(|review: &Review, _: &Env| review.text.clone(), Review::expanded)
+///         .with_max_lines(2)
+/// }
+/// ```
+pub struct ExpandableText<T> {
+    text: LabelText<T>,
+    get_expanded: Box<dyn Fn(&T) -> bool>,
+    set_expanded: Box<dyn Fn(&mut T, bool)>,
+    max_lines: usize,
+    more: String,
+    less: String,
+    expanded: bool,
+    /// 0 for collapsed, 1 for expanded.
+    openness: Animated<f64>,
+    layouts: Option<Layouts>,
+}
+
+impl<T: Data> ExpandableText<T> {
+    /// Create a label of `text`, expanded while the `bool` of `expanded` is true.
+    pub fn new(
+        text: impl Into<LabelText<T>>,
+        expanded: impl Lens<T, bool> + Clone + 'static,
+    ) -> Self {
+        let get = expanded.clone();
+        ExpandableText {
+            text: text.into(),
+            get_expanded: Box::new(move |data| get.get(data)),
+            set_expanded: Box::new(move |data, value| expanded.put(data, value)),
+            max_lines: 3,
+            more: "more".to_string(),
+            less: "less".to_string(),
+            expanded: false,
+            openness: Animated::jump(0.0)
+                .duration(0.2)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+            layouts: None,
+        }
+    }
+
+    /// Builder-style method for setting the number of lines shown while collapsed.
+    ///
+    /// For the non-builder varient, see [`set_max_lines`].
+    ///
+    /// [`set_max_lines`]: #method.set_max_lines
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.set_max_lines(max_lines);
+        self
+    }
+
+    /// Set the number of lines shown while collapsed, 3 by default.
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.max(1);
+        self.layouts = None;
+    }
+
+    /// Builder-style method for setting the text of the toggles, "more" and "less" by default.
+    ///
+    /// For the non-builder varient, see [`set_toggle_text`].
+    ///
+    /// [`set_toggle_text`]: #method.set_toggle_text
+    pub fn with_toggle_text(mut self, more: impl Into<String>, less: impl Into<String>) -> Self {
+        self.set_toggle_text(more, less);
+        self
+    }
+
+    /// Set the text of the toggles.
+    pub fn set_toggle_text(&mut self, more: impl Into<String>, less: impl Into<String>) {
+        self.more = more.into();
+        self.less = less.into();
+        self.layouts = None;
+    }
+
+    /// Build the layouts of `text` wrapped to `width`.
+    fn build(&self, factory: &mut PietText, text: &str, width: f64, env: &Env) -> Layouts {
+        let font = env.get(theme::UI_FONT);
+        let layout = |factory: &mut PietText, text: String, color| {
+            factory
+                .new_text_layout(text)
+                .max_width(width)
+                .font(font.family.clone(), font.size)
+                .text_color(color)
+                .build()
+                .unwrap()
+        };
+        let text_color = env.get(theme::TEXT_COLOR);
+        let toggle_color = env.get(theme::PRIMARY_LIGHT);
+        let space = font.size / 3.0;
+
+        // the toggle goes after the end of the last line of `layout`, or under it
+        let toggle_after = |toggle: PietTextLayout, layout: &PietTextLayout, len: usize| {
+            let end = layout.hit_test_text_position(len).point.x + space;
+            let line = layout.line_count().saturating_sub(1);
+            let metric = layout.line_metric(line).unwrap_or_default();
+            let baseline = toggle.line_metric(0).unwrap_or_default().baseline;
+            let origin = if end + toggle.size().width <= width {
+                Point::new(end, metric.y_offset + metric.baseline - baseline)
+            } else {
+                Point::new(0.0, layout.size().height)
+            };
+            Toggle {
+                layout: toggle,
+                origin,
+            }
+        };
+
+        let full = layout(factory, text.to_string(), text_color);
+        let less = layout(factory, self.less.clone(), toggle_color);
+        let less = toggle_after(less, &full, text.len());
+
+        let collapsed = if full.line_count() > self.max_lines {
+            let more = layout(factory, self.more.clone(), toggle_color);
+            let ellipsis = layout(factory, ELLIPSIS.to_string(), text_color);
+            // cut the last line shown where the ellipsis and the toggle still fit
+            let metric = full.line_metric(self.max_lines - 1).unwrap_or_default();
+            let x = width - ellipsis.size().width - space - more.size().width;
+            let y = metric.y_offset + metric.height / 2.0;
+            let cut = full
+                .hit_test_point(Point::new(x.max(0.0), y))
+                .idx
+                .max(metric.start_offset)
+                .min(metric.end_offset);
+            let shown = format!("{}{}", text[..cut].trim_end(), ELLIPSIS);
+            let len = shown.len();
+            let shown = layout(factory, shown, text_color);
+            let more = toggle_after(more, &shown, len);
+            Some((shown, more))
+        } else {
+            None
+        };
+
+        Layouts {
+            full,
+            collapsed,
+            less,
+            width,
+        }
+    }
+
+    /// The toggle shown, when the label isn't animating.
+    fn toggle(&self) -> Option<&Toggle> {
+        let layouts = self.layouts.as_ref()?;
+        let (_, more) = layouts.collapsed.as_ref()?;
+        if self.openness.animating() {
+            None
+        } else if self.expanded {
+            Some(&layouts.less)
+        } else {
+            Some(more)
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for ExpandableText<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        match event {
+            Event::AnimFrame(nanos) if self.openness.animating() => {
+                self.openness.update(ctx, *nanos);
+                ctx.request_paint();
+            }
+            Event::MouseMove(mouse) => {
+                let over = self
+                    .toggle()
+                    .map_or(false, |t| t.rect().contains(mouse.pos));
+                if over {
+                    ctx.set_cursor(&Cursor::Pointer);
+                } else {
+                    ctx.clear_cursor();
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                if self
+                    .toggle()
+                    .map_or(false, |t| t.rect().contains(mouse.pos))
+                {
+                    // the animation starts in update
+                    (self.set_expanded)(data, !self.expanded);
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.text.resolve(data, env);
+            self.expanded = (self.get_expanded)(data);
+            self.openness
+                .jump_to_value(if self.expanded { 1.0 } else { 0.0 });
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        if self.text.resolve(data, env) || ctx.env_changed() {
+            self.layouts = None;
+            ctx.request_layout();
+        }
+        let expanded = (self.get_expanded)(data);
+        if expanded != self.expanded {
+            self.expanded = expanded;
+            self.openness.animate(ctx, if expanded { 1.0 } else { 0.0 });
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let width = bc.max().width;
+        if self.layouts.as_ref().map_or(true, |l| l.width != width) {
+            let text = self.text.display_text();
+            self.layouts = Some(self.build(ctx.text(), &text, width, env));
+        }
+        let layouts = self.layouts.as_ref().unwrap();
+        let expanded_size = layouts
+            .full
+            .size()
+            .to_rect()
+            .union(layouts.less.rect())
+            .size();
+        let size = match &layouts.collapsed {
+            Some((collapsed, more)) => {
+                let collapsed_size = collapsed.size().to_rect().union(more.rect()).size();
+                let openness = self.openness.get();
+                Size::new(
+                    collapsed_size.width.max(expanded_size.width),
+                    collapsed_size.height
+                        + (expanded_size.height - collapsed_size.height) * openness,
+                )
+            }
+            None => layouts.full.size(),
+        };
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, _env: &Env) {
+        let layouts = match &self.layouts {
+            Some(layouts) => layouts,
+            None => return,
+        };
+        let animating = self.openness.animating();
+        let collapsed = match &layouts.collapsed {
+            Some((collapsed, more)) if !animating && !self.expanded => Some((collapsed, more)),
+            _ => None,
+        };
+        let show_less = layouts.collapsed.is_some() && !animating;
+        let clip = ctx.size().to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            match collapsed {
+                Some((collapsed, more)) => {
+                    ctx.draw_text(collapsed, Point::ORIGIN);
+                    ctx.draw_text(&more.layout, more.origin);
+                }
+                None => {
+                    ctx.draw_text(&layouts.full, Point::ORIGIN);
+                    if show_less {
+                        ctx.draw_text(&layouts.less.layout, layouts.less.origin);
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An area taking files.
+//!
+//! Druid doesn't report files dropped from other applications yet, so files come from a
+//! [`DragSource`] with a `Vec<PathBuf>` payload inside the app, or from the file dialog opened by
+//! clicking the zone.
+//!
+//! [`DragSource`]: crate::drag_drop::DragSource
+
+use std::path::{Path, PathBuf};
+
+use druid::commands::SHOW_OPEN_PANEL;
+use druid::widget::prelude::*;
+use druid::{FileDialogOptions, FileInfo, FileSpec, Point, Selector, WidgetPod};
+
+use crate::drag_drop::DropTarget;
+
+/// Notification sent by a [`FileDropZone`] with the files it took.
+pub const FILES_DROPPED: Selector<Vec<PathBuf>> =
+    Selector::new("druid-widget-nursery.file-drop-zone.files-dropped");
+
+/// Sent by the inner drop target to the zone.
+const DROPPED_PATHS: Selector<Vec<PathBuf>> =
+    Selector::new("druid-widget-nursery.file-drop-zone.dropped-paths");
+/// Sent by the file dialog when a file was chosen.
+const BROWSE_ACCEPTED: Selector<FileInfo> =
+    Selector::new("druid-widget-nursery.file-drop-zone.browse-accepted");
+/// Sent by the file dialog when it was closed without choosing a file.
+const BROWSE_CANCELLED: Selector =
+    Selector::new("druid-widget-nursery.file-drop-zone.browse-cancelled");
+
+/// A widget taking files dropped onto it, or chosen in a file dialog when it is clicked.
+///
+/// The files are passed to the [`on_files`] callback, and sent up as a [`FILES_DROPPED`]
+/// notification. With a file type, other files are left out, and drags without any accepted file
+/// are not highlighted.
+///
+/// [`on_files`]: #method.on_files
+#[allow(clippy::type_complexity)]
+pub struct FileDropZone<T> {
+    inner: WidgetPod<T, DropTarget<T, Vec<PathBuf>>>,
+    file_type: Option<FileSpec>,
+    on_files: Option<Box<dyn Fn(&mut EventCtx, Vec<PathBuf>, &mut T, &Env)>>,
+    browse: bool,
+    /// The file dialog was opened by this zone.
+    browsing: bool,
+}
+
+impl<T: Data> FileDropZone<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        let target = DropTarget::new(inner, |ctx, paths: Vec<PathBuf>, _, _| {
+            ctx.submit_notification(DROPPED_PATHS.with(paths))
+        });
+        FileDropZone {
+            inner: WidgetPod::new(target),
+            file_type: None,
+            on_files: None,
+            browse: true,
+            browsing: false,
+        }
+    }
+
+    /// Builder-style method for only taking files with the extensions of `file_type`.
+    ///
+    /// For the non-builder varient, see [`set_file_type`].
+    ///
+    /// [`set_file_type`]: #method.set_file_type
+    pub fn with_file_type(mut self, file_type: FileSpec) -> Self {
+        self.set_file_type(Some(file_type));
+        self
+    }
+
+    /// Set the type of the files taken, or `None` for any file.
+    pub fn set_file_type(&mut self, file_type: Option<FileSpec>) {
+        self.file_type = file_type;
+        let accept = move |paths: &Vec<PathBuf>, _: &T| {
+            paths.iter().any(|path| accepts(file_type.as_ref(), path))
+        };
+        self.inner.widget_mut().set_accept(accept);
+    }
+
+    /// Builder-style method for opening a file dialog when the zone is clicked.
+    ///
+    /// For the non-builder varient, see [`set_browse`].
+    ///
+    /// [`set_browse`]: #method.set_browse
+    pub fn with_browse(mut self, browse: bool) -> Self {
+        self.set_browse(browse);
+        self
+    }
+
+    /// Set whether clicking the zone opens a file dialog. This is on by default.
+    pub fn set_browse(&mut self, browse: bool) {
+        self.browse = browse;
+    }
+
+    /// Builder-style method for handling the files taken.
+    pub fn on_files(
+        mut self,
+        on_files: impl Fn(&mut EventCtx, Vec<PathBuf>, &mut T, &Env) + 'static,
+    ) -> Self {
+        self.on_files = Some(Box::new(on_files));
+        self
+    }
+
+    fn deliver(&self, ctx: &mut EventCtx, paths: Vec<PathBuf>, data: &mut T, env: &Env) {
+        let paths: Vec<_> = paths
+            .into_iter()
+            .filter(|path| accepts(self.file_type.as_ref(), path))
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        if let Some(on_files) = &self.on_files {
+            on_files(ctx, paths.clone(), data, env);
+        }
+        ctx.submit_notification(FILES_DROPPED.with(paths));
+    }
+}
+
+fn accepts(file_type: Option<&FileSpec>, path: &Path) -> bool {
+    let file_type = match file_type {
+        Some(file_type) => file_type,
+        None => return true,
+    };
+    let extension = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => extension,
+        None => return false,
+    };
+    file_type
+        .extensions
+        .iter()
+        .any(|accepted| accepted.eq_ignore_ascii_case(extension))
+}
+
+impl<T: Data> Widget<T> for FileDropZone<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Notification(notification) if notification.is(DROPPED_PATHS) => {
+                if let Some(paths) = notification.get(DROPPED_PATHS) {
+                    self.deliver(ctx, paths.clone(), data, env);
+                }
+                ctx.set_handled();
+                return;
+            }
+            // the dialog answers to the whole window
+            Event::Command(cmd) if cmd.is(BROWSE_ACCEPTED) && self.browsing => {
+                self.browsing = false;
+                let path = cmd.get_unchecked(BROWSE_ACCEPTED).path().to_owned();
+                self.deliver(ctx, vec![path], data, env);
+                ctx.set_handled();
+                return;
+            }
+            Event::Command(cmd) if cmd.is(BROWSE_CANCELLED) && self.browsing => {
+                self.browsing = false;
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        self.inner.event(ctx, event, data, env);
+        if !self.browse || ctx.is_handled() {
+            return;
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.set_active(true);
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() {
+                    let mut options = FileDialogOptions::new()
+                        .accept_command(BROWSE_ACCEPTED)
+                        .cancel_command(BROWSE_CANCELLED);
+                    if let Some(file_type) = self.file_type {
+                        options = options.allowed_types(vec![file_type]);
+                    }
+                    self.browsing = true;
+                    ctx.submit_command(SHOW_OPEN_PANEL.with(options));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
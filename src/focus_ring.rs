@@ -0,0 +1,83 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The ring drawn around the focused widget when it was focused with the keyboard.
+//!
+//! The interactive widgets of this crate draw it with the same [`COLOR`] and [`WIDTH`], and
+//! other widgets can use [`FocusVisible`] and [`paint`] to draw it the same way.
+
+use druid::widget::prelude::*;
+use druid::{theme, Color, Key, Rect};
+
+/// The color of the focus ring, [`theme::PRIMARY_LIGHT`] when it isn't set.
+pub const COLOR: Key<Color> = Key::new("druid-widget-nursery.focus-ring.color");
+/// The width of the focus ring, 2 when it isn't set.
+pub const WIDTH: Key<f64> = Key::new("druid-widget-nursery.focus-ring.width");
+
+/// Whether the focus ring of a widget is shown.
+///
+/// Like the `:focus-visible` of CSS, the ring is shown when the widget is focused with the
+/// keyboard, or when keys are pressed while it is focused itself, but not when it was clicked. The
+/// widget passes it its events and lifecycle events.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FocusVisible {
+    pointer_down: bool,
+    visible: bool,
+}
+
+impl FocusVisible {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn event(&mut self, ctx: &mut EventCtx, event: &Event) {
+        match event {
+            Event::MouseDown(_) => {
+                self.pointer_down = true;
+                if self.visible {
+                    self.visible = false;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(_) => self.pointer_down = false,
+            Event::KeyDown(_) if ctx.is_focused() && !self.visible => {
+                self.visible = true;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::FocusChanged(focused) = event {
+            self.visible = *focused && !self.pointer_down;
+            ctx.request_paint();
+        }
+    }
+
+    /// Whether the widget has the focus and the ring is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+/// Paint the focus ring just inside `rect`, with corners of `radius`.
+pub fn paint(ctx: &mut PaintCtx, rect: Rect, radius: f64, env: &Env) {
+    let color = env
+        .try_get(COLOR)
+        .unwrap_or_else(|_| env.get(theme::PRIMARY_LIGHT));
+    let width = env.try_get(WIDTH).unwrap_or(2.0);
+    let ring = rect.inset(-width / 2.0).to_rounded_rect(radius);
+    ctx.stroke(ring, &color, width);
+}
@@ -0,0 +1,445 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeping keyboard focus inside a part of a window, and ordering it.
+
+use druid::keyboard_types::Key;
+use druid::widget::prelude::*;
+use druid::{InternalLifeCycle, Point, Selector, WidgetPod};
+
+crate::selectors! {
+    /// Move the focus to the next widget of the scope. Can be sent to the [`FocusScope`] as a
+    /// command, or submitted as a notification by any widget inside it.
+    FOCUS_NEXT_IN_SCOPE,
+    /// Move the focus to the previous widget of the scope.
+    FOCUS_PREV_IN_SCOPE,
+    /// Move the focus to the first widget of the scope.
+    FOCUS_FIRST,
+    /// Move the focus to the last widget of the scope.
+    FOCUS_LAST,
+}
+
+/// Sent by a [`TabStop`] to itself, as `lifecycle` can't submit notifications.
+const REGISTER: Selector = Selector::new("druid-widget-nursery.focus-scope.register");
+/// Sent by a [`TabStop`] to the scope around it, with the id of its child and its index.
+const REGISTER_TAB_STOP: Selector<(WidgetId, usize)> =
+    Selector::new("druid-widget-nursery.focus-scope.register-tab-stop");
+/// Sent by the scope to itself for the next step of moving the focus.
+const STEP: Selector = Selector::new("druid-widget-nursery.focus-scope.step");
+
+/// Whether `event` is `selector`, as a command or as a notification.
+pub(crate) fn is_request(event: &Event, selector: Selector) -> bool {
+    match event {
+        Event::Command(cmd) => cmd.is(selector),
+        Event::Notification(notification) => notification.is(selector),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Next,
+    Prev,
+    Focus(WidgetId),
+    /// Walk through the widgets without a tab index, from the start or the end.
+    Natural(Direction),
+}
+
+/// An invisible widget in the focus chain, marking the start or the end of a scope.
+struct FocusAnchor;
+
+impl<T> Widget<T> for FocusAnchor {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        if let LifeCycle::BuildFocusChain = event {
+            ctx.register_for_focus();
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {}
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        _bc: &BoxConstraints,
+        _data: &T,
+        _env: &Env,
+    ) -> Size {
+        Size::ZERO
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, _data: &T, _env: &Env) {}
+}
+
+/// A widget keeping Tab and Shift+Tab focus cycling inside its child.
+///
+/// Widgets wrapped in a [`TabStop`] are focused first, in the order of their indices, followed
+/// by the other focusable widgets in their usual order. After the last widget the focus wraps
+/// around to the first one.
+///
+/// ```
+/// # use druid::widget::{Flex, TextBox};
+/// # use druid::{lens, Widget, WidgetExt as _};
+/// # use druid_widget_nursery::{FocusScope, WidgetExt as _};
+/// fn login() -> impl Widget<(String, String)> {
+///     FocusScope::new(
+///         Flex::column()
+///             .with_child(TextBox::new().lens(lens!((String, String), 0)).tab_index(1))
+///             .with_child(TextBox::new().lens(lens!((String, String), 1)).tab_index(0)),
+///     )
+///     .with_autofocus(true)
+/// }
+/// ```
+pub struct FocusScope<T> {
+    start: WidgetPod<T, FocusAnchor>,
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    end: WidgetPod<T, FocusAnchor>,
+    /// The registered tab stops, in the order they were registered.
+    stops: Vec<(WidgetId, usize)>,
+    focused: Option<WidgetId>,
+    /// The direction the focus is moving in, while it is passing over widgets.
+    moving: Option<Direction>,
+    /// Whether the focus went around once during this move, to stop if nothing is focusable.
+    wrapped: bool,
+    pending: Option<Step>,
+    autofocus: bool,
+}
+
+impl<T: Data> FocusScope<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        FocusScope {
+            start: WidgetPod::new(FocusAnchor),
+            inner: WidgetPod::new(Box::new(inner)),
+            end: WidgetPod::new(FocusAnchor),
+            stops: Vec::new(),
+            focused: None,
+            moving: None,
+            wrapped: false,
+            pending: None,
+            autofocus: false,
+        }
+    }
+
+    /// Builder-style method for focusing the first widget when the scope is added.
+    ///
+    /// For the non-builder varient, see [`set_autofocus`].
+    ///
+    /// [`set_autofocus`]: #method.set_autofocus
+    pub fn with_autofocus(mut self, autofocus: bool) -> Self {
+        self.set_autofocus(autofocus);
+        self
+    }
+
+    /// Set whether the first widget is focused when the scope is added, like when a dialog
+    /// opens.
+    pub fn set_autofocus(&mut self, autofocus: bool) {
+        self.autofocus = autofocus;
+    }
+
+    /// The tab stops sorted by their index.
+    fn ordered_stops(&self) -> Vec<WidgetId> {
+        let mut stops = self.stops.clone();
+        // stable, so equal indices stay in the order of registration
+        stops.sort_by_key(|(_, index)| *index);
+        stops.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn natural(&mut self, ctx: &mut EventCtx, direction: Direction) {
+        self.moving = Some(direction);
+        let anchor = match direction {
+            Direction::Forward => self.start.id(),
+            Direction::Backward => self.end.id(),
+        };
+        ctx.set_focus(anchor);
+    }
+
+    fn focus_first(&mut self, ctx: &mut EventCtx) {
+        self.wrapped = false;
+        match self.ordered_stops().first() {
+            Some(&first) => {
+                self.moving = None;
+                ctx.set_focus(first);
+            }
+            None => self.natural(ctx, Direction::Forward),
+        }
+    }
+
+    fn focus_last(&mut self, ctx: &mut EventCtx) {
+        self.wrapped = false;
+        self.natural(ctx, Direction::Backward);
+    }
+
+    fn move_focus(&mut self, ctx: &mut EventCtx, direction: Direction) {
+        self.wrapped = false;
+        let stops = self.ordered_stops();
+        let position = self
+            .focused
+            .and_then(|focused| stops.iter().position(|&id| id == focused));
+        match (position, direction) {
+            (Some(i), Direction::Forward) if i + 1 < stops.len() => ctx.set_focus(stops[i + 1]),
+            (Some(i), Direction::Backward) if i > 0 => ctx.set_focus(stops[i - 1]),
+            // continue with the widgets without an index
+            (Some(_), _) => self.natural(ctx, direction),
+            (None, Direction::Forward) => {
+                self.moving = Some(direction);
+                ctx.focus_next();
+            }
+            (None, Direction::Backward) => {
+                self.moving = Some(direction);
+                ctx.focus_prev();
+            }
+        }
+    }
+
+    /// What to do after the focus moved to `new` inside the scope.
+    fn next_step(&mut self, new: WidgetId) -> Option<Step> {
+        let stops = self.ordered_stops();
+        let direction = match self.moving {
+            Some(direction) => direction,
+            // tabbed into the scope from outside
+            None if new == self.start.id() => {
+                return match stops.first() {
+                    Some(&first) => Some(Step::Focus(first)),
+                    None => {
+                        self.moving = Some(Direction::Forward);
+                        Some(Step::Next)
+                    }
+                };
+            }
+            None if new == self.end.id() => {
+                self.moving = Some(Direction::Backward);
+                return Some(Step::Prev);
+            }
+            None => return None,
+        };
+
+        let at_end = match direction {
+            Direction::Forward => new == self.end.id(),
+            Direction::Backward => new == self.start.id(),
+        };
+        if at_end {
+            // went past the widgets without an index, wrap around to the tab stops
+            let stop = match direction {
+                Direction::Forward => stops.first(),
+                Direction::Backward => stops.last(),
+            };
+            if let Some(&stop) = stop {
+                return Some(Step::Focus(stop));
+            }
+            if self.wrapped {
+                // nothing is focusable
+                self.moving = None;
+                return None;
+            }
+            self.wrapped = true;
+            return Some(Step::Natural(direction));
+        }
+
+        if new == self.start.id() || new == self.end.id() || stops.contains(&new) {
+            // tab stops were visited before the other widgets
+            return Some(match direction {
+                Direction::Forward => Step::Next,
+                Direction::Backward => Step::Prev,
+            });
+        }
+        self.moving = None;
+        None
+    }
+}
+
+impl<T: Data> Widget<T> for FocusScope<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(STEP) => {
+                match self.pending.take() {
+                    Some(Step::Next) => ctx.focus_next(),
+                    Some(Step::Prev) => ctx.focus_prev(),
+                    Some(Step::Focus(id)) => {
+                        self.moving = None;
+                        ctx.set_focus(id);
+                    }
+                    Some(Step::Natural(direction)) => self.natural(ctx, direction),
+                    None => {}
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(cmd) if cmd.is(REGISTER_TAB_STOP) => {
+                if let Some(&(id, index)) = cmd.get(REGISTER_TAB_STOP) {
+                    match self.stops.iter_mut().find(|(stop, _)| *stop == id) {
+                        Some(stop) => stop.1 = index,
+                        None => self.stops.push((id, index)),
+                    }
+                }
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, FOCUS_NEXT_IN_SCOPE) => {
+                self.move_focus(ctx, Direction::Forward);
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, FOCUS_PREV_IN_SCOPE) => {
+                self.move_focus(ctx, Direction::Backward);
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, FOCUS_FIRST) => {
+                self.focus_first(ctx);
+                ctx.set_handled();
+                return;
+            }
+            _ if is_request(event, FOCUS_LAST) => {
+                self.focus_last(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key)
+                if key.key == Key::Tab
+                    && ctx.has_focus()
+                    && !key.mods.ctrl()
+                    && !key.mods.alt()
+                    && !key.mods.meta() =>
+            {
+                let direction = if key.mods.shift() {
+                    Direction::Backward
+                } else {
+                    Direction::Forward
+                };
+                self.move_focus(ctx, direction);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.start.event(ctx, event, data, env);
+        self.inner.event(ctx, event, data, env);
+        self.end.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::BuildFocusChain = event {
+            // the tab stops register again, leaving out removed ones
+            self.stops.clear();
+        }
+
+        self.start.lifecycle(ctx, event, data, env);
+        self.inner.lifecycle(ctx, event, data, env);
+        self.end.lifecycle(ctx, event, data, env);
+
+        match event {
+            LifeCycle::WidgetAdded if self.autofocus => {
+                // after the tab stops, so they are registered by then
+                ctx.submit_command(FOCUS_FIRST.to(ctx.widget_id()));
+            }
+            LifeCycle::Internal(InternalLifeCycle::RouteFocusChanged { new, .. }) => match new {
+                Some(new) if ctx.has_focus() => {
+                    self.focused = Some(*new);
+                    if let Some(step) = self.next_step(*new) {
+                        self.pending = Some(step);
+                        ctx.submit_command(STEP.to(ctx.widget_id()));
+                    }
+                }
+                _ => {
+                    self.focused = None;
+                    self.moving = None;
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.start.update(ctx, data, env);
+        self.inner.update(ctx, data, env);
+        self.end.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let anchor_bc = BoxConstraints::tight(Size::ZERO);
+        self.start.layout(ctx, &anchor_bc, data, env);
+        self.start.set_origin(ctx, Point::ORIGIN);
+        self.end.layout(ctx, &anchor_bc, data, env);
+        self.end.set_origin(ctx, Point::ORIGIN);
+
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
+
+/// A widget focused before the others inside a [`FocusScope`], in the order of its index.
+///
+/// Wrap the focusable widget itself, like a `TextBox`, not a container around it. Widgets with
+/// the same index are focused in the order they were added.
+pub struct TabStop<T, W> {
+    inner: WidgetPod<T, W>,
+    index: usize,
+}
+
+impl<T: Data, W: Widget<T>> TabStop<T, W> {
+    pub fn new(inner: W, index: usize) -> Self {
+        TabStop {
+            inner: WidgetPod::new(inner),
+            index,
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for TabStop<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if cmd.is(REGISTER) {
+                ctx.submit_notification(REGISTER_TAB_STOP.with((self.inner.id(), self.index)));
+                ctx.set_handled();
+                return;
+            }
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::BuildFocusChain = event {
+            ctx.submit_command(REGISTER.to(ctx.widget_id()));
+        }
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Forms with validated fields.
+//!
+//! Wrap input widgets in [`Validated`], usually with [`WidgetExt::validate`], and put them
+//! anywhere inside a [`Form`]. The fields show the message of the first failing [`Validator`]
+//! below themselves once they are touched, and the form sets [`IS_VALID`], [`IS_DIRTY`] and
+//! [`IS_TOUCHED`] in the environment of its child, for example to disable the submit button:
+//!
+//! ```
+//! # use druid::widget::{Button, Flex, TextBox};
+//! # use druid::{Data, Lens, Widget, WidgetExt as _};
+//! # use druid_widget_nursery::form::{self, Form, Validator};
+//! # use druid_widget_nursery::WidgetExt;
+//! #[derive(Clone, Data, Lens)]
+//! struct Signup {
+//!     name: String,
+//!     age: f64,
+//! }
+//!
+//! fn signup() -> impl Widget<Signup> {
+//!     let name = TextBox::new()
+//!         .validate(Validator::required("Please enter your name"))
+//!         .lens(Signup::name);
+//!     let submit = Button::new("Sign up")
+//!         .on_click(|_, _: &mut Signup, _| println!("signed up"))
+//!         .disabled_if(|_, env| !env.get(form::IS_VALID));
+//!
+//!     Form::new(Flex::column().with_child(name).with_child(submit))
+//! }
+//! ```
+//!
+//! [`WidgetExt::validate`]: crate::WidgetExt::validate
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use druid::widget::prelude::*;
+use druid::widget::RawLabel;
+use druid::{theme, Color, Key, Point, Selector, WidgetPod};
+
+use crate::{AnyCtx, CommandCtx};
+
+/// True if all fields of the surrounding [`Form`] are valid.
+pub const IS_VALID: Key<bool> = Key::new("druid-widget-nursery.form.is-valid");
+/// True if a field of the surrounding [`Form`] was changed.
+pub const IS_DIRTY: Key<bool> = Key::new("druid-widget-nursery.form.is-dirty");
+/// True if a field of the surrounding [`Form`] was touched.
+pub const IS_TOUCHED: Key<bool> = Key::new("druid-widget-nursery.form.is-touched");
+
+/// Mark all fields as touched, showing all errors. Send this when the user tries to submit an
+/// invalid form.
+pub const TOUCH_ALL: Selector = Selector::new("druid-widget-nursery.form.touch-all");
+/// Mark all fields as untouched, and take their current values as the unchanged ones. Send this
+/// after the form was submitted or reset.
+pub const MARK_PRISTINE: Selector = Selector::new("druid-widget-nursery.form.mark-pristine");
+
+/// Sent by a field to itself, as only `event` can submit notifications.
+const REPORT: Selector = Selector::new("druid-widget-nursery.form.report");
+/// Sent by the form to itself when it forgot fields, as only `event` can request an update.
+const FIELDS_CHANGED: Selector = Selector::new("druid-widget-nursery.form.fields-changed");
+/// Sent by a field to the form when its state changed.
+const FIELD_STATE: Selector<FieldState> = Selector::new("druid-widget-nursery.form.field-state");
+
+const ERROR_COLOR: Color = Color::rgb8(0xe0, 0x40, 0x40);
+const ERROR_SPACING: f64 = 2.0;
+
+/// Checks the value of a [`Validated`] field.
+///
+/// ```
+/// # use druid_widget_nursery::form::Validator;
+/// let name = Validator::required("Please enter a name");
+/// let age = Validator::range(0.0, 150.0);
+/// let even = Validator::new(|value: &i64| match value % 2 {
+///     0 => Ok(()),
+///     _ => Err("Must be even".to_string()),
+/// });
+///
+/// assert!(name.validate(&String::new()).is_err());
+/// assert!(age.validate(&42.0).is_ok());
+/// assert!(even.validate(&3).is_err());
+/// ```
+pub struct Validator<T> {
+    check: Box<dyn Fn(&T) -> Result<(), String>>,
+}
+
+impl<T> Validator<T> {
+    /// Create a validator from a function returning the error message for invalid values.
+    pub fn new(check: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        Validator {
+            check: Box::new(check),
+        }
+    }
+
+    /// Check `value`, returning the error message if it is invalid.
+    pub fn validate(&self, value: &T) -> Result<(), String> {
+        (self.check)(value)
+    }
+}
+
+impl Validator<String> {
+    /// Values must not be empty or whitespace only.
+    pub fn required(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Validator::new(move |value: &String| {
+            if value.trim().is_empty() {
+                Err(message.clone())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Values must match `regex`. Use `^` and `$` to match the whole value.
+    #[cfg(feature = "regex")]
+    pub fn regex(regex: regex::Regex, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Validator::new(move |value: &String| {
+            if regex.is_match(value) {
+                Ok(())
+            } else {
+                Err(message.clone())
+            }
+        })
+    }
+}
+
+impl<T: PartialOrd + Display + 'static> Validator<T> {
+    /// Values must be between `min` and `max`, inclusive.
+    pub fn range(min: T, max: T) -> Self {
+        Validator::new(move |value: &T| {
+            if *value < min || *value > max {
+                Err(format!("Must be between {} and {}", min, max))
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+/// What a [`Form`] knows about a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldState {
+    valid: bool,
+    dirty: bool,
+    touched: bool,
+}
+
+/// A field checking its data with [`Validator`]s, and showing the first error below its child
+/// once it was touched, that is changed by the user or left by the focus.
+///
+/// The child gets a red border while the error is shown. See the [module docs](self) for an
+/// example.
+pub struct Validated<T, W> {
+    inner: WidgetPod<T, W>,
+    error_label: WidgetPod<String, RawLabel<String>>,
+    validators: Vec<Validator<T>>,
+    /// The data when the field was added or marked pristine.
+    initial: Option<T>,
+    error: Option<String>,
+    touched: bool,
+    had_focus: bool,
+    /// The text of the error label, empty while no error is shown.
+    shown_error: String,
+    /// The state the form knows about.
+    reported: Option<FieldState>,
+    /// The last environment we got, and the one we pass to our child.
+    env: Option<(Env, Env)>,
+}
+
+impl<T: Data, W: Widget<T>> Validated<T, W> {
+    /// Create a field around `inner`, without validators yet.
+    pub fn new(inner: W) -> Self {
+        Validated {
+            inner: WidgetPod::new(inner),
+            error_label: WidgetPod::new(RawLabel::new().with_text_color(ERROR_COLOR)),
+            validators: Vec::new(),
+            initial: None,
+            error: None,
+            touched: false,
+            had_focus: false,
+            shown_error: String::new(),
+            reported: None,
+            env: None,
+        }
+    }
+
+    /// Builder-style method for adding a [`Validator`].
+    ///
+    /// For the non-builder varient, see [`add_validator`].
+    ///
+    /// [`add_validator`]: #method.add_validator
+    pub fn with_validator(mut self, validator: Validator<T>) -> Self {
+        self.add_validator(validator);
+        self
+    }
+
+    /// Add a [`Validator`], checked after the ones added before.
+    pub fn add_validator(&mut self, validator: Validator<T>) {
+        self.validators.push(validator);
+    }
+
+    /// Returns the current error, even if it isn't shown yet.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn validate(&mut self, data: &T) {
+        self.error = self
+            .validators
+            .iter()
+            .find_map(|validator| validator.validate(data).err());
+    }
+
+    fn state(&self, data: &T) -> FieldState {
+        FieldState {
+            valid: self.error.is_none(),
+            dirty: self
+                .initial
+                .as_ref()
+                .map_or(false, |initial| !initial.same(data)),
+            touched: self.touched,
+        }
+    }
+
+    fn error_text(&self) -> String {
+        match &self.error {
+            Some(error) if self.touched => error.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Tell the form about our state, once we can.
+    fn report(ctx: &mut (impl CommandCtx + AnyCtx)) {
+        ctx.submit_command(REPORT.to(ctx.widget_id()));
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let mut child_env = env.clone();
+                if !self.shown_error.is_empty() {
+                    child_env.set(theme::BORDER_DARK, ERROR_COLOR);
+                }
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for Validated<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(REPORT) => {
+                let state = self.state(data);
+                if self.reported != Some(state) {
+                    self.reported = Some(state);
+                    ctx.submit_notification(FIELD_STATE.with(state));
+                }
+                if self.error_text() != self.shown_error {
+                    ctx.request_update();
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::Command(cmd) if cmd.is(TOUCH_ALL) => {
+                self.touched = true;
+                Self::report(ctx);
+            }
+            Event::Command(cmd) if cmd.is(MARK_PRISTINE) => {
+                self.initial = Some(data.clone());
+                self.touched = false;
+                Self::report(ctx);
+            }
+            _ => {}
+        }
+
+        let old_data = data.clone();
+        let child_env = self.child_env(env);
+        self.inner.event(ctx, event, data, &child_env);
+        if !old_data.same(data) {
+            // changed by the user
+            self.touched = true;
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.initial = Some(data.clone());
+                self.validate(data);
+                Self::report(ctx);
+            }
+            // the form forgot all fields, see `Form::lifecycle`
+            LifeCycle::BuildFocusChain => {
+                self.reported = None;
+                Self::report(ctx);
+            }
+            _ => {}
+        }
+        let child_env = self.child_env(env);
+        self.inner.lifecycle(ctx, event, data, &child_env);
+        self.error_label
+            .lifecycle(ctx, event, &self.shown_error, env);
+
+        // leaving the field touches it
+        let has_focus = ctx.has_focus();
+        if self.had_focus && !has_focus && !self.touched {
+            self.touched = true;
+            Self::report(ctx);
+        }
+        self.had_focus = has_focus;
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if !old_data.same(data) {
+            self.validate(data);
+            Self::report(ctx);
+        }
+        let error_text = self.error_text();
+        if error_text != self.shown_error {
+            self.shown_error = error_text;
+            // not every widget checks for env changes
+            self.env = None;
+            ctx.request_layout();
+            ctx.request_paint();
+        }
+
+        let child_env = self.child_env(env);
+        self.inner.update(ctx, data, &child_env);
+        self.error_label.update(ctx, &self.shown_error, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_env = self.child_env(env);
+        let inner_size = self.inner.layout(ctx, bc, data, &child_env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        let label_bc = BoxConstraints::new(
+            Size::ZERO,
+            Size::new(inner_size.width.max(bc.min().width), f64::INFINITY),
+        );
+        let label_size = self
+            .error_label
+            .layout(ctx, &label_bc, &self.shown_error, env);
+        let label_origin = Point::new(0.0, inner_size.height + ERROR_SPACING);
+        self.error_label.set_origin(ctx, label_origin);
+        if self.shown_error.is_empty() {
+            return inner_size;
+        }
+
+        bc.constrain(Size::new(
+            inner_size.width.max(label_size.width),
+            label_origin.y + label_size.height,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.paint(ctx, data, &child_env);
+        if !self.shown_error.is_empty() {
+            self.error_label.paint(ctx, &self.shown_error, env);
+        }
+    }
+}
+
+/// A container collecting the state of the [`Validated`] fields inside of it.
+///
+/// The form sets [`IS_VALID`], [`IS_DIRTY`] and [`IS_TOUCHED`] in the environment of its
+/// child.
+pub struct Form<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    fields: HashMap<WidgetId, FieldState>,
+    /// The last environment we got, and the one we pass to our child.
+    env: Option<(Env, Env)>,
+}
+
+impl<T: Data> Form<T> {
+    /// Create a form around `inner`, which contains the [`Validated`] fields.
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Form {
+            inner: WidgetPod::new(Box::new(inner)),
+            fields: HashMap::new(),
+            env: None,
+        }
+    }
+
+    /// Returns true if all fields are valid.
+    pub fn is_valid(&self) -> bool {
+        self.fields.values().all(|field| field.valid)
+    }
+
+    /// Returns true if a field was changed.
+    pub fn is_dirty(&self) -> bool {
+        self.fields.values().any(|field| field.dirty)
+    }
+
+    /// Returns true if a field was touched.
+    pub fn is_touched(&self) -> bool {
+        self.fields.values().any(|field| field.touched)
+    }
+
+    fn summary(&self) -> (bool, bool, bool) {
+        (self.is_valid(), self.is_dirty(), self.is_touched())
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let child_env = env
+                    .clone()
+                    .adding(IS_VALID, self.is_valid())
+                    .adding(IS_DIRTY, self.is_dirty())
+                    .adding(IS_TOUCHED, self.is_touched());
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Form<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if cmd.is(FIELDS_CHANGED) {
+                ctx.request_update();
+                ctx.set_handled();
+                return;
+            }
+        }
+        if let Event::Notification(notification) = event {
+            if let Some(state) = notification.get(FIELD_STATE) {
+                let summary = self.summary();
+                self.fields.insert(notification.source(), *state);
+                if summary != self.summary() {
+                    self.env = None;
+                    ctx.request_update();
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+        let child_env = self.child_env(env);
+        self.inner.event(ctx, event, data, &child_env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::BuildFocusChain = event {
+            // the widget tree changed and fields may be gone, the ones left report again
+            let summary = self.summary();
+            self.fields.clear();
+            if summary != self.summary() {
+                self.env = None;
+                ctx.submit_command(FIELDS_CHANGED.to(ctx.widget_id()));
+            }
+        }
+        let child_env = self.child_env(env);
+        self.inner.lifecycle(ctx, event, data, &child_env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.update(ctx, data, &child_env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_env = self.child_env(env);
+        let size = self.inner.layout(ctx, bc, data, &child_env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.paint(ctx, data, &child_env);
+    }
+}
@@ -12,35 +12,57 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! widgets that can run async tasks
-
-// TODO: add StreamWidget
+//! widgets that can run async tasks, and follow streams
+//!
+//! The tasks run on the tokio runtime, which has to be running when the widget is added. With
+//! the `thread-pool` feature they run on a thread pool instead, and no runtime is needed.
 
 use std::{any::Any, future::Future, pin::Pin};
 
 use druid::widget::prelude::*;
-use druid::{Data, Selector, SingleUse, Target, WidgetPod};
+use druid::widget::Controller;
+use druid::{Data, ExtEventSink, Point, Selector, SingleUse, Target, WidgetPod};
+use futures::future::{self, AbortHandle};
+use futures::{Stream, StreamExt};
 
 struct Response {
+    /// The task this is the result of.
+    generation: u64,
     value: Box<dyn Any + Send>,
 }
 
 const ASYNC_RESPONSE: Selector<SingleUse<Response>> = Selector::new("druid-async.async-response");
+const CHANGE_RESPONSE: Selector<SingleUse<Response>> = Selector::new("druid-async.change-response");
+const STREAM_ITEM: Selector<SingleUse<Box<dyn Any + Send>>> =
+    Selector::new("druid-async.stream-item");
 
 #[cfg(target_arch = "wasm32")]
 pub type FutureWidgetAction<T> =
-    Box<dyn FnOnce(&T, &Env) -> Pin<Box<dyn Future<Output = Box<dyn Any + Send>>>>>;
+    Box<dyn FnMut(&T, &Env) -> Pin<Box<dyn Future<Output = Box<dyn Any + Send>>>>>;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub type FutureWidgetAction<T> =
-    Box<dyn FnOnce(&T, &Env) -> Pin<Box<dyn Send + Future<Output = Box<dyn Any + Send>>>>>;
+    Box<dyn FnMut(&T, &Env) -> Pin<Box<dyn Send + Future<Output = Box<dyn Any + Send>>>>>;
 
-pub type FutureWidgetDone<T, U> = Box<dyn FnOnce(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>>>;
+pub type FutureWidgetDone<T, U> = Box<dyn FnMut(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>>>;
 
+/// A widget running a future when it is added, showing the `pending` widget until the future
+/// is done, and then the widget made by `on_done` from its output.
+///
+/// `on_done` can also write the output into the app data. A widget made with
+/// [`with_rerun_on_change`] runs the future again whenever the data changes, going back to the
+/// pending widget meanwhile.
+///
+/// [`with_rerun_on_change`]: #method.with_rerun_on_change
 pub struct FutureWidget<T, U> {
-    future: Option<FutureWidgetAction<T>>,
-    inner: WidgetPod<T, Box<dyn Widget<T>>>,
-    on_done: Option<FutureWidgetDone<T, U>>,
+    future: FutureWidgetAction<T>,
+    pending: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// The widget made from the output, once the future is done.
+    done: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    on_done: FutureWidgetDone<T, U>,
+    rerun_on_change: bool,
+    /// Counts the tasks, to ignore the output of outdated ones.
+    generation: u64,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -55,16 +77,74 @@ impl<T, U> FutureWidget<T, U> {
         FMaker: FnOnce(&T, &Env) -> Fut + 'static,
         Fut: Future<Output = U> + 'static,
         Done: FnOnce(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+    {
+        let mut future_maker = Some(future_maker);
+        let mut on_done = Some(on_done);
+        let mut widget = Self::with_rerun_on_change(
+            move |data, env| future_maker.take().expect(RAN_TWICE)(data, env),
+            pending,
+            move |value, data, env| on_done.take().expect(RAN_TWICE)(value, data, env),
+        );
+        // so both run once
+        widget.rerun_on_change = false;
+        widget
+    }
+
+    /// Like [`new`], but running the future again whenever the data changes, which is why the
+    /// closures are `FnMut`.
+    ///
+    /// [`new`]: #method.new
+    pub fn with_rerun_on_change<FMaker, Fut, Done>(
+        mut future_maker: FMaker,
+        pending: impl Widget<T> + 'static,
+        on_done: Done,
+    ) -> Self
+    where
+        U: Send + 'static,
+        FMaker: FnMut(&T, &Env) -> Fut + 'static,
+        Fut: Future<Output = U> + 'static,
+        Done: FnMut(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
     {
         Self {
-            future: Some(Box::new(move |data, env| {
+            future: Box::new(move |data, env| {
                 let fut = future_maker(data, env);
                 Box::pin(async move { Box::new(fut.await) as _ })
-            })),
-            inner: WidgetPod::new(Box::new(pending)),
-            on_done: Some(Box::new(on_done)),
+            }),
+            pending: WidgetPod::new(Box::new(pending)),
+            done: None,
+            on_done: Box::new(on_done),
+            rerun_on_change: true,
+            generation: 0,
         }
     }
+
+    /// Like [`new`], for a future that can fail, with separate widgets for the value and the
+    /// error.
+    ///
+    /// [`new`]: #method.new
+    pub fn new_fallible<FMaker, Fut, V, E, OnOk, OnErr>(
+        future_maker: FMaker,
+        pending: impl Widget<T> + 'static,
+        on_ok: OnOk,
+        on_err: OnErr,
+    ) -> FutureWidget<T, Result<V, E>>
+    where
+        V: Send + 'static,
+        E: Send + 'static,
+        FMaker: FnOnce(&T, &Env) -> Fut + 'static,
+        Fut: Future<Output = Result<V, E>> + 'static,
+        OnOk: FnOnce(V, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+        OnErr: FnOnce(E, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+    {
+        FutureWidget::new(
+            future_maker,
+            pending,
+            move |result, data, env| match *result {
+                Ok(value) => on_ok(value, data, env),
+                Err(err) => on_err(err, data, env),
+            },
+        )
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -79,16 +159,126 @@ impl<T, U> FutureWidget<T, U> {
         FMaker: FnOnce(&T, &Env) -> Fut + 'static,
         Fut: Future<Output = U> + 'static + Send,
         Done: FnOnce(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+    {
+        let mut future_maker = Some(future_maker);
+        let mut on_done = Some(on_done);
+        let mut widget = Self::with_rerun_on_change(
+            move |data, env| future_maker.take().expect(RAN_TWICE)(data, env),
+            pending,
+            move |value, data, env| on_done.take().expect(RAN_TWICE)(value, data, env),
+        );
+        // so both run once
+        widget.rerun_on_change = false;
+        widget
+    }
+
+    /// Like [`new`], but running the future again whenever the data changes, which is why the
+    /// closures are `FnMut`.
+    ///
+    /// [`new`]: #method.new
+    pub fn with_rerun_on_change<FMaker, Fut, Done>(
+        mut future_maker: FMaker,
+        pending: impl Widget<T> + 'static,
+        on_done: Done,
+    ) -> Self
+    where
+        U: Send + 'static,
+        FMaker: FnMut(&T, &Env) -> Fut + 'static,
+        Fut: Future<Output = U> + 'static + Send,
+        Done: FnMut(Box<U>, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
     {
         Self {
-            future: Some(Box::new(move |data, env| {
+            future: Box::new(move |data, env| {
                 let fut = future_maker(data, env);
                 Box::pin(async move { Box::new(fut.await) as _ })
-            })),
-            inner: WidgetPod::new(Box::new(pending)),
-            on_done: Some(Box::new(on_done)),
+            }),
+            pending: WidgetPod::new(Box::new(pending)),
+            done: None,
+            on_done: Box::new(on_done),
+            rerun_on_change: true,
+            generation: 0,
         }
     }
+
+    /// Like [`new`], for a future that can fail, with separate widgets for the value and the
+    /// error.
+    ///
+    /// [`new`]: #method.new
+    pub fn new_fallible<FMaker, Fut, V, E, OnOk, OnErr>(
+        future_maker: FMaker,
+        pending: impl Widget<T> + 'static,
+        on_ok: OnOk,
+        on_err: OnErr,
+    ) -> FutureWidget<T, Result<V, E>>
+    where
+        V: Send + 'static,
+        E: Send + 'static,
+        FMaker: FnOnce(&T, &Env) -> Fut + 'static,
+        Fut: Future<Output = Result<V, E>> + 'static + Send,
+        OnOk: FnOnce(V, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+        OnErr: FnOnce(E, &mut T, &Env) -> Box<dyn Widget<T>> + 'static,
+    {
+        FutureWidget::new(
+            future_maker,
+            pending,
+            move |result, data, env| match *result {
+                Ok(value) => on_ok(value, data, env),
+                Err(err) => on_err(err, data, env),
+            },
+        )
+    }
+}
+
+const RAN_TWICE: &str = "FutureWidget::new only runs the future once";
+
+impl<T, U> FutureWidget<T, U> {
+    fn spawn(&mut self, sink: ExtEventSink, widget_id: WidgetId, data: &T, env: &Env) {
+        self.generation += 1;
+        let generation = self.generation;
+        let future = (self.future)(data, env);
+        let task = async move {
+            let value = future.await;
+            // fails only if the app is closed by now
+            let _ = sink.submit_command(
+                ASYNC_RESPONSE,
+                SingleUse::new(Response { generation, value }),
+                Target::Widget(widget_id),
+            );
+        };
+        spawn(task);
+    }
+
+    fn current(&mut self) -> &mut WidgetPod<T, Box<dyn Widget<T>>> {
+        self.done.as_mut().unwrap_or(&mut self.pending)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn(task: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(task);
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "thread-pool"))]
+fn spawn(task: impl Future<Output = ()> + Send + 'static) {
+    thread_pool().spawn_ok(task);
+}
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "thread-pool")))]
+fn spawn(task: impl Future<Output = ()> + Send + 'static) {
+    tokio::spawn(task);
+}
+
+/// The pool shared by all future and stream widgets, started with the first task.
+#[cfg(all(not(target_arch = "wasm32"), feature = "thread-pool"))]
+fn thread_pool() -> futures::executor::ThreadPool {
+    use futures::executor::ThreadPool;
+    use std::sync::Mutex;
+
+    static POOL: Mutex<Option<ThreadPool>> = Mutex::new(None);
+    POOL.lock()
+        .unwrap()
+        .get_or_insert_with(|| ThreadPool::new().expect("failed to start the thread pool"))
+        .clone()
 }
 
 impl<T: Data, U: 'static> Widget<T> for FutureWidget<T, U> {
@@ -96,38 +286,139 @@ impl<T: Data, U: 'static> Widget<T> for FutureWidget<T, U> {
         if let Event::Command(cmd) = event {
             if let Some(res) = cmd.get(ASYNC_RESPONSE) {
                 let res = res.take().unwrap();
-                let value = res.value.downcast::<U>().unwrap();
-                let on_done = self.on_done.take().unwrap();
-                self.inner = WidgetPod::new((on_done)(value, data, env));
-                ctx.children_changed();
+                if res.generation == self.generation {
+                    let value = res.value.downcast::<U>().unwrap();
+                    self.done = Some(WidgetPod::new((self.on_done)(value, data, env)));
+                    ctx.children_changed();
+                }
+                ctx.set_handled();
                 return;
             }
         }
-        self.inner.event(ctx, event, data, env);
+        self.current().event(ctx, event, data, env);
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
         if let LifeCycle::WidgetAdded = event {
-            let sink = ctx.get_external_handle();
-            let widget_id = ctx.widget_id();
-            let future = self.future.take().unwrap()(data, env);
-            let task = async move {
-                let data = future.await;
-                sink.submit_command(
-                    ASYNC_RESPONSE,
-                    SingleUse::new(Response { value: data }),
-                    Target::Widget(widget_id),
-                )
-                .unwrap();
-            };
+            self.spawn(ctx.get_external_handle(), ctx.widget_id(), data, env);
+        }
+
+        self.current().lifecycle(ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if self.rerun_on_change && !old_data.same(data) {
+            self.spawn(ctx.get_external_handle(), ctx.widget_id(), data, env);
+            if self.done.take().is_some() {
+                ctx.children_changed();
+            }
+        }
+        self.current().update(ctx, data, env)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.current().layout(ctx, bc, data, env);
+        self.current().set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.current().paint(ctx, data, env)
+    }
+}
 
-            #[cfg(target_arch = "wasm32")]
-            wasm_bindgen_futures::spawn_local(task);
+#[cfg(target_arch = "wasm32")]
+pub type StreamWidgetStream = Pin<Box<dyn Stream<Item = Box<dyn Any + Send>>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type StreamWidgetStream = Pin<Box<dyn Send + Stream<Item = Box<dyn Any + Send>>>>;
+
+pub type StreamWidgetReducer<T, U> = Box<dyn FnMut(U, &mut T, &Env)>;
+
+/// A widget following a stream while it is in the widget tree, merging each item into the data
+/// with the `reducer`. Useful for log viewers, chats or progress reports.
+///
+/// The stream is dropped along with the widget. A channel can be followed by turning its
+/// receiving end into a stream, like with `flume::Receiver::into_stream`.
+pub struct StreamWidget<T, U> {
+    stream: Option<StreamWidgetStream>,
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    reducer: StreamWidgetReducer<T, U>,
+    abort: Option<AbortHandle>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T, U: Send + 'static> StreamWidget<T, U> {
+    pub fn new(
+        stream: impl Stream<Item = U> + 'static,
+        inner: impl Widget<T> + 'static,
+        reducer: impl FnMut(U, &mut T, &Env) + 'static,
+    ) -> Self {
+        Self {
+            stream: Some(Box::pin(
+                stream.map(|item| Box::new(item) as Box<dyn Any + Send>),
+            )),
+            inner: WidgetPod::new(Box::new(inner)),
+            reducer: Box::new(reducer),
+            abort: None,
+        }
+    }
+}
 
-            #[cfg(not(target_arch = "wasm32"))]
-            tokio::spawn(task);
+#[cfg(not(target_arch = "wasm32"))]
+impl<T, U: Send + 'static> StreamWidget<T, U> {
+    pub fn new(
+        stream: impl Stream<Item = U> + Send + 'static,
+        inner: impl Widget<T> + 'static,
+        reducer: impl FnMut(U, &mut T, &Env) + 'static,
+    ) -> Self {
+        Self {
+            stream: Some(Box::pin(
+                stream.map(|item| Box::new(item) as Box<dyn Any + Send>),
+            )),
+            inner: WidgetPod::new(Box::new(inner)),
+            reducer: Box::new(reducer),
+            abort: None,
         }
+    }
+}
 
+impl<T: Data, U: 'static> Widget<T> for StreamWidget<T, U> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(item) = cmd.get(STREAM_ITEM) {
+                let item = item.take().unwrap().downcast::<U>().unwrap();
+                (self.reducer)(*item, data, env);
+                ctx.set_handled();
+                return;
+            }
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            if let Some(mut stream) = self.stream.take() {
+                let sink = ctx.get_external_handle();
+                let widget_id = ctx.widget_id();
+                let (task, abort) = future::abortable(async move {
+                    while let Some(item) = stream.next().await {
+                        let item = SingleUse::new(item);
+                        if sink
+                            .submit_command(STREAM_ITEM, item, Target::Widget(widget_id))
+                            .is_err()
+                        {
+                            // the app is closed
+                            break;
+                        }
+                    }
+                });
+                spawn(async move {
+                    let _ = task.await;
+                });
+                self.abort = Some(abort);
+            }
+        }
         self.inner.lifecycle(ctx, event, data, env)
     }
 
@@ -135,17 +426,119 @@ impl<T: Data, U: 'static> Widget<T> for FutureWidget<T, U> {
         self.inner.update(ctx, data, env)
     }
 
-    fn layout(
-        &mut self,
-        ctx: &mut LayoutCtx,
-        bc: &druid::BoxConstraints,
-        data: &T,
-        env: &Env,
-    ) -> druid::Size {
-        self.inner.layout(ctx, bc, data, env)
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
         self.inner.paint(ctx, data, env)
     }
 }
+
+impl<T, U> Drop for StreamWidget<T, U> {
+    fn drop(&mut self) {
+        // unsubscribe when the widget is removed
+        if let Some(abort) = self.abort.take() {
+            abort.abort();
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub type OnChangeAsyncTask<T> =
+    Box<dyn Fn(&T, &T, &Env) -> Pin<Box<dyn Future<Output = Box<dyn Any + Send>>>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type OnChangeAsyncTask<T> =
+    Box<dyn Fn(&T, &T, &Env) -> Pin<Box<dyn Send + Future<Output = Box<dyn Any + Send>>>>>;
+
+pub type OnChangeAsyncResult<T, U> = Box<dyn Fn(&mut EventCtx, U, &mut T, &Env)>;
+
+/// A controller running a future when data changes **in a child widget**, like saving or
+/// validating on a server, and merging its output into the data with `on_result`.
+///
+/// `task` gets the old and the new data. The output of a task is ignored when the data changed
+/// again before it was done, so only the latest change is merged.
+pub struct OnChangeAsync<T, U> {
+    task: OnChangeAsyncTask<T>,
+    on_result: OnChangeAsyncResult<T, U>,
+    /// Counts the tasks, to ignore the output of outdated ones.
+    generation: u64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T, U: Send + 'static> OnChangeAsync<T, U> {
+    pub fn new<Fut>(
+        task: impl Fn(&T, &T, &Env) -> Fut + 'static,
+        on_result: impl Fn(&mut EventCtx, U, &mut T, &Env) + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = U> + 'static,
+    {
+        OnChangeAsync {
+            task: Box::new(move |old, new, env| {
+                let future = task(old, new, env);
+                Box::pin(async move { Box::new(future.await) as _ })
+            }),
+            on_result: Box::new(on_result),
+            generation: 0,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T, U: Send + 'static> OnChangeAsync<T, U> {
+    pub fn new<Fut>(
+        task: impl Fn(&T, &T, &Env) -> Fut + 'static,
+        on_result: impl Fn(&mut EventCtx, U, &mut T, &Env) + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        OnChangeAsync {
+            task: Box::new(move |old, new, env| {
+                let future = task(old, new, env);
+                Box::pin(async move { Box::new(future.await) as _ })
+            }),
+            on_result: Box::new(on_result),
+            generation: 0,
+        }
+    }
+}
+
+impl<T: Data, U: 'static, W: Widget<T>> Controller<T, W> for OnChangeAsync<T, U> {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(res) = cmd.get(CHANGE_RESPONSE) {
+                let res = res.take().unwrap();
+                if res.generation == self.generation {
+                    let value = res.value.downcast::<U>().unwrap();
+                    (self.on_result)(ctx, *value, data, env);
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let old_data = data.clone();
+        child.event(ctx, event, data, env);
+        if !old_data.same(data) {
+            self.generation += 1;
+            let generation = self.generation;
+            let future = (self.task)(&old_data, data, env);
+            let sink = ctx.get_external_handle();
+            let widget_id = ctx.widget_id();
+            spawn(async move {
+                let value = future.await;
+                // fails only if the app is closed by now
+                let _ = sink.submit_command(
+                    CHANGE_RESPONSE,
+                    SingleUse::new(Response { generation, value }),
+                    Target::Widget(widget_id),
+                );
+            });
+        }
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::f64::consts::PI;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::{Circle, Line};
+use druid::piet::{PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, KeyOrValue, Point, Vec2};
+
+use crate::animation::{Animated, AnimationCurve};
+
+/// The angle of the minimum, at the bottom left, and the angle from there to the maximum.
+const START_ANGLE: f64 = 0.75 * PI;
+const SWEEP_ANGLE: f64 = 1.5 * PI;
+/// The size taken when the constraints are loose.
+const DEFAULT_SIZE: f64 = 160.0;
+/// The width of the dial, as a fraction of the radius.
+const DIAL_WIDTH: f64 = 0.15;
+
+struct Range {
+    start: f64,
+    end: f64,
+    color: KeyOrValue<Color>,
+}
+
+/// A dial showing a value between a minimum and a maximum with a needle, like a speedometer.
+///
+/// Parts of the dial can be colored, for example to show the values that need attention. The
+/// needle moves smoothly to new values. With [`with_interactive`], the value is also set by
+/// dragging the needle, or with the arrow keys.
+///
+/// ```
+/// # use druid::{Color, Widget};
+/// # use druid_widget_nursery::Gauge;
+/// fn cpu_temperature() -> impl Widget<f64> {
+///     Gauge::new(20.0, 100.0)
+///         .with_range(70.0, 85.0, Color::rgb8(0xf5, 0x7c, 0x00))
+///         .with_range(85.0, 100.0, Color::rgb8(0xd3, 0x2f, 0x2f))
+///         .with_format(|value| format!("{:.0}°C", value))
+/// }
+/// ```
+///
+/// [`with_interactive`]: #method.with_interactive
+pub struct Gauge {
+    min: f64,
+    max: f64,
+    ranges: Vec<Range>,
+    format: Box<dyn Fn(f64) -> String>,
+    interactive: bool,
+    step: Option<f64>,
+    needle_color: KeyOrValue<Color>,
+    needle: Animated<f64>,
+}
+
+impl Gauge {
+    /// Create a gauge from `min` to `max`.
+    pub fn new(min: f64, max: f64) -> Self {
+        Gauge {
+            min,
+            max,
+            ranges: Vec::new(),
+            format: Box::new(|value| format!("{:.0}", value)),
+            interactive: false,
+            step: None,
+            needle_color: theme::PRIMARY_LIGHT.into(),
+            needle: Animated::jump(min)
+                .duration(0.5)
+                .curve(AnimationCurve::EASE_OUT),
+        }
+    }
+
+    /// Builder-style method for coloring the dial from `start` to `end`.
+    pub fn with_range(mut self, start: f64, end: f64, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.add_range(start, end, color);
+        self
+    }
+
+    /// Color the dial from `start` to `end`, over the ranges already added.
+    pub fn add_range(&mut self, start: f64, end: f64, color: impl Into<KeyOrValue<Color>>) {
+        self.ranges.push(Range {
+            start,
+            end,
+            color: color.into(),
+        });
+    }
+
+    /// Builder-style method for turning the values into text, for the value and the labels of
+    /// the minimum and the maximum.
+    pub fn with_format(mut self, format: impl Fn(f64) -> String + 'static) -> Self {
+        self.format = Box::new(format);
+        self
+    }
+
+    /// Builder-style method for setting the value by dragging the needle.
+    ///
+    /// For the non-builder varient, see [`set_interactive`].
+    ///
+    /// [`set_interactive`]: #method.set_interactive
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.set_interactive(interactive);
+        self
+    }
+
+    /// Set whether the value is set by dragging the needle and with the arrow keys.
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+
+    /// Builder-style method for rounding the values set to a multiple of `step`.
+    ///
+    /// For the non-builder varient, see [`set_step`].
+    ///
+    /// [`set_step`]: #method.set_step
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.set_step(Some(step));
+        self
+    }
+
+    /// Set the multiple the values set are rounded to, or `None` for a hundredth of the range
+    /// with the arrow keys and no rounding when dragging.
+    pub fn set_step(&mut self, step: Option<f64>) {
+        self.step = step.filter(|step| *step > 0.0);
+    }
+
+    /// Builder-style method for specifying the color of the needle.
+    ///
+    /// For the non-builder varient, see [`set_needle_color`].
+    ///
+    /// [`set_needle_color`]: #method.set_needle_color
+    pub fn with_needle_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_needle_color(color);
+        self
+    }
+
+    /// Set the color of the needle.
+    pub fn set_needle_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.needle_color = color.into();
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value
+            .max(self.min.min(self.max))
+            .min(self.max.max(self.min))
+    }
+
+    /// The angle of `value` on the dial.
+    fn angle(&self, value: f64) -> f64 {
+        let range = self.max - self.min;
+        let fraction = if range == 0.0 {
+            0.0
+        } else {
+            (self.clamp(value) - self.min) / range
+        };
+        START_ANGLE + fraction * SWEEP_ANGLE
+    }
+
+    /// The value pointed at from the center by `offset`.
+    fn value_at(&self, offset: Vec2) -> f64 {
+        let angle = (offset.atan2() - START_ANGLE).rem_euclid(2.0 * PI);
+        // below the dial, to the closest end
+        let angle = if angle <= SWEEP_ANGLE {
+            angle
+        } else if angle < SWEEP_ANGLE + (2.0 * PI - SWEEP_ANGLE) / 2.0 {
+            SWEEP_ANGLE
+        } else {
+            0.0
+        };
+        let value = self.min + angle / SWEEP_ANGLE * (self.max - self.min);
+        match self.step {
+            Some(step) => self.clamp(self.min + ((value - self.min) / step).round() * step),
+            None => value,
+        }
+    }
+
+    fn geometry(size: Size) -> (Point, f64) {
+        let radius = size.width.min(size.height) / 2.0;
+        (size.to_rect().center(), radius)
+    }
+
+    fn text(&self, ctx: &mut PaintCtx, text: String, size: f64, env: &Env) -> PietTextLayout {
+        let font = env.get(theme::UI_FONT);
+        ctx.text()
+            .new_text_layout(text)
+            .font(font.family, size)
+            .text_color(env.get(theme::TEXT_COLOR))
+            .build()
+            .unwrap()
+    }
+}
+
+impl Widget<f64> for Gauge {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut f64, _env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            if self.needle.animating() {
+                self.needle.update(ctx, *nanos);
+            }
+        }
+        if !self.interactive || ctx.is_disabled() {
+            return;
+        }
+        let (center, _) = Self::geometry(ctx.size());
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.set_active(true);
+                ctx.request_focus();
+                *data = self.value_at(mouse.pos - center);
+            }
+            Event::MouseMove(mouse) if ctx.is_active() => {
+                *data = self.value_at(mouse.pos - center);
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                let step = self
+                    .step
+                    .unwrap_or_else(|| (self.max - self.min).abs() / 100.0);
+                let value = match &key.key {
+                    Key::ArrowRight | Key::ArrowUp => *data + step,
+                    Key::ArrowLeft | Key::ArrowDown => *data - step,
+                    Key::Home => self.min,
+                    Key::End => self.max,
+                    _ => return,
+                };
+                *data = self.clamp(value);
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &f64, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.needle.jump_to_value(*data);
+                if self.interactive {
+                    ctx.register_for_focus();
+                }
+            }
+            LifeCycle::FocusChanged(_) | LifeCycle::DisabledChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &f64, data: &f64, _env: &Env) {
+        if !old_data.same(data) {
+            if ctx.is_active() {
+                // the needle follows the mouse
+                self.needle.jump_to_value(*data);
+                ctx.request_paint();
+            } else {
+                self.needle.animate(ctx, *data);
+            }
+        }
+        if ctx.env_key_changed(&self.needle_color)
+            || self
+                .ranges
+                .iter()
+                .any(|range| ctx.env_key_changed(&range.color))
+        {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &f64,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain(Size::new(DEFAULT_SIZE, DEFAULT_SIZE))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
+        let (center, radius) = Self::geometry(ctx.size());
+        let inner_radius = radius * (1.0 - DIAL_WIDTH);
+        let dial = Circle::new(center, radius);
+
+        ctx.fill(
+            dial.segment(inner_radius, START_ANGLE, SWEEP_ANGLE),
+            &env.get(theme::BACKGROUND_LIGHT),
+        );
+        for range in &self.ranges {
+            let start = self.angle(range.start);
+            let sweep = self.angle(range.end) - start;
+            ctx.fill(
+                dial.segment(inner_radius, start, sweep),
+                &range.color.resolve(env),
+            );
+        }
+        let border = if ctx.has_focus() {
+            env.get(theme::PRIMARY_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(
+            dial.segment(inner_radius, START_ANGLE, SWEEP_ANGLE),
+            &border,
+            1.0,
+        );
+
+        // the labels of the minimum and the maximum, under the ends of the dial
+        let label_size = env.get(theme::TEXT_SIZE_SMALL);
+        for value in [self.min, self.max].iter().copied() {
+            let layout = self.text(ctx, (self.format)(value), label_size, env);
+            let end =
+                center + Vec2::from_angle(self.angle(value)) * radius * (1.0 - DIAL_WIDTH / 2.0);
+            let x = (end.x - layout.size().width / 2.0)
+                .max(0.0)
+                .min(ctx.size().width - layout.size().width);
+            let y = (end.y + radius * DIAL_WIDTH).min(ctx.size().height - layout.size().height);
+            ctx.draw_text(&layout, (x, y));
+        }
+
+        // the value, under the center
+        let value_size = (radius * 0.25).max(label_size);
+        let layout = self.text(ctx, (self.format)(*data), value_size, env);
+        let origin = Point::new(
+            center.x - layout.size().width / 2.0,
+            center.y + radius * 0.3,
+        );
+        ctx.draw_text(&layout, origin);
+
+        let mut needle_color = self.needle_color.resolve(env);
+        if ctx.is_disabled() {
+            needle_color = env.get(theme::DISABLED_TEXT_COLOR);
+        }
+        let tip = center + Vec2::from_angle(self.angle(self.needle.get())) * inner_radius * 0.95;
+        ctx.stroke(Line::new(center, tip), &needle_color, 3.0);
+        ctx.fill(Circle::new(center, radius * 0.08), &needle_color);
+    }
+}
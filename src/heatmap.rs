@@ -0,0 +1,362 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::im::OrdMap;
+use druid::piet::{PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, Cursor, KeyOrValue, Point, Rect};
+
+use crate::date_picker::{civil_from_days, days_from_civil, today, weekday, Ymd, MONTH_NAMES};
+use crate::DateValue;
+
+const CELL_SIZE: f64 = 11.0;
+const GAP: f64 = 3.0;
+/// The space for the names of the weekdays, and of the months.
+const LABEL_WIDTH: f64 = 30.0;
+const LABEL_HEIGHT: f64 = 16.0;
+/// The colors of the values, as fractions of the color of the maximum.
+const LEVELS: [f64; 4] = [0.25, 0.5, 0.75, 1.0];
+const DEFAULT_COLOR: Color = Color::rgb8(0x21, 0x6e, 0x39);
+
+/// A year of days as cells colored by their value, like the contributions graph of GitHub.
+///
+/// The data is a map from dates to values; the days without a value are left empty. The value of
+/// the day under the mouse is shown in a tooltip, and clicking a day calls the callback of
+/// [`on_click`] with its date. The weeks are columns, starting on monday.
+///
+/// ```
+/// # use druid::im::OrdMap;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::Heatmap;
+/// type Date = (i32, u32, u32);
+///
+/// fn commits() -> impl Widget<OrdMap<Date, f64>> {
+///     Heatmap::new()
+///         .with_year(2022)
+///         .with_tooltip(|&(year, month, day): &Date, commits| {
+///             let commits = commits.unwrap_or(0.0);
+///             format!("{} commits on {}/{}/{}", commits, month, day, year)
+///         })
+///         .on_click(|_ctx, date: &Date, _data, _env| println!("{:?}", date))
+/// }
+/// ```
+///
+/// [`on_click`]: #method.on_click
+pub struct Heatmap<D> {
+    year: Option<i32>,
+    color: KeyOrValue<Color>,
+    max: Option<f64>,
+    tooltip: Box<dyn Fn(&D, Option<f64>) -> String>,
+    on_click: Option<Box<dyn Fn(&mut EventCtx, &D, &mut OrdMap<D, f64>, &Env)>>,
+    /// The day under the mouse, in days since 1970-01-01.
+    hovered: Option<i64>,
+}
+
+impl<D: DateValue + Ord> Heatmap<D> {
+    /// Create a heatmap of the last year, up to today.
+    pub fn new() -> Self {
+        Heatmap {
+            year: None,
+            color: DEFAULT_COLOR.into(),
+            max: None,
+            tooltip: Box::new(|date, value| {
+                let (year, month, day) = date.to_ymd();
+                match value {
+                    Some(value) => format!("{} on {:04}-{:02}-{:02}", value, year, month, day),
+                    None => format!("No data on {:04}-{:02}-{:02}", year, month, day),
+                }
+            }),
+            on_click: None,
+            hovered: None,
+        }
+    }
+
+    /// Builder-style method for showing a calendar year instead of the last year.
+    ///
+    /// For the non-builder varient, see [`set_year`].
+    ///
+    /// [`set_year`]: #method.set_year
+    pub fn with_year(mut self, year: i32) -> Self {
+        self.set_year(Some(year));
+        self
+    }
+
+    /// Set the calendar year shown, or `None` for the last year, up to today.
+    pub fn set_year(&mut self, year: Option<i32>) {
+        self.year = year;
+    }
+
+    /// Builder-style method for specifying the color of the maximum. The smaller values are
+    /// lighter shades of it.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the maximum.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the value with the full color.
+    ///
+    /// For the non-builder varient, see [`set_max`].
+    ///
+    /// [`set_max`]: #method.set_max
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(Some(max));
+        self
+    }
+
+    /// Set the value with the full color, or `None` for the largest value of the data.
+    pub fn set_max(&mut self, max: Option<f64>) {
+        self.max = max;
+    }
+
+    /// Builder-style method for the text of the tooltips, from the date and its value.
+    pub fn with_tooltip(mut self, tooltip: impl Fn(&D, Option<f64>) -> String + 'static) -> Self {
+        self.tooltip = Box::new(tooltip);
+        self
+    }
+
+    /// Builder-style method for calling `f` with the date of the days clicked.
+    pub fn on_click(
+        mut self,
+        f: impl Fn(&mut EventCtx, &D, &mut OrdMap<D, f64>, &Env) + 'static,
+    ) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
+    /// The first and the last day shown, in days since 1970-01-01.
+    fn period(&self) -> (i64, i64) {
+        match self.year {
+            Some(year) => (
+                days_from_civil((year, 1, 1)),
+                days_from_civil((year, 12, 31)),
+            ),
+            None => {
+                let last = days_from_civil(today());
+                (last - 364, last)
+            }
+        }
+    }
+
+    /// The monday of the first week.
+    fn grid_start(&self) -> i64 {
+        let first = self.period().0;
+        first - weekday(civil_from_days(first))
+    }
+
+    fn cell_rect(&self, day: i64) -> Rect {
+        let offset = day - self.grid_start();
+        let origin = Point::new(
+            LABEL_WIDTH + (offset / 7) as f64 * (CELL_SIZE + GAP),
+            LABEL_HEIGHT + (offset % 7) as f64 * (CELL_SIZE + GAP),
+        );
+        Rect::from_origin_size(origin, (CELL_SIZE, CELL_SIZE))
+    }
+
+    fn day_at(&self, pos: Point) -> Option<i64> {
+        let x = pos.x - LABEL_WIDTH;
+        let y = pos.y - LABEL_HEIGHT;
+        let step = CELL_SIZE + GAP;
+        if x < 0.0 || y < 0.0 || x % step > CELL_SIZE || y % step > CELL_SIZE {
+            return None;
+        }
+        let (week, row) = ((x / step) as i64, (y / step) as i64);
+        let day = self.grid_start() + week * 7 + row;
+        let (first, last) = self.period();
+        Some(day).filter(|day| row < 7 && (first..=last).contains(day))
+    }
+
+    fn text(&self, ctx: &mut PaintCtx, text: String, env: &Env) -> PietTextLayout {
+        let font = env.get(theme::UI_FONT);
+        ctx.text()
+            .new_text_layout(text)
+            .font(font.family, env.get(theme::TEXT_SIZE_SMALL))
+            .text_color(env.get(theme::TEXT_COLOR))
+            .build()
+            .unwrap()
+    }
+
+    fn paint_tooltip(&self, ctx: &mut PaintCtx, day: i64, data: &OrdMap<D, f64>, env: &Env) {
+        let date = date::<D>(day);
+        let text = (self.tooltip)(&date, data.get(&date).copied());
+        let layout = self.text(ctx, text, env);
+        let size = layout.size() + Size::new(12.0, 6.0);
+
+        // above the day, or below it in the first rows
+        let cell = self.cell_rect(day);
+        let x = (cell.center().x - size.width / 2.0)
+            .min(ctx.size().width - size.width)
+            .max(0.0);
+        let y = if cell.y0 - GAP - size.height >= 0.0 {
+            cell.y0 - GAP - size.height
+        } else {
+            cell.y1 + GAP
+        };
+        let rect = Rect::from_origin_size((x, y), size);
+        ctx.fill(rect.to_rounded_rect(4.0), &env.get(theme::BACKGROUND_DARK));
+        ctx.stroke(
+            rect.inset(-0.5).to_rounded_rect(4.0),
+            &env.get(theme::BORDER_DARK),
+            1.0,
+        );
+        ctx.draw_text(&layout, (x + 6.0, y + 3.0));
+    }
+}
+
+/// The date `day` days after 1970-01-01.
+fn date<D: DateValue>(day: i64) -> D {
+    let (year, month, day): Ymd = civil_from_days(day);
+    D::from_ymd(year, month, day)
+}
+
+impl<D: DateValue + Ord> Default for Heatmap<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: DateValue + Ord> Widget<OrdMap<D, f64>> for Heatmap<D> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut OrdMap<D, f64>, env: &Env) {
+        match event {
+            Event::MouseMove(mouse) => {
+                let hovered = self.day_at(mouse.pos);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    ctx.request_paint();
+                }
+                if hovered.is_some() && self.on_click.is_some() {
+                    ctx.set_cursor(&Cursor::Pointer);
+                } else {
+                    ctx.clear_cursor();
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() && self.hovered.is_some() => {
+                ctx.set_active(true);
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                if let (Some(day), Some(on_click)) = (self.day_at(mouse.pos), &self.on_click) {
+                    let date = date::<D>(day);
+                    on_click(ctx, &date, data, env);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &OrdMap<D, f64>,
+        _env: &Env,
+    ) {
+        if let LifeCycle::HotChanged(false) = event {
+            self.hovered = None;
+            ctx.request_paint();
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &OrdMap<D, f64>,
+        data: &OrdMap<D, f64>,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) || ctx.env_key_changed(&self.color) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &OrdMap<D, f64>,
+        _env: &Env,
+    ) -> Size {
+        let weeks = (self.period().1 - self.grid_start()) / 7 + 1;
+        bc.constrain(Size::new(
+            LABEL_WIDTH + weeks as f64 * (CELL_SIZE + GAP) - GAP,
+            LABEL_HEIGHT + 7.0 * (CELL_SIZE + GAP) - GAP,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &OrdMap<D, f64>, env: &Env) {
+        let (first, last) = self.period();
+
+        // the months above their first week, when there is room
+        let mut label_end = LABEL_WIDTH;
+        for day in first..=last {
+            let (_, month, day_of_month) = civil_from_days(day);
+            if day_of_month != 1 && day != first {
+                continue;
+            }
+            let layout = self.text(ctx, MONTH_NAMES[month as usize - 1][..3].to_string(), env);
+            let x = self.cell_rect(day).x0;
+            if x >= label_end {
+                ctx.draw_text(
+                    &layout,
+                    (x, (LABEL_HEIGHT - GAP - layout.size().height) / 2.0),
+                );
+                label_end = x + layout.size().width + 4.0;
+            }
+        }
+        for (row, name) in [(0, "Mon"), (2, "Wed"), (4, "Fri")].iter() {
+            let layout = self.text(ctx, name.to_string(), env);
+            let cell = self.cell_rect(self.grid_start() + row);
+            let y = cell.center().y - layout.size().height / 2.0;
+            ctx.draw_text(&layout, (0.0, y));
+        }
+
+        let color = self.color.resolve(env);
+        let empty = env.get(theme::BACKGROUND_LIGHT);
+        let max = self
+            .max
+            .unwrap_or_else(|| data.values().copied().fold(0.0, f64::max));
+        for day in first..=last {
+            let date = date::<D>(day);
+            let fill = match data.get(&date) {
+                Some(value) if *value > 0.0 && max > 0.0 => {
+                    let fraction = (value / max).min(1.0);
+                    let level = LEVELS
+                        .iter()
+                        .copied()
+                        .find(|level| fraction <= *level)
+                        .unwrap_or(1.0);
+                    color.with_alpha(level)
+                }
+                _ => empty,
+            };
+            let cell = self.cell_rect(day).to_rounded_rect(2.0);
+            ctx.fill(cell, &fill);
+            if self.hovered == Some(day) {
+                ctx.stroke(cell, &env.get(theme::TEXT_COLOR), 1.0);
+            }
+        }
+
+        if let Some(day) = self.hovered {
+            self.paint_tooltip(ctx, day, data, env);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::any::TypeId;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use druid::Widget;
+
+/// The name of the symbol exported by [`hot_reload_abi!`].
+pub(super) const ABI_SYMBOL: &str = "druid_hot_reload_abi";
+/// The name of the symbol looking up the views exported by [`hot_reload_abi!`].
+pub(super) const VIEWS_SYMBOL: &str = "druid_hot_reload_view";
+
+/// The signature of the view functions of a library.
+pub(super) type ViewFn<T> = fn() -> Box<dyn Widget<T>>;
+/// Returns the [`data_fingerprint`] of a view of the library, if it has a view with that name.
+pub(super) type ViewsFn = fn(&str) -> Option<u64>;
+
+/// A fingerprint of everything that has to match between the app and a view library.
+///
+/// This covers the rustc version, the druid build and the version of this crate. Loading a view
+/// built with a different fingerprint is undefined behaviour, as Rust types have no stable ABI.
+pub fn abi_fingerprint() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    env!("DRUID_NURSERY_RUSTC_VERSION").hash(&mut hasher);
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    // differs between druid versions and builds
+    TypeId::of::<druid::Env>().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A fingerprint of the data type of a view, which has to match the data of the widget showing
+/// it.
+pub(super) fn data_fingerprint<T: 'static>() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    TypeId::of::<T>().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The [`data_fingerprint`] of `view`, for [`hot_reload_abi!`]. Taking the function checks its
+/// signature.
+#[doc(hidden)]
+pub fn view_fingerprint<T: 'static>(_view: fn() -> Box<dyn Widget<T>>) -> u64 {
+    data_fingerprint::<T>()
+}
+
+/// Exports the [`abi_fingerprint`] of a view library, and the views it has with their data
+/// types.
+///
+/// Call this once in the crate that is hot reloaded, with the names of its view functions.
+/// Libraries without it, and views which aren't listed or show different data, are refused by
+/// [`AppLauncher::launch`].
+///
+/// ```ignore
+/// druid_widget_nursery::hot_reload_abi!(view, settings_view);
+/// ```
+///
+/// [`AppLauncher::launch`]: crate::hot_reload::AppLauncher::launch
+#[macro_export]
+macro_rules! hot_reload_abi {
+    ($($view:ident),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn druid_hot_reload_abi() -> u64 {
+            $crate::hot_reload::abi_fingerprint()
+        }
+
+        #[no_mangle]
+        pub fn druid_hot_reload_view(name: &str) -> Option<u64> {
+            match name {
+                $(stringify!($view) => Some($crate::hot_reload::view_fingerprint($view)),)*
+                _ => None,
+            }
+        }
+    };
+}
@@ -0,0 +1,79 @@
+use notify5::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// Editors tend to write several events per save, wait for them to settle before building.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Runs `cargo build` for the view library whenever one of its sources changes.
+///
+/// The library watcher of [`HotReloadLib`] then picks up the new artifact.
+///
+/// [`HotReloadLib`]: super::hot_reload_lib::HotReloadLib
+pub struct CargoBuild {
+    package: String,
+    watch_dirs: Vec<PathBuf>,
+}
+
+impl CargoBuild {
+    pub fn new(package: impl Into<String>, watch_dirs: Vec<PathBuf>) -> Self {
+        Self {
+            package: package.into(),
+            watch_dirs,
+        }
+    }
+
+    /// Start watching. Building stops when the returned watcher is dropped.
+    pub fn spawn(self) -> Result<RecommendedWatcher, String> {
+        let (tx, rx) = mpsc::channel::<()>();
+        let mut watcher = notify5::recommended_watcher(move |ev| {
+            let ev: Event = match ev {
+                Ok(ev) => ev,
+                _ => return,
+            };
+            let is_source = ev
+                .paths
+                .iter()
+                .any(|path| path.extension().map_or(false, |ext| ext == "rs"));
+            if is_source && (ev.kind.is_create() || ev.kind.is_modify() || ev.kind.is_remove()) {
+                let _ = tx.send(());
+            }
+        })
+        .map_err(|err| format!("Failed to create file watcher: {}", err))?;
+        for dir in &self.watch_dirs {
+            watcher
+                .watch(dir, RecursiveMode::Recursive)
+                .map_err(|err| format!("Failed to watch {}: {}", dir.display(), err))?;
+        }
+
+        let package = self.package;
+        thread::spawn(move || {
+            // the loop ends when the watcher, and with it the sender, is dropped
+            while rx.recv().is_ok() {
+                while rx.recv_timeout(DEBOUNCE).is_ok() {}
+                build(&package);
+            }
+        });
+
+        Ok(watcher)
+    }
+}
+
+fn build(package: &str) {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let mut cmd = Command::new(cargo);
+    cmd.args(&["build", "--lib", "-p", package]);
+    if !cfg!(debug_assertions) {
+        cmd.arg("--release");
+    }
+    info!("Rebuilding {}", package);
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => warn!("Building {} failed: {}", package, status),
+        Err(err) => warn!("Failed to run cargo: {}", err),
+    }
+}
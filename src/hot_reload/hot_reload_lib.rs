@@ -1,19 +1,52 @@
 use libloading::{Library, Symbol};
 use notify5::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
 use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Returns the platform specific file name of the dynamic library built for the crate `name`.
+///
+/// This is `libname.so` on Linux, `libname.dylib` on macOS and `name.dll` on Windows. Dashes
+/// are replaced with underscores, just like cargo does.
+pub fn library_file_name(name: &str) -> String {
+    format!("{}{}{}", DLL_PREFIX, name.replace('-', "_"), DLL_SUFFIX)
+}
+
+/// The path cargo builds the library of the crate `name` to, for the profile of this binary.
+pub(super) fn crate_library_path(name: &str) -> PathBuf {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    PathBuf::from("target")
+        .join(profile)
+        .join(library_file_name(name))
+}
 
 pub struct HotReloadLib {
     lib_path: PathBuf,
-    temp_path: PathBuf,
-    library: Option<Library>,
+    library: Option<ShadowLibrary>,
+    /// The library loaded before, see [`replace`].
+    ///
+    /// [`replace`]: HotReloadLib::replace
+    previous: Option<ShadowLibrary>,
     _watcher: RecommendedWatcher,
 }
 
 impl HotReloadLib {
-    pub fn new(path: impl AsRef<Path>, on_reload: impl Fn() + Send + Sync + 'static) -> Self {
-        let lib_path = path.as_ref().canonicalize().unwrap();
-        let (lib, temp_path) = copy_and_load_library(&lib_path);
+    /// Watch the library at `path`. Nothing is loaded until [`load_new`] is called.
+    ///
+    /// [`load_new`]: HotReloadLib::load_new
+    pub fn new(
+        path: impl AsRef<Path>,
+        on_reload: impl Fn() + Send + Sync + 'static,
+    ) -> Result<Self, String> {
+        let path = path.as_ref();
+        let lib_path = path
+            .canonicalize()
+            .map_err(|err| format!("Failed to find {}: {}", path.display(), err))?;
         let mut watcher = notify5::recommended_watcher({
             let lib_path = lib_path.clone();
             move |ev| {
@@ -22,53 +55,100 @@ impl HotReloadLib {
                     _ => return,
                 };
 
-                if ev.paths.contains(&lib_path) && ev.kind.is_create() {
+                // cargo hard-links the finished artifact into place, which shows up as a
+                // create on most platforms and as a modification on some
+                if ev.paths.contains(&lib_path) && (ev.kind.is_create() || ev.kind.is_modify()) {
                     on_reload();
                 }
             }
         })
-        .unwrap();
+        .map_err(|err| format!("Failed to create file watcher: {}", err))?;
         watcher
             .watch(lib_path.parent().unwrap(), RecursiveMode::NonRecursive)
-            .unwrap();
+            .map_err(|err| format!("Failed to watch {}: {}", lib_path.display(), err))?;
 
-        HotReloadLib {
-            temp_path,
+        Ok(HotReloadLib {
             lib_path,
-            library: Some(lib),
+            library: None,
+            previous: None,
             _watcher: watcher,
-        }
+        })
     }
 
     pub unsafe fn load_symbol<Signature>(&self, symbol_name: &str) -> Option<Symbol<Signature>> {
-        let lib = self.library.as_ref().unwrap();
-        lib.get(symbol_name.as_bytes()).ok()
+        self.library.as_ref()?.load_symbol(symbol_name)
     }
 
-    pub fn update(&mut self) {
-        self.library = None;
-        fs::remove_file(&self.temp_path).unwrap();
-        let (library, path) = copy_and_load_library(&self.lib_path);
-        self.library = Some(library);
-        self.temp_path = path;
+    /// Load the current version of the library, next to the one already loaded.
+    pub fn load_new(&self) -> Result<ShadowLibrary, String> {
+        ShadowLibrary::load(&self.lib_path)
     }
-}
 
-impl Drop for HotReloadLib {
-    fn drop(&mut self) {
-        fs::remove_file(&self.temp_path).unwrap();
+    /// Replace the loaded library.
+    ///
+    /// Everything created by the loaded library has to be dropped before calling this. It's
+    /// only unloaded on the next call though, as druid keeps copies of the app data from before
+    /// an event until the following update.
+    pub fn replace(&mut self, library: ShadowLibrary) {
+        self.previous = self.library.replace(library);
     }
 }
 
-fn copy_and_load_library(lib_path: &Path) -> (Library, PathBuf) {
-    let unique_path = {
+/// A loaded copy of the library.
+///
+/// We never load the library from where cargo writes it:
+/// - Windows locks loaded DLLs, so cargo could not overwrite it.
+/// - Linux and macOS return the already loaded library when the same path is opened again.
+///
+/// Loading a shadow copy with a unique name avoids both problems, and lets us load a new version
+/// while the old one is still in use.
+pub struct ShadowLibrary {
+    library: Option<Library>,
+    path: PathBuf,
+}
+
+impl ShadowLibrary {
+    fn load(lib_path: &Path) -> Result<Self, String> {
         let mut path = std::env::temp_dir();
-        path.push(rand::random::<u64>().to_string());
-        path.set_extension(lib_path.extension().unwrap());
-        path
-    };
-    fs::copy(lib_path, &unique_path).expect("Failed to copy lib to unique path");
-    let lib = Library::new(unique_path.as_os_str()).expect("Failed to load library");
+        path.push("druid-hot-reload");
+        fs::create_dir_all(&path)
+            .map_err(|err| format!("Failed to create {}: {}", path.display(), err))?;
+        path.push(format!(
+            "{}{}{}",
+            DLL_PREFIX,
+            rand::random::<u64>(),
+            DLL_SUFFIX
+        ));
+        fs::copy(lib_path, &path)
+            .map_err(|err| format!("Failed to copy {}: {}", lib_path.display(), err))?;
+        match Library::new(path.as_os_str()) {
+            Ok(library) => Ok(ShadowLibrary {
+                library: Some(library),
+                path,
+            }),
+            Err(err) => {
+                remove_shadow_copy(&path);
+                Err(format!("Failed to load {}: {}", lib_path.display(), err))
+            }
+        }
+    }
 
-    (lib, unique_path)
+    pub unsafe fn load_symbol<Signature>(&self, symbol_name: &str) -> Option<Symbol<Signature>> {
+        let lib = self.library.as_ref()?;
+        lib.get(symbol_name.as_bytes()).ok()
+    }
+}
+
+impl Drop for ShadowLibrary {
+    fn drop(&mut self) {
+        // unload before removing the file, Windows refuses to delete loaded libraries
+        self.library = None;
+        remove_shadow_copy(&self.path);
+    }
+}
+
+fn remove_shadow_copy(path: &Path) {
+    if let Err(err) = fs::remove_file(path) {
+        warn!("Failed to remove {}: {}", path.display(), err);
+    }
 }
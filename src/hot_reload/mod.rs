@@ -1,65 +1,115 @@
-// TODO: survive a change in AppData.
-
 use std::marker::PhantomData;
-use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+mod abi;
+mod cargo_build;
+mod state;
 mod widget;
 
-use druid::{Data, Env, ExtEventSink, PlatformError, Selector, Target};
-use hot_reload_lib::HotReloadLib;
-use widget::HotReloaderWidget;
+use cargo_build::CargoBuild;
+use druid::{Data, Env, ExtEventSink, PlatformError, Selector, Widget};
+use hot_reload_lib::crate_library_path;
+use notify5::RecommendedWatcher;
+use tracing::warn;
+
+use crate::theme_loader::{LoadableTheme, ThemeLoader};
 
 mod hot_reload_lib;
 
+pub use abi::{abi_fingerprint, view_fingerprint};
+pub use hot_reload_lib::library_file_name;
+pub use state::{HotState, HotStateLens};
+pub use widget::HotReload;
+
 const RELOAD: Selector<()> = Selector::new("druid-hot-reload.reload");
 
 pub struct AppLauncher<T> {
     inner: druid::AppLauncher<T>,
+    cargo_build: Option<CargoBuild>,
+    check_abi: Arc<AtomicBool>,
 }
 
 // TODO: add more window customization
 pub struct WindowDesc<T> {
-    lib_path: &'static str,
+    lib_path: PathBuf,
     view: &'static str,
+    theme: Option<(PathBuf, Box<dyn LoadableTheme>)>,
     _ty: PhantomData<*const T>,
 }
 
 impl<T: Data> WindowDesc<T> {
-    pub fn new(lib_path: &'static str, view: &'static str) -> Self {
+    /// Create a window showing the widget returned by the function `view` of the dynamic
+    /// library at `lib_path`.
+    pub fn new(lib_path: impl Into<PathBuf>, view: &'static str) -> Self {
         Self {
-            lib_path,
+            lib_path: lib_path.into(),
             view,
+            theme: None,
             _ty: PhantomData,
         }
     }
 
-    fn build(self, sink: Arc<Mutex<Option<ExtEventSink>>>) -> druid::WindowDesc<T> {
-        let lib_path = self.lib_path;
-        let view_fn_name = self.view;
-        let hot_reloader_widget = HotReloaderWidget {
-            lib: HotReloadLib::new(lib_path, move || {
-                let sink = sink.lock().unwrap();
-                let sink = sink.as_ref().unwrap();
-                sink.submit_command(RELOAD, (), Target::Global).unwrap();
-            }),
-            inner: None,
-            view_fn_name,
+    /// Create a window for the library crate `crate_name` of the current workspace.
+    ///
+    /// The library is looked up in `target/debug` or `target/release`, depending on how this
+    /// binary was built, with the platform specific name (see [`library_file_name`]).
+    pub fn from_crate(crate_name: &str, view: &'static str) -> Self {
+        Self::new(crate_library_path(crate_name), view)
+    }
+
+    /// Load `theme` from the file at `theme_path` into the [`Env`] of the window.
+    ///
+    /// The file is watched and re-applied whenever it changes, without reloading the view
+    /// library. See [`theme_loader`] for the file format.
+    ///
+    /// [`theme_loader`]: crate::theme_loader
+    pub fn theme(
+        mut self,
+        theme_path: impl Into<PathBuf>,
+        theme: impl LoadableTheme + 'static,
+    ) -> Self {
+        self.theme = Some((theme_path.into(), Box::new(theme)));
+        self
+    }
+
+    fn build(self, check_abi: Arc<AtomicBool>) -> druid::WindowDesc<T> {
+        let view = HotReload::with_abi_check(self.lib_path, self.view, check_abi);
+        let root: Box<dyn Widget<T>> = match self.theme {
+            Some((path, theme)) => Box::new(ThemeLoader::new(path, theme, view)),
+            None => Box::new(view),
         };
-        druid::WindowDesc::new(hot_reloader_widget)
+        druid::WindowDesc::new(root)
     }
 }
 
 impl<T: Data> AppLauncher<T> {
     /// Create a new `AppLauncher` with the provided window.
     pub fn with_window(window: WindowDesc<T>) -> Self {
-        let sink = Arc::default();
-        let window = window.build(Arc::clone(&sink));
+        let check_abi = Arc::new(AtomicBool::new(true));
+        let window = window.build(Arc::clone(&check_abi));
         let inner = druid::AppLauncher::with_window(window);
 
-        let mut sink = sink.lock().unwrap();
-        *sink = Some(inner.get_external_handle());
+        Self {
+            inner,
+            cargo_build: None,
+            check_abi,
+        }
+    }
 
-        Self { inner }
+    /// Run `cargo build --lib -p <package>` whenever a `.rs` file in one of `watch_dirs` changes.
+    ///
+    /// Without this the view library has to be rebuilt by hand (or with `cargo watch`). Either
+    /// way the view is swapped automatically as soon as the new library is written.
+    pub fn rebuild_on_change(
+        mut self,
+        package: impl Into<String>,
+        watch_dirs: impl IntoIterator<Item = impl Into<PathBuf>>,
+    ) -> Self {
+        let watch_dirs = watch_dirs.into_iter().map(Into::into).collect();
+        self.cargo_build = Some(CargoBuild::new(package, watch_dirs));
+        self
     }
 
     /// Provide an optional closure that will be given mutable access to
@@ -105,9 +155,36 @@ impl<T: Data> AppLauncher<T> {
     /// Returns an error if a window cannot be instantiated. This is usually
     /// a fatal error.
     ///
-    /// ## Safety
-    /// This function can cause UB. don't use this except for development.
-    pub unsafe fn launch(self, data: T) -> Result<(), PlatformError> {
+    /// View libraries are only loaded if they were built with the same
+    /// rustc, druid and nursery versions as the app, which they advertise
+    /// with [`hot_reload_abi!`], and if the view shows the data type of the
+    /// window. Libraries failing the check are reported in the window instead
+    /// of being loaded.
+    ///
+    /// [`hot_reload_abi!`]: crate::hot_reload_abi
+    pub fn launch(self, data: T) -> Result<(), PlatformError> {
+        // keep the source watcher alive while the app is running
+        let _watcher: Option<RecommendedWatcher> = self.cargo_build.and_then(|build| {
+            build
+                .spawn()
+                .map_err(|err| warn!("Not rebuilding on changes: {}", err))
+                .ok()
+        });
         self.inner.launch(data)
     }
+
+    /// Like [`launch`], but loads view libraries without checking their ABI.
+    ///
+    /// ## Safety
+    /// Loading a library built with a different compiler or different
+    /// dependency versions is undefined behaviour. Don't use this except
+    /// for development, with libraries that can't export
+    /// [`hot_reload_abi!`].
+    ///
+    /// [`launch`]: AppLauncher::launch
+    /// [`hot_reload_abi!`]: crate::hot_reload_abi
+    pub unsafe fn launch_unchecked(self, data: T) -> Result<(), PlatformError> {
+        self.check_abi.store(false, Ordering::Relaxed);
+        self.launch(data)
+    }
 }
@@ -0,0 +1,81 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use druid::{Data, Lens};
+
+/// Application state owned by the view library.
+///
+/// The data type of the launcher is compiled into the binary and can not change while the app
+/// is running. Using `HotState` as that type keeps the actual state behind a pointer allocated by
+/// the view library, so the library is free to change its layout, as long as it migrates the
+/// old state when it is reloaded.
+///
+/// For this, the view library exports three functions next to the view:
+///
+/// ```ignore
+/// #[no_mangle]
+/// pub fn state_version() -> u32 { 2 }
+///
+/// #[no_mangle]
+/// pub fn serialize_state(state: &HotState) -> Vec<u8> { /* called on the old library */ }
+///
+/// #[no_mangle]
+/// pub fn deserialize_state(version: u32, bytes: &[u8]) -> Option<HotState> {
+///     /* called on the new library, with the version of the old one */
+/// }
+/// ```
+///
+/// The state is moved over on every reload, so that nothing the old library allocated outlives
+/// it. Bump `state_version` whenever the layout of the state changes, so that
+/// `deserialize_state` knows which layout it reads.
+#[derive(Clone)]
+pub struct HotState(Arc<dyn Any>);
+
+impl HotState {
+    pub fn new<T: Any>(state: T) -> Self {
+        Self(Arc::new(state))
+    }
+
+    /// Returns the state if it is of type `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// A lens from `HotState` to the state of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// The lens panics if the state is not of type `T`, which happens if its layout was changed
+    /// without bumping `state_version`.
+    pub fn lens<T>() -> HotStateLens<T> {
+        HotStateLens(PhantomData)
+    }
+}
+
+impl Data for HotState {
+    fn same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// See [`HotState::lens`].
+pub struct HotStateLens<T>(PhantomData<T>);
+
+impl<T: Any + Clone + Data> Lens<HotState, T> for HotStateLens<T> {
+    fn with<V, F: FnOnce(&T) -> V>(&self, data: &HotState, f: F) -> V {
+        f(data.get().expect(STATE_CHANGED))
+    }
+
+    fn with_mut<V, F: FnOnce(&mut T) -> V>(&self, data: &mut HotState, f: F) -> V {
+        let old: &T = data.get().expect(STATE_CHANGED);
+        let mut new = old.clone();
+        let value = f(&mut new);
+        if !old.same(&new) {
+            *data = HotState::new(new);
+        }
+        value
+    }
+}
+
+const STATE_CHANGED: &str = "the layout of HotState changed, bump state_version";
@@ -1,37 +1,272 @@
-use super::{hot_reload_lib::HotReloadLib, RELOAD};
-use druid::{widget::prelude::*, WidgetPod};
+use super::abi::{abi_fingerprint, data_fingerprint, ViewFn, ViewsFn, ABI_SYMBOL, VIEWS_SYMBOL};
+use super::hot_reload_lib::{crate_library_path, HotReloadLib, ShadowLibrary};
+use super::RELOAD;
+use druid::piet::{Text, TextLayoutBuilder};
+use druid::{widget::prelude::*, Color, Point, WidgetPod};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Once};
+use tracing::warn;
 
-pub struct HotReloaderWidget<T> {
-    pub(super) lib: HotReloadLib,
-    pub(super) inner: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
-    pub(super) view_fn_name: &'static str,
+type AbiFn = extern "C" fn() -> u64;
+type StateVersionFn = fn() -> u32;
+type SerializeFn<T> = fn(&T) -> Vec<u8>;
+type DeserializeFn<T> = fn(u32, &[u8]) -> Option<T>;
+
+const ERROR_BACKGROUND: Color = Color::rgba8(0x80, 0, 0, 0xe0);
+const ERROR_TEXT_COLOR: Color = Color::WHITE;
+const ERROR_PADDING: f64 = 10.0;
+
+thread_local! {
+    /// Set while a view function is called on this thread, to the message of its panic.
+    static VIEW_PANIC: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// A widget showing a view from a dynamic library, which is reloaded whenever the library
+/// changes.
+///
+/// [`WindowDesc`] uses this for whole windows, but it can be used anywhere in an ordinary druid
+/// app, so only the panel under development is hot reloaded while the rest stays statically
+/// compiled. Several `HotReload` widgets can load different views from the same or from
+/// different libraries.
+///
+/// The library has to export its ABI and its views with [`hot_reload_abi!`].
+///
+/// [`WindowDesc`]: super::WindowDesc
+/// [`hot_reload_abi!`]: crate::hot_reload_abi
+pub struct HotReload<T> {
+    lib_path: PathBuf,
+    lib: Option<HotReloadLib>,
+    inner: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    view_fn_name: &'static str,
+    /// The error of the last failed reload, shown on top of the previous view.
+    error: Option<String>,
+    check_abi: Arc<AtomicBool>,
 }
 
-impl<T> HotReloaderWidget<T> {
-    fn update_lib(&mut self) {
-        // droping it before unloading the library
+impl<T: 'static> HotReload<T> {
+    /// Show the widget returned by the function `view` of the dynamic library at `lib_path`.
+    pub fn new(lib_path: impl Into<PathBuf>, view: &'static str) -> Self {
+        Self::with_abi_check(lib_path.into(), view, Arc::new(AtomicBool::new(true)))
+    }
+
+    /// Show a view of the library crate `crate_name` of the current workspace.
+    ///
+    /// The library is looked up in `target/debug` or `target/release`, depending on how this
+    /// binary was built, with the platform specific name (see [`library_file_name`]).
+    ///
+    /// [`library_file_name`]: super::library_file_name
+    pub fn from_crate(crate_name: &str, view: &'static str) -> Self {
+        Self::new(crate_library_path(crate_name), view)
+    }
+
+    /// Load the library without checking its ABI.
+    ///
+    /// ## Safety
+    /// Loading a library built with a different compiler or different dependency versions is
+    /// undefined behaviour.
+    pub unsafe fn unchecked(self) -> Self {
+        self.check_abi.store(false, Ordering::Relaxed);
+        self
+    }
+
+    pub(super) fn with_abi_check(
+        lib_path: PathBuf,
+        view_fn_name: &'static str,
+        check_abi: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            lib_path,
+            lib: None,
+            inner: None,
+            view_fn_name,
+            error: None,
+            check_abi,
+        }
+    }
+
+    /// Load the new library, move the state over to it, and swap the view.
+    ///
+    /// Everything the old library made, the view and the state, is dropped before it is
+    /// unloaded. If anything goes wrong the previous library, view and state are kept. `data`
+    /// is `None` when the first library is loaded, as there's no state to move yet.
+    fn reload(&mut self, data: Option<&mut T>) -> Result<(), String> {
+        let lib = self.lib.as_mut().ok_or("The library is not loaded")?;
+        let library = lib.load_new()?;
+        if self.check_abi.load(Ordering::Relaxed) {
+            check_abi::<T>(&library, self.view_fn_name)?;
+        }
+        let widget = create_view::<T>(&library, self.view_fn_name)?;
+        let migrated = match data {
+            Some(data) => migrate_state(lib, &library, data)?.map(|new_data| (data, new_data)),
+            None => None,
+        };
         drop(self.inner.take());
-        self.lib.update();
-        let load = unsafe {
-            self.lib
-                .load_symbol::<fn() -> Box<dyn Widget<T>>>(self.view_fn_name)
-                .unwrap()
+        if let Some((data, new_data)) = migrated {
+            *data = new_data;
+        }
+        lib.replace(library);
+        self.inner = Some(WidgetPod::new(widget));
+        Ok(())
+    }
+
+    fn reload_or_show_error(&mut self, data: Option<&mut T>) {
+        self.error = match self.reload(data) {
+            Ok(()) => None,
+            Err(err) => {
+                warn!("Hot reload failed: {}", err);
+                Some(err)
+            }
+        };
+    }
+
+    fn paint_error(&self, ctx: &mut PaintCtx) {
+        let error = match &self.error {
+            Some(error) => error,
+            None => return,
         };
-        let returned_widget = load();
-        self.inner = Some(WidgetPod::new(returned_widget));
+        let size = ctx.size();
+        ctx.fill(size.to_rect(), &ERROR_BACKGROUND);
+        let layout = ctx
+            .text()
+            .new_text_layout(format!("Hot reload failed:\n\n{}", error))
+            .text_color(ERROR_TEXT_COLOR)
+            .max_width(size.width - 2. * ERROR_PADDING)
+            .build();
+        if let Ok(layout) = layout {
+            ctx.draw_text(&layout, Point::new(ERROR_PADDING, ERROR_PADDING));
+        }
+    }
+}
+
+/// Serialize the state with the loaded library, and deserialize it with the new `library`.
+///
+/// Returns `None` when the loaded library doesn't support migration, then the state is assumed
+/// to be compiled into the binary rather than made by the library.
+fn migrate_state<T>(
+    lib: &HotReloadLib,
+    library: &ShadowLibrary,
+    data: &T,
+) -> Result<Option<T>, String> {
+    let (version, serialize) = unsafe {
+        match (
+            lib.load_symbol::<StateVersionFn>("state_version"),
+            lib.load_symbol::<SerializeFn<T>>("serialize_state"),
+        ) {
+            (Some(version), Some(serialize)) => (version(), serialize),
+            _ => return Ok(None),
+        }
+    };
+    let deserialize = unsafe { library.load_symbol::<DeserializeFn<T>>("deserialize_state") }
+        .ok_or("The library has no `deserialize_state` to take over the state of the old one")?;
+    let bytes = serialize(data);
+    deserialize(version, &bytes)
+        .map(Some)
+        .ok_or_else(|| format!("Failed to migrate the state from version {}", version))
+}
+
+/// Check that `library` was built like the app, and that its view `name` shows `T`.
+fn check_abi<T: 'static>(library: &ShadowLibrary, name: &str) -> Result<(), String> {
+    let export_hint = || {
+        format!(
+            "add `druid_widget_nursery::hot_reload_abi!({});` to the library",
+            name
+        )
+    };
+    let abi = unsafe { library.load_symbol::<AbiFn>(ABI_SYMBOL) }
+        .ok_or_else(|| format!("The library does not export its ABI, {}", export_hint()))?;
+    if abi() != abi_fingerprint() {
+        return Err("The library was built with a different rustc, druid or \
+            druid-widget-nursery version than the app. Rebuild both."
+            .into());
+    }
+    // only called once the ABI is known to match
+    let views = unsafe { library.load_symbol::<ViewsFn>(VIEWS_SYMBOL) }
+        .ok_or_else(|| format!("The library does not export its views, {}", export_hint()))?;
+    match views(name) {
+        Some(fingerprint) if fingerprint == data_fingerprint::<T>() => Ok(()),
+        Some(_) => Err(format!(
+            "The view `{}` of the library doesn't show `{}`, the data of this widget",
+            name,
+            std::any::type_name::<T>()
+        )),
+        None => Err(format!(
+            "The library does not export the view `{}`, {}",
+            name,
+            export_hint()
+        )),
     }
 }
 
-impl<T: Data> Widget<T> for HotReloaderWidget<T> {
+/// Call the view function of `library`, catching panics.
+fn create_view<T>(library: &ShadowLibrary, name: &str) -> Result<Box<dyn Widget<T>>, String> {
+    let view = unsafe { library.load_symbol::<ViewFn<T>>(name) }
+        .ok_or_else(|| format!("The library has no symbol `{}`", name))?;
+    let view: ViewFn<T> = *view;
+
+    install_panic_hook();
+    VIEW_PANIC.with(|panic| *panic.borrow_mut() = Some(None));
+    let result = panic::catch_unwind(AssertUnwindSafe(view));
+    let message = VIEW_PANIC.with(|panic| panic.borrow_mut().take()).flatten();
+
+    result.map_err(|payload| {
+        message
+            .or_else(|| payload_message(&*payload))
+            .unwrap_or_else(|| format!("`{}` panicked", name))
+    })
+}
+
+/// Install a panic hook capturing the panics of view functions, with their backtrace, and
+/// passing every other panic on to the hook set before.
+///
+/// It's installed once for the whole process, as other threads may panic while a view is made.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let captured = VIEW_PANIC
+                .try_with(|panic| match &mut *panic.borrow_mut() {
+                    Some(message) => {
+                        let backtrace = Backtrace::force_capture();
+                        *message = Some(format!("{}\n\n{}", info, backtrace));
+                        true
+                    }
+                    None => false,
+                })
+                .unwrap_or(false);
+            if !captured {
+                previous_hook(info);
+            }
+        }));
+    });
+}
+
+/// The message of a panic, for when the app replaced our hook.
+fn payload_message(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+impl<T: Data> Widget<T> for HotReload<T> {
     fn event(&mut self, ctx: &mut druid::EventCtx, event: &Event, data: &mut T, env: &druid::Env) {
         if let Event::Command(cmd) = event {
             if cmd.is(RELOAD) {
-                self.update_lib();
+                self.reload_or_show_error(Some(data));
                 ctx.children_changed();
                 return;
             }
         }
-        self.inner.as_mut().unwrap().event(ctx, event, data, env)
+        if let Some(inner) = &mut self.inner {
+            inner.event(ctx, event, data, env)
+        }
     }
 
     fn lifecycle(
@@ -43,20 +278,33 @@ impl<T: Data> Widget<T> for HotReloaderWidget<T> {
     ) {
         if let LifeCycle::WidgetAdded = event {
             // just update library first time
-            if self.inner.is_none() {
-                self.update_lib();
+            if self.lib.is_none() {
+                let sink = ctx.get_external_handle();
+                let id = ctx.widget_id();
+                let lib = HotReloadLib::new(&self.lib_path, move || {
+                    // only fails if the app is gone
+                    let _ = sink.submit_command(RELOAD, (), id);
+                });
+                match lib {
+                    Ok(lib) => {
+                        self.lib = Some(lib);
+                        self.reload_or_show_error(None);
+                    }
+                    Err(err) => self.error = Some(err),
+                }
                 ctx.children_changed();
                 ctx.request_layout();
             }
         }
-        self.inner
-            .as_mut()
-            .unwrap()
-            .lifecycle(ctx, event, data, env)
+        if let Some(inner) = &mut self.inner {
+            inner.lifecycle(ctx, event, data, env)
+        }
     }
 
     fn update(&mut self, ctx: &mut druid::UpdateCtx, _old_data: &T, data: &T, env: &druid::Env) {
-        self.inner.as_mut().unwrap().update(ctx, data, env)
+        if let Some(inner) = &mut self.inner {
+            inner.update(ctx, data, env)
+        }
     }
 
     fn layout(
@@ -66,14 +314,19 @@ impl<T: Data> Widget<T> for HotReloaderWidget<T> {
         data: &T,
         env: &druid::Env,
     ) -> druid::Size {
-        self.inner
-            .as_mut()
-            .unwrap()
-            .set_origin(ctx, (0.0, 0.0).into());
-        self.inner.as_mut().unwrap().layout(ctx, bc, data, env)
+        match &mut self.inner {
+            Some(inner) => {
+                inner.set_origin(ctx, (0.0, 0.0).into());
+                inner.layout(ctx, bc, data, env)
+            }
+            None => bc.max(),
+        }
     }
 
     fn paint(&mut self, ctx: &mut druid::PaintCtx, data: &T, env: &druid::Env) {
-        self.inner.as_mut().unwrap().paint(ctx, data, env)
+        if let Some(inner) = &mut self.inner {
+            inner.paint(ctx, data, env)
+        }
+        self.paint_error(ctx);
     }
 }
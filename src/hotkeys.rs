@@ -0,0 +1,781 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configurable keyboard shortcuts.
+//!
+//! A [`Keymap`] binds key sequences like `"Ctrl+K Ctrl+S"` to the names of actions. It can be
+//! written to and parsed from text, so users can customize it. The [`HotkeyController`] wraps
+//! the root widget of a window and submits the command of an action when its keys are pressed.
+//!
+//! ```
+//! # use druid::widget::Label;
+//! # use druid::{commands, Widget, WidgetExt};
+//! # use druid_widget_nursery::hotkeys::{HotkeyController, Keymap};
+//! let keymap: Keymap = "
+//!     quit = Mod+Q
+//!     about = Mod+K Mod+A, F1
+//! "
+//! .parse()
+//! .unwrap();
+//! assert!(keymap.conflicts().is_empty());
+//!
+//! fn root(keymap: Keymap) -> impl Widget<()> {
+//!     Label::new("Hello").controller(
+//!         HotkeyController::new(keymap)
+//!             .with_action("quit", commands::QUIT_APP)
+//!             .with_action("about", commands::SHOW_ABOUT),
+//!     )
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::{Code, Command, KbKey, KeyEvent, Modifiers, TimerToken};
+use tracing::warn;
+
+crate::selectors! {
+    /// Replace the keymap of a [`HotkeyController`], for example after the user customized it.
+    SET_KEYMAP: Keymap,
+}
+
+/// How long a multi-stroke sequence waits for its next chord.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// An error parsing a [`Chord`], a [`KeySequence`] or a [`Keymap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHotkeyError {
+    UnknownModifier(String),
+    UnknownKey(String),
+    /// A chord or a sequence without any key.
+    Empty,
+    /// A keymap line without `=`.
+    InvalidLine(String),
+}
+
+impl fmt::Display for ParseHotkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseHotkeyError::UnknownModifier(m) => write!(f, "unknown modifier \"{}\"", m),
+            ParseHotkeyError::UnknownKey(key) => write!(f, "unknown key \"{}\"", key),
+            ParseHotkeyError::Empty => write!(f, "no key given"),
+            ParseHotkeyError::InvalidLine(line) => {
+                write!(f, "expected \"action = keys\", got \"{}\"", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseHotkeyError {}
+
+/// The modifier used for most shortcuts on this platform, Cmd on macOS and Ctrl elsewhere.
+fn primary_modifier() -> Modifiers {
+    if cfg!(target_os = "macos") {
+        Modifiers::META
+    } else {
+        Modifiers::CONTROL
+    }
+}
+
+fn named_key(name: &str) -> Option<KbKey> {
+    let key = match name.to_lowercase().as_str() {
+        "enter" | "return" => KbKey::Enter,
+        "escape" | "esc" => KbKey::Escape,
+        "tab" => KbKey::Tab,
+        "space" => KbKey::Character(" ".to_string()),
+        "plus" => KbKey::Character("+".to_string()),
+        "comma" => KbKey::Character(",".to_string()),
+        "backspace" => KbKey::Backspace,
+        "delete" | "del" => KbKey::Delete,
+        "insert" | "ins" => KbKey::Insert,
+        "home" => KbKey::Home,
+        "end" => KbKey::End,
+        "pageup" => KbKey::PageUp,
+        "pagedown" => KbKey::PageDown,
+        "up" | "arrowup" => KbKey::ArrowUp,
+        "down" | "arrowdown" => KbKey::ArrowDown,
+        "left" | "arrowleft" => KbKey::ArrowLeft,
+        "right" | "arrowright" => KbKey::ArrowRight,
+        "f1" => KbKey::F1,
+        "f2" => KbKey::F2,
+        "f3" => KbKey::F3,
+        "f4" => KbKey::F4,
+        "f5" => KbKey::F5,
+        "f6" => KbKey::F6,
+        "f7" => KbKey::F7,
+        "f8" => KbKey::F8,
+        "f9" => KbKey::F9,
+        "f10" => KbKey::F10,
+        "f11" => KbKey::F11,
+        "f12" => KbKey::F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// The character of a key without Shift, on a US layout, for the keys which don't produce a
+/// letter.
+fn unshifted_key(code: &Code) -> Option<&'static str> {
+    let key = match code {
+        Code::Digit0 => "0",
+        Code::Digit1 => "1",
+        Code::Digit2 => "2",
+        Code::Digit3 => "3",
+        Code::Digit4 => "4",
+        Code::Digit5 => "5",
+        Code::Digit6 => "6",
+        Code::Digit7 => "7",
+        Code::Digit8 => "8",
+        Code::Digit9 => "9",
+        Code::Minus => "-",
+        Code::Equal => "=",
+        Code::BracketLeft => "[",
+        Code::BracketRight => "]",
+        Code::Backslash => "\\",
+        Code::Semicolon => ";",
+        Code::Quote => "'",
+        Code::Comma => ",",
+        Code::Period => ".",
+        Code::Slash => "/",
+        Code::Backquote => "`",
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// A key with modifiers, like Ctrl+Shift+P.
+///
+/// Chords are written as modifiers and a key joined by `+`. The modifiers are `Ctrl`, `Alt`,
+/// `Shift`, `Cmd` (or `Meta`), and `Mod`, which is Cmd on macOS and Ctrl elsewhere. Letters
+/// match regardless of case. Other characters typed with Shift match both ways, so on a US
+/// layout Ctrl+Shift+1 and Ctrl+! are the same key press.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    pub mods: Modifiers,
+    pub key: KbKey,
+}
+
+impl Chord {
+    pub fn new(mods: Modifiers, key: KbKey) -> Self {
+        Chord {
+            mods,
+            key: normalize_key(key),
+        }
+    }
+
+    /// The chord of a key press.
+    ///
+    /// With Shift, a key which doesn't produce a letter is taken from the physical key rather
+    /// than from the character it produced, so that Ctrl+Shift+1 matches even though the key
+    /// gives "!". See [`from_event_shifted`] for the chord with the character.
+    ///
+    /// [`from_event_shifted`]: Chord::from_event_shifted
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mods = chord_mods(event.mods);
+        let key = match &event.key {
+            KbKey::Character(c) if mods.shift() && !has_case(c) => unshifted_key(&event.code)
+                .map_or_else(
+                    || event.key.clone(),
+                    |key| KbKey::Character(key.to_string()),
+                ),
+            key => key.clone(),
+        };
+        Chord::new(mods, key)
+    }
+
+    /// The chord of a key press with the character produced with Shift, and without Shift, like
+    /// Ctrl+? for Ctrl+Shift+/ on a US layout. `None` if Shift didn't change the character.
+    pub fn from_event_shifted(event: &KeyEvent) -> Option<Self> {
+        let mods = chord_mods(event.mods);
+        match &event.key {
+            KbKey::Character(c) if mods.shift() && !has_case(c) => {
+                Some(Chord::new(mods - Modifiers::SHIFT, event.key.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The modifiers which are part of a chord.
+fn chord_mods(mods: Modifiers) -> Modifiers {
+    mods & (Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT | Modifiers::META)
+}
+
+/// Whether a character is a letter with upper and lower case.
+fn has_case(c: &str) -> bool {
+    c.to_lowercase() != c.to_uppercase()
+}
+
+fn normalize_key(key: KbKey) -> KbKey {
+    match key {
+        KbKey::Character(c) => KbKey::Character(c.to_lowercase()),
+        key => key,
+    }
+}
+
+fn is_modifier_key(key: &KbKey) -> bool {
+    matches!(
+        key,
+        KbKey::Control
+            | KbKey::Alt
+            | KbKey::AltGraph
+            | KbKey::Shift
+            | KbKey::Meta
+            | KbKey::Super
+            | KbKey::CapsLock
+    )
+}
+
+impl FromStr for Chord {
+    type Err = ParseHotkeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.trim().split('+').map(str::trim).collect();
+        let key = parts.pop().filter(|key| !key.is_empty());
+        let key = key.ok_or(ParseHotkeyError::Empty)?;
+
+        let mut mods = Modifiers::empty();
+        for part in parts {
+            mods |= match part.to_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "alt" | "option" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                "cmd" | "command" | "meta" | "super" => Modifiers::META,
+                "mod" | "cmdorctrl" => primary_modifier(),
+                _ => return Err(ParseHotkeyError::UnknownModifier(part.to_string())),
+            };
+        }
+
+        let key = match named_key(key) {
+            Some(key) => key,
+            None if key.chars().count() == 1 => KbKey::Character(key.to_string()),
+            None => return Err(ParseHotkeyError::UnknownKey(key.to_string())),
+        };
+        Ok(Chord::new(mods, key))
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let meta = if cfg!(target_os = "macos") {
+            "Cmd"
+        } else {
+            "Meta"
+        };
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::META, meta),
+        ] {
+            if self.mods.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match &self.key {
+            KbKey::Character(c) if c == " " => write!(f, "Space"),
+            KbKey::Character(c) if c == "+" => write!(f, "Plus"),
+            KbKey::Character(c) if c == "," => write!(f, "Comma"),
+            KbKey::Character(c) => write!(f, "{}", c.to_uppercase()),
+            key => write!(f, "{}", key),
+        }
+    }
+}
+
+/// One or more chords pressed one after the other, like Ctrl+K Ctrl+S.
+///
+/// Sequences are written as chords separated by spaces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<Chord>);
+
+impl KeySequence {
+    /// Returns `true` if `self` is a proper prefix of `other`, which is then never reached.
+    fn is_prefix_of(&self, other: &KeySequence) -> bool {
+        self.0.len() < other.0.len() && other.0.starts_with(&self.0)
+    }
+
+    fn clashes_with(&self, other: &KeySequence) -> bool {
+        self == other || self.is_prefix_of(other) || other.is_prefix_of(self)
+    }
+}
+
+impl From<Chord> for KeySequence {
+    fn from(chord: Chord) -> Self {
+        KeySequence(vec![chord])
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = ParseHotkeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chords = s
+            .split_whitespace()
+            .map(Chord::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(ParseHotkeyError::Empty);
+        }
+        Ok(KeySequence(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+/// Two bindings of a [`Keymap`] which get in each other's way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub first: String,
+    pub second: String,
+    /// The sequence of `first`. It is either the same as the sequence of `second`, or the start
+    /// of it, so `second` can never be reached.
+    pub sequence: KeySequence,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" and \"{}\" both start with {}",
+            self.first, self.second, self.sequence
+        )
+    }
+}
+
+enum Lookup<'a> {
+    Action(&'a str),
+    /// The keys are the start of at least one binding.
+    Prefix,
+    None,
+}
+
+/// Bindings of key sequences to action names.
+///
+/// An action can have several bindings. Keymaps are written as one action per line, with its
+/// bindings separated by commas:
+///
+/// ```text
+/// save = Mod+S
+/// format = Mod+K Mod+F, Alt+Shift+F
+/// ```
+///
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct Keymap {
+    #[data(same_fn = "PartialEq::eq")]
+    bindings: BTreeMap<String, Vec<KeySequence>>,
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method for adding a binding, written as in the keymap text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` can't be parsed.
+    pub fn with_binding(mut self, action: impl Into<String>, keys: &str) -> Self {
+        let sequence = keys
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid key sequence \"{}\": {}", keys, err));
+        self.bind(action, sequence);
+        self
+    }
+
+    /// Add a binding for `action`.
+    pub fn bind(&mut self, action: impl Into<String>, sequence: KeySequence) {
+        let sequences = self.bindings.entry(action.into()).or_default();
+        if !sequences.contains(&sequence) {
+            sequences.push(sequence);
+        }
+    }
+
+    /// Remove a binding of `action`.
+    pub fn unbind(&mut self, action: &str, sequence: &KeySequence) {
+        if let Some(sequences) = self.bindings.get_mut(action) {
+            sequences.retain(|s| s != sequence);
+            if sequences.is_empty() {
+                self.bindings.remove(action);
+            }
+        }
+    }
+
+    /// Remove all bindings of `action`.
+    pub fn clear(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// The bindings of `action`.
+    pub fn bindings(&self, action: &str) -> &[KeySequence] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first binding of `action`, for showing it in menus and tooltips.
+    pub fn shortcut(&self, action: &str) -> Option<&KeySequence> {
+        self.bindings(action).first()
+    }
+
+    /// All actions with bindings, sorted by name.
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.bindings.keys().map(String::as_str)
+    }
+
+    /// The actions whose bindings would conflict with binding `sequence`.
+    pub fn conflicts_with(&self, sequence: &KeySequence) -> Vec<&str> {
+        self.bindings
+            .iter()
+            .filter(|(_, sequences)| sequences.iter().any(|s| s.clashes_with(sequence)))
+            .map(|(action, _)| action.as_str())
+            .collect()
+    }
+
+    /// All pairs of bindings which get in each other's way.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let all: Vec<(&String, &KeySequence)> = self
+            .bindings
+            .iter()
+            .flat_map(|(action, sequences)| sequences.iter().map(move |s| (action, s)))
+            .collect();
+        let mut conflicts = Vec::new();
+        for (i, (a_action, a)) in all.iter().enumerate() {
+            for (b_action, b) in &all[i + 1..] {
+                if !a.clashes_with(b) {
+                    continue;
+                }
+                let (first, second, sequence) = if b.is_prefix_of(a) {
+                    (b_action, a_action, b)
+                } else {
+                    (a_action, b_action, a)
+                };
+                conflicts.push(Conflict {
+                    first: first.to_string(),
+                    second: second.to_string(),
+                    sequence: (*sequence).clone(),
+                });
+            }
+        }
+        conflicts
+    }
+
+    fn lookup(&self, keys: &[Chord]) -> Lookup {
+        let mut prefix = false;
+        for (action, sequences) in &self.bindings {
+            for sequence in sequences {
+                if sequence.0 == keys {
+                    return Lookup::Action(action);
+                }
+                prefix |= sequence.0.starts_with(keys);
+            }
+        }
+        if prefix {
+            Lookup::Prefix
+        } else {
+            Lookup::None
+        }
+    }
+}
+
+impl FromStr for Keymap {
+    type Err = ParseHotkeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keymap = Keymap::new();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (action, keys) = line
+                .split_once('=')
+                .ok_or_else(|| ParseHotkeyError::InvalidLine(line.to_string()))?;
+            for keys in keys.split(',') {
+                keymap.bind(action.trim(), keys.parse()?);
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+impl fmt::Display for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (action, sequences) in &self.bindings {
+            write!(f, "{} = ", action)?;
+            for (i, sequence) in sequences.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", sequence)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A controller submitting commands when the keys bound in a [`Keymap`] are pressed.
+///
+/// Put it around the root widget of a window, so it sees all key presses before the focused
+/// widget does. For a multi-stroke sequence, the chords after the first have to follow within
+/// 1.5 seconds; a chord not continuing the sequence is swallowed.
+///
+/// Conflicting bindings are logged when the keymap is set; the action whose name sorts first
+/// wins.
+pub struct HotkeyController {
+    keymap: Keymap,
+    commands: HashMap<String, Command>,
+    /// The chords of the sequence being typed.
+    pending: Vec<Chord>,
+    timer: TimerToken,
+}
+
+impl HotkeyController {
+    pub fn new(keymap: Keymap) -> Self {
+        let mut controller = HotkeyController {
+            keymap: Keymap::new(),
+            commands: HashMap::new(),
+            pending: Vec::new(),
+            timer: TimerToken::INVALID,
+        };
+        controller.set_keymap(keymap);
+        controller
+    }
+
+    /// Builder-style method for specifying the command submitted for an action.
+    pub fn with_action(mut self, action: impl Into<String>, command: impl Into<Command>) -> Self {
+        self.set_action(action, command);
+        self
+    }
+
+    /// Set the command submitted for an action.
+    pub fn set_action(&mut self, action: impl Into<String>, command: impl Into<Command>) {
+        self.commands.insert(action.into(), command.into());
+    }
+
+    /// Replace the keymap. At runtime, this can also be done with [`SET_KEYMAP`].
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        for conflict in keymap.conflicts() {
+            warn!("Conflicting key bindings: {}", conflict);
+        }
+        self.keymap = keymap;
+        self.pending.clear();
+    }
+
+    /// The keymap in use.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+}
+
+impl<T, W: Widget<T>> Controller<T, W> for HotkeyController {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(SET_KEYMAP) => {
+                self.set_keymap(cmd.get_unchecked(SET_KEYMAP).clone());
+                ctx.set_handled();
+                return;
+            }
+            Event::Timer(token) if *token == self.timer => {
+                self.pending.clear();
+                self.timer = TimerToken::INVALID;
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if !key.repeat && !is_modifier_key(&key.key) => {
+                let pending = std::mem::take(&mut self.pending);
+                let mut prefix = None;
+                let chords =
+                    std::iter::once(Chord::from_event(key)).chain(Chord::from_event_shifted(key));
+                for chord in chords {
+                    let mut keys = pending.clone();
+                    keys.push(chord);
+                    match self.keymap.lookup(&keys) {
+                        Lookup::Action(action) => {
+                            match self.commands.get(action) {
+                                Some(command) => ctx.submit_command(command.clone()),
+                                None => warn!("No command for the action \"{}\"", action),
+                            }
+                            ctx.set_handled();
+                            return;
+                        }
+                        Lookup::Prefix => {
+                            prefix.get_or_insert(keys);
+                        }
+                        Lookup::None => {}
+                    }
+                }
+                if let Some(keys) = prefix {
+                    self.pending = keys;
+                    self.timer = ctx.request_timer(SEQUENCE_TIMEOUT);
+                    ctx.set_handled();
+                    return;
+                }
+                if !pending.is_empty() {
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            _ => {}
+        }
+        child.event(ctx, event, data, env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seq(keys: &str) -> KeySequence {
+        keys.parse().unwrap()
+    }
+
+    fn chord(keys: &str) -> Chord {
+        keys.parse().unwrap()
+    }
+
+    fn key_event(mods: Modifiers, key: &str, code: Code) -> KeyEvent {
+        KeyEvent {
+            key: KbKey::Character(key.to_string()),
+            code,
+            mods,
+            ..KeyEvent::default()
+        }
+    }
+
+    #[test]
+    fn parse_keymap() {
+        let keymap: Keymap = "
+            # comments and empty lines are skipped
+
+            save = Ctrl+S
+            format = Ctrl+K Ctrl+F, alt+shift+f
+            zoom = Ctrl+Plus, Ctrl+Comma
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(
+            keymap.actions().collect::<Vec<_>>(),
+            ["format", "save", "zoom"]
+        );
+        assert_eq!(keymap.bindings("save"), [seq("Ctrl+S")]);
+        assert_eq!(
+            keymap.bindings("format"),
+            [seq("Ctrl+K Ctrl+F"), seq("Alt+Shift+F")]
+        );
+        assert_eq!(
+            keymap.bindings("zoom")[0].0[0],
+            Chord::new(Modifiers::CONTROL, KbKey::Character("+".into()))
+        );
+        assert_eq!(
+            keymap.bindings("zoom")[1].0[0],
+            Chord::new(Modifiers::CONTROL, KbKey::Character(",".into()))
+        );
+        assert_eq!(keymap.shortcut("format"), Some(&seq("Ctrl+K Ctrl+F")));
+        assert_eq!(keymap.shortcut("missing"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "Hyper+A".parse::<Chord>(),
+            Err(ParseHotkeyError::UnknownModifier("Hyper".into()))
+        );
+        assert_eq!(
+            "Ctrl+Nope".parse::<Chord>(),
+            Err(ParseHotkeyError::UnknownKey("Nope".into()))
+        );
+        assert_eq!("Ctrl+".parse::<Chord>(), Err(ParseHotkeyError::Empty));
+        assert_eq!("  ".parse::<KeySequence>(), Err(ParseHotkeyError::Empty));
+        assert_eq!(
+            "save Ctrl+S".parse::<Keymap>(),
+            Err(ParseHotkeyError::InvalidLine("save Ctrl+S".into()))
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let keymap = Keymap::new()
+            .with_binding("save", "Mod+S")
+            .with_binding("format", "Ctrl+K Ctrl+F")
+            .with_binding("format", "Alt+Shift+F")
+            .with_binding("zoom", "Ctrl+Plus")
+            .with_binding("next", "Ctrl+Comma")
+            .with_binding("search", "Ctrl+Space")
+            .with_binding("help", "F1")
+            .with_binding("back", "Meta+Left");
+        assert_eq!(keymap.to_string().parse::<Keymap>().unwrap(), keymap);
+        assert_eq!(seq("ctrl+shift+p").to_string(), "Ctrl+Shift+P");
+        assert_eq!(seq("Ctrl+K  ctrl+s").to_string(), "Ctrl+K Ctrl+S");
+    }
+
+    #[test]
+    fn conflicts() {
+        let keymap: Keymap = "
+            a = Ctrl+K
+            b = Ctrl+K Ctrl+S
+            c = F1
+            d = F1, F2
+            e = Ctrl+S
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(
+            keymap.conflicts(),
+            [
+                Conflict {
+                    first: "a".into(),
+                    second: "b".into(),
+                    sequence: seq("Ctrl+K"),
+                },
+                Conflict {
+                    first: "c".into(),
+                    second: "d".into(),
+                    sequence: seq("F1"),
+                },
+            ]
+        );
+        assert_eq!(keymap.conflicts_with(&seq("Ctrl+K Ctrl+T")), ["a"]);
+        assert_eq!(keymap.conflicts_with(&seq("F2")), ["d"]);
+        assert!(keymap.conflicts_with(&seq("Ctrl+T")).is_empty());
+    }
+
+    #[test]
+    fn shifted_keys() {
+        let event = key_event(Modifiers::CONTROL | Modifiers::SHIFT, "!", Code::Digit1);
+        assert_eq!(Chord::from_event(&event), chord("Ctrl+Shift+1"));
+        assert_eq!(Chord::from_event_shifted(&event), Some(chord("Ctrl+!")));
+
+        let event = key_event(Modifiers::CONTROL | Modifiers::SHIFT, "P", Code::KeyP);
+        assert_eq!(Chord::from_event(&event), chord("Ctrl+Shift+P"));
+        assert_eq!(Chord::from_event_shifted(&event), None);
+
+        let event = key_event(Modifiers::CONTROL, "/", Code::Slash);
+        assert_eq!(Chord::from_event(&event), chord("Ctrl+/"));
+        assert_eq!(Chord::from_event_shifted(&event), None);
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use druid::kurbo::{Affine, Circle, RoundedRect};
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::{theme, Color, KeyOrValue, Lens, LensExt, Point, Rect, TextLayout};
+
+use crate::material_icons::IconData;
+use crate::TooltipController;
+
+/// The space around the icon.
+const PADDING: f64 = 6.0;
+const BADGE_TEXT_SIZE: f64 = 10.0;
+
+type ClickFn<T> = dyn Fn(&mut EventCtx, &mut T, &Env);
+type BadgeFn<T> = dyn Fn(&T, &Env) -> Option<String>;
+
+/// What clicking an [`IconButton`] does, shared with its entry in the overflow menu of a
+/// [`Toolbar`].
+///
+/// [`Toolbar`]: crate::Toolbar
+pub(crate) struct Behavior<T> {
+    on_click: Option<Rc<ClickFn<T>>>,
+    /// Gets and flips the toggled state.
+    toggle: Option<(Rc<dyn Fn(&T) -> bool>, Rc<dyn Fn(&mut T)>)>,
+}
+
+impl<T> Clone for Behavior<T> {
+    fn clone(&self) -> Self {
+        Behavior {
+            on_click: self.on_click.clone(),
+            toggle: self.toggle.clone(),
+        }
+    }
+}
+
+impl<T> Behavior<T> {
+    pub(crate) fn click(&self, ctx: &mut EventCtx, data: &mut T, env: &Env) {
+        if let Some((_, flip)) = &self.toggle {
+            flip(data);
+        }
+        if let Some(on_click) = &self.on_click {
+            on_click(ctx, data, env);
+        }
+    }
+
+    pub(crate) fn is_selected(&self, data: &T) -> bool {
+        matches!(&self.toggle, Some((selected, _)) if selected(data))
+    }
+}
+
+/// The entry of an [`IconButton`] in the overflow menu of a [`Toolbar`].
+///
+/// [`Toolbar`]: crate::Toolbar
+pub(crate) struct MenuEntry<T> {
+    pub(crate) icon: IconData,
+    pub(crate) label: String,
+    pub(crate) behavior: Behavior<T>,
+}
+
+impl<T> Clone for MenuEntry<T> {
+    fn clone(&self) -> Self {
+        MenuEntry {
+            icon: self.icon.clone(),
+            label: self.label.clone(),
+            behavior: self.behavior.clone(),
+        }
+    }
+}
+
+/// A flat button showing an icon, with an optional tooltip.
+///
+/// The button can toggle a `bool` with [`with_toggle`], showing whether it is on, and can show a
+/// badge, like a count of unread messages, in its corner.
+///
+/// ```
+/// # use druid::{Data, Lens, Widget};
+/// # use druid_widget_nursery::material_icons::normal::{content, editor};
+/// # use druid_widget_nursery::IconButton;
+/// #[derive(Clone, Data, Lens)]
+/// struct Editor {
+///     bold: bool,
+///     drafts: usize,
+/// }
+///
+/// fn bold() -> impl Widget<Editor> {
+///     IconButton::new(editor::FORMAT_BOLD)
+///         .with_tooltip("Bold")
+///         .with_toggle(Editor::bold)
+/// }
+///
+/// fn drafts() -> impl Widget<Editor> {
+///     IconButton::new(content::DRAFTS)
+///         .with_tooltip("Drafts")
+///         .with_badge(|data: &Editor, _| (data.drafts > 0).then(|| data.drafts.to_string()))
+///         .with_on_click(|_ctx, _data, _env| println!("show the drafts"))
+/// }
+/// ```
+///
+/// [`with_toggle`]: #method.with_toggle
+pub struct IconButton<T> {
+    body: Body<T>,
+    tooltip: Option<(String, TooltipController<T>)>,
+}
+
+/// The button itself, wrapped by the tooltip controller.
+struct Body<T> {
+    icon: IconData,
+    color: KeyOrValue<Color>,
+    icon_size: KeyOrValue<f64>,
+    behavior: Behavior<T>,
+    badge: Option<Box<BadgeFn<T>>>,
+    badge_color: KeyOrValue<Color>,
+    badge_layout: Option<TextLayout<String>>,
+}
+
+impl<T: Data> IconButton<T> {
+    /// Create a button showing `icon`, like one of the material icons.
+    pub fn new(icon: impl Into<IconData>) -> Self {
+        IconButton {
+            body: Body {
+                icon: icon.into(),
+                color: theme::TEXT_COLOR.into(),
+                icon_size: KeyOrValue::Concrete(20.0),
+                behavior: Behavior {
+                    on_click: None,
+                    toggle: None,
+                },
+                badge: None,
+                badge_color: KeyOrValue::Concrete(Color::rgb8(0xd3, 0x2f, 0x2f)),
+                badge_layout: None,
+            },
+            tooltip: None,
+        }
+    }
+
+    /// Builder-style method for showing a tooltip. In a [`Toolbar`], the text is also the label of
+    /// the button in the overflow menu.
+    ///
+    /// [`Toolbar`]: crate::Toolbar
+    pub fn with_tooltip(mut self, text: impl Into<String>) -> Self {
+        let text = text.into();
+        self.tooltip = Some((text.clone(), TooltipController::new(text)));
+        self
+    }
+
+    /// Builder-style method for calling `on_click` when the button is clicked.
+    ///
+    /// Unlike wrapping the button with `on_click`, this also works from the overflow menu of a
+    /// [`Toolbar`].
+    ///
+    /// [`Toolbar`]: crate::Toolbar
+    pub fn with_on_click(
+        mut self,
+        on_click: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> Self {
+        self.body.behavior.on_click = Some(Rc::new(on_click));
+        self
+    }
+
+    /// Builder-style method for making the button toggle the `bool` of `lens`. The button is
+    /// highlighted while it is on.
+    pub fn with_toggle(mut self, lens: impl Lens<T, bool> + Clone + 'static) -> Self {
+        let get = lens.clone();
+        self.body.behavior.toggle = Some((
+            Rc::new(move |data: &T| get.get(data)),
+            Rc::new(move |data: &mut T| lens.with_mut(data, |on| *on = !*on)),
+        ));
+        self
+    }
+
+    /// Builder-style method for specifying the color of the icon.
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.body.color = color.into();
+        self
+    }
+
+    /// Builder-style method for specifying the height of the icon.
+    pub fn with_icon_size(mut self, size: impl Into<KeyOrValue<f64>>) -> Self {
+        self.body.icon_size = size.into();
+        self
+    }
+
+    /// Builder-style method for showing a badge in the top right corner, with the text returned
+    /// by `badge`, or no badge for `None`.
+    pub fn with_badge(mut self, badge: impl Fn(&T, &Env) -> Option<String> + 'static) -> Self {
+        self.body.badge = Some(Box::new(badge));
+        self
+    }
+
+    /// Builder-style method for specifying the background color of the badge.
+    pub fn with_badge_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.body.badge_color = color.into();
+        self
+    }
+
+    /// The entry of the button in the overflow menu of a toolbar.
+    pub(crate) fn menu_entry(&self) -> MenuEntry<T> {
+        MenuEntry {
+            icon: self.body.icon.clone(),
+            label: self
+                .tooltip
+                .as_ref()
+                .map(|(text, _)| text.clone())
+                .unwrap_or_default(),
+            behavior: self.body.behavior.clone(),
+        }
+    }
+}
+
+impl<T: Data> Body<T> {
+    /// Update the text of the badge, returning whether it changed.
+    fn update_badge(&mut self, data: &T, env: &Env) -> bool {
+        let text = self.badge.as_ref().and_then(|badge| badge(data, env));
+        match (&mut self.badge_layout, text) {
+            (Some(layout), Some(text)) => {
+                if layout.text().map_or(true, |old| *old != text) {
+                    layout.set_text(text);
+                    true
+                } else {
+                    false
+                }
+            }
+            (layout @ None, Some(text)) => {
+                let mut new = TextLayout::from_text(text);
+                new.set_text_size(BADGE_TEXT_SIZE);
+                new.set_text_color(Color::WHITE);
+                *layout = Some(new);
+                true
+            }
+            (layout, None) => layout.take().is_some(),
+        }
+    }
+
+    fn icon_size(&self, env: &Env) -> Size {
+        let height = self.icon_size.resolve(env);
+        Size::new(height / self.icon.size().aspect_ratio(), height)
+    }
+}
+
+impl<T: Data> Widget<T> for Body<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::MouseDown(_) if !ctx.is_disabled() => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() && !ctx.is_disabled() {
+                    self.behavior.click(ctx, data, env);
+                }
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.update_badge(data, env);
+            }
+            LifeCycle::HotChanged(_) | LifeCycle::DisabledChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if self.update_badge(data, env) {
+            ctx.request_layout();
+        }
+        if let Some(layout) = &mut self.badge_layout {
+            if layout.needs_rebuild_after_update(ctx) {
+                ctx.request_layout();
+            }
+        }
+        if self.behavior.is_selected(old_data) != self.behavior.is_selected(data) {
+            ctx.request_paint();
+        }
+        if let KeyOrValue::Key(key) = &self.icon_size {
+            if ctx.env_key_changed(key) {
+                ctx.request_layout();
+            }
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        if let Some(layout) = &mut self.badge_layout {
+            layout.rebuild_if_needed(ctx.text(), env);
+        }
+        let icon_size = self.icon_size(env);
+        bc.constrain(Size::new(
+            icon_size.width + 2.0 * PADDING,
+            icon_size.height + 2.0 * PADDING,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let radius = env.get(theme::BUTTON_BORDER_RADIUS);
+        let background = size.to_rect().to_rounded_rect(radius);
+        let disabled = ctx.is_disabled();
+        if self.behavior.is_selected(data) {
+            ctx.fill(background, &env.get(theme::PRIMARY_DARK).with_alpha(0.5));
+        }
+        if ctx.is_active() && !disabled {
+            ctx.fill(background, &env.get(theme::BUTTON_DARK));
+        } else if ctx.is_hot() && !disabled {
+            ctx.fill(background, &env.get(theme::BUTTON_LIGHT).with_alpha(0.5));
+        }
+
+        let color = if disabled {
+            env.get(theme::DISABLED_TEXT_COLOR)
+        } else {
+            self.color.resolve(env)
+        };
+        let icon_size = self.icon_size(env);
+        let icon_rect = Rect::from_center_size(size.to_rect().center(), icon_size);
+        let view_box = self.icon.size();
+        ctx.with_save(|ctx| {
+            ctx.transform(
+                Affine::translate(icon_rect.origin().to_vec2())
+                    * Affine::scale_non_uniform(
+                        icon_size.width / view_box.width,
+                        icon_size.height / view_box.height,
+                    ),
+            );
+            self.icon.fill(ctx, &color);
+        });
+
+        if let Some(layout) = &self.badge_layout {
+            let text_size = layout.size();
+            let height = text_size.height;
+            let width = text_size.width.max(height) + height / 2.0;
+            let rect = Rect::new(size.width - width, 0.0, size.width, height);
+            if text_size.width <= height {
+                ctx.fill(
+                    Circle::new(rect.center(), width / 2.0),
+                    &self.badge_color.resolve(env),
+                );
+            } else {
+                ctx.fill(
+                    RoundedRect::from_rect(rect, height / 2.0),
+                    &self.badge_color.resolve(env),
+                );
+            }
+            layout.draw(
+                ctx,
+                Point::new(rect.center().x - text_size.width / 2.0, rect.y0),
+            );
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for IconButton<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match &mut self.tooltip {
+            Some((_, tooltip)) => tooltip.event(&mut self.body, ctx, event, data, env),
+            None => self.body.event(ctx, event, data, env),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match &mut self.tooltip {
+            Some((_, tooltip)) => tooltip.lifecycle(&mut self.body, ctx, event, data, env),
+            None => self.body.lifecycle(ctx, event, data, env),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        match &mut self.tooltip {
+            Some((_, tooltip)) => tooltip.update(&mut self.body, ctx, old_data, data, env),
+            None => self.body.update(ctx, old_data, data, env),
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.body.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.body.paint(ctx, data, env)
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An image viewer with zoom, pan and rotation, with the `image` feature.
+
+use std::f64::consts::FRAC_PI_2;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use druid::piet::{InterpolationMode, PietImage, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Affine, Data, ExtEventSink, ImageBuf, Point, Rect, Selector, Vec2, WidgetId};
+
+/// Zooms in around the center.
+pub const ZOOM_IN: Selector = Selector::new("druid-widget-nursery.image-viewer.zoom-in");
+/// Zooms out around the center.
+pub const ZOOM_OUT: Selector = Selector::new("druid-widget-nursery.image-viewer.zoom-out");
+/// Sets how the image is sized, and puts it back in the middle.
+pub const SET_FIT_MODE: Selector<FitMode> =
+    Selector::new("druid-widget-nursery.image-viewer.set-fit-mode");
+/// Rotates the image a quarter turn counterclockwise.
+pub const ROTATE_LEFT: Selector = Selector::new("druid-widget-nursery.image-viewer.rotate-left");
+/// Rotates the image a quarter turn clockwise.
+pub const ROTATE_RIGHT: Selector = Selector::new("druid-widget-nursery.image-viewer.rotate-right");
+
+/// Sent to itself by the viewer from the thread loading the image, with the number of the load.
+const LOADED: Selector<(u64, Result<ImageBuf, String>)> =
+    Selector::new("druid-widget-nursery.image-viewer.loaded");
+
+const DEFAULT_SIZE: Size = Size::new(400.0, 300.0);
+const ZOOM_STEP: f64 = 1.25;
+const MIN_ZOOM: f64 = 0.05;
+const MAX_ZOOM: f64 = 32.0;
+
+/// The image of an [`ImageViewer`].
+#[derive(Clone, Data)]
+pub enum ImageSource {
+    Empty,
+    /// A file loaded on another thread.
+    Path(Arc<PathBuf>),
+    /// Encoded bytes, like the content of a file, decoded on another thread.
+    Bytes(Arc<Vec<u8>>),
+    /// An image already decoded.
+    Image(ImageBuf),
+}
+
+impl Default for ImageSource {
+    fn default() -> Self {
+        ImageSource::Empty
+    }
+}
+
+/// How an [`ImageViewer`] sizes the image, until it is zoomed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum FitMode {
+    /// The whole image is shown, as large as possible.
+    Fit,
+    /// The viewer is covered by the image, as small as possible.
+    Fill,
+    /// A pixel of the image for a pixel of the screen.
+    ActualSize,
+}
+
+/// A viewer of an [`ImageSource`], zoomed with the mouse wheel around the mouse and panned by
+/// dragging.
+///
+/// Files and bytes are decoded on another thread, showing "Loading…" meanwhile. The image is
+/// sized by a [`FitMode`] until it is zoomed; double-clicking sizes it again. The image is also
+/// zoomed and rotated with the commands of this module.
+///
+/// ```
+/// # use std::path::PathBuf;
+/// # use std::sync::Arc;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::image_viewer::{FitMode, ImageSource, ImageViewer};
+/// fn photo() -> impl Widget<ImageSource> {
+///     ImageViewer::new().with_fit_mode(FitMode::Fill)
+/// }
+///
+/// let data = ImageSource::Path(Arc::new(PathBuf::from("holidays.jpg")));
+/// ```
+pub struct ImageViewer {
+    fit_mode: FitMode,
+    image: Option<ImageBuf>,
+    /// The image for the render context, made when painting.
+    cached: Option<PietImage>,
+    loading: bool,
+    error: Option<String>,
+    /// The number of the last load, to ignore the images that finish loading too late.
+    load: u64,
+    /// The zoom, or `None` for the zoom of the fit mode.
+    zoom: Option<f64>,
+    /// The offset of the center of the image from the center of the viewer.
+    offset: Vec2,
+    /// The rotation, in quarter turns clockwise.
+    quarter_turns: u8,
+    drag_start: Option<Point>,
+}
+
+impl ImageViewer {
+    pub fn new() -> Self {
+        ImageViewer {
+            fit_mode: FitMode::Fit,
+            image: None,
+            cached: None,
+            loading: false,
+            error: None,
+            load: 0,
+            zoom: None,
+            offset: Vec2::ZERO,
+            quarter_turns: 0,
+            drag_start: None,
+        }
+    }
+
+    /// Builder-style method for specifying how the image is sized until it is zoomed.
+    ///
+    /// For the non-builder varient, see [`set_fit_mode`].
+    ///
+    /// [`set_fit_mode`]: #method.set_fit_mode
+    pub fn with_fit_mode(mut self, fit_mode: FitMode) -> Self {
+        self.set_fit_mode(fit_mode);
+        self
+    }
+
+    /// Set how the image is sized, and put it back in the middle.
+    pub fn set_fit_mode(&mut self, fit_mode: FitMode) {
+        self.fit_mode = fit_mode;
+        self.reset_view();
+    }
+
+    fn reset_view(&mut self) {
+        self.zoom = None;
+        self.offset = Vec2::ZERO;
+    }
+
+    /// Show the image of `data`, loading it on another thread if needed.
+    fn load(&mut self, sink: ExtEventSink, id: WidgetId, data: &ImageSource) {
+        self.load += 1;
+        self.image = None;
+        self.cached = None;
+        self.error = None;
+        self.loading = false;
+        self.reset_view();
+        let load = self.load;
+        match data {
+            ImageSource::Empty => {}
+            ImageSource::Image(image) => self.image = Some(image.clone()),
+            ImageSource::Path(_) | ImageSource::Bytes(_) => {
+                self.loading = true;
+                let source = data.clone();
+                thread::spawn(move || {
+                    let result = match source {
+                        ImageSource::Path(path) => ImageBuf::from_file(&*path),
+                        ImageSource::Bytes(bytes) => ImageBuf::from_data(&bytes),
+                        _ => unreachable!(),
+                    };
+                    let result = result.map_err(|err| err.to_string());
+                    // only fails if the app is gone
+                    let _ = sink.submit_command(LOADED, (load, result), id);
+                });
+            }
+        }
+    }
+
+    /// The size of the image once rotated.
+    fn rotated_size(&self) -> Option<Size> {
+        let size = self.image.as_ref()?.size();
+        Some(if self.quarter_turns % 2 == 1 {
+            Size::new(size.height, size.width)
+        } else {
+            size
+        })
+    }
+
+    fn scale(&self, view: Size) -> f64 {
+        if let Some(zoom) = self.zoom {
+            return zoom;
+        }
+        let image = match self.rotated_size() {
+            Some(image) if !image.is_empty() => image,
+            _ => return 1.0,
+        };
+        let (x, y) = (view.width / image.width, view.height / image.height);
+        match self.fit_mode {
+            FitMode::Fit => x.min(y),
+            FitMode::Fill => x.max(y),
+            FitMode::ActualSize => 1.0,
+        }
+    }
+
+    /// Zoom by `factor`, keeping the point `around` of the viewer in place.
+    fn zoom_by(&mut self, ctx: &mut EventCtx, factor: f64, around: Point) {
+        let view = ctx.size();
+        let old = self.scale(view);
+        let new = (old * factor).max(MIN_ZOOM).min(MAX_ZOOM);
+        let center = view.to_rect().center();
+        let from_image = around - center - self.offset;
+        self.offset = around - center - from_image * (new / old);
+        self.zoom = Some(new);
+        ctx.request_paint();
+    }
+
+    fn transform(&self, view: Size) -> Option<Affine> {
+        let image = self.image.as_ref()?.size();
+        let center = view.to_rect().center() + self.offset;
+        Some(
+            Affine::translate(center.to_vec2())
+                * Affine::rotate(self.quarter_turns as f64 * FRAC_PI_2)
+                * Affine::scale(self.scale(view))
+                * Affine::translate((-image.width / 2.0, -image.height / 2.0)),
+        )
+    }
+}
+
+impl Default for ImageViewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<ImageSource> for ImageViewer {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut ImageSource, _env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(LOADED) => {
+                let (load, result) = cmd.get_unchecked(LOADED);
+                if *load == self.load {
+                    self.loading = false;
+                    match result {
+                        Ok(image) => self.image = Some(image.clone()),
+                        Err(err) => self.error = Some(err.clone()),
+                    }
+                    ctx.request_layout();
+                }
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(ZOOM_IN) || cmd.is(ZOOM_OUT) => {
+                let factor = if cmd.is(ZOOM_IN) {
+                    ZOOM_STEP
+                } else {
+                    1.0 / ZOOM_STEP
+                };
+                let center = ctx.size().to_rect().center();
+                self.zoom_by(ctx, factor, center);
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(SET_FIT_MODE) => {
+                self.set_fit_mode(*cmd.get_unchecked(SET_FIT_MODE));
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(ROTATE_LEFT) || cmd.is(ROTATE_RIGHT) => {
+                let turns = if cmd.is(ROTATE_RIGHT) { 1 } else { 3 };
+                self.quarter_turns = (self.quarter_turns + turns) % 4;
+                // rotate around the center of the viewer
+                let rotation = if cmd.is(ROTATE_RIGHT) { 1.0 } else { -1.0 } * FRAC_PI_2;
+                self.offset = Affine::rotate(rotation) * self.offset.to_point() - Point::ORIGIN;
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            Event::Wheel(wheel) if self.image.is_some() => {
+                let factor = ZOOM_STEP.powf(-wheel.wheel_delta.y / 120.0);
+                self.zoom_by(ctx, factor, wheel.pos);
+                ctx.set_handled();
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() && self.image.is_some() => {
+                if mouse.count == 2 {
+                    self.reset_view();
+                    ctx.request_paint();
+                } else {
+                    self.drag_start = Some(mouse.pos);
+                    ctx.set_active(true);
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some(start) = self.drag_start {
+                    self.offset += mouse.pos - start;
+                    self.drag_start = Some(mouse.pos);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                self.drag_start = None;
+                ctx.set_active(false);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &ImageSource,
+        _env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            self.load(ctx.get_external_handle(), ctx.widget_id(), data);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &ImageSource,
+        data: &ImageSource,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) {
+            self.load(ctx.get_external_handle(), ctx.widget_id(), data);
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &ImageSource,
+        _env: &Env,
+    ) -> Size {
+        let natural = self.rotated_size().unwrap_or(DEFAULT_SIZE);
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            natural.width
+        };
+        let height = if bc.is_height_bounded() {
+            bc.max().height
+        } else {
+            natural.height
+        };
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &ImageSource, env: &Env) {
+        let size = ctx.size();
+        let message = if self.loading {
+            Some("Loading\u{2026}".to_string())
+        } else {
+            self.error.clone()
+        };
+        if let Some(message) = message {
+            let font = env.get(theme::UI_FONT);
+            let layout = ctx
+                .text()
+                .new_text_layout(message)
+                .font(font.family, font.size)
+                .text_color(env.get(theme::DISABLED_TEXT_COLOR))
+                .max_width(size.width)
+                .build()
+                .unwrap();
+            let origin = size.to_rect().center() - layout.size().to_vec2() / 2.0;
+            ctx.draw_text(&layout, origin);
+            return;
+        }
+
+        let (image, transform) = match (&self.image, self.transform(size)) {
+            (Some(image), Some(transform)) => (image, transform),
+            _ => return,
+        };
+        if self.cached.is_none() {
+            self.cached = Some(image.to_image(ctx.render_ctx));
+        }
+        let cached = match &self.cached {
+            Some(cached) => cached,
+            None => return,
+        };
+        let rect = Rect::from_origin_size(Point::ORIGIN, image.size());
+        // smooth when shrunk, sharp pixels when zoomed in
+        let interpolation = if self.scale(size) < 1.0 {
+            InterpolationMode::Bilinear
+        } else {
+            InterpolationMode::NearestNeighbor
+        };
+        let clip = size.to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            ctx.transform(transform);
+            ctx.draw_image(cached, rect, interpolation);
+        });
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use druid::text::{Formatter, Selection, Validation, ValidationError};
+use druid::widget::{TextBox, ValueTextBox};
+
+use crate::Validator;
+
+/// A position of an [`InputMask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    Digit,
+    Letter,
+    Hex,
+    Alphanumeric,
+}
+
+impl Token {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Token::Literal(_) => false,
+            Token::Digit => c.is_ascii_digit(),
+            Token::Letter => c.is_alphabetic(),
+            Token::Hex => c.is_ascii_hexdigit(),
+            Token::Alphanumeric => c.is_alphanumeric(),
+        }
+    }
+}
+
+/// Why text doesn't fit an [`InputMask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskError {
+    /// The character doesn't fit at its position.
+    InvalidCharacter(char),
+    /// There are more characters than the mask has room for.
+    TooLong,
+}
+
+impl fmt::Display for MaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaskError::InvalidCharacter(c) => write!(f, "'{}' is not allowed here", c),
+            MaskError::TooLong => write!(f, "Too many characters"),
+        }
+    }
+}
+
+impl std::error::Error for MaskError {}
+
+/// A pattern for text input, like `(###) ###-####` for phone numbers.
+///
+/// In the pattern, `#` stands for a digit, `A` for a letter, `H` for a hexadecimal digit and
+/// `*` for a letter or digit. Every other character is a literal, which is inserted
+/// automatically; `\` makes the next character a literal.
+///
+/// The raw value consists of the characters the user typed for the placeholders, the formatted
+/// value adds the literals:
+///
+/// ```
+/// # use druid_widget_nursery::InputMask;
+/// let phone = InputMask::new("(###) ###-####");
+/// assert_eq!(phone.format("5551234"), "(555) 123-4");
+/// assert_eq!(phone.raw("(555) 123-4567"), Ok("5551234567".to_string()));
+/// assert!(phone.is_complete("5551234567"));
+///
+/// let color = InputMask::new("\\#HHHHHH");
+/// assert_eq!(color.format("ff8800"), "#ff8800");
+/// assert!(color.raw("#ff88zz").is_err());
+/// ```
+///
+/// As a [`Formatter`], the mask edits the raw value in a [`TextBox`], rejecting keystrokes that
+/// don't fit. See [`text_box`].
+///
+/// [`text_box`]: #method.text_box
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMask {
+    tokens: Vec<Token>,
+}
+
+impl InputMask {
+    pub fn new(pattern: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '#' => Token::Digit,
+                'A' => Token::Letter,
+                'H' => Token::Hex,
+                '*' => Token::Alphanumeric,
+                '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+                c => Token::Literal(c),
+            });
+        }
+        InputMask { tokens }
+    }
+
+    /// Create a [`TextBox`] editing the raw value with this mask.
+    pub fn text_box(self) -> ValueTextBox<String> {
+        TextBox::new()
+            .with_formatter(self)
+            .update_data_while_editing(true)
+    }
+
+    /// Create a [`Validator`] requiring the value to be complete.
+    pub fn validator(&self, message: impl Into<String>) -> Validator<String> {
+        let mask = self.clone();
+        let message = message.into();
+        Validator::new(move |raw: &String| {
+            if mask.is_complete(raw) {
+                Ok(())
+            } else {
+                Err(message.clone())
+            }
+        })
+    }
+
+    /// The number of characters of a complete raw value.
+    pub fn len(&self) -> usize {
+        self.tokens
+            .iter()
+            .filter(|token| !matches!(token, Token::Literal(_)))
+            .count()
+    }
+
+    /// Returns true if the mask has no placeholders.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if `raw` fills all placeholders.
+    pub fn is_complete(&self, raw: &str) -> bool {
+        raw.chars().count() == self.len()
+    }
+
+    /// Insert the literals into `raw`, up to the last character of `raw`.
+    ///
+    /// Characters that don't fit are skipped.
+    pub fn format(&self, raw: &str) -> String {
+        let mut formatted = String::new();
+        let mut literals = String::new();
+        let mut raw = raw.chars().peekable();
+        for token in &self.tokens {
+            match token {
+                Token::Literal(c) => literals.push(*c),
+                token => {
+                    while let Some(c) = raw.next() {
+                        if token.accepts(c) {
+                            formatted.push_str(&literals);
+                            formatted.push(c);
+                            literals.clear();
+                            break;
+                        }
+                    }
+                    if raw.peek().is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+        formatted
+    }
+
+    /// Extract the raw value from `text`, which may be formatted or not.
+    ///
+    /// Missing literals are fine, and spaces and punctuation not matching a literal are skipped.
+    pub fn raw(&self, text: &str) -> Result<String, MaskError> {
+        self.raw_with_offsets(text)
+            .map(|raw| raw.into_iter().map(|(c, _)| c).collect())
+    }
+
+    /// The raw characters with their byte offset in `text`.
+    fn raw_with_offsets(&self, text: &str) -> Result<Vec<(char, usize)>, MaskError> {
+        let mut raw = Vec::new();
+        let mut tokens = self.tokens.iter().peekable();
+        for (offset, c) in text.char_indices() {
+            loop {
+                match tokens.peek() {
+                    Some(Token::Literal(literal)) if *literal == c => {
+                        tokens.next();
+                        break;
+                    }
+                    Some(Token::Literal(_)) => {
+                        // the user didn't type the literal
+                        tokens.next();
+                    }
+                    Some(token) if token.accepts(c) => {
+                        raw.push((c, offset));
+                        tokens.next();
+                        break;
+                    }
+                    Some(_) if !c.is_alphanumeric() => break,
+                    Some(_) => return Err(MaskError::InvalidCharacter(c)),
+                    None if !c.is_alphanumeric() => break,
+                    None => return Err(MaskError::TooLong),
+                }
+            }
+        }
+        Ok(raw)
+    }
+}
+
+impl Formatter<String> for InputMask {
+    fn format(&self, raw: &String) -> String {
+        InputMask::format(self, raw)
+    }
+
+    fn validate_partial_input(&self, input: &str, sel: &Selection) -> Validation {
+        let raw = match self.raw_with_offsets(input) {
+            Ok(raw) => raw,
+            Err(err) => return Validation::failure(err),
+        };
+        let raw_text: String = raw.iter().map(|(c, _)| c).collect();
+        let formatted = InputMask::format(self, &raw_text);
+        if formatted == input {
+            return Validation::success();
+        }
+
+        // keep the caret behind the same raw character
+        let before_caret = raw
+            .iter()
+            .filter(|(_, offset)| *offset < sel.active)
+            .count();
+        let caret = match before_caret {
+            0 => 0,
+            n => self
+                .raw_with_offsets(&formatted)
+                .ok()
+                .and_then(|raw| raw.get(n - 1).map(|(c, offset)| offset + c.len_utf8()))
+                .unwrap_or_else(|| formatted.len()),
+        };
+        Validation::success()
+            .change_text(formatted)
+            .change_selection(Selection::caret(caret))
+    }
+
+    fn value(&self, input: &str) -> Result<String, ValidationError> {
+        self.raw(input).map_err(ValidationError::new)
+    }
+}
@@ -0,0 +1,365 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A developer overlay showing the widgets under the mouse, like the element picker of the
+//! devtools of a browser.
+
+use std::any::type_name;
+use std::fmt::Debug;
+
+use druid::piet::{StrokeStyle, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{
+    theme, Color, FontFamily, HotKey, Insets, KbKey, Key, Point, Rect, Selector, SysMods, Vec2,
+    WidgetPod,
+};
+
+/// Shows or hides the overlay of an [`Inspector`], for example from a menu item.
+pub const TOGGLE_INSPECTOR: Selector = Selector::new("druid-widget-nursery.inspector.toggle");
+
+/// Submitted by the [`Inspectable`] widgets under the mouse, the innermost first.
+const INSPECTED: Selector<WidgetInfo> = Selector::new("druid-widget-nursery.inspector.inspected");
+
+/// Whether an [`Inspector`] is showing the widgets under the mouse.
+const INSPECTING: Key<bool> = Key::new("druid-widget-nursery.inspector.inspecting");
+
+/// The longest data snapshot shown, in characters.
+const MAX_SNAPSHOT: usize = 200;
+const PANEL_PADDING: f64 = 6.0;
+
+/// What an [`Inspectable`] widget reports about itself.
+#[derive(Debug, Clone)]
+pub struct WidgetInfo {
+    pub id: WidgetId,
+    pub type_name: &'static str,
+    /// The layout rect, in window coordinates.
+    pub layout_rect: Rect,
+    pub paint_insets: Insets,
+    /// The data of the widget, when it was made with a snapshot.
+    pub snapshot: Option<String>,
+}
+
+type SnapshotFn<T> = Box<dyn Fn(&T) -> String>;
+
+/// A wrapper making a widget visible to the [`Inspector`] above it.
+///
+/// It's made with [`inspectable`], or [`inspectable_debug`] to show the data too.
+///
+/// [`inspectable`]: crate::WidgetExt::inspectable
+/// [`inspectable_debug`]: crate::WidgetExt::inspectable_debug
+pub struct Inspectable<T, W> {
+    child: WidgetPod<T, W>,
+    snapshot: Option<SnapshotFn<T>>,
+}
+
+impl<T: Data, W: Widget<T>> Inspectable<T, W> {
+    pub fn new(child: W) -> Self {
+        Inspectable {
+            child: WidgetPod::new(child),
+            snapshot: None,
+        }
+    }
+
+    /// Builder-style method for showing the data of the widget, formatted by `snapshot`.
+    pub fn with_snapshot(mut self, snapshot: impl Fn(&T) -> String + 'static) -> Self {
+        self.snapshot = Some(Box::new(snapshot));
+        self
+    }
+}
+
+impl<T: Data + Debug, W: Widget<T>> Inspectable<T, W> {
+    /// Make `child` inspectable, showing its data with [`Debug`].
+    pub fn debug(child: W) -> Self {
+        Self::new(child).with_snapshot(|data| format!("{:?}", data))
+    }
+}
+
+impl<T: Data, W: Widget<T>> Widget<T> for Inspectable<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.child.event(ctx, event, data, env);
+        // the widgets inside report first, so the path goes outwards
+        if let Event::MouseMove(_) = event {
+            if self.child.is_hot() && env.try_get(INSPECTING).unwrap_or(false) {
+                let info = WidgetInfo {
+                    id: self.child.id(),
+                    type_name: type_name::<W>(),
+                    layout_rect: self.child.layout_rect() + ctx.window_origin().to_vec2(),
+                    paint_insets: self.child.paint_insets(),
+                    snapshot: self.snapshot.as_ref().map(|snapshot| snapshot(data)),
+                };
+                ctx.submit_notification(INSPECTED.with(info));
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.child.baseline_offset());
+        ctx.set_paint_insets(self.child.paint_insets());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+    }
+}
+
+/// A developer overlay, toggled with a hotkey, highlighting the [`Inspectable`] widget under the
+/// mouse.
+///
+/// The overlay shows the id, the type, the layout rect and the paint insets of the widget, and
+/// its data when it was made with a snapshot. While it's shown, the app doesn't get the clicks:
+/// clicking pins the widget highlighted, and clicking again follows the mouse. The up and down
+/// arrows walk to the inspectable widget around the one highlighted, and inside it.
+///
+/// The inspector wraps the root widget of a window, and only sees the widgets marked with
+/// [`inspectable`], as druid doesn't expose the widget tree.
+///
+/// Druid only passes key events along the path to the focused widget, so the hotkey only works
+/// while a widget inside the inspector has the focus. Otherwise the overlay can be toggled with
+/// the [`TOGGLE_INSPECTOR`] command.
+///
+/// ```
+/// # use druid::widget::{Flex, Label, TextBox};
+/// # use druid::{Widget, WidgetExt as _};
+/// # use druid_widget_nursery::inspector::Inspector;
+/// # use druid_widget_nursery::WidgetExt as _;
+/// fn root() -> impl Widget<String> {
+///     let form = Flex::column()
+///         .with_child(Label::new("Name").inspectable())
+///         .with_child(TextBox::new().inspectable_debug());
+///     Inspector::new(form.padding(8.0).inspectable())
+/// }
+/// ```
+///
+/// [`inspectable`]: crate::WidgetExt::inspectable
+pub struct Inspector<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    hotkey: HotKey,
+    inspecting: bool,
+    /// The inspectable widgets under the mouse, the innermost first.
+    path: Vec<WidgetInfo>,
+    /// The index in `path` of the widget highlighted.
+    selected: usize,
+    pinned: bool,
+    window_origin: Point,
+}
+
+impl<T: Data> Inspector<T> {
+    pub fn new(child: impl Widget<T> + 'static) -> Self {
+        Inspector {
+            child: WidgetPod::new(Box::new(child)),
+            hotkey: HotKey::new(SysMods::CmdShift, "I"),
+            inspecting: false,
+            path: Vec::new(),
+            selected: 0,
+            pinned: false,
+            window_origin: Point::ORIGIN,
+        }
+    }
+
+    /// Builder-style method for setting the hotkey showing and hiding the overlay.
+    ///
+    /// For the non-builder varient, see [`set_hotkey`].
+    ///
+    /// [`set_hotkey`]: #method.set_hotkey
+    pub fn with_hotkey(mut self, hotkey: HotKey) -> Self {
+        self.set_hotkey(hotkey);
+        self
+    }
+
+    /// Set the hotkey showing and hiding the overlay, Cmd+Shift+I or Ctrl+Shift+I by default.
+    pub fn set_hotkey(&mut self, hotkey: HotKey) {
+        self.hotkey = hotkey;
+    }
+
+    /// Whether the overlay is shown.
+    pub fn is_inspecting(&self) -> bool {
+        self.inspecting
+    }
+
+    /// The widget highlighted.
+    pub fn selected(&self) -> Option<&WidgetInfo> {
+        self.path.get(self.selected)
+    }
+
+    fn set_inspecting(&mut self, ctx: &mut EventCtx, inspecting: bool) {
+        self.inspecting = inspecting;
+        self.path.clear();
+        self.selected = 0;
+        self.pinned = false;
+        // the inspectable widgets see it in the env
+        ctx.request_update();
+        ctx.request_paint();
+    }
+
+    fn child_env(&self, env: &Env) -> Env {
+        env.clone().adding(INSPECTING, self.inspecting)
+    }
+
+    fn paint_overlay(&self, ctx: &mut PaintCtx, env: &Env) {
+        let info = match self.selected() {
+            Some(info) => info,
+            None => return,
+        };
+        let to_local = -self.window_origin.to_vec2();
+        let rect = info.layout_rect + to_local;
+        let highlight = env.get(theme::PRIMARY_LIGHT);
+        ctx.fill(rect, &highlight.with_alpha(0.25));
+        ctx.stroke(rect, &highlight, 1.0);
+        if info.paint_insets != Insets::ZERO {
+            let dashes = StrokeStyle::new().dash_pattern(&[4.0, 2.0]);
+            let paint_rect = rect + info.paint_insets;
+            ctx.stroke_styled(paint_rect, &Color::rgb8(0xff, 0xa5, 0x00), 1.0, &dashes);
+        }
+
+        let mut text = format!(
+            "{}\n{:?}  ({}/{}, ↑ outer, ↓ inner)\norigin ({:.1}, {:.1})  size {:.1} × {:.1}",
+            info.type_name,
+            info.id,
+            self.selected + 1,
+            self.path.len(),
+            info.layout_rect.x0,
+            info.layout_rect.y0,
+            info.layout_rect.width(),
+            info.layout_rect.height(),
+        );
+        let insets = info.paint_insets;
+        if insets != Insets::ZERO {
+            text += &format!(
+                "\npaint insets {:.1} {:.1} {:.1} {:.1}",
+                insets.x0, insets.y0, insets.x1, insets.y1
+            );
+        }
+        if let Some(snapshot) = &info.snapshot {
+            let mut shown: String = snapshot.chars().take(MAX_SNAPSHOT).collect();
+            if shown.len() < snapshot.len() {
+                shown.push('…');
+            }
+            text += &format!("\ndata {}", shown);
+        }
+        if self.pinned {
+            text += "\npinned, click to follow the mouse";
+        }
+
+        let size = ctx.size();
+        let layout = ctx
+            .text()
+            .new_text_layout(text)
+            .font(FontFamily::MONOSPACE, 11.0)
+            .text_color(Color::WHITE)
+            .max_width((size.width - PANEL_PADDING * 4.0).max(0.0))
+            .build()
+            .unwrap();
+        let panel_size = layout.size() + Size::new(PANEL_PADDING * 2.0, PANEL_PADDING * 2.0);
+        // under the widget when there's room, else over it, and inside the window
+        let mut origin = Point::new(rect.x0, rect.y1 + 4.0);
+        if origin.y + panel_size.height > size.height {
+            origin.y = rect.y0 - 4.0 - panel_size.height;
+        }
+        origin.x = origin.x.min(size.width - panel_size.width).max(0.0);
+        origin.y = origin.y.min(size.height - panel_size.height).max(0.0);
+        let panel = Rect::from_origin_size(origin, panel_size).to_rounded_rect(4.0);
+        ctx.fill(panel, &Color::rgba8(0x20, 0x20, 0x20, 0xe6));
+        ctx.draw_text(&layout, origin + Vec2::new(PANEL_PADDING, PANEL_PADDING));
+    }
+}
+
+impl<T: Data> Widget<T> for Inspector<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.window_origin = ctx.window_origin();
+        match event {
+            Event::Command(cmd) if cmd.is(TOGGLE_INSPECTOR) => {
+                self.set_inspecting(ctx, !self.inspecting);
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if self.hotkey.matches(key) => {
+                self.set_inspecting(ctx, !self.inspecting);
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if self.inspecting => {
+                match &key.key {
+                    KbKey::ArrowUp if self.selected + 1 < self.path.len() => self.selected += 1,
+                    KbKey::ArrowDown if self.selected > 0 => self.selected -= 1,
+                    KbKey::Escape => self.set_inspecting(ctx, false),
+                    _ => {}
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(notification) if notification.is(INSPECTED) => {
+                if self.inspecting && !self.pinned {
+                    self.path.push(notification.get(INSPECTED).unwrap().clone());
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseDown(_) | Event::MouseUp(_) | Event::Wheel(_) if self.inspecting => {
+                if let Event::MouseDown(_) = event {
+                    self.pinned = !self.pinned && !self.path.is_empty();
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(_) if self.inspecting && !self.pinned => {
+                // the inspectable widgets under the mouse report themselves again
+                self.path.clear();
+                self.selected = 0;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+        let env = self.child_env(env);
+        self.child.event(ctx, event, data, &env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.child.lifecycle(ctx, event, data, &env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.child.update(ctx, data, &env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let env = self.child_env(env);
+        let size = self.child.layout(ctx, bc, data, &env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.child.paint(ctx, data, &child_env);
+        if self.inspecting {
+            self.paint_overlay(ctx, env);
+        }
+    }
+}
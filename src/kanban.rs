@@ -0,0 +1,667 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A board of columns holding cards, which can be dragged within and between the columns.
+
+use druid::im::Vector;
+use druid::widget::prelude::*;
+use druid::{theme, Color, KeyOrValue, Point, Rect, Selector, TextLayout, Vec2, WidgetPod};
+
+/// Submitted as a notification by a [`KanbanBoard`] after a card was dragged to another place.
+pub const CARD_MOVED: Selector<KanbanMove> =
+    Selector::new("druid-widget-nursery.kanban.card-moved");
+/// Submitted as a notification by a [`KanbanBoard`] after a card was added to a column, with the
+/// column and the index of the card.
+pub const CARD_ADDED: Selector<(usize, usize)> =
+    Selector::new("druid-widget-nursery.kanban.card-added");
+
+/// How far the mouse has to move with the button pressed before a drag starts.
+const DRAG_THRESHOLD: f64 = 4.0;
+const PADDING: f64 = 8.0;
+const CARD_SPACING: f64 = 8.0;
+const ADD_ROW_HEIGHT: f64 = 28.0;
+const SCROLL_SPEED: f64 = 1.0;
+const RADIUS: f64 = 6.0;
+
+/// A column of a [`KanbanBoard`].
+#[derive(Debug, Clone)]
+pub struct KanbanColumn<C> {
+    pub title: String,
+    pub cards: Vector<C>,
+}
+
+impl<C: Clone> KanbanColumn<C> {
+    pub fn new(title: impl Into<String>, cards: Vector<C>) -> Self {
+        KanbanColumn {
+            title: title.into(),
+            cards,
+        }
+    }
+}
+
+impl<C: Data> Data for KanbanColumn<C> {
+    fn same(&self, other: &Self) -> bool {
+        self.title.same(&other.title) && self.cards.same(&other.cards)
+    }
+}
+
+/// A card dragged from one place to another, sent with [`CARD_MOVED`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KanbanMove {
+    /// The index of the card in its column before the move.
+    pub card: usize,
+    /// The column the card was taken from.
+    pub from: usize,
+    /// The column the card was dropped in.
+    pub to: usize,
+    /// The index of the card in its new column.
+    pub index: usize,
+}
+
+struct Column<C> {
+    title: TextLayout<String>,
+    cards: Vec<WidgetPod<C, Box<dyn Widget<C>>>>,
+    scroll: f64,
+    /// Where the cards are shown, from the last layout.
+    area: Rect,
+    add_rect: Rect,
+    /// The indices, tops and heights of the cards which are not dragged, in the coordinates of
+    /// the content, from the last layout.
+    slots: Vec<(usize, f64, f64)>,
+}
+
+struct Drag {
+    column: usize,
+    card: usize,
+    /// Where the card was grabbed, from its origin.
+    grab: Vec2,
+    press: Point,
+    mouse: Point,
+    dragging: bool,
+    /// The column and the index the card would be dropped at.
+    target: (usize, usize),
+}
+
+/// A board of columns of cards, like for the tasks of a project.
+///
+/// The cards can be dragged within their column and to other columns. The board moves the card
+/// in the data, then submits a [`CARD_MOVED`] notification, for applications keeping a copy
+/// elsewhere. The columns scroll with the mouse wheel when their cards don't fit, and can have
+/// a "+ Add card" button with [`with_add_card`].
+///
+/// ```
+/// # use druid::im::{vector, Vector};
+/// # use druid::widget::Label;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::kanban::{KanbanBoard, KanbanColumn};
+/// fn board() -> impl Widget<Vector<KanbanColumn<String>>> {
+///     KanbanBoard::new(|| Label::dynamic(|text: &String, _| text.clone()))
+///         .with_add_card(|_| "New task".to_string())
+/// }
+///
+/// fn data() -> Vector<KanbanColumn<String>> {
+///     vector![
+///         KanbanColumn::new("To do", vector!["Write docs".to_string()]),
+///         KanbanColumn::new("Done", Vector::new()),
+///     ]
+/// }
+/// ```
+///
+/// [`with_add_card`]: #method.with_add_card
+pub struct KanbanBoard<C> {
+    make_card: Box<dyn Fn() -> Box<dyn Widget<C>>>,
+    new_card: Option<Box<dyn Fn(usize) -> C>>,
+    columns: Vec<Column<C>>,
+    column_width: KeyOrValue<f64>,
+    spacing: KeyOrValue<f64>,
+    add_label: TextLayout<String>,
+    drag: Option<Drag>,
+    /// The columns whose add button is pressed, and hovered.
+    add_press: Option<usize>,
+    add_hot: Option<usize>,
+    /// Where the dragged card would go, from the last layout.
+    gap: Option<Rect>,
+}
+
+impl<C: Data> KanbanBoard<C> {
+    /// Create a board whose cards are built by `make_card`.
+    pub fn new<W: Widget<C> + 'static>(make_card: impl Fn() -> W + 'static) -> Self {
+        KanbanBoard {
+            make_card: Box::new(move || Box::new(make_card())),
+            new_card: None,
+            columns: Vec::new(),
+            column_width: KeyOrValue::Concrete(260.0),
+            spacing: KeyOrValue::Concrete(12.0),
+            add_label: TextLayout::from_text("+ Add card".to_string()),
+            drag: None,
+            add_press: None,
+            add_hot: None,
+            gap: None,
+        }
+    }
+
+    /// Builder-style method for showing a "+ Add card" button under each column, adding the
+    /// card made by `new_card` from the index of the column.
+    pub fn with_add_card(mut self, new_card: impl Fn(usize) -> C + 'static) -> Self {
+        self.new_card = Some(Box::new(new_card));
+        self
+    }
+
+    /// Builder-style method for specifying the width of the columns.
+    ///
+    /// For the non-builder varient, see [`set_column_width`].
+    ///
+    /// [`set_column_width`]: #method.set_column_width
+    pub fn with_column_width(mut self, width: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_column_width(width);
+        self
+    }
+
+    /// Set the width of the columns, 260 by default.
+    pub fn set_column_width(&mut self, width: impl Into<KeyOrValue<f64>>) {
+        self.column_width = width.into();
+    }
+
+    /// Builder-style method for specifying the space between the columns.
+    ///
+    /// For the non-builder varient, see [`set_spacing`].
+    ///
+    /// [`set_spacing`]: #method.set_spacing
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Set the space between the columns.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+
+    /// Make a column and a card for each of the data, returning whether any were added or
+    /// removed.
+    fn update_children(&mut self, data: &Vector<KanbanColumn<C>>) -> bool {
+        let mut changed = self.columns.len() != data.len();
+        self.columns.truncate(data.len());
+        while self.columns.len() < data.len() {
+            self.columns.push(Column {
+                title: TextLayout::new(),
+                cards: Vec::new(),
+                scroll: 0.0,
+                area: Rect::ZERO,
+                add_rect: Rect::ZERO,
+                slots: Vec::new(),
+            });
+        }
+        for (column, column_data) in self.columns.iter_mut().zip(data.iter()) {
+            if column.title.text() != Some(&column_data.title) {
+                column.title.set_text(column_data.title.clone());
+            }
+            let count = column_data.cards.len();
+            if column.cards.len() != count {
+                changed = true;
+                column.cards.truncate(count);
+                while column.cards.len() < count {
+                    column.cards.push(WidgetPod::new((self.make_card)()));
+                }
+            }
+        }
+        changed
+    }
+
+    /// The column at `x`, the nearest one between columns.
+    fn column_at(&self, x: f64, env: &Env) -> usize {
+        let stride = self.column_width.resolve(env) + self.spacing.resolve(env);
+        let column = ((x + self.spacing.resolve(env) / 2.0) / stride)
+            .floor()
+            .max(0.0) as usize;
+        column.min(self.columns.len().saturating_sub(1))
+    }
+
+    /// The column and the index of the card under `pos`.
+    fn card_at(&self, pos: Point) -> Option<(usize, usize)> {
+        self.columns.iter().enumerate().find_map(|(index, column)| {
+            if !column.area.contains(pos) {
+                return None;
+            }
+            let card = column
+                .cards
+                .iter()
+                .position(|card| card.layout_rect().contains(pos))?;
+            Some((index, card))
+        })
+    }
+
+    /// Where the dragged card would be dropped, with the mouse at `pos`.
+    fn drop_target(&self, pos: Point, env: &Env) -> (usize, usize) {
+        let column_index = self.column_at(pos.x, env);
+        let column = &self.columns[column_index];
+        let y = pos.y - column.area.y0 + column.scroll;
+        let index = column
+            .slots
+            .iter()
+            .filter(|(_, top, height)| top + height / 2.0 < y)
+            .count();
+        (column_index, index)
+    }
+
+    fn drop_card(&mut self, ctx: &mut EventCtx, data: &mut Vector<KanbanColumn<C>>) {
+        let drag = match self.drag.take() {
+            Some(drag) if drag.dragging => drag,
+            _ => return,
+        };
+        let (to, index) = drag.target;
+        // the data may have changed during the drag
+        let valid = drag.card < data.get(drag.column).map_or(0, |column| column.cards.len())
+            && to < data.len();
+        if valid && (drag.column, drag.card) != (to, index) {
+            let card = data[drag.column].cards.remove(drag.card);
+            let index = index.min(data[to].cards.len());
+            data[to].cards.insert(index, card);
+            ctx.submit_notification(CARD_MOVED.with(KanbanMove {
+                card: drag.card,
+                from: drag.column,
+                to,
+                index,
+            }));
+        }
+        ctx.request_layout();
+    }
+}
+
+impl<C: Data> Widget<Vector<KanbanColumn<C>>> for KanbanBoard<C> {
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut Vector<KanbanColumn<C>>,
+        env: &Env,
+    ) {
+        let dragging = self.drag.as_ref().map_or(false, |drag| drag.dragging);
+        // the cards only get the mouse where they are shown, and not during a drag
+        let to_cards = match event {
+            Event::MouseDown(mouse) | Event::Wheel(mouse) => {
+                !dragging
+                    && self
+                        .columns
+                        .iter()
+                        .any(|column| column.area.contains(mouse.pos))
+            }
+            Event::MouseMove(_) => !dragging,
+            _ => true,
+        };
+        if to_cards {
+            for (index, column) in self.columns.iter_mut().enumerate() {
+                let mut cards = data[index].cards.clone();
+                for (card, card_data) in column.cards.iter_mut().zip(cards.iter_mut()) {
+                    card.event(ctx, event, card_data, env);
+                }
+                if !data[index].cards.same(&cards) {
+                    data[index].cards = cards;
+                }
+            }
+        }
+
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                if let Some((column, card)) = self.card_at(mouse.pos) {
+                    let origin = self.columns[column].cards[card].layout_rect().origin();
+                    self.drag = Some(Drag {
+                        column,
+                        card,
+                        grab: mouse.pos - origin,
+                        press: mouse.pos,
+                        mouse: mouse.pos,
+                        dragging: false,
+                        target: (column, card),
+                    });
+                    ctx.set_active(true);
+                } else if self.new_card.is_some() {
+                    self.add_press = self
+                        .columns
+                        .iter()
+                        .position(|column| column.add_rect.contains(mouse.pos));
+                    if self.add_press.is_some() {
+                        ctx.set_active(true);
+                        ctx.set_handled();
+                    }
+                }
+            }
+            Event::MouseMove(mouse) => {
+                let is_dragging = match &mut self.drag {
+                    Some(drag) => {
+                        if (mouse.pos - drag.press).hypot() > DRAG_THRESHOLD {
+                            drag.dragging = true;
+                        }
+                        drag.mouse = mouse.pos;
+                        drag.dragging
+                    }
+                    None => false,
+                };
+                if is_dragging {
+                    let target = self.drop_target(mouse.pos, env);
+                    if let Some(drag) = &mut self.drag {
+                        drag.target = target;
+                    }
+                    // the card follows the mouse, and the others make room for it
+                    ctx.request_layout();
+                    ctx.set_handled();
+                }
+                let add_hot = if self.new_card.is_some() && !is_dragging {
+                    self.columns
+                        .iter()
+                        .position(|column| column.add_rect.contains(mouse.pos))
+                } else {
+                    None
+                };
+                if add_hot != self.add_hot {
+                    self.add_hot = add_hot;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse) => {
+                if self.drag.is_some() {
+                    self.drop_card(ctx, data);
+                    ctx.set_active(false);
+                    ctx.set_handled();
+                }
+                if let Some(column) = self.add_press.take() {
+                    ctx.set_active(false);
+                    let released_on = self.columns.get(column).map(|column| column.add_rect);
+                    if released_on.map_or(false, |rect| rect.contains(mouse.pos)) {
+                        if let Some(new_card) = &self.new_card {
+                            let card = new_card(column);
+                            data[column].cards.push_back(card);
+                            let index = data[column].cards.len() - 1;
+                            ctx.submit_notification(CARD_ADDED.with((column, index)));
+                            // show the new card
+                            self.columns[column].scroll = f64::INFINITY;
+                        }
+                    }
+                    ctx.set_handled();
+                }
+            }
+            Event::Wheel(mouse) if !ctx.is_handled() => {
+                if let Some(column) = self
+                    .columns
+                    .iter_mut()
+                    .find(|column| column.area.contains(mouse.pos))
+                {
+                    column.scroll += mouse.wheel_delta.y * SCROLL_SPEED;
+                    ctx.request_layout();
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &Vector<KanbanColumn<C>>,
+        env: &Env,
+    ) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                if self.update_children(data) {
+                    ctx.children_changed();
+                }
+            }
+            LifeCycle::HotChanged(false) if self.add_hot.is_some() => {
+                self.add_hot = None;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+        for (column, column_data) in self.columns.iter_mut().zip(data.iter()) {
+            for (card, card_data) in column.cards.iter_mut().zip(column_data.cards.iter()) {
+                card.lifecycle(ctx, event, card_data, env);
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &Vector<KanbanColumn<C>>,
+        data: &Vector<KanbanColumn<C>>,
+        env: &Env,
+    ) {
+        for (column, column_data) in self.columns.iter_mut().zip(data.iter()) {
+            for (card, card_data) in column.cards.iter_mut().zip(column_data.cards.iter()) {
+                card.update(ctx, card_data, env);
+            }
+        }
+        if self.update_children(data) {
+            ctx.children_changed();
+        }
+        let mut rebuild = self.add_label.needs_rebuild_after_update(ctx);
+        for column in &mut self.columns {
+            rebuild |= column.title.needs_rebuild_after_update(ctx);
+        }
+        if rebuild {
+            ctx.request_layout();
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &Vector<KanbanColumn<C>>,
+        env: &Env,
+    ) -> Size {
+        let column_width = self.column_width.resolve(env);
+        let spacing = self.spacing.resolve(env);
+        let card_width = (column_width - 2.0 * PADDING).max(0.0);
+        let card_bc = BoxConstraints::new(
+            Size::new(card_width, 0.0),
+            Size::new(card_width, f64::INFINITY),
+        );
+        let add_height = if self.new_card.is_some() {
+            ADD_ROW_HEIGHT
+        } else {
+            0.0
+        };
+        self.add_label.rebuild_if_needed(ctx.text(), env);
+
+        // lay out the cards first, to know how high the columns want to be
+        let mut heights = Vec::with_capacity(self.columns.len());
+        let mut title_height: f64 = 0.0;
+        for (column, column_data) in self.columns.iter_mut().zip(data.iter()) {
+            column.title.rebuild_if_needed(ctx.text(), env);
+            title_height = title_height.max(column.title.size().height);
+            let sizes: Vec<f64> = column
+                .cards
+                .iter_mut()
+                .zip(column_data.cards.iter())
+                .map(|(card, card_data)| card.layout(ctx, &card_bc, card_data, env).height)
+                .collect();
+            heights.push(sizes);
+        }
+        let dragged_height = self
+            .drag
+            .as_ref()
+            .filter(|drag| drag.dragging)
+            .and_then(|drag| heights.get(drag.column)?.get(drag.card).copied());
+
+        let area_top = PADDING + title_height + PADDING;
+        let mut content_heights = Vec::with_capacity(self.columns.len());
+        for (index, (column, sizes)) in self.columns.iter_mut().zip(&heights).enumerate() {
+            // the dragged card leaves its place, and a gap is made where it would go
+            let gap = self
+                .drag
+                .as_ref()
+                .filter(|drag| drag.dragging && drag.target.0 == index)
+                .map(|drag| drag.target.1);
+            column.slots.clear();
+            let mut y = 0.0;
+            for (card, height) in sizes.iter().enumerate() {
+                let dragged = self.drag.as_ref().map_or(false, |drag| {
+                    drag.dragging && (drag.column, drag.card) == (index, card)
+                });
+                if dragged {
+                    continue;
+                }
+                if gap == Some(column.slots.len()) {
+                    y += dragged_height.unwrap_or(0.0) + CARD_SPACING;
+                }
+                column.slots.push((card, y, *height));
+                y += height + CARD_SPACING;
+            }
+            if gap == Some(column.slots.len()) {
+                y += dragged_height.unwrap_or(0.0) + CARD_SPACING;
+            }
+            content_heights.push((y - CARD_SPACING).max(0.0));
+        }
+
+        let width = (self.columns.len() as f64 * (column_width + spacing) - spacing).max(0.0);
+        let height = if bc.max().height.is_finite() {
+            bc.max().height
+        } else {
+            let content = content_heights.iter().copied().fold(0.0, f64::max);
+            area_top + content + PADDING + add_height
+        };
+        let size = bc.constrain(Size::new(width, height));
+
+        self.gap = None;
+        let area_height = (size.height - area_top - PADDING - add_height).max(0.0);
+        for (index, (column, content_height)) in
+            self.columns.iter_mut().zip(content_heights).enumerate()
+        {
+            let x = index as f64 * (column_width + spacing);
+            column.area = Rect::new(
+                x + PADDING,
+                area_top,
+                x + column_width - PADDING,
+                area_top + area_height,
+            );
+            column.add_rect = Rect::new(
+                x + PADDING,
+                area_top + area_height,
+                x + column_width - PADDING,
+                area_top + area_height + add_height,
+            );
+            column.scroll = column.scroll.min(content_height - area_height).max(0.0);
+            for (card, top, _) in &column.slots {
+                column.cards[*card].set_origin(
+                    ctx,
+                    Point::new(column.area.x0, column.area.y0 + top - column.scroll),
+                );
+            }
+            if let (Some(drag), Some(height)) = (&self.drag, dragged_height) {
+                if drag.target.0 == index {
+                    let top = column
+                        .slots
+                        .get(drag.target.1)
+                        .map_or(content_height - height, |(_, top, _)| {
+                            top - height - CARD_SPACING
+                        });
+                    let top = column.area.y0 + top - column.scroll;
+                    self.gap = Some(Rect::new(column.area.x0, top, column.area.x1, top + height));
+                }
+            }
+        }
+
+        if let Some(drag) = self.drag.as_ref().filter(|drag| drag.dragging) {
+            let dragged = self
+                .columns
+                .get_mut(drag.column)
+                .and_then(|column| column.cards.get_mut(drag.card));
+            if let Some(card) = dragged {
+                card.set_origin(ctx, drag.mouse - drag.grab);
+            }
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Vector<KanbanColumn<C>>, env: &Env) {
+        let column_width = self.column_width.resolve(env);
+        let spacing = self.spacing.resolve(env);
+        let height = ctx.size().height;
+        let background = env.get(theme::BACKGROUND_LIGHT);
+        let dragged = self
+            .drag
+            .as_ref()
+            .filter(|drag| drag.dragging)
+            .map(|drag| (drag.column, drag.card));
+
+        for (index, (column, column_data)) in self.columns.iter_mut().zip(data.iter()).enumerate() {
+            let x = index as f64 * (column_width + spacing);
+            let rect = Rect::new(x, 0.0, x + column_width, height);
+            ctx.fill(rect.to_rounded_rect(RADIUS), &background);
+            column.title.draw(ctx, Point::new(x + PADDING, PADDING));
+
+            let area = column.area;
+            let cards = &mut column.cards;
+            ctx.with_save(|ctx| {
+                ctx.clip(area);
+                for (card_index, (card, card_data)) in
+                    cards.iter_mut().zip(column_data.cards.iter()).enumerate()
+                {
+                    if dragged != Some((index, card_index)) {
+                        card.paint(ctx, card_data, env);
+                    }
+                }
+            });
+
+            if self.new_card.is_some() {
+                let add_rect = column.add_rect;
+                if self.add_hot == Some(index) {
+                    ctx.fill(
+                        add_rect.to_rounded_rect(RADIUS),
+                        &env.get(theme::BORDER_LIGHT),
+                    );
+                }
+                let label_size = self.add_label.size();
+                self.add_label.draw(
+                    ctx,
+                    Point::new(
+                        add_rect.x0 + PADDING,
+                        add_rect.center().y - label_size.height / 2.0,
+                    ),
+                );
+            }
+        }
+
+        if let Some(gap) = self.gap {
+            ctx.stroke(
+                gap.inset(-0.5).to_rounded_rect(RADIUS),
+                &env.get(theme::BORDER_DARK),
+                1.0,
+            );
+        }
+
+        if let Some((column, card)) = dragged {
+            let pod = self
+                .columns
+                .get_mut(column)
+                .and_then(|column| column.cards.get_mut(card));
+            let card_data = data.get(column).and_then(|column| column.cards.get(card));
+            if let (Some(pod), Some(card_data)) = (pod, card_data) {
+                let rect = pod.layout_rect();
+                ctx.blurred_rect(
+                    rect + Vec2::new(0.0, 2.0),
+                    6.0,
+                    &Color::rgba8(0, 0, 0, 0x60),
+                );
+                pod.paint(ctx, card_data, env);
+            }
+        }
+    }
+}
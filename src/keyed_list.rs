@@ -0,0 +1,214 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use druid::widget::prelude::*;
+use druid::widget::{Axis, ListIter};
+use druid::{KeyOrValue, Point, Rect, WidgetPod};
+
+type Child<C> = WidgetPod<C, Box<dyn Widget<C>>>;
+pub(crate) type KeyFn<C, K> = Rc<dyn Fn(&C) -> K>;
+
+/// A list like druid's [`List`], matching its children to the items by a key instead of by their
+/// position.
+///
+/// When items are inserted, removed or reordered, the children of the other items keep their
+/// state, like the scroll position, the focus or the text being edited. The keys of the items
+/// should be unique; an item with the key of an earlier item gets a new child.
+///
+/// ```
+/// # use druid::im::Vector;
+/// # use druid::widget::TextBox;
+/// # use druid::{Data, Lens, Widget, WidgetExt};
+/// # use druid_widget_nursery::KeyedList;
+/// #[derive(Clone, Data, Lens)]
+/// struct Todo {
+///     id: u64,
+///     text: String,
+/// }
+///
+/// fn todos() -> impl Widget<Vector<Todo>> {
+///     KeyedList::new(|| TextBox::new().lens(Todo::text), |todo: &Todo| todo.id)
+/// }
+/// ```
+///
+/// [`List`]: druid::widget::List
+pub struct KeyedList<C, K> {
+    closure: Box<dyn Fn() -> Box<dyn Widget<C>>>,
+    key: KeyFn<C, K>,
+    children: Vec<(K, Child<C>)>,
+    axis: Axis,
+    spacing: KeyOrValue<f64>,
+}
+
+impl<C: Data, K: Hash + Eq> KeyedList<C, K> {
+    /// Create a new list, building the child of an item with `closure`, and matching the children
+    /// to the items with the key made by `key`.
+    pub fn new<W: Widget<C> + 'static>(
+        closure: impl Fn() -> W + 'static,
+        key: impl Fn(&C) -> K + 'static,
+    ) -> Self {
+        KeyedList {
+            closure: Box::new(move || Box::new(closure())),
+            key: Rc::new(key),
+            children: Vec::new(),
+            axis: Axis::Vertical,
+            spacing: KeyOrValue::Concrete(0.),
+        }
+    }
+
+    /// Sets the widget to display the list horizontally, not vertically.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self
+    }
+
+    /// Builder-style method for specifying the spacing between the children.
+    ///
+    /// For the non-builder varient, see [`set_spacing`].
+    ///
+    /// [`set_spacing`]: #method.set_spacing
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Set the spacing between the children.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+
+    /// The function making the keys of the items, for [`ListFilter::keyed`].
+    ///
+    /// [`ListFilter::keyed`]: crate::ListFilter::keyed
+    pub(crate) fn key_fn(&self) -> KeyFn<C, K> {
+        self.key.clone()
+    }
+
+    /// Match the children to the items, building children for new keys.
+    ///
+    /// Returns whether the children changed, and which of them were just built.
+    fn update_children(&mut self, data: &impl ListIter<C>) -> (bool, Vec<bool>) {
+        let mut keys = Vec::with_capacity(data.data_len());
+        data.for_each(|child_data, _| keys.push((self.key)(child_data)));
+        if keys.len() == self.children.len()
+            && keys
+                .iter()
+                .zip(&self.children)
+                .all(|(key, (old, _))| key == old)
+        {
+            return (false, vec![false; keys.len()]);
+        }
+
+        let mut old: HashMap<K, Child<C>> = self.children.drain(..).collect();
+        let mut built = Vec::with_capacity(keys.len());
+        for key in keys {
+            let child = match old.remove(&key) {
+                Some(child) => {
+                    built.push(false);
+                    child
+                }
+                None => {
+                    built.push(true);
+                    WidgetPod::new((self.closure)())
+                }
+            };
+            self.children.push((key, child));
+        }
+        (true, built)
+    }
+}
+
+impl<C: Data, K: Hash + Eq, T: ListIter<C>> Widget<T> for KeyedList<C, K> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let mut children = self.children.iter_mut();
+        data.for_each_mut(|child_data, _| {
+            if let Some((_, child)) = children.next() {
+                child.event(ctx, event, child_data, env);
+            }
+        });
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            if self.update_children(data).0 {
+                ctx.children_changed();
+            }
+        }
+
+        let mut children = self.children.iter_mut();
+        data.for_each(|child_data, _| {
+            if let Some((_, child)) = children.next() {
+                child.lifecycle(ctx, event, child_data, env);
+            }
+        });
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let (changed, built) = self.update_children(data);
+        if changed {
+            ctx.children_changed();
+        }
+
+        // the children built just now aren't added yet
+        let mut children = self.children.iter_mut().zip(built);
+        data.for_each(|child_data, _| {
+            if let Some(((_, child), false)) = children.next() {
+                child.update(ctx, child_data, env);
+            }
+        });
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let axis = self.axis;
+        let spacing = self.spacing.resolve(env);
+        let mut minor = axis.minor(bc.min());
+        let mut major_pos = 0.0;
+        let mut paint_rect = Rect::ZERO;
+        let mut children = self.children.iter_mut();
+        let child_bc = axis.constraints(bc, 0., f64::INFINITY);
+        data.for_each(|child_data, _| {
+            let child = match children.next() {
+                Some((_, child)) => child,
+                None => return,
+            };
+            let child_size = child.layout(ctx, &child_bc, child_data, env);
+            let child_pos: Point = axis.pack(major_pos, 0.).into();
+            child.set_origin(ctx, child_pos);
+            paint_rect = paint_rect.union(child.paint_rect());
+            minor = minor.max(axis.minor(child_size));
+            major_pos += axis.major(child_size) + spacing;
+        });
+
+        // correct overshoot at end.
+        major_pos -= spacing;
+
+        let my_size = bc.constrain(Size::from(axis.pack(major_pos, minor)));
+        let insets = paint_rect - my_size.to_rect();
+        ctx.set_paint_insets(insets);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let mut children = self.children.iter_mut();
+        data.for_each(|child_data, _| {
+            if let Some((_, child)) = children.next() {
+                child.paint(ctx, child_data, env);
+            }
+        });
+    }
+}
@@ -0,0 +1,425 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Selecting an area over a widget, freehand, with a rectangle or with a polygon.
+
+use std::sync::Arc;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::BezPath;
+use druid::piet::StrokeStyle;
+use druid::widget::prelude::*;
+use druid::{theme, Lens, LensExt, MouseEvent, Point, Rect, WidgetPod};
+
+/// Points closer than this to the last point of a freehand lasso are skipped.
+const MIN_DISTANCE: f64 = 2.0;
+
+/// How a [`Lasso`] draws its shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum LassoMode {
+    /// The shape follows the mouse while it is dragged.
+    Freehand,
+    /// A rectangle between where the mouse was pressed and released.
+    Rectangle,
+    /// Each click adds a vertex, and a double click closes the polygon.
+    Polygon,
+}
+
+/// Whether a region of a [`LassoShape`] is added to the regions before it or taken out of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum LassoOp {
+    Add,
+    Subtract,
+}
+
+/// A selection drawn with a [`Lasso`], in the coordinates of its child.
+///
+/// It is made of closed polygons, each added to or subtracted from the ones drawn before it.
+#[derive(Debug, Clone, Data)]
+pub struct LassoShape {
+    regions: Arc<Vec<(LassoOp, Vec<Point>)>>,
+}
+
+impl LassoShape {
+    /// The shape through `vertices`, closed from the last one to the first one.
+    pub fn new(vertices: Vec<Point>) -> Self {
+        LassoShape {
+            regions: Arc::new(vec![(LassoOp::Add, vertices)]),
+        }
+    }
+
+    /// This shape with the polygon through `vertices` added or subtracted.
+    pub fn combine(&self, op: LassoOp, vertices: Vec<Point>) -> Self {
+        let mut regions = (*self.regions).clone();
+        regions.push((op, vertices));
+        LassoShape {
+            regions: Arc::new(regions),
+        }
+    }
+
+    /// The polygons of the shape, in the order they were combined.
+    pub fn regions(&self) -> &[(LassoOp, Vec<Point>)] {
+        &self.regions
+    }
+
+    /// The smallest rect holding the shape.
+    pub fn bounding_box(&self) -> Rect {
+        let mut points = self
+            .regions
+            .iter()
+            .filter(|(op, _)| *op == LassoOp::Add)
+            .flat_map(|(_, vertices)| vertices);
+        let first = match points.next() {
+            Some(point) => Rect::from_points(*point, *point),
+            None => return Rect::ZERO,
+        };
+        points.fold(first, |rect, point| rect.union_pt(*point))
+    }
+
+    /// Whether `point` is inside the shape. Parts of a polygon crossing itself are inside every
+    /// other time, like with an even-odd fill.
+    pub fn contains(&self, point: Point) -> bool {
+        self.regions
+            .iter()
+            .fold(false, |inside, (op, vertices)| match op {
+                LassoOp::Add => inside || polygon_contains(vertices, point),
+                LassoOp::Subtract => inside && !polygon_contains(vertices, point),
+            })
+    }
+
+    /// The indices of the `points` inside the shape.
+    ///
+    /// ```
+    /// # use druid::Point;
+    /// # use druid_widget_nursery::lasso::LassoShape;
+    /// let triangle = LassoShape::new(vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    /// ]);
+    /// let points = [Point::new(2.0, 2.0), Point::new(8.0, 8.0), Point::new(1.0, 7.0)];
+    /// assert_eq!(triangle.select(points), vec![0, 2]);
+    /// ```
+    pub fn select(&self, points: impl IntoIterator<Item = Point>) -> Vec<usize> {
+        points
+            .into_iter()
+            .enumerate()
+            .filter(|(_, point)| self.contains(*point))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+fn polygon_contains(vertices: &[Point], point: Point) -> bool {
+    if vertices.len() < 3 {
+        return false;
+    }
+    let mut inside = false;
+    let mut previous = vertices[vertices.len() - 1];
+    for &vertex in vertices {
+        // count the edges crossed by a ray going right from the point
+        if (vertex.y > point.y) != (previous.y > point.y) {
+            let x =
+                vertex.x + (point.y - vertex.y) / (previous.y - vertex.y) * (previous.x - vertex.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+        previous = vertex;
+    }
+    inside
+}
+
+/// Add the polygon through `vertices` to `path`, closed when `close` is true. With `clockwise`,
+/// its vertices go clockwise on the screen, or counterclockwise, so that filling with the nonzero
+/// rule leaves out the parts of added polygons covered by subtracted ones.
+fn add_polygon(path: &mut BezPath, vertices: &[Point], close: bool, clockwise: Option<bool>) {
+    let area: f64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+    let reverse = clockwise.map_or(false, |clockwise| (area > 0.0) != clockwise);
+    let mut vertices: Box<dyn Iterator<Item = &Point>> = if reverse {
+        Box::new(vertices.iter().rev())
+    } else {
+        Box::new(vertices.iter())
+    };
+    if let Some(first) = vertices.next() {
+        path.move_to(*first);
+        for vertex in vertices {
+            path.line_to(*vertex);
+        }
+        if close {
+            path.close_path();
+        }
+    }
+}
+
+/// The mode and the shape of a [`Lasso`].
+#[derive(Debug, Clone, Data, Lens)]
+pub struct LassoState {
+    pub mode: LassoMode,
+    /// The last shape drawn, or `None` when nothing is selected.
+    pub shape: Option<LassoShape>,
+}
+
+impl LassoState {
+    pub fn new(mode: LassoMode) -> Self {
+        LassoState { mode, shape: None }
+    }
+}
+
+/// A widget drawing a selection shape over its child with the left mouse button.
+///
+/// The mode is taken from the [`LassoState`] of the data, and each shape drawn replaces its shape
+/// when it is closed; [`LassoShape::select`] finds the items under it. Escape drops the shape
+/// being drawn, or the selection. Other events go to the child.
+///
+/// Holding Shift when starting a shape adds it to the selection, and holding Alt subtracts it.
+/// While drawing, Shift keeps freehand and polygon edges horizontal or vertical, and Ctrl snaps
+/// the vertices to the grid set with [`with_grid`].
+///
+/// ```
+/// # use druid::{Data, Lens, Widget};
+/// # use druid_widget_nursery::lasso::{Lasso, LassoState};
+/// # use druid_widget_nursery::Canvas;
+/// #[derive(Clone, Data, Lens)]
+/// struct Board {
+///     lasso: LassoState,
+/// }
+///
+/// fn board() -> impl Widget<Board> {
+///     Lasso::new(Canvas::new(), Board::lasso).with_grid(10.0)
+/// }
+/// ```
+///
+/// [`with_grid`]: #method.with_grid
+pub struct Lasso<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    get_state: Box<dyn Fn(&T) -> LassoState>,
+    set_shape: Box<dyn Fn(&mut T, Option<LassoShape>)>,
+    grid: Option<f64>,
+    /// The vertices of the shape being drawn.
+    drawing: Vec<Point>,
+    /// How the shape being drawn is combined with the selection, or `None` to replace it.
+    op: Option<LassoOp>,
+    /// Where the next vertex of a polygon goes, following the mouse.
+    next_vertex: Option<Point>,
+}
+
+impl<T: Data> Lasso<T> {
+    /// Draw over `child`, with the state of `lens`.
+    pub fn new(
+        child: impl Widget<T> + 'static,
+        lens: impl Lens<T, LassoState> + Clone + 'static,
+    ) -> Self {
+        let shape = lens.clone().then(LassoState::shape);
+        Lasso {
+            child: WidgetPod::new(Box::new(child)),
+            get_state: Box::new(move |data| lens.get(data)),
+            set_shape: Box::new(move |data, value| shape.put(data, value)),
+            grid: None,
+            drawing: Vec::new(),
+            op: None,
+            next_vertex: None,
+        }
+    }
+
+    /// Builder-style method for setting the size of the grid vertices snap to while Ctrl is held.
+    ///
+    /// For the non-builder varient, see [`set_grid`].
+    ///
+    /// [`set_grid`]: #method.set_grid
+    pub fn with_grid(mut self, size: f64) -> Self {
+        self.set_grid(Some(size));
+        self
+    }
+
+    /// Set the size of the grid vertices snap to, or `None` for no snapping.
+    pub fn set_grid(&mut self, size: Option<f64>) {
+        self.grid = size.filter(|size| *size > 0.0);
+    }
+
+    /// Where a vertex goes for `mouse`, snapped and constrained by the modifiers.
+    fn place(&self, mouse: &MouseEvent, mode: LassoMode) -> Point {
+        let mut pos = mouse.pos;
+        if let (true, Some(grid)) = (mouse.mods.ctrl(), self.grid) {
+            pos = Point::new((pos.x / grid).round() * grid, (pos.y / grid).round() * grid);
+        }
+        if let (true, Some(last)) = (mouse.mods.shift(), self.drawing.last()) {
+            if mode != LassoMode::Rectangle {
+                if (pos.x - last.x).abs() > (pos.y - last.y).abs() {
+                    pos.y = last.y;
+                } else {
+                    pos.x = last.x;
+                }
+            }
+        }
+        pos
+    }
+
+    fn finish(&mut self, ctx: &mut EventCtx, data: &mut T) {
+        let vertices = std::mem::take(&mut self.drawing);
+        ctx.request_paint();
+        if vertices.len() < 3 {
+            return;
+        }
+        let shape = match (self.op, (self.get_state)(data).shape) {
+            (Some(op), Some(shape)) => shape.combine(op, vertices),
+            // there's nothing to subtract from
+            (Some(LassoOp::Subtract), None) => return,
+            _ => LassoShape::new(vertices),
+        };
+        (self.set_shape)(data, Some(shape));
+    }
+}
+
+impl<T: Data> Widget<T> for Lasso<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let mode = (self.get_state)(data).mode;
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.request_focus();
+                if self.drawing.is_empty() {
+                    self.op = if mouse.mods.shift() {
+                        Some(LassoOp::Add)
+                    } else if mouse.mods.alt() {
+                        Some(LassoOp::Subtract)
+                    } else {
+                        None
+                    };
+                }
+                let pos = self.place(mouse, mode);
+                match mode {
+                    LassoMode::Freehand => self.drawing = vec![pos],
+                    LassoMode::Rectangle => self.drawing = vec![pos; 4],
+                    LassoMode::Polygon if mouse.count >= 2 => {
+                        // the first click of the double click added the last vertex
+                        self.finish(ctx, data);
+                        return;
+                    }
+                    LassoMode::Polygon => self.drawing.push(pos),
+                }
+                ctx.set_active(true);
+                ctx.request_paint();
+                return;
+            }
+            Event::MouseMove(mouse) => {
+                let pos = self.place(mouse, mode);
+                self.next_vertex = Some(pos);
+                if ctx.is_active() {
+                    match mode {
+                        LassoMode::Freehand => {
+                            if self
+                                .drawing
+                                .last()
+                                .map_or(true, |last| last.distance(pos) >= MIN_DISTANCE)
+                            {
+                                self.drawing.push(pos);
+                            }
+                        }
+                        LassoMode::Rectangle => {
+                            if let Some(&anchor) = self.drawing.first() {
+                                self.drawing = vec![
+                                    anchor,
+                                    Point::new(pos.x, anchor.y),
+                                    pos,
+                                    Point::new(anchor.x, pos.y),
+                                ];
+                            }
+                        }
+                        LassoMode::Polygon => {}
+                    }
+                    ctx.request_paint();
+                } else if mode == LassoMode::Polygon && !self.drawing.is_empty() {
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse) if mouse.button.is_left() && ctx.is_active() => {
+                ctx.set_active(false);
+                if mode != LassoMode::Polygon {
+                    self.finish(ctx, data);
+                }
+                return;
+            }
+            Event::KeyDown(key) if key.key == Key::Escape && ctx.has_focus() => {
+                if self.drawing.is_empty() {
+                    (self.set_shape)(data, None);
+                } else {
+                    self.drawing.clear();
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.child.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let old_state = (self.get_state)(old_data);
+        let state = (self.get_state)(data);
+        if old_state.mode != state.mode {
+            self.drawing.clear();
+            ctx.request_paint();
+        }
+        if !old_state.shape.same(&state.shape) {
+            ctx.request_paint();
+        }
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+
+        let color = env.get(theme::PRIMARY_LIGHT);
+        let fill = color.with_alpha(0.15);
+        let dashes = StrokeStyle::new().dash_pattern(&[4.0, 4.0]);
+        if let Some(shape) = (self.get_state)(data).shape {
+            let mut path = BezPath::new();
+            for (op, vertices) in shape.regions() {
+                add_polygon(&mut path, vertices, true, Some(*op == LassoOp::Add));
+            }
+            ctx.fill(&path, &fill);
+            ctx.stroke_styled(&path, &color, 1.0, &dashes);
+        }
+        if !self.drawing.is_empty() {
+            let mode = (self.get_state)(data).mode;
+            let mut vertices = self.drawing.clone();
+            // the next edge of a polygon follows the mouse
+            if let (LassoMode::Polygon, Some(next)) = (mode, self.next_vertex) {
+                vertices.push(next);
+            }
+            let mut path = BezPath::new();
+            add_polygon(&mut path, &vertices, mode != LassoMode::Polygon, None);
+            ctx.fill_even_odd(&path, &fill);
+            ctx.stroke_styled(&path, &color, 1.0, &dashes);
+        }
+    }
+}
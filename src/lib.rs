@@ -16,79 +16,240 @@
 
 #![allow(clippy::new_ret_no_self)]
 
+pub mod accessibility;
 pub mod animation;
+mod ansi_text;
 mod autofocus;
+mod avatar;
+mod badged;
 mod canvas;
-mod computed;
+mod card;
+pub mod chart;
+pub mod clipboard;
+pub mod code_view;
+pub mod collapsible;
+mod color_scheme;
+mod command_logger;
+pub mod command_palette;
+pub mod computed;
 mod configure_env;
 mod context_traits;
+mod date_picker;
+mod debounce;
+pub mod drag_drop;
+mod drawing_canvas;
 pub mod dropdown;
 mod dropdown_select;
 mod dyn_lens;
 mod dynamic_sized_box;
 pub mod enum_switcher;
+pub mod every;
+mod expandable_text;
+pub mod file_drop_zone;
+pub mod focus_ring;
+pub mod focus_scope;
+pub mod form;
+mod gauge;
+mod heatmap;
+mod input_mask;
+pub mod inspector;
+pub mod kanban;
+mod keyed_list;
+pub mod lasso;
 mod list_select;
+pub mod log_view;
 #[macro_use]
 mod macros;
 mod advanced_slider;
 mod mask;
+mod masonry;
+mod maybe;
+pub mod modal;
 mod multi_value;
 pub mod navigator;
+mod number_input;
 mod on_change;
 mod on_cmd;
 mod on_monitor;
+pub mod overscroll;
 pub mod prism;
 mod progress_bar;
+pub mod progress_indicator;
+mod rating;
+pub mod request;
+mod resizable;
+pub mod rich_label;
+pub mod scroll_controller;
+pub mod search;
+mod segmented_control;
 mod separator;
+mod skeleton;
+mod sparkline;
 pub mod splits;
 mod stack;
 pub mod stack_tooltip;
+mod step_input;
+mod style;
 pub mod table;
+pub mod text_area;
+mod theme_editor;
 pub mod theme_loader;
+mod theme_scope;
+mod time_picker;
+mod timeline;
 mod titlebar;
+mod toggle_switch;
 mod tooltip;
+mod tooltip_host;
 pub mod tree;
 mod versioned;
 pub mod wedge;
 mod widget_ext;
+pub mod wizard;
 pub mod wrap;
+pub mod zoom_box;
 
+#[cfg(feature = "material-icons")]
+mod icon_button;
 #[cfg(feature = "material-icons")]
 pub mod material_icons;
+#[cfg(feature = "material-icons")]
+mod toolbar;
+
+#[cfg(feature = "serde")]
+pub mod persisted;
+
+#[cfg(feature = "pulldown-cmark")]
+pub mod markdown;
+
+#[cfg(feature = "image")]
+pub mod image_viewer;
 
-#[cfg(feature = "async")]
+#[cfg(feature = "svg")]
+mod svg;
+
+#[cfg(feature = "barcode")]
+mod barcode;
+
+#[cfg(any(feature = "async", feature = "thread-pool"))]
 mod future_widget;
 
 #[cfg(feature = "hot-reload")]
 pub mod hot_reload;
+pub mod hotkeys;
 mod list_filter;
 
-pub use advanced_slider::AdvancedSlider;
+pub use accessibility::{
+    A11yLabel, AccessState, Accessible, AnnouncementHost, Politeness, Role, ANNOUNCE,
+};
+pub use advanced_slider::{AdvancedSlider, RangeSlider};
+pub use ansi_text::{AnsiBuffer, AnsiText};
 pub use autofocus::AutoFocus;
-pub use canvas::{Canvas, CanvasLayout, CanvasWrap};
+pub use avatar::{Avatar, AvatarShape};
+pub use badged::Badged;
+pub use canvas::{Canvas, CanvasLayout, CanvasViewport, CanvasWrap};
+pub use card::Card;
+pub use chart::{BarChart, LineChart, PieChart};
+pub use clipboard::{ClipboardController, CopyButton, PasteTarget};
+pub use code_view::CodeView;
+pub use collapsible::{Accordion, Collapsible};
+pub use color_scheme::{ColorScheme, SystemColorScheme};
+pub use command_logger::{CommandLogger, CommandLoggerDelegate};
+pub use command_palette::{CommandPalette, CommandRegistry};
 pub use computed::ComputedWidget;
 pub use configure_env::configure_env;
 pub use context_traits::{AnyCtx, CommandCtx, CursorCtx, LaidOutCtx, RequestCtx};
+pub use date_picker::{Calendar, DatePicker, DateValue};
+pub use debounce::{OnChangeDebounced, OnDebounce, OnThrottle};
+pub use drag_drop::{DragDropHost, DragSource, DropTarget};
+pub use drawing_canvas::{Drawing, DrawingCanvas, DrawingTool, Stroke};
 pub use dropdown::Dropdown;
 pub use dropdown_select::DropdownSelect;
 pub use dyn_lens::DynLens;
 pub use dynamic_sized_box::DynamicSizedBox;
+pub use every::Every;
+pub use expandable_text::ExpandableText;
+pub use file_drop_zone::FileDropZone;
+pub use focus_scope::{FocusScope, TabStop};
+pub use form::{Form, Validated, Validator};
+pub use gauge::Gauge;
+pub use heatmap::Heatmap;
+pub use input_mask::{InputMask, MaskError};
+pub use inspector::{Inspectable, Inspector};
+pub use kanban::{KanbanBoard, KanbanColumn};
+pub use keyed_list::KeyedList;
+pub use lasso::{Lasso, LassoMode, LassoOp, LassoShape, LassoState};
 pub use list_filter::{FilterIter, ListFilter};
 pub use list_select::ListSelect;
+pub use log_view::{LogBuffer, LogView};
 pub use mask::Mask;
-pub use multi_value::{MultiCheckbox, MultiRadio};
+pub use masonry::Masonry;
+pub use maybe::Maybe;
+pub use modal::Modal;
+pub use multi_value::{CheckboxGroup, MultiCheckbox, MultiRadio};
+pub use number_input::{NumberInput, NumberValue};
 pub use on_change::OnChange;
 pub use on_cmd::OnCmd;
 pub use on_monitor::OnMonitor;
+pub use overscroll::Overscroll;
 pub use progress_bar::ProgressBar;
-pub use separator::{Orientation, Separator};
-pub use stack::{Stack, StackChildParams, StackChildPosition};
+pub use progress_indicator::{ActivitySpinner, CircularProgress};
+pub use rating::{Rating, RatingValue};
+pub use resizable::Resizable;
+pub use rich_label::{RichLabel, Span};
+pub use scroll_controller::{ScrollController, ScrollTarget};
+pub use search::{HighlightMatches, SearchBox};
+pub use segmented_control::SegmentedControl;
+pub use separator::{LineStyle, Orientation, Separator};
+pub use skeleton::{Skeleton, SkeletonShape};
+pub use sparkline::{BarSparkline, Sparkline, WinLoss};
+pub use stack::{
+    Stack, StackChildParams, StackChildPosition, STACK_LOWER_CHILD, STACK_RAISE_CHILD,
+    STACK_SET_Z_INDEX,
+};
+pub use style::Style;
+pub use text_area::TextArea;
+pub use theme_editor::ThemeEditor;
+pub use theme_scope::{Theme, ThemeScope, SET_THEME};
+pub use time_picker::{DurationInput, TimePicker, TimeValue};
+pub use timeline::Timeline;
 pub use titlebar::TitleBar;
-pub use tooltip::TooltipController;
+pub use toggle_switch::ToggleSwitch;
+pub use tooltip::{TooltipController, TooltipPlacement};
+pub use tooltip_host::TooltipHost;
 pub use tree::{Tree, TreeNode, TREE_NODE_REMOVE};
 pub use versioned::Versioned;
 pub use wedge::Wedge;
 pub use widget_ext::WidgetExt;
+pub use wizard::Wizard;
+pub use zoom_box::ZoomBox;
+
+#[cfg(any(feature = "async", feature = "thread-pool"))]
+pub use future_widget::{FutureWidget, OnChangeAsync, StreamWidget};
+
+pub use hotkeys::{HotkeyController, Keymap};
+
+#[cfg(feature = "material-icons")]
+pub use icon_button::IconButton;
+#[cfg(feature = "material-icons")]
+pub use toolbar::Toolbar;
+
+#[cfg(feature = "serde")]
+pub use persisted::Persisted;
+
+#[cfg(feature = "pulldown-cmark")]
+pub use markdown::Markdown;
+
+#[cfg(feature = "image")]
+pub use image_viewer::ImageViewer;
+
+#[cfg(feature = "svg")]
+pub use svg::{Svg, SvgData};
+
+#[cfg(feature = "barcode")]
+pub use barcode::{Barcode, ErrorCorrection, Symbology};
 
-#[cfg(feature = "async")]
-pub use future_widget::FutureWidget;
+#[cfg(feature = "chrono")]
+pub use date_picker::ChronoDate;
+#[cfg(feature = "chrono")]
+pub use time_picker::ChronoTime;
@@ -4,8 +4,11 @@ use druid::{
     BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
     UpdateCtx, Widget,
 };
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
 use std::marker::PhantomData;
-use std::ops::Range;
+
+use crate::KeyedList;
 
 #[derive(Data, Clone)]
 pub struct FilterIter<I> {
@@ -55,9 +58,43 @@ impl<T: Data, I: ListIter<T>> ListIter<T> for FilterIter<I> {
     }
 }
 
-type FilterUpdate<I, D> = dyn Fn(&mut Vector<usize>, usize, &I, Range<usize>, &D);
+/// Filters the list, with the filter options and whether they changed since the last call.
+type FilterUpdate<I, D> = dyn FnMut(&I, &D, bool) -> Vector<usize>;
+
+/// The last filter result of an item.
+struct Filtered<T> {
+    item: T,
+    accepted: bool,
+}
+
+impl<T: Data> Filtered<T> {
+    fn new(item: &T, filter: impl FnOnce(&T) -> bool) -> Self {
+        Filtered {
+            item: item.clone(),
+            accepted: filter(item),
+        }
+    }
+
+    /// Filter `item` again if it isn't the item filtered last, or if `refilter`.
+    fn update(&mut self, item: &T, refilter: bool, filter: impl FnOnce(&T) -> bool) -> bool {
+        if refilter || !self.item.same(item) {
+            *self = Filtered::new(item, filter);
+        }
+        self.accepted
+    }
+}
 
 /// A widget which filters a list for its inner widget.
+///
+/// The filter is only evaluated again for the items that changed, unless the filter options
+/// changed. With [`new`], items are matched to their last result by position, and so are the
+/// children of the inner widget, which then show other items when the filter changes. With
+/// [`keyed`], both are matched by the key of a [`KeyedList`]: children keep their state while
+/// items are hidden and shown, and inserting or removing items doesn't filter the items after
+/// them again.
+///
+/// [`new`]: #method.new
+/// [`keyed`]: #method.keyed
 pub struct ListFilter<D, T, I> {
     accepted: Vector<usize>,
     filter_update: Box<FilterUpdate<I, D>>,
@@ -69,22 +106,74 @@ impl<D: Data, T: Data, I: ListIter<T>> ListFilter<D, T, I> {
     pub fn new(
         inner: impl Widget<FilterIter<I>> + 'static,
         filter: impl Fn(&T, &D) -> bool + 'static,
+    ) -> Self {
+        let mut cache: Vec<Filtered<T>> = Vec::new();
+        Self::with_filter_update(inner, move |elements, filter_option, refilter| {
+            let mut accepted = Vector::new();
+            elements.for_each(|element, index| {
+                let filter = |element: &T| filter(element, filter_option);
+                let accept = match cache.get_mut(index) {
+                    Some(filtered) => filtered.update(element, refilter, filter),
+                    None => {
+                        cache.push(Filtered::new(element, filter));
+                        cache[index].accepted
+                    }
+                };
+                if accept {
+                    accepted.push_back(index);
+                }
+            });
+            cache.truncate(elements.data_len());
+            accepted
+        })
+    }
+
+    /// Filter the items of a [`KeyedList`], matching them to their last filter result by the
+    /// key of the list.
+    pub fn keyed<K: Hash + Eq + 'static>(
+        inner: KeyedList<T, K>,
+        filter: impl Fn(&T, &D) -> bool + 'static,
+    ) -> Self {
+        let key = inner.key_fn();
+        // with the update in which each key was last seen, to drop the removed items
+        let mut cache: HashMap<K, (Filtered<T>, u64)> = HashMap::new();
+        let mut generation = 0;
+        Self::with_filter_update(inner, move |elements, filter_option, refilter| {
+            generation += 1;
+            let mut accepted = Vector::new();
+            elements.for_each(|element, index| {
+                let filter = |element: &T| filter(element, filter_option);
+                let accept = match cache.entry(key(element)) {
+                    Entry::Occupied(mut entry) => {
+                        let (filtered, seen) = entry.get_mut();
+                        *seen = generation;
+                        filtered.update(element, refilter, filter)
+                    }
+                    Entry::Vacant(entry) => {
+                        entry
+                            .insert((Filtered::new(element, filter), generation))
+                            .0
+                            .accepted
+                    }
+                };
+                if accept {
+                    accepted.push_back(index);
+                }
+            });
+            if cache.len() > elements.data_len() {
+                cache.retain(|_, (_, seen)| *seen == generation);
+            }
+            accepted
+        })
+    }
+
+    fn with_filter_update(
+        inner: impl Widget<FilterIter<I>> + 'static,
+        filter_update: impl FnMut(&I, &D, bool) -> Vector<usize> + 'static,
     ) -> Self {
         Self {
             accepted: Vector::new(),
-            filter_update: Box::new(
-                move |indices, mut insert_index, elements, update_range, filter_option| {
-                    elements.for_each(|element, index| {
-                        if index >= update_range.start
-                            && index < update_range.end
-                            && filter(element, filter_option)
-                        {
-                            indices.insert(insert_index, index);
-                            insert_index += 1;
-                        }
-                    })
-                },
-            ),
+            filter_update: Box::new(filter_update),
             inner: Box::new(inner),
             phantom: PhantomData,
         }
@@ -100,13 +189,7 @@ impl<T: Data, D: Data, I: ListIter<T>> Widget<(I, D)> for ListFilter<D, T, I> {
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &(I, D), env: &Env) {
         if let LifeCycle::WidgetAdded = event {
-            (self.filter_update)(
-                &mut self.accepted,
-                0,
-                &data.0,
-                0..(data.0.data_len()),
-                &data.1,
-            );
+            self.accepted = (self.filter_update)(&data.0, &data.1, true);
         }
         let inner_data = FilterIter::new(data.0.clone(), self.accepted.clone());
         self.inner.lifecycle(ctx, event, &inner_data, env);
@@ -116,15 +199,8 @@ impl<T: Data, D: Data, I: ListIter<T>> Widget<(I, D)> for ListFilter<D, T, I> {
         let old_inner = FilterIter::new(old_data.0.clone(), self.accepted.clone());
 
         if !old_data.same(data) {
-            //TODO: do real diffing here
-            self.accepted.clear();
-            (self.filter_update)(
-                &mut self.accepted,
-                0,
-                &data.0,
-                0..(data.0.data_len()),
-                &data.1,
-            );
+            let refilter = !old_data.1.same(&data.1);
+            self.accepted = (self.filter_update)(&data.0, &data.1, refilter);
         }
         let inner_data = FilterIter::new(data.0.clone(), self.accepted.clone());
         self.inner.update(ctx, &old_inner, &inner_data, env);
@@ -21,6 +21,8 @@ use druid::{
     LinearGradient, PaintCtx, RenderContext, Size, UnitPoint, UpdateCtx, Widget,
 };
 
+use crate::focus_ring::{self, FocusVisible};
+
 // added padding between the edges of the widget and the text.
 const LABEL_X_PADDING: f64 = 8.0;
 
@@ -30,6 +32,7 @@ pub struct ListSelect<T> {
     widget: Flex<T>,
     /// A controller handling item selection.
     controller: ListSelectController<T>,
+    focus: FocusVisible,
 }
 
 impl<T: Data> ListSelect<T> {
@@ -50,6 +53,7 @@ impl<T: Data> ListSelect<T> {
                 variants,
                 action: None,
             },
+            focus: FocusVisible::new(),
         }
     }
 
@@ -64,17 +68,23 @@ impl<T: Data> ListSelect<T> {
                 variants,
                 action: Some(Box::new(f)),
             },
+            focus: self.focus,
         }
     }
 }
 
 impl<T: Data> Widget<T> for ListSelect<T> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.focus.event(ctx, event);
         self.controller
             .event(&mut self.widget, ctx, event, data, env)
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        self.focus.lifecycle(ctx, event);
         self.controller
             .lifecycle(&mut self.widget, ctx, event, data, env)
     }
@@ -89,7 +99,11 @@ impl<T: Data> Widget<T> for ListSelect<T> {
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        self.widget.paint(ctx, data, env)
+        self.widget.paint(ctx, data, env);
+        if self.focus.is_visible() {
+            let rect = ctx.size().to_rect();
+            focus_ring::paint(ctx, rect, 0.0, env);
+        }
     }
 }
 
@@ -0,0 +1,489 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A view of a large and growing log, painting only the visible lines.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use druid::keyboard_types::Key;
+use druid::piet::{Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, FontDescriptor, FontFamily, KeyOrValue, Point, Rect, Selector};
+use log::{Level, LevelFilter};
+
+/// Shows the lines containing the text, ignoring case, or all the lines for an empty text.
+pub const SET_FILTER: Selector<String> = Selector::new("druid-widget-nursery.log-view.set-filter");
+/// Shows the lines of the level or more severe.
+pub const SET_MIN_LEVEL: Selector<LevelFilter> =
+    Selector::new("druid-widget-nursery.log-view.set-min-level");
+/// Turns following the last line on or off.
+pub const SET_FOLLOW: Selector<bool> = Selector::new("druid-widget-nursery.log-view.set-follow");
+/// Notification sent when following the last line is turned on or off, by the user or by
+/// [`SET_FOLLOW`].
+pub const FOLLOW_CHANGED: Selector<bool> =
+    Selector::new("druid-widget-nursery.log-view.follow-changed");
+
+const DEFAULT_SIZE: Size = Size::new(600.0, 400.0);
+const PADDING: f64 = 4.0;
+const SCROLLBAR_WIDTH: f64 = 8.0;
+const MIN_THUMB_HEIGHT: f64 = 16.0;
+
+/// A line of a [`LogBuffer`].
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: Level,
+    pub text: String,
+}
+
+struct Ring {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    /// The number of lines dropped from the front, the index of the first line since the
+    /// buffer was created.
+    dropped: u64,
+}
+
+/// The lines of a [`LogView`], keeping the last lines up to a capacity.
+///
+/// The lines are shared by the clones of the buffer and appended in place, so adding a line
+/// doesn't copy the others; a version tells the clones apart, like [`Versioned`].
+///
+/// [`Versioned`]: crate::Versioned
+#[derive(Clone)]
+pub struct LogBuffer {
+    ring: Rc<RefCell<Ring>>,
+    version: u64,
+}
+
+impl LogBuffer {
+    /// Create a buffer of up to `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        LogBuffer {
+            ring: Rc::new(RefCell::new(Ring {
+                lines: VecDeque::new(),
+                capacity: capacity.max(1),
+                dropped: 0,
+            })),
+            version: 0,
+        }
+    }
+
+    /// Append a line, dropping the first line when the buffer is full.
+    pub fn push(&mut self, level: Level, text: impl Into<String>) {
+        self.extend(Some((level, text.into())));
+    }
+
+    /// Append lines, dropping the first lines when the buffer is full.
+    pub fn extend(&mut self, lines: impl IntoIterator<Item = (Level, String)>) {
+        let mut ring = self.ring.borrow_mut();
+        for (level, text) in lines {
+            if ring.lines.len() == ring.capacity {
+                ring.lines.pop_front();
+                ring.dropped += 1;
+            }
+            ring.lines.push_back(LogLine { level, text });
+        }
+        self.version += 1;
+    }
+
+    /// Remove all the lines.
+    pub fn clear(&mut self) {
+        let mut ring = self.ring.borrow_mut();
+        ring.dropped += ring.lines.len() as u64;
+        ring.lines.clear();
+        self.version += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring.borrow().lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Data for LogBuffer {
+    fn same(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.ring, &other.ring) && self.version == other.version
+    }
+}
+
+/// A view of a [`LogBuffer`] that stays fast with millions of lines, following the last line.
+///
+/// Only the visible lines are laid out and painted. While following, which is the default, the
+/// view scrolls to the new lines; scrolling up stops following, and scrolling back to the end or
+/// pressing End starts it again. The lines are colored by their level, and filtered by their
+/// level and by text with the commands of this module.
+///
+/// ```
+/// # use druid::widget::{Flex, TextBox};
+/// # use druid::{Data, Lens, Widget, WidgetExt};
+/// # use druid_widget_nursery::log_view::{LogBuffer, LogView, SET_FILTER};
+/// # use druid_widget_nursery::WidgetExt as _;
+/// #[derive(Clone, Data, Lens)]
+/// struct AppData {
+///     filter: String,
+///     log: LogBuffer,
+/// }
+///
+/// fn ui() -> impl Widget<AppData> {
+///     let log_id = druid::WidgetId::next();
+///     let filter = TextBox::new()
+///         .lens(AppData::filter)
+///         .on_change(move |ctx, _, data: &mut AppData, _| {
+///             ctx.submit_command(SET_FILTER.with(data.filter.clone()).to(log_id));
+///         });
+///     Flex::column()
+///         .with_child(filter)
+///         .with_flex_child(LogView::new().lens(AppData::log).with_id(log_id), 1.0)
+/// }
+/// ```
+pub struct LogView {
+    font: KeyOrValue<FontDescriptor>,
+    level_colors: Vec<(Level, KeyOrValue<Color>)>,
+    filter: String,
+    min_level: LevelFilter,
+    follow: bool,
+    /// The indexes since the buffer was created of the lines shown.
+    matches: VecDeque<u64>,
+    /// The index of the first line not filtered yet.
+    scanned: u64,
+    scroll_y: f64,
+    line_height: f64,
+    dragging_scrollbar: bool,
+}
+
+impl LogView {
+    pub fn new() -> Self {
+        LogView {
+            font: FontDescriptor::new(FontFamily::MONOSPACE).into(),
+            level_colors: Vec::new(),
+            filter: String::new(),
+            min_level: LevelFilter::Trace,
+            follow: true,
+            matches: VecDeque::new(),
+            scanned: 0,
+            scroll_y: 0.0,
+            line_height: 0.0,
+            dragging_scrollbar: false,
+        }
+    }
+
+    /// Builder-style method for specifying the font, a monospace font by default.
+    pub fn with_font(mut self, font: impl Into<KeyOrValue<FontDescriptor>>) -> Self {
+        self.font = font.into();
+        self
+    }
+
+    /// Builder-style method for specifying the color of the lines of `level`.
+    pub fn with_level_color(mut self, level: Level, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.level_colors.retain(|(other, _)| *other != level);
+        self.level_colors.push((level, color.into()));
+        self
+    }
+
+    /// Builder-style method for only showing the lines containing `filter`, ignoring case.
+    pub fn with_filter(mut self, filter: &str) -> Self {
+        self.filter = filter.to_lowercase();
+        self
+    }
+
+    /// Builder-style method for only showing the lines of `min_level` or more severe.
+    pub fn with_min_level(mut self, min_level: LevelFilter) -> Self {
+        self.min_level = min_level;
+        self
+    }
+
+    /// Builder-style method for specifying whether the view starts following the last line.
+    pub fn with_follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    fn color(&self, level: Level, env: &Env) -> Color {
+        if let Some((_, color)) = self.level_colors.iter().find(|(other, _)| *other == level) {
+            return color.resolve(env);
+        }
+        match level {
+            Level::Error => Color::rgb8(0xd3, 0x2f, 0x2f),
+            Level::Warn => Color::rgb8(0xf5, 0x7c, 0x00),
+            Level::Info => env.get(theme::TEXT_COLOR),
+            Level::Debug | Level::Trace => env.get(theme::DISABLED_TEXT_COLOR),
+        }
+    }
+
+    fn is_shown(&self, line: &LogLine) -> bool {
+        line.level <= self.min_level
+            && (self.filter.is_empty() || line.text.to_lowercase().contains(&self.filter))
+    }
+
+    /// Filter the lines added since the last time, and forget the lines dropped.
+    fn scan(&mut self, data: &LogBuffer) {
+        let ring = data.ring.borrow();
+        let end = ring.dropped + ring.lines.len() as u64;
+        for index in self.scanned.max(ring.dropped)..end {
+            if self.is_shown(&ring.lines[(index - ring.dropped) as usize]) {
+                self.matches.push_back(index);
+            }
+        }
+        self.scanned = end;
+
+        // keep the same lines in view
+        let mut removed = 0;
+        while self
+            .matches
+            .front()
+            .map_or(false, |index| *index < ring.dropped)
+        {
+            self.matches.pop_front();
+            removed += 1;
+        }
+        self.scroll_y = (self.scroll_y - removed as f64 * self.line_height).max(0.0);
+    }
+
+    /// Filter all the lines again.
+    fn rescan(&mut self, data: &LogBuffer) {
+        self.matches.clear();
+        self.scanned = 0;
+        self.scan(data);
+    }
+
+    fn max_scroll(&self, height: f64) -> f64 {
+        let content = self.matches.len() as f64 * self.line_height + 2.0 * PADDING;
+        (content - height).max(0.0)
+    }
+
+    fn set_follow(&mut self, ctx: &mut EventCtx, follow: bool) {
+        if follow != self.follow {
+            self.follow = follow;
+            ctx.submit_notification(FOLLOW_CHANGED.with(follow));
+        }
+        if follow {
+            self.scroll_y = self.max_scroll(ctx.size().height);
+            ctx.request_paint();
+        }
+    }
+
+    /// Scroll to `scroll_y`, following when it is the end.
+    fn scroll_to(&mut self, ctx: &mut EventCtx, scroll_y: f64) {
+        let max_scroll = self.max_scroll(ctx.size().height);
+        self.scroll_y = scroll_y.min(max_scroll).max(0.0);
+        self.set_follow(ctx, self.scroll_y >= max_scroll);
+        ctx.request_paint();
+    }
+
+    /// The thumb of the scrollbar, if the lines don't fit.
+    fn thumb(&self, size: Size) -> Option<Rect> {
+        let max_scroll = self.max_scroll(size.height);
+        if max_scroll <= 0.0 {
+            return None;
+        }
+        let height = (size.height * size.height / (size.height + max_scroll))
+            .max(MIN_THUMB_HEIGHT)
+            .min(size.height);
+        let y = self.scroll_y / max_scroll * (size.height - height);
+        let x = size.width - SCROLLBAR_WIDTH;
+        Some(Rect::new(x, y, size.width, y + height))
+    }
+
+    /// The scroll putting the middle of the thumb at `y`.
+    fn scroll_for_thumb(&self, y: f64, size: Size) -> f64 {
+        let thumb = self.thumb(size).map_or(0.0, |thumb| thumb.height());
+        let max_scroll = self.max_scroll(size.height);
+        (y - thumb / 2.0) / (size.height - thumb).max(1.0) * max_scroll
+    }
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<LogBuffer> for LogView {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut LogBuffer, _env: &Env) {
+        let size = ctx.size();
+        match event {
+            Event::Wheel(wheel) => {
+                self.scroll_to(ctx, self.scroll_y + wheel.wheel_delta.y);
+                ctx.set_handled();
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.request_focus();
+                if mouse.pos.x >= size.width - SCROLLBAR_WIDTH && self.thumb(size).is_some() {
+                    self.dragging_scrollbar = true;
+                    ctx.set_active(true);
+                    self.scroll_to(ctx, self.scroll_for_thumb(mouse.pos.y, size));
+                }
+            }
+            Event::MouseMove(mouse) if self.dragging_scrollbar => {
+                self.scroll_to(ctx, self.scroll_for_thumb(mouse.pos.y, size));
+            }
+            Event::MouseUp(_) if self.dragging_scrollbar => {
+                self.dragging_scrollbar = false;
+                ctx.set_active(false);
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                let page = size.height - self.line_height;
+                match &key.key {
+                    Key::End => self.set_follow(ctx, true),
+                    Key::Home => self.scroll_to(ctx, 0.0),
+                    Key::PageUp => self.scroll_to(ctx, self.scroll_y - page),
+                    Key::PageDown => self.scroll_to(ctx, self.scroll_y + page),
+                    Key::ArrowUp => self.scroll_to(ctx, self.scroll_y - self.line_height),
+                    Key::ArrowDown => self.scroll_to(ctx, self.scroll_y + self.line_height),
+                    _ => return,
+                }
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(SET_FILTER) => {
+                self.filter = cmd.get_unchecked(SET_FILTER).to_lowercase();
+                self.rescan(data);
+                self.scroll_to(ctx, if self.follow { f64::INFINITY } else { 0.0 });
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(SET_MIN_LEVEL) => {
+                self.min_level = *cmd.get_unchecked(SET_MIN_LEVEL);
+                self.rescan(data);
+                self.scroll_to(ctx, if self.follow { f64::INFINITY } else { 0.0 });
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(SET_FOLLOW) => {
+                self.set_follow(ctx, *cmd.get_unchecked(SET_FOLLOW));
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &LogBuffer,
+        _env: &Env,
+    ) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.register_for_focus();
+                self.rescan(data);
+            }
+            LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &LogBuffer, data: &LogBuffer, _env: &Env) {
+        if !Rc::ptr_eq(&old_data.ring, &data.ring) {
+            self.rescan(data);
+        } else if !old_data.same(data) {
+            self.scan(data);
+        }
+        if !old_data.same(data) {
+            if self.follow {
+                self.scroll_y = self.max_scroll(ctx.size().height);
+            }
+            ctx.request_paint();
+        }
+        if ctx.env_changed() {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &LogBuffer,
+        env: &Env,
+    ) -> Size {
+        let font = self.font.resolve(env);
+        let sample = ctx
+            .text()
+            .new_text_layout("Ag")
+            .font(font.family, font.size)
+            .build()
+            .unwrap();
+        self.line_height = sample.size().height;
+
+        let size = Size::new(
+            if bc.is_width_bounded() {
+                bc.max().width
+            } else {
+                DEFAULT_SIZE.width
+            },
+            if bc.is_height_bounded() {
+                bc.max().height
+            } else {
+                DEFAULT_SIZE.height
+            },
+        );
+        let size = bc.constrain(size);
+        let max_scroll = self.max_scroll(size.height);
+        self.scroll_y = if self.follow {
+            max_scroll
+        } else {
+            self.scroll_y.min(max_scroll)
+        };
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &LogBuffer, env: &Env) {
+        let size = ctx.size();
+        let font = self.font.resolve(env);
+        let ring = data.ring.borrow();
+        let clip = size.to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            if self.line_height <= 0.0 {
+                return;
+            }
+            let first = ((self.scroll_y - PADDING) / self.line_height)
+                .floor()
+                .max(0.0) as usize;
+            let count = (size.height / self.line_height).ceil() as usize + 1;
+            for row in first..(first + count).min(self.matches.len()) {
+                let index = self.matches[row];
+                let line = match index
+                    .checked_sub(ring.dropped)
+                    .and_then(|index| ring.lines.get(index as usize))
+                {
+                    Some(line) => line,
+                    None => continue,
+                };
+                let layout = ctx
+                    .text()
+                    .new_text_layout(line.text.clone())
+                    .font(font.family.clone(), font.size)
+                    .text_color(self.color(line.level, env))
+                    .build()
+                    .unwrap();
+                let y = PADDING + row as f64 * self.line_height - self.scroll_y;
+                ctx.draw_text(&layout, Point::new(PADDING, y));
+            }
+
+            if let Some(thumb) = self.thumb(size) {
+                let color = if self.dragging_scrollbar {
+                    env.get(theme::SCROLLBAR_COLOR)
+                } else {
+                    env.get(theme::SCROLLBAR_COLOR).with_alpha(0.6)
+                };
+                ctx.fill(thumb.inset(-1.0).to_rounded_rect(3.0), &color);
+            }
+        });
+    }
+}
@@ -16,8 +16,45 @@
 /// /// Bar the qux yay much
 /// pub const BAR: Selector<usize> = Selector::new("path::to::module::BAR@0:0");
 /// ```
+///
+/// The module path can be replaced by a namespace, which makes the keys easier
+/// to read when debugging commands. Module, line and column are still appended
+/// so keys stay unique.
+/// ```
+/// # use druid_widget_nursery::selectors;
+/// # use druid::WidgetId;
+/// selectors! {
+///     @namespace "my_app.editor";
+///     FOCUS_EDIT_BOX: WidgetId,
+/// }
+/// assert!(FOCUS_EDIT_BOX
+///     .to_string()
+///     .contains("\"my_app.editor::FOCUS_EDIT_BOX@"));
+/// ```
 #[macro_export]
 macro_rules! selectors {
+    (
+        @namespace $namespace:literal;
+        $(
+            $(#[$attr:meta])*
+            $name:ident $( : $ty:ty)?
+        ),* $(,)?
+    ) => {
+        $(
+            $(#[$attr])*
+            pub const $name: ::druid::Selector<$($ty)?> = ::druid::Selector::new(concat!(
+                $namespace,
+                "::",
+                stringify!($name),
+                "@",
+                module_path!(),
+                ":",
+                line!(),
+                ":",
+                column!()
+            ));
+        )*
+    };
     (
         $(
             $(#[$attr:meta])*
@@ -0,0 +1,419 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A widget rendering CommonMark text, with the `pulldown-cmark` feature.
+//!
+//! Clicking a link submits [`OPEN_LINK`] with its destination; the application handles it, for
+//! example in its `AppDelegate`. The colors and fonts are taken from the keys of this module when
+//! they are in the environment.
+
+use druid::text::{RichText, RichTextBuilder};
+use druid::widget::prelude::*;
+use druid::widget::{
+    CrossAxisAlignment, FillStrat, Flex, Image, Label, LineBreaking, Painter, RawLabel, SizedBox,
+};
+use druid::{
+    lens, theme, ArcStr, Color, FontDescriptor, FontFamily, FontStyle, FontWeight, ImageBuf,
+    Insets, Key, Point, Rect, Selector, WidgetExt, WidgetPod,
+};
+use pulldown_cmark::{Event as MdEvent, Options, Parser, Tag};
+
+use crate::Separator;
+
+/// Submitted with the destination of a link when it is clicked.
+pub const OPEN_LINK: Selector<String> = Selector::new("druid-widget-nursery.markdown.open-link");
+
+/// The color of links.
+pub const LINK_COLOR: Key<Color> = Key::new("druid-widget-nursery.markdown.link-color");
+/// The font of code, inline and in blocks.
+pub const CODE_FONT: Key<FontDescriptor> = Key::new("druid-widget-nursery.markdown.code-font");
+/// The background of code blocks.
+pub const CODE_BACKGROUND: Key<Color> = Key::new("druid-widget-nursery.markdown.code-background");
+/// The color of the bars on the left of quotes.
+pub const QUOTE_COLOR: Key<Color> = Key::new("druid-widget-nursery.markdown.quote-color");
+/// The space between paragraphs, headings and the other blocks.
+pub const BLOCK_SPACING: Key<f64> = Key::new("druid-widget-nursery.markdown.block-spacing");
+
+/// The indentation of each level of lists and quotes.
+const INDENT: f64 = 20.0;
+const QUOTE_INDENT: f64 = 12.0;
+/// The size of the headings, relative to the size of the text.
+const HEADING_SCALES: [f64; 6] = [2.0, 1.5, 1.25, 1.1, 1.0, 0.9];
+
+type Loader = Box<dyn Fn(&str) -> Option<ImageBuf>>;
+
+/// A widget rendering its data as CommonMark.
+///
+/// Headings, emphasis, strikethrough, lists, task lists, quotes, code, links, rules and images
+/// are supported; HTML is left out. Images are loaded with the function given to
+/// [`with_image_loader`], and replaced with their description without one.
+///
+/// ```
+/// # use druid::{AppDelegate, Command, DelegateCtx, Env, Handled, Target, Widget};
+/// # use druid_widget_nursery::markdown::{Markdown, OPEN_LINK};
+/// fn readme() -> impl Widget<String> {
+///     Markdown::new()
+/// }
+///
+/// struct Delegate;
+///
+/// impl AppDelegate<String> for Delegate {
+///     fn command(
+///         &mut self,
+///         _ctx: &mut DelegateCtx,
+///         _target: Target,
+///         cmd: &Command,
+///         _data: &mut String,
+///         _env: &Env,
+///     ) -> Handled {
+///         if let Some(url) = cmd.get(OPEN_LINK) {
+///             println!("opening {}", url);
+///             return Handled::Yes;
+///         }
+///         Handled::No
+///     }
+/// }
+/// ```
+///
+/// [`with_image_loader`]: #method.with_image_loader
+pub struct Markdown {
+    content: WidgetPod<(), Box<dyn Widget<()>>>,
+    image_loader: Option<Loader>,
+}
+
+impl Markdown {
+    pub fn new() -> Self {
+        Markdown {
+            content: WidgetPod::new(Box::new(SizedBox::empty())),
+            image_loader: None,
+        }
+    }
+
+    /// Builder-style method for loading the images, from their destination.
+    pub fn with_image_loader(
+        mut self,
+        loader: impl Fn(&str) -> Option<ImageBuf> + 'static,
+    ) -> Self {
+        self.image_loader = Some(Box::new(loader));
+        self
+    }
+
+    fn rebuild(&mut self, text: &str, env: &Env) {
+        let mut blocks = Blocks::new(env, &self.image_loader);
+        let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+        for event in Parser::new_ext(text, options) {
+            blocks.push(event);
+        }
+        self.content = WidgetPod::new(Box::new(blocks.finish()));
+    }
+}
+
+impl Default for Markdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data + AsRef<str>> Widget<T> for Markdown {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, env: &Env) {
+        self.content.event(ctx, event, &mut (), env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.rebuild(data.as_ref(), env);
+        }
+        self.content.lifecycle(ctx, event, &(), env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if !old_data.same(data) || ctx.env_changed() {
+            self.rebuild(data.as_ref(), env);
+            ctx.children_changed();
+        } else {
+            self.content.update(ctx, &(), env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let size = self.content.layout(ctx, bc, &(), env);
+        self.content.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        self.content.paint(ctx, &(), env);
+    }
+}
+
+/// Turns the events of the parser into a column of widgets.
+struct Blocks<'a> {
+    env: &'a Env,
+    image_loader: &'a Option<Loader>,
+    column: Flex<()>,
+    empty: bool,
+    text: RichTextBuilder,
+    /// The length of `text`, which the builder doesn't tell.
+    len: usize,
+    /// Where the styled spans started.
+    spans: Vec<usize>,
+    /// The next number of each list, or `None` for bullets.
+    lists: Vec<Option<u64>>,
+    /// The bullet or number of the item, until its first block.
+    marker: Option<String>,
+    quotes: usize,
+    code: Option<String>,
+    /// The destination and the description of an image.
+    image: Option<(String, String)>,
+}
+
+impl<'a> Blocks<'a> {
+    fn new(env: &'a Env, image_loader: &'a Option<Loader>) -> Self {
+        Blocks {
+            env,
+            image_loader,
+            column: Flex::column().cross_axis_alignment(CrossAxisAlignment::Fill),
+            empty: true,
+            text: RichTextBuilder::new(),
+            len: 0,
+            spans: Vec::new(),
+            lists: Vec::new(),
+            marker: None,
+            quotes: 0,
+            code: None,
+            image: None,
+        }
+    }
+
+    fn code_font(&self) -> FontDescriptor {
+        self.env
+            .try_get(CODE_FONT)
+            .unwrap_or_else(|_| FontDescriptor::new(FontFamily::MONOSPACE))
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.text.push(text);
+        self.len += text.len();
+    }
+
+    fn push(&mut self, event: MdEvent) {
+        match event {
+            MdEvent::Start(tag) => self.start(tag),
+            MdEvent::End(tag) => self.end(tag),
+            MdEvent::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.push_str(&text);
+                } else if let Some((_, description)) = &mut self.image {
+                    description.push_str(&text);
+                } else {
+                    self.push_text(&text);
+                }
+            }
+            MdEvent::Code(code) => {
+                let start = self.len;
+                self.push_text(&code);
+                let font = self.code_font();
+                self.text
+                    .add_attributes_for_range(start..self.len)
+                    .font_descriptor(font);
+            }
+            MdEvent::SoftBreak => self.push_text(" "),
+            MdEvent::HardBreak => self.push_text("\n"),
+            MdEvent::Rule => {
+                self.flush();
+                self.add_block(Separator::new());
+            }
+            MdEvent::TaskListMarker(checked) => {
+                self.push_text(if checked { "\u{2611} " } else { "\u{2610} " })
+            }
+            MdEvent::Html(_) | MdEvent::FootnoteReference(_) => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::BlockQuote => {
+                self.flush();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.code = Some(String::new());
+            }
+            Tag::List(first) => {
+                self.flush();
+                self.lists.push(first);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "\u{2022}".to_string(),
+                };
+                self.marker = Some(marker);
+            }
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) => {
+                self.spans.push(self.len)
+            }
+            Tag::Image(_, destination, _) => {
+                self.flush();
+                self.image = Some((destination.to_string(), String::new()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph | Tag::Item => self.flush(),
+            Tag::Heading(level, _, _) => {
+                let scale = HEADING_SCALES[level as usize - 1];
+                let size = self.env.get(theme::TEXT_SIZE_NORMAL) * scale;
+                self.text
+                    .add_attributes_for_range(0..self.len)
+                    .size(size)
+                    .weight(FontWeight::BOLD);
+                self.flush();
+            }
+            Tag::BlockQuote => {
+                self.flush();
+                self.quotes -= 1;
+            }
+            Tag::CodeBlock(_) => {
+                let code = self.code.take().unwrap_or_default();
+                let label = RawLabel::new()
+                    .with_font(self.code_font())
+                    .with_line_break_mode(LineBreaking::Clip)
+                    .lens(lens::Constant(ArcStr::from(code.trim_end_matches('\n'))));
+                let background = self
+                    .env
+                    .try_get(CODE_BACKGROUND)
+                    .unwrap_or_else(|_| self.env.get(theme::BACKGROUND_LIGHT));
+                self.add_block(label.padding(8.0).background(background).rounded(4.0));
+            }
+            Tag::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) => {
+                let start = self.spans.pop().unwrap_or(0);
+                let mut attributes = self.text.add_attributes_for_range(start..self.len);
+                match tag {
+                    Tag::Emphasis => {
+                        attributes.style(FontStyle::Italic);
+                    }
+                    Tag::Strong => {
+                        attributes.weight(FontWeight::BOLD);
+                    }
+                    Tag::Strikethrough => {
+                        attributes.strikethrough(true);
+                    }
+                    Tag::Link(_, destination, _) => {
+                        let color = self
+                            .env
+                            .try_get(LINK_COLOR)
+                            .unwrap_or_else(|_| self.env.get(theme::PRIMARY_LIGHT));
+                        attributes
+                            .text_color(color)
+                            .underline(true)
+                            .link(OPEN_LINK.with(destination.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+            Tag::Image(..) => {
+                let (destination, description) = self.image.take().unwrap_or_default();
+                let image = self
+                    .image_loader
+                    .as_ref()
+                    .and_then(|load| load(&destination));
+                match image {
+                    Some(image) => {
+                        self.add_block(Image::new(image).fill_mode(FillStrat::ScaleDown))
+                    }
+                    None => {
+                        let label = Label::new(description)
+                            .with_text_color(self.env.get(theme::DISABLED_TEXT_COLOR))
+                            .with_line_break_mode(LineBreaking::WordWrap);
+                        self.add_block(label);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Turn the text so far into a block.
+    fn flush(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+        let text = std::mem::replace(&mut self.text, RichTextBuilder::new()).build();
+        self.len = 0;
+        self.spans.clear();
+        let label = RawLabel::new()
+            .with_line_break_mode(LineBreaking::WordWrap)
+            .lens(lens::Constant::<RichText>(text));
+        self.add_block(label);
+    }
+
+    /// Add a block, in the lists and the quotes it is in.
+    fn add_block(&mut self, block: impl Widget<()> + 'static) {
+        let mut block: Box<dyn Widget<()>> = Box::new(block);
+        if !self.lists.is_empty() {
+            let marker = Label::new(self.marker.take().unwrap_or_default());
+            let row = Flex::row()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(SizedBox::new(marker).width(INDENT))
+                .with_flex_child(block, 1.0);
+            let indent = (self.lists.len() - 1) as f64 * INDENT;
+            block = Box::new(row.padding(Insets::new(indent, 0.0, 0.0, 0.0)));
+        }
+        if self.quotes > 0 {
+            let quotes = self.quotes;
+            let color = self
+                .env
+                .try_get(QUOTE_COLOR)
+                .unwrap_or_else(|_| self.env.get(theme::BORDER_LIGHT));
+            let bars = Painter::new(move |ctx, _, _| {
+                let height = ctx.size().height;
+                for quote in 0..quotes {
+                    let x = quote as f64 * QUOTE_INDENT;
+                    ctx.fill(Rect::new(x, 0.0, x + 3.0, height), &color);
+                }
+            });
+            let indent = quotes as f64 * QUOTE_INDENT;
+            block = Box::new(
+                block
+                    .padding(Insets::new(indent, 0.0, 0.0, 0.0))
+                    .background(bars),
+            );
+        }
+
+        if !self.empty {
+            let spacing = self.env.try_get(BLOCK_SPACING).unwrap_or(8.0);
+            self.column.add_spacer(spacing);
+        }
+        self.column.add_child(block);
+        self.empty = false;
+    }
+
+    fn finish(mut self) -> Flex<()> {
+        self.flush();
+        self.column
+    }
+}
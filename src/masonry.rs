@@ -0,0 +1,285 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::widget::prelude::*;
+use druid::{KeyOrValue, Point, Selector, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve};
+
+/// Sent to itself by the masonry when the children moved, to animate them to their new place.
+const REFLOW: Selector = Selector::new("druid-widget-nursery.masonry.reflow");
+
+/// The width of the columns when the width of the masonry is unbounded.
+const DEFAULT_COLUMN_WIDTH: f64 = 200.0;
+
+struct Child<T> {
+    widget: WidgetPod<T, Box<dyn Widget<T>>>,
+    origin: Animated<Point>,
+    /// Where the child goes, from the last layout.
+    target: Point,
+}
+
+/// A container packing children of various heights into columns of the same width, like the
+/// pictures of a gallery.
+///
+/// Each child goes at the bottom of the shortest column, so the columns end up about as high as
+/// each other. With a minimum column width, there are as many columns as fit, and the children are
+/// packed again when the width changes; with [`with_animated_reflow`], they move to their new place
+/// smoothly.
+///
+/// ```
+/// # use druid::widget::{Label, LineBreaking};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::Masonry;
+/// fn gallery(captions: &[&str]) -> impl Widget<()> {
+///     let mut masonry = Masonry::new()
+///         .with_min_column_width(160.0)
+///         .with_spacing(8.0)
+///         .with_animated_reflow(true);
+///     for caption in captions {
+///         masonry.add_child(Label::new(*caption).with_line_break_mode(LineBreaking::WordWrap));
+///     }
+///     masonry
+/// }
+/// ```
+///
+/// [`with_animated_reflow`]: #method.with_animated_reflow
+pub struct Masonry<T> {
+    children: Vec<Child<T>>,
+    columns: usize,
+    min_column_width: Option<f64>,
+    spacing: KeyOrValue<f64>,
+    animated_reflow: bool,
+    laid_out: bool,
+    reflow_pending: bool,
+}
+
+impl<T: Data> Masonry<T> {
+    pub fn new() -> Self {
+        Masonry {
+            children: Vec::new(),
+            columns: 2,
+            min_column_width: None,
+            spacing: KeyOrValue::Concrete(0.0),
+            animated_reflow: false,
+            laid_out: false,
+            reflow_pending: false,
+        }
+    }
+
+    /// Builder-style method for adding a child.
+    pub fn with_child(mut self, child: impl Widget<T> + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Add a child, after the ones already there.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static) {
+        self.children.push(Child {
+            widget: WidgetPod::new(Box::new(child)),
+            origin: Animated::jump(Point::ORIGIN)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+            target: Point::ORIGIN,
+        });
+    }
+
+    /// Builder-style method for specifying the number of columns.
+    ///
+    /// For the non-builder varient, see [`set_columns`].
+    ///
+    /// [`set_columns`]: #method.set_columns
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.set_columns(columns);
+        self
+    }
+
+    /// Set the number of columns, 2 by default. This is ignored when there is a minimum column
+    /// width and the width of the masonry is bounded.
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = columns.max(1);
+    }
+
+    /// Builder-style method for fitting as many columns as possible, at least `width` wide.
+    ///
+    /// For the non-builder varient, see [`set_min_column_width`].
+    ///
+    /// [`set_min_column_width`]: #method.set_min_column_width
+    pub fn with_min_column_width(mut self, width: f64) -> Self {
+        self.set_min_column_width(Some(width));
+        self
+    }
+
+    /// Fit as many columns as possible, at least `width` wide, or use the number of columns
+    /// for `None`.
+    pub fn set_min_column_width(&mut self, width: Option<f64>) {
+        self.min_column_width = width;
+    }
+
+    /// Builder-style method for specifying the space between the columns and between the children
+    /// of a column.
+    ///
+    /// For the non-builder varient, see [`set_spacing`].
+    ///
+    /// [`set_spacing`]: #method.set_spacing
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Set the space between the columns and between the children of a column.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+
+    /// Builder-style method for animating the children to their new place when they are packed
+    /// again.
+    ///
+    /// For the non-builder varient, see [`set_animated_reflow`].
+    ///
+    /// [`set_animated_reflow`]: #method.set_animated_reflow
+    pub fn with_animated_reflow(mut self, animated: bool) -> Self {
+        self.set_animated_reflow(animated);
+        self
+    }
+
+    /// Set whether the children are animated to their new place when they are packed again.
+    pub fn set_animated_reflow(&mut self, animated: bool) {
+        self.animated_reflow = animated;
+    }
+
+    /// The number and the width of the columns for `width`.
+    fn column_layout(&self, width: f64, spacing: f64) -> (usize, f64) {
+        if !width.is_finite() {
+            let column_width = self.min_column_width.unwrap_or(DEFAULT_COLUMN_WIDTH);
+            return (self.columns, column_width);
+        }
+        let columns = match self.min_column_width {
+            Some(min_width) => {
+                ((width + spacing) / (min_width + spacing)).floor().max(1.0) as usize
+            }
+            None => self.columns,
+        };
+        let column_width = (width - spacing * (columns - 1) as f64) / columns as f64;
+        (columns, column_width.max(0.0))
+    }
+}
+
+impl<T: Data> Default for Masonry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for Masonry<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(REFLOW) => {
+                self.reflow_pending = false;
+                for child in &mut self.children {
+                    child.origin.animate(ctx, child.target);
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::AnimFrame(nanos) => {
+                for child in &mut self.children {
+                    if child.origin.animating() {
+                        child.origin.update(ctx, *nanos);
+                    }
+                }
+            }
+            _ => {}
+        }
+        for child in &mut self.children {
+            child.widget.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let spacing = self.spacing.resolve(env);
+        let (columns, column_width) = self.column_layout(bc.max().width, spacing);
+        let child_bc = BoxConstraints::new(
+            Size::new(column_width, 0.0),
+            Size::new(column_width, f64::INFINITY),
+        );
+
+        let animate = self.animated_reflow && self.laid_out;
+        let mut heights = vec![0.0; columns];
+        let mut moved = false;
+        for child in &mut self.children {
+            let size = child.widget.layout(ctx, &child_bc, data, env);
+            // the first of the shortest columns
+            let (column, y) = heights.iter().copied().enumerate().fold(
+                (0, f64::INFINITY),
+                |shortest, (column, y)| {
+                    if y < shortest.1 {
+                        (column, y)
+                    } else {
+                        shortest
+                    }
+                },
+            );
+            heights[column] = y + size.height + spacing;
+
+            child.target = Point::new(column as f64 * (column_width + spacing), y);
+            if !animate {
+                child.origin.jump_to_value(child.target);
+            } else if child.target != child.origin.end() {
+                moved = true;
+            }
+            child.widget.set_origin(ctx, child.origin.get());
+        }
+        self.laid_out = true;
+
+        // animations can't be started from layout
+        if moved && !self.reflow_pending {
+            self.reflow_pending = true;
+            ctx.submit_command(REFLOW.to(ctx.widget_id()));
+        }
+
+        let width = columns as f64 * (column_width + spacing) - spacing;
+        let height = heights.into_iter().fold(0.0, f64::max);
+        let height = if self.children.is_empty() {
+            0.0
+        } else {
+            height - spacing
+        };
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        // moving children can go out of the masonry
+        let clip = ctx.size().to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            for child in &mut self.children {
+                child.widget.paint(ctx, data, env);
+            }
+        });
+    }
+}
@@ -1,12 +1,153 @@
+//! Widgets and a registry for the material icons, and for custom icons made of SVG path data.
+//!
+//! Icons can be used directly, like `Icon::new(normal::action::ALARM_ADD)`, or registered under a
+//! name, and looked up at runtime with [`Icon::named`], [`lookup_icon`] and [`search_icons`]. The
+//! registry is per thread, like the widgets using it.
+
 pub use druid_material_icons::{normal, IconPaths};
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use druid::{
-    kurbo::{Affine, Size},
+    kurbo::{Affine, BezPath, Size, SvgParseError},
     widget::prelude::*,
     Color, KeyOrValue,
 };
 
-/// A widget that draws one of the material icons.
+thread_local! {
+    /// The icons registered by name.
+    static REGISTRY: RefCell<HashMap<String, IconData>> = RefCell::new(HashMap::new());
+}
+
+/// The shape of an icon: the paths of a material icon, or paths parsed from SVG path data.
+#[derive(Debug, Clone)]
+pub enum IconData {
+    Material(IconPaths),
+    Custom {
+        paths: Arc<[BezPath]>,
+        /// The size of the view box the paths are drawn in.
+        size: Size,
+    },
+}
+
+impl IconData {
+    /// Parse icon paths from SVG path data, like the `d` attributes of the `path` elements of an
+    /// SVG file, drawn in a view box of `size`.
+    pub fn from_svg(paths: &[&str], size: impl Into<Size>) -> Result<Self, SvgParseError> {
+        let paths = paths
+            .iter()
+            .map(|path| BezPath::from_svg(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(IconData::Custom {
+            paths: paths.into(),
+            size: size.into(),
+        })
+    }
+
+    /// The size of the view box of the icon.
+    pub fn size(&self) -> Size {
+        match self {
+            IconData::Material(paths) => paths.size,
+            IconData::Custom { size, .. } => *size,
+        }
+    }
+
+    pub(crate) fn fill(&self, ctx: &mut PaintCtx, color: &Color) {
+        match self {
+            IconData::Material(paths) => {
+                for path in paths.paths {
+                    ctx.fill(path, color);
+                }
+            }
+            IconData::Custom { paths, .. } => {
+                for path in paths.iter() {
+                    ctx.fill(path, color);
+                }
+            }
+        }
+    }
+}
+
+impl From<IconPaths> for IconData {
+    fn from(paths: IconPaths) -> Self {
+        IconData::Material(paths)
+    }
+}
+
+/// Register an icon under `name`, replacing the icon registered before under that name.
+pub fn register_icon(name: impl Into<String>, icon: impl Into<IconData>) {
+    let icon = icon.into();
+    REGISTRY.with(|registry| registry.borrow_mut().insert(name.into(), icon));
+}
+
+/// Register an icon made of SVG path data under `name`. See [`IconData::from_svg`].
+pub fn register_svg_icon(
+    name: impl Into<String>,
+    paths: &[&str],
+    size: impl Into<Size>,
+) -> Result<(), SvgParseError> {
+    register_icon(name, IconData::from_svg(paths, size)?);
+    Ok(())
+}
+
+/// The icon registered under `name`.
+pub fn lookup_icon(name: &str) -> Option<IconData> {
+    REGISTRY.with(|registry| registry.borrow().get(name).cloned())
+}
+
+/// The sorted names of the registered icons containing `query`, ignoring case.
+pub fn search_icons(query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    let mut names: Vec<String> = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .keys()
+            .filter(|name| name.to_lowercase().contains(&query))
+            .cloned()
+            .collect()
+    });
+    names.sort();
+    names
+}
+
+/// Register material icons by the lowercase name of their constant, like `alarm_add` for
+/// `normal::action::ALARM_ADD`.
+///
+/// `druid-material-icons` has no list of its icons, so the icons an app looks up by name have to
+/// be registered first.
+///
+/// ```
+/// use druid_widget_nursery::material_icons::{lookup_icon, normal::action};
+/// use druid_widget_nursery::register_material_icons;
+///
+/// register_material_icons!(action::ALARM_ADD, action::ADD_TASK);
+/// assert!(lookup_icon("add_task").is_some());
+/// ```
+#[macro_export]
+macro_rules! register_material_icons {
+    ($($icon:path),* $(,)?) => {
+        $(
+            $crate::material_icons::register_icon(
+                $crate::material_icons::material_icon_name(stringify!($icon)),
+                $icon,
+            );
+        )*
+    };
+}
+
+#[doc(hidden)]
+/// The name of the material icon stringified as `path`, used by [`register_material_icons`].
+pub fn material_icon_name(path: &str) -> String {
+    path.rsplit("::")
+        .next()
+        .unwrap_or(path)
+        .trim()
+        .to_lowercase()
+}
+
+/// A widget that draws one of the material icons, or a registered icon.
 ///
 /// # Examples
 ///
@@ -15,28 +156,57 @@ use druid::{
 /// use druid_widget_nursery::material_icons::{Icon, normal::action::ALARM_ADD};
 /// let icon = Icon::new(ALARM_ADD)
 ///     // optional - defaults to text color
-///     .with_color(Color::WHITE);
+///     .with_color(Color::WHITE)
+///     // optional - defaults to the size of the icon
+///     .with_size(32.0);
 /// // use `icon` as you would any widget...
 /// ```
 #[derive(Debug, Clone)]
 pub struct Icon {
-    paths: IconPaths,
+    paths: IconData,
     color: KeyOrValue<Color>,
+    size: Option<KeyOrValue<f64>>,
 }
 
 impl Icon {
     #[inline]
-    pub fn new(paths: IconPaths) -> Self {
+    pub fn new(paths: impl Into<IconData>) -> Self {
         Self {
-            paths,
+            paths: paths.into(),
             color: KeyOrValue::from(druid::theme::TEXT_COLOR),
+            size: None,
         }
     }
 
+    /// The icon registered under `name`, if there is one.
+    pub fn named(name: &str) -> Option<Self> {
+        lookup_icon(name).map(Self::new)
+    }
+
     pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
         self.color = color.into();
         self
     }
+
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the height of the icon.
+    ///
+    /// For the non-builder varient, see [`set_size`].
+    ///
+    /// [`set_size`]: #method.set_size
+    pub fn with_size(mut self, size: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Set the height of the icon, the width following from its aspect ratio. Without a size,
+    /// the icon is as big as its view box.
+    pub fn set_size(&mut self, size: impl Into<KeyOrValue<f64>>) {
+        self.size = Some(size.into());
+    }
 }
 
 impl<T: Data> Widget<T> for Icon {
@@ -46,12 +216,27 @@ impl<T: Data> Widget<T> for Icon {
     fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {
         // no lifecycle
     }
-    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
-        // no update
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
+        if let Some(KeyOrValue::Key(key)) = &self.size {
+            if ctx.env_key_changed(key) {
+                ctx.request_layout();
+            }
+        }
+        if let KeyOrValue::Key(key) = &self.color {
+            if ctx.env_key_changed(key) {
+                ctx.request_paint();
+            }
+        }
     }
-    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, _env: &Env) -> Size {
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let icon_size = self.paths.size();
+        let aspect_ratio = icon_size.aspect_ratio();
+        let width = match &self.size {
+            Some(size) => size.resolve(env) / aspect_ratio,
+            None => icon_size.width,
+        };
         // Try to preserve aspect ratio if possible, but if not then allow non-uniform scaling.
-        bc.constrain_aspect_ratio(self.paths.size.aspect_ratio(), self.paths.size.width)
+        bc.constrain_aspect_ratio(aspect_ratio, width)
     }
     fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
         let color = self.color.resolve(env);
@@ -59,13 +244,11 @@ impl<T: Data> Widget<T> for Icon {
         let Size {
             width: icon_width,
             height: icon_height,
-        } = self.paths.size;
+        } = self.paths.size();
         ctx.transform(Affine::scale_non_uniform(
             width * icon_width.recip(),
             height * icon_height.recip(),
         ));
-        for path in self.paths.paths {
-            ctx.fill(path, &color);
-        }
+        self.paths.fill(ctx, &color);
     }
 }
@@ -0,0 +1,347 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::SizedBox;
+use druid::{TimerToken, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, SwitchTransition};
+use crate::RequestCtx;
+
+type SomeMaker<T> = dyn Fn() -> Box<dyn Widget<T>>;
+type NoneMaker = dyn Fn(&Env) -> Box<dyn Widget<()>>;
+
+/// A widget switching between a child for `Some` data and a placeholder for `None`, like druid's
+/// [`Maybe`].
+///
+/// Both children are built when they are needed, the placeholder with the [`Env`], so it can
+/// depend on the theme. The `Some` child can be kept alive for a while after the data became
+/// `None`, keeping its state when the data comes back quickly, and the change can be animated.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use druid::widget::{Label, TextBox};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::animation::SwitchTransition;
+/// # use druid_widget_nursery::Maybe;
+/// fn name() -> impl Widget<Option<String>> {
+///     Maybe::new(
+///         || TextBox::new(),
+///         |_env| Label::new("No name"),
+///     )
+///     .with_keep_alive(Duration::from_secs(1))
+///     .with_transition(SwitchTransition::Fade)
+/// }
+/// ```
+///
+/// [`Maybe`]: druid::widget::Maybe
+pub struct Maybe<T> {
+    some_maker: Box<SomeMaker<T>>,
+    none_maker: Box<NoneMaker>,
+    some: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    /// The last `Some` data, for the `Some` child while it is hidden.
+    some_data: Option<T>,
+    none: Option<WidgetPod<(), Box<dyn Widget<()>>>>,
+    is_some: bool,
+    keep_alive: Option<Duration>,
+    keep_alive_timer: TimerToken,
+    /// The `Some` child isn't kept alive anymore, and is dropped once it is hidden.
+    expired: bool,
+    transition: Option<SwitchTransition>,
+    progress: Animated<f64>,
+}
+
+impl<T: Data> Maybe<T> {
+    pub fn new<W1, W2>(
+        some_maker: impl Fn() -> W1 + 'static,
+        none_maker: impl Fn(&Env) -> W2 + 'static,
+    ) -> Self
+    where
+        W1: Widget<T> + 'static,
+        W2: Widget<()> + 'static,
+    {
+        Maybe {
+            some_maker: Box::new(move || Box::new(some_maker())),
+            none_maker: Box::new(move |env| Box::new(none_maker(env))),
+            some: None,
+            some_data: None,
+            none: None,
+            is_some: false,
+            keep_alive: None,
+            keep_alive_timer: TimerToken::INVALID,
+            expired: true,
+            transition: None,
+            progress: Animated::jump(1.0)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_IN_OUT)
+                .layout(true),
+        }
+    }
+
+    /// Create a `Maybe` showing nothing for `None`.
+    pub fn or_empty<W: Widget<T> + 'static>(some_maker: impl Fn() -> W + 'static) -> Self {
+        Self::new(some_maker, |_| SizedBox::empty())
+    }
+
+    /// Builder-style method for keeping the `Some` child for `duration` after the data became
+    /// `None`.
+    ///
+    /// For the non-builder varient, see [`set_keep_alive`].
+    ///
+    /// [`set_keep_alive`]: #method.set_keep_alive
+    pub fn with_keep_alive(mut self, duration: Duration) -> Self {
+        self.set_keep_alive(Some(duration));
+        self
+    }
+
+    /// Set how long the `Some` child is kept after the data became `None`. If the data comes back
+    /// in time, the child is shown again with its state, instead of being built again. With
+    /// `None`, it is dropped right away.
+    pub fn set_keep_alive(&mut self, duration: Option<Duration>) {
+        self.keep_alive = duration;
+    }
+
+    /// Builder-style method for animating the change between `Some` and `None`.
+    ///
+    /// For the non-builder varient, see [`set_transition`].
+    ///
+    /// [`set_transition`]: #method.set_transition
+    pub fn with_transition(mut self, transition: SwitchTransition) -> Self {
+        self.set_transition(Some(transition));
+        self
+    }
+
+    /// Set the [`SwitchTransition`], or `None` to switch at once.
+    pub fn set_transition(&mut self, transition: Option<SwitchTransition>) {
+        self.transition = transition;
+    }
+
+    /// Builder-style method for specifying the duration of the transition.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the transition in seconds.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.progress.set_duration(duration);
+    }
+
+    /// Drop the hidden children which aren't needed anymore.
+    fn settle(&mut self, ctx: &mut impl RequestCtx) {
+        if self.progress.animating() {
+            return;
+        }
+        let dropped = if self.is_some {
+            self.none.take().is_some()
+        } else if self.expired {
+            self.some_data = None;
+            self.some.take().is_some()
+        } else {
+            false
+        };
+        if dropped {
+            ctx.children_changed();
+        }
+    }
+}
+
+impl<T: Data> Widget<Option<T>> for Maybe<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Option<T>, env: &Env) {
+        match event {
+            Event::AnimFrame(nanos) if self.progress.animating() => {
+                self.progress.update(ctx, *nanos);
+                self.settle(ctx);
+            }
+            Event::Timer(token) if *token == self.keep_alive_timer => {
+                self.keep_alive_timer = TimerToken::INVALID;
+                self.expired = true;
+                self.settle(ctx);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        let hidden = event.should_propagate_to_hidden();
+        match data {
+            Some(inner) => {
+                if let Some(some) = &mut self.some {
+                    some.event(ctx, event, inner, env);
+                }
+            }
+            None if hidden => {
+                if let (Some(some), Some(some_data)) = (&mut self.some, &mut self.some_data) {
+                    some.event(ctx, event, some_data, env);
+                }
+            }
+            None => {}
+        }
+        if data.is_none() || hidden {
+            if let Some(none) = &mut self.none {
+                none.event(ctx, event, &mut (), env);
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &Option<T>,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            self.is_some = data.is_some();
+            self.some_data = data.clone();
+            if self.is_some {
+                self.some = Some(WidgetPod::new((self.some_maker)()));
+            } else {
+                self.none = Some(WidgetPod::new((self.none_maker)(env)));
+            }
+        }
+
+        if let (Some(some), Some(some_data)) = (&mut self.some, &self.some_data) {
+            some.lifecycle(ctx, event, some_data, env);
+        }
+        if let Some(none) = &mut self.none {
+            none.lifecycle(ctx, event, &(), env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &Option<T>, data: &Option<T>, env: &Env) {
+        // a child built in this update isn't added yet
+        let (mut built_some, mut built_none) = (false, false);
+        if data.is_some() != self.is_some {
+            self.is_some = data.is_some();
+            if self.is_some {
+                self.keep_alive_timer = TimerToken::INVALID;
+                self.expired = false;
+                if self.some.is_none() {
+                    self.some = Some(WidgetPod::new((self.some_maker)()));
+                    built_some = true;
+                }
+            } else {
+                match self.keep_alive {
+                    Some(duration) => self.keep_alive_timer = ctx.request_timer(duration),
+                    None => self.expired = true,
+                }
+                if self.none.is_none() {
+                    self.none = Some(WidgetPod::new((self.none_maker)(env)));
+                    built_none = true;
+                }
+            }
+            ctx.children_changed();
+            ctx.request_layout();
+
+            if self.transition.is_some() {
+                self.progress.jump_to_value(0.0);
+                self.progress.animate(ctx, 1.0);
+            }
+            self.settle(ctx);
+        }
+
+        if data.is_some() {
+            self.some_data = data.clone();
+        }
+        if let (Some(some), Some(some_data)) = (&mut self.some, &self.some_data) {
+            if !built_some {
+                some.update(ctx, some_data, env);
+            }
+        }
+        if let Some(none) = &mut self.none {
+            if !built_none {
+                none.update(ctx, &(), env);
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Option<T>,
+        env: &Env,
+    ) -> Size {
+        let transition = self.transition.unwrap_or_default();
+        let animating = self.progress.animating();
+        let mut size = bc.min();
+
+        if let (Some(some), Some(some_data)) = (&mut self.some, &self.some_data) {
+            if self.is_some {
+                size = some.layout(ctx, bc, some_data, env);
+            } else if animating {
+                some.layout(ctx, bc, some_data, env);
+            }
+        }
+        if let Some(none) = &mut self.none {
+            if !self.is_some {
+                size = none.layout(ctx, bc, &(), env);
+            } else if animating {
+                none.layout(ctx, bc, &(), env);
+            }
+        }
+
+        let (old_offset, new_offset) = transition.offsets(self.progress.get(), size);
+        let (some_offset, none_offset) = if self.is_some {
+            (new_offset, old_offset)
+        } else {
+            (old_offset, new_offset)
+        };
+        if let Some(some) = &mut self.some {
+            some.set_origin(ctx, some_offset);
+        }
+        if let Some(none) = &mut self.none {
+            none.set_origin(ctx, none_offset);
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &Option<T>, env: &Env) {
+        let transition = self.transition.unwrap_or_default();
+        let animating = self.progress.animating();
+        let some_data = &self.some_data;
+        let some = self
+            .some
+            .as_mut()
+            .zip(some_data.as_ref())
+            .map(|(some, data)| move |ctx: &mut PaintCtx| some.paint(ctx, data, env));
+        let none = self
+            .none
+            .as_mut()
+            .map(|none| move |ctx: &mut PaintCtx| none.paint(ctx, &(), env));
+
+        if self.is_some {
+            let paint_old = none.filter(|_| animating);
+            transition.paint(ctx, self.progress.get(), env, paint_old, |ctx| {
+                if let Some(some) = some {
+                    some(ctx);
+                }
+            });
+        } else {
+            let paint_old = some.filter(|_| animating);
+            transition.paint(ctx, self.progress.get(), env, paint_old, |ctx| {
+                if let Some(none) = none {
+                    none(ctx);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-window modal dialog.
+
+use std::any::Any;
+use std::rc::Rc;
+
+use druid::widget::prelude::*;
+use druid::{Color, HotKey, KbKey, KeyOrValue, Point, SysMods, Target, WidgetPod};
+
+crate::selectors! {
+    /// Open the dialog. Can be sent to the [`Modal`] as a command, or submitted as a
+    /// notification by any widget it wraps.
+    MODAL_SHOW,
+    /// Close the dialog without a result. Can be sent to the [`Modal`] as a command, or
+    /// submitted as a notification from inside the dialog.
+    MODAL_CLOSE,
+    /// Close the dialog with a result.
+    ///
+    /// Submit this as a notification from inside the dialog. The [`Modal`] closes the dialog and
+    /// re-sends it as a command to the widgets it wraps.
+    MODAL_RESULT: ModalResult,
+}
+
+/// The value a dialog was closed with, see [`MODAL_RESULT`].
+#[derive(Clone)]
+pub struct ModalResult(Rc<dyn Any>);
+
+impl ModalResult {
+    /// Wrap `value`, which the widgets getting [`MODAL_RESULT`] take back out with [`get`].
+    ///
+    /// [`get`]: ModalResult::get
+    pub fn new(value: impl Any) -> Self {
+        Self(Rc::new(value))
+    }
+
+    /// Returns the result if it is of type `R`.
+    pub fn get<R: Any>(&self) -> Option<&R> {
+        self.0.downcast_ref()
+    }
+}
+
+type DialogFn<T> = Box<dyn Fn(&T, &Env) -> Box<dyn Widget<T>>>;
+
+/// A widget that shows a dialog on top of its child, inside the same window.
+///
+/// While the dialog is open the child is dimmed and receives no mouse or keyboard input. Tab
+/// focus is trapped inside the dialog and pressing Escape closes it.
+pub struct Modal<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    dialog: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    make_dialog: DialogFn<T>,
+    dim_color: KeyOrValue<Color>,
+    close_on_escape: bool,
+}
+
+impl<T: Data> Modal<T> {
+    /// Create a new `Modal` around `inner`. `make_dialog` is called every time the dialog
+    /// is opened.
+    pub fn new<W: Widget<T> + 'static>(
+        inner: impl Widget<T> + 'static,
+        make_dialog: impl Fn(&T, &Env) -> W + 'static,
+    ) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            dialog: None,
+            make_dialog: Box::new(move |data, env| Box::new(make_dialog(data, env))),
+            dim_color: Color::rgba8(0, 0, 0, 0x80).into(),
+            close_on_escape: true,
+        }
+    }
+
+    /// Builder-style method for setting the color painted over the child while the dialog is
+    /// open.
+    pub fn with_dim_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_dim_color(color);
+        self
+    }
+
+    /// Set the color painted over the child while the dialog is open.
+    pub fn set_dim_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.dim_color = color.into();
+    }
+
+    /// Builder-style method for setting whether Escape closes the dialog.
+    pub fn with_close_on_escape(mut self, close: bool) -> Self {
+        self.set_close_on_escape(close);
+        self
+    }
+
+    /// Set whether Escape closes the dialog.
+    pub fn set_close_on_escape(&mut self, close: bool) {
+        self.close_on_escape = close;
+    }
+
+    /// Returns `true` if the dialog is currently open.
+    pub fn is_open(&self) -> bool {
+        self.dialog.is_some()
+    }
+
+    fn show(&mut self, ctx: &mut EventCtx, data: &T, env: &Env) {
+        if self.dialog.is_some() {
+            return;
+        }
+        self.dialog = Some(WidgetPod::new((self.make_dialog)(data, env)));
+        // Take focus away from the child. Tab from here moves into the dialog.
+        ctx.request_focus();
+        ctx.children_changed();
+    }
+
+    fn close(&mut self, ctx: &mut EventCtx) {
+        if self.dialog.take().is_some() {
+            ctx.resign_focus();
+            ctx.children_changed();
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Modal<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(MODAL_SHOW) => {
+                self.show(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(cmd) if cmd.is(MODAL_SHOW) => {
+                self.show(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+            Event::Command(cmd) if cmd.is(MODAL_CLOSE) => {
+                self.close(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(cmd) if cmd.is(MODAL_CLOSE) => {
+                self.close(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(cmd) => {
+                if let Some(result) = cmd.get(MODAL_RESULT) {
+                    let inner_cmd = MODAL_RESULT.with(result.clone()).to(Target::Global);
+                    self.close(ctx);
+                    // handled events are not passed on, so the child gets it first
+                    self.inner.event(ctx, &Event::Command(inner_cmd), data, env);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        let dialog = match &mut self.dialog {
+            Some(dialog) => dialog,
+            None => {
+                self.inner.event(ctx, event, data, env);
+                return;
+            }
+        };
+
+        dialog.event(ctx, event, data, env);
+        // the child only gets events which are not user input
+        if event.should_propagate_to_hidden() {
+            self.inner.event(ctx, event, data, env);
+        }
+
+        if let Event::KeyDown(key) = event {
+            if ctx.is_handled() {
+                return;
+            }
+            if self.close_on_escape && HotKey::new(None, KbKey::Escape).matches(key) {
+                self.close(ctx);
+                ctx.set_handled();
+            } else if ctx.is_focused() {
+                // Nothing inside the dialog is focused yet, jump into its focus chain.
+                if HotKey::new(None, KbKey::Tab).matches(key) {
+                    ctx.focus_next();
+                    ctx.set_handled();
+                } else if HotKey::new(SysMods::Shift, KbKey::Tab).matches(key) {
+                    ctx.focus_prev();
+                    ctx.set_handled();
+                }
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match &mut self.dialog {
+            Some(dialog) => {
+                dialog.lifecycle(ctx, event, data, env);
+                // leaving the child out of the focus chain traps Tab inside the dialog
+                if !matches!(event, LifeCycle::BuildFocusChain) {
+                    self.inner.lifecycle(ctx, event, data, env);
+                }
+            }
+            None => self.inner.lifecycle(ctx, event, data, env),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+        if let Some(dialog) = &mut self.dialog {
+            dialog.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        if let Some(dialog) = &mut self.dialog {
+            let dialog_bc = BoxConstraints::new(Size::ZERO, size);
+            let dialog_size = dialog.layout(ctx, &dialog_bc, data, env);
+            let origin = Point::new(
+                (size.width - dialog_size.width) / 2.,
+                (size.height - dialog_size.height) / 2.,
+            );
+            dialog.set_origin(ctx, origin);
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+        if let Some(dialog) = &mut self.dialog {
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &self.dim_color.resolve(env));
+            dialog.paint(ctx, data, env);
+        }
+    }
+}
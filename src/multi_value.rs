@@ -1,12 +1,16 @@
 use crate::animation::{Animated, AnimationCurve, Interpolate};
 use crate::prism::{DisablePrismWrap, OptionSome, Prism};
-use druid::theme::WIDGET_PADDING_VERTICAL;
-use druid::widget::{Checkbox, Radio};
+use druid::kurbo::{BezPath, Line};
+use druid::piet::{LineCap, LineJoin, LinearGradient, StrokeStyle, UnitPoint};
+use druid::theme::{self, WIDGET_PADDING_VERTICAL};
+use druid::widget::{Checkbox, CrossAxisAlignment, Flex, Label, LabelText, Radio};
 use druid::{
-    BoxConstraints, Data, Env, Event, EventCtx, Key, KeyOrValue, LayoutCtx, LifeCycle,
-    LifeCycleCtx, PaintCtx, Point, RenderContext, Size, UpdateCtx, Vec2, Widget, WidgetPod,
+    BoxConstraints, Data, Env, Event, EventCtx, Key, KeyOrValue, LayoutCtx, Lens, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, RenderContext, Size, UpdateCtx, Vec2, Widget, WidgetExt,
+    WidgetPod,
 };
 use std::fmt::Debug;
+use std::rc::Rc;
 
 ///A Radio which has further configuration for the value it represents
 pub struct MultiRadio<W, T, U, P> {
@@ -66,6 +70,17 @@ where
         self
     }
 
+    /// Set whether the inner widget is placed next to the button instead of below it.
+    pub fn set_horizontal(&mut self, horizontal: bool) {
+        self.layout.horizontal = horizontal;
+    }
+
+    /// Builder-style method to place the inner widget next to the button instead of below it.
+    pub fn horizontal(mut self) -> Self {
+        self.layout.horizontal = true;
+        self
+    }
+
     /// A Builder-style method to set the duration for the transition
     /// between shown and hidden.
     pub fn set_transition_duration(&mut self, duration: f64) {
@@ -227,6 +242,17 @@ where
         self
     }
 
+    /// Set whether the inner widget is placed next to the button instead of below it.
+    pub fn set_horizontal(&mut self, horizontal: bool) {
+        self.layout.horizontal = horizontal;
+    }
+
+    /// Builder-style method to place the inner widget next to the button instead of below it.
+    pub fn horizontal(mut self) -> Self {
+        self.layout.horizontal = true;
+        self
+    }
+
     /// A Builder-style method to set the duration for the transition
     /// between shown and hidden.
     pub fn set_transition_duration(&mut self, duration: f64) {
@@ -353,12 +379,370 @@ where
     }
 }
 
+/// A leaf option of a [`CheckboxGroup`], shared with the groups containing it.
+struct GroupOption<T> {
+    get: Rc<dyn Fn(&T) -> bool>,
+    set: Rc<dyn Fn(&mut T, bool)>,
+    enabled: Option<Rc<dyn Fn(&T, &Env) -> bool>>,
+}
+
+impl<T> Clone for GroupOption<T> {
+    fn clone(&self) -> Self {
+        GroupOption {
+            get: self.get.clone(),
+            set: self.set.clone(),
+            enabled: self.enabled.clone(),
+        }
+    }
+}
+
+impl<T> GroupOption<T> {
+    fn is_enabled(&self, data: &T, env: &Env) -> bool {
+        self.enabled
+            .as_ref()
+            .map_or(true, |enabled| enabled(data, env))
+    }
+}
+
+/// A checkbox selecting or clearing a group of options, which are indented below it.
+///
+/// The checkbox is checked when all options are, and shows a dash when only some are. Groups can
+/// be nested, and options can be disabled depending on the data, in which case the group checkbox
+/// leaves them alone.
+///
+/// ```
+/// # use druid::{Data, Lens, Widget};
+/// # use druid_widget_nursery::CheckboxGroup;
+/// #[derive(Clone, Data, Lens)]
+/// struct Toppings {
+///     cheese: bool,
+///     ham: bool,
+///     pineapple: bool,
+///     vegetarian: bool,
+/// }
+///
+/// fn toppings() -> impl Widget<Toppings> {
+///     CheckboxGroup::new("All toppings")
+///         .with_option("Cheese", Toppings::cheese)
+///         .with_option_enabled_if("Ham", Toppings::ham, |data: &Toppings, _| !data.vegetarian)
+///         .with_option("Pineapple", Toppings::pineapple)
+/// }
+/// ```
+pub struct CheckboxGroup<T> {
+    check_box: WidgetPod<Option<bool>, TriStateCheckbox>,
+    /// The options, built once the direction is known.
+    children: Vec<Box<dyn Widget<T>>>,
+    body: Option<WidgetPod<T, Flex<T>>>,
+    options: Vec<GroupOption<T>>,
+    layout: IndentLayout,
+}
+
+impl<T: Data> CheckboxGroup<T> {
+    /// Create an empty group with the label of its checkbox.
+    pub fn new(name: impl Into<LabelText<()>>) -> Self {
+        let mut layout = IndentLayout::new();
+        layout.always_visible = true;
+        CheckboxGroup {
+            check_box: WidgetPod::new(TriStateCheckbox::new(name)),
+            children: Vec::new(),
+            body: None,
+            options: Vec::new(),
+            layout,
+        }
+    }
+
+    /// Builder-style method for adding an option, checked when `lens` is `true`.
+    pub fn with_option(
+        self,
+        name: impl Into<LabelText<bool>>,
+        lens: impl Lens<T, bool> + Clone + 'static,
+    ) -> Self {
+        self.add_option(name, lens, None)
+    }
+
+    /// Builder-style method for adding an option which is only enabled when `enabled` returns
+    /// `true`.
+    pub fn with_option_enabled_if(
+        self,
+        name: impl Into<LabelText<bool>>,
+        lens: impl Lens<T, bool> + Clone + 'static,
+        enabled: impl Fn(&T, &Env) -> bool + 'static,
+    ) -> Self {
+        self.add_option(name, lens, Some(Rc::new(enabled)))
+    }
+
+    /// Builder-style method for nesting a group. Its options also belong to this group.
+    pub fn with_group(mut self, group: CheckboxGroup<T>) -> Self {
+        self.options.extend(group.options.iter().cloned());
+        self.children.push(Box::new(group));
+        self
+    }
+
+    /// Builder-style method to lay out the options in a row instead of a column.
+    pub fn horizontal(mut self) -> Self {
+        self.layout.horizontal = true;
+        self
+    }
+
+    /// Set the indent of the options
+    pub fn set_indent(&mut self, indent: impl Into<KeyOrValue<f64>>) {
+        self.layout.indent = indent.into();
+    }
+
+    /// Builder-style method to set the indent of the options
+    pub fn with_indent(mut self, indent: impl Into<KeyOrValue<f64>>) -> Self {
+        self.layout.indent = indent.into();
+        self
+    }
+
+    fn add_option(
+        mut self,
+        name: impl Into<LabelText<bool>>,
+        lens: impl Lens<T, bool> + Clone + 'static,
+        enabled: Option<Rc<dyn Fn(&T, &Env) -> bool>>,
+    ) -> Self {
+        let get_lens = lens.clone();
+        let set_lens = lens.clone();
+        let option = GroupOption {
+            get: Rc::new(move |data| get_lens.with(data, |checked| *checked)),
+            set: Rc::new(move |data, checked| set_lens.with_mut(data, |value| *value = checked)),
+            enabled,
+        };
+
+        let check_box = Checkbox::new(name).lens(lens);
+        match option.enabled.clone() {
+            Some(enabled) => self.children.push(Box::new(
+                check_box.disabled_if(move |data, env| !enabled(data, env)),
+            )),
+            None => self.children.push(Box::new(check_box)),
+        }
+        self.options.push(option);
+        self
+    }
+
+    /// `Some` if all options are checked or all are unchecked, `None` for a mix.
+    fn state(&self, data: &T) -> Option<bool> {
+        let mut options = self.options.iter().map(|option| (option.get)(data));
+        let first = options.next()?;
+        if options.all(|checked| checked == first) {
+            Some(first)
+        } else {
+            None
+        }
+    }
+
+    fn build_body(&mut self) {
+        let mut body = if self.layout.horizontal {
+            Flex::row()
+        } else {
+            Flex::column().cross_axis_alignment(CrossAxisAlignment::Start)
+        };
+        for (index, child) in self.children.drain(..).enumerate() {
+            if index > 0 {
+                body.add_default_spacer();
+            }
+            body.add_child(child);
+        }
+        self.body = Some(WidgetPod::new(body));
+    }
+}
+
+impl<T: Data> Widget<T> for CheckboxGroup<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let state = self.state(data);
+        let mut new_state = state;
+        self.check_box.event(ctx, event, &mut new_state, env);
+        if new_state != state {
+            let checked = new_state.unwrap_or(false);
+            for option in &self.options {
+                if option.is_enabled(data, env) {
+                    (option.set)(data, checked);
+                }
+            }
+        }
+
+        if let Some(body) = &mut self.body {
+            body.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.build_body();
+            self.layout.init_visible(true);
+        }
+        self.check_box.lifecycle(ctx, event, &self.state(data), env);
+        if let Some(body) = &mut self.body {
+            body.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.check_box.update(ctx, &self.state(data), env);
+        if let Some(body) = &mut self.body {
+            body.update(ctx, data, env);
+        }
+        self.layout.update_values(ctx, true);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let state = self.state(data);
+        match &mut self.body {
+            Some(body) => self
+                .layout
+                .layout(&mut self.check_box, body, &state, data, ctx, bc, env),
+            None => bc.min(),
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let state = self.state(data);
+        if let Some(body) = &mut self.body {
+            self.layout
+                .paint(&mut self.check_box, body, &state, data, ctx, env);
+        }
+    }
+}
+
+/// A checkbox which can also show a dash, for a mix of checked and unchecked options.
+struct TriStateCheckbox {
+    label: Label<()>,
+}
+
+impl TriStateCheckbox {
+    fn new(name: impl Into<LabelText<()>>) -> Self {
+        TriStateCheckbox {
+            label: Label::new(name),
+        }
+    }
+}
+
+impl Widget<Option<bool>> for TriStateCheckbox {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Option<bool>, _env: &Env) {
+        match event {
+            Event::MouseDown(_) if !ctx.is_disabled() => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() {
+                    // a mix becomes all checked
+                    *data = Some(*data != Some(true));
+                }
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Option<bool>,
+        env: &Env,
+    ) {
+        self.label.lifecycle(ctx, event, &(), env);
+        if let LifeCycle::HotChanged(_) | LifeCycle::DisabledChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &Option<bool>,
+        _data: &Option<bool>,
+        env: &Env,
+    ) {
+        self.label.update(ctx, &(), &(), env);
+        ctx.request_paint();
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Option<bool>,
+        env: &Env,
+    ) -> Size {
+        let x_padding = env.get(theme::WIDGET_CONTROL_COMPONENT_PADDING);
+        let check_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let label_size = self.label.layout(ctx, bc, &(), env);
+
+        let desired_size = Size::new(
+            check_size + x_padding + label_size.width,
+            check_size.max(label_size.height),
+        );
+        let our_size = bc.constrain(desired_size);
+        let baseline = self.label.baseline_offset() + (our_size.height - label_size.height);
+        ctx.set_baseline_offset(baseline);
+        our_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Option<bool>, env: &Env) {
+        let size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let x_padding = env.get(theme::WIDGET_CONTROL_COMPONENT_PADDING);
+        let border_width = 1.;
+
+        let rect = Size::new(size, size)
+            .to_rect()
+            .inset(-border_width / 2.)
+            .to_rounded_rect(2.);
+
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                env.get(theme::BACKGROUND_LIGHT),
+                env.get(theme::BACKGROUND_DARK),
+            ),
+        );
+        ctx.fill(rect, &background_gradient);
+
+        let border_color = if ctx.is_hot() && !ctx.is_disabled() {
+            env.get(theme::BORDER_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(rect, &border_color, border_width);
+
+        let mark_color = if ctx.is_disabled() {
+            env.get(theme::DISABLED_TEXT_COLOR)
+        } else {
+            env.get(theme::TEXT_COLOR)
+        };
+        let style = StrokeStyle::new()
+            .line_cap(LineCap::Round)
+            .line_join(LineJoin::Round);
+        match data {
+            Some(true) => {
+                let mut path = BezPath::new();
+                path.move_to((4.0, 9.0));
+                path.line_to((8.0, 13.0));
+                path.line_to((14.0, 5.0));
+                ctx.stroke_styled(path, &mark_color, 2., &style);
+            }
+            None => {
+                let dash = Line::new((size * 0.25, size / 2.), (size * 0.75, size / 2.));
+                ctx.stroke_styled(dash, &mark_color, 2., &style);
+            }
+            Some(false) => {}
+        }
+
+        self.label.draw_at(ctx, (size + x_padding, 0.0));
+    }
+}
+
 pub const INDENT: Key<f64> = Key::new("druid-widget-nursery.multivalue.indent");
 
 pub struct IndentLayout {
     space: KeyOrValue<f64>,
     indent: KeyOrValue<f64>,
     always_visible: bool,
+    /// Place the body next to the header, instead of indented below it.
+    horizontal: bool,
     height: Animated<f64>,
 }
 
@@ -368,6 +752,7 @@ impl IndentLayout {
             space: KeyOrValue::Key(WIDGET_PADDING_VERTICAL),
             indent: KeyOrValue::Key(INDENT),
             always_visible: false,
+            horizontal: false,
             height: Animated::new(0.0)
                 .duration(0.2)
                 .curve(AnimationCurve::EASE_OUT)
@@ -415,6 +800,24 @@ impl IndentLayout {
         let radio_size = header.layout(ctx, bc, data_a, env);
         header.set_origin(ctx, Point::ZERO);
 
+        if self.horizontal {
+            let inner_origin = Vec2::new(radio_size.width + self.space.resolve(env), 0.0);
+            let inner_bc = bc.shrink((inner_origin.x, 0.0));
+            let inner_size = body.layout(ctx, &inner_bc, data_b, env);
+            body.set_origin(ctx, inner_origin.to_point());
+
+            return if !inner_size.is_empty() {
+                Size::new(
+                    radio_size
+                        .width
+                        .interpolate(&(inner_origin.x + inner_size.width), self.height.get()),
+                    radio_size.height.max(inner_size.height),
+                )
+            } else {
+                radio_size
+            };
+        }
+
         let inner_origin = Vec2::new(
             self.indent.resolve(env),
             radio_size.height + self.space.resolve(env),
@@ -0,0 +1,308 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use druid::widget::prelude::*;
+use druid::widget::TextBox;
+use druid::TextAlignment;
+
+use crate::step_input::{InputFormat, Step, StepInput};
+
+/// A number that can be edited by a [`NumberInput`].
+pub trait NumberValue: Data + Copy + PartialOrd + FromStr + Display {
+    /// The default step.
+    const ONE: Self;
+
+    /// Returns `self + step`, saturating at the bounds of the type.
+    fn step_up(self, step: Self) -> Self;
+
+    /// Returns `self - step`, saturating at the bounds of the type.
+    fn step_down(self, step: Self) -> Self;
+}
+
+macro_rules! impl_number_value {
+    (int: $($ty:ty),*) => {
+        $(impl NumberValue for $ty {
+            const ONE: Self = 1;
+
+            fn step_up(self, step: Self) -> Self {
+                self.saturating_add(step)
+            }
+
+            fn step_down(self, step: Self) -> Self {
+                self.saturating_sub(step)
+            }
+        })*
+    };
+    (float: $($ty:ty),*) => {
+        $(impl NumberValue for $ty {
+            const ONE: Self = 1.0;
+
+            fn step_up(self, step: Self) -> Self {
+                self + step
+            }
+
+            fn step_down(self, step: Self) -> Self {
+                self - step
+            }
+        })*
+    };
+}
+
+impl_number_value!(int: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_number_value!(float: f32, f64);
+
+/// Returns the decimal separator of the locale in `LC_ALL`, `LC_NUMERIC` or `LANG`, or `.` if
+/// none of them is set.
+fn system_decimal_separator() -> char {
+    // languages writing 1.5 as "1,5"
+    const COMMA_LANGUAGES: &[&str] = &[
+        "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "is", "it",
+        "lt", "lv", "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr",
+        "uk", "vi",
+    ];
+
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty());
+    match locale {
+        Some(locale) => {
+            let language = locale.split(|c| c == '_' || c == '.').next().unwrap_or("");
+            if COMMA_LANGUAGES.contains(&language) {
+                ','
+            } else {
+                '.'
+            }
+        }
+        None => '.',
+    }
+}
+
+struct NumberFormat<T> {
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+    precision: Option<usize>,
+    decimal_separator: char,
+}
+
+impl<T: NumberValue> InputFormat<T> for NumberFormat<T> {
+    fn parse(&self, text: &str) -> Option<T> {
+        let group_separator = if self.decimal_separator == '.' {
+            ','
+        } else {
+            '.'
+        };
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+        text.parse().ok()
+    }
+
+    fn format(&self, value: &T) -> String {
+        let text = match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        };
+        text.replace('.', &self.decimal_separator.to_string())
+    }
+
+    fn step(&self, value: &T, step: Step, _large: bool) -> T {
+        match step {
+            Step::Up => value.step_up(self.step),
+            Step::Down => value.step_down(self.step),
+        }
+    }
+
+    fn is_valid(&self, value: &T) -> bool {
+        self.min.map_or(true, |min| *value >= min) && self.max.map_or(true, |max| *value <= max)
+    }
+
+    fn clamp(&self, mut value: T) -> T {
+        if let Some(min) = self.min {
+            if value < min {
+                value = min;
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max {
+                value = max;
+            }
+        }
+        value
+    }
+}
+
+/// A text box for numbers, with buttons for stepping the value up and down.
+///
+/// The value can also be stepped with the arrow keys and the mouse wheel. Text that doesn't parse
+/// or is out of range is highlighted, and replaced by the last valid value when the text box
+/// loses focus or Enter is pressed; values out of range are clamped then.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::NumberInput;
+/// fn volume() -> impl Widget<f64> {
+///     NumberInput::new()
+///         .with_range(0.0, 10.0)
+///         .with_step(0.5)
+///         .with_precision(1)
+/// }
+/// ```
+pub struct NumberInput<T> {
+    input: StepInput<T, NumberFormat<T>>,
+}
+
+impl<T: NumberValue> NumberInput<T> {
+    /// Create a number input using the decimal separator of the system.
+    pub fn new() -> Self {
+        NumberInput {
+            input: StepInput::new(
+                NumberFormat {
+                    min: None,
+                    max: None,
+                    step: T::ONE,
+                    precision: None,
+                    decimal_separator: system_decimal_separator(),
+                },
+                TextBox::new().with_text_alignment(TextAlignment::End),
+            ),
+        }
+    }
+
+    /// Builder-style method for specifying the minimum and maximum value.
+    ///
+    /// For the non-builder varient, see [`set_range`].
+    ///
+    /// [`set_range`]: #method.set_range
+    pub fn with_range(mut self, min: T, max: T) -> Self {
+        self.set_range(min, max);
+        self
+    }
+
+    /// Set the minimum and maximum value.
+    pub fn set_range(&mut self, min: T, max: T) {
+        self.input.format.min = Some(min);
+        self.input.format.max = Some(max);
+    }
+
+    /// Builder-style method for specifying the minimum value.
+    ///
+    /// For the non-builder varient, see [`set_min`].
+    ///
+    /// [`set_min`]: #method.set_min
+    pub fn with_min(mut self, min: T) -> Self {
+        self.set_min(Some(min));
+        self
+    }
+
+    /// Set the minimum value.
+    pub fn set_min(&mut self, min: Option<T>) {
+        self.input.format.min = min;
+    }
+
+    /// Builder-style method for specifying the maximum value.
+    ///
+    /// For the non-builder varient, see [`set_max`].
+    ///
+    /// [`set_max`]: #method.set_max
+    pub fn with_max(mut self, max: T) -> Self {
+        self.set_max(Some(max));
+        self
+    }
+
+    /// Set the maximum value.
+    pub fn set_max(&mut self, max: Option<T>) {
+        self.input.format.max = max;
+    }
+
+    /// Builder-style method for specifying the step.
+    ///
+    /// For the non-builder varient, see [`set_step`].
+    ///
+    /// [`set_step`]: #method.set_step
+    pub fn with_step(mut self, step: T) -> Self {
+        self.set_step(step);
+        self
+    }
+
+    /// Set the amount added or subtracted by the buttons, the arrow keys and the mouse wheel.
+    pub fn set_step(&mut self, step: T) {
+        self.input.format.step = step;
+    }
+
+    /// Builder-style method for specifying the number of decimal places shown.
+    ///
+    /// For the non-builder varient, see [`set_precision`].
+    ///
+    /// [`set_precision`]: #method.set_precision
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.set_precision(Some(precision));
+        self
+    }
+
+    /// Set the number of decimal places shown for floats, or `None` to show as many as needed.
+    pub fn set_precision(&mut self, precision: Option<usize>) {
+        self.input.format.precision = precision;
+    }
+
+    /// Builder-style method for specifying the decimal separator.
+    ///
+    /// For the non-builder varient, see [`set_decimal_separator`].
+    ///
+    /// [`set_decimal_separator`]: #method.set_decimal_separator
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.set_decimal_separator(separator);
+        self
+    }
+
+    /// Set the decimal separator, usually `.` or `,`. The other one is ignored as a digit group
+    /// separator when parsing.
+    pub fn set_decimal_separator(&mut self, separator: char) {
+        self.input.format.decimal_separator = separator;
+    }
+}
+
+impl<T: NumberValue> Default for NumberInput<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: NumberValue> Widget<T> for NumberInput<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.input.event(ctx, event, data, env)
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.input.lifecycle(ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.input.update(ctx, old_data, data, env)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.input.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.input.paint(ctx, data, env)
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rubber-band overscroll and pull-to-refresh around a scrolling widget.
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use druid::kurbo::Arc;
+use druid::widget::prelude::*;
+use druid::{theme, Point, Selector, TimerToken, Vec2, WidgetPod};
+
+use crate::animation::{reduced_motion, Animated, AnimationCurve};
+
+/// Submitted by an [`Overscroll`] when it was pulled far enough to refresh, with its id. It shows
+/// that it is refreshing until it gets [`REFRESH_DONE`].
+pub const REFRESH: Selector<WidgetId> = Selector::new("druid-widget-nursery.overscroll.refresh");
+
+/// Tells the [`Overscroll`] this is sent to that the refresh is done.
+pub const REFRESH_DONE: Selector = Selector::new("druid-widget-nursery.overscroll.refresh-done");
+
+/// The distance overscroll approaches, however far it's pulled.
+const MAX_PULL: f64 = 160.0;
+/// How long without wheel events ends a pull made with the wheel or a touchpad.
+const RELEASE_DELAY: Duration = Duration::from_millis(150);
+const SPINNER_RADIUS: f64 = 10.0;
+
+/// The distance shown for a pull of `raw`, resisting more the farther it goes.
+fn rubber_band(raw: f64) -> f64 {
+    let shown = MAX_PULL * (1.0 - 1.0 / (1.0 + raw.abs() * 0.55 / MAX_PULL));
+    shown.copysign(raw)
+}
+
+/// A wrapper letting its child be pulled past its ends, springing back when it's released.
+///
+/// Pulls are made with the wheel or a touchpad when the child doesn't scroll anymore, or by
+/// dragging where the child doesn't handle the mouse, as touch screens do. With
+/// [`with_refresh`], pulling the top down past a threshold submits [`REFRESH`], and a spinner
+/// shows until [`REFRESH_DONE`] is sent back.
+///
+/// ```
+/// # use druid::widget::{List, Label, Scroll};
+/// # use druid::im::Vector;
+/// # use druid::{Widget, WidgetExt};
+/// # use druid_widget_nursery::overscroll::Overscroll;
+/// fn feed() -> impl Widget<Vector<String>> {
+///     let list = List::new(|| Label::dynamic(|item: &String, _| item.clone()));
+///     Overscroll::new(Scroll::new(list).vertical()).with_refresh(true)
+/// }
+/// ```
+///
+/// [`with_refresh`]: #method.with_refresh
+pub struct Overscroll<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    refresh: bool,
+    threshold: f64,
+    /// How far the pull went, before it was made to resist.
+    raw: f64,
+    /// The distance shown, positive at the top.
+    pull: Animated<f64>,
+    /// Where a drag started, with the pull then.
+    drag: Option<(f64, f64)>,
+    release_timer: TimerToken,
+    refreshing: bool,
+    spinner_angle: f64,
+}
+
+impl<T: Data> Overscroll<T> {
+    pub fn new(child: impl Widget<T> + 'static) -> Self {
+        Overscroll {
+            child: WidgetPod::new(Box::new(child)),
+            refresh: false,
+            threshold: 64.0,
+            raw: 0.0,
+            pull: Animated::jump(0.0)
+                .duration(0.3)
+                .curve(AnimationCurve::EASE_OUT)
+                .layout(true),
+            drag: None,
+            release_timer: TimerToken::INVALID,
+            refreshing: false,
+            spinner_angle: 0.0,
+        }
+    }
+
+    /// Builder-style method for enabling pull-to-refresh.
+    ///
+    /// For the non-builder varient, see [`set_refresh`].
+    ///
+    /// [`set_refresh`]: #method.set_refresh
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.set_refresh(refresh);
+        self
+    }
+
+    /// Set whether pulling the top down refreshes.
+    pub fn set_refresh(&mut self, refresh: bool) {
+        self.refresh = refresh;
+    }
+
+    /// Builder-style method for setting how far the top has to be pulled to refresh.
+    ///
+    /// For the non-builder varient, see [`set_threshold`].
+    ///
+    /// [`set_threshold`]: #method.set_threshold
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.set_threshold(threshold);
+        self
+    }
+
+    /// Set how far the top has to be pulled to refresh, 64 by default.
+    pub fn set_threshold(&mut self, threshold: f64) {
+        self.threshold = threshold.max(1.0).min(MAX_PULL * 0.9);
+    }
+
+    fn pull_to(&mut self, ctx: &mut EventCtx, raw: f64) {
+        self.raw = raw;
+        let mut shown = rubber_band(raw);
+        if self.refreshing {
+            // the spinner stays visible
+            shown += self.threshold;
+        }
+        self.pull.jump_to_value(shown);
+        ctx.request_layout();
+    }
+
+    fn release(&mut self, ctx: &mut EventCtx) {
+        let shown = rubber_band(self.raw);
+        self.raw = 0.0;
+        if self.refresh && !self.refreshing && shown >= self.threshold {
+            self.refreshing = true;
+            ctx.submit_command(REFRESH.with(ctx.widget_id()));
+            ctx.request_anim_frame();
+        }
+        let rest = if self.refreshing { self.threshold } else { 0.0 };
+        self.pull.animate(ctx, rest);
+    }
+}
+
+impl<T: Data> Widget<T> for Overscroll<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::AnimFrame(nanos) => {
+                if self.pull.animating() {
+                    self.pull.update(ctx, *nanos);
+                }
+                // with reduced motion the spinner stays still
+                if self.refreshing && !reduced_motion() {
+                    self.spinner_angle =
+                        (self.spinner_angle + *nanos as f64 * 1e-9 * 2.0 * PI) % (2.0 * PI);
+                    ctx.request_anim_frame();
+                    ctx.request_paint();
+                }
+            }
+            Event::Timer(token) if *token == self.release_timer => {
+                self.release(ctx);
+                return;
+            }
+            Event::Command(cmd) if cmd.is(REFRESH_DONE) => {
+                self.refreshing = false;
+                if self.drag.is_none() && self.raw == 0.0 {
+                    self.pull.animate(ctx, 0.0);
+                }
+                ctx.set_handled();
+                return;
+            }
+            // scroll the pull back before the child
+            Event::Wheel(wheel) if self.raw != 0.0 => {
+                let mut raw = self.raw - wheel.wheel_delta.y;
+                if raw.signum() != self.raw.signum() {
+                    raw = 0.0;
+                }
+                self.pull_to(ctx, raw);
+                self.release_timer = ctx.request_timer(RELEASE_DELAY);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse) if self.drag.is_some() => {
+                let (start, raw) = self.drag.unwrap();
+                self.pull_to(ctx, raw + mouse.pos.y - start);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseUp(_) if self.drag.is_some() => {
+                self.drag = None;
+                ctx.set_active(false);
+                self.release(ctx);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        self.child.event(ctx, event, data, env);
+        if ctx.is_handled() {
+            return;
+        }
+        match event {
+            Event::Wheel(wheel) if wheel.wheel_delta.y != 0.0 => {
+                // the child is at its end
+                self.pull_to(ctx, -wheel.wheel_delta.y);
+                self.release_timer = ctx.request_timer(RELEASE_DELAY);
+                ctx.set_handled();
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                self.drag = Some((mouse.pos.y, self.raw));
+                ctx.set_active(true);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, Point::new(0.0, self.pull.get()));
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let pull = self.pull.get();
+        let clip = size.to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            if self.refresh && pull > 0.0 {
+                let center = Point::new(size.width / 2.0, pull / 2.0);
+                let color = env.get(theme::PRIMARY_LIGHT);
+                let (start, sweep) = if self.refreshing {
+                    (self.spinner_angle, 1.5 * PI)
+                } else {
+                    // the circle closes as the pull reaches the threshold
+                    let progress = (pull / self.threshold).min(1.0);
+                    (-PI / 2.0, 1.8 * PI * progress)
+                };
+                let alpha = (pull / self.threshold).min(1.0);
+                let arc = Arc {
+                    center,
+                    radii: Vec2::new(SPINNER_RADIUS, SPINNER_RADIUS),
+                    start_angle: start,
+                    sweep_angle: sweep,
+                    x_rotation: 0.0,
+                };
+                ctx.stroke(arc, &color.with_alpha(alpha), 2.5);
+            }
+            self.child.paint(ctx, data, env);
+        });
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeping app data in files across runs.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::{Point, Selector, TimerToken, WidgetPod};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::warn;
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Sent by a [`Persisted`] to itself, as `lifecycle` can't change the data.
+const APPLY_LOADED: Selector = Selector::new("druid-widget-nursery.persisted.apply-loaded");
+
+/// A widget keeping its data in a file, like the window size, split ratios or recent files.
+///
+/// When the widget is added, the data is replaced with the saved one, if there is a saved one.
+/// Changes are saved once the data stays the same for the [debounce] delay, and when the widget
+/// is dropped. The file is JSON, in the platform's config directory by default.
+///
+/// Requires the `serde` feature.
+///
+/// ```no_run
+/// # use druid::widget::{Checkbox, Flex, TextBox};
+/// # use druid::{Data, Lens, Widget, WidgetExt};
+/// # use druid_widget_nursery::Persisted;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Data, Lens, Serialize, Deserialize)]
+/// struct Settings {
+///     name: String,
+///     dark_mode: bool,
+/// }
+///
+/// fn settings() -> impl Widget<Settings> {
+///     let form = Flex::column()
+///         .with_child(TextBox::new().lens(Settings::name))
+///         .with_child(Checkbox::new("Dark mode").lens(Settings::dark_mode));
+///     // kept in e.g. ~/.config/my-app/settings.json
+///     Persisted::new(form, "my-app", "settings")
+/// }
+/// ```
+///
+/// [debounce]: #method.with_debounce
+pub struct Persisted<T: Serialize, W> {
+    inner: WidgetPod<T, W>,
+    path: Option<PathBuf>,
+    debounce: Duration,
+    timer: TimerToken,
+    loaded: Option<T>,
+    /// The data changed since it was saved last.
+    unsaved: Option<T>,
+}
+
+impl<T: Data + Serialize + DeserializeOwned, W: Widget<T>> Persisted<T, W> {
+    /// Keep the data in `<config dir>/<app>/<name>.json`.
+    ///
+    /// Nothing is loaded or saved if the platform has no config directory.
+    pub fn new(inner: W, app: impl AsRef<Path>, name: &str) -> Self {
+        let path = dirs::config_dir().map(|dir| dir.join(app).join(format!("{}.json", name)));
+        if path.is_none() {
+            warn!("no config directory, {} won't be saved", name);
+        }
+        Persisted {
+            inner: WidgetPod::new(inner),
+            path,
+            debounce: DEFAULT_DEBOUNCE,
+            timer: TimerToken::INVALID,
+            loaded: None,
+            unsaved: None,
+        }
+    }
+
+    /// Keep the data in the file at `path`.
+    pub fn with_path(inner: W, path: impl Into<PathBuf>) -> Self {
+        Persisted {
+            inner: WidgetPod::new(inner),
+            path: Some(path.into()),
+            debounce: DEFAULT_DEBOUNCE,
+            timer: TimerToken::INVALID,
+            loaded: None,
+            unsaved: None,
+        }
+    }
+
+    /// Builder-style method for specifying how long the data has to stay the same before it is
+    /// saved.
+    ///
+    /// For the non-builder varient, see [`set_debounce`].
+    ///
+    /// [`set_debounce`]: #method.set_debounce
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.set_debounce(debounce);
+        self
+    }
+
+    /// Set how long the data has to stay the same before it is saved.
+    pub fn set_debounce(&mut self, debounce: Duration) {
+        self.debounce = debounce;
+    }
+
+    /// The file the data is kept in.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+impl<T: Serialize, W> Persisted<T, W> {
+    fn save(&mut self) {
+        if let (Some(path), Some(data)) = (&self.path, self.unsaved.take()) {
+            if let Err(err) = save(path, &data) {
+                warn!("failed to save {}: {}", path.display(), err);
+            }
+        }
+    }
+}
+
+/// Load data saved by a [`Persisted`] at `path`, like when building the initial app data.
+///
+/// Returns `None` if nothing was saved yet, or the file can't be read.
+pub fn load<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            warn!("failed to read {}: {}", path.display(), err);
+            return None;
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            warn!("failed to parse {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+fn save<T: Serialize>(path: &Path, data: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(data)?;
+    // write a temporary file first, so a crash can't leave half of a file behind
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+impl<T: Data + Serialize + DeserializeOwned, W: Widget<T>> Widget<T> for Persisted<T, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(APPLY_LOADED) => {
+                if let Some(loaded) = self.loaded.take() {
+                    *data = loaded;
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::Timer(token) if *token == self.timer => {
+                self.timer = TimerToken::INVALID;
+                self.save();
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.loaded = self.path.as_deref().and_then(load);
+            if self.loaded.is_some() {
+                ctx.submit_command(APPLY_LOADED.to(ctx.widget_id()));
+            }
+        }
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if !old_data.same(data) {
+            self.unsaved = Some(data.clone());
+            // restart the delay on every change
+            self.timer = ctx.request_timer(self.debounce);
+        }
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.inner.baseline_offset());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
+
+impl<T: Serialize, W> Drop for Persisted<T, W> {
+    fn drop(&mut self) {
+        // the app may close before the last change was saved
+        self.save();
+    }
+}
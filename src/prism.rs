@@ -1,8 +1,16 @@
+use std::marker::PhantomData;
+
 use druid::{
-    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, Lens, LifeCycle, LifeCycleCtx, PaintCtx,
     Point, Size, UpdateCtx, Widget, WidgetPod,
 };
 
+/// Derives a prism for every variant of an enum.
+///
+/// For `enum Shape { Circle(f64), Rect { width: f64, height: f64 }, Empty }` this generates the
+/// unit structs `ShapeCircle`, `ShapeRect` and `ShapeEmpty`, implementing `Prism<Shape, f64>`,
+/// `Prism<Shape, (f64, f64)>` and `Prism<Shape, ()>`. Variants with several fields, named or not,
+/// map to a tuple of the fields in the order they are declared.
 #[cfg(feature = "derive")]
 pub use druid_widget_nursery_derive::Prism;
 
@@ -20,6 +28,117 @@ pub trait Prism<T, U> {
     fn put(&self, data: &mut T, inner: U);
 }
 
+/// Combinators for [`Prism`]s, implemented for every prism.
+pub trait PrismExt<T, U>: Prism<T, U> + Sized {
+    /// Focus further on the data of this prism with another prism, like the payload of an enum
+    /// variant inside another variant.
+    fn then<V, P: Prism<U, V>>(self, other: P) -> Then<Self, P, U> {
+        Then::new(self, other)
+    }
+
+    /// Focus further on the data of this prism with a [`Lens`], like a field of the payload of
+    /// an enum variant.
+    fn then_lens<V, L: Lens<U, V>>(self, lens: L) -> ThenLens<Self, L, U> {
+        ThenLens::new(self, lens)
+    }
+}
+
+impl<T, U, P: Prism<T, U>> PrismExt<T, U> for P {}
+
+/// A [`Prism`] applying two prisms after each other, created by [`PrismExt::then`].
+#[derive(Debug, Clone, Copy)]
+pub struct Then<A, B, U> {
+    left: A,
+    right: B,
+    _marker: PhantomData<U>,
+}
+
+impl<A, B, U> Then<A, B, U> {
+    pub fn new(left: A, right: B) -> Self {
+        Then {
+            left,
+            right,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, U, V, A: Prism<T, U>, B: Prism<U, V>> Prism<T, V> for Then<A, B, U> {
+    fn get(&self, data: &T) -> Option<V> {
+        self.right.get(&self.left.get(data)?)
+    }
+
+    /// Does nothing if the outer prism has no data, since the rest of `U` is missing.
+    fn put(&self, data: &mut T, inner: V) {
+        if let Some(mut middle) = self.left.get(data) {
+            self.right.put(&mut middle, inner);
+            self.left.put(data, middle);
+        }
+    }
+}
+
+/// A [`Prism`] applying a prism and then a [`Lens`], created by [`PrismExt::then_lens`].
+#[derive(Debug, Clone, Copy)]
+pub struct ThenLens<P, L, U> {
+    prism: P,
+    lens: L,
+    _marker: PhantomData<U>,
+}
+
+impl<P, L, U> ThenLens<P, L, U> {
+    pub fn new(prism: P, lens: L) -> Self {
+        ThenLens {
+            prism,
+            lens,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, U, V: Clone, P: Prism<T, U>, L: Lens<U, V>> Prism<T, V> for ThenLens<P, L, U> {
+    fn get(&self, data: &T) -> Option<V> {
+        let middle = self.prism.get(data)?;
+        Some(self.lens.with(&middle, V::clone))
+    }
+
+    /// Does nothing if the prism has no data.
+    fn put(&self, data: &mut T, inner: V) {
+        if let Some(mut middle) = self.prism.get(data) {
+            self.lens.with_mut(&mut middle, |value| *value = inner);
+            self.prism.put(data, middle);
+        }
+    }
+}
+
+/// A [`Prism`] applying a [`Lens`] and then a prism, like for an enum in a field of a struct.
+#[derive(Debug, Clone, Copy)]
+pub struct LensThen<L, P, U> {
+    lens: L,
+    prism: P,
+    _marker: PhantomData<U>,
+}
+
+impl<L, P, U> LensThen<L, P, U> {
+    pub fn new(lens: L, prism: P) -> Self {
+        LensThen {
+            lens,
+            prism,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, U, V, L: Lens<T, U>, P: Prism<U, V>> Prism<T, V> for LensThen<L, P, U> {
+    fn get(&self, data: &T) -> Option<V> {
+        self.lens.with(data, |middle| self.prism.get(middle))
+    }
+
+    fn put(&self, data: &mut T, inner: V) {
+        self.lens
+            .with_mut(data, |middle| self.prism.put(middle, inner));
+    }
+}
+
 /// A trait implemented by PrismWrappers to check if this widget can handle the current data.
 pub trait PrismWidget<T>: Widget<T> {
     fn is_active_for(&self, data: &T) -> bool;
@@ -172,6 +291,24 @@ where
 
 /// A Widget wrapper similar to PrismWrapDisable, but with the limitation that this widget should
 /// only be visible if its data is present. In return you dont need to provide the initial data.
+///
+/// While the data is missing, the inner widget keeps the data it had last. It is usually created
+/// with [`WidgetExt::prism`], and shown by a [`Switcher`].
+///
+/// ```
+/// # use druid::widget::{Label, Slider};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::prism::{OptionNone, OptionSome};
+/// # use druid_widget_nursery::Switcher;
+/// fn volume() -> impl Widget<Option<f64>> {
+///     Switcher::new()
+///         .with_variant(OptionSome, Slider::new())
+///         .with_variant(OptionNone, Label::new("Muted"))
+/// }
+/// ```
+///
+/// [`WidgetExt::prism`]: crate::WidgetExt::prism
+/// [`Switcher`]: crate::Switcher
 pub struct PrismWrap<W, P, U> {
     inner: WidgetPod<U, W>,
     prism: P,
@@ -219,6 +356,9 @@ impl<T, U: Data, P: Prism<T, U>, W: Widget<U>> Widget<T> for PrismWrap<W, P, U>
         if let Some(data) = self.prism.get(data) {
             if self.cached_data.is_some() {
                 self.inner.update(ctx, &data, env);
+            } else {
+                // the inner widget wasn't added yet, since it had no data
+                ctx.children_changed();
             }
             self.cached_data = Some(data);
         }
@@ -228,6 +368,7 @@ impl<T, U: Data, P: Prism<T, U>, W: Widget<U>> Widget<T> for PrismWrap<W, P, U>
         if let Some(data) = &self.cached_data {
             let size = self.inner.layout(ctx, bc, data, env);
             self.inner.set_origin(ctx, Point::ORIGIN);
+            ctx.set_baseline_offset(self.inner.baseline_offset());
             size
         } else {
             bc.min()
@@ -0,0 +1,417 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circular progress indicators, complementing the linear [`ProgressBar`].
+//!
+//! The default size and stroke width are read from [`SIZE`] and [`STROKE_WIDTH`], which are set
+//! by [`configure_env`].
+//!
+//! [`ProgressBar`]: crate::ProgressBar
+//! [`configure_env`]: crate::configure_env
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use druid::kurbo::{Arc, Circle};
+use druid::piet::{LineCap, StrokeStyle};
+use druid::widget::prelude::*;
+use druid::{theme, Color, Key, KeyOrValue, Point, Vec2};
+
+use crate::animation::{AnimationCurve, KeyframeAnimation, KeyframeRepeat, Keyframes};
+use crate::RequestCtx;
+
+/// The width and height of the indicators.
+pub const SIZE: Key<f64> = Key::new("druid-widget-nursery.progress-indicator.size");
+/// The width of the ring.
+pub const STROKE_WIDTH: Key<f64> = Key::new("druid-widget-nursery.progress-indicator.stroke-width");
+
+/// An arc going around the circle, growing and shrinking.
+struct Indeterminate {
+    rotation: KeyframeAnimation<f64>,
+    sweep: KeyframeAnimation<f64>,
+}
+
+impl Indeterminate {
+    fn new() -> Self {
+        Indeterminate {
+            rotation: KeyframeAnimation::new(Keyframes::new(0.0).keyframe(
+                1.0,
+                2.0 * PI,
+                AnimationCurve::LINEAR,
+            ))
+            .duration(1.6)
+            .repeat(KeyframeRepeat::Loop),
+            sweep: KeyframeAnimation::new(Keyframes::new(0.15 * PI).keyframe(
+                1.0,
+                1.5 * PI,
+                AnimationCurve::EASE_IN_OUT,
+            ))
+            .duration(0.8)
+            .repeat(KeyframeRepeat::PingPong),
+        }
+    }
+
+    fn start(&mut self, ctx: &mut impl RequestCtx) {
+        if !self.rotation.animating() {
+            self.rotation.start(ctx);
+            self.sweep.start(ctx);
+        }
+    }
+
+    fn stop(&mut self) {
+        self.rotation.stop();
+        self.sweep.stop();
+    }
+
+    fn update(&mut self, ctx: &mut EventCtx, nanos: u64) {
+        self.rotation.update(ctx, nanos);
+        self.sweep.update(ctx, nanos);
+    }
+
+    fn arc(&self, center: Point, radius: f64) -> Arc {
+        Arc {
+            center,
+            radii: Vec2::new(radius, radius),
+            start_angle: *self.rotation - FRAC_PI_2,
+            sweep_angle: *self.sweep,
+            x_rotation: 0.0,
+        }
+    }
+}
+
+/// The center and the radius of the ring.
+fn ring(size: Size, stroke_width: f64) -> (Point, f64) {
+    let center = size.to_rect().center();
+    let radius = (size.min_side() - stroke_width).max(0.0) / 2.0;
+    (center, radius)
+}
+
+fn stroke_arc(ctx: &mut PaintCtx, arc: Arc, color: &Color, stroke_width: f64) {
+    let style = StrokeStyle::new().line_cap(LineCap::Round);
+    ctx.stroke_styled(arc, color, stroke_width, &style);
+}
+
+/// An indeterminate spinner: an arc spinning around a circle, for any data.
+///
+/// Unlike druid's [`Spinner`], this looks like an indeterminate [`CircularProgress`].
+///
+/// [`Spinner`]: druid::widget::Spinner
+pub struct ActivitySpinner {
+    color: KeyOrValue<Color>,
+    size: KeyOrValue<f64>,
+    stroke_width: KeyOrValue<f64>,
+    indeterminate: Indeterminate,
+}
+
+impl ActivitySpinner {
+    pub fn new() -> Self {
+        ActivitySpinner {
+            color: theme::PRIMARY_LIGHT.into(),
+            size: SIZE.into(),
+            stroke_width: STROKE_WIDTH.into(),
+            indeterminate: Indeterminate::new(),
+        }
+    }
+
+    /// Builder-style method for specifying the color.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the arc.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the size.
+    ///
+    /// For the non-builder varient, see [`set_size`].
+    ///
+    /// [`set_size`]: #method.set_size
+    pub fn with_size(mut self, size: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Set the width and height of the spinner.
+    pub fn set_size(&mut self, size: impl Into<KeyOrValue<f64>>) {
+        self.size = size.into();
+    }
+
+    /// Builder-style method for specifying the stroke width.
+    ///
+    /// For the non-builder varient, see [`set_stroke_width`].
+    ///
+    /// [`set_stroke_width`]: #method.set_stroke_width
+    pub fn with_stroke_width(mut self, stroke_width: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_stroke_width(stroke_width);
+        self
+    }
+
+    /// Set the width of the arc.
+    pub fn set_stroke_width(&mut self, stroke_width: impl Into<KeyOrValue<f64>>) {
+        self.stroke_width = stroke_width.into();
+    }
+}
+
+impl Default for ActivitySpinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for ActivitySpinner {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            self.indeterminate.update(ctx, *nanos);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.indeterminate.start(ctx);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
+        if ctx.env_key_changed(&self.size) || ctx.env_key_changed(&self.stroke_width) {
+            ctx.request_layout();
+        }
+        if ctx.env_key_changed(&self.color) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let size = self.size.resolve(env);
+        bc.constrain(Size::new(size, size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let stroke_width = self.stroke_width.resolve(env);
+        let (center, radius) = ring(ctx.size(), stroke_width);
+        let arc = self.indeterminate.arc(center, radius);
+        stroke_arc(ctx, arc, &self.color.resolve(env), stroke_width);
+    }
+}
+
+/// A ring filling up clockwise with the progress, starting at the top.
+///
+/// As a `Widget<f64>`, this expects a value between 0.0 and 1.0. As a `Widget<Option<f64>>`,
+/// `None` shows an arc spinning around the ring, for when the progress isn't known.
+pub struct CircularProgress {
+    color: KeyOrValue<Color>,
+    track_color: KeyOrValue<Color>,
+    size: KeyOrValue<f64>,
+    stroke_width: KeyOrValue<f64>,
+    indeterminate: Indeterminate,
+}
+
+impl CircularProgress {
+    pub fn new() -> Self {
+        CircularProgress {
+            color: theme::PRIMARY_LIGHT.into(),
+            track_color: theme::BACKGROUND_LIGHT.into(),
+            size: SIZE.into(),
+            stroke_width: STROKE_WIDTH.into(),
+            indeterminate: Indeterminate::new(),
+        }
+    }
+
+    /// Builder-style method for specifying the color.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the filled part of the ring.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the track color.
+    ///
+    /// For the non-builder varient, see [`set_track_color`].
+    ///
+    /// [`set_track_color`]: #method.set_track_color
+    pub fn with_track_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_track_color(color);
+        self
+    }
+
+    /// Set the color of the empty part of the ring.
+    pub fn set_track_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.track_color = color.into();
+    }
+
+    /// Builder-style method for specifying the size.
+    ///
+    /// For the non-builder varient, see [`set_size`].
+    ///
+    /// [`set_size`]: #method.set_size
+    pub fn with_size(mut self, size: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Set the width and height of the ring.
+    pub fn set_size(&mut self, size: impl Into<KeyOrValue<f64>>) {
+        self.size = size.into();
+    }
+
+    /// Builder-style method for specifying the stroke width.
+    ///
+    /// For the non-builder varient, see [`set_stroke_width`].
+    ///
+    /// [`set_stroke_width`]: #method.set_stroke_width
+    pub fn with_stroke_width(mut self, stroke_width: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_stroke_width(stroke_width);
+        self
+    }
+
+    /// Set the width of the ring.
+    pub fn set_stroke_width(&mut self, stroke_width: impl Into<KeyOrValue<f64>>) {
+        self.stroke_width = stroke_width.into();
+    }
+
+    fn env_changed(&self, ctx: &mut UpdateCtx) {
+        if ctx.env_key_changed(&self.size) || ctx.env_key_changed(&self.stroke_width) {
+            ctx.request_layout();
+        }
+        if ctx.env_key_changed(&self.color) || ctx.env_key_changed(&self.track_color) {
+            ctx.request_paint();
+        }
+    }
+
+    fn size(&self, bc: &BoxConstraints, env: &Env) -> Size {
+        let size = self.size.resolve(env);
+        bc.constrain(Size::new(size, size))
+    }
+
+    /// Paint the ring, filled up to `progress`, or with the spinning arc for `None`.
+    fn paint_ring(&self, ctx: &mut PaintCtx, progress: Option<f64>, env: &Env) {
+        let stroke_width = self.stroke_width.resolve(env);
+        let (center, radius) = ring(ctx.size(), stroke_width);
+        ctx.stroke(
+            Circle::new(center, radius),
+            &self.track_color.resolve(env),
+            stroke_width,
+        );
+
+        let arc = match progress {
+            Some(progress) if progress <= 0.0 => return,
+            Some(progress) => Arc {
+                center,
+                radii: Vec2::new(radius, radius),
+                start_angle: -FRAC_PI_2,
+                sweep_angle: progress.min(1.0) * 2.0 * PI,
+                x_rotation: 0.0,
+            },
+            None => self.indeterminate.arc(center, radius),
+        };
+        stroke_arc(ctx, arc, &self.color.resolve(env), stroke_width);
+    }
+}
+
+impl Default for CircularProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<f64> for CircularProgress {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut f64, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &f64, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &f64, data: &f64, _env: &Env) {
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+        self.env_changed(ctx);
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &f64,
+        env: &Env,
+    ) -> Size {
+        self.size(bc, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
+        self.paint_ring(ctx, Some(*data), env);
+    }
+}
+
+impl Widget<Option<f64>> for CircularProgress {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut Option<f64>, _env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            self.indeterminate.update(ctx, *nanos);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &Option<f64>,
+        _env: &Env,
+    ) {
+        if let (LifeCycle::WidgetAdded, None) = (event, data) {
+            self.indeterminate.start(ctx);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &Option<f64>,
+        data: &Option<f64>,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) {
+            match data {
+                Some(_) => self.indeterminate.stop(),
+                None => self.indeterminate.start(ctx),
+            }
+            ctx.request_paint();
+        }
+        self.env_changed(ctx);
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Option<f64>,
+        env: &Env,
+    ) -> Size {
+        self.size(bc, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Option<f64>, env: &Env) {
+        self.paint_ring(ctx, *data, env);
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::marker::PhantomData;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::BezPath;
+use druid::widget::prelude::*;
+use druid::{theme, Affine, Color, KeyOrValue, Rect};
+
+const DEFAULT_SIZE: f64 = 20.0;
+const SPACING: f64 = 4.0;
+const STAR_COLOR: Color = Color::rgb8(0xff, 0xc1, 0x07);
+
+/// A value that can be shown by a [`Rating`].
+pub trait RatingValue: Data + Copy {
+    /// Whether half stars can be represented.
+    const HALVES: bool;
+
+    fn to_f64(self) -> f64;
+    /// Convert from a rating, rounding if the type can't represent halves.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl RatingValue for f64 {
+    const HALVES: bool = true;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+impl RatingValue for u8 {
+    const HALVES: bool = false;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as u8
+    }
+}
+
+/// A five-pointed star filling the unit square.
+fn star() -> BezPath {
+    let mut path = BezPath::new();
+    for i in 0..10 {
+        let radius = if i % 2 == 0 { 0.5 } else { 0.2 };
+        let angle = -FRAC_PI_2 + i as f64 * PI / 5.0;
+        let point = (0.5 + radius * angle.cos(), 0.55 + radius * angle.sin());
+        if i == 0 {
+            path.move_to(point);
+        } else {
+            path.line_to(point);
+        }
+    }
+    path.close_path();
+    path
+}
+
+/// A row of stars for showing and picking a rating.
+///
+/// Hovering previews the rating under the mouse, clicking picks it and clicking the current
+/// rating again clears it. When focused, the arrow keys change the rating and Home and End set
+/// it to zero and the maximum.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::Rating;
+/// fn review() -> impl Widget<f64> {
+///     Rating::new().with_half_stars(true)
+/// }
+///
+/// fn average() -> impl Widget<f64> {
+///     Rating::new().read_only()
+/// }
+/// ```
+pub struct Rating<T> {
+    max: u8,
+    half_stars: bool,
+    read_only: bool,
+    glyph: BezPath,
+    size: f64,
+    color: KeyOrValue<Color>,
+    empty_color: KeyOrValue<Color>,
+    /// The rating under the mouse.
+    hover: Option<f64>,
+    _t: PhantomData<T>,
+}
+
+impl<T: RatingValue> Rating<T> {
+    /// Create a rating of up to five stars.
+    pub fn new() -> Self {
+        Rating {
+            max: 5,
+            half_stars: false,
+            read_only: false,
+            glyph: star(),
+            size: DEFAULT_SIZE,
+            color: STAR_COLOR.into(),
+            empty_color: theme::BORDER_DARK.into(),
+            hover: None,
+            _t: PhantomData,
+        }
+    }
+
+    /// Builder-style method for specifying the number of stars.
+    ///
+    /// For the non-builder varient, see [`set_max`].
+    ///
+    /// [`set_max`]: #method.set_max
+    pub fn with_max(mut self, max: u8) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    /// Set the number of stars, which is the highest rating.
+    pub fn set_max(&mut self, max: u8) {
+        self.max = max.max(1);
+    }
+
+    /// Builder-style method for allowing half stars.
+    ///
+    /// For the non-builder varient, see [`set_half_stars`].
+    ///
+    /// [`set_half_stars`]: #method.set_half_stars
+    pub fn with_half_stars(mut self, half_stars: bool) -> Self {
+        self.set_half_stars(half_stars);
+        self
+    }
+
+    /// Set whether half stars can be picked. This has no effect for integer data.
+    pub fn set_half_stars(&mut self, half_stars: bool) {
+        self.half_stars = half_stars;
+    }
+
+    /// Builder-style method for only showing the rating.
+    pub fn read_only(mut self) -> Self {
+        self.set_read_only(true);
+        self
+    }
+
+    /// Set whether the rating is only shown, not picked. Any fraction is shown then.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Builder-style method for specifying the shape of the stars.
+    ///
+    /// For the non-builder varient, see [`set_glyph`].
+    ///
+    /// [`set_glyph`]: #method.set_glyph
+    pub fn with_glyph(mut self, glyph: BezPath) -> Self {
+        self.set_glyph(glyph);
+        self
+    }
+
+    /// Set the shape of the stars, a path filling the square from `(0, 0)` to `(1, 1)`.
+    pub fn set_glyph(&mut self, glyph: BezPath) {
+        self.glyph = glyph;
+    }
+
+    /// Builder-style method for specifying the size of the stars.
+    ///
+    /// For the non-builder varient, see [`set_size`].
+    ///
+    /// [`set_size`]: #method.set_size
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Set the width and height of the stars.
+    pub fn set_size(&mut self, size: f64) {
+        self.size = size;
+    }
+
+    /// Builder-style method for specifying the color of filled stars.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of filled stars.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the color of empty stars.
+    ///
+    /// For the non-builder varient, see [`set_empty_color`].
+    ///
+    /// [`set_empty_color`]: #method.set_empty_color
+    pub fn with_empty_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_empty_color(color);
+        self
+    }
+
+    /// Set the color of empty stars.
+    pub fn set_empty_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.empty_color = color.into();
+    }
+
+    /// The smallest change of the rating.
+    fn precision(&self) -> f64 {
+        if self.half_stars && T::HALVES {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// The rating picked by clicking at `x`.
+    fn value_at(&self, x: f64) -> f64 {
+        let star = (x / (self.size + SPACING)).floor();
+        let within = (x - star * (self.size + SPACING)) / self.size;
+        let fraction = if self.precision() < 1.0 && within <= 0.5 {
+            0.5
+        } else {
+            1.0
+        };
+        (star + fraction).max(self.precision()).min(self.max as f64)
+    }
+
+    fn set_value(&self, ctx: &mut EventCtx, data: &mut T, value: f64) {
+        let value = value.max(0.0).min(self.max as f64);
+        let new = T::from_f64(value);
+        if !new.same(data) {
+            *data = new;
+        }
+        ctx.request_paint();
+    }
+}
+
+impl<T: RatingValue> Default for Rating<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RatingValue> Widget<T> for Rating<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        if self.read_only || ctx.is_disabled() {
+            return;
+        }
+        match event {
+            Event::MouseMove(mouse) => {
+                let hover = Some(self.value_at(mouse.pos.x));
+                if hover != self.hover {
+                    self.hover = hover;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.set_active(true);
+                ctx.request_focus();
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() {
+                    let value = self.value_at(mouse.pos.x);
+                    if (data.to_f64() - value).abs() < 1e-9 {
+                        // clicking the current rating clears it
+                        self.set_value(ctx, data, 0.0);
+                    } else {
+                        self.set_value(ctx, data, value);
+                    }
+                }
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                let value = data.to_f64();
+                let value = match &key.key {
+                    Key::ArrowRight | Key::ArrowUp => value + self.precision(),
+                    Key::ArrowLeft | Key::ArrowDown => value - self.precision(),
+                    Key::Home => 0.0,
+                    Key::End => self.max as f64,
+                    _ => return,
+                };
+                self.set_value(ctx, data, value);
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded if !self.read_only => ctx.register_for_focus(),
+            LifeCycle::HotChanged(false) => {
+                self.hover = None;
+                ctx.request_paint();
+            }
+            LifeCycle::FocusChanged(_) | LifeCycle::DisabledChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data)
+            || ctx.env_key_changed(&self.color)
+            || ctx.env_key_changed(&self.empty_color)
+        {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, _env: &Env) -> Size {
+        let count = self.max as f64;
+        bc.constrain(Size::new(
+            count * self.size + (count - 1.0) * SPACING,
+            self.size,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let value = self.hover.unwrap_or_else(|| data.to_f64());
+        let mut color = self.color.resolve(env);
+        if self.hover.is_some() {
+            color = color.with_alpha(0.7);
+        }
+        if ctx.is_disabled() {
+            color = env.get(theme::DISABLED_TEXT_COLOR);
+        }
+        let empty_color = self.empty_color.resolve(env);
+
+        for star in 0..self.max {
+            let x = star as f64 * (self.size + SPACING);
+            let glyph = Affine::translate((x, 0.0)) * Affine::scale(self.size) * self.glyph.clone();
+            ctx.fill(glyph.clone(), &empty_color);
+
+            let fill = (value - star as f64).max(0.0).min(1.0);
+            if fill > 0.0 {
+                ctx.with_save(|ctx| {
+                    ctx.clip(Rect::new(x, 0.0, x + fill * self.size, self.size));
+                    ctx.fill(glyph, &color);
+                });
+            }
+        }
+
+        if ctx.has_focus() && !self.read_only {
+            let rect = ctx.size().to_rect().inset(-0.5).to_rounded_rect(2.0);
+            ctx.stroke(rect, &env.get(theme::PRIMARY_LIGHT), 1.0);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request/response command pairs.
+//!
+//! A widget submits a [`Request`] with [`PendingRequests::submit`]. Whoever handles the request
+//! (a delegate, another widget or a background thread) answers with [`Request::respond`], which
+//! routes a [`Response`] back to the widget that asked. [`PendingRequests::take_response`] then
+//! hands out the result, ignoring responses to requests that were cancelled in the meantime.
+//!
+//! ```
+//! use druid::WidgetId;
+//! use druid_widget_nursery::request::{Request, Response};
+//! use druid_widget_nursery::selectors;
+//!
+//! selectors! {
+//!     LOAD_USER: Request<u32>,
+//!     USER_LOADED: Response<String>,
+//! }
+//! ```
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use druid::{Command, Event, EventCtx, ExtEventError, ExtEventSink, Selector, WidgetId};
+
+/// Identifies a single request, and the widget that made it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RequestToken {
+    requester: WidgetId,
+    id: u64,
+}
+
+impl RequestToken {
+    /// Create a new, unique token for a request made by `requester`.
+    pub fn new(requester: WidgetId) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            requester,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// The widget the response is delivered to.
+    pub fn requester(&self) -> WidgetId {
+        self.requester
+    }
+}
+
+/// The payload of a request command.
+pub struct Request<Q> {
+    token: RequestToken,
+    payload: Q,
+}
+
+impl<Q> Request<Q> {
+    pub fn new(token: RequestToken, payload: Q) -> Self {
+        Self { token, payload }
+    }
+
+    pub fn token(&self) -> RequestToken {
+        self.token
+    }
+
+    pub fn payload(&self) -> &Q {
+        &self.payload
+    }
+
+    /// Returns the command answering this request with `result`.
+    ///
+    /// The command is targeted at the widget that made the request, submit it from any context.
+    pub fn respond<R: Any>(&self, selector: Selector<Response<R>>, result: R) -> Command {
+        selector
+            .with(Response {
+                token: self.token,
+                result,
+            })
+            .to(self.token.requester)
+    }
+
+    /// Answer this request from another thread.
+    pub fn respond_with_sink<R: Any + Send>(
+        &self,
+        sink: &ExtEventSink,
+        selector: Selector<Response<R>>,
+        result: R,
+    ) -> Result<(), ExtEventError> {
+        let response = Response {
+            token: self.token,
+            result,
+        };
+        sink.submit_command(selector, response, self.token.requester)
+    }
+}
+
+/// The payload of a response command.
+pub struct Response<R> {
+    token: RequestToken,
+    result: R,
+}
+
+impl<R> Response<R> {
+    pub fn token(&self) -> RequestToken {
+        self.token
+    }
+
+    pub fn result(&self) -> &R {
+        &self.result
+    }
+}
+
+/// Keeps track of the requests a widget is waiting for.
+#[derive(Debug, Default)]
+pub struct PendingRequests {
+    tokens: HashSet<RequestToken>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submit a request on behalf of the current widget, returning its token.
+    pub fn submit<Q: Any>(
+        &mut self,
+        ctx: &mut EventCtx,
+        selector: Selector<Request<Q>>,
+        payload: Q,
+    ) -> RequestToken {
+        let token = RequestToken::new(ctx.widget_id());
+        self.tokens.insert(token);
+        ctx.submit_command(selector.with(Request::new(token, payload)));
+        token
+    }
+
+    /// If `event` is a response to one of our pending requests, returns its result.
+    ///
+    /// The request is no longer pending afterwards.
+    pub fn take_response<'a, R: Any>(
+        &mut self,
+        event: &'a Event,
+        selector: Selector<Response<R>>,
+    ) -> Option<&'a R> {
+        match event {
+            Event::Command(cmd) => {
+                let response = cmd.get(selector)?;
+                if self.tokens.remove(&response.token) {
+                    Some(&response.result)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Stop waiting for a request, its response will be ignored.
+    pub fn cancel(&mut self, token: RequestToken) {
+        self.tokens.remove(&token);
+    }
+
+    /// Stop waiting for all requests.
+    pub fn cancel_all(&mut self) {
+        self.tokens.clear();
+    }
+
+    pub fn is_pending(&self, token: RequestToken) -> bool {
+        self.tokens.contains(&token)
+    }
+
+    /// Returns `true` if no request is pending.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::kurbo::{Circle, Line};
+use druid::widget::prelude::*;
+use druid::{theme, Cursor, Lens, LensExt, Point, Rect, Vec2, WidgetPod};
+
+/// The height of the bar dragged to move the child.
+const HANDLE_HEIGHT: f64 = 12.0;
+/// How far from the edges of the frame they can be grabbed to resize it.
+const GRIP_WIDTH: f64 = 6.0;
+
+/// The edges moved by a drag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Edges {
+    left: bool,
+    top: bool,
+    right: bool,
+    bottom: bool,
+}
+
+impl Edges {
+    const ALL: Edges = Edges {
+        left: true,
+        top: true,
+        right: true,
+        bottom: true,
+    };
+
+    fn cursor(&self) -> Cursor {
+        let horizontal = self.left || self.right;
+        let vertical = self.top || self.bottom;
+        match (horizontal, vertical) {
+            (true, false) => Cursor::ResizeLeftRight,
+            (false, true) => Cursor::ResizeUpDown,
+            _ => Cursor::Crosshair,
+        }
+    }
+}
+
+struct Drag {
+    edges: Edges,
+    start: Point,
+    frame: Rect,
+}
+
+/// A wrapper showing its child in a frame that can be moved and resized with the mouse, for
+/// floating panels or the items of a design canvas.
+///
+/// The frame is a [`Rect`] of the data, holding a bar to drag at the top and the child under it.
+/// The wrapper takes all the space it is given, and keeps the frame inside it. The edges and the
+/// corners of the frame resize it, unless it was made with [`movable`].
+///
+/// ```
+/// # use druid::widget::Label;
+/// # use druid::{Data, Lens, Rect, Widget};
+/// # use druid_widget_nursery::Resizable;
+/// #[derive(Clone, Data, Lens)]
+/// struct Panel {
+///     frame: Rect,
+/// }
+///
+/// fn panel() -> impl Widget<Panel> {
+///     Resizable::new(Label::new("Layers"), Panel::frame).with_min_size((120.0, 80.0))
+/// }
+/// ```
+///
+/// [`movable`]: #method.movable
+pub struct Resizable<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    get_frame: Box<dyn Fn(&T) -> Rect>,
+    set_frame: Box<dyn Fn(&mut T, Rect)>,
+    resizable: bool,
+    min_size: Size,
+    drag: Option<Drag>,
+}
+
+impl<T: Data> Resizable<T> {
+    /// Show `child` in the frame of `lens`, which can be moved and resized.
+    pub fn new(
+        child: impl Widget<T> + 'static,
+        lens: impl Lens<T, Rect> + Clone + 'static,
+    ) -> Self {
+        let get = lens.clone();
+        Resizable {
+            child: WidgetPod::new(Box::new(child)),
+            get_frame: Box::new(move |data| get.get(data)),
+            set_frame: Box::new(move |data, frame| lens.put(data, frame)),
+            resizable: true,
+            min_size: Size::new(40.0, 30.0 + HANDLE_HEIGHT),
+            drag: None,
+        }
+    }
+
+    /// Show `child` in the frame of `lens`, which can be moved but not resized.
+    pub fn movable(
+        child: impl Widget<T> + 'static,
+        lens: impl Lens<T, Rect> + Clone + 'static,
+    ) -> Self {
+        let mut resizable = Self::new(child, lens);
+        resizable.resizable = false;
+        resizable
+    }
+
+    /// Builder-style method for setting the smallest size of the frame, with the bar.
+    ///
+    /// For the non-builder varient, see [`set_min_size`].
+    ///
+    /// [`set_min_size`]: #method.set_min_size
+    pub fn with_min_size(mut self, size: impl Into<Size>) -> Self {
+        self.set_min_size(size);
+        self
+    }
+
+    /// Set the smallest size of the frame, with the bar.
+    pub fn set_min_size(&mut self, size: impl Into<Size>) {
+        let size = size.into();
+        self.min_size = Size::new(size.width.max(0.0), size.height.max(HANDLE_HEIGHT));
+    }
+
+    /// `frame` moved inside `bounds`, and made smaller when it doesn't fit.
+    fn clamp(frame: Rect, bounds: Size) -> Rect {
+        let size = Size::new(
+            frame.width().min(bounds.width),
+            frame.height().min(bounds.height),
+        );
+        let origin = Point::new(
+            frame.x0.min(bounds.width - size.width).max(0.0),
+            frame.y0.min(bounds.height - size.height).max(0.0),
+        );
+        Rect::from_origin_size(origin, size)
+    }
+
+    /// The frame shown, with the smallest size and inside the wrapper.
+    fn frame(&self, data: &T, bounds: Size) -> Rect {
+        let frame = (self.get_frame)(data).abs();
+        let size = Size::new(
+            frame.width().max(self.min_size.width),
+            frame.height().max(self.min_size.height),
+        );
+        Self::clamp(Rect::from_origin_size(frame.origin(), size), bounds)
+    }
+
+    /// The edges under `pos`, or all of them for the bar, which moves the frame.
+    fn edges_at(&self, frame: Rect, pos: Point) -> Option<Edges> {
+        if !frame.inflate(GRIP_WIDTH, GRIP_WIDTH).contains(pos) {
+            return None;
+        }
+        if self.resizable {
+            let edges = Edges {
+                left: (pos.x - frame.x0).abs() <= GRIP_WIDTH,
+                top: (pos.y - frame.y0).abs() <= GRIP_WIDTH,
+                right: (pos.x - frame.x1).abs() <= GRIP_WIDTH,
+                bottom: (pos.y - frame.y1).abs() <= GRIP_WIDTH,
+            };
+            if edges.left || edges.top || edges.right || edges.bottom {
+                return Some(edges);
+            }
+        }
+        if frame.contains(pos) && pos.y < frame.y0 + HANDLE_HEIGHT {
+            return Some(Edges::ALL);
+        }
+        None
+    }
+
+    /// The frame of `drag` after the mouse moved by `delta`, inside `bounds`.
+    fn dragged(&self, drag: &Drag, delta: Vec2, bounds: Size) -> Rect {
+        let Drag { edges, frame, .. } = *drag;
+        if edges == Edges::ALL {
+            return Self::clamp(frame + delta, bounds);
+        }
+        let min = self.min_size;
+        let mut rect = frame;
+        if edges.left {
+            rect.x0 = (frame.x0 + delta.x).max(0.0).min(frame.x1 - min.width);
+        }
+        if edges.right {
+            rect.x1 = (frame.x1 + delta.x)
+                .min(bounds.width)
+                .max(frame.x0 + min.width);
+        }
+        if edges.top {
+            rect.y0 = (frame.y0 + delta.y).max(0.0).min(frame.y1 - min.height);
+        }
+        if edges.bottom {
+            rect.y1 = (frame.y1 + delta.y)
+                .min(bounds.height)
+                .max(frame.y0 + min.height);
+        }
+        rect
+    }
+}
+
+impl<T: Data> Widget<T> for Resizable<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let bounds = ctx.size();
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                let frame = self.frame(data, bounds);
+                if let Some(edges) = self.edges_at(frame, mouse.pos) {
+                    self.drag = Some(Drag {
+                        edges,
+                        start: mouse.pos,
+                        frame,
+                    });
+                    ctx.set_active(true);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some(drag) = &self.drag {
+                    let frame = self.dragged(drag, mouse.pos - drag.start, bounds);
+                    (self.set_frame)(data, frame);
+                    ctx.request_layout();
+                    ctx.set_handled();
+                    return;
+                }
+                let frame = self.frame(data, bounds);
+                match self.edges_at(frame, mouse.pos) {
+                    Some(edges) if edges != Edges::ALL => ctx.set_cursor(&edges.cursor()),
+                    _ => ctx.clear_cursor(),
+                }
+            }
+            Event::MouseUp(mouse) if mouse.button.is_left() && self.drag.is_some() => {
+                self.drag = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+        self.child.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if (self.get_frame)(old_data) != (self.get_frame)(data) {
+            ctx.request_layout();
+        }
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let wanted = (self.get_frame)(data);
+        let size = bc.constrain(Size::new(
+            if bc.is_width_bounded() {
+                bc.max().width
+            } else {
+                wanted.x1.max(self.min_size.width)
+            },
+            if bc.is_height_bounded() {
+                bc.max().height
+            } else {
+                wanted.y1.max(self.min_size.height)
+            },
+        ));
+        let frame = self.frame(data, size);
+        let child_size = Size::new(frame.width(), (frame.height() - HANDLE_HEIGHT).max(0.0));
+        self.child
+            .layout(ctx, &BoxConstraints::tight(child_size), data, env);
+        self.child
+            .set_origin(ctx, Point::new(frame.x0, frame.y0 + HANDLE_HEIGHT));
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let frame = self.frame(data, ctx.size());
+        let handle = Rect::new(frame.x0, frame.y0, frame.x1, frame.y0 + HANDLE_HEIGHT);
+        ctx.fill(frame, &env.get(theme::BACKGROUND_DARK));
+        ctx.fill(handle, &env.get(theme::BACKGROUND_LIGHT));
+
+        // dots in the middle of the bar
+        let dot_color = env.get(theme::DISABLED_TEXT_COLOR);
+        let center = handle.center();
+        for i in -1..=1 {
+            let dot = Circle::new(center + Vec2::new(i as f64 * 5.0, 0.0), 1.5);
+            ctx.fill(dot, &dot_color);
+        }
+
+        self.child.paint(ctx, data, env);
+
+        let border = if self.drag.is_some() {
+            env.get(theme::PRIMARY_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(frame.inset(-0.5), &border, 1.0);
+
+        if self.resizable {
+            // lines in the bottom right corner
+            let corner = Point::new(frame.x1, frame.y1);
+            for offset in [4.0, 8.0] {
+                let line = Line::new(
+                    corner - Vec2::new(offset, 2.0),
+                    corner - Vec2::new(2.0, offset),
+                );
+                ctx.stroke(line, &dot_color, 1.0);
+            }
+        }
+    }
+}
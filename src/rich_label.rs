@@ -0,0 +1,361 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A label made of styled spans of text, with links, which can be selected and copied.
+
+use std::ops::Range;
+
+use druid::im::Vector;
+use druid::piet::{
+    FontStyle, FontWeight, PietText, PietTextLayout, Text, TextAttribute, TextLayout,
+    TextLayoutBuilder,
+};
+use druid::widget::prelude::*;
+use druid::{
+    theme, Application, ArcStr, Color, Cursor, FontDescriptor, HotKey, Key, KeyOrValue, Point,
+    Selector, SysMods,
+};
+
+/// Submitted when a link of a [`RichLabel`] is clicked, with its target; the application handles
+/// it, for example in its `AppDelegate`.
+pub const LINK_CLICKED: Selector<ArcStr> =
+    Selector::new("druid-widget-nursery.rich-label.link-clicked");
+
+/// The color of links without a color of their own.
+pub const LINK_COLOR: Key<Color> = Key::new("druid-widget-nursery.rich-label.link-color");
+
+/// A span of text of a [`RichLabel`], with its style.
+///
+/// Spans without a color have the text color, or the link color for links.
+#[derive(Debug, Clone, Data)]
+pub struct Span {
+    pub text: ArcStr,
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// The target submitted with [`LINK_CLICKED`] when the span is clicked.
+    pub link: Option<ArcStr>,
+}
+
+impl Span {
+    /// A span of `text` in the default style.
+    pub fn new(text: impl Into<ArcStr>) -> Self {
+        Span {
+            text: text.into(),
+            color: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            link: None,
+        }
+    }
+
+    /// A link to `target`, which is underlined.
+    pub fn link(text: impl Into<ArcStr>, target: impl Into<ArcStr>) -> Self {
+        Span {
+            underline: true,
+            link: Some(target.into()),
+            ..Span::new(text)
+        }
+    }
+
+    /// Builder-style method for setting the color of the span.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Builder-style method for making the span bold.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Builder-style method for making the span italic.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Builder-style method for underlining the span.
+    pub fn underlined(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+}
+
+/// A label showing spans of styled text, wrapped to its width.
+///
+/// Clicking a link submits [`LINK_CLICKED`] with its target. The text can be selected with the
+/// mouse, and copied with the usual shortcut.
+///
+/// ```
+/// # use druid::im::vector;
+/// # use druid::{AppDelegate, Command, DelegateCtx, Env, Handled, Target, Widget};
+/// # use druid_widget_nursery::rich_label::{RichLabel, Span, LINK_CLICKED};
+/// fn welcome() -> impl Widget<druid::im::Vector<Span>> {
+///     RichLabel::new()
+/// }
+///
+/// let spans = vector![
+///     Span::new("Read the "),
+///     Span::link("guide", "https://linebender.org/druid/"),
+///     Span::new(" before you start.").bold(),
+/// ];
+///
+/// struct Delegate;
+///
+/// impl<T: druid::Data> AppDelegate<T> for Delegate {
+///     fn command(&mut self, _: &mut DelegateCtx, _: Target, cmd: &Command, _: &mut T, _: &Env) -> Handled {
+///         if let Some(target) = cmd.get(LINK_CLICKED) {
+///             println!("opening {}", target);
+///             return Handled::Yes;
+///         }
+///         Handled::No
+///     }
+/// }
+/// ```
+pub struct RichLabel {
+    font: KeyOrValue<FontDescriptor>,
+    layout: Option<PietTextLayout>,
+    /// The width the layout was wrapped to.
+    layout_width: f64,
+    /// The ranges of the links in the text, in bytes.
+    links: Vec<(Range<usize>, ArcStr)>,
+    text: String,
+    /// Where the selection started, and where it ends, in bytes.
+    selection: Option<(usize, usize)>,
+}
+
+impl RichLabel {
+    pub fn new() -> Self {
+        RichLabel {
+            font: theme::UI_FONT.into(),
+            layout: None,
+            layout_width: f64::INFINITY,
+            links: Vec::new(),
+            text: String::new(),
+            selection: None,
+        }
+    }
+
+    /// Builder-style method for setting the font of the text.
+    ///
+    /// For the non-builder varient, see [`set_font`].
+    ///
+    /// [`set_font`]: #method.set_font
+    pub fn with_font(mut self, font: impl Into<KeyOrValue<FontDescriptor>>) -> Self {
+        self.set_font(font);
+        self
+    }
+
+    /// Set the font of the text.
+    pub fn set_font(&mut self, font: impl Into<KeyOrValue<FontDescriptor>>) {
+        self.font = font.into();
+        self.layout = None;
+    }
+
+    /// The offset of the text under `pos`, in bytes.
+    fn offset_at(&self, pos: Point) -> Option<usize> {
+        Some(self.layout.as_ref()?.hit_test_point(pos).idx)
+    }
+
+    /// The target of the link under `pos`.
+    fn link_at(&self, pos: Point) -> Option<&ArcStr> {
+        let hit = self.layout.as_ref()?.hit_test_point(pos);
+        if !hit.is_inside {
+            return None;
+        }
+        self.links
+            .iter()
+            .find(|(range, _)| range.contains(&hit.idx))
+            .map(|(_, target)| target)
+    }
+
+    fn selected_range(&self) -> Option<Range<usize>> {
+        let (anchor, active) = self.selection?;
+        Some(anchor.min(active)..anchor.max(active)).filter(|range| !range.is_empty())
+    }
+
+    fn build_layout(&mut self, text: &mut PietText, spans: &Vector<Span>, width: f64, env: &Env) {
+        self.text = spans.iter().map(|span| &*span.text).collect();
+        self.links.clear();
+
+        let font = self.font.resolve(env);
+        let link_color = env
+            .try_get(LINK_COLOR)
+            .unwrap_or_else(|_| env.get(theme::PRIMARY_LIGHT));
+        let mut builder = text
+            .new_text_layout(self.text.clone())
+            .max_width(width)
+            .font(font.family.clone(), font.size)
+            .default_attribute(TextAttribute::Weight(font.weight))
+            .default_attribute(TextAttribute::Style(font.style))
+            .text_color(env.get(theme::TEXT_COLOR));
+        let mut start = 0;
+        for span in spans {
+            let range = start..start + span.text.len();
+            start = range.end;
+            let color = match (&span.color, &span.link) {
+                (Some(color), _) => Some(*color),
+                (None, Some(_)) => Some(link_color),
+                (None, None) => None,
+            };
+            if let Some(color) = color {
+                builder = builder.range_attribute(range.clone(), TextAttribute::TextColor(color));
+            }
+            if span.bold {
+                builder = builder.range_attribute(range.clone(), FontWeight::BOLD);
+            }
+            if span.italic {
+                builder = builder.range_attribute(range.clone(), FontStyle::Italic);
+            }
+            if span.underline {
+                builder = builder.range_attribute(range.clone(), TextAttribute::Underline(true));
+            }
+            if let Some(target) = &span.link {
+                self.links.push((range, target.clone()));
+            }
+        }
+        self.layout = Some(builder.build().unwrap());
+        self.layout_width = width;
+    }
+}
+
+impl Default for RichLabel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<Vector<Span>> for RichLabel {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut Vector<Span>, _env: &Env) {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.request_focus();
+                ctx.set_active(true);
+                if let Some(offset) = self.offset_at(mouse.pos) {
+                    self.selection = match self.selection {
+                        Some((anchor, _)) if mouse.mods.shift() => Some((anchor, offset)),
+                        _ => Some((offset, offset)),
+                    };
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if ctx.is_active() {
+                    if let (Some((anchor, _)), Some(offset)) =
+                        (self.selection, self.offset_at(mouse.pos))
+                    {
+                        self.selection = Some((anchor, offset));
+                        ctx.request_paint();
+                    }
+                } else if self.link_at(mouse.pos).is_some() {
+                    ctx.set_cursor(&Cursor::Pointer);
+                } else {
+                    ctx.clear_cursor();
+                }
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                // a click on a link, not the end of a selection
+                if self.selected_range().is_none() {
+                    if let Some(target) = self.link_at(mouse.pos) {
+                        ctx.submit_command(LINK_CLICKED.with(target.clone()));
+                    }
+                }
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                if HotKey::new(SysMods::Cmd, "c").matches(key) {
+                    if let Some(range) = self.selected_range() {
+                        let text = self.text.get(range).unwrap_or_default();
+                        Application::global().clipboard().put_string(text);
+                    }
+                    ctx.set_handled();
+                } else if HotKey::new(SysMods::Cmd, "a").matches(key) {
+                    self.selection = Some((0, self.text.len()));
+                    ctx.request_paint();
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Vector<Span>,
+        _env: &Env,
+    ) {
+        if let LifeCycle::FocusChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &Vector<Span>,
+        data: &Vector<Span>,
+        _env: &Env,
+    ) {
+        if !old_data.same(data) {
+            self.selection = None;
+            self.layout = None;
+            ctx.request_layout();
+        } else if ctx.env_changed() {
+            self.layout = None;
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &Vector<Span>,
+        env: &Env,
+    ) -> Size {
+        let width = bc.max().width;
+        if self.layout.is_none() || self.layout_width != width {
+            self.build_layout(ctx.text(), data, width, env);
+        }
+        let size = self
+            .layout
+            .as_ref()
+            .map_or(Size::ZERO, |layout| layout.size());
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &Vector<Span>, env: &Env) {
+        let layout = match &self.layout {
+            Some(layout) => layout,
+            None => return,
+        };
+        if let Some(range) = self.selected_range() {
+            let color = if ctx.has_focus() {
+                env.get(theme::SELECTED_TEXT_BACKGROUND_COLOR)
+            } else {
+                env.get(theme::SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR)
+            };
+            for rect in layout.rects_for_range(range) {
+                ctx.fill(rect, &color);
+            }
+        }
+        ctx.draw_text(layout, Point::ORIGIN);
+    }
+}
@@ -0,0 +1,524 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A scroll container animating its scrolling, with overlay scrollbars and commands to scroll to
+//! a point or to a widget.
+//!
+//! Widgets inside it can also reveal an area with `scroll_area_to_view`, as they would in a
+//! `Scroll`.
+
+use std::time::Duration;
+
+use druid::commands::SCROLL_TO_VIEW;
+use druid::kurbo::RoundedRect;
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::{theme, Affine, Point, Rect, Selector, TimerToken, Vec2, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve};
+
+/// Scrolls the [`ScrollController`] this is sent to so that this point of its content is at its
+/// top left corner, or as close as it can be.
+pub const SCROLL_TO: Selector<Point> = Selector::new("druid-widget-nursery.scroll-controller.to");
+
+/// Scrolls the [`ScrollController`] this is sent to so that the widget with this id is visible.
+/// The widget has to be wrapped in a [`ScrollTarget`].
+pub const SCROLL_TO_WIDGET: Selector<WidgetId> =
+    Selector::new("druid-widget-nursery.scroll-controller.to-widget");
+
+/// Sent to a [`ScrollTarget`] to make it reveal itself.
+const REVEAL: Selector = Selector::new("druid-widget-nursery.scroll-controller.reveal");
+
+/// The thickness of a scrollbar, and of a hovered one.
+const THIN: f64 = 4.0;
+const THICK: f64 = 10.0;
+/// The space between the scrollbars and the edges.
+const MARGIN: f64 = 2.0;
+/// The shortest length of a thumb.
+const MIN_THUMB: f64 = 20.0;
+/// How long scrollbars stay visible after scrolling.
+const HIDE_DELAY: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn of(self, vec: Vec2) -> f64 {
+        match self {
+            Axis::Horizontal => vec.x,
+            Axis::Vertical => vec.y,
+        }
+    }
+
+    fn with(self, vec: Vec2, value: f64) -> Vec2 {
+        match self {
+            Axis::Horizontal => Vec2::new(value, vec.y),
+            Axis::Vertical => Vec2::new(vec.x, value),
+        }
+    }
+}
+
+/// A controller making the widget it controls scroll into view in a [`ScrollController`], when
+/// [`SCROLL_TO_WIDGET`] is sent with its id.
+///
+/// The id is the one of the widget holding the controller, given with `with_id`.
+pub struct ScrollTarget;
+
+impl<T, W: Widget<T>> Controller<T, W> for ScrollTarget {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if cmd.is(REVEAL) {
+                let rect = ctx.size().to_rect();
+                ctx.scroll_area_to_view(rect);
+                ctx.set_handled();
+                return;
+            }
+        }
+        child.event(ctx, event, data, env);
+    }
+}
+
+/// A scroll container animating its scrolling, with scrollbars over its content.
+///
+/// The scrollbars show up while scrolling and when the mouse is near them, and get thicker when
+/// it's over them. [`SCROLL_TO`] and [`SCROLL_TO_WIDGET`] scroll it from the outside, and the
+/// widgets inside it can reveal an area of themselves with `scroll_area_to_view`.
+///
+/// ```
+/// # use druid::widget::{Button, Flex, Label};
+/// # use druid::{Widget, WidgetExt, WidgetId};
+/// # use druid_widget_nursery::scroll_controller::{ScrollController, ScrollTarget, SCROLL_TO_WIDGET};
+/// fn chapters() -> impl Widget<()> {
+///     let scroll = WidgetId::next();
+///     let last = WidgetId::next();
+///     let mut column = Flex::column();
+///     for i in 1..=50 {
+///         column.add_child(Label::new(format!("Chapter {}", i)).padding(20.0));
+///     }
+///     column.add_child(Label::new("The end").controller(ScrollTarget).with_id(last));
+///
+///     Flex::column()
+///         .with_child(Button::new("Go to the end").on_click(move |ctx, _, _| {
+///             ctx.submit_command(SCROLL_TO_WIDGET.with(last).to(scroll));
+///         }))
+///         .with_flex_child(ScrollController::vertical(column).with_id(scroll), 1.0)
+/// }
+/// ```
+pub struct ScrollController<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    horizontal: bool,
+    vertical: bool,
+    offset: Animated<Vec2>,
+    content_size: Size,
+    /// The opacity of the scrollbars, which fade out.
+    opacity: Animated<f64>,
+    hide_timer: TimerToken,
+    /// The thickness of the horizontal and the vertical scrollbar.
+    thickness: (Animated<f64>, Animated<f64>),
+    hovered: Option<Axis>,
+    /// The scrollbar dragged, with where the mouse and the offset were when it was pressed.
+    drag: Option<(Axis, f64, Vec2)>,
+}
+
+impl<T: Data> ScrollController<T> {
+    /// Scroll `child` in both directions.
+    pub fn new(child: impl Widget<T> + 'static) -> Self {
+        let thickness = || {
+            Animated::jump(THIN)
+                .duration(0.15)
+                .curve(AnimationCurve::EASE_OUT)
+        };
+        ScrollController {
+            child: WidgetPod::new(Box::new(child)),
+            horizontal: true,
+            vertical: true,
+            offset: Animated::jump(Vec2::ZERO)
+                .duration(0.25)
+                .curve(AnimationCurve::EASE_OUT),
+            content_size: Size::ZERO,
+            opacity: Animated::jump(0.0)
+                .duration(0.3)
+                .curve(AnimationCurve::EASE_IN_OUT),
+            hide_timer: TimerToken::INVALID,
+            thickness: (thickness(), thickness()),
+            hovered: None,
+            drag: None,
+        }
+    }
+
+    /// Scroll `child` vertically, giving it the width of the container.
+    pub fn vertical(child: impl Widget<T> + 'static) -> Self {
+        let mut scroll = Self::new(child);
+        scroll.horizontal = false;
+        scroll
+    }
+
+    /// Scroll `child` horizontally, giving it the height of the container.
+    pub fn horizontal(child: impl Widget<T> + 'static) -> Self {
+        let mut scroll = Self::new(child);
+        scroll.vertical = false;
+        scroll
+    }
+
+    /// Builder-style method for setting the duration of the scrolling animation.
+    ///
+    /// For the non-builder varient, see [`set_duration`].
+    ///
+    /// [`set_duration`]: #method.set_duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.set_duration(duration);
+        self
+    }
+
+    /// Set the duration of the scrolling animation in seconds, 0 to scroll at once.
+    pub fn set_duration(&mut self, duration: f64) {
+        self.offset.set_duration(duration);
+    }
+
+    /// The offset where the animation ends.
+    pub fn offset(&self) -> Vec2 {
+        self.offset.end()
+    }
+
+    fn max_offset(&self, size: Size) -> Vec2 {
+        Vec2::new(
+            (self.content_size.width - size.width).max(0.0),
+            (self.content_size.height - size.height).max(0.0),
+        )
+    }
+
+    fn clamp(&self, offset: Vec2, size: Size) -> Vec2 {
+        let max = self.max_offset(size);
+        Vec2::new(offset.x.min(max.x).max(0.0), offset.y.min(max.y).max(0.0))
+    }
+
+    fn scroll_to(&mut self, ctx: &mut EventCtx, offset: Vec2, animate: bool) {
+        let offset = self.clamp(offset, ctx.size());
+        if animate {
+            self.offset.animate(ctx, offset);
+        } else {
+            self.offset.jump_to_value(offset);
+        }
+        // animations update it with each frame
+        self.child.set_viewport_offset(self.offset.get());
+        self.show_scrollbars(ctx);
+        ctx.request_paint();
+    }
+
+    /// Scroll as little as possible to show `rect` of the content.
+    fn reveal(&mut self, ctx: &mut EventCtx, rect: Rect) {
+        let size = ctx.size();
+        let mut offset = self.offset.end();
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            let (start, end) = match axis {
+                Axis::Horizontal => (rect.x0, rect.x1),
+                Axis::Vertical => (rect.y0, rect.y1),
+            };
+            let view = axis.of(size.to_vec2());
+            let current = axis.of(offset);
+            if start < current {
+                offset = axis.with(offset, start);
+            } else if end > current + view {
+                offset = axis.with(offset, (end - view).min(start));
+            }
+        }
+        self.scroll_to(ctx, offset, true);
+    }
+
+    fn show_scrollbars(&mut self, ctx: &mut EventCtx) {
+        self.opacity.animate(ctx, 1.0);
+        self.hide_timer = ctx.request_timer(HIDE_DELAY);
+    }
+
+    fn scrolls(&self, axis: Axis, size: Size) -> bool {
+        let enabled = match axis {
+            Axis::Horizontal => self.horizontal,
+            Axis::Vertical => self.vertical,
+        };
+        enabled && axis.of(self.max_offset(size)) > 0.0
+    }
+
+    /// The track of a scrollbar, as thick as a hovered one.
+    fn track(&self, axis: Axis, size: Size) -> Rect {
+        let corner = if self.scrolls(Axis::Horizontal, size) && self.scrolls(Axis::Vertical, size) {
+            THICK + MARGIN
+        } else {
+            0.0
+        };
+        match axis {
+            Axis::Horizontal => Rect::new(
+                MARGIN,
+                size.height - MARGIN - THICK,
+                size.width - MARGIN - corner,
+                size.height - MARGIN,
+            ),
+            Axis::Vertical => Rect::new(
+                size.width - MARGIN - THICK,
+                MARGIN,
+                size.width - MARGIN,
+                size.height - MARGIN - corner,
+            ),
+        }
+    }
+
+    /// The thumb of a scrollbar, with its thickness.
+    fn thumb(&self, axis: Axis, size: Size, thickness: f64) -> Rect {
+        let track = self.track(axis, size);
+        let (length, view, content) = match axis {
+            Axis::Horizontal => (track.width(), size.width, self.content_size.width),
+            Axis::Vertical => (track.height(), size.height, self.content_size.height),
+        };
+        let thumb = (length * view / content).max(MIN_THUMB).min(length);
+        let max = axis.of(self.max_offset(size));
+        let start = if max > 0.0 {
+            axis.of(self.offset.get()) / max * (length - thumb)
+        } else {
+            0.0
+        };
+        match axis {
+            Axis::Horizontal => Rect::new(
+                track.x0 + start,
+                track.y1 - thickness,
+                track.x0 + start + thumb,
+                track.y1,
+            ),
+            Axis::Vertical => Rect::new(
+                track.x1 - thickness,
+                track.y0 + start,
+                track.x1,
+                track.y0 + start + thumb,
+            ),
+        }
+    }
+
+    /// The scrollbar under `pos`.
+    fn scrollbar_at(&self, pos: Point, size: Size) -> Option<Axis> {
+        [Axis::Vertical, Axis::Horizontal]
+            .iter()
+            .copied()
+            .find(|&axis| self.scrolls(axis, size) && self.track(axis, size).contains(pos))
+    }
+
+    fn thickness_mut(&mut self, axis: Axis) -> &mut Animated<f64> {
+        match axis {
+            Axis::Horizontal => &mut self.thickness.0,
+            Axis::Vertical => &mut self.thickness.1,
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for ScrollController<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let size = ctx.size();
+        match event {
+            Event::AnimFrame(nanos) => {
+                if self.offset.animating() {
+                    self.offset.update(ctx, *nanos);
+                    self.child.set_viewport_offset(self.offset.get());
+                    ctx.request_paint();
+                }
+                for animated in [
+                    &mut self.opacity,
+                    &mut self.thickness.0,
+                    &mut self.thickness.1,
+                ] {
+                    if animated.animating() {
+                        animated.update(ctx, *nanos);
+                        ctx.request_paint();
+                    }
+                }
+            }
+            Event::Timer(token) if *token == self.hide_timer => {
+                if self.hovered.is_none() && self.drag.is_none() {
+                    self.opacity.animate(ctx, 0.0);
+                } else {
+                    self.hide_timer = ctx.request_timer(HIDE_DELAY);
+                }
+                return;
+            }
+            Event::Command(cmd) if cmd.is(SCROLL_TO) => {
+                let point = *cmd.get_unchecked(SCROLL_TO);
+                self.scroll_to(ctx, point.to_vec2(), true);
+                ctx.set_handled();
+                return;
+            }
+            Event::Command(cmd) if cmd.is(SCROLL_TO_WIDGET) => {
+                let id = *cmd.get_unchecked(SCROLL_TO_WIDGET);
+                ctx.submit_command(REVEAL.to(id));
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(notification) if notification.is(SCROLL_TO_VIEW) => {
+                // the area is in window coordinates
+                let area = *notification.get(SCROLL_TO_VIEW).unwrap();
+                let content_origin = ctx.window_origin().to_vec2() - self.offset.get();
+                self.reveal(ctx, area - content_origin);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                if let Some(axis) = self.scrollbar_at(mouse.pos, size) {
+                    let thumb = self.thumb(axis, size, THICK);
+                    if !thumb.contains(mouse.pos) {
+                        // jump to put the middle of the thumb under the mouse
+                        let delta = axis.of(mouse.pos - thumb.center());
+                        let length = axis.of(self.track(axis, size).size().to_vec2());
+                        let thumb_length = axis.of(thumb.size().to_vec2());
+                        let ratio = axis.of(self.max_offset(size)) / (length - thumb_length);
+                        let offset = self.offset.end();
+                        let target = axis.with(offset, axis.of(offset) + delta * ratio);
+                        self.scroll_to(ctx, target, true);
+                    }
+                    self.drag = Some((axis, axis.of(mouse.pos.to_vec2()), self.offset.end()));
+                    ctx.set_active(true);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if let Some((axis, start, offset)) = self.drag {
+                    let length = axis.of(self.track(axis, size).size().to_vec2());
+                    let thumb = axis.of(self.thumb(axis, size, THICK).size().to_vec2());
+                    let ratio = axis.of(self.max_offset(size)) / (length - thumb).max(1.0);
+                    let delta = (axis.of(mouse.pos.to_vec2()) - start) * ratio;
+                    let target = axis.with(offset, axis.of(offset) + delta);
+                    self.scroll_to(ctx, target, false);
+                    ctx.set_handled();
+                    return;
+                }
+                let hovered = self.scrollbar_at(mouse.pos, size);
+                if hovered != self.hovered {
+                    if let Some(axis) = self.hovered {
+                        self.thickness_mut(axis).animate(ctx, THIN);
+                    }
+                    if let Some(axis) = hovered {
+                        self.thickness_mut(axis).animate(ctx, THICK);
+                        self.show_scrollbars(ctx);
+                    }
+                    self.hovered = hovered;
+                }
+            }
+            Event::MouseUp(_) if self.drag.is_some() => {
+                self.drag = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        let viewport = size.to_rect();
+        let force = self.child.is_hot() || self.child.has_active();
+        if let Some(child_event) = event.transform_scroll(self.offset.get(), viewport, force) {
+            self.child.event(ctx, &child_event, data, env);
+        }
+
+        if let Event::Wheel(wheel) = event {
+            if !ctx.is_handled() {
+                let mut delta = wheel.wheel_delta;
+                if !self.horizontal {
+                    delta.x = 0.0;
+                }
+                if !self.vertical {
+                    delta = Vec2::new(delta.x + delta.y, 0.0);
+                }
+                let target = self.offset.end() + delta;
+                if self.clamp(target, size) != self.offset.end() {
+                    self.scroll_to(ctx, target, true);
+                    ctx.set_handled();
+                }
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_bc = BoxConstraints::new(
+            Size::new(
+                if self.horizontal { 0.0 } else { bc.min().width },
+                if self.vertical { 0.0 } else { bc.min().height },
+            ),
+            Size::new(
+                if self.horizontal {
+                    f64::INFINITY
+                } else {
+                    bc.max().width
+                },
+                if self.vertical {
+                    f64::INFINITY
+                } else {
+                    bc.max().height
+                },
+            ),
+        );
+        self.content_size = self.child.layout(ctx, &child_bc, data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        let size = bc.constrain(self.content_size);
+
+        // the content may have shrunk
+        let offset = self.clamp(self.offset.end(), size);
+        if offset != self.offset.end() {
+            self.offset.jump_to_value(offset);
+        }
+        self.child.set_viewport_offset(self.offset.get());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let offset = self.offset.get();
+        ctx.with_save(|ctx| {
+            ctx.clip(size.to_rect());
+            ctx.transform(Affine::translate(-offset));
+            let mut visible = ctx.region().clone();
+            visible += offset;
+            ctx.with_child_ctx(visible, |ctx| self.child.paint_raw(ctx, data, env));
+        });
+
+        let opacity = self.opacity.get();
+        if opacity <= 0.0 {
+            return;
+        }
+        let color = env.get(theme::SCROLLBAR_COLOR);
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            if !self.scrolls(axis, size) {
+                continue;
+            }
+            let thickness = match axis {
+                Axis::Horizontal => self.thickness.0.get(),
+                Axis::Vertical => self.thickness.1.get(),
+            };
+            let thumb = self.thumb(axis, size, thickness);
+            let alpha = if self.hovered == Some(axis) || self.drag.is_some() {
+                opacity
+            } else {
+                opacity * 0.7
+            };
+            ctx.fill(
+                RoundedRect::from_rect(thumb, thickness / 2.0),
+                &color.with_alpha(alpha),
+            );
+        }
+    }
+}
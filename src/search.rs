@@ -0,0 +1,408 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A search field, and labels highlighting the search query.
+//!
+//! The [`SearchBox`] edits the query. To highlight it in [`HighlightMatches`] labels elsewhere
+//! in the app, put the query into the [`SEARCH_QUERY`] key of the environment of a common
+//! parent:
+//!
+//! ```
+//! # use druid::widget::{Flex, List};
+//! # use druid::{im::Vector, Data, Env, Lens, Widget, WidgetExt};
+//! # use druid_widget_nursery::search::{HighlightMatches, SearchBox, SEARCH_QUERY};
+//! #[derive(Clone, Data, Lens)]
+//! struct AppState {
+//!     query: String,
+//!     names: Vector<String>,
+//! }
+//!
+//! fn ui() -> impl Widget<AppState> {
+//!     Flex::column()
+//!         .with_child(SearchBox::new().lens(AppState::query))
+//!         .with_child(
+//!             List::new(|| HighlightMatches::new(|name: &String, _: &Env| name.clone()))
+//!                 .lens(AppState::names),
+//!         )
+//!         .env_scope(|env, data: &AppState| env.set(SEARCH_QUERY, data.query.as_str()))
+//! }
+//! ```
+
+use std::ops::Range;
+use std::time::Duration;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::{Circle, Line};
+use druid::piet::{LineCap, StrokeStyle};
+use druid::widget::prelude::*;
+use druid::widget::{LabelText, TextBox};
+use druid::{
+    theme, ArcStr, Color, Insets, KeyOrValue, Point, Rect, TextLayout, TimerToken, WidgetPod,
+};
+
+/// The search query highlighted by [`HighlightMatches`].
+pub const SEARCH_QUERY: druid::Key<ArcStr> = druid::Key::new("druid-widget-nursery.search.query");
+
+/// The width of the magnifier icon and the clear button.
+const ICON_WIDTH: f64 = 20.0;
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+const HIGHLIGHT_COLOR: Color = Color::rgba8(0xff, 0xd5, 0x4f, 0x80);
+
+/// A text box for a search query, with a magnifier icon and a clear button.
+///
+/// The query is written to the data once typing pauses for the [debounce] delay, or right away
+/// when Enter is pressed. Escape and the clear button empty the query.
+///
+/// [debounce]: #method.with_debounce
+pub struct SearchBox {
+    text_box: WidgetPod<String, TextBox<String>>,
+    /// The text being edited, which becomes the data after the debounce delay.
+    buffer: String,
+    debounce: Duration,
+    timer: TimerToken,
+    clear_hot: bool,
+    /// The last environment we got, and the one we pass to the text box.
+    env: Option<(Env, Env)>,
+}
+
+impl SearchBox {
+    pub fn new() -> Self {
+        SearchBox {
+            text_box: WidgetPod::new(TextBox::new().with_placeholder("Search")),
+            buffer: String::new(),
+            debounce: DEFAULT_DEBOUNCE,
+            timer: TimerToken::INVALID,
+            clear_hot: false,
+            env: None,
+        }
+    }
+
+    /// Builder-style method for specifying the placeholder shown while the query is empty.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.text_box.widget_mut().set_placeholder(placeholder);
+        self
+    }
+
+    /// Builder-style method for specifying the debounce delay.
+    ///
+    /// For the non-builder varient, see [`set_debounce`].
+    ///
+    /// [`set_debounce`]: #method.set_debounce
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.set_debounce(debounce);
+        self
+    }
+
+    /// Set how long typing has to pause before the query is updated. With `Duration::ZERO`
+    /// every keystroke updates it.
+    pub fn set_debounce(&mut self, debounce: Duration) {
+        self.debounce = debounce;
+    }
+
+    fn clear_rect(&self, size: Size) -> Rect {
+        Rect::new(size.width - ICON_WIDTH, 0.0, size.width, size.height)
+    }
+
+    fn flush(&mut self, data: &mut String) {
+        self.timer = TimerToken::INVALID;
+        if *data != self.buffer {
+            *data = self.buffer.clone();
+        }
+    }
+
+    fn clear(&mut self, ctx: &mut EventCtx, data: &mut String) {
+        self.buffer.clear();
+        self.flush(data);
+        ctx.request_update();
+        ctx.request_paint();
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                // make room for the icons
+                let insets = env.get(theme::TEXTBOX_INSETS);
+                let mut child_env = env.clone();
+                child_env.set(
+                    theme::TEXTBOX_INSETS,
+                    Insets::new(
+                        insets.x0 + ICON_WIDTH,
+                        insets.y0,
+                        insets.x1 + ICON_WIDTH,
+                        insets.y1,
+                    ),
+                );
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+impl Default for SearchBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<String> for SearchBox {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut String, env: &Env) {
+        let mut flush = false;
+        match event {
+            Event::Timer(token) if *token == self.timer => {
+                self.flush(data);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse) => {
+                let clear_hot =
+                    !self.buffer.is_empty() && self.clear_rect(ctx.size()).contains(mouse.pos);
+                if clear_hot != self.clear_hot {
+                    self.clear_hot = clear_hot;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() && self.clear_hot => {
+                self.clear(ctx, data);
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if ctx.has_focus() => match &key.key {
+                Key::Escape if !self.buffer.is_empty() => {
+                    self.clear(ctx, data);
+                    ctx.set_handled();
+                    return;
+                }
+                Key::Enter => flush = true,
+                _ => {}
+            },
+            _ => {}
+        }
+
+        let child_env = self.child_env(env);
+        let old_buffer = self.buffer.clone();
+        self.text_box
+            .event(ctx, event, &mut self.buffer, &child_env);
+        if flush || self.debounce == Duration::ZERO {
+            self.flush(data);
+        } else if self.buffer != old_buffer {
+            self.timer = ctx.request_timer(self.debounce);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &String, env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => self.buffer = data.clone(),
+            LifeCycle::HotChanged(false) if self.clear_hot => {
+                self.clear_hot = false;
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+        let child_env = self.child_env(env);
+        self.text_box
+            .lifecycle(ctx, event, &self.buffer, &child_env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &String, data: &String, env: &Env) {
+        if old_data != data && *data != self.buffer {
+            // changed from outside
+            self.buffer = data.clone();
+            self.timer = TimerToken::INVALID;
+        }
+        if self.env.is_none() {
+            ctx.request_paint();
+        }
+        let child_env = self.child_env(env);
+        self.text_box.update(ctx, &self.buffer, &child_env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &String,
+        env: &Env,
+    ) -> Size {
+        let child_env = self.child_env(env);
+        let size = self.text_box.layout(ctx, bc, &self.buffer, &child_env);
+        self.text_box.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &String, env: &Env) {
+        let child_env = self.child_env(env);
+        self.text_box.paint(ctx, &self.buffer, &child_env);
+
+        let size = ctx.size();
+        let insets = env.get(theme::TEXTBOX_INSETS);
+        let style = StrokeStyle::new().line_cap(LineCap::Round);
+        let icon_color = env.get(theme::PLACEHOLDER_COLOR);
+
+        // the magnifier
+        let center = Point::new(insets.x0 + ICON_WIDTH / 2.0 - 1.0, size.height / 2.0 - 1.0);
+        ctx.stroke(Circle::new(center, 4.5), &icon_color, 1.5);
+        let handle = Line::new(center + (3.2, 3.2), center + (6.5, 6.5));
+        ctx.stroke_styled(handle, &icon_color, 1.5, &style);
+
+        // the clear button
+        if !self.buffer.is_empty() {
+            let center = self.clear_rect(size).center();
+            let color = if self.clear_hot {
+                env.get(theme::TEXT_COLOR)
+            } else {
+                icon_color
+            };
+            for (dx, dy) in [(-3.5, -3.5), (-3.5, 3.5)] {
+                let line = Line::new(center + (dx, dy), center - (dx, dy));
+                ctx.stroke_styled(line, &color, 1.5, &style);
+            }
+        }
+    }
+}
+
+/// The byte ranges of the non-overlapping occurrences of `query` in `text`, ignoring case.
+fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+    while start < chars.len() {
+        let mut matched = 0;
+        let mut end = start;
+        while matched < query.len() && end < chars.len() {
+            let lower = chars[end].1.to_lowercase();
+            let rest = &query[matched..];
+            if lower.len() > rest.len() || !lower.clone().zip(rest).all(|(a, b)| a == *b) {
+                break;
+            }
+            matched += lower.len();
+            end += 1;
+        }
+        if matched == query.len() {
+            let end_index = chars.get(end).map_or(text.len(), |(index, _)| *index);
+            matches.push(chars[start].0..end_index);
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+/// A label highlighting the occurrences of the [`SEARCH_QUERY`] in its text.
+///
+/// Case is ignored when matching. See the [module docs](self) for how to provide the query.
+pub struct HighlightMatches<T> {
+    text: LabelText<T>,
+    layout: TextLayout<ArcStr>,
+    query: String,
+    matches: Vec<Range<usize>>,
+    highlight_color: KeyOrValue<Color>,
+}
+
+impl<T: Data> HighlightMatches<T> {
+    pub fn new(text: impl Into<LabelText<T>>) -> Self {
+        HighlightMatches {
+            text: text.into(),
+            layout: TextLayout::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            highlight_color: HIGHLIGHT_COLOR.into(),
+        }
+    }
+
+    /// Builder-style method for specifying the color behind the matches.
+    ///
+    /// For the non-builder varient, see [`set_highlight_color`].
+    ///
+    /// [`set_highlight_color`]: #method.set_highlight_color
+    pub fn with_highlight_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_highlight_color(color);
+        self
+    }
+
+    /// Set the color drawn behind the matches.
+    pub fn set_highlight_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.highlight_color = color.into();
+    }
+
+    /// Take the query from the environment, returning whether it changed.
+    fn update_query(&mut self, env: &Env) -> bool {
+        let query = env
+            .try_get(SEARCH_QUERY)
+            .map(|query| query.to_string())
+            .unwrap_or_default();
+        if query != self.query {
+            self.query = query;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update_matches(&mut self) {
+        self.matches = find_matches(&self.text.display_text(), &self.query);
+    }
+}
+
+impl<T: Data> Widget<T> for HighlightMatches<T> {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.text.resolve(data, env);
+            self.layout.set_text(self.text.display_text());
+            self.update_query(env);
+            self.update_matches();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let text_changed = self.text.resolve(data, env);
+        if text_changed {
+            self.layout.set_text(self.text.display_text());
+            ctx.request_layout();
+        }
+        if self.update_query(env) || text_changed {
+            self.update_matches();
+            ctx.request_paint();
+        }
+        if self.layout.needs_rebuild_after_update(ctx) || ctx.env_key_changed(&self.highlight_color)
+        {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        self.layout.set_wrap_width(bc.max().width);
+        self.layout.rebuild_if_needed(ctx.text(), env);
+        bc.constrain(self.layout.size())
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let color = self.highlight_color.resolve(env);
+        for range in &self.matches {
+            for rect in self.layout.rects_for_range(range.clone()) {
+                ctx.fill(rect.to_rounded_rect(2.0), &color);
+            }
+        }
+        self.layout.draw(ctx, Point::ORIGIN);
+    }
+}
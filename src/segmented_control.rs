@@ -0,0 +1,276 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::keyboard_types::Key;
+use druid::kurbo::Line;
+use druid::widget::prelude::*;
+use druid::widget::{Label, LabelText};
+use druid::{theme, LinearGradient, Point, Rect, UnitPoint, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, Interpolate};
+
+/// The horizontal space between a label and the edges of its segment.
+const SEGMENT_PADDING: f64 = 10.0;
+
+struct Segment<T> {
+    label: WidgetPod<T, Label<T>>,
+    variant: T,
+    rect: Rect,
+}
+
+/// A row of adjacent buttons of which exactly one is selected, like a compact [`RadioGroup`].
+///
+/// The selection indicator slides to the picked segment. When focused, the left and right arrow
+/// keys cycle through the variants.
+///
+/// ```
+/// # use druid::{Data, Widget};
+/// # use druid_widget_nursery::SegmentedControl;
+/// #[derive(Clone, Copy, Data, PartialEq)]
+/// enum Align {
+///     Left,
+///     Center,
+///     Right,
+/// }
+///
+/// fn alignment() -> impl Widget<Align> {
+///     SegmentedControl::new(vec![
+///         ("Left", Align::Left),
+///         ("Center", Align::Center),
+///         ("Right", Align::Right),
+///     ])
+/// }
+/// ```
+///
+/// [`RadioGroup`]: druid::widget::RadioGroup
+pub struct SegmentedControl<T> {
+    segments: Vec<Segment<T>>,
+    /// The index of the selected segment, animated between segments.
+    position: Animated<f64>,
+    /// The segment the mouse was pressed on.
+    pressed: Option<usize>,
+}
+
+impl<T: Data + PartialEq> SegmentedControl<T> {
+    /// Create a control with a segment for each variant.
+    pub fn new(variants: impl IntoIterator<Item = (impl Into<LabelText<T>> + 'static, T)>) -> Self {
+        let segments = variants
+            .into_iter()
+            .map(|(label, variant)| Segment {
+                label: WidgetPod::new(Label::new(label)),
+                variant,
+                rect: Rect::ZERO,
+            })
+            .collect();
+        SegmentedControl {
+            segments,
+            position: Animated::new(0.0)
+                .duration(0.2)
+                .curve(AnimationCurve::EASE_OUT),
+            pressed: None,
+        }
+    }
+
+    fn selected(&self, data: &T) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|segment| segment.variant == *data)
+    }
+
+    fn segment_at(&self, pos: Point) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|segment| segment.rect.contains(pos))
+    }
+
+    /// The rect of the selection indicator at the current position of the animation.
+    fn indicator_rect(&self) -> Option<Rect> {
+        let last = self.segments.len().checked_sub(1)?;
+        let position = self.position.get().max(0.0).min(last as f64);
+        let from = position.floor() as usize;
+        let to = position.ceil() as usize;
+        Some(
+            self.segments[from]
+                .rect
+                .interpolate(&self.segments[to].rect, position - from as f64),
+        )
+    }
+}
+
+impl<T: Data + PartialEq> Widget<T> for SegmentedControl<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            self.position.update(ctx, *nanos);
+        }
+        if ctx.is_disabled() {
+            return;
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                self.pressed = self.segment_at(mouse.pos);
+                ctx.set_active(true);
+                ctx.request_focus();
+                ctx.request_paint();
+            }
+            Event::MouseUp(mouse) if ctx.is_active() => {
+                ctx.set_active(false);
+                if let Some(index) = self.pressed.take() {
+                    if ctx.is_hot() && self.segment_at(mouse.pos) == Some(index) {
+                        *data = self.segments[index].variant.clone();
+                    }
+                }
+                ctx.request_paint();
+            }
+            Event::KeyDown(key) if ctx.has_focus() && !self.segments.is_empty() => {
+                let count = self.segments.len();
+                let index = match (&key.key, self.selected(data)) {
+                    (Key::ArrowRight, Some(index)) => (index + 1) % count,
+                    (Key::ArrowLeft, Some(index)) => (index + count - 1) % count,
+                    (Key::ArrowRight, None) => 0,
+                    (Key::ArrowLeft, None) => count - 1,
+                    _ => return,
+                };
+                *data = self.segments[index].variant.clone();
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.register_for_focus();
+                if let Some(index) = self.selected(data) {
+                    self.position.jump_to_value(index as f64);
+                }
+            }
+            LifeCycle::HotChanged(_)
+            | LifeCycle::FocusChanged(_)
+            | LifeCycle::DisabledChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+        for segment in &mut self.segments {
+            segment.label.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if old_data != data {
+            if let Some(index) = self.selected(data) {
+                if self.selected(old_data).is_some() {
+                    self.position.animate(ctx, index as f64);
+                } else {
+                    self.position.jump_to_value(index as f64);
+                }
+            }
+            ctx.request_paint();
+        }
+        for segment in &mut self.segments {
+            segment.label.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        let label_bc = BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, height));
+        let label_sizes: Vec<Size> = self
+            .segments
+            .iter_mut()
+            .map(|segment| segment.label.layout(ctx, &label_bc, data, env))
+            .collect();
+
+        let natural_width: f64 = label_sizes
+            .iter()
+            .map(|size| size.width + 2.0 * SEGMENT_PADDING)
+            .sum();
+        let size = bc.constrain(Size::new(natural_width, height));
+        // spread any extra width evenly over the segments
+        let extra = if self.segments.is_empty() {
+            0.0
+        } else {
+            (size.width - natural_width) / self.segments.len() as f64
+        };
+
+        let mut x = 0.0;
+        for (segment, label_size) in self.segments.iter_mut().zip(label_sizes) {
+            let width = label_size.width + 2.0 * SEGMENT_PADDING + extra;
+            segment.rect = Rect::new(x, 0.0, x + width, size.height);
+            segment.label.set_origin(
+                ctx,
+                Point::new(
+                    x + (width - label_size.width) / 2.0,
+                    (size.height - label_size.height) / 2.0,
+                ),
+            );
+            x += width;
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let radius = env.get(theme::BUTTON_BORDER_RADIUS);
+        let track = ctx.size().to_rect().inset(-0.5).to_rounded_rect(radius);
+        ctx.fill(track, &env.get(theme::BACKGROUND_LIGHT));
+
+        let disabled = ctx.is_disabled();
+        let indicator = self.selected(data).and(self.indicator_rect());
+        if let Some(rect) = indicator {
+            let (light, dark) = if disabled {
+                (
+                    env.get(theme::DISABLED_BUTTON_LIGHT),
+                    env.get(theme::DISABLED_BUTTON_DARK),
+                )
+            } else {
+                (env.get(theme::BUTTON_LIGHT), env.get(theme::BUTTON_DARK))
+            };
+            let gradient = LinearGradient::new(UnitPoint::TOP, UnitPoint::BOTTOM, (light, dark));
+            ctx.fill(rect.inset(-1.0).to_rounded_rect(radius), &gradient);
+        }
+
+        // separators between the segments, hidden next to the indicator
+        for segment in self.segments.iter().skip(1) {
+            let x = segment.rect.x0;
+            if let Some(rect) = indicator {
+                if x > rect.x0 - 1.0 && x < rect.x1 + 1.0 {
+                    continue;
+                }
+            }
+            let line = Line::new((x, 4.0), (x, ctx.size().height - 4.0));
+            ctx.stroke(line, &env.get(theme::BORDER_DARK), 1.0);
+        }
+
+        if let Some(index) = self.pressed.filter(|_| ctx.is_active()) {
+            let rect = self.segments[index]
+                .rect
+                .inset(-1.0)
+                .to_rounded_rect(radius);
+            ctx.fill(rect, &env.get(theme::BUTTON_DARK).with_alpha(0.5));
+        }
+
+        let border_color = if ctx.is_focused() {
+            env.get(theme::PRIMARY_LIGHT)
+        } else if ctx.is_hot() && !disabled {
+            env.get(theme::BORDER_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(track, &border_color, 1.0);
+
+        for segment in &mut self.segments {
+            segment.label.paint(ctx, data, env);
+        }
+    }
+}
@@ -14,16 +14,43 @@
 
 //! A separator widget.
 
+use druid::kurbo::Line;
+use druid::piet::{GradientStop, LineCap, StrokeStyle};
 use druid::widget::prelude::*;
-use druid::{kurbo::Line, piet::StrokeStyle};
-use druid::{theme, Color, KeyOrValue};
+use druid::{theme, ArcStr, Color, KeyOrValue, LinearGradient, Point, TextLayout, UnitPoint};
+
+/// The space between the label and the line on each side.
+const LABEL_PADDING: f64 = 8.0;
 
 /// A separator widget.
+///
+/// The line can have a label in its middle, like "OR" between two options or the title of a
+/// section, can be dashed or dotted, and can fade out at its ends.
+///
+/// ```
+/// # use druid::widget::{Flex, Label};
+/// # use druid::Widget;
+/// # use druid_widget_nursery::{LineStyle, Separator};
+/// fn login() -> impl Widget<()> {
+///     Flex::column()
+///         .with_child(Label::new("Sign in with a password"))
+///         .with_child(
+///             Separator::new()
+///                 .with_label("OR")
+///                 .with_line_style(LineStyle::Dashed)
+///                 .with_fade(40.0),
+///         )
+///         .with_child(Label::new("Sign in with a key"))
+/// }
+/// ```
 pub struct Separator {
     width: KeyOrValue<f64>,
     color: KeyOrValue<Color>,
     orientation: Orientation,
     stroke_style: StrokeStyle,
+    line_style: LineStyle,
+    label: Option<TextLayout<ArcStr>>,
+    fade: f64,
 }
 
 pub enum Orientation {
@@ -31,6 +58,17 @@ pub enum Orientation {
     Horizontal,
 }
 
+/// How the line of a [`Separator`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    /// A continuous line, drawn with the stroke style of the separator.
+    Solid,
+    /// Dashes three times as long as the line is thick.
+    Dashed,
+    /// Round dots, as wide as the line is thick.
+    Dotted,
+}
+
 impl Default for Separator {
     fn default() -> Self {
         Separator {
@@ -38,6 +76,9 @@ impl Default for Separator {
             color: theme::BORDER_LIGHT.into(),
             orientation: Orientation::Horizontal,
             stroke_style: StrokeStyle::new(),
+            line_style: LineStyle::Solid,
+            label: None,
+            fade: 0.0,
         }
     }
 }
@@ -84,6 +125,117 @@ impl Separator {
     pub fn set_orientation(&mut self, orientation: Orientation) {
         self.orientation = orientation;
     }
+
+    /// Builder-style method for drawing the line solid, dashed or dotted.
+    ///
+    /// For the non-builder varient, see [`set_line_style`].
+    ///
+    /// [`set_line_style`]: #method.set_line_style
+    pub fn with_line_style(mut self, line_style: LineStyle) -> Self {
+        self.set_line_style(line_style);
+        self
+    }
+
+    /// Set the [`LineStyle`]. The dashes and the dots scale with the width of the separator.
+    pub fn set_line_style(&mut self, line_style: LineStyle) {
+        self.line_style = line_style;
+    }
+
+    /// Builder-style method for showing a label in the middle of the line.
+    ///
+    /// For the non-builder varient, see [`set_label`].
+    ///
+    /// [`set_label`]: #method.set_label
+    pub fn with_label(mut self, label: impl Into<ArcStr>) -> Self {
+        self.set_label(Some(label.into()));
+        self
+    }
+
+    /// Set the label shown in the middle of the line, or `None` for an unbroken line.
+    pub fn set_label(&mut self, label: Option<ArcStr>) {
+        self.label = label.map(TextLayout::from_text);
+    }
+
+    /// Builder-style method for fading the line out at its ends.
+    ///
+    /// For the non-builder varient, see [`set_fade`].
+    ///
+    /// [`set_fade`]: #method.set_fade
+    pub fn with_fade(mut self, length: f64) -> Self {
+        self.set_fade(length);
+        self
+    }
+
+    /// Set the length over which the line fades out at its ends. Next to a label, the line
+    /// doesn't fade.
+    pub fn set_fade(&mut self, length: f64) {
+        self.fade = length;
+    }
+
+    /// The point at `major` along the line and `minor` across it.
+    fn point(&self, major: f64, minor: f64) -> Point {
+        match self.orientation {
+            Orientation::Vertical => Point::new(minor, major),
+            Orientation::Horizontal => Point::new(major, minor),
+        }
+    }
+
+    /// Stroke the line from `start` to `end` along the middle of the separator, fading out at
+    /// the ends where `fade_start` and `fade_end` are set.
+    fn stroke(
+        &self,
+        ctx: &mut PaintCtx,
+        (start, end): (f64, f64),
+        (fade_start, fade_end): (bool, bool),
+        color: &Color,
+        width: f64,
+        style: &StrokeStyle,
+    ) {
+        let length = end - start;
+        if length <= 0.0 {
+            return;
+        }
+        let size = ctx.size();
+        let middle = match self.orientation {
+            Orientation::Vertical => size.width / 2.0,
+            Orientation::Horizontal => size.height / 2.0,
+        };
+        let line = Line::new(self.point(start, middle), self.point(end, middle));
+
+        let fade = (self.fade / length).min(0.5) as f32;
+        if fade <= 0.0 || !(fade_start || fade_end) {
+            ctx.stroke_styled(line, color, width, style);
+            return;
+        }
+        let transparent = color.with_alpha(0.0);
+        let mut stops = Vec::with_capacity(4);
+        if fade_start {
+            stops.push(GradientStop {
+                pos: 0.0,
+                color: transparent,
+            });
+        }
+        stops.push(GradientStop {
+            pos: if fade_start { fade } else { 0.0 },
+            color: *color,
+        });
+        stops.push(GradientStop {
+            pos: if fade_end { 1.0 - fade } else { 1.0 },
+            color: *color,
+        });
+        if fade_end {
+            stops.push(GradientStop {
+                pos: 1.0,
+                color: transparent,
+            });
+        }
+        let (from, to) = match self.orientation {
+            Orientation::Vertical => (UnitPoint::TOP, UnitPoint::BOTTOM),
+            Orientation::Horizontal => (UnitPoint::LEFT, UnitPoint::RIGHT),
+        };
+        let gradient = LinearGradient::new(from, to, stops);
+        ctx.stroke_styled(line, &gradient, width, style);
+    }
 }
 
 impl<T> Widget<T> for Separator {
@@ -91,21 +243,70 @@ impl<T> Widget<T> for Separator {
 
     fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
 
-    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {}
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
+        if let Some(label) = &mut self.label {
+            if label.needs_rebuild_after_update(ctx) {
+                ctx.request_layout();
+            }
+        }
+    }
 
-    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
         let width = self.width.resolve(env);
+        let label_size = match &mut self.label {
+            Some(label) => {
+                label.rebuild_if_needed(ctx.text(), env);
+                label.size()
+            }
+            None => Size::ZERO,
+        };
+        // the separator is as thick as the line, or as the label across it
         let size = match self.orientation {
-            Orientation::Vertical => (width, f64::INFINITY),
-            Orientation::Horizontal => (f64::INFINITY, width),
+            Orientation::Vertical => (width.max(label_size.width), f64::INFINITY),
+            Orientation::Horizontal => (f64::INFINITY, width.max(label_size.height)),
         };
         bc.constrain(size)
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
-        let line = Line::new((0., 0.), ctx.size().to_vec2().to_point());
+        let size = ctx.size();
         let color = self.color.resolve(env);
         let width = self.width.resolve(env);
-        ctx.stroke_styled(line, &color, width, &self.stroke_style);
+        let mut style = self.stroke_style.clone();
+        match self.line_style {
+            LineStyle::Solid => {}
+            LineStyle::Dashed => style.set_dash_pattern(vec![width * 3.0, width * 2.0]),
+            LineStyle::Dotted => {
+                style.set_line_cap(LineCap::Round);
+                style.set_dash_pattern(vec![0.0, width * 2.0]);
+            }
+        }
+
+        let length = match self.orientation {
+            Orientation::Vertical => size.height,
+            Orientation::Horizontal => size.width,
+        };
+        let label_length = match &self.label {
+            Some(label) => {
+                let label_size = label.size();
+                let origin = Point::new(
+                    (size.width - label_size.width) / 2.0,
+                    (size.height - label_size.height) / 2.0,
+                );
+                label.draw(ctx, origin);
+                match self.orientation {
+                    Orientation::Vertical => label_size.height,
+                    Orientation::Horizontal => label_size.width,
+                }
+            }
+            None => {
+                self.stroke(ctx, (0.0, length), (true, true), &color, width, &style);
+                return;
+            }
+        };
+        let gap_start = (length - label_length) / 2.0 - LABEL_PADDING;
+        let gap_end = (length + label_length) / 2.0 + LABEL_PADDING;
+        self.stroke(ctx, (0.0, gap_start), (true, false), &color, width, &style);
+        self.stroke(ctx, (gap_end, length), (false, true), &color, width, &style);
     }
 }
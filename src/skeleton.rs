@@ -0,0 +1,274 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::kurbo::RoundedRect;
+use druid::widget::prelude::*;
+use druid::widget::Maybe;
+use druid::{theme, Color, KeyOrValue, LinearGradient, Point, Rect, UnitPoint, WidgetPod};
+
+use crate::animation::{AnimationCurve, KeyframeAnimation, KeyframeRepeat, Keyframes};
+use crate::RequestCtx;
+
+/// The width of lines if the width isn't constrained.
+const DEFAULT_LINE_WIDTH: f64 = 200.0;
+/// The width of the shimmering band, relative to the width of the placeholder.
+const SHIMMER_WIDTH: f64 = 0.4;
+
+/// The shape of the placeholder shown by a [`Skeleton`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkeletonShape {
+    /// Lines of text as wide as possible, the last one shorter.
+    Lines(usize),
+    /// A circle with the given diameter, for example for an avatar.
+    Circle(f64),
+    /// A rectangle with rounded corners, for example for an image.
+    Rect(Size),
+}
+
+/// A widget showing a shimmering placeholder instead of its child while data is loading.
+///
+/// ```
+/// # use druid::widget::Label;
+/// # use druid::{Data, Lens, Widget, WidgetExt};
+/// # use druid_widget_nursery::{Skeleton, SkeletonShape};
+/// #[derive(Clone, Data, Lens)]
+/// struct Profile {
+///     loading: bool,
+///     bio: String,
+/// }
+///
+/// fn bio() -> impl Widget<Profile> {
+///     Skeleton::new(
+///         SkeletonShape::Lines(3),
+///         Label::raw().lens(Profile::bio),
+///         |profile: &Profile| profile.loading,
+///     )
+/// }
+///
+/// fn avatar() -> impl Widget<Option<String>> {
+///     Skeleton::for_option(SkeletonShape::Circle(48.0), Label::raw)
+/// }
+/// ```
+pub struct Skeleton<T> {
+    shape: SkeletonShape,
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    is_loading: Box<dyn Fn(&T) -> bool>,
+    loading: bool,
+    color: KeyOrValue<Color>,
+    highlight_color: KeyOrValue<Color>,
+    shimmer: KeyframeAnimation<f64>,
+}
+
+impl<T: Data> Skeleton<T> {
+    /// Create a skeleton showing the placeholder while `is_loading` returns true.
+    pub fn new(
+        shape: SkeletonShape,
+        child: impl Widget<T> + 'static,
+        is_loading: impl Fn(&T) -> bool + 'static,
+    ) -> Self {
+        Skeleton {
+            shape,
+            child: WidgetPod::new(Box::new(child)),
+            is_loading: Box::new(is_loading),
+            loading: false,
+            color: theme::BACKGROUND_LIGHT.into(),
+            highlight_color: Color::WHITE.with_alpha(0.12).into(),
+            shimmer: KeyframeAnimation::new(Keyframes::new(0.0).keyframe(
+                1.0,
+                1.0,
+                AnimationCurve::LINEAR,
+            ))
+            .duration(1.4)
+            .repeat(KeyframeRepeat::Loop),
+        }
+    }
+
+    /// Builder-style method for specifying the color of the placeholder.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the placeholder.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the color of the shimmer.
+    ///
+    /// For the non-builder varient, see [`set_highlight_color`].
+    ///
+    /// [`set_highlight_color`]: #method.set_highlight_color
+    pub fn with_highlight_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_highlight_color(color);
+        self
+    }
+
+    /// Set the color of the shimmer moving over the placeholder.
+    pub fn set_highlight_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.highlight_color = color.into();
+    }
+
+    /// Returns true if the placeholder is shown.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    fn set_loading(&mut self, ctx: &mut impl RequestCtx, loading: bool) {
+        self.loading = loading;
+        if loading {
+            self.shimmer.start(ctx);
+        } else {
+            self.shimmer.stop();
+        }
+    }
+
+    fn placeholder_size(&self, bc: &BoxConstraints, env: &Env) -> Size {
+        match self.shape {
+            SkeletonShape::Lines(count) => {
+                let width = if bc.is_width_bounded() {
+                    bc.max().width
+                } else {
+                    DEFAULT_LINE_WIDTH
+                };
+                let line_height = env.get(theme::TEXT_SIZE_NORMAL);
+                let count = count.max(1) as f64;
+                Size::new(width, line_height * (count * 1.5 - 0.5))
+            }
+            SkeletonShape::Circle(diameter) => Size::new(diameter, diameter),
+            SkeletonShape::Rect(size) => size,
+        }
+    }
+
+    /// The blocks making up the placeholder.
+    fn blocks(&self, size: Size, env: &Env) -> Vec<RoundedRect> {
+        match self.shape {
+            SkeletonShape::Lines(count) => {
+                let line_height = env.get(theme::TEXT_SIZE_NORMAL);
+                let count = count.max(1);
+                (0..count)
+                    .map(|line| {
+                        let width = if line + 1 == count && count > 1 {
+                            size.width * 0.6
+                        } else {
+                            size.width
+                        };
+                        let y = line as f64 * line_height * 1.5;
+                        Rect::from_origin_size((0.0, y), (width, line_height))
+                            .to_rounded_rect(line_height / 4.0)
+                    })
+                    .collect()
+            }
+            SkeletonShape::Circle(_) => {
+                vec![size.to_rect().to_rounded_rect(size.min_side() / 2.0)]
+            }
+            SkeletonShape::Rect(_) => {
+                vec![size
+                    .to_rect()
+                    .to_rounded_rect(env.get(theme::BUTTON_BORDER_RADIUS))]
+            }
+        }
+    }
+}
+
+impl<U: Data> Skeleton<Option<U>> {
+    /// Create a skeleton showing the placeholder while the data is `None`, and the widget built
+    /// by `child` for `Some`.
+    pub fn for_option<W: Widget<U> + 'static>(
+        shape: SkeletonShape,
+        child: impl Fn() -> W + 'static,
+    ) -> Self {
+        Skeleton::new(shape, Maybe::or_empty(child), Option::is_none)
+    }
+}
+
+impl<T: Data> Widget<T> for Skeleton<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            if self.shimmer.animating() {
+                self.shimmer.update(ctx, *nanos);
+            }
+        }
+        if !self.loading || event.should_propagate_to_hidden() {
+            self.child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            let loading = (self.is_loading)(data);
+            self.set_loading(ctx, loading);
+        }
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let loading = (self.is_loading)(data);
+        if loading != self.loading {
+            self.set_loading(ctx, loading);
+            ctx.request_layout();
+        }
+        if ctx.env_key_changed(&self.color) || ctx.env_key_changed(&self.highlight_color) {
+            ctx.request_paint();
+        }
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        if self.loading {
+            bc.constrain(self.placeholder_size(bc, env))
+        } else {
+            child_size
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        if !self.loading {
+            self.child.paint(ctx, data, env);
+            return;
+        }
+
+        let size = ctx.size();
+        let color = self.color.resolve(env);
+        let highlight = self.highlight_color.resolve(env);
+
+        // a band of highlight moving from left to right
+        let band_width = size.width * SHIMMER_WIDTH;
+        let band_x = -band_width + self.shimmer.get() * (size.width + band_width);
+        let band = Rect::from_origin_size((band_x, 0.0), (band_width, size.height));
+        let gradient = LinearGradient::new(
+            UnitPoint::LEFT,
+            UnitPoint::RIGHT,
+            (
+                highlight.with_alpha(0.0),
+                highlight,
+                highlight.with_alpha(0.0),
+            ),
+        );
+
+        for block in self.blocks(size, env) {
+            ctx.fill(block, &color);
+            ctx.with_save(|ctx| {
+                ctx.clip(block);
+                ctx.fill(band, &gradient);
+            });
+        }
+    }
+}
@@ -0,0 +1,660 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::im::Vector;
+use druid::kurbo::{BezPath, Circle, Line};
+use druid::piet::{Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, KeyOrValue, Point, Rect};
+
+/// The size taken when the constraints are loose.
+const DEFAULT_SIZE: Size = Size::new(80.0, 20.0);
+/// The space kept around the line, for its width and the hover dot.
+const INSET: f64 = 2.0;
+const BAR_GAP: f64 = 1.0;
+
+/// What the mini-charts have in common: a range of values, colored bands, and the value under the
+/// mouse.
+struct Chart {
+    format: Box<dyn Fn(f64) -> String>,
+    range: Option<(f64, f64)>,
+    bands: Vec<(f64, f64, KeyOrValue<Color>)>,
+    hovered: Option<usize>,
+}
+
+impl Chart {
+    fn new() -> Self {
+        Chart {
+            format: Box::new(|value| {
+                if value.fract() == 0.0 {
+                    format!("{:.0}", value)
+                } else {
+                    format!("{:.2}", value)
+                }
+            }),
+            range: None,
+            bands: Vec::new(),
+            hovered: None,
+        }
+    }
+
+    /// The lowest and the highest value shown, `zero` being always included for bars.
+    fn value_range(&self, data: &Vector<f64>, zero: bool) -> (f64, f64) {
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let start = if zero { 0.0 } else { f64::INFINITY };
+            let min = data.iter().copied().fold(start, f64::min);
+            let start = if zero { 0.0 } else { f64::NEG_INFINITY };
+            let max = data.iter().copied().fold(start, f64::max);
+            (min, max)
+        });
+        if !min.is_finite() || !max.is_finite() {
+            (0.0, 1.0)
+        } else if max - min < f64::EPSILON {
+            // a flat line is drawn in the middle
+            (min - 1.0, max + 1.0)
+        } else {
+            (min, max)
+        }
+    }
+
+    /// The vertical position of `value`, in a widget of `height`.
+    fn y_at(value: f64, (min, max): (f64, f64), height: f64) -> f64 {
+        INSET + (max - value) / (max - min) * (height - 2.0 * INSET)
+    }
+
+    fn set_hovered(&mut self, ctx: &mut EventCtx, hovered: Option<usize>) {
+        if self.hovered != hovered {
+            self.hovered = hovered;
+            ctx.request_paint();
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::HotChanged(false) = event {
+            self.hovered = None;
+            ctx.request_paint();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &Vector<f64>, data: &Vector<f64>) {
+        if !old_data.same(data) {
+            if self.hovered.map_or(false, |index| index >= data.len()) {
+                self.hovered = None;
+            }
+            ctx.request_paint();
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn paint_bands(&self, ctx: &mut PaintCtx, range: (f64, f64), env: &Env) {
+        let size = ctx.size();
+        for (from, to, color) in &self.bands {
+            let y0 = Self::y_at(from.max(*to), range, size.height);
+            let y1 = Self::y_at(from.min(*to), range, size.height);
+            let rect = Rect::new(0.0, y0, size.width, y1).intersect(size.to_rect());
+            ctx.fill(rect, &color.resolve(env));
+        }
+    }
+
+    /// Show the hovered value in a bubble next to `anchor`, inside the widget.
+    fn paint_value(&self, ctx: &mut PaintCtx, value: f64, anchor: Point, env: &Env) {
+        let size = ctx.size();
+        let font = env.get(theme::UI_FONT);
+        let layout = ctx
+            .text()
+            .new_text_layout((self.format)(value))
+            .font(font.family, env.get(theme::TEXT_SIZE_SMALL))
+            .text_color(Color::WHITE)
+            .build()
+            .unwrap();
+        let text_size = layout.size();
+        let bubble = Size::new(text_size.width + 6.0, text_size.height + 2.0);
+        let x = (anchor.x - bubble.width / 2.0)
+            .min(size.width - bubble.width)
+            .max(0.0);
+        // above the value, or below it when there is no room
+        let y = if anchor.y - bubble.height - 3.0 >= 0.0 {
+            anchor.y - bubble.height - 3.0
+        } else {
+            (anchor.y + 3.0).min(size.height - bubble.height).max(0.0)
+        };
+        let rect = Rect::from_origin_size((x, y), bubble);
+        ctx.fill(rect.to_rounded_rect(3.0), &Color::rgba8(0, 0, 0, 0xc0));
+        ctx.draw_text(&layout, (x + 3.0, y + 1.0));
+    }
+}
+
+/// Builder methods for the value range, the bands and the format of a mini-chart.
+macro_rules! chart_builders {
+    ($ty:ty) => {
+        impl $ty {
+            /// Builder-style method for specifying the lowest and the highest value shown,
+            /// instead of the ones of the data.
+            ///
+            /// For the non-builder varient, see [`set_range`].
+            ///
+            /// [`set_range`]: #method.set_range
+            pub fn with_range(mut self, min: f64, max: f64) -> Self {
+                self.set_range(Some((min, max)));
+                self
+            }
+
+            /// Set the lowest and the highest value shown, or `None` to fit the data.
+            pub fn set_range(&mut self, range: Option<(f64, f64)>) {
+                self.chart.range = range;
+            }
+
+            /// Builder-style method for coloring the background between two values, like to
+            /// show the normal range.
+            pub fn with_band(
+                mut self,
+                from: f64,
+                to: f64,
+                color: impl Into<KeyOrValue<Color>>,
+            ) -> Self {
+                self.add_band(from, to, color);
+                self
+            }
+
+            /// Color the background between two values.
+            pub fn add_band(&mut self, from: f64, to: f64, color: impl Into<KeyOrValue<Color>>) {
+                self.chart.bands.push((from, to, color.into()));
+            }
+
+            /// Builder-style method for turning the value under the mouse into text.
+            pub fn with_format(mut self, format: impl Fn(f64) -> String + 'static) -> Self {
+                self.chart.format = Box::new(format);
+                self
+            }
+        }
+    };
+}
+
+/// A small line chart of a series of values, to show a trend in a table or a list.
+///
+/// The value under the mouse is shown in a bubble. The background can be colored between values
+/// with [`with_band`].
+///
+/// ```
+/// # use druid::im::Vector;
+/// # use druid::{Color, Widget, WidgetExt};
+/// # use druid_widget_nursery::Sparkline;
+/// fn cpu_usage() -> impl Widget<Vector<f64>> {
+///     Sparkline::new()
+///         .with_fill(true)
+///         .with_range(0.0, 100.0)
+///         .with_band(80.0, 100.0, Color::rgba8(0xd3, 0x2f, 0x2f, 0x40))
+///         .with_format(|percent| format!("{:.0}%", percent))
+///         .fix_size(120.0, 24.0)
+/// }
+/// ```
+///
+/// [`with_band`]: #method.with_band
+pub struct Sparkline {
+    chart: Chart,
+    color: KeyOrValue<Color>,
+    line_width: f64,
+    fill: bool,
+}
+
+impl Sparkline {
+    pub fn new() -> Self {
+        Sparkline {
+            chart: Chart::new(),
+            color: theme::PRIMARY_LIGHT.into(),
+            line_width: 1.5,
+            fill: false,
+        }
+    }
+
+    /// Builder-style method for specifying the color of the line.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the line.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the width of the line.
+    ///
+    /// For the non-builder varient, see [`set_line_width`].
+    ///
+    /// [`set_line_width`]: #method.set_line_width
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.set_line_width(width);
+        self
+    }
+
+    /// Set the width of the line, 1.5 by default.
+    pub fn set_line_width(&mut self, width: f64) {
+        self.line_width = width;
+    }
+
+    /// Builder-style method for filling the area under the line.
+    ///
+    /// For the non-builder varient, see [`set_fill`].
+    ///
+    /// [`set_fill`]: #method.set_fill
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        self.set_fill(fill);
+        self
+    }
+
+    /// Set whether the area under the line is filled.
+    pub fn set_fill(&mut self, fill: bool) {
+        self.fill = fill;
+    }
+
+    fn point_at(index: usize, value: f64, count: usize, range: (f64, f64), size: Size) -> Point {
+        let step = (size.width - 2.0 * INSET) / (count.max(2) - 1) as f64;
+        Point::new(
+            INSET + index as f64 * step,
+            Chart::y_at(value, range, size.height),
+        )
+    }
+}
+
+impl Default for Sparkline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+chart_builders!(Sparkline);
+
+impl Widget<Vector<f64>> for Sparkline {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Vector<f64>, _env: &Env) {
+        if let Event::MouseMove(mouse) = event {
+            let count = data.len();
+            let hovered = if count == 0 {
+                None
+            } else {
+                // the nearest point
+                let step = (ctx.size().width - 2.0 * INSET) / (count.max(2) - 1) as f64;
+                let index = ((mouse.pos.x - INSET) / step).round().max(0.0) as usize;
+                Some(index.min(count - 1))
+            };
+            self.chart.set_hovered(ctx, hovered);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.lifecycle(ctx, event);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &Vector<f64>,
+        data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.update(ctx, old_data, data);
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain(DEFAULT_SIZE)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Vector<f64>, env: &Env) {
+        let size = ctx.size();
+        let range = self.chart.value_range(data, false);
+        self.chart.paint_bands(ctx, range, env);
+        if data.is_empty() {
+            return;
+        }
+
+        let count = data.len();
+        let mut line = BezPath::new();
+        for (index, value) in data.iter().enumerate() {
+            let point = Self::point_at(index, *value, count, range, size);
+            if index == 0 {
+                line.move_to(point);
+            } else {
+                line.line_to(point);
+            }
+        }
+        let color = self.color.resolve(env);
+        if self.fill {
+            let mut area = line.clone();
+            area.line_to((
+                Self::point_at(count - 1, 0.0, count, range, size).x,
+                size.height,
+            ));
+            area.line_to((INSET, size.height));
+            area.close_path();
+            ctx.fill(area, &color.with_alpha(0.25));
+        }
+        ctx.stroke(line, &color, self.line_width);
+
+        if let Some(index) = self.chart.hovered {
+            if let Some(value) = data.get(index) {
+                let point = Self::point_at(index, *value, count, range, size);
+                ctx.fill(Circle::new(point, self.line_width + 1.0), &color);
+                self.chart.paint_value(ctx, *value, point, env);
+            }
+        }
+    }
+}
+
+/// A small bar chart of a series of values, with the bars of negative values going down from
+/// zero.
+///
+/// The value under the mouse is shown in a bubble. The background can be colored between values
+/// with [`with_band`].
+///
+/// ```
+/// # use druid::im::Vector;
+/// # use druid::{Color, Widget, WidgetExt};
+/// # use druid_widget_nursery::BarSparkline;
+/// fn balance() -> impl Widget<Vector<f64>> {
+///     BarSparkline::new()
+///         .with_negative_color(Color::rgb8(0xd3, 0x2f, 0x2f))
+///         .fix_size(120.0, 24.0)
+/// }
+/// ```
+///
+/// [`with_band`]: #method.with_band
+pub struct BarSparkline {
+    chart: Chart,
+    color: KeyOrValue<Color>,
+    negative_color: Option<KeyOrValue<Color>>,
+}
+
+impl BarSparkline {
+    pub fn new() -> Self {
+        BarSparkline {
+            chart: Chart::new(),
+            color: theme::PRIMARY_LIGHT.into(),
+            negative_color: None,
+        }
+    }
+
+    /// Builder-style method for specifying the color of the bars.
+    ///
+    /// For the non-builder varient, see [`set_color`].
+    ///
+    /// [`set_color`]: #method.set_color
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Set the color of the bars.
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Builder-style method for specifying the color of the bars of negative values.
+    ///
+    /// For the non-builder varient, see [`set_negative_color`].
+    ///
+    /// [`set_negative_color`]: #method.set_negative_color
+    pub fn with_negative_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_negative_color(Some(color.into()));
+        self
+    }
+
+    /// Set the color of the bars of negative values, or `None` for the color of the others.
+    pub fn set_negative_color(&mut self, color: Option<KeyOrValue<Color>>) {
+        self.negative_color = color;
+    }
+}
+
+impl Default for BarSparkline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+chart_builders!(BarSparkline);
+
+/// The bar under `x`, for mini-charts with a bar per value.
+fn bar_at(x: f64, width: f64, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    let index = (x / width * count as f64).floor().max(0.0) as usize;
+    Some(index.min(count - 1))
+}
+
+/// The horizontal extent of the bar `index` of `count`.
+fn bar_span(index: usize, count: usize, width: f64) -> (f64, f64) {
+    let slot = width / count as f64;
+    let gap = if slot > 3.0 * BAR_GAP { BAR_GAP } else { 0.0 };
+    (index as f64 * slot, (index + 1) as f64 * slot - gap)
+}
+
+impl Widget<Vector<f64>> for BarSparkline {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Vector<f64>, _env: &Env) {
+        if let Event::MouseMove(mouse) = event {
+            let hovered = bar_at(mouse.pos.x, ctx.size().width, data.len());
+            self.chart.set_hovered(ctx, hovered);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.lifecycle(ctx, event);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &Vector<f64>,
+        data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.update(ctx, old_data, data);
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain(DEFAULT_SIZE)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Vector<f64>, env: &Env) {
+        let size = ctx.size();
+        let range = self.chart.value_range(data, true);
+        self.chart.paint_bands(ctx, range, env);
+
+        let zero = Chart::y_at(0.0, range, size.height);
+        let color = self.color.resolve(env);
+        let negative_color = self.negative_color.as_ref().map(|color| color.resolve(env));
+        let count = data.len();
+        for (index, value) in data.iter().enumerate() {
+            let (x0, x1) = bar_span(index, count, size.width);
+            let y = Chart::y_at(*value, range, size.height);
+            let color = match &negative_color {
+                Some(negative_color) if *value < 0.0 => negative_color,
+                _ => &color,
+            };
+            ctx.fill(Rect::new(x0, zero.min(y), x1, zero.max(y)), color);
+        }
+
+        if let Some(index) = self.chart.hovered {
+            if let Some(value) = data.get(index) {
+                let (x0, x1) = bar_span(index, count, size.width);
+                let rect = Rect::new(x0, 0.0, x1, size.height);
+                ctx.fill(rect, &env.get(theme::FOREGROUND_LIGHT).with_alpha(0.15));
+                let anchor = Point::new((x0 + x1) / 2.0, Chart::y_at(*value, range, size.height));
+                self.chart.paint_value(ctx, *value, anchor, env);
+            }
+        }
+    }
+}
+
+/// A small chart of wins and losses, with a block up for each positive value, a block down for
+/// each negative one, and a dash for zero.
+///
+/// The value under the mouse is shown in a bubble.
+///
+/// ```
+/// # use druid::im::Vector;
+/// # use druid::{Widget, WidgetExt};
+/// # use druid_widget_nursery::WinLoss;
+/// fn last_games() -> impl Widget<Vector<f64>> {
+///     WinLoss::new().fix_size(120.0, 16.0)
+/// }
+/// ```
+pub struct WinLoss {
+    chart: Chart,
+    win_color: KeyOrValue<Color>,
+    loss_color: KeyOrValue<Color>,
+}
+
+impl WinLoss {
+    pub fn new() -> Self {
+        WinLoss {
+            chart: Chart::new(),
+            win_color: KeyOrValue::Concrete(Color::rgb8(0x43, 0xa0, 0x47)),
+            loss_color: KeyOrValue::Concrete(Color::rgb8(0xd3, 0x2f, 0x2f)),
+        }
+    }
+
+    /// Builder-style method for specifying the colors of the wins and the losses.
+    ///
+    /// For the non-builder varient, see [`set_colors`].
+    ///
+    /// [`set_colors`]: #method.set_colors
+    pub fn with_colors(
+        mut self,
+        win: impl Into<KeyOrValue<Color>>,
+        loss: impl Into<KeyOrValue<Color>>,
+    ) -> Self {
+        self.set_colors(win, loss);
+        self
+    }
+
+    /// Set the colors of the wins and the losses, green and red by default.
+    pub fn set_colors(
+        &mut self,
+        win: impl Into<KeyOrValue<Color>>,
+        loss: impl Into<KeyOrValue<Color>>,
+    ) {
+        self.win_color = win.into();
+        self.loss_color = loss.into();
+    }
+
+    /// Builder-style method for turning the value under the mouse into text.
+    pub fn with_format(mut self, format: impl Fn(f64) -> String + 'static) -> Self {
+        self.chart.format = Box::new(format);
+        self
+    }
+}
+
+impl Default for WinLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<Vector<f64>> for WinLoss {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Vector<f64>, _env: &Env) {
+        if let Event::MouseMove(mouse) = event {
+            let hovered = bar_at(mouse.pos.x, ctx.size().width, data.len());
+            self.chart.set_hovered(ctx, hovered);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.lifecycle(ctx, event);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &Vector<f64>,
+        data: &Vector<f64>,
+        _env: &Env,
+    ) {
+        self.chart.update(ctx, old_data, data);
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Vector<f64>,
+        _env: &Env,
+    ) -> Size {
+        bc.constrain(DEFAULT_SIZE)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Vector<f64>, env: &Env) {
+        let size = ctx.size();
+        let middle = size.height / 2.0;
+        let win_color = self.win_color.resolve(env);
+        let loss_color = self.loss_color.resolve(env);
+        let count = data.len();
+        for (index, value) in data.iter().enumerate() {
+            let (x0, x1) = bar_span(index, count, size.width);
+            if *value > 0.0 {
+                ctx.fill(Rect::new(x0, 0.0, x1, middle - 1.0), &win_color);
+            } else if *value < 0.0 {
+                ctx.fill(Rect::new(x0, middle + 1.0, x1, size.height), &loss_color);
+            } else {
+                let line = Line::new((x0, middle), (x1, middle));
+                ctx.stroke(line, &env.get(theme::BORDER_LIGHT), 1.0);
+            }
+        }
+
+        if let Some(index) = self.chart.hovered {
+            if let Some(value) = data.get(index) {
+                let (x0, x1) = bar_span(index, count, size.width);
+                let rect = Rect::new(x0, 0.0, x1, size.height);
+                ctx.fill(rect, &env.get(theme::FOREGROUND_LIGHT).with_alpha(0.15));
+                let anchor = Point::new((x0 + x1) / 2.0, middle);
+                self.chart.paint_value(ctx, *value, anchor, env);
+            }
+        }
+    }
+}
@@ -14,11 +14,11 @@
 
 use druid::{
     BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    Point, Rect, RenderContext, Size, UnitPoint, UpdateCtx, Widget, WidgetPod,
+    Point, Rect, RenderContext, Size, UnitPoint, UpdateCtx, Vec2, Widget, WidgetId, WidgetPod,
 };
 use tracing::warn;
 
-use crate::animation::{Animated, AnimationCurve, Interpolate};
+use crate::animation::{Animated, AnimationCurve, Interpolate, Spring};
 use druid::kurbo::Shape;
 
 /// Stack child position
@@ -34,7 +34,12 @@ use druid::kurbo::Shape;
 /// need to be `None`).
 ///
 /// If `width` or `height` is unconstrained, they are positioned
-/// according to the [Stack::align] property.
+/// according to the `align` value, or the [Stack::align] property
+/// if it is not set.
+///
+/// If `relative` is set, all distances and sizes are fractions of
+/// the stack size instead of pixels (`0.5` is 50%). The `offset` is
+/// always in pixels and is added after the child is positioned.
 #[derive(Clone, Debug, Default, PartialEq, Data)]
 pub struct StackChildPosition {
     /// Disance from left edge.
@@ -49,6 +54,13 @@ pub struct StackChildPosition {
     pub width: Option<f64>,
     /// Widhet height.
     pub height: Option<f64>,
+    /// Alignment overriding [Stack::align], `(0, 0)` is top-left
+    /// and `(1, 1)` is bottom-right.
+    pub align: Option<Point>,
+    /// Pixel offset added to the computed position.
+    pub offset: Vec2,
+    /// Interpret distances and sizes as fractions of the stack size.
+    pub relative: bool,
 }
 
 impl Interpolate for StackChildPosition {
@@ -80,6 +92,17 @@ impl Interpolate for StackChildPosition {
             bottom: lerp(self.bottom, other.bottom, fraction),
             width: lerp(self.width, other.width, fraction),
             height: lerp(self.height, other.height, fraction),
+            align: match (self.align, other.align) {
+                (Some(a), Some(b)) => Some(a.interpolate(&b, fraction)),
+                _ if fraction < 0.5 => self.align,
+                _ => other.align,
+            },
+            offset: self.offset.interpolate(&other.offset, fraction),
+            relative: if fraction < 0.5 {
+                self.relative
+            } else {
+                other.relative
+            },
         }
     }
 }
@@ -125,6 +148,46 @@ impl StackChildPosition {
         self.height = value;
         self
     }
+
+    /// Builder-style method to set the child alignment.
+    ///
+    /// This overrides [Stack::align] for this child.
+    pub fn align(mut self, value: UnitPoint) -> Self {
+        self.align = Some(value.resolve(Rect::new(0., 0., 1., 1.)));
+        self
+    }
+
+    /// Builder-style method to set the pixel offset.
+    pub fn offset(mut self, value: impl Into<Vec2>) -> Self {
+        self.offset = value.into();
+        self
+    }
+
+    /// Builder-style method to interpret distances and sizes as
+    /// fractions of the stack size.
+    pub fn relative(mut self, value: bool) -> Self {
+        self.relative = value;
+        self
+    }
+
+    // resolve relative values against the stack size
+    fn to_pixels(&self, stack_size: Size) -> Self {
+        if !self.relative {
+            return self.clone();
+        }
+        let x = |v: Option<f64>| v.map(|v| v * stack_size.width);
+        let y = |v: Option<f64>| v.map(|v| v * stack_size.height);
+        StackChildPosition {
+            left: x(self.left),
+            right: x(self.right),
+            top: y(self.top),
+            bottom: y(self.bottom),
+            width: x(self.width),
+            height: y(self.height),
+            relative: false,
+            ..self.clone()
+        }
+    }
 }
 
 type PositionCallback<T> = Box<dyn for<'a> Fn(&'a T, &Env) -> &'a StackChildPosition>;
@@ -144,6 +207,8 @@ pub struct StackChildParams<T> {
     position: Position<T>,
     // We also store the animation state here - just to keep it simple
     animated_position: Animated<StackChildPosition>,
+    z_index: i32,
+    hit_test: bool,
 }
 
 impl<T> From<StackChildPosition> for StackChildParams<T> {
@@ -152,12 +217,20 @@ impl<T> From<StackChildPosition> for StackChildParams<T> {
     }
 }
 
+impl<T> Default for StackChildParams<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> StackChildParams<T> {
-    // setup for non-positioned children
-    fn new() -> Self {
+    /// Create a *non-positioned* stack child
+    pub fn new() -> Self {
         Self {
             position: Position::None,
             animated_position: Animated::jump(StackChildPosition::new()).layout(true),
+            z_index: 0,
+            hit_test: true,
         }
     }
 
@@ -165,7 +238,7 @@ impl<T> StackChildParams<T> {
     pub fn fixed(position: StackChildPosition) -> Self {
         Self {
             position: Position::Fixed(position),
-            animated_position: Animated::jump(StackChildPosition::new()).layout(true),
+            ..Self::new()
         }
     }
 
@@ -180,9 +253,49 @@ impl<T> StackChildParams<T> {
                 .curve(AnimationCurve::EASE_OUT)
                 .duration(0.3)
                 .layout(true),
+            ..Self::new()
         }
     }
 
+    /// Builder-style method for specifying the z-index.
+    ///
+    /// For the non-builder varient, see [`set_z_index`].
+    ///
+    /// [`set_z_index`]: #method.set_z_index
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.set_z_index(z_index);
+        self
+    }
+
+    /// Set the z-index.
+    ///
+    /// Children with a higher z-index are painted above and receive
+    /// mouse events before children with a lower one. Children with
+    /// the same z-index keep the order they were added in.
+    pub fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+
+    /// Builder-style method for specifying the `hit_test` attribute.
+    ///
+    /// For the non-builder varient, see [`set_hit_test`].
+    ///
+    /// [`set_hit_test`]: #method.set_hit_test
+    pub fn hit_test(mut self, hit_test: bool) -> Self {
+        self.set_hit_test(hit_test);
+        self
+    }
+
+    /// Set the `hit_test` attribute.
+    ///
+    /// Mouse events are only delivered to the top-most child under
+    /// the cursor. Children with `hit_test` set to `false` still
+    /// receive them, but let them pass through to the children
+    /// below. This is useful for decorative overlays.
+    pub fn set_hit_test(&mut self, hit_test: bool) {
+        self.hit_test = hit_test;
+    }
+
     /// Builder-style method for specifying the [`AnimationCurve`].
     ///
     /// For the non-builder varient, see [`set_curve`].
@@ -218,6 +331,24 @@ impl<T> StackChildParams<T> {
     pub fn set_duration(&mut self, duration: f64) {
         self.animated_position.set_duration(duration);
     }
+
+    /// Builder-style method for animating the position with a [`Spring`].
+    ///
+    /// For the non-builder varient, see [`set_spring`].
+    ///
+    /// [`set_spring`]: #method.set_spring
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.animated_position.set_spring(Some(spring));
+        self
+    }
+
+    /// Animate the position with a [`Spring`], or with the duration and the curve for `None`.
+    ///
+    /// The spring is used by dynamically positioned children to
+    /// animate the position change.
+    pub fn set_spring(&mut self, spring: Option<Spring>) {
+        self.animated_position.set_spring(spring);
+    }
 }
 
 struct StackChild<T> {
@@ -234,6 +365,21 @@ impl<T: Data> StackChild<T> {
     }
 }
 
+crate::selectors! {
+    /// Move the child with the given id above all other children.
+    ///
+    /// Can be sent to the [`Stack`] as a command, or submitted as a
+    /// notification by one of its children.
+    STACK_RAISE_CHILD: WidgetId,
+    /// Move the child with the given id below all other children.
+    ///
+    /// Can be sent to the [`Stack`] as a command, or submitted as a
+    /// notification by one of its children.
+    STACK_LOWER_CHILD: WidgetId,
+    /// Set the z-index of the child with the given id.
+    STACK_SET_Z_INDEX: (WidgetId, i32),
+}
+
 /// Stack of widgets
 ///
 /// Stack provides an easy way to stack widgets on top of each
@@ -247,6 +393,12 @@ impl<T: Data> StackChild<T> {
 /// *Positioned* children are layed-out after *non-positioned*
 /// children. Their position is relative to the container edges (see
 /// [`StackChildPosition`]).
+///
+/// Children are painted in the order of their z-index (see
+/// [`StackChildParams::z_index`]), which can be changed at runtime
+/// with the [`STACK_RAISE_CHILD`], [`STACK_LOWER_CHILD`] and
+/// [`STACK_SET_Z_INDEX`] commands. Children are identified by their
+/// [`WidgetId`], so give them one using `with_id`.
 pub struct Stack<T> {
     children: Vec<StackChild<T>>,
     align: UnitPoint,
@@ -319,8 +471,7 @@ impl<T: Data> Stack<T> {
 
     /// Add another stack child.
     pub fn add_child(&mut self, child: impl Widget<T> + 'static) {
-        let child = StackChild::new(child, StackChildParams::new());
-        self.children.push(child);
+        self.add_child_with_params(child, StackChildParams::new());
     }
 
     /// Builder-style variant of `add_positioned_child`.
@@ -341,11 +492,107 @@ impl<T: Data> Stack<T> {
     ) {
         let child = StackChild::new(child, params.into());
         self.children.push(child);
+        self.sort_children();
+    }
+
+    /// Builder-style variant of `add_child_with_params`.
+    pub fn with_child_params(
+        mut self,
+        child: impl Widget<T> + 'static,
+        params: StackChildParams<T>,
+    ) -> Self {
+        self.add_child_with_params(child, params);
+        self
+    }
+
+    /// Add another child with the given [`StackChildParams`].
+    ///
+    /// Use [`StackChildParams::new`] to configure z-index or hit
+    /// testing of a *non-positioned* child.
+    pub fn add_child_with_params(
+        &mut self,
+        child: impl Widget<T> + 'static,
+        params: StackChildParams<T>,
+    ) {
+        let child = StackChild::new(child, params);
+        self.children.push(child);
+        self.sort_children();
+    }
+
+    /// Set the z-index of the child with the given id.
+    ///
+    /// Returns `false` if there is no such child.
+    pub fn set_child_z_index(&mut self, id: WidgetId, z_index: i32) -> bool {
+        match self.children.iter_mut().find(|c| c.widget.id() == id) {
+            Some(child) => {
+                child.params.z_index = z_index;
+                self.sort_children();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the child with the given id above all other children.
+    pub fn raise_child(&mut self, id: WidgetId) -> bool {
+        let max = self.children.iter().map(|c| c.params.z_index).max();
+        let z_index = max.unwrap_or(0).saturating_add(1);
+        self.set_child_z_index(id, z_index)
+    }
+
+    /// Move the child with the given id below all other children.
+    pub fn lower_child(&mut self, id: WidgetId) -> bool {
+        let min = self.children.iter().map(|c| c.params.z_index).min();
+        let z_index = min.unwrap_or(0).saturating_sub(1);
+        self.set_child_z_index(id, z_index)
+    }
+
+    // stable, so children with the same z-index keep insertion order
+    fn sort_children(&mut self) {
+        self.children.sort_by_key(|c| c.params.z_index);
+    }
+
+    fn handle_z_order_command(&mut self, ctx: &mut EventCtx, event: &Event) {
+        let changed = match event {
+            Event::Command(cmd) => self.apply_z_order_command(
+                cmd.get(STACK_RAISE_CHILD),
+                cmd.get(STACK_LOWER_CHILD),
+                cmd.get(STACK_SET_Z_INDEX),
+            ),
+            Event::Notification(notification) => self.apply_z_order_command(
+                notification.get(STACK_RAISE_CHILD),
+                notification.get(STACK_LOWER_CHILD),
+                notification.get(STACK_SET_Z_INDEX),
+            ),
+            _ => None,
+        };
+        if changed == Some(true) {
+            ctx.set_handled();
+            ctx.request_paint();
+        }
+    }
+
+    /// Returns `None` if none of the z-order commands was received.
+    fn apply_z_order_command(
+        &mut self,
+        raise: Option<&WidgetId>,
+        lower: Option<&WidgetId>,
+        set: Option<&(WidgetId, i32)>,
+    ) -> Option<bool> {
+        if let Some(id) = raise {
+            Some(self.raise_child(*id))
+        } else if let Some(id) = lower {
+            Some(self.lower_child(*id))
+        } else {
+            set.map(|(id, z_index)| self.set_child_z_index(*id, *z_index))
+        }
     }
 }
 
 impl<T: Data> Widget<T> for Stack<T> {
     fn event(&mut self, ctx: &mut EventCtx<'_, '_>, event: &Event, data: &mut T, env: &Env) {
+        self.handle_z_order_command(ctx, event);
+
         for child in self.children.iter_mut().rev() {
             if ctx.is_handled() {
                 return;
@@ -356,7 +603,9 @@ impl<T: Data> Widget<T> for Stack<T> {
                 Event::MouseMove(mouse_event)
                 | Event::MouseDown(mouse_event)
                 | Event::MouseUp(mouse_event)
-                | Event::Wheel(mouse_event) => rect.winding(mouse_event.pos) != 0,
+                | Event::Wheel(mouse_event) => {
+                    child.params.hit_test && rect.winding(mouse_event.pos) != 0
+                }
                 _ => false,
             };
 
@@ -449,6 +698,7 @@ impl<T: Data> Widget<T> for Stack<T> {
                 Position::Fixed(position) => position,
                 Position::Dynamic(_) => &animated_position,
             };
+            let position = &position.to_pixels(size);
 
             let mut min_width = 0f64;
             let mut max_width = std::f64::INFINITY;
@@ -494,30 +744,28 @@ impl<T: Data> Widget<T> for Stack<T> {
             );
 
             let child_size = child.widget.layout(ctx, &child_bc, data, env);
-            let align = self.align;
+            let align = position
+                .align
+                .unwrap_or_else(|| self.align.resolve(Rect::new(0., 0., 1., 1.)));
+            let aligned = Point::new(
+                (stack_width - child_size.width) * align.x,
+                (stack_height - child_size.height) * align.y,
+            )
+            .expand();
 
             let offset_x = match (position.left, position.right) {
                 (Some(left), _) => left,
                 (None, Some(right)) => stack_width - right - child_size.width,
-                (None, None) => {
-                    let extra_width = stack_width - child_size.width;
-                    align.resolve(Rect::new(0., 0., extra_width, 0.)).expand().x
-                }
+                (None, None) => aligned.x,
             };
 
             let offset_y = match (position.top, position.bottom) {
                 (Some(top), _) => top,
                 (None, Some(bottom)) => stack_height - bottom - child_size.height,
-                (None, None) => {
-                    let extra_height = stack_height - child_size.height;
-                    align
-                        .resolve(Rect::new(0., 0., 0., extra_height))
-                        .expand()
-                        .y
-                }
+                (None, None) => aligned.y,
             };
 
-            let origin = Point::new(offset_x, offset_y);
+            let origin = Point::new(offset_x, offset_y) + position.offset;
             child.widget.set_origin(ctx, origin);
         }
 
@@ -0,0 +1,288 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A text box with step buttons, for values which can be parsed, formatted and stepped.
+
+use std::marker::PhantomData;
+
+use druid::keyboard_types::Key;
+use druid::kurbo::BezPath;
+use druid::piet::{LineCap, LineJoin, StrokeStyle};
+use druid::widget::prelude::*;
+use druid::widget::TextBox;
+use druid::{theme, Color, Point, Rect, Selector, WidgetPod};
+
+/// The width of the step buttons.
+const BUTTON_WIDTH: f64 = 16.0;
+const INVALID_COLOR: Color = Color::rgb8(0xe0, 0x40, 0x40);
+
+/// Sent to ourselves when the text box lost focus, as `lifecycle` can't change the data.
+const COMMIT: Selector = Selector::new("druid-widget-nursery.step-input.commit");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    Up,
+    Down,
+}
+
+/// How a [`StepInput`] parses, formats and steps its value.
+pub(crate) trait InputFormat<T> {
+    fn parse(&self, text: &str) -> Option<T>;
+    fn format(&self, value: &T) -> String;
+    /// Step the value, by a larger amount for Page Up and Page Down.
+    fn step(&self, value: &T, step: Step, large: bool) -> T;
+
+    /// Whether a parsed value can be applied while typing. Values which aren't are highlighted,
+    /// and replaced by [`clamp`] when committed.
+    ///
+    /// [`clamp`]: InputFormat::clamp
+    fn is_valid(&self, _value: &T) -> bool {
+        true
+    }
+
+    /// The closest valid value.
+    fn clamp(&self, value: T) -> T {
+        value
+    }
+}
+
+/// A text box with step buttons, committing the parsed and normalized text on Enter and when
+/// losing focus.
+pub(crate) struct StepInput<T, F> {
+    pub(crate) format: F,
+    text_box: WidgetPod<String, TextBox<String>>,
+    text: String,
+    valid: bool,
+    pressed: Option<Step>,
+    had_focus: bool,
+    /// The last environment we got, and the one we pass to the text box.
+    env: Option<(Env, Env)>,
+    _t: PhantomData<T>,
+}
+
+impl<T: Data, F: InputFormat<T>> StepInput<T, F> {
+    pub(crate) fn new(format: F, text_box: TextBox<String>) -> Self {
+        StepInput {
+            format,
+            text_box: WidgetPod::new(text_box),
+            text: String::new(),
+            valid: true,
+            pressed: None,
+            had_focus: false,
+            env: None,
+            _t: PhantomData,
+        }
+    }
+
+    fn set_valid(&mut self, valid: bool) {
+        if self.valid != valid {
+            self.valid = valid;
+            self.env = None;
+        }
+    }
+
+    /// Apply the text, or go back to the last valid value.
+    fn commit(&mut self, data: &mut T) {
+        if let Some(value) = self.format.parse(&self.text) {
+            *data = self.format.clamp(value);
+        }
+        self.text = self.format.format(data);
+        self.set_valid(true);
+    }
+
+    fn step(&mut self, step: Step, large: bool, data: &mut T) {
+        let value = self
+            .format
+            .parse(&self.text)
+            .unwrap_or_else(|| data.clone());
+        *data = self.format.clamp(self.format.step(&value, step, large));
+        self.text = self.format.format(data);
+        self.set_valid(true);
+    }
+
+    fn button_rect(&self, size: Size, step: Step) -> Rect {
+        let x0 = size.width - BUTTON_WIDTH;
+        let half = size.height / 2.0;
+        match step {
+            Step::Up => Rect::new(x0, 0.0, size.width, half),
+            Step::Down => Rect::new(x0, half, size.width, size.height),
+        }
+    }
+
+    fn button_at(&self, size: Size, pos: Point) -> Option<Step> {
+        [Step::Up, Step::Down]
+            .iter()
+            .copied()
+            .find(|step| self.button_rect(size, *step).contains(pos))
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let mut child_env = env.clone();
+                if !self.valid {
+                    child_env.set(theme::BORDER_DARK, INVALID_COLOR);
+                    child_env.set(theme::PRIMARY_LIGHT, INVALID_COLOR);
+                }
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+impl<T: Data, F: InputFormat<T>> Widget<T> for StepInput<T, F> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let mut commit = false;
+        match event {
+            Event::Command(cmd) if cmd.is(COMMIT) => {
+                self.commit(data);
+                ctx.request_update();
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                if let Some(step) = self.button_at(ctx.size(), mouse.pos) {
+                    self.pressed = Some(step);
+                    self.step(step, false, data);
+                    ctx.request_update();
+                    ctx.set_active(true);
+                    ctx.request_paint();
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseUp(_) if self.pressed.is_some() => {
+                self.pressed = None;
+                ctx.set_active(false);
+                ctx.request_paint();
+                ctx.set_handled();
+                return;
+            }
+            Event::Wheel(mouse) if ctx.is_hot() && mouse.wheel_delta.y != 0.0 => {
+                let step = if mouse.wheel_delta.y < 0.0 {
+                    Step::Up
+                } else {
+                    Step::Down
+                };
+                self.step(step, false, data);
+                ctx.request_update();
+                ctx.set_handled();
+                return;
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                let step = match &key.key {
+                    Key::ArrowUp => Some((Step::Up, false)),
+                    Key::ArrowDown => Some((Step::Down, false)),
+                    Key::PageUp => Some((Step::Up, true)),
+                    Key::PageDown => Some((Step::Down, true)),
+                    Key::Enter => {
+                        commit = true;
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some((step, large)) = step {
+                    self.step(step, large, data);
+                    ctx.request_update();
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        let child_env = self.child_env(env);
+        let old_text = self.text.clone();
+        self.text_box.event(ctx, event, &mut self.text, &child_env);
+        if commit {
+            self.commit(data);
+            ctx.request_update();
+        } else if self.text != old_text {
+            match self.format.parse(&self.text) {
+                Some(value) if self.format.is_valid(&value) => {
+                    *data = value;
+                    self.set_valid(true);
+                }
+                _ => self.set_valid(false),
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.text = self.format.format(data);
+        }
+        let child_env = self.child_env(env);
+        self.text_box.lifecycle(ctx, event, &self.text, &child_env);
+
+        let has_focus = ctx.has_focus();
+        if self.had_focus && !has_focus {
+            ctx.submit_command(COMMIT.to(ctx.widget_id()));
+        }
+        self.had_focus = has_focus;
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let shown = self.format.parse(&self.text);
+        if !old_data.same(data) && !shown.map_or(false, |shown| shown.same(data)) {
+            // changed from outside
+            self.text = self.format.format(data);
+            self.set_valid(true);
+        }
+        if self.env.is_none() {
+            // not every widget checks for env changes
+            ctx.request_paint();
+        }
+        let child_env = self.child_env(env);
+        self.text_box.update(ctx, &self.text, &child_env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let child_env = self.child_env(env);
+        let text_bc = bc.shrink((BUTTON_WIDTH, 0.0));
+        let text_size = self.text_box.layout(ctx, &text_bc, &self.text, &child_env);
+        self.text_box.set_origin(ctx, Point::ORIGIN);
+        ctx.set_baseline_offset(self.text_box.baseline_offset());
+        bc.constrain(Size::new(text_size.width + BUTTON_WIDTH, text_size.height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.text_box.paint(ctx, &self.text, &child_env);
+
+        let size = ctx.size();
+        let style = StrokeStyle::new()
+            .line_cap(LineCap::Round)
+            .line_join(LineJoin::Round);
+        for step in [Step::Up, Step::Down] {
+            let rect = self.button_rect(size, step);
+            if self.pressed == Some(step) {
+                ctx.fill(rect, &env.get(theme::PRIMARY_DARK));
+            }
+
+            let center = rect.center();
+            let dy = match step {
+                Step::Up => -2.0,
+                Step::Down => 2.0,
+            };
+            let mut chevron = BezPath::new();
+            chevron.move_to((center.x - 4.0, center.y - dy));
+            chevron.line_to((center.x, center.y + dy));
+            chevron.line_to((center.x + 4.0, center.y - dy));
+            ctx.stroke_styled(chevron, &env.get(theme::TEXT_COLOR), 1.5, &style);
+        }
+    }
+}
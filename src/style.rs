@@ -0,0 +1,127 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Overriding `Env` values for a single subtree.
+
+use druid::{theme, Color, Env, FontDescriptor, Insets, Key, ValueType};
+
+use crate::Theme;
+
+/// A set of [`Env`] overrides for a subtree, built with [`WidgetExt::styled`].
+///
+/// The named methods cover the keys of druid's [`theme`] that are most often changed, use
+/// [`adding`] for anything else, including the keys of your own widgets.
+///
+/// ```
+/// use druid::widget::TextBox;
+/// use druid::{Color, Widget};
+/// use druid_widget_nursery::WidgetExt;
+///
+/// fn search_box() -> impl Widget<String> {
+///     TextBox::new().styled(|style| {
+///         style
+///             .border_color(Color::rgb8(0x80, 0x80, 0xff))
+///             .border_radius(8.0)
+///             .padding((8.0, 4.0))
+///     })
+/// }
+/// ```
+///
+/// [`WidgetExt::styled`]: crate::WidgetExt::styled
+/// [`adding`]: Style::adding
+#[derive(Clone, Default)]
+pub struct Style {
+    theme: Theme,
+}
+
+impl Style {
+    /// An empty style, which doesn't change anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method to override `key` with `value`.
+    pub fn adding<V: ValueType + Send + Sync + 'static>(
+        mut self,
+        key: Key<V>,
+        value: impl Into<V>,
+    ) -> Self {
+        self.theme = self.theme.adding(key, value);
+        self
+    }
+
+    /// Set the color of text.
+    pub fn text_color(self, color: impl Into<Color>) -> Self {
+        self.adding(theme::TEXT_COLOR, color.into())
+    }
+
+    /// Set the size of text, in points.
+    pub fn text_size(self, size: f64) -> Self {
+        self.adding(theme::TEXT_SIZE_NORMAL, size)
+    }
+
+    /// Set the font of text.
+    pub fn font(self, font: FontDescriptor) -> Self {
+        self.adding(theme::UI_FONT, font)
+    }
+
+    /// Set the color of the borders of buttons, text boxes and other controls.
+    pub fn border_color(self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        self.adding(theme::BORDER_DARK, color)
+            .adding(theme::BORDER_LIGHT, color)
+    }
+
+    /// Set the width of the borders of buttons and text boxes.
+    pub fn border_width(self, width: f64) -> Self {
+        self.adding(theme::BUTTON_BORDER_WIDTH, width)
+            .adding(theme::TEXTBOX_BORDER_WIDTH, width)
+    }
+
+    /// Set the corner radius of buttons and text boxes.
+    pub fn border_radius(self, radius: f64) -> Self {
+        self.adding(theme::BUTTON_BORDER_RADIUS, radius)
+            .adding(theme::TEXTBOX_BORDER_RADIUS, radius)
+    }
+
+    /// Set the padding inside text boxes.
+    pub fn padding(self, insets: impl Into<Insets>) -> Self {
+        self.adding(theme::TEXTBOX_INSETS, insets.into())
+    }
+
+    /// Set the background of text boxes, sliders, checkboxes and other controls.
+    pub fn background_color(self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        self.adding(theme::BACKGROUND_LIGHT, color)
+            .adding(theme::BACKGROUND_DARK, color)
+    }
+
+    /// Set the color of focus rings, selected items and other highlights.
+    pub fn accent_color(self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        self.adding(theme::PRIMARY_LIGHT, color)
+            .adding(theme::PRIMARY_DARK, color)
+    }
+
+    /// Apply the style to `env`.
+    pub fn apply(&self, env: &mut Env) {
+        self.theme.apply(env);
+    }
+}
+
+impl From<Style> for Theme {
+    fn from(style: Style) -> Theme {
+        style.theme
+    }
+}
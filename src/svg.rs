@@ -0,0 +1,294 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use druid::kurbo::BezPath;
+use druid::widget::prelude::*;
+use druid::{Affine, Color, KeyOrValue, Rect};
+
+/// A shape of an SVG, in the coordinates of its view box.
+struct Shape {
+    path: BezPath,
+    fill: Option<(Color, bool)>,
+    stroke: Option<(Color, f64)>,
+    /// The ids of the element and of the groups it is in.
+    ids: Vec<String>,
+}
+
+/// A parsed SVG document for an [`Svg`], turned into paths once and shared by its clones.
+///
+/// Gradients, patterns, images and filters are left out; text is only shown when `usvg` turns
+/// it into paths.
+#[derive(Clone)]
+pub struct SvgData {
+    shapes: Arc<Vec<Shape>>,
+    view_box: Rect,
+    size: Size,
+}
+
+impl FromStr for SvgData {
+    type Err = Box<dyn Error>;
+
+    fn from_str(svg: &str) -> Result<Self, Self::Err> {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default().to_ref())?;
+        let mut shapes = Vec::new();
+        collect(&tree.root, Affine::IDENTITY, &[], &mut shapes);
+        let view_box = tree.view_box.rect;
+        Ok(SvgData {
+            shapes: Arc::new(shapes),
+            view_box: Rect::new(
+                view_box.x(),
+                view_box.y(),
+                view_box.x() + view_box.width(),
+                view_box.y() + view_box.height(),
+            ),
+            size: Size::new(tree.size.width(), tree.size.height()),
+        })
+    }
+}
+
+fn affine(transform: usvg::Transform) -> Affine {
+    let usvg::Transform { a, b, c, d, e, f } = transform;
+    Affine::new([a, b, c, d, e, f])
+}
+
+fn color(paint: &usvg::Paint, opacity: f64) -> Option<Color> {
+    match paint {
+        usvg::Paint::Color(color) => {
+            let alpha = (opacity * 255.0).round() as u8;
+            Some(Color::rgba8(color.red, color.green, color.blue, alpha))
+        }
+        _ => None,
+    }
+}
+
+/// Add the shapes of `node` and of its children.
+fn collect(node: &usvg::Node, transform: Affine, ids: &[String], shapes: &mut Vec<Shape>) {
+    match &*node.borrow() {
+        usvg::NodeKind::Group(group) => {
+            let transform = transform * affine(group.transform);
+            let mut ids = ids.to_vec();
+            if !group.id.is_empty() {
+                ids.push(group.id.clone());
+            }
+            for child in node.children() {
+                collect(&child, transform, &ids, shapes);
+            }
+        }
+        usvg::NodeKind::Path(path) if path.visibility == usvg::Visibility::Visible => {
+            let transform = transform * affine(path.transform);
+            let mut bez = BezPath::new();
+            for segment in path.data.segments() {
+                match segment {
+                    usvg::PathSegment::MoveTo { x, y } => bez.move_to((x, y)),
+                    usvg::PathSegment::LineTo { x, y } => bez.line_to((x, y)),
+                    usvg::PathSegment::CurveTo {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        x,
+                        y,
+                    } => bez.curve_to((x1, y1), (x2, y2), (x, y)),
+                    usvg::PathSegment::ClosePath => bez.close_path(),
+                }
+            }
+            let fill = path.fill.as_ref().and_then(|fill| {
+                let even_odd = fill.rule == usvg::FillRule::EvenOdd;
+                color(&fill.paint, fill.opacity.get()).map(|color| (color, even_odd))
+            });
+            // the stroke is as thick as the transform makes it
+            let scale = transform.determinant().abs().sqrt();
+            let stroke = path.stroke.as_ref().and_then(|stroke| {
+                let width = stroke.width.get() * scale;
+                color(&stroke.paint, stroke.opacity.get()).map(|color| (color, width))
+            });
+            let mut ids = ids.to_vec();
+            if !path.id.is_empty() {
+                ids.push(path.id.clone());
+            }
+            shapes.push(Shape {
+                path: transform * bez,
+                fill,
+                stroke,
+                ids,
+            });
+        }
+        _ => {}
+    }
+}
+
+enum ColorSource<T> {
+    Value(KeyOrValue<Color>),
+    Data(Box<dyn Fn(&T, &Env) -> Color>),
+}
+
+/// A color replacing the colors of the elements with an id, or of all the elements.
+struct Recolor<T> {
+    id: Option<String>,
+    color: ColorSource<T>,
+}
+
+/// A widget showing an SVG, with the `svg` feature, recolored from the environment or the data.
+///
+/// The SVG is painted with vectors, so it is crisp at any size; it keeps its aspect ratio and is
+/// centered. Elements are recolored by their id or by the id of a group they are in, keeping
+/// their opacity, which is handy for tinting icons or showing a state.
+///
+/// ```
+/// # use druid::{theme, Color, Widget};
+/// # use druid_widget_nursery::{Svg, SvgData};
+/// const ICON: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+///     <path d="M4 4h16v16H4z"/>
+///     <circle id="badge" cx="20" cy="4" r="4"/>
+/// </svg>"#;
+///
+/// fn inbox() -> impl Widget<usize> {
+///     let icon: SvgData = ICON.parse().unwrap();
+///     Svg::new(icon)
+///         .with_tint(theme::TEXT_COLOR)
+///         .with_element_color_fn("badge", |unread: &usize, _env| {
+///             if *unread > 0 {
+///                 Color::rgb8(0xd3, 0x2f, 0x2f)
+///             } else {
+///                 Color::TRANSPARENT
+///             }
+///         })
+/// }
+/// ```
+pub struct Svg<T> {
+    svg: SvgData,
+    recolors: Vec<Recolor<T>>,
+}
+
+impl<T: Data> Svg<T> {
+    pub fn new(svg: SvgData) -> Self {
+        Svg {
+            svg,
+            recolors: Vec::new(),
+        }
+    }
+
+    /// Builder-style method for painting every element with `color`.
+    pub fn with_tint(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.recolors.push(Recolor {
+            id: None,
+            color: ColorSource::Value(color.into()),
+        });
+        self
+    }
+
+    /// Builder-style method for painting the element with `id`, or the elements of the group
+    /// with `id`, with `color`.
+    pub fn with_element_color(mut self, id: &str, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.recolors.push(Recolor {
+            id: Some(id.to_string()),
+            color: ColorSource::Value(color.into()),
+        });
+        self
+    }
+
+    /// Builder-style method for painting the element with `id`, or the elements of the group
+    /// with `id`, with a color from the data.
+    pub fn with_element_color_fn(
+        mut self,
+        id: &str,
+        color: impl Fn(&T, &Env) -> Color + 'static,
+    ) -> Self {
+        self.recolors.push(Recolor {
+            id: Some(id.to_string()),
+            color: ColorSource::Data(Box::new(color)),
+        });
+        self
+    }
+
+    /// The color of `shape` instead of `original`, from the last recolor matching it.
+    fn recolor(&self, shape: &Shape, original: &Color, data: &T, env: &Env) -> Color {
+        let recolor = self
+            .recolors
+            .iter()
+            .rev()
+            .find(|recolor| match &recolor.id {
+                Some(id) => shape.ids.contains(id),
+                None => true,
+            });
+        let color = match recolor.map(|recolor| &recolor.color) {
+            Some(ColorSource::Value(color)) => color.resolve(env),
+            Some(ColorSource::Data(color)) => color(data, env),
+            None => return original.clone(),
+        };
+        let (r, g, b, a) = color.as_rgba();
+        let (_, _, _, original_alpha) = original.as_rgba();
+        Color::rgba(r, g, b, a * original_alpha)
+    }
+}
+
+impl<T: Data> Widget<T> for Svg<T> {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        let changed = self.recolors.iter().any(|recolor| match &recolor.color {
+            ColorSource::Value(color) => ctx.env_key_changed(color),
+            ColorSource::Data(_) => !old_data.same(data),
+        });
+        if changed {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, _env: &Env) -> Size {
+        let size = self.svg.size;
+        if size.is_empty() {
+            return bc.min();
+        }
+        bc.constrain_aspect_ratio(size.height / size.width, size.width)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let view_box = self.svg.view_box;
+        if view_box.width() <= 0.0 || view_box.height() <= 0.0 {
+            return;
+        }
+        let scale = (size.width / view_box.width()).min(size.height / view_box.height());
+        let offset = (size.to_vec2() - view_box.size().to_vec2() * scale) / 2.0;
+        let transform = Affine::translate(offset)
+            * Affine::scale(scale)
+            * Affine::translate(-view_box.origin().to_vec2());
+
+        let shapes = self.svg.shapes.clone();
+        ctx.with_save(|ctx| {
+            ctx.transform(transform);
+            for shape in shapes.iter() {
+                if let Some((color, even_odd)) = &shape.fill {
+                    let color = self.recolor(shape, color, data, env);
+                    if *even_odd {
+                        ctx.fill_even_odd(&shape.path, &color);
+                    } else {
+                        ctx.fill(&shape.path, &color);
+                    }
+                }
+                if let Some((color, width)) = &shape.stroke {
+                    let color = self.recolor(shape, color, data, env);
+                    ctx.stroke(&shape.path, &color, *width);
+                }
+            }
+        });
+    }
+}
@@ -20,6 +20,8 @@ use druid::{
     LifeCycleCtx, PaintCtx, Point, RenderContext, Size, UpdateCtx, Widget,
 };
 
+use crate::accessibility::{Accessible, Role};
+
 use super::{ComplexTableColumnWidth, TableCellVerticalAlignment, TableColumnWidth, TableRow};
 
 #[derive(Debug)]
@@ -567,3 +569,9 @@ impl<T: Data> Widget<T> for FlexTable<T> {
         }
     }
 }
+
+impl<T: Data> Accessible<T> for FlexTable<T> {
+    fn role(&self) -> Role {
+        Role::Table
+    }
+}
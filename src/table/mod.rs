@@ -16,6 +16,8 @@
 
 use druid::{Data, Widget, WidgetPod};
 
+use crate::accessibility::{Accessible, Role};
+
 mod table_column_width;
 pub use table_column_width::*;
 
@@ -107,3 +109,9 @@ impl<T: Data> TableRow<T> {
         self.children.push(child);
     }
 }
+
+impl<T: Data> Accessible<T> for TableRow<T> {
+    fn role(&self) -> Role {
+        Role::Row
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A multi-line text box growing with its content.
+
+use druid::piet::{Text, TextLayout as _, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::widget::TextBox;
+use druid::{theme, Color, KeyOrValue, Point, Selector, TextLayout, WidgetPod};
+
+/// The space between the text box and the counter.
+const COUNTER_GAP: f64 = 2.0;
+const LIMIT_COLOR: Color = Color::rgb8(0xe0, 0x40, 0x40);
+
+/// Notification sent by a [`TextArea`] when its text reaches the character limit, or when input
+/// past the limit was cut off. The payload is the limit.
+pub const LIMIT_REACHED: Selector<usize> =
+    Selector::new("druid-widget-nursery.text-area.limit-reached");
+
+/// A multi-line text box that grows with its content.
+///
+/// The text area is between [`min_lines`] and [`max_lines`] lines high, and scrolls once the
+/// text is longer than that. With a character limit, input past the limit is cut off and
+/// [`LIMIT_REACHED`] is sent as a notification. A counter of the characters can be shown below
+/// the text.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::TextArea;
+/// fn tweet() -> impl Widget<String> {
+///     TextArea::new()
+///         .with_placeholder("What's happening?")
+///         .with_max_lines(6)
+///         .with_limit(280)
+///         .with_counter(true)
+/// }
+/// ```
+///
+/// [`min_lines`]: #method.with_min_lines
+/// [`max_lines`]: #method.with_max_lines
+pub struct TextArea {
+    text_box: WidgetPod<String, TextBox<String>>,
+    min_lines: usize,
+    max_lines: usize,
+    limit: Option<usize>,
+    counter: bool,
+    counter_text: TextLayout<String>,
+}
+
+impl TextArea {
+    /// Create a text area between one and five lines high.
+    pub fn new() -> Self {
+        let mut counter_text = TextLayout::new();
+        counter_text.set_text_size(theme::TEXT_SIZE_SMALL);
+        TextArea {
+            text_box: WidgetPod::new(TextBox::multiline()),
+            min_lines: 1,
+            max_lines: 5,
+            limit: None,
+            counter: false,
+            counter_text,
+        }
+    }
+
+    /// Builder-style method for specifying the placeholder shown while the text is empty.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.text_box.widget_mut().set_placeholder(placeholder);
+        self
+    }
+
+    /// Builder-style method for specifying the lowest height in lines.
+    ///
+    /// For the non-builder varient, see [`set_min_lines`].
+    ///
+    /// [`set_min_lines`]: #method.set_min_lines
+    pub fn with_min_lines(mut self, lines: usize) -> Self {
+        self.set_min_lines(lines);
+        self
+    }
+
+    /// Set the number of lines the text area is high when the text is shorter.
+    pub fn set_min_lines(&mut self, lines: usize) {
+        self.min_lines = lines.max(1);
+        self.max_lines = self.max_lines.max(self.min_lines);
+    }
+
+    /// Builder-style method for specifying the greatest height in lines.
+    ///
+    /// For the non-builder varient, see [`set_max_lines`].
+    ///
+    /// [`set_max_lines`]: #method.set_max_lines
+    pub fn with_max_lines(mut self, lines: usize) -> Self {
+        self.set_max_lines(lines);
+        self
+    }
+
+    /// Set the number of lines after which the text area stops growing and scrolls instead.
+    pub fn set_max_lines(&mut self, lines: usize) {
+        self.max_lines = lines.max(1);
+        self.min_lines = self.min_lines.min(self.max_lines);
+    }
+
+    /// Builder-style method for specifying the character limit.
+    ///
+    /// For the non-builder varient, see [`set_limit`].
+    ///
+    /// [`set_limit`]: #method.set_limit
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.set_limit(Some(limit));
+        self
+    }
+
+    /// Set the greatest number of characters, or `None` for no limit.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Builder-style method for showing the character counter.
+    ///
+    /// For the non-builder varient, see [`set_counter`].
+    ///
+    /// [`set_counter`]: #method.set_counter
+    pub fn with_counter(mut self, counter: bool) -> Self {
+        self.set_counter(counter);
+        self
+    }
+
+    /// Set whether the number of characters, and the limit if there is one, is shown below the
+    /// text.
+    pub fn set_counter(&mut self, counter: bool) {
+        self.counter = counter;
+    }
+
+    fn update_counter(&mut self, data: &str) {
+        let count = data.chars().count();
+        let (text, color): (String, KeyOrValue<Color>) = match self.limit {
+            Some(limit) if count >= limit => (format!("{} / {}", count, limit), LIMIT_COLOR.into()),
+            Some(limit) => (
+                format!("{} / {}", count, limit),
+                theme::PLACEHOLDER_COLOR.into(),
+            ),
+            None => (count.to_string(), theme::PLACEHOLDER_COLOR.into()),
+        };
+        self.counter_text.set_text(text);
+        self.counter_text.set_text_color(color);
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The height of `lines` lines of text in the font of the text box.
+fn lines_height(ctx: &mut LayoutCtx, lines: usize, env: &Env) -> f64 {
+    let font = env.get(theme::UI_FONT);
+    let text = vec!["X"; lines].join("\n");
+    ctx.text()
+        .new_text_layout(text)
+        .font(font.family, font.size)
+        .build()
+        .map(|layout| layout.size().height)
+        .unwrap_or(0.0)
+}
+
+impl Widget<String> for TextArea {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut String, env: &Env) {
+        let old_count = data.chars().count();
+        self.text_box.event(ctx, event, data, env);
+
+        if let Some(limit) = self.limit {
+            let count = data.chars().count();
+            if count > limit {
+                *data = data.chars().take(limit).collect();
+            }
+            if count >= limit && (old_count < limit || count > limit) {
+                ctx.submit_notification(LIMIT_REACHED.with(limit));
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &String, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.update_counter(data);
+        }
+        self.text_box.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &String, data: &String, env: &Env) {
+        if old_data != data {
+            self.update_counter(data);
+            // the height follows the number of lines
+            ctx.request_layout();
+        }
+        if self.counter_text.needs_rebuild_after_update(ctx) {
+            ctx.request_layout();
+        }
+        self.text_box.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &String,
+        env: &Env,
+    ) -> Size {
+        let counter_height = if self.counter {
+            self.counter_text.rebuild_if_needed(ctx.text(), env);
+            self.counter_text.size().height + COUNTER_GAP
+        } else {
+            0.0
+        };
+
+        let insets = env.get(theme::TEXTBOX_INSETS).y_value();
+        let max_height = (lines_height(ctx, self.max_lines, env) + insets)
+            .min(bc.max().height - counter_height)
+            .max(0.0);
+        let min_height = (lines_height(ctx, self.min_lines, env) + insets).min(max_height);
+        let text_box_bc = BoxConstraints::new(
+            Size::new(bc.min().width, min_height),
+            Size::new(bc.max().width, max_height),
+        );
+        let text_box_size = self.text_box.layout(ctx, &text_box_bc, data, env);
+        self.text_box.set_origin(ctx, Point::ORIGIN);
+
+        bc.constrain(Size::new(
+            text_box_size.width,
+            text_box_size.height + counter_height,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &String, env: &Env) {
+        self.text_box.paint(ctx, data, env);
+
+        if self.counter {
+            let text_box_rect = self.text_box.layout_rect();
+            let origin = Point::new(
+                text_box_rect.x1 - self.counter_text.size().width,
+                text_box_rect.y1 + COUNTER_GAP,
+            );
+            self.counter_text.draw(ctx, origin);
+        }
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A developer panel for editing the `Env` of a running app.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use druid::im::Vector;
+use druid::lens::Map;
+use druid::widget::prelude::*;
+use druid::widget::{Button, EnvScope, Flex, Label, LineBreaking, Painter, Scroll, TextBox};
+use druid::{
+    theme, Application, ArcStr, Color, Data, Key, Lens, Point, Value, WidgetExt as _, WidgetPod,
+};
+
+use crate::theme_loader::ValueKind;
+use crate::{KeyedList, ListFilter};
+
+const PANEL_WIDTH: f64 = 320.0;
+const DRUID_THEME_PREFIX: &str = "org.linebender.druid.theme.";
+
+#[derive(Clone, Data, Lens)]
+struct EditorState {
+    filter: String,
+    entries: Vector<EntryState>,
+}
+
+#[derive(Clone, Data, Lens)]
+struct EntryState {
+    key: ArcStr,
+    #[data(same_fn = "PartialEq::eq")]
+    kind: ValueKind,
+    /// The value the app has, as text.
+    original: String,
+    text: String,
+    valid: bool,
+}
+
+impl EntryState {
+    fn new(key: &ArcStr, value: &Value) -> Option<Self> {
+        let (kind, text) = match value {
+            Value::Color(color) => {
+                let (r, g, b, a) = color.as_rgba8();
+                (ValueKind::Color, format!("#{r:02x}{g:02x}{b:02x}{a:02x}"))
+            }
+            Value::Float(f) => (ValueKind::Float, f.to_string()),
+            Value::String(s) => (ValueKind::String, s.to_string()),
+            _ => return None,
+        };
+        Some(EntryState {
+            key: key.clone(),
+            kind,
+            original: text.clone(),
+            text,
+            valid: true,
+        })
+    }
+
+    fn is_changed(&self) -> bool {
+        self.text != self.original
+    }
+}
+
+impl EditorState {
+    fn from_env(env: &Env) -> Self {
+        let mut entries: Vec<_> = env
+            .get_all()
+            .filter_map(|(key, value)| EntryState::new(key, value))
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        EditorState {
+            filter: String::new(),
+            entries: entries.into(),
+        }
+    }
+
+    /// Parse the changed entries, and return the values that should be overridden.
+    fn parse(&mut self) -> Vec<(ArcStr, Value)> {
+        let mut overrides = Vec::new();
+        for entry in self.entries.iter_mut() {
+            let value = if entry.is_changed() {
+                entry.kind.parse(&entry.text).ok()
+            } else {
+                None
+            };
+            let valid = value.is_some() || !entry.is_changed();
+            if entry.valid != valid {
+                entry.valid = valid;
+            }
+            if let Some(value) = value {
+                overrides.push((entry.key.clone(), value));
+            }
+        }
+        overrides
+    }
+
+    /// The changed values, in the text format of the [`theme_loader`].
+    ///
+    /// [`theme_loader`]: crate::theme_loader
+    fn export(&self) -> String {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_changed() && entry.valid)
+            .map(|entry| format!("{}: {}\n", entry.key, entry.text.trim()))
+            .collect()
+    }
+}
+
+/// A widget showing its child next to a panel for editing the [`Env`].
+///
+/// The panel lists the colors, floats and strings of the environment the editor got when it was
+/// added, with a text box for each. Valid changes are applied to the child immediately. The
+/// "Copy" button puts the changed values on the clipboard in the text format of the
+/// [`theme_loader`], with the full key names, so they can be loaded with [`loadable_theme!`]
+/// using `as "org.linebender.druid.theme..."`.
+///
+/// This is meant for development: as [`Key`]s need static names, the name of each edited key is
+/// leaked once.
+///
+/// [`theme_loader`]: crate::theme_loader
+/// [`loadable_theme!`]: crate::loadable_theme
+pub struct ThemeEditor<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    panel: WidgetPod<EditorState, Box<dyn Widget<EditorState>>>,
+    state: EditorState,
+    overrides: Vec<(ArcStr, Value)>,
+    /// The last environment we got, and the one we pass to our child.
+    env: Option<(Env, Env)>,
+}
+
+impl<T: Data> ThemeEditor<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            panel: WidgetPod::new(Box::new(panel())),
+            state: EditorState {
+                filter: String::new(),
+                entries: Vector::new(),
+            },
+            overrides: Vec::new(),
+            env: None,
+        }
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let mut child_env = env.clone();
+                for (key, value) in &self.overrides {
+                    set_raw(&mut child_env, key, value.clone());
+                }
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+/// Set the value of the key named `key`.
+fn set_raw(env: &mut Env, key: &str, value: Value) {
+    thread_local! {
+        static KEYS: RefCell<HashMap<String, &'static str>> = RefCell::new(HashMap::new());
+    }
+    let key = KEYS.with(|keys| {
+        *keys
+            .borrow_mut()
+            .entry(key.to_string())
+            .or_insert_with(|| Box::leak(key.to_string().into_boxed_str()))
+    });
+    match value {
+        Value::Color(color) => env.set(Key::<Color>::new(key), color),
+        Value::Float(f) => env.set(Key::<f64>::new(key), f),
+        Value::String(s) => env.set(Key::<ArcStr>::new(key), s),
+        _ => {}
+    }
+}
+
+impl<T: Data> Widget<T> for ThemeEditor<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.event(ctx, event, data, &child_env);
+
+        let mut state = self.state.clone();
+        self.panel.event(ctx, event, &mut state, env);
+        if !state.same(&self.state) {
+            let overrides = state.parse();
+            if overrides != self.overrides {
+                self.overrides = overrides;
+                self.env = None;
+                // not every widget checks for env changes
+                ctx.request_layout();
+                ctx.request_paint();
+            }
+            self.state = state;
+            ctx.request_update();
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.state = EditorState::from_env(env);
+        }
+        let child_env = self.child_env(env);
+        self.inner.lifecycle(ctx, event, data, &child_env);
+        self.panel.lifecycle(ctx, event, &self.state, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.update(ctx, data, &child_env);
+        self.panel.update(ctx, &self.state, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_env = self.child_env(env);
+        let inner_bc = bc.shrink((PANEL_WIDTH, 0.0));
+        let inner_size = self.inner.layout(ctx, &inner_bc, data, &child_env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        let panel_bc = BoxConstraints::tight(Size::new(PANEL_WIDTH, inner_size.height));
+        self.panel.layout(ctx, &panel_bc, &self.state, env);
+        self.panel
+            .set_origin(ctx, Point::new(inner_size.width, 0.0));
+
+        bc.constrain(Size::new(inner_size.width + PANEL_WIDTH, inner_size.height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let child_env = self.child_env(env);
+        self.inner.paint(ctx, data, &child_env);
+        self.panel.paint(ctx, &self.state, env);
+    }
+}
+
+fn panel() -> impl Widget<EditorState> {
+    let filter = TextBox::new()
+        .with_placeholder("Filter keys")
+        .expand_width()
+        .lens(EditorState::filter);
+
+    let entries = ListFilter::keyed(
+        KeyedList::new(entry_row, |entry: &EntryState| entry.key.clone()),
+        |entry: &EntryState, filter: &String| {
+            entry.key.to_lowercase().contains(&filter.to_lowercase())
+        },
+    )
+    .lens(Map::new(
+        |state: &EditorState| (state.entries.clone(), state.filter.clone()),
+        |state: &mut EditorState, (entries, filter)| {
+            state.entries = entries;
+            state.filter = filter;
+        },
+    ));
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Reset").on_click(|_, state: &mut EditorState, _| {
+                for entry in state.entries.iter_mut() {
+                    if entry.is_changed() {
+                        entry.text = entry.original.clone();
+                    }
+                }
+            }),
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Copy").on_click(|_, state: &mut EditorState, _| {
+                Application::global().clipboard().put_string(state.export());
+            }),
+        );
+
+    Flex::column()
+        .with_child(filter)
+        .with_default_spacer()
+        .with_flex_child(Scroll::new(entries).vertical(), 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .padding(6.0)
+        .background(theme::BACKGROUND_DARK)
+}
+
+fn entry_row() -> impl Widget<EntryState> {
+    let swatch = Painter::new(|ctx, entry: &EntryState, _| {
+        if entry.kind == ValueKind::Color {
+            if let Ok(color) = Color::from_hex_str(entry.text.trim()) {
+                let rect = ctx.size().to_rect();
+                ctx.fill(rect, &color);
+            }
+        }
+    })
+    .fix_size(14.0, 14.0);
+
+    let name = Label::dynamic(|entry: &EntryState, _| {
+        let key: &str = &entry.key;
+        key.strip_prefix(DRUID_THEME_PREFIX)
+            .unwrap_or(key)
+            .to_string()
+    })
+    .with_line_break_mode(LineBreaking::Clip)
+    .with_text_size(12.0);
+
+    // invalid values get a red border
+    let text = EnvScope::new(
+        |env, entry: &EntryState| {
+            if !entry.valid {
+                env.set(theme::BORDER_DARK, Color::rgb8(0xe0, 0x40, 0x40));
+            }
+        },
+        TextBox::new().lens(EntryState::text),
+    )
+    .fix_width(110.0);
+
+    Flex::row()
+        .with_child(swatch)
+        .with_spacer(4.0)
+        .with_flex_child(name.expand_width(), 1.0)
+        .with_child(text)
+        .padding((0.0, 2.0))
+}
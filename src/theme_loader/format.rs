@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use crate::theme_loader::ThemeLoadError;
+
+/// The format of a theme file.
+///
+/// All formats are flattened into a list of key/value pairs before they are
+/// checked against a [`LoadableTheme`]; see the [module docs] for details.
+///
+/// [`LoadableTheme`]: super::LoadableTheme
+/// [module docs]: super
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    /// The custom `KEY: value` text format.
+    Text,
+    /// TOML, this requires the `toml` feature.
+    Toml,
+    /// JSON, this requires the `serde_json` feature.
+    Json,
+}
+
+impl ThemeFormat {
+    /// Guess the format from the extension of `path`.
+    ///
+    /// Files ending in `.toml` or `.json` use the corresponding format,
+    /// everything else is read as [`ThemeFormat::Text`].
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => ThemeFormat::Toml,
+            Some(ext) if ext.eq_ignore_ascii_case("json") => ThemeFormat::Json,
+            _ => ThemeFormat::Text,
+        }
+    }
+
+    /// Parse `contents` into a list of flattened key/value pairs.
+    pub(crate) fn parse(self, contents: &str) -> Result<Vec<(String, String)>, ThemeLoadError> {
+        match self {
+            ThemeFormat::Text => iter_items(contents)
+                .map(|item| item.map(|(key, val)| (key.to_string(), val.to_string())))
+                .collect(),
+            ThemeFormat::Toml => parse_toml(contents),
+            ThemeFormat::Json => parse_json(contents),
+        }
+    }
+}
+
+fn iter_items(s: &str) -> impl Iterator<Item = Result<(&str, &str), ThemeLoadError>> {
+    s.lines().filter_map(|line| {
+        if line.trim().is_empty() {
+            None
+        } else {
+            let mut split = line.split(':');
+            match (split.next(), split.next(), split.next()) {
+                (Some(key), Some(val), None) => Some(Ok((key.trim(), val.trim()))),
+                _ => Some(Err(ThemeLoadError::ParseThemeLineError(line.to_string()))),
+            }
+        }
+    })
+}
+
+/// Join the key of a nested table to the key of its parent.
+#[cfg(any(feature = "toml", feature = "serde_json"))]
+fn join_key(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(contents: &str) -> Result<Vec<(String, String)>, ThemeLoadError> {
+    let table: toml::value::Table =
+        toml::from_str(contents).map_err(|e| ThemeLoadError::ParseFileError(e.to_string()))?;
+    let mut items = Vec::new();
+    for (key, value) in &table {
+        flatten_toml(key.clone(), value, &mut items)?;
+    }
+    Ok(items)
+}
+
+#[cfg(feature = "toml")]
+fn flatten_toml(
+    key: String,
+    value: &toml::Value,
+    items: &mut Vec<(String, String)>,
+) -> Result<(), ThemeLoadError> {
+    use toml::Value;
+
+    let value = match value {
+        Value::Table(table) => {
+            for (child, value) in table {
+                flatten_toml(join_key(&key, child), value, items)?;
+            }
+            return Ok(());
+        }
+        Value::Array(array) => array
+            .iter()
+            .map(|value| match value {
+                Value::Integer(i) => Ok(i.to_string()),
+                Value::Float(f) => Ok(f.to_string()),
+                _ => Err(ThemeLoadError::ParseFileError(format!(
+                    "'{key}' must be an array of numbers"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", "),
+        Value::String(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Datetime(_) => {
+            return Err(ThemeLoadError::ParseFileError(format!(
+                "'{key}' is a date, which is not a theme value"
+            )))
+        }
+    };
+    items.push((key, value));
+    Ok(())
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_contents: &str) -> Result<Vec<(String, String)>, ThemeLoadError> {
+    Err(ThemeLoadError::UnsupportedFormat("toml"))
+}
+
+#[cfg(feature = "serde_json")]
+fn parse_json(contents: &str) -> Result<Vec<(String, String)>, ThemeLoadError> {
+    let object: serde_json::Map<String, serde_json::Value> = serde_json::from_str(contents)
+        .map_err(|e| ThemeLoadError::ParseFileError(e.to_string()))?;
+    let mut items = Vec::new();
+    for (key, value) in &object {
+        flatten_json(key.clone(), value, &mut items)?;
+    }
+    Ok(items)
+}
+
+#[cfg(feature = "serde_json")]
+fn flatten_json(
+    key: String,
+    value: &serde_json::Value,
+    items: &mut Vec<(String, String)>,
+) -> Result<(), ThemeLoadError> {
+    use serde_json::Value;
+
+    let value = match value {
+        Value::Object(object) => {
+            for (child, value) in object {
+                flatten_json(join_key(&key, child), value, items)?;
+            }
+            return Ok(());
+        }
+        Value::Array(array) => array
+            .iter()
+            .map(|value| match value {
+                Value::Number(n) => Ok(n.to_string()),
+                _ => Err(ThemeLoadError::ParseFileError(format!(
+                    "'{key}' must be an array of numbers"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", "),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => {
+            return Err(ThemeLoadError::ParseFileError(format!(
+                "'{key}' is null, which is not a theme value"
+            )))
+        }
+    };
+    items.push((key, value));
+    Ok(())
+}
+
+#[cfg(not(feature = "serde_json"))]
+fn parse_json(_contents: &str) -> Result<Vec<(String, String)>, ThemeLoadError> {
+    Err(ThemeLoadError::UnsupportedFormat("serde_json"))
+}
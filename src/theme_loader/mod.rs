@@ -4,21 +4,13 @@
 //! (a typed collection of keys and values) and a widget for loading declared
 //! themes from files on disk.
 //!
-//! ## File format
+//! ## File formats
 //!
-//! Currently, this module uses a custom text format for themes. This format
-//! is simple: each non-blank line must contain a key/value pair, separated
-//! by a colon. You can currently declare either floats or colors. If this
-//! idea is adopted, we may want to find a better format, as this one will
-//! not scale very well as we add support for more value types.
+//! Themes can be written in one of three formats, see [`ThemeFormat`]. By
+//! default, the format is picked from the extension of the theme file.
 //!
-//! ## Value types:
-//!
-//! - **Colors** must be hex strings in one of the following formats: `rgb`,
-//! `rgba`, `rrggbb`, `rrggbbaa`, with our without a leading `#`.
-//! - **Floats** must be a string that can be parsed by `f64::from_str`.
-//!
-//! ## Example file
+//! The original format is a custom text format. This format is simple: each
+//! non-blank line must contain a key/value pair, separated by a colon.
 //!
 //! ```text
 //! BACKGROUND_COLOR:   #fda
@@ -26,15 +18,65 @@
 //! TITLE_PADDING:      16
 //! ```
 //!
+//! With the `toml` or `serde_json` feature, themes can also be written in TOML
+//! or JSON. Nested tables are flattened, joining their keys with a dot, which
+//! is handy for grouping the keys of a single widget. Arrays of numbers are
+//! joined with commas.
+//!
+//! ```toml
+//! background = "#fda"
+//! font = "Fira Sans 14 bold"
+//!
+//! [multi_value]
+//! indent = 12
+//! padding = [4, 2]
+//! ```
+//!
+//! This file provides the keys `background`, `font`, `multi_value.indent` and
+//! `multi_value.padding`. Use `as` in [`loadable_theme!`] to load them into
+//! keys with a different name.
+//!
+//! Every key of the theme must be in the file, and every key in the file must
+//! be part of the theme, otherwise loading fails.
+//!
+//! ## Value types:
+//!
+//! - **Colors** must be hex strings in one of the following formats: `rgb`,
+//! `rgba`, `rrggbb`, `rrggbbaa`, with our without a leading `#`.
+//! - **Floats** must be a string that can be parsed by `f64::from_str`.
+//! - **Bools** are `true` or `false`, **unsigned ints** are parsed by `u64::from_str`.
+//! - **Points** and **sizes** are two comma separated floats, **rects** are
+//! four (`x0, y0, x1, y1`).
+//! - **Insets** and **rounded rect radii** are either a single float for all
+//! sides, or four (`left, top, right, bottom` and `top_left, top_right,
+//! bottom_right, bottom_left`). Insets also accept two (`x, y`).
+//! - **Strings** are used verbatim.
+//! - **Fonts** are a family name, an optional size, an optional weight (`thin`,
+//! `light`, `regular`, `medium`, `semi-bold`, `bold`, ...) and an optional
+//! `italic`, separated by spaces: `Fira Sans 14 bold italic`. The generic
+//! families are `system-ui`, `sans-serif`, `serif` and `monospace`.
+//!
 //! ## Live reloading
 //!
 //! If you would like your app to update the theme when it is edited, you need
-//! to enable the `notify` feature of this crate in your manifest.
+//! to enable the `notify` feature of this crate in your manifest. The
+//! `hot-reload` feature enables it as well, see `hot_reload::WindowDesc::theme`.
+//! This works the same for all file formats.
 
+mod format;
+#[cfg(test)]
+mod test;
 mod widget;
+pub use format::ThemeFormat;
 pub use widget::ThemeLoader;
 
-use druid::Env;
+use std::any::TypeId;
+
+use druid::kurbo::RoundedRectRadii;
+use druid::{
+    ArcStr, Color, Env, FontDescriptor, FontFamily, FontStyle, FontWeight, Insets, Point, Rect,
+    Size, Value,
+};
 
 type RawTheme<'a> = std::collections::HashMap<&'a str, &'a str>;
 
@@ -47,81 +89,75 @@ type RawTheme<'a> = std::collections::HashMap<&'a str, &'a str>;
 /// will use, with their correct types. All of these keys must be present and
 /// in the theme file, and have the correct values.
 ///
+/// By default, a key is looked up in the file by the name of its constant. Add
+/// `as "name"` after the constant to use another name, for example to read it
+/// from a table of a TOML or JSON file.
+///
 /// After using this macro to generate a struct that implements the [`LoadableTheme`]
 /// trait, you can pass that struct, along with a path to the theme file, to
 /// the [`ThemeLoader`] widget. This should sit at the base of your widget tree,
 /// and will insert the values from the file into the [`Env`].
 ///
-/// Note: currently this only supports [`Color`] and `f64` values.
+/// See [`ValueKind`] for the supported value types.
 ///
 /// # Examples
 ///
 /// ```
-/// use druid::{Color, Key, Data, Widget};
+/// use druid::{Color, FontDescriptor, Key, Data, Widget};
 /// use druid_widget_nursery::{loadable_theme, theme_loader::ThemeLoader};
 ///
 /// // first declare your theme keys;
 /// pub const BACKGROUND_COLOR: Key<Color> = Key::new("druid.nursery.theme.bg-color");
 /// pub const TEXT_COLOR: Key<Color> = Key::new("druid.nursery.theme.text-color");
 /// pub const TITLE_PADDING: Key<f64> = Key::new("druid.nursery.theme.title-padding");
+/// pub const TITLE_FONT: Key<FontDescriptor> = Key::new("druid.nursery.theme.title-font");
 ///
 ///
 /// // declares a new struct, MyTheme.
 /// loadable_theme!(pub MyTheme {
 ///     BACKGROUND_COLOR,
 ///     TEXT_COLOR,
-///     TITLE_PADDING
+///     TITLE_PADDING as "title.padding",
+///     TITLE_FONT as "title.font",
 /// });
 ///
 /// // once you have declared a theme, you can use it with the ThemeLoader widget
 /// fn themed_widget<T: Data>(w: impl Widget<T>) -> impl Widget<T> {
-///     ThemeLoader::new("./themes/my_theme.txt", MyTheme, w)
+///     ThemeLoader::new("./themes/my_theme.toml", MyTheme, w)
 /// }
 /// ```
 ///
 /// [`Key`]: druid::Key
-/// [`Color`]: druid::Color
 #[macro_export]
 macro_rules! loadable_theme {
-    ($vis:vis $ty:ident { $($key:ident),+ } ) => {
+    (@name $key:ident) => { stringify!($key) };
+    (@name $key:ident $name:literal) => { $name };
+    ($vis:vis $ty:ident { $($key:ident $(as $name:literal)?),+ $(,)? } ) => {
 
         $vis struct $ty;
 
         impl $crate::theme_loader::LoadableTheme for $ty {
             fn load(&self, raw: &std::collections::HashMap<&str, &str>, current: &druid::Env) -> Result<druid::Env, $crate::theme_loader::ThemeLoadError> {
-                use std::any::TypeId;
-                use druid::{Color, Value};
-                use $crate::theme_loader::{ThemeLoadError, LoadableTheme, ValueKind};
+                use $crate::theme_loader::{ThemeLoadError, ValueKind};
 
                 fn get_kind<T: 'static>(_k: &druid::Key<T>) -> Result<ValueKind, ThemeLoadError> {
-                    let this_type = TypeId::of::<T>();
-                    if this_type  == TypeId::of::<Color>() {
-                        Ok(ValueKind::Color)
-                    } else if this_type == TypeId::of::<f64>() {
-                        Ok(ValueKind::Float)
-                    } else {
-                        Err(ThemeLoadError::UnknownType(std::any::type_name::<T>()))
-                    }
+                    ValueKind::of::<T>().ok_or_else(|| ThemeLoadError::UnknownType(std::any::type_name::<T>()))
                 }
 
                 let mut new_env = current.clone();
 
                 $(
                 let kind = get_kind(&$key)?;
-                let key_ident = stringify!($key);
+                let key_ident = $crate::loadable_theme!(@name $key $($name)?);
                 let val = raw.get(key_ident).ok_or_else(|| ThemeLoadError::MissingKey(key_ident))?;
-                let val: Value = match kind {
-                    ValueKind::Color => {
-                        Color::from_hex_str(val).map(Into::into).map_err(ThemeLoadError::ParseColorError)
-                    }
-                    ValueKind::Float => {
-                        val.parse::<f64>().map(Into::into).map_err(ThemeLoadError::ParseFloatError)
-                    }
-                }?;
-                new_env.try_set_raw($key, val).map_err(ThemeLoadError::ValueTypeError)?;
+                new_env.try_set_raw($key, kind.parse(val)?).map_err(ThemeLoadError::ValueTypeError)?;
                 )+
                     Ok(new_env)
             }
+
+            fn keys(&self) -> Option<&[&'static str]> {
+                Some(&[$($crate::loadable_theme!(@name $key $($name)?)),+])
+            }
         }
     }
 }
@@ -134,6 +170,49 @@ macro_rules! loadable_theme {
 /// [`loadable_theme`] macro to generate it.
 pub trait LoadableTheme {
     fn load(&self, raw: &RawTheme, current: &Env) -> Result<Env, ThemeLoadError>;
+
+    /// The names of all keys of this theme, as they appear in the file.
+    ///
+    /// Files with other keys are rejected. Returns `None` by default, which doesn't check the
+    /// keys of the file.
+    fn keys(&self) -> Option<&[&'static str]> {
+        None
+    }
+}
+
+impl LoadableTheme for Box<dyn LoadableTheme> {
+    fn load(&self, raw: &RawTheme, current: &Env) -> Result<Env, ThemeLoadError> {
+        (**self).load(raw, current)
+    }
+
+    fn keys(&self) -> Option<&[&'static str]> {
+        (**self).keys()
+    }
+}
+
+/// Load `theme` from the `contents` of a theme file, on top of `current`.
+///
+/// This fails if the file contains a key that isn't part of `theme`, when it lists its
+/// [`keys`], or if a key of `theme` is missing from the file.
+///
+/// [`keys`]: LoadableTheme::keys
+pub fn load_theme<T: LoadableTheme + ?Sized>(
+    theme: &T,
+    format: ThemeFormat,
+    contents: &str,
+    current: &Env,
+) -> Result<Env, ThemeLoadError> {
+    let items = format.parse(contents)?;
+    if let Some(keys) = theme.keys() {
+        if let Some((key, _)) = items.iter().find(|(key, _)| !keys.contains(&key.as_str())) {
+            return Err(ThemeLoadError::UnknownKey(key.clone()));
+        }
+    }
+    let raw = items
+        .iter()
+        .map(|(key, val)| (key.as_str(), val.as_str()))
+        .collect();
+    theme.load(&raw, current)
 }
 
 /// A type for errors that occur when loading a theme from file.
@@ -142,8 +221,14 @@ pub enum ThemeLoadError {
     IoError(std::io::Error),
     UnknownType(&'static str),
     MissingKey(&'static str),
+    UnknownKey(String),
+    UnsupportedFormat(&'static str),
+    ParseFileError(String),
     ParseColorError(druid::piet::ColorParseError),
     ParseFloatError(std::num::ParseFloatError),
+    ParseIntError(std::num::ParseIntError),
+    ParseBoolError(std::str::ParseBoolError),
+    ParseValueError(ValueKind, String),
     ValueTypeError(druid::ValueTypeError),
     ParseThemeLineError(String),
 }
@@ -154,8 +239,18 @@ impl std::fmt::Display for ThemeLoadError {
             Self::IoError(err) => write!(f, "io error loading theme: '{err}'"),
             Self::UnknownType(t) => write!(f, "Unsupported theme key type '{t}'"),
             Self::MissingKey(k) => write!(f, "Theme is missing expected key '{k}'"),
+            Self::UnknownKey(k) => write!(f, "Theme contains unknown key '{k}'"),
+            Self::UnsupportedFormat(feature) => {
+                write!(f, "Theme format requires the '{feature}' feature")
+            }
+            Self::ParseFileError(e) => write!(f, "Theme failed to parse: '{e}'"),
             Self::ParseColorError(e) => write!(f, "Theme failed to parse color: '{e}'"),
             Self::ParseFloatError(e) => write!(f, "Theme failed to parse float: '{e}'"),
+            Self::ParseIntError(e) => write!(f, "Theme failed to parse integer: '{e}'"),
+            Self::ParseBoolError(e) => write!(f, "Theme failed to parse bool: '{e}'"),
+            Self::ParseValueError(kind, s) => {
+                write!(f, "Theme failed to parse {kind:?}: '{}'", s.escape_debug())
+            }
             Self::ValueTypeError(e) => write!(f, "Theme value type mismatch: '{e}'"),
             Self::ParseThemeLineError(s) => {
                 write!(f, "Theme contained malformed line: '{}'", s.escape_debug())
@@ -182,13 +277,160 @@ impl From<std::io::Error> for ThemeLoadError {
 pub enum ValueKind {
     Color,
     Float,
-    //TODO: add more types as needed
-    //Point,
-    //Size,
-    //Rect,
-    //Insets,
-    //Bool,
-    //UnsignedInt,
-    //String,
-    //Font,
+    Point,
+    Size,
+    Rect,
+    Insets,
+    RoundedRectRadii,
+    Bool,
+    UnsignedInt,
+    String,
+    Font,
+}
+
+impl ValueKind {
+    /// The kind of the values of type `T`, if they can be loaded from a theme.
+    pub fn of<T: 'static>() -> Option<ValueKind> {
+        let kinds = [
+            (TypeId::of::<Color>(), ValueKind::Color),
+            (TypeId::of::<f64>(), ValueKind::Float),
+            (TypeId::of::<Point>(), ValueKind::Point),
+            (TypeId::of::<Size>(), ValueKind::Size),
+            (TypeId::of::<Rect>(), ValueKind::Rect),
+            (TypeId::of::<Insets>(), ValueKind::Insets),
+            (
+                TypeId::of::<RoundedRectRadii>(),
+                ValueKind::RoundedRectRadii,
+            ),
+            (TypeId::of::<bool>(), ValueKind::Bool),
+            (TypeId::of::<u64>(), ValueKind::UnsignedInt),
+            (TypeId::of::<ArcStr>(), ValueKind::String),
+            (TypeId::of::<FontDescriptor>(), ValueKind::Font),
+        ];
+        let this_type = TypeId::of::<T>();
+        kinds
+            .iter()
+            .find(|(type_id, _)| *type_id == this_type)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Parse a value of this kind, in the format described in the [module docs](crate::theme_loader).
+    pub fn parse(self, s: &str) -> Result<Value, ThemeLoadError> {
+        let s = s.trim();
+        let value = match self {
+            ValueKind::Color => Color::from_hex_str(s)
+                .map_err(ThemeLoadError::ParseColorError)?
+                .into(),
+            ValueKind::Float => s
+                .parse::<f64>()
+                .map_err(ThemeLoadError::ParseFloatError)?
+                .into(),
+            ValueKind::Point => match self.parse_floats(s)?[..] {
+                [x, y] => Point::new(x, y).into(),
+                _ => return Err(self.error(s)),
+            },
+            ValueKind::Size => match self.parse_floats(s)?[..] {
+                [width, height] => Size::new(width, height).into(),
+                _ => return Err(self.error(s)),
+            },
+            ValueKind::Rect => match self.parse_floats(s)?[..] {
+                [x0, y0, x1, y1] => Rect::new(x0, y0, x1, y1).into(),
+                _ => return Err(self.error(s)),
+            },
+            ValueKind::Insets => match self.parse_floats(s)?[..] {
+                [all] => Insets::uniform(all).into(),
+                [x, y] => Insets::uniform_xy(x, y).into(),
+                [x0, y0, x1, y1] => Insets::new(x0, y0, x1, y1).into(),
+                _ => return Err(self.error(s)),
+            },
+            ValueKind::RoundedRectRadii => match self.parse_floats(s)?[..] {
+                [all] => RoundedRectRadii::from_single_radius(all).into(),
+                [top_left, top_right, bottom_right, bottom_left] => {
+                    RoundedRectRadii::new(top_left, top_right, bottom_right, bottom_left).into()
+                }
+                _ => return Err(self.error(s)),
+            },
+            ValueKind::Bool => s
+                .parse::<bool>()
+                .map_err(ThemeLoadError::ParseBoolError)?
+                .into(),
+            ValueKind::UnsignedInt => s
+                .parse::<u64>()
+                .map_err(ThemeLoadError::ParseIntError)?
+                .into(),
+            ValueKind::String => ArcStr::from(s).into(),
+            ValueKind::Font => parse_font(s).ok_or_else(|| self.error(s))?.into(),
+        };
+        Ok(value)
+    }
+
+    fn parse_floats(self, s: &str) -> Result<Vec<f64>, ThemeLoadError> {
+        s.split(',')
+            .map(|f| {
+                f.trim()
+                    .parse::<f64>()
+                    .map_err(ThemeLoadError::ParseFloatError)
+            })
+            .collect()
+    }
+
+    fn error(self, s: &str) -> ThemeLoadError {
+        ThemeLoadError::ParseValueError(self, s.to_string())
+    }
+}
+
+fn parse_font(s: &str) -> Option<FontDescriptor> {
+    let mut words: Vec<&str> = s.split_whitespace().collect();
+
+    let italic = matches!(words.last(), Some(word) if word.eq_ignore_ascii_case("italic"));
+    if italic {
+        words.pop();
+    }
+    let weight = words.last().and_then(|word| font_weight(word));
+    if weight.is_some() {
+        words.pop();
+    }
+    let size = words.last().and_then(|word| word.parse::<f64>().ok());
+    if size.is_some() {
+        words.pop();
+    }
+    if words.is_empty() {
+        return None;
+    }
+
+    let family = match &*words.join(" ") {
+        "system-ui" => FontFamily::SYSTEM_UI,
+        "sans-serif" => FontFamily::SANS_SERIF,
+        "serif" => FontFamily::SERIF,
+        "monospace" => FontFamily::MONOSPACE,
+        name => FontFamily::new_unchecked(name),
+    };
+    let mut font = FontDescriptor::new(family);
+    if let Some(size) = size {
+        font = font.with_size(size);
+    }
+    if let Some(weight) = weight {
+        font = font.with_weight(weight);
+    }
+    if italic {
+        font = font.with_style(FontStyle::Italic);
+    }
+    Some(font)
+}
+
+fn font_weight(s: &str) -> Option<FontWeight> {
+    let weight = match &*s.to_ascii_lowercase() {
+        "thin" | "hairline" => FontWeight::THIN,
+        "extra-light" => FontWeight::EXTRA_LIGHT,
+        "light" => FontWeight::LIGHT,
+        "regular" | "normal" => FontWeight::REGULAR,
+        "medium" => FontWeight::MEDIUM,
+        "semi-bold" => FontWeight::SEMI_BOLD,
+        "bold" => FontWeight::BOLD,
+        "extra-bold" => FontWeight::EXTRA_BOLD,
+        "black" | "heavy" => FontWeight::BLACK,
+        "extra-black" => FontWeight::EXTRA_BLACK,
+        _ => return None,
+    };
+    Some(weight)
 }
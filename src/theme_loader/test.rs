@@ -0,0 +1,181 @@
+use super::*;
+use druid::Key;
+
+const BACKGROUND: Key<Color> = Key::new("druid-widget-nursery.theme-loader.test.background");
+const PADDING: Key<Insets> = Key::new("druid-widget-nursery.theme-loader.test.padding");
+const FONT: Key<FontDescriptor> = Key::new("druid-widget-nursery.theme-loader.test.font");
+
+crate::loadable_theme!(TestTheme {
+    BACKGROUND,
+    PADDING as "widget.padding",
+    FONT as "widget.font",
+});
+
+fn load(format: ThemeFormat, contents: &str) -> Result<Env, ThemeLoadError> {
+    load_theme(&TestTheme, format, contents, &Env::empty())
+}
+
+fn fira_sans() -> FontDescriptor {
+    FontDescriptor::new(FontFamily::new_unchecked("Fira Sans"))
+        .with_size(14.0)
+        .with_weight(FontWeight::BOLD)
+        .with_style(FontStyle::Italic)
+}
+
+fn check_env(env: &Env) {
+    assert_eq!(env.get(BACKGROUND), Color::rgb8(0xff, 0xdd, 0xaa));
+    assert_eq!(env.get(PADDING), Insets::uniform_xy(4.0, 2.0));
+    assert_eq!(env.get(FONT), fira_sans());
+}
+
+#[test]
+fn test_text_format() {
+    let env = load(
+        ThemeFormat::Text,
+        "
+        BACKGROUND: #fda
+
+        widget.padding: 4, 2
+        widget.font: Fira Sans 14 bold italic
+        ",
+    )
+    .unwrap();
+    check_env(&env);
+
+    let result = load(ThemeFormat::Text, "BACKGROUND #fda");
+    assert!(
+        matches!(result, Err(ThemeLoadError::ParseThemeLineError(line)) if line == "BACKGROUND #fda")
+    );
+}
+
+#[test]
+fn test_unknown_and_missing_keys() {
+    let result = load(
+        ThemeFormat::Text,
+        "
+        BACKGROUND: #fda
+        widget.padding: 4
+        widget.font: serif
+        widget.margin: 4
+        ",
+    );
+    assert!(matches!(result, Err(ThemeLoadError::UnknownKey(key)) if key == "widget.margin"));
+
+    let result = load(
+        ThemeFormat::Text,
+        "
+        BACKGROUND: #fda
+        widget.padding: 4
+        ",
+    );
+    assert!(matches!(
+        result,
+        Err(ThemeLoadError::MissingKey("widget.font"))
+    ));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_format() {
+    let env = load(
+        ThemeFormat::Toml,
+        r##"
+        BACKGROUND = "#fda"
+
+        [widget]
+        padding = [4, 2]
+        font = "Fira Sans 14 bold italic"
+        "##,
+    )
+    .unwrap();
+    check_env(&env);
+
+    let items = ThemeFormat::Toml
+        .parse("[a.b]\nc = 1.5\nd = true\n[e]\nf = [1, 2, 3, 4]")
+        .unwrap();
+    assert_eq!(
+        items,
+        [
+            ("a.b.c".to_string(), "1.5".to_string()),
+            ("a.b.d".to_string(), "true".to_string()),
+            ("e.f".to_string(), "1, 2, 3, 4".to_string()),
+        ]
+    );
+
+    let result = ThemeFormat::Toml.parse("padding = [\"4\", \"2\"]");
+    assert!(matches!(result, Err(ThemeLoadError::ParseFileError(_))));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_json_format() {
+    let env = load(
+        ThemeFormat::Json,
+        r##"{
+            "BACKGROUND": "#fda",
+            "widget": { "padding": [4, 2], "font": "Fira Sans 14 bold italic" }
+        }"##,
+    )
+    .unwrap();
+    check_env(&env);
+
+    let items = ThemeFormat::Json
+        .parse(r#"{ "a": { "b": { "c": 1.5 } }, "d": [1, 2] }"#)
+        .unwrap();
+    assert_eq!(
+        items,
+        [
+            ("a.b.c".to_string(), "1.5".to_string()),
+            ("d".to_string(), "1, 2".to_string()),
+        ]
+    );
+
+    let result = ThemeFormat::Json.parse(r#"{ "padding": null }"#);
+    assert!(matches!(result, Err(ThemeLoadError::ParseFileError(_))));
+}
+
+#[test]
+fn test_insets_and_radii() {
+    let insets = |s| match ValueKind::Insets.parse(s) {
+        Ok(Value::Insets(insets)) => Some(insets),
+        _ => None,
+    };
+    assert_eq!(insets("3"), Some(Insets::uniform(3.0)));
+    assert_eq!(insets(" 4, 2 "), Some(Insets::uniform_xy(4.0, 2.0)));
+    assert_eq!(insets("1,2,3,4"), Some(Insets::new(1.0, 2.0, 3.0, 4.0)));
+    assert_eq!(insets("1, 2, 3"), None);
+    assert_eq!(insets("1, x"), None);
+
+    let radii = |s| match ValueKind::RoundedRectRadii.parse(s) {
+        Ok(Value::RoundedRectRadii(radii)) => Some(radii),
+        _ => None,
+    };
+    assert_eq!(radii("5"), Some(RoundedRectRadii::from_single_radius(5.0)));
+    assert_eq!(
+        radii("1, 2, 3, 4"),
+        Some(RoundedRectRadii::new(1.0, 2.0, 3.0, 4.0))
+    );
+    assert_eq!(radii("1, 2"), None);
+}
+
+#[test]
+fn test_parse_font() {
+    assert_eq!(parse_font("Fira Sans 14 bold italic"), Some(fira_sans()));
+    assert_eq!(
+        parse_font("Fira Sans Semi-Bold"),
+        Some(
+            FontDescriptor::new(FontFamily::new_unchecked("Fira Sans"))
+                .with_weight(FontWeight::SEMI_BOLD)
+        )
+    );
+    assert_eq!(
+        parse_font("monospace 11.5"),
+        Some(FontDescriptor::new(FontFamily::MONOSPACE).with_size(11.5))
+    );
+    assert_eq!(
+        parse_font("serif italic"),
+        Some(FontDescriptor::new(FontFamily::SERIF).with_style(FontStyle::Italic))
+    );
+    assert_eq!(parse_font("14 bold"), None);
+    assert_eq!(parse_font(""), None);
+}
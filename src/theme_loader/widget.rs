@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use druid::ExtEventSink;
 use druid::{widget::prelude::*, Selector};
 
-use crate::theme_loader::{LoadableTheme, ThemeLoadError};
+use crate::theme_loader::{load_theme, LoadableTheme, ThemeFormat, ThemeLoadError};
 
 pub const RELOAD_THEME: Selector<()> = Selector::new("runebender.theme-loader-reload");
 
@@ -14,6 +14,7 @@ pub const RELOAD_THEME: Selector<()> = Selector::new("runebender.theme-loader-re
 /// feature is enabled.
 pub struct ThemeLoader<T, W> {
     theme_path: PathBuf,
+    format: ThemeFormat,
     theme: T,
     current_env: Option<Env>,
     inner: W,
@@ -25,20 +26,39 @@ impl<T: LoadableTheme, W> ThemeLoader<T, W> {
     /// The `path` argument should be a path to the theme file. The `them`
     /// argument is a theme generated by the [`loadable_theme!`] macro.
     ///
+    /// The format of the file is guessed from its extension, see
+    /// [`ThemeFormat::from_path`].
+    ///
     /// [`loadable_theme!`] crate::loadable_theme
     pub fn new(path: impl Into<PathBuf>, theme: T, inner: W) -> Self {
+        let theme_path = path.into();
         ThemeLoader {
-            theme_path: path.into(),
+            format: ThemeFormat::from_path(&theme_path),
+            theme_path,
             theme,
             inner,
             current_env: None,
         }
     }
 
+    /// Builder-style method for setting the format of the theme file.
+    ///
+    /// For the non-builder varient, see [`set_format`].
+    ///
+    /// [`set_format`]: ThemeLoader::set_format
+    pub fn with_format(mut self, format: ThemeFormat) -> Self {
+        self.set_format(format);
+        self
+    }
+
+    /// Set the format of the theme file, instead of guessing it from the extension.
+    pub fn set_format(&mut self, format: ThemeFormat) {
+        self.format = format;
+    }
+
     fn add_env_to_theme(&mut self, env: &Env) -> Result<Env, ThemeLoadError> {
         let file_contents = std::fs::read_to_string(&self.theme_path)?;
-        let contents = iter_items(&file_contents).collect::<Result<_, _>>()?;
-        self.theme.load(&contents, env)
+        load_theme(&self.theme, self.format, &file_contents, env)
     }
 
     fn reload_theme_and_log_errors(&mut self, env: &Env) {
@@ -49,20 +69,6 @@ impl<T: LoadableTheme, W> ThemeLoader<T, W> {
     }
 }
 
-fn iter_items(s: &str) -> impl Iterator<Item = Result<(&str, &str), ThemeLoadError>> {
-    s.lines().filter_map(|line| {
-        if line.trim().is_empty() {
-            None
-        } else {
-            let mut split = line.split(':');
-            match (split.next(), split.next(), split.next()) {
-                (Some(key), Some(val), None) => Some(Ok((key.trim(), val.trim()))),
-                _ => Some(Err(ThemeLoadError::ParseThemeLineError(line.to_string()))),
-            }
-        }
-    })
-}
-
 impl<T, S, W> Widget<T> for ThemeLoader<S, W>
 where
     T: Data,
@@ -137,7 +143,8 @@ fn start_watcher(sink: ExtEventSink, path: PathBuf, target: WidgetId) {
 
         loop {
             match rx.recv() {
-                Ok(DebouncedEvent::Write(_)) => {
+                // editors that save by replacing the file produce a create
+                Ok(DebouncedEvent::Write(_)) | Ok(DebouncedEvent::Create(_)) => {
                     log::info!("sending reload command");
                     if sink.submit_command(RELOAD_THEME, (), target).is_err() {
                         break;
@@ -0,0 +1,373 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Switching themes at runtime.
+
+use std::sync::Arc;
+
+use druid::widget::prelude::*;
+use druid::{theme, Color, Key, Point, Selector, Value, ValueType, WidgetPod};
+
+use crate::animation::{Animated, AnimationCurve, Interpolate};
+use crate::{focus_ring, rich_label, wedge};
+
+/// Replace the theme of every [`ThemeScope`] the command reaches.
+///
+/// Submit it to a window or globally to re-theme the whole app.
+pub const SET_THEME: Selector<Theme> = Selector::new("druid-widget-nursery.theme-scope.set-theme");
+
+type GetFn = dyn Fn(&Env) -> Option<Value> + Send + Sync;
+type SetFn = dyn Fn(&mut Env, &Value) + Send + Sync;
+
+#[derive(Clone)]
+struct ThemeEntry {
+    value: Value,
+    get: Arc<GetFn>,
+    set: Arc<SetFn>,
+}
+
+/// A set of [`Env`] overrides, applied by a [`ThemeScope`].
+///
+/// ```
+/// use druid::{theme, Color};
+/// use druid_widget_nursery::Theme;
+///
+/// let solarized = Theme::dark()
+///     .adding(theme::WINDOW_BACKGROUND_COLOR, Color::rgb8(0x00, 0x2b, 0x36))
+///     .adding(theme::TEXT_COLOR, Color::rgb8(0x83, 0x94, 0x96))
+///     .adding(theme::TEXT_SIZE_NORMAL, 14.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct Theme {
+    entries: Arc<Vec<ThemeEntry>>,
+}
+
+impl Theme {
+    /// An empty theme, which doesn't change anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style method to override `key` with `value`.
+    ///
+    /// Overriding a key twice keeps the last value.
+    pub fn adding<V: ValueType + Send + Sync + 'static>(
+        mut self,
+        key: Key<V>,
+        value: impl Into<V>,
+    ) -> Self {
+        let value: Value = value.into().into();
+        let get_key = key.clone();
+        let entry = ThemeEntry {
+            value,
+            get: Arc::new(move |env| env.try_get(&get_key).ok().map(Into::into)),
+            set: Arc::new(move |env, value| {
+                if let Ok(value) = V::try_from_value(value) {
+                    env.set(&key, value);
+                }
+            }),
+        };
+        Arc::make_mut(&mut self.entries).push(entry);
+        self
+    }
+
+    /// The colors of druid's default theme.
+    pub fn dark() -> Self {
+        Self::from_palette(&[
+            (
+                theme::WINDOW_BACKGROUND_COLOR,
+                Color::rgb8(0x29, 0x29, 0x29),
+            ),
+            (theme::TEXT_COLOR, Color::rgb8(0xf0, 0xf0, 0xea)),
+            (theme::DISABLED_TEXT_COLOR, Color::rgb8(0xa0, 0xa0, 0x9a)),
+            (theme::PLACEHOLDER_COLOR, Color::rgb8(0x80, 0x80, 0x80)),
+            (theme::PRIMARY_LIGHT, Color::rgb8(0x5c, 0xc4, 0xff)),
+            (theme::PRIMARY_DARK, Color::rgb8(0x00, 0x8d, 0xdd)),
+            (theme::BACKGROUND_LIGHT, Color::rgb8(0x3a, 0x3a, 0x3a)),
+            (theme::BACKGROUND_DARK, Color::rgb8(0x31, 0x31, 0x31)),
+            (theme::FOREGROUND_LIGHT, Color::rgb8(0xf9, 0xf9, 0xf9)),
+            (theme::FOREGROUND_DARK, Color::rgb8(0xbf, 0xbf, 0xbf)),
+            (theme::BUTTON_DARK, Color::BLACK),
+            (theme::BUTTON_LIGHT, Color::rgb8(0x21, 0x21, 0x21)),
+            (theme::BORDER_DARK, Color::rgb8(0x3a, 0x3a, 0x3a)),
+            (theme::BORDER_LIGHT, Color::rgb8(0xa1, 0xa1, 0xa1)),
+            (
+                theme::SELECTED_TEXT_BACKGROUND_COLOR,
+                Color::rgb8(0x43, 0x70, 0xa8),
+            ),
+            (theme::CURSOR_COLOR, Color::WHITE),
+            (theme::SCROLLBAR_COLOR, Color::rgb8(0xff, 0xff, 0xff)),
+            (theme::SCROLLBAR_BORDER_COLOR, Color::rgb8(0x77, 0x77, 0x77)),
+        ])
+    }
+
+    /// A light counterpart of [`Theme::dark`].
+    pub fn light() -> Self {
+        Self::from_palette(&[
+            (
+                theme::WINDOW_BACKGROUND_COLOR,
+                Color::rgb8(0xf2, 0xf2, 0xf2),
+            ),
+            (theme::TEXT_COLOR, Color::rgb8(0x1a, 0x1a, 0x1a)),
+            (theme::DISABLED_TEXT_COLOR, Color::rgb8(0x8a, 0x8a, 0x8a)),
+            (theme::PLACEHOLDER_COLOR, Color::rgb8(0x8c, 0x8c, 0x8c)),
+            (theme::PRIMARY_LIGHT, Color::rgb8(0x5c, 0xc4, 0xff)),
+            (theme::PRIMARY_DARK, Color::rgb8(0x00, 0x8d, 0xdd)),
+            (theme::BACKGROUND_LIGHT, Color::rgb8(0xff, 0xff, 0xff)),
+            (theme::BACKGROUND_DARK, Color::rgb8(0xe6, 0xe6, 0xe6)),
+            (theme::FOREGROUND_LIGHT, Color::rgb8(0x1a, 0x1a, 0x1a)),
+            (theme::FOREGROUND_DARK, Color::rgb8(0x4a, 0x4a, 0x4a)),
+            (theme::BUTTON_DARK, Color::rgb8(0xd4, 0xd4, 0xd4)),
+            (theme::BUTTON_LIGHT, Color::rgb8(0xf6, 0xf6, 0xf6)),
+            (theme::BORDER_DARK, Color::rgb8(0xc8, 0xc8, 0xc8)),
+            (theme::BORDER_LIGHT, Color::rgb8(0x6e, 0x6e, 0x6e)),
+            (
+                theme::SELECTED_TEXT_BACKGROUND_COLOR,
+                Color::rgb8(0xb3, 0xd7, 0xff),
+            ),
+            (theme::CURSOR_COLOR, Color::rgb8(0x1a, 0x1a, 0x1a)),
+            (theme::SCROLLBAR_COLOR, Color::rgb8(0x40, 0x40, 0x40)),
+            (theme::SCROLLBAR_BORDER_COLOR, Color::rgb8(0xa0, 0xa0, 0xa0)),
+        ])
+    }
+
+    /// White on black, with yellow for the focus and the selection, and thicker borders.
+    ///
+    /// The keys of this crate are included, so that its widgets respect it too.
+    pub fn high_contrast() -> Self {
+        let yellow = Color::rgb8(0xff, 0xff, 0x00);
+        Self::from_palette(&[
+            (theme::WINDOW_BACKGROUND_COLOR, Color::BLACK),
+            (theme::TEXT_COLOR, Color::WHITE),
+            (theme::DISABLED_TEXT_COLOR, Color::rgb8(0xc0, 0xc0, 0xc0)),
+            (theme::PLACEHOLDER_COLOR, Color::rgb8(0xc0, 0xc0, 0xc0)),
+            (theme::PRIMARY_LIGHT, yellow),
+            (theme::PRIMARY_DARK, Color::rgb8(0x00, 0xff, 0xff)),
+            (theme::BACKGROUND_LIGHT, Color::BLACK),
+            (theme::BACKGROUND_DARK, Color::BLACK),
+            (theme::FOREGROUND_LIGHT, Color::WHITE),
+            (theme::FOREGROUND_DARK, Color::WHITE),
+            (theme::BUTTON_DARK, Color::BLACK),
+            (theme::BUTTON_LIGHT, Color::BLACK),
+            (theme::BORDER_DARK, Color::WHITE),
+            (theme::BORDER_LIGHT, yellow),
+            (
+                theme::SELECTED_TEXT_BACKGROUND_COLOR,
+                Color::rgb8(0x00, 0x00, 0xff),
+            ),
+            (theme::CURSOR_COLOR, Color::WHITE),
+            (theme::SCROLLBAR_COLOR, Color::WHITE),
+            (theme::SCROLLBAR_BORDER_COLOR, Color::BLACK),
+            (focus_ring::COLOR, yellow),
+            (wedge::COLOR, Color::WHITE),
+            (wedge::HOT_COLOR, yellow),
+            (rich_label::LINK_COLOR, Color::rgb8(0x00, 0xff, 0xff)),
+        ])
+        .adding(theme::BUTTON_BORDER_WIDTH, 2.0)
+        .adding(theme::TEXTBOX_BORDER_WIDTH, 2.0)
+        .adding(focus_ring::WIDTH, 3.0)
+    }
+
+    fn from_palette(palette: &[(Key<Color>, Color)]) -> Self {
+        palette.iter().fold(Self::new(), |theme, (key, color)| {
+            theme.adding(key.clone(), *color)
+        })
+    }
+
+    /// Returns `true` if the theme doesn't override anything.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Apply the theme to `env`.
+    pub fn apply(&self, env: &mut Env) {
+        for entry in self.entries.iter() {
+            (entry.set)(env, &entry.value);
+        }
+    }
+
+    /// The values `env` currently has for the keys of this theme.
+    fn current_values(&self, env: &Env) -> Vec<Option<Value>> {
+        self.entries.iter().map(|entry| (entry.get)(env)).collect()
+    }
+
+    /// Apply the theme, with values that can be interpolated moved `progress` of the way from
+    /// `from`.
+    fn apply_partially(&self, env: &mut Env, from: &[Option<Value>], progress: f64) {
+        for (entry, from) in self.entries.iter().zip(from) {
+            let value = from
+                .as_ref()
+                .and_then(|from| interpolate_value(from, &entry.value, progress))
+                .unwrap_or_else(|| entry.value.clone());
+            (entry.set)(env, &value);
+        }
+    }
+}
+
+impl Data for Theme {
+    fn same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+fn interpolate_value(from: &Value, to: &Value, progress: f64) -> Option<Value> {
+    let value = match (from, to) {
+        (Value::Color(from), Value::Color(to)) => Value::Color(from.interpolate(to, progress)),
+        (Value::Float(from), Value::Float(to)) => Value::Float(from.interpolate(to, progress)),
+        (Value::Point(from), Value::Point(to)) => Value::Point(from.interpolate(to, progress)),
+        (Value::Size(from), Value::Size(to)) => Value::Size(from.interpolate(to, progress)),
+        (Value::Rect(from), Value::Rect(to)) => Value::Rect(from.interpolate(to, progress)),
+        (Value::Insets(from), Value::Insets(to)) => Value::Insets(from.interpolate(to, progress)),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// A widget applying a [`Theme`] to its child, which can be replaced at runtime with
+/// [`SET_THEME`].
+///
+/// As all nursery widgets read their colors from the [`Env`], they follow the theme as well.
+/// The scope paints [`WINDOW_BACKGROUND_COLOR`] behind its child, as the window would.
+///
+/// [`WINDOW_BACKGROUND_COLOR`]: druid::theme::WINDOW_BACKGROUND_COLOR
+pub struct ThemeScope<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    theme: Theme,
+    /// The values the keys of the theme had before the last theme change.
+    from: Vec<Option<Value>>,
+    progress: Animated<f64>,
+    /// The last environment we got, and the one we pass to our child.
+    env: Option<(Env, Env)>,
+}
+
+impl<T: Data> ThemeScope<T> {
+    pub fn new(theme: Theme, inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            theme,
+            from: Vec::new(),
+            progress: Animated::jump(1.0).curve(AnimationCurve::EASE_IN_OUT),
+            env: None,
+        }
+    }
+
+    /// Builder-style method for animating theme changes over `duration` seconds.
+    ///
+    /// Colors, floats, points, sizes, rects and insets are interpolated, other values are
+    /// switched immediately.
+    ///
+    /// For the non-builder varient, see [`set_transition`].
+    ///
+    /// [`set_transition`]: #method.set_transition
+    pub fn with_transition(mut self, duration: f64) -> Self {
+        self.set_transition(duration);
+        self
+    }
+
+    /// Set the duration of theme changes in seconds, zero to switch immediately.
+    pub fn set_transition(&mut self, duration: f64) {
+        self.progress.set_duration(duration);
+    }
+
+    /// The current theme.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Switch to `theme`, with a transition.
+    pub(crate) fn set_theme(&mut self, ctx: &mut EventCtx, theme: Theme) {
+        if let Some((_, child_env)) = &self.env {
+            self.from = theme.current_values(child_env);
+        }
+        self.theme = theme;
+        self.env = None;
+        self.progress.jump_to_value(0.0);
+        self.progress.animate(ctx, 1.0);
+        env_changed(ctx);
+    }
+
+    /// Switch to `theme` immediately.
+    pub(crate) fn replace_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.env = None;
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let mut child_env = env.clone();
+                if self.progress.animating() {
+                    self.theme
+                        .apply_partially(&mut child_env, &self.from, self.progress.get());
+                } else {
+                    self.theme.apply(&mut child_env);
+                }
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+/// Pass the new environment on, not every widget checks for env changes.
+fn env_changed(ctx: &mut EventCtx) {
+    ctx.request_update();
+    ctx.request_layout();
+    ctx.request_paint();
+}
+
+impl<T: Data> Widget<T> for ThemeScope<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(SET_THEME) => {
+                self.set_theme(ctx, cmd.get_unchecked(SET_THEME).clone());
+            }
+            Event::AnimFrame(nanos) if self.progress.animating() => {
+                self.progress.update(ctx, *nanos);
+                self.env = None;
+                env_changed(ctx);
+            }
+            _ => {}
+        }
+        let env = self.child_env(env);
+        self.inner.event(ctx, event, data, &env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.inner.lifecycle(ctx, event, data, &env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.inner.update(ctx, data, &env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let env = self.child_env(env);
+        let size = self.inner.layout(ctx, bc, data, &env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        // the window paints its background with the env of the app
+        let background = env.get(theme::WINDOW_BACKGROUND_COLOR);
+        let rect = ctx.size().to_rect();
+        ctx.fill(rect, &background);
+        self.inner.paint(ctx, data, &env);
+    }
+}
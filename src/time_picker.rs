@@ -0,0 +1,440 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text inputs for times of day and durations.
+
+use std::time::Duration;
+
+use druid::widget::prelude::*;
+use druid::widget::TextBox;
+
+use crate::step_input::{InputFormat, Step, StepInput};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// A time of day that can be edited by a [`TimePicker`].
+///
+/// This is implemented for `(hours, minutes)` tuples, and for [`ChronoTime`] with the `chrono`
+/// feature.
+pub trait TimeValue: Data {
+    /// The hours (0-23) and minutes (0-59).
+    fn to_hm(&self) -> (u32, u32);
+    /// Create a value from valid hours and minutes.
+    fn from_hm(hours: u32, minutes: u32) -> Self;
+}
+
+impl TimeValue for (u32, u32) {
+    fn to_hm(&self) -> (u32, u32) {
+        *self
+    }
+
+    fn from_hm(hours: u32, minutes: u32) -> Self {
+        (hours, minutes)
+    }
+}
+
+/// A `chrono::NaiveTime` which can be used as druid data, as `NaiveTime` doesn't implement
+/// [`Data`].
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChronoTime(pub chrono::NaiveTime);
+
+#[cfg(feature = "chrono")]
+impl Data for ChronoTime {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for ChronoTime {
+    fn from(time: chrono::NaiveTime) -> Self {
+        ChronoTime(time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<ChronoTime> for chrono::NaiveTime {
+    fn from(time: ChronoTime) -> Self {
+        time.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeValue for ChronoTime {
+    fn to_hm(&self) -> (u32, u32) {
+        use chrono::Timelike;
+        (self.0.hour(), self.0.minute())
+    }
+
+    fn from_hm(hours: u32, minutes: u32) -> Self {
+        ChronoTime(chrono::NaiveTime::from_hms_opt(hours, minutes, 0).expect("invalid time"))
+    }
+}
+
+struct TimeFormat {
+    twelve_hour: bool,
+    minute_step: u32,
+}
+
+impl TimeFormat {
+    /// Parse times like `9:05`, `0905`, `21.05`, `9pm` or `9:05 AM`.
+    fn parse_hm(text: &str) -> Option<(u32, u32)> {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        let (text, pm) = if let Some(text) = text.strip_suffix("am").or(text.strip_suffix('a')) {
+            (text, Some(false))
+        } else if let Some(text) = text.strip_suffix("pm").or(text.strip_suffix('p')) {
+            (text, Some(true))
+        } else {
+            (text.as_str(), None)
+        };
+        if text.is_empty()
+            || !text
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b == b':' || b == b'.')
+        {
+            return None;
+        }
+
+        let (hours, minutes) = match text.find(|c: char| c == ':' || c == '.') {
+            Some(separator) => (&text[..separator], &text[separator + 1..]),
+            None if text.len() > 2 => text.split_at(text.len() - 2),
+            None => (text, ""),
+        };
+        if hours.is_empty() || minutes.len() > 2 {
+            return None;
+        }
+        let hours: u32 = hours.parse().ok()?;
+        let minutes: u32 = if minutes.is_empty() {
+            0
+        } else {
+            minutes.parse().ok()?
+        };
+
+        let hours = match pm {
+            Some(_) if !(1..=12).contains(&hours) => return None,
+            Some(pm) => hours % 12 + if pm { 12 } else { 0 },
+            None if hours > 23 => return None,
+            None => hours,
+        };
+        if minutes > 59 {
+            return None;
+        }
+        Some((hours, minutes))
+    }
+}
+
+impl<T: TimeValue> InputFormat<T> for TimeFormat {
+    fn parse(&self, text: &str) -> Option<T> {
+        TimeFormat::parse_hm(text).map(|(hours, minutes)| T::from_hm(hours, minutes))
+    }
+
+    fn format(&self, value: &T) -> String {
+        let (hours, minutes) = value.to_hm();
+        if self.twelve_hour {
+            let suffix = if hours < 12 { "AM" } else { "PM" };
+            let hours = match hours % 12 {
+                0 => 12,
+                hours => hours,
+            };
+            format!("{}:{:02} {}", hours, minutes, suffix)
+        } else {
+            format!("{:02}:{:02}", hours, minutes)
+        }
+    }
+
+    fn step(&self, value: &T, step: Step, large: bool) -> T {
+        let (hours, minutes) = value.to_hm();
+        let total = (hours * 60 + minutes) as i64;
+        let step_size = if large {
+            60
+        } else {
+            self.minute_step.max(1) as i64
+        };
+        // snap to multiples of the step
+        let total = match step {
+            Step::Up => (total.div_euclid(step_size) + 1) * step_size,
+            Step::Down => ((total + step_size - 1).div_euclid(step_size) - 1) * step_size,
+        }
+        .rem_euclid(MINUTES_PER_DAY);
+        T::from_hm((total / 60) as u32, (total % 60) as u32)
+    }
+}
+
+/// A text box for a time of day in 24 or 12 hour format, with buttons for stepping the minutes.
+///
+/// Times like `9:05`, `0905`, `21.05`, `9pm` or `9:05 AM` are accepted in both formats, and
+/// normalized when the text box loses focus or Enter is pressed. The arrow keys and the mouse
+/// wheel step the minutes, Page Up and Page Down the hours.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::TimePicker;
+/// fn alarm() -> impl Widget<(u32, u32)> {
+///     TimePicker::new().with_twelve_hour(true).with_minute_step(15)
+/// }
+/// ```
+pub struct TimePicker<T> {
+    input: StepInput<T, TimeFormat>,
+}
+
+impl<T: TimeValue> TimePicker<T> {
+    pub fn new() -> Self {
+        TimePicker {
+            input: StepInput::new(
+                TimeFormat {
+                    twelve_hour: false,
+                    minute_step: 1,
+                },
+                TextBox::new().with_placeholder("hh:mm"),
+            ),
+        }
+    }
+
+    /// Builder-style method for showing the time in 12 hour format with AM and PM.
+    ///
+    /// For the non-builder varient, see [`set_twelve_hour`].
+    ///
+    /// [`set_twelve_hour`]: #method.set_twelve_hour
+    pub fn with_twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.set_twelve_hour(twelve_hour);
+        self
+    }
+
+    /// Set whether the time is shown in 12 hour format with AM and PM.
+    pub fn set_twelve_hour(&mut self, twelve_hour: bool) {
+        self.input.format.twelve_hour = twelve_hour;
+    }
+
+    /// Builder-style method for specifying the minutes added or subtracted by a step.
+    ///
+    /// For the non-builder varient, see [`set_minute_step`].
+    ///
+    /// [`set_minute_step`]: #method.set_minute_step
+    pub fn with_minute_step(mut self, minutes: u32) -> Self {
+        self.set_minute_step(minutes);
+        self
+    }
+
+    /// Set the minutes added or subtracted by the buttons, the arrow keys and the mouse wheel.
+    /// Stepping snaps to multiples of the step.
+    pub fn set_minute_step(&mut self, minutes: u32) {
+        self.input.format.minute_step = minutes;
+    }
+}
+
+impl<T: TimeValue> Default for TimePicker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct DurationFormat {
+    step: Duration,
+}
+
+impl DurationFormat {
+    /// Parse durations like `1h 30m`, `90 min`, `1.5h` or `1:30`. A number without a unit is
+    /// in minutes.
+    fn parse_duration(text: &str) -> Option<Duration> {
+        let text = text.trim().to_lowercase();
+        if text.is_empty() {
+            return None;
+        }
+
+        if text.contains(':') {
+            // hours:minutes or hours:minutes:seconds
+            let parts: Vec<&str> = text.split(':').map(str::trim).collect();
+            if parts.len() > 3 {
+                return None;
+            }
+            let mut seconds = 0;
+            for (i, (part, unit)) in parts.iter().zip(&[3600, 60, 1]).enumerate() {
+                if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let value: u64 = part.parse().ok()?;
+                if i > 0 && value >= 60 {
+                    return None;
+                }
+                seconds += value * unit;
+            }
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let mut seconds = 0.0;
+        let mut rest = text.as_str();
+        let mut first = true;
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+                .unwrap_or(rest.len());
+            if number_len == 0 {
+                return None;
+            }
+            let number: f64 = rest[..number_len].replace(',', ".").parse().ok()?;
+            rest = rest[number_len..].trim_start();
+            let unit_len = rest
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(rest.len());
+            let unit = match &rest[..unit_len] {
+                "" if first && rest.is_empty() => 60.0,
+                "d" | "day" | "days" => 86400.0,
+                "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+                "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+                "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+                _ => return None,
+            };
+            seconds += number * unit;
+            rest = rest[unit_len..].trim_start();
+            first = false;
+        }
+        if seconds > u32::MAX as f64 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(seconds))
+    }
+}
+
+impl InputFormat<Duration> for DurationFormat {
+    fn parse(&self, text: &str) -> Option<Duration> {
+        DurationFormat::parse_duration(text)
+    }
+
+    fn format(&self, value: &Duration) -> String {
+        let total = value.as_secs_f64().round() as u64;
+        let parts = [
+            (total / 86400, "d"),
+            (total / 3600 % 24, "h"),
+            (total / 60 % 60, "m"),
+            (total % 60, "s"),
+        ];
+        let text: Vec<String> = parts
+            .iter()
+            .filter(|(value, _)| *value > 0)
+            .map(|(value, unit)| format!("{}{}", value, unit))
+            .collect();
+        if text.is_empty() {
+            "0m".to_string()
+        } else {
+            text.join(" ")
+        }
+    }
+
+    fn step(&self, value: &Duration, step: Step, large: bool) -> Duration {
+        let step_size = if large { self.step * 60 } else { self.step };
+        match step {
+            Step::Up => value.checked_add(step_size).unwrap_or(*value),
+            Step::Down => value.checked_sub(step_size).unwrap_or_default(),
+        }
+    }
+}
+
+/// A text box for durations like `1h 30m`, with buttons for stepping the value.
+///
+/// Besides combinations of days, hours, minutes and seconds, `1:30` is accepted for an hour and
+/// a half and a number without a unit is taken as minutes. The text is normalized when the text
+/// box loses focus or Enter is pressed.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::DurationInput;
+/// fn timeout() -> impl Widget<Duration> {
+///     DurationInput::new().with_step(Duration::from_secs(5 * 60))
+/// }
+/// ```
+pub struct DurationInput {
+    input: StepInput<Duration, DurationFormat>,
+}
+
+impl DurationInput {
+    pub fn new() -> Self {
+        DurationInput {
+            input: StepInput::new(
+                DurationFormat {
+                    step: Duration::from_secs(60),
+                },
+                TextBox::new().with_placeholder("1h 30m"),
+            ),
+        }
+    }
+
+    /// Builder-style method for specifying the step.
+    ///
+    /// For the non-builder varient, see [`set_step`].
+    ///
+    /// [`set_step`]: #method.set_step
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.set_step(step);
+        self
+    }
+
+    /// Set the amount added or subtracted by the buttons, the arrow keys and the mouse wheel.
+    /// Page Up and Page Down step by 60 times this amount.
+    pub fn set_step(&mut self, step: Duration) {
+        self.input.format.step = step;
+    }
+}
+
+impl Default for DurationInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! delegate_widget {
+    ($ty:ty, $data:ty, $($bounds:tt)*) => {
+        impl $($bounds)* Widget<$data> for $ty {
+            fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut $data, env: &Env) {
+                self.input.event(ctx, event, data, env)
+            }
+
+            fn lifecycle(
+                &mut self,
+                ctx: &mut LifeCycleCtx,
+                event: &LifeCycle,
+                data: &$data,
+                env: &Env,
+            ) {
+                self.input.lifecycle(ctx, event, data, env)
+            }
+
+            fn update(&mut self, ctx: &mut UpdateCtx, old_data: &$data, data: &$data, env: &Env) {
+                self.input.update(ctx, old_data, data, env)
+            }
+
+            fn layout(
+                &mut self,
+                ctx: &mut LayoutCtx,
+                bc: &BoxConstraints,
+                data: &$data,
+                env: &Env,
+            ) -> Size {
+                self.input.layout(ctx, bc, data, env)
+            }
+
+            fn paint(&mut self, ctx: &mut PaintCtx, data: &$data, env: &Env) {
+                self.input.paint(ctx, data, env)
+            }
+        }
+    };
+}
+
+delegate_widget!(TimePicker<T>, T, <T: TimeValue>);
+delegate_widget!(DurationInput, Duration,);
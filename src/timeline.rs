@@ -0,0 +1,558 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::im::Vector;
+use druid::kurbo::Line;
+use druid::piet::{Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, Cursor, KeyOrValue, Lens, Point, Rect};
+
+use crate::DynLens;
+
+const HEADER_HEIGHT: f64 = 24.0;
+const ROW_HEIGHT: f64 = 28.0;
+const BAR_INSET: f64 = 4.0;
+/// The width of the group names, when the rows are grouped.
+const GUTTER_WIDTH: f64 = 120.0;
+/// How close to the ends of a bar the mouse has to be to resize it.
+const EDGE_WIDTH: f64 = 5.0;
+/// The smallest space between two ticks of the time scale.
+const MIN_TICK_SPACING: f64 = 80.0;
+/// How much the scale changes for a wheel delta of one.
+const ZOOM_SPEED: f64 = 0.002;
+/// The width taken when the width is unbounded.
+const DEFAULT_WIDTH: f64 = 600.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragMode {
+    Move,
+    ResizeStart,
+    ResizeEnd,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Drag {
+    Bar {
+        item: usize,
+        mode: DragMode,
+        press_x: f64,
+        /// The span of the item when the drag started.
+        start: f64,
+        end: f64,
+    },
+    Pan {
+        press_x: f64,
+        view_start: f64,
+    },
+}
+
+/// A time scale with a bar for each item, spanning from its start to its end, like a Gantt chart.
+///
+/// Times are numbers in any unit, like days or hours since an epoch; [`with_tick_format`] turns
+/// them into the labels of the scale. The bars can be moved, or resized by their ends, with the
+/// mouse. The timeline scrolls sideways with the mouse wheel while Shift is held, or by dragging
+/// the background, and zooms with the wheel while Ctrl is held.
+///
+/// Each item has its own row, unless they are grouped with [`with_group`], like by the person
+/// working on them.
+///
+/// ```
+/// # use druid::{Data, Lens, Widget};
+/// # use druid::im::Vector;
+/// # use druid_widget_nursery::Timeline;
+/// #[derive(Clone, Data, Lens)]
+/// struct Task {
+///     name: String,
+///     owner: String,
+///     /// In days.
+///     start: f64,
+///     end: f64,
+/// }
+///
+/// fn planning(today: f64) -> impl Widget<Vector<Task>> {
+///     Timeline::new(Task::start, Task::end)
+///         .with_label(|task: &Task| task.name.clone())
+///         .with_group(|task: &Task| task.owner.clone())
+///         .with_tick_format(|day| format!("Day {}", day))
+///         .with_snap(1.0)
+///         .with_today(today)
+/// }
+/// ```
+///
+/// [`with_tick_format`]: #method.with_tick_format
+/// [`with_group`]: #method.with_group
+pub struct Timeline<I> {
+    start: Box<dyn DynLens<I, f64>>,
+    end: Box<dyn DynLens<I, f64>>,
+    label: Option<Box<dyn Fn(&I) -> String>>,
+    group: Option<Box<dyn Fn(&I) -> String>>,
+    tick_format: Box<dyn Fn(f64) -> String>,
+    today: Option<f64>,
+    snap: Option<f64>,
+    bar_color: KeyOrValue<Color>,
+    /// The time at the left edge of the scale, and the pixels per unit of time.
+    view_start: f64,
+    scale: f64,
+    /// The row of each item, and the names of the groups.
+    rows: Vec<usize>,
+    groups: Vec<String>,
+    drag: Option<Drag>,
+}
+
+impl<I: Data> Timeline<I> {
+    /// Create a timeline of items starting and ending at the times of the `start` and `end`
+    /// lenses.
+    pub fn new(start: impl Lens<I, f64> + 'static, end: impl Lens<I, f64> + 'static) -> Self {
+        Timeline {
+            start: Box::new(start),
+            end: Box::new(end),
+            label: None,
+            group: None,
+            tick_format: Box::new(|time| time.to_string()),
+            today: None,
+            snap: None,
+            bar_color: theme::PRIMARY_DARK.into(),
+            view_start: 0.0,
+            scale: 20.0,
+            rows: Vec::new(),
+            groups: Vec::new(),
+            drag: None,
+        }
+    }
+
+    /// Builder-style method for writing the text returned by `label` on the bars.
+    pub fn with_label(mut self, label: impl Fn(&I) -> String + 'static) -> Self {
+        self.label = Some(Box::new(label));
+        self
+    }
+
+    /// Builder-style method for showing the items with the same name returned by `group` in the
+    /// same row, with the name on its left.
+    pub fn with_group(mut self, group: impl Fn(&I) -> String + 'static) -> Self {
+        self.group = Some(Box::new(group));
+        self
+    }
+
+    /// Builder-style method for turning the times of the scale into labels.
+    pub fn with_tick_format(mut self, format: impl Fn(f64) -> String + 'static) -> Self {
+        self.tick_format = Box::new(format);
+        self
+    }
+
+    /// Builder-style method for marking the current time with a line.
+    ///
+    /// For the non-builder varient, see [`set_today`].
+    ///
+    /// [`set_today`]: #method.set_today
+    pub fn with_today(mut self, today: f64) -> Self {
+        self.set_today(Some(today));
+        self
+    }
+
+    /// Set the time marked with a line, or `None` for no line.
+    pub fn set_today(&mut self, today: Option<f64>) {
+        self.today = today;
+    }
+
+    /// Builder-style method for rounding the times of moved and resized bars to multiples of
+    /// `snap`.
+    ///
+    /// For the non-builder varient, see [`set_snap`].
+    ///
+    /// [`set_snap`]: #method.set_snap
+    pub fn with_snap(mut self, snap: f64) -> Self {
+        self.set_snap(Some(snap));
+        self
+    }
+
+    /// Set the interval the times of moved and resized bars are rounded to, or `None` to keep
+    /// them as they are.
+    pub fn set_snap(&mut self, snap: Option<f64>) {
+        self.snap = snap;
+    }
+
+    /// Builder-style method for specifying the color of the bars.
+    ///
+    /// For the non-builder varient, see [`set_bar_color`].
+    ///
+    /// [`set_bar_color`]: #method.set_bar_color
+    pub fn with_bar_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_bar_color(color);
+        self
+    }
+
+    /// Set the color of the bars.
+    pub fn set_bar_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.bar_color = color.into();
+    }
+
+    /// Builder-style method for specifying the time shown at the left edge, and the pixels per
+    /// unit of time.
+    ///
+    /// For the non-builder varient, see [`set_view`].
+    ///
+    /// [`set_view`]: #method.set_view
+    pub fn with_view(mut self, start: f64, scale: f64) -> Self {
+        self.set_view(start, scale);
+        self
+    }
+
+    /// Set the time shown at the left edge, 0 by default, and the pixels per unit of time, 20 by
+    /// default.
+    pub fn set_view(&mut self, start: f64, scale: f64) {
+        self.view_start = start;
+        self.scale = scale;
+    }
+
+    fn update_rows(&mut self, data: &Vector<I>) {
+        self.rows.clear();
+        self.groups.clear();
+        for (index, item) in data.iter().enumerate() {
+            let row = match &self.group {
+                Some(group) => {
+                    let name = group(item);
+                    match self.groups.iter().position(|other| *other == name) {
+                        Some(row) => row,
+                        None => {
+                            self.groups.push(name);
+                            self.groups.len() - 1
+                        }
+                    }
+                }
+                None => index,
+            };
+            self.rows.push(row);
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        match &self.group {
+            Some(_) => self.groups.len(),
+            None => self.rows.len(),
+        }
+    }
+
+    /// Where the scale starts, after the group names.
+    fn scale_x0(&self) -> f64 {
+        match &self.group {
+            Some(_) => GUTTER_WIDTH,
+            None => 0.0,
+        }
+    }
+
+    fn x_at(&self, time: f64) -> f64 {
+        self.scale_x0() + (time - self.view_start) * self.scale
+    }
+
+    fn time_at(&self, x: f64) -> f64 {
+        self.view_start + (x - self.scale_x0()) / self.scale
+    }
+
+    fn span(&self, item: &I) -> (f64, f64) {
+        (
+            self.start.with(item, |start| *start),
+            self.end.with(item, |end| *end),
+        )
+    }
+
+    fn bar_rect(&self, index: usize, item: &I) -> Rect {
+        let (start, end) = self.span(item);
+        let x0 = self.x_at(start);
+        let x1 = self.x_at(end).max(x0 + 2.0);
+        let y = HEADER_HEIGHT + self.rows[index] as f64 * ROW_HEIGHT;
+        Rect::new(x0, y + BAR_INSET, x1, y + ROW_HEIGHT - BAR_INSET)
+    }
+
+    /// The bar under `pos`, and what dragging it does.
+    fn bar_at(&self, pos: Point, data: &Vector<I>) -> Option<(usize, DragMode)> {
+        if pos.x < self.scale_x0() {
+            return None;
+        }
+        // the bars painted last are on top
+        data.iter()
+            .enumerate()
+            .rev()
+            .map(|(index, item)| (index, self.bar_rect(index, item)))
+            .find(|(_, rect)| rect.contains(pos))
+            .map(|(index, rect)| {
+                let mode = if rect.width() <= 3.0 * EDGE_WIDTH {
+                    DragMode::Move
+                } else if pos.x < rect.x0 + EDGE_WIDTH {
+                    DragMode::ResizeStart
+                } else if pos.x > rect.x1 - EDGE_WIDTH {
+                    DragMode::ResizeEnd
+                } else {
+                    DragMode::Move
+                };
+                (index, mode)
+            })
+    }
+
+    fn snap(&self, time: f64) -> f64 {
+        match self.snap {
+            Some(snap) if snap > 0.0 => (time / snap).round() * snap,
+            _ => time,
+        }
+    }
+
+    /// The interval between the ticks of the scale, a round number keeping them apart.
+    fn tick_step(&self) -> f64 {
+        let min_step = MIN_TICK_SPACING / self.scale;
+        let magnitude = 10f64.powf(min_step.log10().floor());
+        [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|factor| factor * magnitude)
+            .find(|step| *step >= min_step)
+            .unwrap_or(10.0 * magnitude)
+    }
+}
+
+impl<I: Data> Widget<Vector<I>> for Timeline<I> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Vector<I>, _env: &Env) {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                self.drag = Some(match self.bar_at(mouse.pos, data) {
+                    Some((item, mode)) => {
+                        let (start, end) = self.span(&data[item]);
+                        Drag::Bar {
+                            item,
+                            mode,
+                            press_x: mouse.pos.x,
+                            start,
+                            end,
+                        }
+                    }
+                    None => Drag::Pan {
+                        press_x: mouse.pos.x,
+                        view_start: self.view_start,
+                    },
+                });
+                ctx.set_active(true);
+                ctx.set_handled();
+            }
+            Event::MouseMove(mouse) => match self.drag {
+                Some(Drag::Bar {
+                    item,
+                    mode,
+                    press_x,
+                    start,
+                    end,
+                }) if item < data.len() => {
+                    let delta = (mouse.pos.x - press_x) / self.scale;
+                    let (new_start, new_end) = match mode {
+                        DragMode::Move => {
+                            let new_start = self.snap(start + delta);
+                            (new_start, new_start + end - start)
+                        }
+                        DragMode::ResizeStart => (self.snap(start + delta).min(end), end),
+                        DragMode::ResizeEnd => (start, self.snap(end + delta).max(start)),
+                    };
+                    let mut moved = data[item].clone();
+                    self.start.with_mut(&mut moved, |start| *start = new_start);
+                    self.end.with_mut(&mut moved, |end| *end = new_end);
+                    data[item] = moved;
+                    ctx.set_handled();
+                }
+                Some(Drag::Pan {
+                    press_x,
+                    view_start,
+                }) => {
+                    self.view_start = view_start - (mouse.pos.x - press_x) / self.scale;
+                    ctx.request_paint();
+                    ctx.set_handled();
+                }
+                _ => match self.bar_at(mouse.pos, data) {
+                    Some((_, DragMode::ResizeStart)) | Some((_, DragMode::ResizeEnd)) => {
+                        ctx.set_cursor(&Cursor::ResizeLeftRight)
+                    }
+                    _ => ctx.clear_cursor(),
+                },
+            },
+            Event::MouseUp(_) if self.drag.is_some() => {
+                self.drag = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+            }
+            Event::Wheel(mouse) if mouse.mods.ctrl() => {
+                // the time under the mouse stays there
+                let anchor = self.time_at(mouse.pos.x);
+                self.scale = (self.scale * (-mouse.wheel_delta.y * ZOOM_SPEED).exp())
+                    .max(1e-6)
+                    .min(1e6);
+                self.view_start = anchor - (mouse.pos.x - self.scale_x0()) / self.scale;
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            Event::Wheel(mouse) => {
+                // vertical scrolling is left to the parent, unless Shift is held
+                let delta = if mouse.mods.shift() {
+                    mouse.wheel_delta.y
+                } else {
+                    mouse.wheel_delta.x
+                };
+                if delta != 0.0 {
+                    self.view_start += delta / self.scale;
+                    ctx.request_paint();
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &Vector<I>,
+        _env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            self.update_rows(data);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &Vector<I>, data: &Vector<I>, _env: &Env) {
+        if !old_data.same(data) {
+            let rows = self.row_count();
+            self.update_rows(data);
+            if self.row_count() != rows {
+                ctx.request_layout();
+            }
+            ctx.request_paint();
+        }
+        if ctx.env_changed() {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &Vector<I>,
+        _env: &Env,
+    ) -> Size {
+        let width = if bc.max().width.is_finite() {
+            bc.max().width
+        } else {
+            DEFAULT_WIDTH
+        };
+        let height = HEADER_HEIGHT + self.row_count() as f64 * ROW_HEIGHT;
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Vector<I>, env: &Env) {
+        let size = ctx.size();
+        let font = env.get(theme::UI_FONT);
+        let small_size = env.get(theme::TEXT_SIZE_SMALL);
+        let text_color = env.get(theme::TEXT_COLOR);
+        let grid_color = env.get(theme::BORDER_DARK);
+        let scale_rect = Rect::new(self.scale_x0(), 0.0, size.width, size.height);
+
+        ctx.with_save(|ctx| {
+            ctx.clip(scale_rect);
+
+            // the ticks of the scale, with a line through the rows
+            let step = self.tick_step();
+            let mut time = (self.view_start / step).ceil() * step;
+            while self.x_at(time) < size.width {
+                let x = self.x_at(time);
+                ctx.stroke(
+                    Line::new((x, HEADER_HEIGHT - 4.0), (x, size.height)),
+                    &grid_color,
+                    1.0,
+                );
+                let label = ctx
+                    .text()
+                    .new_text_layout((self.tick_format)(time))
+                    .font(font.family.clone(), small_size)
+                    .text_color(text_color)
+                    .build()
+                    .unwrap();
+                ctx.draw_text(&label, (x + 3.0, 2.0));
+                time += step;
+            }
+
+            let bar_color = self.bar_color.resolve(env);
+            let active_color = env.get(theme::PRIMARY_LIGHT);
+            let dragged = match self.drag {
+                Some(Drag::Bar { item, .. }) => Some(item),
+                _ => None,
+            };
+            for (index, item) in data.iter().enumerate() {
+                let rect = self.bar_rect(index, item);
+                let color = if dragged == Some(index) {
+                    &active_color
+                } else {
+                    &bar_color
+                };
+                ctx.fill(rect.to_rounded_rect(3.0), color);
+                if let Some(label) = &self.label {
+                    let label = ctx
+                        .text()
+                        .new_text_layout(label(item))
+                        .font(font.family.clone(), small_size)
+                        .text_color(Color::WHITE)
+                        .build()
+                        .unwrap();
+                    let y = rect.center().y - label.size().height / 2.0;
+                    ctx.with_save(|ctx| {
+                        ctx.clip(rect);
+                        ctx.draw_text(&label, (rect.x0 + 4.0, y));
+                    });
+                }
+            }
+
+            if let Some(today) = self.today {
+                let x = self.x_at(today);
+                ctx.stroke(
+                    Line::new((x, 0.0), (x, size.height)),
+                    &Color::rgb8(0xd3, 0x2f, 0x2f),
+                    2.0,
+                );
+            }
+        });
+
+        ctx.stroke(
+            Line::new(
+                (0.0, HEADER_HEIGHT - 0.5),
+                (size.width, HEADER_HEIGHT - 0.5),
+            ),
+            &grid_color,
+            1.0,
+        );
+        if self.group.is_some() {
+            ctx.stroke(
+                Line::new((GUTTER_WIDTH - 0.5, 0.0), (GUTTER_WIDTH - 0.5, size.height)),
+                &grid_color,
+                1.0,
+            );
+            for (row, name) in self.groups.iter().enumerate() {
+                let label = ctx
+                    .text()
+                    .new_text_layout(name.clone())
+                    .font(font.family.clone(), font.size)
+                    .text_color(text_color)
+                    .max_width(GUTTER_WIDTH - 8.0)
+                    .build()
+                    .unwrap();
+                let y = HEADER_HEIGHT
+                    + row as f64 * ROW_HEIGHT
+                    + (ROW_HEIGHT - label.size().height) / 2.0;
+                ctx.draw_text(&label, (4.0, y));
+            }
+        }
+    }
+}
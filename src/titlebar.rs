@@ -1,4 +1,56 @@
-use druid::{Data, Event, Widget};
+use druid::commands::CLOSE_WINDOW;
+use druid::kurbo::{Circle, Line};
+use druid::widget::prelude::*;
+use druid::{
+    theme, Color, Data, Menu, MenuItem, MouseButton, Point, Rect, Vec2, WidgetPod, WindowHandle,
+    WindowState,
+};
+
+/// The width of a window control button, on platforms with rectangular buttons.
+const CONTROL_WIDTH: f64 = 46.0;
+/// The minimum height of the titlebar when it shows window controls.
+const CONTROL_MIN_HEIGHT: f64 = 30.0;
+const CONTROL_GLYPH_SIZE: f64 = 10.0;
+/// The narrowest the drag area gets before children are collapsed into the overflow menu.
+const MIN_DRAG_WIDTH: f64 = 40.0;
+const OVERFLOW_WIDTH: f64 = 30.0;
+const OVERFLOW_DOT_RADIUS: f64 = 1.5;
+const CLOSE_HOT_COLOR: Color = Color::rgb8(0xe8, 0x11, 0x23);
+const CONTROL_HOT_COLOR: Color = Color::rgba8(0x80, 0x80, 0x80, 0x50);
+
+/// The distance between the centers of the macOS "traffic lights".
+const MAC_CONTROL_SPACING: f64 = 20.0;
+const MAC_CONTROL_RADIUS: f64 = 6.0;
+const MAC_CONTROL_PADDING: f64 = 8.0;
+
+struct TitleBarChild<T> {
+    pod: WidgetPod<T, Box<dyn Widget<T>>>,
+    collapse: Option<Collapse<T>>,
+    /// Set in layout when the child doesn't fit and is shown in the overflow menu instead.
+    collapsed: bool,
+}
+
+struct Collapse<T> {
+    priority: i32,
+    menu_item: Box<dyn Fn() -> MenuItem<T>>,
+}
+
+impl<T> TitleBarChild<T> {
+    fn new(child: impl Widget<T> + 'static, collapse: Option<Collapse<T>>) -> Self {
+        Self {
+            pod: WidgetPod::new(Box::new(child)),
+            collapse,
+            collapsed: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WindowControl {
+    Minimize,
+    Maximize,
+    Close,
+}
 
 /// A TitleBar widget.
 ///
@@ -7,35 +59,36 @@ use druid::{Data, Event, Widget};
 /// Presumably, this is because clicking means you are now dragging the window,
 /// not interacting with it.
 ///
-/// It uses the layout of its inner widget.
+/// The inner widget is the drag area. Widgets added with [`with_child`] and
+/// [`with_leading_child`] are laid out next to it and stay clickable, and
+/// [`with_window_controls`] adds minimize, maximize/restore and close buttons, placed according
+/// to the platform conventions. Double-clicking the drag area maximizes or restores the window.
+/// Without any of these, it uses the layout of its inner widget.
 ///
-/// This only works on windows due to it's use of [`handle_titlebar`](<https://docs.rs/druid-shell/0.7.0/druid_shell/struct.WindowHandle.html#method.handle_titlebar>)
+/// Dragging only works on windows due to its use of [`handle_titlebar`](<https://docs.rs/druid-shell/0.7.0/druid_shell/struct.WindowHandle.html#method.handle_titlebar>)
+///
+/// [`with_child`]: TitleBar::with_child
+/// [`with_leading_child`]: TitleBar::with_leading_child
+/// [`with_window_controls`]: TitleBar::with_window_controls
 ///
 /// ## Example
 /// ```ignore
 /// use druid::widget::{Button, Flex, Label};
-/// use druid::{AppLauncher, Application, Widget, WidgetExt, WindowDesc};
+/// use druid::{AppLauncher, Widget, WidgetExt, WindowDesc};
 /// use druid_widget_nursery::TitleBar;
 ///
 /// fn build_titlebar() -> impl Widget<u32> {
-///     // Make a row of buttons and title for the titlebar.
-///     Flex::row()
-///         // Increases the state by one each time clicked.
-///         .with_child(Button::new("+1").on_click(|_event, t, _env| *t += 1))
-///         // Flex child so it fills up all the space, which means it has to be limited.
-///         // This is done by the column in build_ui()
-///         .with_flex_child(
-///             // New titlebar with a label
-///             TitleBar::new(
-///                 // label is dynamic, using the value from data
-///                 Label::dynamic(|data: &u32, _env| format!("TitleBar: Value = {}", data))
-///                 // Center the label in the titlebar.
-///                 .center()
-///             ), 1.0
-///         )
-///         // Button to close the window.
-///         // There isn't a normal titlebar, so something like this has to exist.
-///         .with_child(Button::new("Close").on_click(|_,_,_| Application::global().quit()))
+///     // New titlebar with a label, which is the area you can drag the window by
+///     TitleBar::new(
+///         // label is dynamic, using the value from data
+///         Label::dynamic(|data: &u32, _env| format!("TitleBar: Value = {}", data))
+///         // Center the label in the titlebar.
+///         .center()
+///     )
+///     // Increases the state by one each time clicked, children stay clickable.
+///     .with_leading_child(Button::new("+1").on_click(|_event, t, _env| *t += 1))
+///     // There isn't a normal titlebar, so add minimize, maximize and close buttons.
+///     .with_window_controls(true)
 /// }
 ///
 /// fn build_ui() -> impl Widget<u32> {
@@ -60,58 +113,555 @@ use druid::{Data, Event, Widget};
 /// }
 /// ```
 pub struct TitleBar<T> {
-    inner: Box<dyn Widget<T>>,
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    leading: Vec<TitleBarChild<T>>,
+    trailing: Vec<TitleBarChild<T>>,
+    window_controls: bool,
+    /// Where the window controls are, set in layout.
+    controls_rect: Rect,
+    /// Where the overflow button is, empty if no child is collapsed.
+    overflow_rect: Rect,
+    overflow_hot: bool,
+    hot_control: Option<WindowControl>,
+    pressed_control: Option<WindowControl>,
 }
 
 impl<T: Data> TitleBar<T> {
     pub fn new(inner: impl Widget<T> + 'static) -> TitleBar<T> {
         TitleBar {
-            inner: Box::new(inner),
+            inner: WidgetPod::new(Box::new(inner)),
+            leading: Vec::new(),
+            trailing: Vec::new(),
+            window_controls: false,
+            controls_rect: Rect::ZERO,
+            overflow_rect: Rect::ZERO,
+            overflow_hot: false,
+            hot_control: None,
+            pressed_control: None,
         }
     }
-}
 
-// Feed all calls through into inner.
-impl<T: Data> Widget<T> for TitleBar<T> {
-    fn event(
+    /// Builder-style method for adding a clickable widget after the drag area.
+    ///
+    /// For the non-builder varient, see [`add_child`].
+    ///
+    /// [`add_child`]: TitleBar::add_child
+    pub fn with_child(mut self, child: impl Widget<T> + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Add a clickable widget after the drag area.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static) {
+        self.trailing.push(TitleBarChild::new(child, None));
+    }
+
+    /// Builder-style method for adding a widget after the drag area, which is moved into the
+    /// overflow menu when the window gets too narrow.
+    ///
+    /// Children with the lowest `priority` are collapsed first. In the overflow menu the child is
+    /// represented by the item returned by `menu_item`. As for all menus, `T` has to be the data
+    /// type of the application.
+    ///
+    /// For the non-builder varient, see [`add_collapsible_child`].
+    ///
+    /// [`add_collapsible_child`]: TitleBar::add_collapsible_child
+    pub fn with_collapsible_child(
+        mut self,
+        child: impl Widget<T> + 'static,
+        priority: i32,
+        menu_item: impl Fn() -> MenuItem<T> + 'static,
+    ) -> Self {
+        self.add_collapsible_child(child, priority, menu_item);
+        self
+    }
+
+    /// Add a widget after the drag area, which is moved into the overflow menu when the window
+    /// gets too narrow. See [`with_collapsible_child`].
+    ///
+    /// [`with_collapsible_child`]: TitleBar::with_collapsible_child
+    pub fn add_collapsible_child(
         &mut self,
-        ctx: &mut druid::EventCtx,
-        event: &druid::Event,
-        data: &mut T,
-        env: &druid::Env,
+        child: impl Widget<T> + 'static,
+        priority: i32,
+        menu_item: impl Fn() -> MenuItem<T> + 'static,
     ) {
-        // On any mousemove event in this tell the window to handle the titlebar.
-        if let Event::MouseMove(_) = event {
-            ctx.window().handle_titlebar(true);
+        let collapse = Collapse {
+            priority,
+            menu_item: Box::new(menu_item),
+        };
+        self.trailing
+            .push(TitleBarChild::new(child, Some(collapse)));
+    }
+
+    /// Builder-style method for adding a clickable widget before the drag area.
+    ///
+    /// For the non-builder varient, see [`add_leading_child`].
+    ///
+    /// [`add_leading_child`]: TitleBar::add_leading_child
+    pub fn with_leading_child(mut self, child: impl Widget<T> + 'static) -> Self {
+        self.add_leading_child(child);
+        self
+    }
+
+    /// Add a clickable widget before the drag area.
+    pub fn add_leading_child(&mut self, child: impl Widget<T> + 'static) {
+        self.leading.push(TitleBarChild::new(child, None));
+    }
+
+    /// Builder-style method for showing minimize, maximize/restore and close buttons.
+    ///
+    /// They are on the left on macOS, and on the right everywhere else.
+    ///
+    /// For the non-builder varient, see [`set_window_controls`].
+    ///
+    /// [`set_window_controls`]: TitleBar::set_window_controls
+    pub fn with_window_controls(mut self, window_controls: bool) -> Self {
+        self.set_window_controls(window_controls);
+        self
+    }
+
+    /// Set whether minimize, maximize/restore and close buttons are shown.
+    pub fn set_window_controls(&mut self, window_controls: bool) {
+        self.window_controls = window_controls;
+    }
+
+    /// All children, including the collapsed ones.
+    fn children(&mut self) -> impl Iterator<Item = &mut WidgetPod<T, Box<dyn Widget<T>>>> {
+        self.leading
+            .iter_mut()
+            .map(|child| &mut child.pod)
+            .chain(std::iter::once(&mut self.inner))
+            .chain(self.trailing.iter_mut().map(|child| &mut child.pod))
+    }
+
+    /// The children which are not collapsed.
+    fn visible_children(&mut self) -> impl Iterator<Item = &mut WidgetPod<T, Box<dyn Widget<T>>>> {
+        self.leading
+            .iter_mut()
+            .filter(|child| !child.collapsed)
+            .map(|child| &mut child.pod)
+            .chain(std::iter::once(&mut self.inner))
+            .chain(
+                self.trailing
+                    .iter_mut()
+                    .filter(|child| !child.collapsed)
+                    .map(|child| &mut child.pod),
+            )
+    }
+
+    fn overflow_menu(&self) -> Menu<T> {
+        self.leading
+            .iter()
+            .chain(self.trailing.iter())
+            .filter(|child| child.collapsed)
+            .filter_map(|child| child.collapse.as_ref())
+            .fold(Menu::empty(), |menu, collapse| {
+                menu.entry((collapse.menu_item)())
+            })
+    }
+
+    /// Decide which children are collapsed, given the width of all children.
+    fn collapse_children(&mut self, available: f64, mut used: f64) {
+        let mut candidates: Vec<_> = self
+            .leading
+            .iter_mut()
+            .chain(self.trailing.iter_mut())
+            .filter(|child| child.collapse.is_some())
+            .collect();
+        if used + MIN_DRAG_WIDTH <= available || candidates.is_empty() {
+            return;
+        }
+
+        used += OVERFLOW_WIDTH;
+        candidates.sort_by_key(|child| child.collapse.as_ref().map(|c| c.priority));
+        for child in candidates {
+            if used + MIN_DRAG_WIDTH <= available {
+                break;
+            }
+            child.collapsed = true;
+            used -= child.pod.layout_rect().width();
         }
-        self.inner.event(ctx, event, data, env);
     }
 
-    fn lifecycle(
-        &mut self,
-        ctx: &mut druid::LifeCycleCtx,
-        event: &druid::LifeCycle,
-        data: &T,
-        env: &druid::Env,
-    ) {
-        self.inner.lifecycle(ctx, event, data, env);
+    fn controls_width(&self) -> f64 {
+        match (self.window_controls, cfg!(target_os = "macos")) {
+            (false, _) => 0.0,
+            (true, true) => 3.0 * MAC_CONTROL_SPACING + 2.0 * MAC_CONTROL_PADDING,
+            (true, false) => 3.0 * CONTROL_WIDTH,
+        }
     }
 
-    fn update(&mut self, ctx: &mut druid::UpdateCtx, old_data: &T, data: &T, env: &druid::Env) {
-        self.inner.update(ctx, old_data, data, env);
+    /// The window controls in the order they are shown, with their hit areas.
+    fn controls(&self) -> Vec<(WindowControl, Rect)> {
+        if !self.window_controls {
+            return Vec::new();
+        }
+        let rect = self.controls_rect;
+        if cfg!(target_os = "macos") {
+            [
+                WindowControl::Close,
+                WindowControl::Minimize,
+                WindowControl::Maximize,
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, control)| {
+                let x0 = rect.x0 + MAC_CONTROL_PADDING + i as f64 * MAC_CONTROL_SPACING;
+                (
+                    *control,
+                    Rect::new(x0, rect.y0, x0 + MAC_CONTROL_SPACING, rect.y1),
+                )
+            })
+            .collect()
+        } else {
+            [
+                WindowControl::Minimize,
+                WindowControl::Maximize,
+                WindowControl::Close,
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, control)| {
+                let x0 = rect.x0 + i as f64 * CONTROL_WIDTH;
+                (
+                    *control,
+                    Rect::new(x0, rect.y0, x0 + CONTROL_WIDTH, rect.y1),
+                )
+            })
+            .collect()
+        }
     }
 
-    fn layout(
-        &mut self,
-        ctx: &mut druid::LayoutCtx,
-        bc: &druid::BoxConstraints,
-        data: &T,
-        env: &druid::Env,
-    ) -> druid::Size {
-        self.inner.layout(ctx, bc, data, env)
+    fn control_at(&self, pos: Point) -> Option<WindowControl> {
+        self.controls()
+            .into_iter()
+            .find(|(_, rect)| rect.contains(pos))
+            .map(|(control, _)| control)
+    }
+
+    /// Returns `true` if `pos` is on the drag area, not on a child or a window control.
+    fn is_drag_area(&self, pos: Point) -> bool {
+        !self.controls_rect.contains(pos)
+            && !self.overflow_rect.contains(pos)
+            && !self
+                .leading
+                .iter()
+                .chain(self.trailing.iter())
+                .any(|child| !child.collapsed && child.pod.layout_rect().contains(pos))
     }
+}
+
+fn is_mouse_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) | Event::Wheel(_)
+    )
+}
+
+fn toggle_maximized(window: &WindowHandle) {
+    let mut window = window.clone();
+    let state = match window.get_window_state() {
+        WindowState::Maximized => WindowState::Restored,
+        _ => WindowState::Maximized,
+    };
+    window.set_window_state(state);
+}
+
+impl<T: Data> Widget<T> for TitleBar<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::MouseMove(mouse) => {
+                // Let the window handle the titlebar only on the drag area, so children stay
+                // clickable.
+                ctx.window()
+                    .handle_titlebar(ctx.is_hot() && self.is_drag_area(mouse.pos));
+                let hot_control = self.control_at(mouse.pos).filter(|_| ctx.is_hot());
+                let overflow_hot = ctx.is_hot() && self.overflow_rect.contains(mouse.pos);
+                if hot_control != self.hot_control || overflow_hot != self.overflow_hot {
+                    self.hot_control = hot_control;
+                    self.overflow_hot = overflow_hot;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button == MouseButton::Left => {
+                if let Some(control) = self.control_at(mouse.pos) {
+                    self.pressed_control = Some(control);
+                    ctx.set_active(true);
+                    ctx.request_paint();
+                    ctx.set_handled();
+                    return;
+                }
+                if self.overflow_rect.contains(mouse.pos) {
+                    let position = Point::new(self.overflow_rect.x0, self.overflow_rect.y1);
+                    ctx.show_context_menu(self.overflow_menu(), ctx.to_window(position));
+                    ctx.set_handled();
+                    return;
+                }
+                if mouse.count == 2 && self.is_drag_area(mouse.pos) {
+                    toggle_maximized(ctx.window());
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseUp(mouse) if mouse.button == MouseButton::Left => {
+                if let Some(pressed) = self.pressed_control.take() {
+                    ctx.set_active(false);
+                    ctx.request_paint();
+                    ctx.set_handled();
+                    if self.control_at(mouse.pos) == Some(pressed) {
+                        match pressed {
+                            WindowControl::Minimize => ctx
+                                .window()
+                                .clone()
+                                .set_window_state(WindowState::Minimized),
+                            WindowControl::Maximize => toggle_maximized(ctx.window()),
+                            WindowControl::Close => ctx.submit_command(CLOSE_WINDOW),
+                        }
+                    }
+                    return;
+                }
+            }
+            _ => {}
+        }
+        if is_mouse_event(event) {
+            for child in self.visible_children() {
+                child.event(ctx, event, data, env);
+            }
+        } else {
+            for child in self.children() {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::HotChanged(false) = event {
+            if self.hot_control.take().is_some() || self.overflow_hot {
+                self.overflow_hot = false;
+                ctx.request_paint();
+            }
+        }
+        for child in self.children() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in self.children() {
+            child.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let controls_width = self.controls_width();
+        let mut height = if self.window_controls {
+            CONTROL_MIN_HEIGHT
+        } else {
+            0.0
+        };
 
-    fn paint(&mut self, ctx: &mut druid::PaintCtx, data: &T, env: &druid::Env) {
-        self.inner.paint(ctx, data, env);
+        let child_bc = BoxConstraints::new(
+            Size::new(0.0, bc.min().height),
+            Size::new(f64::INFINITY, bc.max().height),
+        );
+        let mut used_width = controls_width;
+        for child in self.leading.iter_mut().chain(self.trailing.iter_mut()) {
+            let size = child.pod.layout(ctx, &child_bc, data, env);
+            used_width += size.width;
+            height = height.max(size.height);
+            child.collapsed = false;
+        }
+
+        self.collapse_children(bc.max().width, used_width);
+        let collapsed = self
+            .leading
+            .iter()
+            .chain(self.trailing.iter())
+            .filter(|child| child.collapsed);
+        let overflow_width = if collapsed.clone().next().is_some() {
+            OVERFLOW_WIDTH
+        } else {
+            0.0
+        };
+        used_width += overflow_width - collapsed.map(|c| c.pod.layout_rect().width()).sum::<f64>();
+
+        let inner_bc = BoxConstraints::new(
+            Size::new((bc.min().width - used_width).max(0.0), bc.min().height),
+            Size::new((bc.max().width - used_width).max(0.0), bc.max().height),
+        );
+        let inner_size = self.inner.layout(ctx, &inner_bc, data, env);
+        height = height.max(inner_size.height);
+        let size = bc.constrain(Size::new(used_width + inner_size.width, height));
+
+        let center = |child: &WidgetPod<T, Box<dyn Widget<T>>>, x: f64| {
+            Point::new(x, (size.height - child.layout_rect().height()) / 2.0)
+        };
+
+        // controls, leading children, inner ... trailing children, controls
+        let mut x = 0.0;
+        if cfg!(target_os = "macos") {
+            self.controls_rect = Rect::new(0.0, 0.0, controls_width, size.height);
+            x += controls_width;
+        } else {
+            self.controls_rect =
+                Rect::new(size.width - controls_width, 0.0, size.width, size.height);
+        }
+        for child in self.leading.iter_mut().filter(|child| !child.collapsed) {
+            let origin = center(&child.pod, x);
+            child.pod.set_origin(ctx, origin);
+            x += child.pod.layout_rect().width();
+        }
+        let origin = center(&self.inner, x);
+        self.inner.set_origin(ctx, origin);
+
+        let mut x = if cfg!(target_os = "macos") {
+            size.width
+        } else {
+            size.width - controls_width
+        };
+        x -= overflow_width;
+        self.overflow_rect = Rect::new(x, 0.0, x + overflow_width, size.height);
+        for child in self
+            .trailing
+            .iter_mut()
+            .rev()
+            .filter(|child| !child.collapsed)
+        {
+            x -= child.pod.layout_rect().width();
+            let origin = center(&child.pod, x);
+            child.pod.set_origin(ctx, origin);
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for child in self.visible_children() {
+            child.paint(ctx, data, env);
+        }
+        self.paint_overflow(ctx, env);
+        if cfg!(target_os = "macos") {
+            self.paint_mac_controls(ctx);
+        } else {
+            self.paint_controls(ctx, env);
+        }
+    }
+}
+
+impl<T: Data> TitleBar<T> {
+    fn paint_overflow(&self, ctx: &mut PaintCtx, env: &Env) {
+        if self.overflow_rect.is_empty() {
+            return;
+        }
+        if self.overflow_hot {
+            ctx.fill(self.overflow_rect, &CONTROL_HOT_COLOR);
+        }
+        let center = self.overflow_rect.center();
+        let color = env.get(theme::TEXT_COLOR);
+        for dx in &[-5.0, 0.0, 5.0] {
+            let dot = Circle::new(center + Vec2::new(*dx, 0.0), OVERFLOW_DOT_RADIUS);
+            ctx.fill(dot, &color);
+        }
+    }
+
+    fn paint_controls(&self, ctx: &mut PaintCtx, env: &Env) {
+        let maximized = ctx.window().get_window_state() == WindowState::Maximized;
+        for (control, rect) in self.controls() {
+            let hot = self.hot_control == Some(control);
+            let text_color = if hot && control == WindowControl::Close {
+                ctx.fill(rect, &CLOSE_HOT_COLOR);
+                Color::WHITE
+            } else {
+                if hot || self.pressed_control == Some(control) {
+                    ctx.fill(rect, &CONTROL_HOT_COLOR);
+                }
+                env.get(theme::TEXT_COLOR)
+            };
+
+            let glyph = Rect::from_center_size(
+                rect.center(),
+                Size::new(CONTROL_GLYPH_SIZE, CONTROL_GLYPH_SIZE),
+            );
+            match control {
+                WindowControl::Minimize => {
+                    let y = glyph.center().y.round() + 0.5;
+                    ctx.stroke(Line::new((glyph.x0, y), (glyph.x1, y)), &text_color, 1.0);
+                }
+                WindowControl::Maximize if maximized => {
+                    // two overlapping windows
+                    let back = glyph.with_origin(glyph.origin() + Vec2::new(2.0, -2.0));
+                    let front = glyph
+                        .inflate(-1.0, -1.0)
+                        .with_origin(glyph.origin() + Vec2::new(0.0, 1.0));
+                    ctx.stroke(
+                        Line::new((back.x0, back.y0), (back.x1, back.y0)),
+                        &text_color,
+                        1.0,
+                    );
+                    ctx.stroke(
+                        Line::new((back.x1, back.y0), (back.x1, back.y1)),
+                        &text_color,
+                        1.0,
+                    );
+                    ctx.stroke(front, &text_color, 1.0);
+                }
+                WindowControl::Maximize => ctx.stroke(glyph, &text_color, 1.0),
+                WindowControl::Close => {
+                    ctx.stroke(
+                        Line::new((glyph.x0, glyph.y0), (glyph.x1, glyph.y1)),
+                        &text_color,
+                        1.0,
+                    );
+                    ctx.stroke(
+                        Line::new((glyph.x1, glyph.y0), (glyph.x0, glyph.y1)),
+                        &text_color,
+                        1.0,
+                    );
+                }
+            }
+        }
+    }
+
+    fn paint_mac_controls(&self, ctx: &mut PaintCtx) {
+        // like macOS, show the symbols on all buttons when any of them is hovered
+        let show_glyphs = self.hot_control.is_some();
+        let glyph_color = Color::rgba8(0, 0, 0, 0x99);
+        for (control, rect) in self.controls() {
+            let circle = Circle::new(rect.center(), MAC_CONTROL_RADIUS);
+            let color = match control {
+                WindowControl::Close => Color::rgb8(0xff, 0x5f, 0x57),
+                WindowControl::Minimize => Color::rgb8(0xfe, 0xbc, 0x2e),
+                WindowControl::Maximize => Color::rgb8(0x28, 0xc8, 0x40),
+            };
+            ctx.fill(circle, &color);
+            if !show_glyphs {
+                continue;
+            }
+
+            let c = rect.center();
+            let r = MAC_CONTROL_RADIUS / 2.0;
+            match control {
+                WindowControl::Close => {
+                    ctx.stroke(
+                        Line::new((c.x - r, c.y - r), (c.x + r, c.y + r)),
+                        &glyph_color,
+                        1.0,
+                    );
+                    ctx.stroke(
+                        Line::new((c.x + r, c.y - r), (c.x - r, c.y + r)),
+                        &glyph_color,
+                        1.0,
+                    );
+                }
+                WindowControl::Minimize => {
+                    ctx.stroke(Line::new((c.x - r, c.y), (c.x + r, c.y)), &glyph_color, 1.0);
+                }
+                WindowControl::Maximize => {
+                    ctx.stroke(Line::new((c.x - r, c.y), (c.x + r, c.y)), &glyph_color, 1.0);
+                    ctx.stroke(Line::new((c.x, c.y - r), (c.x, c.y + r)), &glyph_color, 1.0);
+                }
+            }
+        }
     }
 }
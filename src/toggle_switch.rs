@@ -0,0 +1,264 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use druid::keyboard_types::Key;
+use druid::kurbo::Circle;
+use druid::piet::{PietText, PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{theme, Color, KeyOrValue, LinearGradient, Point, UnitPoint};
+
+use crate::animation::{Animated, AnimationCurve, Interpolate};
+
+/// The space between the labels and the ends of the track.
+const LABEL_PADDING: f64 = 6.0;
+
+/// An on/off switch with a sliding thumb.
+///
+/// The switch is toggled by clicking it, or with Space and Enter when it is focused. Labels
+/// can be shown inside the track, on the side not covered by the thumb.
+///
+/// ```
+/// # use druid::Widget;
+/// # use druid_widget_nursery::ToggleSwitch;
+/// fn wifi() -> impl Widget<bool> {
+///     ToggleSwitch::new().with_labels("ON", "OFF")
+/// }
+/// ```
+pub struct ToggleSwitch {
+    position: Animated<f64>,
+    labels: Option<(String, String)>,
+    /// The width of the wider label, computed in layout.
+    label_width: f64,
+    on_color: KeyOrValue<Color>,
+}
+
+impl ToggleSwitch {
+    pub fn new() -> Self {
+        ToggleSwitch {
+            position: Animated::new(0.0)
+                .duration(0.15)
+                .curve(AnimationCurve::EASE_IN_OUT),
+            labels: None,
+            label_width: 0.0,
+            on_color: theme::PRIMARY_DARK.into(),
+        }
+    }
+
+    /// Builder-style method for specifying the labels shown inside the track.
+    ///
+    /// For the non-builder varient, see [`set_labels`].
+    ///
+    /// [`set_labels`]: #method.set_labels
+    pub fn with_labels(mut self, on: impl Into<String>, off: impl Into<String>) -> Self {
+        self.set_labels(Some((on.into(), off.into())));
+        self
+    }
+
+    /// Set the labels shown inside the track when the switch is on and off.
+    pub fn set_labels(&mut self, labels: Option<(String, String)>) {
+        self.labels = labels;
+    }
+
+    /// Builder-style method for specifying the color of the track when the switch is on.
+    ///
+    /// For the non-builder varient, see [`set_on_color`].
+    ///
+    /// [`set_on_color`]: #method.set_on_color
+    pub fn with_on_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.set_on_color(color);
+        self
+    }
+
+    /// Set the color of the track when the switch is on.
+    pub fn set_on_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.on_color = color.into();
+    }
+
+    fn toggle(&mut self, ctx: &mut EventCtx, data: &mut bool) {
+        *data = !*data;
+        self.position.animate(ctx, if *data { 1.0 } else { 0.0 });
+    }
+}
+
+fn label_layout(
+    text: &mut PietText,
+    label: &str,
+    color: Color,
+    env: &Env,
+) -> Option<PietTextLayout> {
+    text.new_text_layout(label.to_string())
+        .font(
+            env.get(theme::UI_FONT).family,
+            env.get(theme::TEXT_SIZE_SMALL),
+        )
+        .text_color(color)
+        .build()
+        .ok()
+}
+
+impl Default for ToggleSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget<bool> for ToggleSwitch {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut bool, _env: &Env) {
+        if let Event::AnimFrame(nanos) = event {
+            self.position.update(ctx, *nanos);
+        }
+        if ctx.is_disabled() {
+            return;
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                ctx.set_active(true);
+                ctx.request_focus();
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                if ctx.is_hot() {
+                    self.toggle(ctx, data);
+                }
+                ctx.request_paint();
+            }
+            Event::KeyDown(key) if ctx.has_focus() => {
+                if key.key == Key::Enter || key.key == Key::Character(" ".to_string()) {
+                    self.toggle(ctx, data);
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &bool, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.register_for_focus();
+                self.position.jump_to_value(if *data { 1.0 } else { 0.0 });
+            }
+            LifeCycle::HotChanged(_)
+            | LifeCycle::FocusChanged(_)
+            | LifeCycle::DisabledChanged(_) => ctx.request_paint(),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &bool, data: &bool, _env: &Env) {
+        if old_data != data {
+            self.position.animate(ctx, if *data { 1.0 } else { 0.0 });
+        }
+        if ctx.env_changed() || ctx.env_key_changed(&self.on_color) {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &bool,
+        env: &Env,
+    ) -> Size {
+        let height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        let mut width = height * 1.8;
+
+        self.label_width = 0.0;
+        if let Some((on, off)) = &self.labels {
+            for label in [on, off] {
+                let layout = label_layout(ctx.text(), label, Color::WHITE, env);
+                if let Some(layout) = layout {
+                    self.label_width = self.label_width.max(layout.size().width);
+                }
+            }
+            // the label shares the track with the thumb
+            width = width.max(height + self.label_width + 2.0 * LABEL_PADDING);
+        }
+
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &bool, env: &Env) {
+        let size = ctx.size();
+        let radius = size.height / 2.0;
+        let position = self.position.get();
+        let disabled = ctx.is_disabled();
+
+        // the track
+        let off_color = env.get(theme::BACKGROUND_LIGHT);
+        let on_color = if disabled {
+            env.get(theme::DISABLED_BUTTON_DARK)
+        } else {
+            self.on_color.resolve(env)
+        };
+        let track = size.to_rect().inset(-0.5).to_rounded_rect(radius);
+        ctx.fill(track, &off_color.interpolate(&on_color, position));
+        let border_color = if ctx.is_focused() {
+            env.get(theme::PRIMARY_LIGHT)
+        } else if ctx.is_hot() && !disabled {
+            env.get(theme::BORDER_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.stroke(track, &border_color, 1.0);
+
+        // the labels on the side not covered by the thumb, fading as it slides over them
+        if let Some((on, off)) = &self.labels {
+            let text_color = if disabled {
+                env.get(theme::DISABLED_TEXT_COLOR)
+            } else {
+                env.get(theme::TEXT_COLOR)
+            };
+            let label_space = size.width - size.height;
+            for (label, x, alpha) in [(on, 0.0, position), (off, size.height, 1.0 - position)] {
+                if alpha <= 0.0 {
+                    continue;
+                }
+                let color = text_color.with_alpha(alpha);
+                if let Some(layout) = label_layout(ctx.text(), label, color, env) {
+                    let label_size = layout.size();
+                    let origin = Point::new(
+                        x + (label_space - label_size.width) / 2.0,
+                        (size.height - label_size.height) / 2.0,
+                    );
+                    ctx.draw_text(&layout, origin);
+                }
+            }
+        }
+
+        // the thumb
+        let center_x = radius + position * (size.width - 2.0 * radius);
+        let thumb = Circle::new((center_x, radius), radius - 2.0);
+        let (light, dark) = if disabled {
+            (
+                env.get(theme::DISABLED_FOREGROUND_LIGHT),
+                env.get(theme::DISABLED_FOREGROUND_DARK),
+            )
+        } else {
+            (
+                env.get(theme::FOREGROUND_LIGHT),
+                env.get(theme::FOREGROUND_DARK),
+            )
+        };
+        let gradient = if ctx.is_active() {
+            LinearGradient::new(UnitPoint::TOP, UnitPoint::BOTTOM, (dark, light))
+        } else {
+            LinearGradient::new(UnitPoint::TOP, UnitPoint::BOTTOM, (light, dark))
+        };
+        ctx.fill(thumb, &gradient);
+        ctx.stroke(thumb, &env.get(theme::BORDER_DARK), 1.0);
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use druid::commands::CLOSE_WINDOW;
+use druid::kurbo::Line;
+use druid::widget::prelude::*;
+use druid::widget::{Flex, Label};
+use druid::{theme, KeyOrValue, Point, WidgetExt as _, WidgetPod};
+
+use crate::dropdown::DROPDOWN_SHOW;
+use crate::icon_button::MenuEntry;
+use crate::material_icons::{normal::navigation, Icon};
+use crate::{Dropdown, IconButton};
+
+/// The width taken by a separator between groups.
+const SEPARATOR_WIDTH: f64 = 9.0;
+
+enum Item<T> {
+    Widget {
+        pod: WidgetPod<T, Box<dyn Widget<T>>>,
+        /// The entry in the overflow menu, for icon buttons.
+        entry: Option<MenuEntry<T>>,
+    },
+    Separator,
+}
+
+/// A row of tools, usually [`IconButton`]s, in groups divided by separators.
+///
+/// When the row doesn't fit, the items at its end are hidden behind a button opening an overflow
+/// menu. Icon buttons are listed in the menu with their icon and their tooltip, and work from
+/// there like from the toolbar; other widgets are only hidden.
+///
+/// ```
+/// # use druid::{Data, Lens, Widget};
+/// # use druid_widget_nursery::material_icons::normal::{content, editor};
+/// # use druid_widget_nursery::{IconButton, Toolbar};
+/// #[derive(Clone, Data, Lens)]
+/// struct Editor {
+///     bold: bool,
+///     italic: bool,
+/// }
+///
+/// fn toolbar() -> impl Widget<Editor> {
+///     Toolbar::new()
+///         .with_button(IconButton::new(content::CONTENT_CUT).with_tooltip("Cut"))
+///         .with_button(IconButton::new(content::CONTENT_COPY).with_tooltip("Copy"))
+///         .with_separator()
+///         .with_button(
+///             IconButton::new(editor::FORMAT_BOLD)
+///                 .with_tooltip("Bold")
+///                 .with_toggle(Editor::bold),
+///         )
+///         .with_button(
+///             IconButton::new(editor::FORMAT_ITALIC)
+///                 .with_tooltip("Italic")
+///                 .with_toggle(Editor::italic),
+///         )
+/// }
+/// ```
+pub struct Toolbar<T> {
+    items: Vec<Item<T>>,
+    spacing: KeyOrValue<f64>,
+    /// The button opening the overflow menu.
+    more: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// The entries of the hidden icon buttons, shown in the overflow menu.
+    overflowed: Rc<RefCell<Vec<MenuEntry<T>>>>,
+    /// Whether the items are shown, and where they are, from the last layout.
+    visible: Vec<bool>,
+    positions: Vec<f64>,
+    overflowing: bool,
+}
+
+impl<T: Data> Toolbar<T> {
+    pub fn new() -> Self {
+        let overflowed = Rc::new(RefCell::new(Vec::new()));
+        let entries = overflowed.clone();
+        let more = Dropdown::new(
+            IconButton::new(navigation::MORE_HORIZ)
+                .with_tooltip("More")
+                .with_on_click(|ctx, _, _| ctx.submit_notification(DROPDOWN_SHOW)),
+            move |_, env| overflow_menu(&entries.borrow(), env),
+        );
+        Toolbar {
+            items: Vec::new(),
+            spacing: KeyOrValue::Concrete(2.0),
+            more: WidgetPod::new(Box::new(more)),
+            overflowed,
+            visible: Vec::new(),
+            positions: Vec::new(),
+            overflowing: false,
+        }
+    }
+
+    /// Builder-style method for adding an [`IconButton`], which is listed in the overflow menu
+    /// when it doesn't fit.
+    pub fn with_button(mut self, button: IconButton<T>) -> Self {
+        self.add_button(button);
+        self
+    }
+
+    /// Add an [`IconButton`], which is listed in the overflow menu when it doesn't fit.
+    pub fn add_button(&mut self, button: IconButton<T>) {
+        let entry = Some(button.menu_entry());
+        self.items.push(Item::Widget {
+            pod: WidgetPod::new(Box::new(button)),
+            entry,
+        });
+    }
+
+    /// Builder-style method for adding an arbitrary widget, like a search box.
+    pub fn with_child(mut self, child: impl Widget<T> + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Add an arbitrary widget, which is hidden when it doesn't fit.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static) {
+        self.items.push(Item::Widget {
+            pod: WidgetPod::new(Box::new(child)),
+            entry: None,
+        });
+    }
+
+    /// Builder-style method for adding a separator, ending a group of tools.
+    pub fn with_separator(mut self) -> Self {
+        self.add_separator();
+        self
+    }
+
+    /// Add a separator, ending a group of tools.
+    pub fn add_separator(&mut self) {
+        self.items.push(Item::Separator);
+    }
+
+    /// Builder-style method for specifying the spacing between the items.
+    ///
+    /// For the non-builder varient, see [`set_spacing`].
+    ///
+    /// [`set_spacing`]: #method.set_spacing
+    pub fn with_spacing(mut self, spacing: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_spacing(spacing);
+        self
+    }
+
+    /// Set the spacing between the items.
+    pub fn set_spacing(&mut self, spacing: impl Into<KeyOrValue<f64>>) {
+        self.spacing = spacing.into();
+    }
+}
+
+impl<T: Data> Default for Toolbar<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The menu listing the hidden icon buttons.
+fn overflow_menu<T: Data>(entries: &[MenuEntry<T>], env: &Env) -> impl Widget<T> {
+    let mut menu = Flex::column();
+    for entry in entries {
+        let behavior = entry.behavior.clone();
+        let row = Flex::row()
+            .with_child(Icon::new(entry.icon.clone()).with_size(18.0))
+            .with_spacer(8.0)
+            .with_child(Label::new(entry.label.clone()))
+            .padding((8.0, 4.0))
+            .expand_width()
+            .on_click(move |ctx, data, env| {
+                behavior.click(ctx, data, env);
+                ctx.submit_command(CLOSE_WINDOW.to(ctx.window_id()));
+            });
+        menu.add_child(row);
+    }
+    menu.fix_width(200.0)
+        .background(env.get(theme::BACKGROUND_LIGHT))
+        .border(env.get(theme::BORDER_DARK), 1.0)
+}
+
+impl<T: Data> Widget<T> for Toolbar<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let hidden = event.should_propagate_to_hidden();
+        for (index, item) in self.items.iter_mut().enumerate() {
+            let visible = self.visible.get(index).copied().unwrap_or(true);
+            if let Item::Widget { pod, .. } = item {
+                if visible || hidden {
+                    pod.event(ctx, event, data, env);
+                }
+            }
+        }
+        if self.overflowing || hidden {
+            self.more.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for item in &mut self.items {
+            if let Item::Widget { pod, .. } = item {
+                pod.lifecycle(ctx, event, data, env);
+            }
+        }
+        self.more.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for item in &mut self.items {
+            if let Item::Widget { pod, .. } = item {
+                pod.update(ctx, data, env);
+            }
+        }
+        self.more.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let spacing = self.spacing.resolve(env);
+        let child_bc = BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, bc.max().height));
+
+        let mut sizes = Vec::with_capacity(self.items.len());
+        for item in &mut self.items {
+            sizes.push(match item {
+                Item::Widget { pod, .. } => pod.layout(ctx, &child_bc, data, env),
+                Item::Separator => Size::new(SEPARATOR_WIDTH, 0.0),
+            });
+        }
+        let more_size = self.more.layout(ctx, &child_bc, data, env);
+
+        let total = sizes.iter().map(|size| size.width + spacing).sum::<f64>() - spacing;
+        self.overflowing = total > bc.max().width;
+        let available = if self.overflowing {
+            bc.max().width - more_size.width - spacing
+        } else {
+            f64::INFINITY
+        };
+
+        // show the items fitting in order, hiding all of them after the first which doesn't
+        self.visible.clear();
+        let mut x = 0.0;
+        for size in &sizes {
+            let fits = self.visible.last().copied().unwrap_or(true) && x + size.width <= available;
+            self.visible.push(fits);
+            if fits {
+                x += size.width + spacing;
+            }
+        }
+        // a group doesn't end right before the overflow button
+        for (index, item) in self.items.iter().enumerate().rev() {
+            if !self.visible[index] {
+                continue;
+            }
+            if let Item::Separator = item {
+                self.visible[index] = false;
+            } else {
+                break;
+            }
+        }
+
+        let mut overflowed = self.overflowed.borrow_mut();
+        overflowed.clear();
+        for (item, visible) in self.items.iter().zip(&self.visible) {
+            if let (
+                Item::Widget {
+                    entry: Some(entry), ..
+                },
+                false,
+            ) = (item, visible)
+            {
+                overflowed.push(entry.clone());
+            }
+        }
+        drop(overflowed);
+
+        let mut height = sizes
+            .iter()
+            .zip(&self.visible)
+            .filter(|(_, visible)| **visible)
+            .fold(0.0_f64, |height, (size, _)| height.max(size.height));
+        if self.overflowing {
+            height = height.max(more_size.height);
+        }
+        let height = height.max(bc.min().height).min(bc.max().height);
+
+        self.positions.clear();
+        let mut x = 0.0;
+        for ((item, size), visible) in self.items.iter_mut().zip(&sizes).zip(&self.visible) {
+            self.positions.push(x);
+            if !visible {
+                continue;
+            }
+            if let Item::Widget { pod, .. } = item {
+                pod.set_origin(ctx, Point::new(x, (height - size.height) / 2.0));
+            }
+            x += size.width + spacing;
+        }
+        if self.overflowing {
+            self.more
+                .set_origin(ctx, Point::new(x, (height - more_size.height) / 2.0));
+            x += more_size.width + spacing;
+        }
+
+        bc.constrain(Size::new((x - spacing).max(0.0), height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let height = ctx.size().height;
+        for (index, item) in self.items.iter_mut().enumerate() {
+            if !self.visible.get(index).copied().unwrap_or(true) {
+                continue;
+            }
+            match item {
+                Item::Widget { pod, .. } => pod.paint(ctx, data, env),
+                Item::Separator => {
+                    let x = self.positions[index] + SEPARATOR_WIDTH / 2.0;
+                    let line = Line::new((x, 4.0), (x, height - 4.0));
+                    ctx.stroke(line, &env.get(theme::BORDER_LIGHT), 1.0);
+                }
+            }
+        }
+        if self.overflowing {
+            self.more.paint(ctx, data, env);
+        }
+    }
+}
@@ -1,16 +1,24 @@
 use druid::commands::CLOSE_WINDOW;
+use druid::kurbo::BezPath;
 use druid::widget::prelude::*;
 use druid::widget::{Controller, Label, LabelText};
 use druid::{
-    Color, Data, Point, TimerToken, Vec2, Widget, WidgetExt, WindowConfig, WindowId, WindowLevel,
-    WindowSizePolicy,
+    theme, Color, Command, Data, KbKey, Point, Rect, SingleUse, TimerToken, Vec2, Widget, WidgetId,
+    WidgetPod, WindowConfig, WindowId, WindowLevel, WindowSizePolicy,
 };
+use std::cell::Cell;
 use std::time::{Duration, Instant};
 
+use crate::tooltip_host::{HostedTooltip, Snapshot, HAS_TOOLTIP_HOST, HIDE_TOOLTIP, SHOW_TOOLTIP};
 use crate::WidgetExt as _;
 
+thread_local! {
+    /// When the last tooltip was closed, used for the grace period.
+    static LAST_CLOSED: Cell<Option<Instant>> = Cell::new(None);
+}
+
 #[derive(Clone)]
-pub(crate) enum TooltipState {
+enum TooltipState {
     Off,
     Waiting {
         timer: TimerToken,
@@ -18,23 +26,242 @@ pub(crate) enum TooltipState {
         last_mouse_pos: Point,
     },
     Showing {
-        id: WindowId,
+        tooltip: OpenTooltip,
         // We store last_mouse_pos here because we seem to sometimes get a synthesized MouseMove
         // event after showing the tooltip (maybe because the mouse leaves the window?). By storing
         // the last mouse position, we can filter out these spurious moves.
         last_mouse_pos: Point,
     },
+    /// The mouse left the widget, the tooltip is closed when the timer fires.
+    Hiding {
+        tooltip: OpenTooltip,
+        timer: TimerToken,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum OpenTooltip {
+    Window(WindowId),
+    /// Shown by a [`TooltipHost`], for the widget with this id.
+    ///
+    /// [`TooltipHost`]: crate::TooltipHost
+    Hosted(WidgetId),
+}
+
+impl OpenTooltip {
+    fn close(self) -> Command {
+        match self {
+            OpenTooltip::Window(id) => CLOSE_WINDOW.to(id),
+            OpenTooltip::Hosted(owner) => HIDE_TOOLTIP.with(owner),
+        }
+    }
+}
+
+enum TooltipContent<T> {
+    Text(LabelText<T>),
+    Widget(Box<dyn Fn() -> Box<dyn Widget<T>>>),
 }
 
 /// A [`Controller`] responsible for listening to mouse hovers and launching tooltip windows.
 ///
+/// The tooltip is also shown when the widget gets keyboard focus, and hidden again when it loses
+/// focus or Escape is pressed.
+///
+/// If the widget is inside a [`TooltipHost`], the tooltip is shown by the host instead of in a
+/// new window.
+///
 /// Instead of constructing this widget explicitly, you probably want to use
 /// [`TooltipExt::tooltip`].
 ///
 /// [`Controller`]: druid::widget::Controller
+/// [`TooltipHost`]: crate::TooltipHost
 pub struct TooltipController<T> {
-    pub(crate) text: LabelText<T>,
-    pub(crate) state: TooltipState,
+    content: TooltipContent<T>,
+    state: TooltipState,
+    show_delay: Duration,
+    hide_delay: Duration,
+    grace_period: Duration,
+    placement: TooltipPlacement,
+    focused: bool,
+}
+
+impl<T: Data> TooltipController<T> {
+    /// Create a tooltip showing a text.
+    pub fn new(text: impl Into<LabelText<T>>) -> Self {
+        Self::from_content(TooltipContent::Text(text.into()))
+    }
+
+    /// Create a tooltip showing an arbitrary widget.
+    ///
+    /// `make_widget` is called every time the tooltip is opened.
+    pub fn widget<W: Widget<T> + 'static>(make_widget: impl Fn() -> W + 'static) -> Self {
+        Self::from_content(TooltipContent::Widget(Box::new(move || {
+            Box::new(make_widget())
+        })))
+    }
+
+    fn from_content(content: TooltipContent<T>) -> Self {
+        Self {
+            content,
+            state: TooltipState::Off,
+            show_delay: TOOLTIP_DELAY,
+            hide_delay: Duration::ZERO,
+            grace_period: TOOLTIP_GRACE_PERIOD,
+            placement: TooltipPlacement::default(),
+            focused: false,
+        }
+    }
+
+    /// Builder-style method for setting how long the mouse has to rest on the widget before the
+    /// tooltip is shown.
+    ///
+    /// For the non-builder varient, see [`set_show_delay`].
+    ///
+    /// [`set_show_delay`]: #method.set_show_delay
+    pub fn with_show_delay(mut self, delay: Duration) -> Self {
+        self.set_show_delay(delay);
+        self
+    }
+
+    /// Set how long the mouse has to rest on the widget before the tooltip is shown.
+    pub fn set_show_delay(&mut self, delay: Duration) {
+        self.show_delay = delay;
+    }
+
+    /// Builder-style method for setting how long the tooltip stays open after the mouse left the
+    /// widget. Defaults to zero.
+    ///
+    /// For the non-builder varient, see [`set_hide_delay`].
+    ///
+    /// [`set_hide_delay`]: #method.set_hide_delay
+    pub fn with_hide_delay(mut self, delay: Duration) -> Self {
+        self.set_hide_delay(delay);
+        self
+    }
+
+    /// Set how long the tooltip stays open after the mouse left the widget.
+    pub fn set_hide_delay(&mut self, delay: Duration) {
+        self.hide_delay = delay;
+    }
+
+    /// Builder-style method for setting the grace period.
+    ///
+    /// If the mouse enters this widget less than `grace_period` after any tooltip was closed,
+    /// the tooltip is shown immediately. This makes moving along a toolbar feel responsive.
+    ///
+    /// For the non-builder varient, see [`set_grace_period`].
+    ///
+    /// [`set_grace_period`]: #method.set_grace_period
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.set_grace_period(grace_period);
+        self
+    }
+
+    /// Set the grace period, see [`with_grace_period`].
+    ///
+    /// [`with_grace_period`]: #method.with_grace_period
+    pub fn set_grace_period(&mut self, grace_period: Duration) {
+        self.grace_period = grace_period;
+    }
+
+    /// Builder-style method for setting where the tooltip is shown.
+    ///
+    /// For the non-builder varient, see [`set_placement`].
+    ///
+    /// [`set_placement`]: #method.set_placement
+    pub fn with_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.set_placement(placement);
+        self
+    }
+
+    /// Set where the tooltip is shown.
+    pub fn set_placement(&mut self, placement: TooltipPlacement) {
+        self.placement = placement;
+    }
+
+    fn in_grace_period(&self) -> bool {
+        LAST_CLOSED.with(|closed| {
+            closed
+                .get()
+                .map_or(false, |closed| closed.elapsed() < self.grace_period)
+        })
+    }
+
+    fn open(
+        &mut self,
+        ctx: &mut EventCtx,
+        last_mouse_pos: Point,
+        data: &T,
+        env: &Env,
+    ) -> OpenTooltip {
+        let content: Box<dyn Widget<T>> = match &mut self.content {
+            TooltipContent::Text(text) => {
+                text.resolve(data, env);
+                // FIXME: we'd like to use the actual label text instead of
+                // resolving, but LabelText isn't Clone
+                Box::new(Label::new(text.display_text()))
+            }
+            TooltipContent::Widget(make_widget) => make_widget(),
+        };
+        let window = ctx.window();
+        let insets = window.content_insets();
+        let anchor = Rect::from_origin_size(ctx.window_origin(), ctx.size());
+        let bounds = Size::new(
+            window.get_size().width - insets.x_value(),
+            window.get_size().height - insets.y_value(),
+        );
+
+        if env.try_get(HAS_TOOLTIP_HOST).unwrap_or(false) {
+            let owner = ctx.widget_id();
+            let bubble = TooltipBubble::new(
+                Snapshot::new(data.clone(), content),
+                self.placement,
+                anchor,
+                last_mouse_pos,
+                bounds,
+                None,
+            );
+            ctx.submit_command(SHOW_TOOLTIP.with(SingleUse::new(HostedTooltip { owner, bubble })));
+            return OpenTooltip::Hosted(owner);
+        }
+
+        // The bubble moves the window to its final position once it knows its size.
+        let tooltip_position_in_window_coordinates = last_mouse_pos + TOOLTIP_OFFSET;
+        let bubble = TooltipBubble::new(
+            content.on_monitor(window),
+            self.placement,
+            anchor,
+            last_mouse_pos,
+            bounds,
+            Some(tooltip_position_in_window_coordinates),
+        );
+        let id = ctx.new_sub_window(
+            WindowConfig::default()
+                .show_titlebar(false)
+                .transparent(true)
+                .window_size_policy(WindowSizePolicy::Content)
+                .set_level(WindowLevel::Tooltip(ctx.window().clone()))
+                .set_position(tooltip_position_in_window_coordinates),
+            bubble,
+            data.clone(),
+            env.clone(),
+        );
+        OpenTooltip::Window(id)
+    }
+
+    fn wait(&self, ctx: &mut EventCtx, last_mouse_pos: Point) -> TooltipState {
+        TooltipState::Waiting {
+            timer: ctx.request_timer(self.show_delay),
+            last_mouse_move: Instant::now(),
+            last_mouse_pos,
+        }
+    }
+}
+
+/// The command closing `tooltip`, starting the grace period.
+fn close_tooltip(tooltip: OpenTooltip) -> Command {
+    LAST_CLOSED.with(|closed| closed.set(Some(Instant::now())));
+    tooltip.close()
 }
 
 impl<T: Data, W: Widget<T>> Controller<T, W> for TooltipController<T> {
@@ -50,35 +277,22 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for TooltipController<T> {
                     last_mouse_move: Instant::now(),
                     last_mouse_pos: ev.window_pos,
                 },
+                // a tooltip shown because of keyboard focus ignores the mouse
+                Event::MouseMove(_) if self.focused => self.state.clone(),
                 Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) => TooltipState::Off,
+                Event::KeyDown(key) if key.key == KbKey::Escape => TooltipState::Off,
                 Event::Timer(tok) if tok == &timer => {
                     ctx.set_handled();
                     let elapsed = Instant::now().duration_since(last_mouse_move);
-                    if elapsed > TOOLTIP_DELAY_CHECK {
-                        self.text.resolve(data, env);
-                        let tooltip_position_in_window_coordinates =
-                            last_mouse_pos + TOOLTIP_OFFSET;
-                        let win_id = ctx.new_sub_window(
-                            WindowConfig::default()
-                                .show_titlebar(false)
-                                .window_size_policy(WindowSizePolicy::Content)
-                                .set_level(WindowLevel::Tooltip(ctx.window().clone()))
-                                .set_position(tooltip_position_in_window_coordinates),
-                            // FIXME: we'd like to use the actual label text instead of
-                            // resolving, but LabelText isn't Clone
-                            Label::new(self.text.display_text())
-                                .border(TOOLTIP_BORDER_COLOR, TOOLTIP_BORDER_WIDTH)
-                                .on_monitor(ctx.window()),
-                            data.clone(),
-                            env.clone(),
-                        );
+                    if elapsed + TOOLTIP_DELAY_SLACK > self.show_delay {
+                        let tooltip = self.open(ctx, last_mouse_pos, data, env);
                         TooltipState::Showing {
-                            id: win_id,
+                            tooltip,
                             last_mouse_pos,
                         }
                     } else {
                         TooltipState::Waiting {
-                            timer: ctx.request_timer(TOOLTIP_DELAY - elapsed),
+                            timer: ctx.request_timer(self.show_delay - elapsed),
                             last_mouse_move,
                             last_mouse_pos,
                         }
@@ -87,33 +301,62 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for TooltipController<T> {
                 _ => self.state.clone(),
             },
             TooltipState::Off => match ev {
-                Event::MouseMove(ev) if ctx.is_hot() => TooltipState::Waiting {
-                    timer: ctx.request_timer(TOOLTIP_DELAY),
-                    last_mouse_move: Instant::now(),
-                    last_mouse_pos: ev.window_pos,
-                },
+                Event::MouseMove(ev) if ctx.is_hot() => {
+                    if self.in_grace_period() {
+                        let tooltip = self.open(ctx, ev.window_pos, data, env);
+                        TooltipState::Showing {
+                            tooltip,
+                            last_mouse_pos: ev.window_pos,
+                        }
+                    } else {
+                        self.wait(ctx, ev.window_pos)
+                    }
+                }
                 _ => TooltipState::Off,
             },
-            TooltipState::Showing { id, last_mouse_pos } => match ev {
+            TooltipState::Showing {
+                tooltip,
+                last_mouse_pos,
+            } => match ev {
                 Event::MouseMove(ev) if ctx.is_hot() => {
                     // This is annoying. On GTK, after showing a window we instantly get a new
                     // MouseMove event, with a mouse position that tends to be slightly different
                     // than the previous one. If we don't test the positions, this causes the
                     // tooltip to immediately close.
                     if (ev.window_pos - last_mouse_pos).hypot2() > 1.0 {
-                        ctx.submit_command(CLOSE_WINDOW.to(id));
-                        TooltipState::Waiting {
-                            timer: ctx.request_timer(TOOLTIP_DELAY),
-                            last_mouse_move: Instant::now(),
-                            last_mouse_pos: ev.window_pos,
-                        }
+                        ctx.submit_command(tooltip.close());
+                        self.wait(ctx, ev.window_pos)
                     } else {
                         self.state.clone()
                     }
                 }
-                Event::MouseMove(_) | Event::MouseUp(_) | Event::MouseDown(_) => {
-                    ctx.submit_command(CLOSE_WINDOW.to(id));
-                    self.state.clone()
+                Event::MouseUp(_) | Event::MouseDown(_) => {
+                    ctx.submit_command(close_tooltip(tooltip));
+                    TooltipState::Off
+                }
+                Event::KeyDown(key) if key.key == KbKey::Escape => {
+                    ctx.submit_command(close_tooltip(tooltip));
+                    TooltipState::Off
+                }
+                _ => self.state.clone(),
+            },
+            TooltipState::Hiding { tooltip, timer } => match ev {
+                Event::MouseMove(ev) if ctx.is_hot() => TooltipState::Showing {
+                    tooltip,
+                    last_mouse_pos: ev.window_pos,
+                },
+                Event::MouseUp(_) | Event::MouseDown(_) => {
+                    ctx.submit_command(close_tooltip(tooltip));
+                    TooltipState::Off
+                }
+                Event::Timer(tok) if tok == &timer => {
+                    ctx.set_handled();
+                    ctx.submit_command(close_tooltip(tooltip));
+                    TooltipState::Off
+                }
+                Event::KeyDown(key) if key.key == KbKey::Escape => {
+                    ctx.submit_command(close_tooltip(tooltip));
+                    TooltipState::Off
                 }
                 _ => self.state.clone(),
             },
@@ -129,21 +372,331 @@ impl<T: Data, W: Widget<T>> Controller<T, W> for TooltipController<T> {
         data: &T,
         env: &Env,
     ) {
-        if let LifeCycle::HotChanged(false) = ev {
-            if let TooltipState::Showing { id, .. } = self.state {
-                ctx.submit_command(CLOSE_WINDOW.to(id));
-                self.state = TooltipState::Off;
+        match ev {
+            LifeCycle::HotChanged(false) if !self.focused => {
+                if let TooltipState::Showing { tooltip, .. } = self.state {
+                    self.state = if self.hide_delay.is_zero() {
+                        ctx.submit_command(close_tooltip(tooltip));
+                        TooltipState::Off
+                    } else {
+                        TooltipState::Hiding {
+                            tooltip,
+                            timer: ctx.request_timer(self.hide_delay),
+                        }
+                    };
+                }
+            }
+            LifeCycle::FocusChanged(true) => {
+                self.focused = true;
+                // if the widget is hot it was most likely focused by a click, and the mouse
+                // already takes care of the tooltip
+                if !ctx.is_hot() && matches!(self.state, TooltipState::Off) {
+                    let below_widget = ctx.window_origin() + Vec2::new(0.0, ctx.size().height);
+                    self.state = TooltipState::Waiting {
+                        timer: ctx.request_timer(self.show_delay),
+                        last_mouse_move: Instant::now(),
+                        last_mouse_pos: below_widget,
+                    };
+                }
             }
+            LifeCycle::FocusChanged(false) => {
+                self.focused = false;
+                if !ctx.is_hot() {
+                    if let TooltipState::Showing { tooltip, .. }
+                    | TooltipState::Hiding { tooltip, .. } = self.state
+                    {
+                        ctx.submit_command(close_tooltip(tooltip));
+                    }
+                    self.state = TooltipState::Off;
+                }
+            }
+            _ => {}
         }
         child.lifecycle(ctx, ev, data, env);
     }
 }
 
 const TOOLTIP_DELAY: Duration = Duration::from_millis(350);
-const TOOLTIP_DELAY_CHECK: Duration = Duration::from_millis(320);
+// Timers may fire a little early, don't restart them for that.
+const TOOLTIP_DELAY_SLACK: Duration = Duration::from_millis(30);
+const TOOLTIP_GRACE_PERIOD: Duration = Duration::from_millis(500);
 const TOOLTIP_BORDER_COLOR: Color = Color::BLACK;
 const TOOLTIP_BORDER_WIDTH: f64 = 1.0;
+const TOOLTIP_ARROW_SIZE: f64 = 6.0;
 // It looks better if we don't put the tooltip *right* on the tip of the mouse,
 // because the mouse obstructs it.
 // FIXME: this should depend on the actual cursor size.
 const TOOLTIP_OFFSET: Vec2 = Vec2::new(15.0, 15.0);
+
+/// Where a tooltip is shown, relative to the widget it belongs to.
+///
+/// All placements except [`FollowCursor`] draw an arrow pointing at the widget. If the tooltip
+/// doesn't fit into the window, it is flipped to the opposite side.
+///
+/// [`FollowCursor`]: TooltipPlacement::FollowCursor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TooltipPlacement {
+    /// Next to the mouse cursor.
+    FollowCursor,
+    /// Centered above the widget.
+    Above,
+    /// Centered below the widget.
+    Below,
+    /// Above the widget, aligned with its left edge.
+    TopLeft,
+    /// Above the widget, aligned with its right edge.
+    TopRight,
+    /// Below the widget, aligned with its left edge.
+    BottomLeft,
+    /// Below the widget, aligned with its right edge.
+    BottomRight,
+}
+
+impl Default for TooltipPlacement {
+    fn default() -> Self {
+        TooltipPlacement::FollowCursor
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ArrowSide {
+    Top,
+    Bottom,
+}
+
+impl TooltipPlacement {
+    fn is_above(self) -> bool {
+        matches!(
+            self,
+            TooltipPlacement::Above | TooltipPlacement::TopLeft | TooltipPlacement::TopRight
+        )
+    }
+
+    fn flip_vertical(self) -> Self {
+        match self {
+            TooltipPlacement::FollowCursor => TooltipPlacement::FollowCursor,
+            TooltipPlacement::Above => TooltipPlacement::Below,
+            TooltipPlacement::Below => TooltipPlacement::Above,
+            TooltipPlacement::TopLeft => TooltipPlacement::BottomLeft,
+            TooltipPlacement::TopRight => TooltipPlacement::BottomRight,
+            TooltipPlacement::BottomLeft => TooltipPlacement::TopLeft,
+            TooltipPlacement::BottomRight => TooltipPlacement::TopRight,
+        }
+    }
+
+    fn flip_horizontal(self) -> Self {
+        match self {
+            TooltipPlacement::TopLeft => TooltipPlacement::TopRight,
+            TooltipPlacement::TopRight => TooltipPlacement::TopLeft,
+            TooltipPlacement::BottomLeft => TooltipPlacement::BottomRight,
+            TooltipPlacement::BottomRight => TooltipPlacement::BottomLeft,
+            other => other,
+        }
+    }
+
+    /// The origin of a tooltip of `size`, in window coordinates.
+    fn origin(self, anchor: Rect, cursor: Point, size: Size) -> Point {
+        let center_x = anchor.center().x - size.width / 2.0;
+        match self {
+            TooltipPlacement::FollowCursor => cursor + TOOLTIP_OFFSET,
+            TooltipPlacement::Above => Point::new(center_x, anchor.y0 - size.height),
+            TooltipPlacement::Below => Point::new(center_x, anchor.y1),
+            TooltipPlacement::TopLeft => Point::new(anchor.x0, anchor.y0 - size.height),
+            TooltipPlacement::TopRight => {
+                Point::new(anchor.x1 - size.width, anchor.y0 - size.height)
+            }
+            TooltipPlacement::BottomLeft => Point::new(anchor.x0, anchor.y1),
+            TooltipPlacement::BottomRight => Point::new(anchor.x1 - size.width, anchor.y1),
+        }
+    }
+
+    /// Like [`origin`], but flipped to the other side when the tooltip leaves `bounds`.
+    ///
+    /// [`origin`]: #method.origin
+    fn fit(self, anchor: Rect, cursor: Point, size: Size, bounds: Size) -> (Self, Point) {
+        let mut placement = self;
+        let mut origin = placement.origin(anchor, cursor, size);
+
+        if placement == TooltipPlacement::FollowCursor {
+            if origin.x + size.width > bounds.width {
+                origin.x = cursor.x - size.width;
+            }
+            if origin.y + size.height > bounds.height {
+                origin.y = cursor.y - size.height;
+            }
+            return (placement, origin);
+        }
+
+        if origin.y < 0.0 || origin.y + size.height > bounds.height {
+            let flipped = placement.flip_vertical();
+            let flipped_origin = flipped.origin(anchor, cursor, size);
+            if flipped_origin.y >= 0.0 && flipped_origin.y + size.height <= bounds.height {
+                placement = flipped;
+                origin = flipped_origin;
+            }
+        }
+        if origin.x < 0.0 || origin.x + size.width > bounds.width {
+            let flipped = placement.flip_horizontal();
+            let flipped_origin = flipped.origin(anchor, cursor, size);
+            if flipped_origin.x >= 0.0 && flipped_origin.x + size.width <= bounds.width {
+                placement = flipped;
+                origin = flipped_origin;
+            }
+        }
+        // keep centered tooltips inside the window, the arrow still points at the widget
+        if matches!(placement, TooltipPlacement::Above | TooltipPlacement::Below) {
+            origin.x = origin.x.min(bounds.width - size.width).max(0.0);
+        }
+        (placement, origin)
+    }
+}
+
+/// The content of a tooltip: draws the border and the arrow, and moves the tooltip window to
+/// where the placement wants it.
+pub(crate) struct TooltipBubble<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    placement: TooltipPlacement,
+    /// The widget the tooltip belongs to, in the coordinates of its window.
+    anchor: Rect,
+    cursor: Point,
+    /// The content size of the window of the widget.
+    bounds: Size,
+    /// The current position of the tooltip window, relative to the window of the widget, or
+    /// `None` if the bubble is not in its own window.
+    position: Option<Point>,
+    /// Where the tooltip window should be, computed in layout.
+    target: Point,
+    /// The side and horizontal position of the arrow, if any.
+    arrow: Option<(ArrowSide, f64)>,
+}
+
+impl<T: Data> TooltipBubble<T> {
+    pub(crate) fn new(
+        inner: impl Widget<T> + 'static,
+        placement: TooltipPlacement,
+        anchor: Rect,
+        cursor: Point,
+        bounds: Size,
+        window_position: Option<Point>,
+    ) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            placement,
+            anchor,
+            cursor,
+            bounds,
+            position: window_position,
+            target: window_position.unwrap_or(cursor),
+            arrow: None,
+        }
+    }
+
+    /// Where the bubble wants to be, in the coordinates of the window of the widget.
+    ///
+    /// Only valid after layout.
+    pub(crate) fn target(&self) -> Point {
+        self.target
+    }
+}
+
+impl<T: Data> Widget<T> for TooltipBubble<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let (LifeCycle::Size(_), Some(position)) = (event, self.position) {
+            // we only know where the window is relative to the window of the widget, so move it
+            // by the difference instead of setting an absolute position
+            let window = ctx.window();
+            window.set_position(window.get_position() + (self.target - position));
+            self.position = Some(self.target);
+        }
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let arrow_size = if self.placement == TooltipPlacement::FollowCursor {
+            0.0
+        } else {
+            TOOLTIP_ARROW_SIZE
+        };
+        let border = TOOLTIP_BORDER_WIDTH;
+        let child_bc = bc.shrink((2.0 * border, 2.0 * border + arrow_size));
+        let child_size = self.inner.layout(ctx, &child_bc, data, env);
+        let size = Size::new(
+            child_size.width + 2.0 * border,
+            child_size.height + 2.0 * border + arrow_size,
+        );
+
+        let (placement, origin) = self
+            .placement
+            .fit(self.anchor, self.cursor, size, self.bounds);
+        self.arrow = if placement == TooltipPlacement::FollowCursor {
+            None
+        } else {
+            let side = if placement.is_above() {
+                ArrowSide::Bottom
+            } else {
+                ArrowSide::Top
+            };
+            let min_x = border + TOOLTIP_ARROW_SIZE;
+            let x = (self.anchor.center().x - origin.x)
+                .min(size.width - min_x)
+                .max(min_x);
+            Some((side, x))
+        };
+
+        let child_y = match self.arrow {
+            Some((ArrowSide::Top, _)) => border + arrow_size,
+            _ => border,
+        };
+        self.inner.set_origin(ctx, Point::new(border, child_y));
+        self.target = origin;
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let half = TOOLTIP_BORDER_WIDTH / 2.0;
+        let mut body = size.to_rect().inset(-half);
+        let mut path = BezPath::new();
+        match self.arrow {
+            Some((ArrowSide::Top, x)) => {
+                body.y0 += TOOLTIP_ARROW_SIZE;
+                path.move_to((body.x0, body.y0));
+                path.line_to((x - TOOLTIP_ARROW_SIZE, body.y0));
+                path.line_to((x, body.y0 - TOOLTIP_ARROW_SIZE));
+                path.line_to((x + TOOLTIP_ARROW_SIZE, body.y0));
+                path.line_to((body.x1, body.y0));
+                path.line_to((body.x1, body.y1));
+                path.line_to((body.x0, body.y1));
+            }
+            Some((ArrowSide::Bottom, x)) => {
+                body.y1 -= TOOLTIP_ARROW_SIZE;
+                path.move_to((body.x0, body.y0));
+                path.line_to((body.x1, body.y0));
+                path.line_to((body.x1, body.y1));
+                path.line_to((x + TOOLTIP_ARROW_SIZE, body.y1));
+                path.line_to((x, body.y1 + TOOLTIP_ARROW_SIZE));
+                path.line_to((x - TOOLTIP_ARROW_SIZE, body.y1));
+                path.line_to((body.x0, body.y1));
+            }
+            None => {
+                path.move_to((body.x0, body.y0));
+                path.line_to((body.x1, body.y0));
+                path.line_to((body.x1, body.y1));
+                path.line_to((body.x0, body.y1));
+            }
+        }
+        path.close_path();
+
+        ctx.fill(&path, &env.get(theme::WINDOW_BACKGROUND_COLOR));
+        ctx.stroke(&path, &TOOLTIP_BORDER_COLOR, TOOLTIP_BORDER_WIDTH);
+        self.inner.paint(ctx, data, env);
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A layer showing the tooltips of a whole window.
+
+use druid::widget::prelude::*;
+use druid::{Data, Key, Point, Selector, SingleUse, WidgetId, WidgetPod};
+
+use crate::tooltip::TooltipBubble;
+
+/// Set to `true` in the environment of the children of a [`TooltipHost`].
+pub(crate) const HAS_TOOLTIP_HOST: Key<bool> = Key::new("druid-widget-nursery.tooltip.has-host");
+
+/// Show a tooltip, replacing the one currently shown.
+pub(crate) const SHOW_TOOLTIP: Selector<SingleUse<HostedTooltip>> =
+    Selector::new("druid-widget-nursery.tooltip.show");
+
+/// Hide the tooltip of the widget with the given id, if it is still shown.
+pub(crate) const HIDE_TOOLTIP: Selector<WidgetId> =
+    Selector::new("druid-widget-nursery.tooltip.hide");
+
+pub(crate) struct HostedTooltip {
+    pub(crate) owner: WidgetId,
+    pub(crate) bubble: TooltipBubble<()>,
+}
+
+/// A widget showing the tooltips of all [`TooltipController`]s below it.
+///
+/// Put it at the root of a window. Instead of opening a window per tooltip, the controllers send
+/// their tooltips to the host, which paints them on top of everything else. Only one tooltip is
+/// visible at a time: showing a tooltip replaces the previous one.
+///
+/// The tooltip content gets a snapshot of the data of its widget, taken when it is shown.
+///
+/// [`TooltipController`]: crate::TooltipController
+pub struct TooltipHost<T> {
+    inner: WidgetPod<T, Box<dyn Widget<T>>>,
+    tooltip: Option<(WidgetId, WidgetPod<(), TooltipBubble<()>>)>,
+    /// The last environment we got, and the one we pass to our children.
+    env: Option<(Env, Env)>,
+}
+
+impl<T: Data> TooltipHost<T> {
+    pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            inner: WidgetPod::new(Box::new(inner)),
+            tooltip: None,
+            env: None,
+        }
+    }
+
+    /// Returns `true` if a tooltip is currently shown.
+    pub fn is_showing(&self) -> bool {
+        self.tooltip.is_some()
+    }
+
+    fn child_env(&mut self, env: &Env) -> Env {
+        match &self.env {
+            Some((last, child_env)) if last.same(env) => child_env.clone(),
+            _ => {
+                let child_env = env.clone().adding(HAS_TOOLTIP_HOST, true);
+                self.env = Some((env.clone(), child_env.clone()));
+                child_env
+            }
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for TooltipHost<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(tooltip) = cmd.get(SHOW_TOOLTIP).and_then(SingleUse::take) {
+                self.tooltip = Some((tooltip.owner, WidgetPod::new(tooltip.bubble)));
+                ctx.children_changed();
+                ctx.set_handled();
+                return;
+            }
+            if let Some(owner) = cmd.get(HIDE_TOOLTIP) {
+                if matches!(&self.tooltip, Some((current, _)) if current == owner) {
+                    self.tooltip = None;
+                    ctx.children_changed();
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let env = self.child_env(env);
+        self.inner.event(ctx, event, data, &env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.inner.lifecycle(ctx, event, data, &env);
+        if let Some((_, tooltip)) = &mut self.tooltip {
+            tooltip.lifecycle(ctx, event, &(), &env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.inner.update(ctx, data, &env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let env = self.child_env(env);
+        let size = self.inner.layout(ctx, bc, data, &env);
+        self.inner.set_origin(ctx, Point::ORIGIN);
+
+        if let Some((_, tooltip)) = &mut self.tooltip {
+            tooltip.layout(ctx, &bc.loosen(), &(), &env);
+            let origin = tooltip.widget().target() - ctx.window_origin().to_vec2();
+            tooltip.set_origin(ctx, origin);
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.inner.paint(ctx, data, &env);
+        if let Some((_, tooltip)) = &mut self.tooltip {
+            tooltip.paint(ctx, &(), &env);
+        }
+    }
+}
+
+/// Shows a widget with a fixed copy of its data.
+pub(crate) struct Snapshot<T> {
+    data: T,
+    inner: Box<dyn Widget<T>>,
+}
+
+impl<T> Snapshot<T> {
+    pub(crate) fn new(data: T, inner: Box<dyn Widget<T>>) -> Self {
+        Self { data, inner }
+    }
+}
+
+impl<T: Data> Widget<()> for Snapshot<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut (), env: &Env) {
+        self.inner.event(ctx, event, &mut self.data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &(), env: &Env) {
+        self.inner.lifecycle(ctx, event, &self.data, env);
+    }
+
+    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &(), _data: &(), _env: &Env) {}
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &(), env: &Env) -> Size {
+        self.inner.layout(ctx, bc, &self.data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &(), env: &Env) {
+        self.inner.paint(ctx, &self.data, env);
+    }
+}
@@ -19,15 +19,16 @@ use std::fmt::Display;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use druid::kurbo::{BezPath, Size};
-use druid::piet::{LineCap, LineJoin, RenderContext, StrokeStyle};
+use druid::kurbo::Size;
 use druid::widget::Label;
 use druid::{theme, Lens, LensExt};
 use druid::{
-    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    Point, Selector, UpdateCtx, Widget, WidgetId, WidgetPod,
+    BoxConstraints, Data, Env, Event, EventCtx, KbKey, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, Rect, Selector, UpdateCtx, Widget, WidgetId, WidgetPod,
 };
 
+use crate::accessibility::{AccessState, Accessible, Role};
+use crate::focus_ring::{self, FocusVisible};
 use crate::selectors;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -192,74 +193,68 @@ where
     }
 }
 
-// The default opener if none is passed to the Tree builder.
-struct Wedge<T, L>
+// The default opener if none is passed to the Tree builder, showing a [`Wedge`] for branches.
+//
+// [`Wedge`]: crate::Wedge
+struct TreeWedge<T, L>
 where
     T: TreeNode,
     L: Lens<T, bool>,
 {
     expand_lens: L,
+    wedge: WidgetPod<bool, crate::Wedge>,
     phantom: PhantomData<T>,
 }
 
-impl<T: TreeNode, L: Lens<T, bool>> Widget<T> for Wedge<T, L> {
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+impl<T: TreeNode, L: Lens<T, bool>> Widget<T> for TreeWedge<T, L> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
         match event {
             Event::Command(cmd) if cmd.is(TREE_ACTIVATE_NODE) => {
                 self.expand_lens.put(data, !self.expand_lens.get(data));
                 ctx.set_handled();
             }
-            _ => (),
+            _ => {
+                // The Opener handles the clicks, the wedge only gets the events for its hot
+                // state and its animation, so its changes are dropped.
+                let mut expanded = self.expand_lens.get(data);
+                self.wedge.event(ctx, event, &mut expanded, env);
+            }
         }
     }
 
-    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.wedge
+            .lifecycle(ctx, event, &self.expand_lens.get(data), env);
+    }
 
-    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {}
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.wedge.update(ctx, &self.expand_lens.get(data), env);
+    }
 
-    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
-        let size = env.get(theme::BASIC_WIDGET_HEIGHT);
-        bc.constrain(Size::new(size, size))
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.wedge.layout(ctx, bc, &self.expand_lens.get(data), env);
+        self.wedge.set_origin(ctx, Point::ORIGIN);
+        size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        if !data.is_branch() {
-            return;
-        }
-        let stroke_color = if ctx.is_hot() {
-            env.get(theme::FOREGROUND_LIGHT)
-        } else {
-            env.get(theme::FOREGROUND_DARK)
-        };
-
-        // Paint the opener
-        let mut path = BezPath::new();
-        if self.expand_lens.get(data) {
-            // expanded: 'V' shape
-            path.move_to((5.0, 7.0));
-            path.line_to((9.0, 13.0));
-            path.line_to((13.0, 7.0));
-        } else {
-            // collapsed: '>' shape
-            path.move_to((7.0, 5.0));
-            path.line_to((13.0, 9.0));
-            path.line_to((7.0, 13.0));
+        if data.is_branch() {
+            self.wedge.paint(ctx, &self.expand_lens.get(data), env);
         }
-        let style = StrokeStyle::new()
-            .line_cap(LineCap::Round)
-            .line_join(LineJoin::Round);
-
-        ctx.stroke_styled(path, &stroke_color, 2.5, &style);
     }
 }
 
 type TreeItemFactory<T> = Arc<dyn Fn() -> Box<dyn Widget<T>>>;
 type OpenerFactory<T> = dyn Fn() -> Box<dyn Widget<T>>;
 
-fn make_wedge<T: TreeNode, L: Lens<T, bool>>(expand_lens: L) -> Wedge<T, L> {
-    Wedge {
-        phantom: PhantomData,
+fn make_wedge<T: TreeNode, L: Lens<T, bool>>(
+    expand_lens: L,
+    wedge: crate::Wedge,
+) -> TreeWedge<T, L> {
+    TreeWedge {
         expand_lens,
+        wedge: WidgetPod::new(wedge),
+        phantom: PhantomData,
     }
 }
 
@@ -285,6 +280,7 @@ where
     /// The user must provide a Lens<T, bool> that tells if
     /// the node is expanded or not.
     expand_lens: L,
+    focus: FocusVisible,
 }
 
 impl<T: TreeNode, L: Lens<T, bool> + Clone> TreeNodeWidget<T, L> {
@@ -306,7 +302,32 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone> TreeNodeWidget<T, L> {
             make_widget,
             make_opener,
             expand_lens,
+            focus: FocusVisible::new(),
+        }
+    }
+
+    /// Show or hide the children nodes after the node was expanded or collapsed.
+    fn expanded_changed(&mut self, ctx: &mut EventCtx, data: &T) {
+        let cmd: Selector;
+        if self.expand_lens.get(data) {
+            cmd = TREE_CHILD_SHOW;
+            // create child widgets if needed.
+            if self.update_children(data) {
+                // New children were created, inform the context.
+                ctx.children_changed();
+            }
+        } else {
+            cmd = TREE_CHILD_HIDE;
+        };
+        for child_widget_node in self.children.iter_mut() {
+            ctx.submit_command(cmd.to(child_widget_node.id()))
         }
+        ctx.request_layout();
+    }
+
+    /// The row of the node, with its opener and its widget but not its children.
+    fn row_rect(&self) -> Rect {
+        self.opener.layout_rect().union(self.widget.layout_rect())
     }
 
     /// Expand or collapse the node.
@@ -341,6 +362,30 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone> Widget<T> for TreeNodeWidget<T, L> {
         //     Event::MouseMove(_) => (),
         //     _ => eprintln!("{:?} {:?}", ctx.widget_id(), event),
         // }
+        self.focus.event(ctx, event);
+        match event {
+            Event::MouseDown(mouse) if self.row_rect().contains(mouse.pos) => {
+                ctx.request_focus();
+            }
+            Event::KeyDown(key) if ctx.is_focused() && data.is_branch() => {
+                // Enter and Space toggle the node, the arrows open and close it
+                let expanded = self.expand_lens.get(data);
+                let expand = match &key.key {
+                    KbKey::Enter => !expanded,
+                    KbKey::Character(c) if c == " " => !expanded,
+                    KbKey::ArrowRight => true,
+                    KbKey::ArrowLeft => false,
+                    _ => expanded,
+                };
+                if expand != expanded {
+                    self.expand_lens.put(data, expand);
+                    self.expanded_changed(ctx, data);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            _ => {}
+        }
         let event = match event {
             Event::Notification(notif) if notif.is(TREE_OPEN) => {
                 if data.is_branch() {
@@ -443,23 +488,7 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone> Widget<T> for TreeNodeWidget<T, L> {
             if expanded != before {
                 // The opener widget has decided to change the expanded/collapsed state of the node,
                 // handle it by expanding/collapsing children nodes as required.
-
-                let cmd: Selector;
-                if expanded {
-                    cmd = TREE_CHILD_SHOW;
-                    // create child widgets if needed.
-                    if self.update_children(data) {
-                        // New children were created, inform the context.
-                        ctx.children_changed();
-                    }
-                } else {
-                    cmd = TREE_CHILD_HIDE;
-                    // self.children = vec![];
-                };
-                for child_widget_node in self.children.iter_mut() {
-                    ctx.submit_command(cmd.to(child_widget_node.id()))
-                }
-                ctx.request_layout();
+                self.expanded_changed(ctx, data);
             }
             // Forward to children nodes
             if event.should_propagate_to_hidden() {
@@ -497,6 +526,10 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone> Widget<T> for TreeNodeWidget<T, L> {
                 return self.children[idx].lifecycle(ctx, event, data.get_child(idx), env);
             }
         }
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        self.focus.lifecycle(ctx, event);
         self.opener.lifecycle(ctx, event, data, env);
         self.widget.lifecycle(ctx, event, data, env);
         if data.is_branch() & (event.should_propagate_to_hidden() | self.expand_lens.get(data)) {
@@ -614,6 +647,9 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone> Widget<T> for TreeNodeWidget<T, L> {
         }
         self.opener.paint(ctx, data, env);
         self.widget.paint(ctx, data, env);
+        if self.focus.is_visible() {
+            focus_ring::paint(ctx, self.row_rect(), 2.0, env);
+        }
         if data.is_branch() & self.expand_lens.get(data) {
             for (index, child_widget_node) in self.children.iter_mut().enumerate() {
                 let child_tree_node = data.get_child(index);
@@ -632,8 +668,9 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone + 'static> Tree<T, L> {
     ) -> Self {
         let make_widget: TreeItemFactory<T> = Arc::new(move || Box::new(make_widget()));
         let el = expand_lens.clone();
-        let make_opener: Arc<Box<OpenerFactory<T>>> =
-            Arc::new(Box::new(move || Box::new(make_wedge(el.clone()))));
+        let make_opener: Arc<Box<OpenerFactory<T>>> = Arc::new(Box::new(move || {
+            Box::new(make_wedge(el.clone(), crate::Wedge::new()))
+        }));
         Tree {
             root_node: WidgetPod::new(TreeNodeWidget::new(
                 make_widget,
@@ -658,6 +695,14 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone + 'static> Tree<T, L> {
         self
     }
 
+    /// Pass a closure to customize the default opener, like with [`Wedge::glyphs`].
+    ///
+    /// [`Wedge::glyphs`]: crate::Wedge::glyphs
+    pub fn with_wedge(self, closure: impl Fn() -> crate::Wedge + 'static) -> Self {
+        let el = self.root_node.widget().expand_lens.clone();
+        self.with_opener(move || make_wedge(el.clone(), closure()))
+    }
+
     fn get_chroot_from<'a>(
         widget: &'a mut WidgetPod<T, TreeNodeWidget<T, L>>,
         data: &'a T,
@@ -680,8 +725,9 @@ impl<T: TreeNode + Display, L: Lens<T, bool> + Clone + 'static> Tree<T, L> {
         let make_widget: TreeItemFactory<T> =
             Arc::new(|| Box::new(Label::dynamic(|data: &T, _env| data.to_string())));
         let el = expand_lens.clone();
-        let make_opener: Arc<Box<OpenerFactory<T>>> =
-            Arc::new(Box::new(move || Box::new(make_wedge(el.clone()))));
+        let make_opener: Arc<Box<OpenerFactory<T>>> = Arc::new(Box::new(move || {
+            Box::new(make_wedge(el.clone(), crate::Wedge::new()))
+        }));
         Tree {
             root_node: WidgetPod::new(TreeNodeWidget::new(
                 make_widget,
@@ -768,3 +814,18 @@ impl<T: TreeNode, L: Lens<T, bool> + Clone + 'static> Widget<T> for Tree<T, L> {
         root.paint(ctx, chroot_data, env);
     }
 }
+
+impl<T: TreeNode, L: Lens<T, bool> + Clone + 'static> Accessible<T> for Tree<T, L> {
+    fn role(&self) -> Role {
+        Role::Tree
+    }
+
+    /// The state of the root node.
+    fn accessible_state(&self, data: &T) -> AccessState {
+        let root = self.root_node.widget();
+        AccessState {
+            expanded: data.is_branch().then(|| root.expand_lens.get(data)),
+            ..AccessState::default()
+        }
+    }
+}
@@ -1,18 +1,86 @@
-use druid::kurbo::{BezPath, Size};
+//! The arrow-like icon marking whether something is expanded or collapsed.
+//!
+//! The size and the colors can be set in the [`Env`] with [`SIZE`], [`COLOR`] and [`HOT_COLOR`].
+//! Without them the wedge is as high as a basic widget, and uses the foreground colors of the
+//! theme.
+//!
+//! [`Env`]: druid::Env
+
+use std::f64::consts::FRAC_PI_2;
+
+use druid::kurbo::{Affine, BezPath, Size};
 use druid::piet::{LineCap, LineJoin, RenderContext, StrokeStyle};
-use druid::theme;
 use druid::{
-    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
-    Widget,
+    theme, ArcStr, BoxConstraints, Color, Env, Event, EventCtx, Key, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, TextLayout, UpdateCtx, Widget,
 };
 
+use crate::animation::{Animated, AnimationCurve};
+
+/// The width and the height of a [`Wedge`].
+pub const SIZE: Key<f64> = Key::new("druid-widget-nursery.wedge.size");
+/// The color of a [`Wedge`].
+pub const COLOR: Key<Color> = Key::new("druid-widget-nursery.wedge.color");
+/// The color of a [`Wedge`] under the mouse.
+pub const HOT_COLOR: Key<Color> = Key::new("druid-widget-nursery.wedge.hot-color");
+
+/// Text glyphs shown instead of the painted chevron.
+struct Glyphs {
+    collapsed: TextLayout<ArcStr>,
+    expanded: TextLayout<ArcStr>,
+    /// The color the layouts were built with.
+    color: Option<Color>,
+}
+
 /// Wedge is an arbitrary name for the arrow-like icon marking whether a node is expanded or collapsed.
-pub struct Wedge;
+///
+/// By default it is a painted chevron, turning smoothly between pointing right and pointing down.
+/// With [`glyphs`] it shows one of two texts instead, like emojis or symbols from an icon font.
+///
+/// [`glyphs`]: #method.glyphs
+pub struct Wedge {
+    glyphs: Option<Glyphs>,
+    /// 0 for collapsed, 1 for expanded.
+    openness: Animated<f64>,
+}
 
 // Is "Chevron" a better name?
 impl Wedge {
     pub fn new() -> Self {
-        Wedge {}
+        Wedge {
+            glyphs: None,
+            openness: Animated::new(0.0)
+                .duration(0.15)
+                .curve(AnimationCurve::EASE_OUT),
+        }
+    }
+
+    /// A wedge showing `collapsed` or `expanded` as text instead of painting a chevron.
+    pub fn glyphs(collapsed: impl Into<ArcStr>, expanded: impl Into<ArcStr>) -> Self {
+        Wedge {
+            glyphs: Some(Glyphs {
+                collapsed: TextLayout::from_text(collapsed),
+                expanded: TextLayout::from_text(expanded),
+                color: None,
+            }),
+            ..Self::new()
+        }
+    }
+
+    /// Builder-style method for turning the chevron at once instead of animating it.
+    pub fn without_animation(mut self) -> Self {
+        self.openness.set_duration(0.0);
+        self
+    }
+
+    fn color(ctx: &PaintCtx, env: &Env) -> Color {
+        if ctx.is_hot() {
+            env.try_get(HOT_COLOR)
+                .unwrap_or_else(|_| env.get(theme::FOREGROUND_LIGHT))
+        } else {
+            env.try_get(COLOR)
+                .unwrap_or_else(|_| env.get(theme::FOREGROUND_DARK))
+        }
     }
 }
 
@@ -40,51 +108,110 @@ impl Widget<bool> for Wedge {
                     ctx.request_paint();
                 }
             }
+            Event::AnimFrame(nanos) if self.openness.animating() => {
+                self.openness.update(ctx, *nanos);
+                ctx.request_paint();
+            }
             _ => (),
         }
     }
 
-    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &bool, _env: &Env) {
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        expanded: &bool,
+        _env: &Env,
+    ) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.openness
+                    .jump_to_value(if *expanded { 1.0 } else { 0.0 });
+            }
+            LifeCycle::HotChanged(_) => ctx.request_paint(),
+            _ => (),
+        }
     }
 
-    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &bool, _data: &bool, _env: &Env) {}
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &bool, expanded: &bool, _env: &Env) {
+        if old_data != expanded {
+            self.openness
+                .animate(ctx, if *expanded { 1.0 } else { 0.0 });
+        }
+        if ctx.env_key_changed(&SIZE)
+            || self.glyphs.as_ref().map_or(false, |glyphs| {
+                glyphs.collapsed.needs_rebuild_after_update(ctx)
+                    || glyphs.expanded.needs_rebuild_after_update(ctx)
+            })
+        {
+            ctx.request_layout();
+        }
+        if ctx.env_key_changed(&COLOR) || ctx.env_key_changed(&HOT_COLOR) {
+            ctx.request_paint();
+        }
+    }
 
     fn layout(
         &mut self,
-        _ctx: &mut LayoutCtx,
+        ctx: &mut LayoutCtx,
         bc: &BoxConstraints,
         _data: &bool,
         env: &Env,
     ) -> Size {
-        let size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let size = env
+            .try_get(SIZE)
+            .unwrap_or_else(|_| env.get(theme::BASIC_WIDGET_HEIGHT));
+        if let Some(glyphs) = &mut self.glyphs {
+            glyphs.collapsed.set_text_size(size * 0.75);
+            glyphs.expanded.set_text_size(size * 0.75);
+            glyphs.collapsed.rebuild_if_needed(ctx.text(), env);
+            glyphs.expanded.rebuild_if_needed(ctx.text(), env);
+        }
         bc.constrain(Size::new(size, size))
     }
 
-    fn paint(&mut self, ctx: &mut PaintCtx, expanded: &bool, env: &Env) {
-        let y_offset = ((ctx.size().height - 8.0) / 2.0).floor();
-        let stroke_color = if ctx.is_hot() {
-            env.get(theme::FOREGROUND_LIGHT)
-        } else {
-            env.get(theme::FOREGROUND_DARK)
-        };
+    fn paint(&mut self, ctx: &mut PaintCtx, _expanded: &bool, env: &Env) {
+        let color = Self::color(ctx, env);
+        let size = ctx.size();
+        let openness = self.openness.get();
 
-        // Paint the wedge
-        let mut path = BezPath::new();
-        if *expanded {
-            // expanded: 'V' shape
-            path.move_to((5.0, y_offset + 2.0));
-            path.line_to((9.0, y_offset + 8.0));
-            path.line_to((13.0, y_offset + 2.0));
-        } else {
-            // collapsed: '>' shape
-            path.move_to((7.0, y_offset));
-            path.line_to((13.0, y_offset + 4.0));
-            path.line_to((7.0, y_offset + 8.0));
+        if let Some(glyphs) = &mut self.glyphs {
+            if glyphs.color.as_ref() != Some(&color) {
+                glyphs.collapsed.set_text_color(color);
+                glyphs.expanded.set_text_color(color);
+                glyphs.color = Some(color);
+            }
+            let glyph = if openness < 0.5 {
+                &mut glyphs.collapsed
+            } else {
+                &mut glyphs.expanded
+            };
+            glyph.rebuild_if_needed(ctx.text(), env);
+            let glyph_size = glyph.size();
+            let origin = Point::new(
+                (size.width - glyph_size.width) / 2.0,
+                (size.height - glyph_size.height) / 2.0,
+            );
+            glyph.draw(ctx, origin);
+            return;
         }
+
+        // Paint the wedge as '>', and turn it down while it opens. It is drawn for a wedge of 18
+        // by 18, around its center, and scaled.
+        let scale = size.width.min(size.height) / 18.0;
+        let center = Point::new(size.width / 2.0, size.height / 2.0);
+        let mut path = BezPath::new();
+        path.move_to((-3.0, -4.0));
+        path.line_to((3.0, 0.0));
+        path.line_to((-3.0, 4.0));
+        let transform = Affine::translate(center.to_vec2())
+            * Affine::rotate(openness * FRAC_PI_2)
+            * Affine::scale(scale);
+
         let style = StrokeStyle::new()
             .line_cap(LineCap::Round)
             .line_join(LineJoin::Round);
 
-        ctx.stroke_styled(path, &stroke_color, 2.5, &style);
+        ctx.stroke_styled(transform * path, &color, 2.5 * scale, &style);
     }
 }
@@ -1,11 +1,26 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
 use druid::widget::prelude::*;
-use druid::widget::{ControllerHost, LabelText};
-use druid::{Point, Selector, WidgetExt as _, WindowHandle};
+use druid::widget::{ControllerHost, EnvScope, LabelText};
+use druid::{Lens, Point, Selector, WidgetExt as _, WindowHandle};
 
+use crate::accessibility::A11yLabel;
+use crate::every::Every;
+use crate::focus_scope::TabStop;
+use crate::inspector::Inspectable;
 use crate::on_cmd::OnCmd;
+use crate::prism::{Prism, PrismWrap};
 use crate::stack_tooltip::{PlainOrRich, StackTooltip, ADVISE_TOOLTIP_SHOW, CANCEL_TOOLTIP_SHOW};
-use crate::tooltip::TooltipState;
-use crate::{OnChange, OnMonitor, TooltipController};
+#[cfg(all(
+    any(feature = "async", feature = "thread-pool"),
+    not(target_arch = "wasm32")
+))]
+use crate::OnChangeAsync;
+use crate::{
+    Badged, CommandLogger, OnChange, OnChangeDebounced, OnDebounce, OnMonitor, OnThrottle, Style,
+    TooltipController, Validated, Validator,
+};
 
 pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
     fn on_command<CT: 'static>(
@@ -16,6 +31,43 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         self.controller(OnCmd::new(selector, handler))
     }
 
+    /// Wrap this widget in a [`PrismWrap`], showing it only while `prism` finds its data, like
+    /// the payload of an enum variant.
+    ///
+    /// ```
+    /// # use druid::widget::TextBox;
+    /// # use druid::{Data, Widget};
+    /// # use druid_widget_nursery::prism::Closures;
+    /// # use druid_widget_nursery::WidgetExt as _;
+    /// #[derive(Clone, Data)]
+    /// enum Contact {
+    ///     Anonymous,
+    ///     Named(String),
+    /// }
+    ///
+    /// fn name() -> impl Widget<Contact> {
+    ///     TextBox::new().prism(Closures(
+    ///         |contact: &Contact| match contact {
+    ///             Contact::Named(name) => Some(name.clone()),
+    ///             Contact::Anonymous => None,
+    ///         },
+    ///         |contact: &mut Contact, name| *contact = Contact::Named(name),
+    ///     ))
+    /// }
+    /// ```
+    ///
+    /// With the `derive` feature, `#[derive(Prism)]` creates the prisms of an enum.
+    fn prism<S, P: Prism<S, T>>(self, prism: P) -> PrismWrap<Self, P, T> {
+        PrismWrap::new(self, prism)
+    }
+
+    /// Log the commands and notifications reaching this widget.
+    ///
+    /// See [`CommandLogger`] for filtering and logging commands app-wide.
+    fn log_commands(self) -> ControllerHost<Self, CommandLogger> {
+        self.controller(CommandLogger::new())
+    }
+
     /// Calls the function when data changes **in a child widget**
     ///
     /// `&T` is the old data and `&mut T` is the new data
@@ -26,15 +78,113 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
         self.controller(OnChange::new(f))
     }
 
+    /// Calls the function once data changed **in a child widget** and then stayed the same for
+    /// `delay`, like running a search after typing stops.
+    fn on_debounce(
+        self,
+        delay: Duration,
+        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, OnDebounce<T>> {
+        self.controller(OnDebounce::new(delay, f))
+    }
+
+    /// Calls the function once data changed **in a child widget** and then stayed the same for
+    /// `delay`, with the data from before the changes.
+    ///
+    /// `&T` is the old data and `&mut T` is the new data
+    fn on_change_debounced(
+        self,
+        delay: Duration,
+        f: impl Fn(&mut EventCtx, &T, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, OnChangeDebounced<T>> {
+        self.controller(OnChangeDebounced::new(delay, f))
+    }
+
+    /// Runs the future made by `task` when data changes **in a child widget**, and calls
+    /// `on_result` with its output, unless the data changed again meanwhile.
+    ///
+    /// `task` gets the old and the new data. See [`OnChangeAsync`] for more.
+    #[cfg(all(
+        any(feature = "async", feature = "thread-pool"),
+        not(target_arch = "wasm32")
+    ))]
+    fn on_change_async<U: Send + 'static, Fut>(
+        self,
+        task: impl Fn(&T, &T, &Env) -> Fut + 'static,
+        on_result: impl Fn(&mut EventCtx, U, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, OnChangeAsync<T, U>>
+    where
+        Fut: std::future::Future<Output = U> + Send + 'static,
+    {
+        self.controller(OnChangeAsync::new(task, on_result))
+    }
+
+    /// Calls the function when data changes **in a child widget**, at most once every
+    /// `interval`.
+    fn on_throttle(
+        self,
+        interval: Duration,
+        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, OnThrottle<T>> {
+        self.controller(OnThrottle::new(interval, f))
+    }
+
+    /// Calls the function every `interval` while this widget is in the widget tree.
+    ///
+    /// See [`Every`] for pausing and resuming it.
+    ///
+    /// [`Every`]: crate::every::Every
+    fn every(
+        self,
+        interval: Duration,
+        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
+    ) -> ControllerHost<Self, Every<T>> {
+        self.controller(Every::new(interval, f))
+    }
+
     /// Open a tooltip when the mouse is hovered over this widget.
     fn tooltip<LT: Into<LabelText<T>>>(
         self,
         text: LT,
     ) -> ControllerHost<Self, TooltipController<T>> {
-        self.controller(TooltipController {
-            text: text.into(),
-            state: TooltipState::Off,
-        })
+        self.controller(TooltipController::new(text))
+    }
+
+    /// Open a tooltip showing an arbitrary widget when the mouse is hovered over this widget.
+    ///
+    /// See [`TooltipController`] for configuring the delays.
+    fn tooltip_widget<W: Widget<T> + 'static>(
+        self,
+        make_widget: impl Fn() -> W + 'static,
+    ) -> ControllerHost<Self, TooltipController<T>> {
+        self.controller(TooltipController::widget(make_widget))
+    }
+
+    /// Override [`Env`] values for this widget and its children.
+    ///
+    /// ```
+    /// # use druid::widget::Button;
+    /// # use druid::{Color, Widget};
+    /// # use druid_widget_nursery::WidgetExt;
+    /// fn delete_button() -> impl Widget<()> {
+    ///     Button::new("Delete").styled(|style| style.text_color(Color::RED).border_radius(0.0))
+    /// }
+    /// ```
+    ///
+    /// See [`Style`] for the available overrides.
+    fn styled(self, style: impl FnOnce(Style) -> Style) -> EnvScope<T, Self> {
+        let style = style(Style::new());
+        EnvScope::new(move |env, _| style.apply(env), self)
+    }
+
+    /// Check the data of this widget with a [`Validator`], showing its error below the widget.
+    ///
+    /// More validators can be added with [`Validated::with_validator`]. See the [`form`] module
+    /// for collecting the state of the fields.
+    ///
+    /// [`form`]: crate::form
+    fn validate(self, validator: Validator<T>) -> Validated<T, Self> {
+        Validated::new(self).with_validator(validator)
     }
 
     /// A convenience method for ensuring that this widget is fully visible on the same monitor as
@@ -60,6 +210,45 @@ pub trait WidgetExt<T: Data>: Widget<T> + Sized + 'static {
     fn stack_tooltip(self, label: impl Into<PlainOrRich>) -> StackTooltip<T> {
         StackTooltip::new(self, label)
     }
+
+    /// Focus this widget before the others inside a [`FocusScope`], in the order of `index`.
+    ///
+    /// [`FocusScope`]: crate::FocusScope
+    fn tab_index(self, index: usize) -> TabStop<T, Self> {
+        TabStop::new(self, index)
+    }
+
+    /// Show the count of `lens` in a bubble on the top right corner of this widget.
+    fn badge_count(self, lens: impl Lens<T, usize> + 'static) -> Badged<T, Self> {
+        Badged::count(self, lens)
+    }
+
+    /// Show a dot on the top right corner of this widget while the `bool` of `lens` is true.
+    fn badge_dot(self, lens: impl Lens<T, bool> + 'static) -> Badged<T, Self> {
+        Badged::dot(self, lens)
+    }
+
+    /// Give this widget a name for assistive technologies, with an [`A11yLabel`].
+    fn with_a11y_label(self, label: impl Into<LabelText<T>>) -> A11yLabel<T, Self> {
+        A11yLabel::new(self, label)
+    }
+
+    /// Make this widget visible to the [`Inspector`] above it.
+    ///
+    /// [`Inspector`]: crate::inspector::Inspector
+    fn inspectable(self) -> Inspectable<T, Self> {
+        Inspectable::new(self)
+    }
+
+    /// Make this widget visible to the [`Inspector`] above it, showing its data with [`Debug`].
+    ///
+    /// [`Inspector`]: crate::inspector::Inspector
+    fn inspectable_debug(self) -> Inspectable<T, Self>
+    where
+        T: Debug,
+    {
+        Inspectable::debug(self)
+    }
 }
 
 impl<T: Data, W: Widget<T> + 'static> WidgetExt<T> for W {}
@@ -0,0 +1,493 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A multi-step wizard.
+//!
+//! The current step of a [`Wizard`] is stored in the app data, as an index or an enum
+//! implementing [`StepIndex`]. The wizard shows a header with all steps, the current step, and
+//! Back and Next buttons; the Next button turns into a Finish button on the last step.
+//!
+//! Steps can be gated by a validation function: the wizard only advances past a step when it
+//! returns true. Steps before the current one can be revisited by clicking them in the header,
+//! as can the steps after it that were already reached, if the steps in between are valid.
+//!
+//! The steps can also navigate by submitting [`BACK`], [`NEXT`] or [`GO_TO`] as notifications,
+//! and read [`CAN_GO_BACK`], [`CAN_ADVANCE`] and [`IS_LAST_STEP`] from the environment.
+//!
+//! ```
+//! # use druid::widget::{Label, TextBox};
+//! # use druid::{Data, Lens, Widget, WidgetExt};
+//! # use druid_widget_nursery::wizard::Wizard;
+//! #[derive(Clone, Data, Lens)]
+//! struct Setup {
+//!     step: usize,
+//!     name: String,
+//! }
+//!
+//! fn setup() -> impl Widget<Setup> {
+//!     Wizard::new(Setup::step)
+//!         .with_validated_step("Name", TextBox::new().lens(Setup::name), |setup: &Setup| {
+//!             !setup.name.is_empty()
+//!         })
+//!         .with_step("Done", Label::new("All set!"))
+//!         .on_finish(|_, _, _| println!("finished"))
+//! }
+//! ```
+
+use std::rc::Rc;
+
+use druid::kurbo::{Circle, Line};
+use druid::piet::{Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::widget::Button;
+use druid::{theme, Key, Lens, Point, Rect, Selector, WidgetExt as _, WidgetPod};
+
+/// True if the surrounding [`Wizard`] is not on its first step.
+pub const CAN_GO_BACK: Key<bool> = Key::new("druid-widget-nursery.wizard.can-go-back");
+/// True if the current step of the surrounding [`Wizard`] is valid.
+pub const CAN_ADVANCE: Key<bool> = Key::new("druid-widget-nursery.wizard.can-advance");
+/// True if the surrounding [`Wizard`] is on its last step.
+pub const IS_LAST_STEP: Key<bool> = Key::new("druid-widget-nursery.wizard.is-last-step");
+
+/// Go to the previous step.
+pub const BACK: Selector = Selector::new("druid-widget-nursery.wizard.back");
+/// Go to the next step if the current one is valid, or finish on the last step.
+pub const NEXT: Selector = Selector::new("druid-widget-nursery.wizard.next");
+/// Go to the given step, if it could be reached by clicking it in the header.
+pub const GO_TO: Selector<usize> = Selector::new("druid-widget-nursery.wizard.go-to");
+
+const HEADER_HEIGHT: f64 = 48.0;
+const INDICATOR_RADIUS: f64 = 11.0;
+const FOOTER_SPACING: f64 = 8.0;
+
+/// The current step of a [`Wizard`].
+///
+/// This is implemented for `usize`. Implement it for an enum to name the steps.
+pub trait StepIndex: Data {
+    fn index(&self) -> usize;
+    fn from_index(index: usize) -> Self;
+}
+
+impl StepIndex for usize {
+    fn index(&self) -> usize {
+        *self
+    }
+
+    fn from_index(index: usize) -> Self {
+        index
+    }
+}
+
+struct Step<T> {
+    title: String,
+    widget: WidgetPod<T, Box<dyn Widget<T>>>,
+    validate: Option<Box<dyn Fn(&T) -> bool>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NavState {
+    can_go_back: bool,
+    can_advance: bool,
+    is_last_step: bool,
+}
+
+/// A container showing one step at a time, with a step indicator and navigation buttons.
+///
+/// See the [module documentation](self) for details.
+pub struct Wizard<T> {
+    steps: Vec<Step<T>>,
+    get_step: Box<dyn Fn(&T) -> usize>,
+    set_step: Box<dyn Fn(&mut T, usize)>,
+    on_finish: Option<Box<dyn Fn(&mut EventCtx, &mut T, &Env)>>,
+    back_button: WidgetPod<T, Box<dyn Widget<T>>>,
+    next_button: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// The furthest step reached so far.
+    furthest: usize,
+    /// The last environment and navigation state we got, and the environment of the children.
+    env: Option<(Env, NavState, Env)>,
+}
+
+impl<T: Data> Wizard<T> {
+    /// Create a wizard whose current step is stored at `step`.
+    pub fn new<S: StepIndex>(step: impl Lens<T, S> + 'static) -> Self {
+        let step = Rc::new(step);
+        let get_lens = step.clone();
+        let back_button = Button::new("Back")
+            .on_click(|ctx, _, _| ctx.submit_notification(BACK))
+            .disabled_if(|_, env| !env.get(CAN_GO_BACK));
+        let next_button = Button::dynamic(|_, env| {
+            if env.get(IS_LAST_STEP) {
+                "Finish".to_string()
+            } else {
+                "Next".to_string()
+            }
+        })
+        .on_click(|ctx, _, _| ctx.submit_notification(NEXT))
+        .disabled_if(|_, env| !env.get(CAN_ADVANCE));
+        Wizard {
+            steps: Vec::new(),
+            get_step: Box::new(move |data| get_lens.with(data, |step| step.index())),
+            set_step: Box::new(move |data, index| {
+                step.with_mut(data, |step| *step = S::from_index(index))
+            }),
+            on_finish: None,
+            back_button: WidgetPod::new(back_button.boxed()),
+            next_button: WidgetPod::new(next_button.boxed()),
+            furthest: 0,
+            env: None,
+        }
+    }
+
+    /// Builder-style method to add a step.
+    pub fn with_step(mut self, title: impl Into<String>, widget: impl Widget<T> + 'static) -> Self {
+        self.add_step(title, widget, None);
+        self
+    }
+
+    /// Builder-style method to add a step which can only be left forward when `validate`
+    /// returns true.
+    pub fn with_validated_step(
+        mut self,
+        title: impl Into<String>,
+        widget: impl Widget<T> + 'static,
+        validate: impl Fn(&T) -> bool + 'static,
+    ) -> Self {
+        self.add_step(title, widget, Some(Box::new(validate)));
+        self
+    }
+
+    fn add_step(
+        &mut self,
+        title: impl Into<String>,
+        widget: impl Widget<T> + 'static,
+        validate: Option<Box<dyn Fn(&T) -> bool>>,
+    ) {
+        self.steps.push(Step {
+            title: title.into(),
+            widget: WidgetPod::new(widget.boxed()),
+            validate,
+        });
+    }
+
+    /// Builder-style method for specifying what happens when Finish is clicked on the last
+    /// step.
+    pub fn on_finish(mut self, f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        self.on_finish = Some(Box::new(f));
+        self
+    }
+
+    fn current(&self, data: &T) -> usize {
+        (self.get_step)(data).min(self.steps.len().saturating_sub(1))
+    }
+
+    fn is_valid(&self, index: usize, data: &T) -> bool {
+        self.steps[index]
+            .validate
+            .as_ref()
+            .map_or(true, |validate| validate(data))
+    }
+
+    /// Returns true if the user can go to step `index` from the current one.
+    fn can_go_to(&self, index: usize, data: &T) -> bool {
+        let current = self.current(data);
+        if index >= self.steps.len() {
+            false
+        } else if index <= current {
+            true
+        } else {
+            index <= self.furthest && (current..index).all(|step| self.is_valid(step, data))
+        }
+    }
+
+    fn nav_state(&self, data: &T) -> NavState {
+        let current = self.current(data);
+        NavState {
+            can_go_back: current > 0,
+            can_advance: !self.steps.is_empty() && self.is_valid(current, data),
+            is_last_step: current + 1 >= self.steps.len(),
+        }
+    }
+
+    fn child_env(&mut self, data: &T, env: &Env) -> Env {
+        let nav = self.nav_state(data);
+        match &self.env {
+            Some((last, last_nav, child_env)) if last.same(env) && *last_nav == nav => {
+                child_env.clone()
+            }
+            _ => {
+                let child_env = env
+                    .clone()
+                    .adding(CAN_GO_BACK, nav.can_go_back)
+                    .adding(CAN_ADVANCE, nav.can_advance)
+                    .adding(IS_LAST_STEP, nav.is_last_step);
+                self.env = Some((env.clone(), nav, child_env.clone()));
+                child_env
+            }
+        }
+    }
+
+    fn go_to(&mut self, ctx: &mut EventCtx, data: &mut T, index: usize) {
+        if index != self.current(data) && self.can_go_to(index, data) {
+            (self.set_step)(data, index);
+            self.furthest = self.furthest.max(index);
+            ctx.request_layout();
+        }
+    }
+
+    fn next(&mut self, ctx: &mut EventCtx, data: &mut T, env: &Env) {
+        let nav = self.nav_state(data);
+        if !nav.can_advance {
+            return;
+        }
+        if nav.is_last_step {
+            if let Some(on_finish) = &self.on_finish {
+                on_finish(ctx, data, env);
+            }
+        } else {
+            let next = self.current(data) + 1;
+            self.go_to(ctx, data, next);
+        }
+    }
+
+    /// The step whose indicator is at `pos`.
+    fn step_at(&self, pos: Point, width: f64) -> Option<usize> {
+        if self.steps.is_empty() || pos.y < 0.0 || pos.y >= HEADER_HEIGHT {
+            return None;
+        }
+        let column = (pos.x / (width / self.steps.len() as f64)).floor();
+        if column >= 0.0 && (column as usize) < self.steps.len() {
+            Some(column as usize)
+        } else {
+            None
+        }
+    }
+
+    fn paint_header(&self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let width = ctx.size().width;
+        let count = self.steps.len();
+        let column_width = width / count as f64;
+        let current = self.current(data);
+        let center_y = 4.0 + INDICATOR_RADIUS;
+        let font = env.get(theme::UI_FONT);
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let center = Point::new(column_width * (index as f64 + 0.5), center_y);
+            if index + 1 < count {
+                let next_x = center.x + column_width;
+                let line = Line::new(
+                    (center.x + INDICATOR_RADIUS + 4.0, center_y),
+                    (next_x - INDICATOR_RADIUS - 4.0, center_y),
+                );
+                let color = if index < current {
+                    env.get(theme::PRIMARY_LIGHT)
+                } else {
+                    env.get(theme::BORDER_DARK)
+                };
+                ctx.stroke(line, &color, 1.0);
+            }
+
+            let circle = Circle::new(center, INDICATOR_RADIUS);
+            let reachable = self.can_go_to(index, data);
+            let (fill, text_color) = if index == current {
+                (
+                    env.get(theme::PRIMARY_LIGHT),
+                    env.get(theme::FOREGROUND_DARK),
+                )
+            } else if index <= self.furthest {
+                (env.get(theme::PRIMARY_DARK), env.get(theme::TEXT_COLOR))
+            } else {
+                (
+                    env.get(theme::BACKGROUND_LIGHT),
+                    env.get(theme::DISABLED_TEXT_COLOR),
+                )
+            };
+            ctx.fill(circle, &fill);
+            ctx.stroke(circle, &env.get(theme::BORDER_DARK), 1.0);
+
+            let number = ctx
+                .text()
+                .new_text_layout((index + 1).to_string())
+                .font(font.family.clone(), env.get(theme::TEXT_SIZE_NORMAL))
+                .text_color(text_color)
+                .build();
+            if let Ok(number) = number {
+                ctx.draw_text(&number, center - number.size().to_vec2() / 2.0);
+            }
+
+            let title_color = if index == current || reachable {
+                env.get(theme::TEXT_COLOR)
+            } else {
+                env.get(theme::DISABLED_TEXT_COLOR)
+            };
+            let title = ctx
+                .text()
+                .new_text_layout(step.title.clone())
+                .font(font.family.clone(), env.get(theme::TEXT_SIZE_SMALL))
+                .text_color(title_color)
+                .max_width(column_width - 4.0)
+                .build();
+            if let Ok(title) = title {
+                let origin = Point::new(
+                    center.x - title.size().width / 2.0,
+                    center_y + INDICATOR_RADIUS + 2.0,
+                );
+                ctx.with_save(|ctx| {
+                    let column = Rect::new(
+                        column_width * index as f64,
+                        0.0,
+                        column_width * (index + 1) as f64,
+                        HEADER_HEIGHT,
+                    );
+                    ctx.clip(column);
+                    ctx.draw_text(&title, origin);
+                });
+            }
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Wizard<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Notification(n) if n.is(BACK) => {
+                let current = self.current(data);
+                if current > 0 {
+                    self.go_to(ctx, data, current - 1);
+                }
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(n) if n.is(NEXT) => {
+                self.next(ctx, data, env);
+                ctx.set_handled();
+                return;
+            }
+            Event::Notification(n) if n.is(GO_TO) => {
+                let index = *n.get(GO_TO).unwrap();
+                self.go_to(ctx, data, index);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                if let Some(index) = self.step_at(mouse.pos, ctx.size().width) {
+                    self.go_to(ctx, data, index);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseMove(mouse) => {
+                let clickable = self
+                    .step_at(mouse.pos, ctx.size().width)
+                    .map_or(false, |index| {
+                        index != self.current(data) && self.can_go_to(index, data)
+                    });
+                if clickable {
+                    ctx.set_cursor(&druid::Cursor::Pointer);
+                } else {
+                    ctx.clear_cursor();
+                }
+            }
+            _ => {}
+        }
+
+        let child_env = self.child_env(data, env);
+        if self.steps.is_empty() {
+            return;
+        }
+        let current = self.current(data);
+        if event.should_propagate_to_hidden() {
+            for step in &mut self.steps {
+                step.widget.event(ctx, event, data, &child_env);
+            }
+        } else {
+            self.steps[current]
+                .widget
+                .event(ctx, event, data, &child_env);
+        }
+        self.back_button.event(ctx, event, data, &child_env);
+        self.next_button.event(ctx, event, data, &child_env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.furthest = self.current(data);
+        }
+        let child_env = self.child_env(data, env);
+        for step in &mut self.steps {
+            step.widget.lifecycle(ctx, event, data, &child_env);
+        }
+        self.back_button.lifecycle(ctx, event, data, &child_env);
+        self.next_button.lifecycle(ctx, event, data, &child_env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let current = self.current(data);
+        if self.current(old_data) != current {
+            // changed from outside
+            self.furthest = self.furthest.max(current);
+            ctx.request_layout();
+        }
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+        let child_env = self.child_env(data, env);
+        for step in &mut self.steps {
+            step.widget.update(ctx, data, &child_env);
+        }
+        self.back_button.update(ctx, data, &child_env);
+        self.next_button.update(ctx, data, &child_env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let child_env = self.child_env(data, env);
+        let button_bc = bc.loosen();
+        let back_size = self.back_button.layout(ctx, &button_bc, data, &child_env);
+        let next_size = self.next_button.layout(ctx, &button_bc, data, &child_env);
+        let footer_height = back_size.height.max(next_size.height) + FOOTER_SPACING;
+
+        let body_bc = bc.shrink((0.0, HEADER_HEIGHT + footer_height)).loosen();
+        let mut body_size = Size::ZERO;
+        if !self.steps.is_empty() {
+            let current = self.current(data);
+            let widget = &mut self.steps[current].widget;
+            body_size = widget.layout(ctx, &body_bc, data, &child_env);
+            widget.set_origin(ctx, Point::new(0.0, HEADER_HEIGHT));
+        }
+
+        let width = body_size
+            .width
+            .max(back_size.width + next_size.width + FOOTER_SPACING);
+        let size = bc.constrain(Size::new(
+            width,
+            HEADER_HEIGHT + body_size.height + footer_height,
+        ));
+        let footer_y = size.height - footer_height + FOOTER_SPACING;
+        self.back_button.set_origin(ctx, Point::new(0.0, footer_y));
+        self.next_button
+            .set_origin(ctx, Point::new(size.width - next_size.width, footer_y));
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        if self.steps.is_empty() {
+            return;
+        }
+        self.paint_header(ctx, data, env);
+        let child_env = self.child_env(data, env);
+        let current = self.current(data);
+        self.steps[current].widget.paint(ctx, data, &child_env);
+        self.back_button.paint(ctx, data, &child_env);
+        self.next_button.paint(ctx, data, &child_env);
+    }
+}
@@ -12,12 +12,44 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! A container laying out its children in runs, wrapping to a new run when one is full.
+
 use druid::widget::prelude::*;
 use druid::widget::Axis;
 use druid::{KeyOrValue, Widget, WidgetPod};
 
+struct Child<T> {
+    widget: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// Overrides the cross alignment of the wrap for this child.
+    alignment: Option<WrapCrossAlignment>,
+}
+
+/// A container laying out its children one after the other, like words in a paragraph, starting a
+/// new run when the next child doesn't fit.
+///
+/// This is how chips, tag clouds or groups of buttons are usually laid out. The children are laid
+/// out in rows from left to right by default, or in columns with [`direction`].
+///
+/// ```
+/// # use druid::widget::Button;
+/// # use druid::Widget;
+/// # use druid_widget_nursery::wrap::{Wrap, WrapAlignment, WrapCrossAlignment};
+/// fn tags() -> impl Widget<()> {
+///     let mut wrap = Wrap::new()
+///         .spacing(4.0)
+///         .run_spacing(4.0)
+///         .alignment(WrapAlignment::Center)
+///         .cross_alignment(WrapCrossAlignment::Center);
+///     for tag in &["rust", "gui", "druid", "widgets"] {
+///         wrap.add_child(Box::new(Button::new(*tag)));
+///     }
+///     wrap
+/// }
+/// ```
+///
+/// [`direction`]: #method.direction
 pub struct Wrap<T> {
-    children: Vec<WidgetPod<T, Box<dyn Widget<T>>>>,
+    children: Vec<Child<T>>,
     direction: Axis,
     run_spacing: KeyOrValue<f64>,
     spacing: KeyOrValue<f64>,
@@ -26,6 +58,8 @@ pub struct Wrap<T> {
     cross_alignment: WrapCrossAlignment,
 }
 
+/// How the runs, or the children in a run, are placed along an axis when there is free space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WrapAlignment {
     Start,
     End,
@@ -35,6 +69,8 @@ pub enum WrapAlignment {
     SpaceAround,
 }
 
+/// How the children are placed across a run which is thicker than them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WrapCrossAlignment {
     Start,
     End,
@@ -62,7 +98,10 @@ impl<T> Wrap<T> {
 
     // allow Box<dyn Widget> in add_child
     pub fn add_child(&mut self, child: Box<dyn Widget<T>>) {
-        self.children.push(WidgetPod::new(child))
+        self.children.push(Child {
+            widget: WidgetPod::new(child),
+            alignment: None,
+        })
     }
 
     pub fn with_child(mut self, child: impl Widget<T> + 'static) -> Self {
@@ -70,6 +109,25 @@ impl<T> Wrap<T> {
         self
     }
 
+    /// Add a child placed across its run with `alignment`, instead of the cross alignment of the
+    /// wrap.
+    pub fn add_aligned_child(&mut self, child: Box<dyn Widget<T>>, alignment: WrapCrossAlignment) {
+        self.children.push(Child {
+            widget: WidgetPod::new(child),
+            alignment: Some(alignment),
+        })
+    }
+
+    /// Builder style method to add a child placed across its run with `alignment`.
+    pub fn with_aligned_child(
+        mut self,
+        child: impl Widget<T> + 'static,
+        alignment: WrapCrossAlignment,
+    ) -> Self {
+        self.add_aligned_child(Box::new(child), alignment);
+        self
+    }
+
     /// Set the wrap's direction.
     pub fn set_direction(&mut self, direction: Axis) {
         self.direction = direction;
@@ -140,19 +198,19 @@ impl<T> Wrap<T> {
 impl<T: Data> Widget<T> for Wrap<T> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
         for x in &mut self.children {
-            x.event(ctx, event, data, env);
+            x.widget.event(ctx, event, data, env);
         }
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
         for x in &mut self.children {
-            x.lifecycle(ctx, event, data, env);
+            x.widget.lifecycle(ctx, event, data, env);
         }
     }
 
     fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
         for x in &mut self.children {
-            x.update(ctx, data, env);
+            x.widget.update(ctx, data, env);
         }
     }
 
@@ -174,7 +232,7 @@ impl<T: Data> Widget<T> for Wrap<T> {
         let mut child_count = 0;
         let mut run_metrics = Vec::new();
         for child in &mut self.children {
-            let child_size = child.layout(ctx, &child_bc, data, env);
+            let child_size = child.widget.layout(ctx, &child_bc, data, env);
             let child_main_axis_extent = dir.major(child_size);
             let child_cross_axis_extent = dir.minor(child_size);
             if child_count > 0
@@ -240,37 +298,39 @@ impl<T: Data> Widget<T> for Wrap<T> {
             let main_axis_free_space =
                 f64::max(0.0, container_main_axis_extent - run_main_axis_extent);
 
+            // the free space is shared between the children of the run
             let (child_leading_space, mut child_between_space) = match self.alignment {
                 WrapAlignment::Start => (0., 0.),
                 WrapAlignment::End => (main_axis_free_space, 0.),
                 WrapAlignment::Center => (main_axis_free_space / 2., 0.),
-                WrapAlignment::SpaceBetween if run_count > 1 => {
-                    (0., main_axis_free_space / (run_count as f64 - 1.))
+                WrapAlignment::SpaceBetween if child_count > 1 => {
+                    (0., main_axis_free_space / (child_count as f64 - 1.))
                 }
                 WrapAlignment::SpaceBetween => (0., 0.),
                 WrapAlignment::SpaceAround => (
-                    main_axis_free_space / run_count as f64 / 2.,
-                    main_axis_free_space / run_count as f64,
+                    main_axis_free_space / child_count as f64 / 2.,
+                    main_axis_free_space / child_count as f64,
                 ),
                 WrapAlignment::SpaceEvenly => (
-                    main_axis_free_space / (run_count as f64 + 1.),
-                    main_axis_free_space / (run_count as f64 + 1.),
+                    main_axis_free_space / (child_count as f64 + 1.),
+                    main_axis_free_space / (child_count as f64 + 1.),
                 ),
             };
             child_between_space += spacing;
             let mut child_main_position = child_leading_space;
 
             for child in (&mut childs).take(child_count) {
-                let child_size = child.layout_rect().size();
+                let child_size = child.widget.layout_rect().size();
                 let free_space = run_cross_axis_extent - dir.minor(child_size);
 
-                let child_cross_axis_offset = match self.cross_alignment {
+                let cross_alignment = child.alignment.unwrap_or(self.cross_alignment);
+                let child_cross_axis_offset = match cross_alignment {
                     WrapCrossAlignment::Start => cross_axis_offset,
                     WrapCrossAlignment::End => cross_axis_offset + free_space,
                     WrapCrossAlignment::Center => cross_axis_offset + free_space / 2.,
                 };
 
-                child.set_origin(
+                child.widget.set_origin(
                     ctx,
                     dir.pack(child_main_position, child_cross_axis_offset)
                         .into(),
@@ -285,7 +345,7 @@ impl<T: Data> Widget<T> for Wrap<T> {
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
         for x in &mut self.children {
-            x.paint(ctx, data, env);
+            x.widget.paint(ctx, data, env);
         }
     }
 }
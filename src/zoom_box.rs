@@ -0,0 +1,290 @@
+// Copyright 2022 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A wrapper zooming its child with Ctrl+wheel and pinch gestures.
+
+use druid::widget::prelude::*;
+use druid::{Affine, Point, Selector, Vec2, WidgetPod};
+
+use crate::canvas::transform_mouse;
+
+/// Zooms a [`ZoomBox`] so that its whole child is shown, centered.
+pub const ZOOM_TO_FIT: Selector = Selector::new("druid-widget-nursery.zoom-box.zoom-to-fit");
+/// Sets the zoom of a [`ZoomBox`], around its center.
+pub const SET_ZOOM: Selector<f64> = Selector::new("druid-widget-nursery.zoom-box.set-zoom");
+
+/// How much the zoom changes for a wheel delta of one line.
+const ZOOM_STEP: f64 = 1.1;
+/// The wheel delta of one line.
+const LINE_DELTA: f64 = 120.0;
+
+/// A wrapper showing its child scaled, and moved to show the part of it which is zoomed into.
+///
+/// Ctrl+wheel zooms around the mouse, as does pinching a touchpad. When the child is bigger than
+/// the wrapper, the wheel the child doesn't handle and dragging with the middle button pan it. The
+/// child is laid out in the size of the wrapper, and gets the mouse events at its own coordinates.
+///
+/// ```
+/// # use druid::widget::{Button, Flex, Label};
+/// # use druid::{Widget, WidgetExt};
+/// # use druid_widget_nursery::zoom_box::{ZoomBox, ZOOM_TO_FIT};
+/// fn diagram() -> impl Widget<()> {
+///     Flex::column()
+///         .with_child(
+///             Button::new("Fit").on_click(|ctx, _, _| ctx.submit_command(ZOOM_TO_FIT)),
+///         )
+///         .with_flex_child(
+///             ZoomBox::new(Label::new("A diagram")).with_max_zoom(8.0),
+///             1.0,
+///         )
+/// }
+/// ```
+pub struct ZoomBox<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    zoom: f64,
+    min_zoom: f64,
+    max_zoom: f64,
+    /// Where the origin of the child is shown.
+    offset: Vec2,
+    child_size: Size,
+    /// Where the mouse was last, which pinching zooms around.
+    mouse_pos: Option<Point>,
+    pan_origin: Option<Point>,
+}
+
+impl<T: Data> ZoomBox<T> {
+    pub fn new(child: impl Widget<T> + 'static) -> Self {
+        ZoomBox {
+            child: WidgetPod::new(Box::new(child)),
+            zoom: 1.0,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            offset: Vec2::ZERO,
+            child_size: Size::ZERO,
+            mouse_pos: None,
+            pan_origin: None,
+        }
+    }
+
+    /// Builder-style method for setting the smallest zoom.
+    ///
+    /// For the non-builder varient, see [`set_min_zoom`].
+    ///
+    /// [`set_min_zoom`]: #method.set_min_zoom
+    pub fn with_min_zoom(mut self, zoom: f64) -> Self {
+        self.set_min_zoom(zoom);
+        self
+    }
+
+    /// Set the smallest zoom, 0.1 by default.
+    pub fn set_min_zoom(&mut self, zoom: f64) {
+        self.min_zoom = zoom.max(f64::EPSILON);
+        self.max_zoom = self.max_zoom.max(self.min_zoom);
+        self.zoom = self.clamp_zoom(self.zoom);
+    }
+
+    /// Builder-style method for setting the largest zoom.
+    ///
+    /// For the non-builder varient, see [`set_max_zoom`].
+    ///
+    /// [`set_max_zoom`]: #method.set_max_zoom
+    pub fn with_max_zoom(mut self, zoom: f64) -> Self {
+        self.set_max_zoom(zoom);
+        self
+    }
+
+    /// Set the largest zoom, 10 by default.
+    pub fn set_max_zoom(&mut self, zoom: f64) {
+        self.max_zoom = zoom.max(self.min_zoom);
+        self.zoom = self.clamp_zoom(self.zoom);
+    }
+
+    /// The scale the child is shown at.
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    fn clamp_zoom(&self, zoom: f64) -> f64 {
+        zoom.max(self.min_zoom).min(self.max_zoom)
+    }
+
+    /// The transform from the child to the wrapper.
+    fn transform(&self) -> Affine {
+        Affine::translate(self.offset) * Affine::scale(self.zoom)
+    }
+
+    /// Keep the child filling the wrapper where it can, and centered where it's smaller.
+    fn clamp_offset(&mut self, view: Size) {
+        let content = self.child_size * self.zoom;
+        let clamp = |offset: f64, view: f64, content: f64| {
+            if content <= view {
+                (view - content) / 2.0
+            } else {
+                offset.max(view - content).min(0.0)
+            }
+        };
+        self.offset = Vec2::new(
+            clamp(self.offset.x, view.width, content.width),
+            clamp(self.offset.y, view.height, content.height),
+        );
+    }
+
+    /// Set the zoom, keeping the point `around` of the wrapper in place.
+    fn zoom_to(&mut self, ctx: &mut EventCtx, zoom: f64, around: Point) {
+        let zoom = self.clamp_zoom(zoom);
+        let anchor = (around.to_vec2() - self.offset) / self.zoom;
+        self.offset = around.to_vec2() - anchor * zoom;
+        self.zoom = zoom;
+        self.clamp_offset(ctx.size());
+        ctx.request_paint();
+    }
+
+    fn zoom_to_fit(&mut self, ctx: &mut EventCtx) {
+        let view = ctx.size();
+        if self.child_size.is_empty() {
+            return;
+        }
+        let zoom = (view.width / self.child_size.width).min(view.height / self.child_size.height);
+        self.zoom = self.clamp_zoom(zoom);
+        self.clamp_offset(view);
+        ctx.request_paint();
+    }
+}
+
+impl<T: Data> Widget<T> for ZoomBox<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(ZOOM_TO_FIT) => {
+                self.zoom_to_fit(ctx);
+                ctx.set_handled();
+                return;
+            }
+            Event::Command(cmd) if cmd.is(SET_ZOOM) => {
+                let center = ctx.size().to_rect().center();
+                self.zoom_to(ctx, *cmd.get_unchecked(SET_ZOOM), center);
+                ctx.set_handled();
+                return;
+            }
+            Event::Wheel(wheel) if wheel.mods.ctrl() => {
+                let factor = ZOOM_STEP.powf(-wheel.wheel_delta.y / LINE_DELTA);
+                self.zoom_to(ctx, self.zoom * factor, wheel.pos);
+                ctx.set_handled();
+                return;
+            }
+            Event::Zoom(delta) => {
+                // pinching gives the change of the magnification
+                let around = self
+                    .mouse_pos
+                    .unwrap_or_else(|| ctx.size().to_rect().center());
+                self.zoom_to(ctx, self.zoom * (1.0 + delta).max(0.1), around);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse) => {
+                self.mouse_pos = Some(mouse.pos);
+                if let Some(origin) = self.pan_origin {
+                    self.pan_origin = Some(mouse.pos);
+                    self.offset += mouse.pos - origin;
+                    self.clamp_offset(ctx.size());
+                    ctx.request_paint();
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseDown(mouse) if mouse.button.is_middle() => {
+                self.pan_origin = Some(mouse.pos);
+                ctx.set_active(true);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseUp(_) if self.pan_origin.is_some() => {
+                self.pan_origin = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+                return;
+            }
+            _ => {}
+        }
+
+        // the child is hit tested in its own coordinates
+        let child_event = transform_mouse(event, self.transform().inverse());
+        self.child
+            .event(ctx, child_event.as_ref().unwrap_or(event), data, env);
+
+        match event {
+            Event::Wheel(wheel) if !ctx.is_handled() => {
+                let old = self.offset;
+                self.offset -= wheel.wheel_delta;
+                self.clamp_offset(ctx.size());
+                if self.offset != old {
+                    ctx.set_handled();
+                }
+            }
+            _ => {}
+        }
+
+        // the child invalidates its rects without the transform
+        if self.zoom != 1.0 || self.offset != Vec2::ZERO {
+            ctx.request_paint();
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::HotChanged(false) = event {
+            self.mouse_pos = None;
+        }
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+        if !old_data.same(data) && (self.zoom != 1.0 || self.offset != Vec2::ZERO) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        self.child_size = self.child.layout(ctx, &bc.loosen(), data, env);
+        self.child.set_origin(ctx, Point::ORIGIN);
+        let size = Size::new(
+            if bc.is_width_bounded() {
+                bc.max().width
+            } else {
+                self.child_size.width
+            },
+            if bc.is_height_bounded() {
+                bc.max().height
+            } else {
+                self.child_size.height
+            },
+        );
+        self.clamp_offset(size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let transform = self.transform();
+        let clip = ctx.size().to_rect();
+        // the child only paints in the invalid region, which it sees in its own coordinates
+        let region = transform
+            .inverse()
+            .transform_rect_bbox(ctx.region().bounding_box());
+        let child = &mut self.child;
+        ctx.with_save(|ctx| {
+            ctx.clip(clip);
+            ctx.transform(transform);
+            ctx.with_child_ctx(region, |ctx| child.paint(ctx, data, env));
+        });
+    }
+}
@@ -35,3 +35,27 @@ where
     V2(T),
     V3(PhantomData<T>, Box<(U, U)>),
 }
+
+#[derive(Clone, Prism)]
+enum Named {
+    Empty {},
+    One { value: u32 },
+    Two { data: String, len: usize },
+}
+
+#[test]
+fn combinators() {
+    use druid_widget_nursery::prism::PrismExt;
+
+    let mut data = MyOption::Some(Named::Two {
+        data: "a".into(),
+        len: 1,
+    });
+    let two = MyOptionSome.then(NamedTwo);
+    assert_eq!(two.get(&data), Some(("a".to_string(), 1)));
+
+    let len = two.then_lens(druid::lens!((String, usize), 1));
+    len.put(&mut data, 3);
+    assert_eq!(len.get(&data), Some(3));
+    assert_eq!(NamedOne.get(&Named::One { value: 2 }), Some(2));
+}